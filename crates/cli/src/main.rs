@@ -0,0 +1,301 @@
+//! Headless Dictara CLI.
+//!
+//! Exercises the dictation pipeline from `dictara-core` without launching the GUI:
+//!
+//! ```text
+//! dictara-cli record [--seconds N] [--vad <path>] [--loopback <device>] [--transcribe [transcribe options]]
+//! dictara-cli transcribe <file> [--provider openai|azure|local] [--model <path|name>] [--model-type whisper|parakeet]
+//! dictara-cli paste-last
+//! ```
+//!
+//! API credentials are read from the environment (`OPENAI_API_KEY`, or
+//! `AZURE_OPENAI_API_KEY` + `AZURE_OPENAI_ENDPOINT`). The VAD model path can
+//! also be set with `DICTARA_VAD_MODEL`. `--model` takes a model path, or the name of
+//! a catalog model (e.g. `whisper-small`) downloaded into `DICTARA_MODELS_DIR`.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use dictara_core::host::AppPaths;
+use dictara_core::models::{find_model, ModelStore};
+use dictara_core::paste::paste_text;
+use dictara_core::recorder::{AudioRecorder, RecordingOptions};
+use dictara_core::transcription::{
+    ApiTranscriber, AzureClient, LocalClient, ModelType, OpenAIClient, TranscriptionService,
+};
+use secrecy::SecretString;
+
+/// Default recording length when `--seconds` is not given
+const DEFAULT_RECORD_SECONDS: u64 = 5;
+
+const USAGE: &str = "\
+Usage:
+  dictara-cli record [--seconds N] [--vad <path>] [--loopback <device>] [--transcribe [transcribe options]]
+  dictara-cli transcribe <file> [--provider openai|azure|local] [--model <path|name>] [--model-type whisper|parakeet]
+  dictara-cli paste-last
+
+Environment:
+  OPENAI_API_KEY                          credentials for --provider openai (default)
+  AZURE_OPENAI_API_KEY, AZURE_OPENAI_ENDPOINT  credentials for --provider azure
+  DICTARA_VAD_MODEL                       Silero V6 model used by `record` when --vad is not given
+  DICTARA_MODELS_DIR                      models directory of the app, for --model <name>";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("record") => record(&args[1..]),
+        Some("transcribe") => transcribe(&args[1..]),
+        Some("paste-last") => paste_last(),
+        Some("-h") | Some("--help") | None => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(other) => Err(format!("Unknown command '{}'\n\n{}", other, USAGE)),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Options shared by `transcribe` and `record --transcribe`
+struct TranscribeOptions {
+    provider: String,
+    /// Model path or catalog name
+    model: Option<String>,
+    /// Explicit `--model-type`, otherwise from the catalog (or Whisper for a path)
+    model_type: Option<ModelType>,
+}
+
+impl Default for TranscribeOptions {
+    fn default() -> Self {
+        Self {
+            provider: "openai".to_string(),
+            model: None,
+            model_type: None,
+        }
+    }
+}
+
+impl TranscribeOptions {
+    /// Try to consume a transcribe option at `args[*i]`, returns false if it isn't one
+    fn parse_flag(&mut self, args: &[String], i: &mut usize) -> Result<bool, String> {
+        match args[*i].as_str() {
+            "--provider" => self.provider = flag_value(args, i)?.to_string(),
+            "--model" => self.model = Some(flag_value(args, i)?.to_string()),
+            "--model-type" => {
+                self.model_type = Some(match flag_value(args, i)? {
+                    "whisper" => ModelType::Whisper,
+                    "parakeet" => ModelType::Parakeet,
+                    other => return Err(format!("Unknown model type '{}'", other)),
+                })
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Path and type of the local model. An existing path wins over a catalog name.
+    fn local_model(&self) -> Result<(PathBuf, ModelType), String> {
+        let model = self
+            .model
+            .as_deref()
+            .ok_or("--model is required for the local provider")?;
+
+        let path = PathBuf::from(model);
+        if path.exists() {
+            return Ok((path, self.model_type.unwrap_or(ModelType::Whisper)));
+        }
+
+        let entry = find_model(model)
+            .ok_or_else(|| format!("No model file or catalog model '{}'", model))?;
+        let paths = CliPaths::from_env(None);
+        if paths.models_dir.is_none() {
+            return Err("DICTARA_MODELS_DIR is not set, needed to find a model by name".into());
+        }
+        let store = ModelStore::for_app(&paths);
+        if !store.is_downloaded(&entry) {
+            return Err(format!(
+                "Model '{}' is not downloaded in {}",
+                model,
+                store.models_dir().display()
+            ));
+        }
+        Ok((
+            store.client_path(&entry),
+            self.model_type.unwrap_or(entry.model_type),
+        ))
+    }
+}
+
+/// Directories of the CLI: models from `DICTARA_MODELS_DIR`, recordings in the temp dir
+struct CliPaths {
+    models_dir: Option<PathBuf>,
+    vad_model: Option<PathBuf>,
+}
+
+impl CliPaths {
+    fn from_env(vad_model: Option<PathBuf>) -> Self {
+        Self {
+            models_dir: std::env::var_os("DICTARA_MODELS_DIR").map(PathBuf::from),
+            vad_model,
+        }
+    }
+}
+
+impl AppPaths for CliPaths {
+    fn models_dir(&self) -> PathBuf {
+        self.models_dir.clone().unwrap_or_default()
+    }
+
+    fn recordings_dir(&self) -> PathBuf {
+        std::env::temp_dir().join("dictara-cli").join("recordings")
+    }
+
+    fn vad_model_path(&self) -> Option<PathBuf> {
+        self.vad_model.clone()
+    }
+}
+
+/// Return the value following the flag at `args[*i]` and advance past it
+fn flag_value<'a>(args: &'a [String], i: &mut usize) -> Result<&'a str, String> {
+    let flag = &args[*i];
+    *i += 1;
+    args.get(*i)
+        .map(String::as_str)
+        .ok_or_else(|| format!("Missing value for {}", flag))
+}
+
+fn record(args: &[String]) -> Result<(), String> {
+    let mut seconds = DEFAULT_RECORD_SECONDS;
+    let mut vad_path = std::env::var_os("DICTARA_VAD_MODEL").map(PathBuf::from);
+    let mut loopback_device = None;
+    let mut transcribe_after = false;
+    let mut options = TranscribeOptions::default();
+
+    let mut i = 0;
+    while i < args.len() {
+        if !options.parse_flag(args, &mut i)? {
+            match args[i].as_str() {
+                "--seconds" => {
+                    seconds = flag_value(args, &mut i)?
+                        .parse()
+                        .map_err(|e| format!("Invalid --seconds: {}", e))?
+                }
+                "--vad" => vad_path = Some(PathBuf::from(flag_value(args, &mut i)?)),
+                "--loopback" => loopback_device = Some(flag_value(args, &mut i)?.to_string()),
+                "--transcribe" => transcribe_after = true,
+                other => return Err(format!("Unknown option '{}'", other)),
+            }
+        }
+        i += 1;
+    }
+
+    if vad_path.is_none() {
+        eprintln!("No VAD model given, recording without voice activity filtering");
+    }
+
+    let recorder = AudioRecorder::for_app(&CliPaths::from_env(vad_path));
+
+    eprintln!("Recording for {}s...", seconds);
+    let recording_options = RecordingOptions {
+        loopback_device,
+        device_lost_callback: Some(Box::new(|| {
+            eprintln!("Microphone disconnected, no more audio is captured")
+        })),
+        ..Default::default()
+    };
+    let recording = recorder
+        .start(recording_options)
+        .map_err(|e| e.user_message())?;
+    std::thread::sleep(Duration::from_secs(seconds));
+    let result = recording.stop().map_err(|e| e.user_message())?;
+
+    eprintln!(
+        "Recorded {}ms of speech to {}",
+        result.speech_duration_ms, result.file_path
+    );
+
+    if transcribe_after {
+        run_transcription(Path::new(&result.file_path), &options)?;
+    } else {
+        println!("{}", result.file_path);
+    }
+
+    Ok(())
+}
+
+fn transcribe(args: &[String]) -> Result<(), String> {
+    let mut file = None;
+    let mut options = TranscribeOptions::default();
+
+    let mut i = 0;
+    while i < args.len() {
+        if !options.parse_flag(args, &mut i)? {
+            match args[i].as_str() {
+                flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+                path => file = Some(PathBuf::from(path)),
+            }
+        }
+        i += 1;
+    }
+
+    let file = file.ok_or_else(|| format!("Missing audio file\n\n{}", USAGE))?;
+    run_transcription(&file, &options)
+}
+
+/// Transcribe `audio_path`, print the text and remember it for `paste-last`
+fn run_transcription(audio_path: &Path, options: &TranscribeOptions) -> Result<(), String> {
+    let text = match options.provider.as_str() {
+        "openai" => {
+            let api_key = required_env("OPENAI_API_KEY")?;
+            ApiTranscriber::new(Box::new(OpenAIClient::new(api_key))).transcribe(audio_path)
+        }
+        "azure" => {
+            let api_key = required_env("AZURE_OPENAI_API_KEY")?;
+            let endpoint = std::env::var("AZURE_OPENAI_ENDPOINT")
+                .map_err(|_| "AZURE_OPENAI_ENDPOINT is not set".to_string())?;
+            ApiTranscriber::new(Box::new(AzureClient::new(api_key, endpoint)))
+                .transcribe(audio_path)
+        }
+        "local" => {
+            let (model, model_type) = options.local_model()?;
+            LocalClient::new(&model, model_type)
+                .and_then(|mut client| client.transcribe_file(audio_path))
+        }
+        other => return Err(format!("Unknown provider '{}'", other)),
+    }
+    .map_err(|e| e.user_message())?;
+
+    println!("{}", text);
+
+    if let Err(e) = std::fs::write(last_transcript_path(), &text) {
+        eprintln!("Failed to save transcript for paste-last: {}", e);
+    }
+
+    Ok(())
+}
+
+fn paste_last() -> Result<(), String> {
+    let text = std::fs::read_to_string(last_transcript_path())
+        .map_err(|_| "No previous transcription to paste".to_string())?;
+
+    paste_text(&text).map_err(|e| format!("Failed to paste: {}", e))
+}
+
+fn required_env(name: &str) -> Result<SecretString, String> {
+    std::env::var(name)
+        .map(SecretString::from)
+        .map_err(|_| format!("{} is not set", name))
+}
+
+/// File where the last transcript is kept between invocations
+fn last_transcript_path() -> PathBuf {
+    std::env::temp_dir().join("dictara-cli-last.txt")
+}
@@ -0,0 +1,223 @@
+//! Decoding of existing audio files into the 16kHz mono format used for transcription.
+//!
+//! Supports WAV, MP3 and M4A/AAC via symphonia. Decoded audio is downmixed to mono
+//! and resampled with the same rubato FFT resampler the recorder uses.
+
+use std::fs::File;
+use std::path::Path;
+
+use hound::{WavSpec, WavWriter};
+use log::{info, warn};
+use rubato::{FftFixedInOut, Resampler};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::recorder::SAMPLE_RATE;
+
+/// File extensions accepted by [`load_audio_file`] (lowercase)
+pub const SUPPORTED_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a"];
+
+/// Frames per resampler chunk (same as the recorder)
+const RESAMPLER_CHUNK_SIZE: usize = 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AudioFileError {
+    #[error("Unsupported audio format: {0}")]
+    UnsupportedFormat(String),
+
+    #[error("Failed to decode audio: {0}")]
+    DecodeError(String),
+
+    #[error("Failed to resample audio: {0}")]
+    ResampleError(String),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Audio encoding error: {0}")]
+    EncodingError(#[from] hound::Error),
+}
+
+impl AudioFileError {
+    /// Returns a user-friendly error message suitable for display in the UI
+    pub fn user_message(&self) -> String {
+        match self {
+            AudioFileError::UnsupportedFormat(_) => {
+                "Unsupported file type. Use WAV, MP3 or M4A.".to_string()
+            }
+            AudioFileError::DecodeError(_) | AudioFileError::ResampleError(_) => {
+                "Could not read this audio file. It may be damaged.".to_string()
+            }
+            AudioFileError::IoError(_) | AudioFileError::EncodingError(_) => {
+                "Failed to read or write audio file. Check disk space.".to_string()
+            }
+        }
+    }
+}
+
+/// Returns true if the file extension is one of [`SUPPORTED_EXTENSIONS`]
+pub fn is_supported_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| SUPPORTED_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Decode an audio file into 16kHz mono f32 samples
+pub fn load_audio_file(path: &Path) -> Result<Vec<f32>, AudioFileError> {
+    if !is_supported_audio_file(path) {
+        return Err(AudioFileError::UnsupportedFormat(
+            path.to_string_lossy().to_string(),
+        ));
+    }
+
+    let (samples, sample_rate) = decode_to_mono(path)?;
+    info!(
+        "Decoded {:?}: {} samples at {}Hz",
+        path,
+        samples.len(),
+        sample_rate
+    );
+
+    resample_to_16k(&samples, sample_rate)
+}
+
+/// Write 16kHz mono f32 samples to a 16-bit WAV file
+pub fn write_wav_16k_mono(path: &Path, samples: &[f32]) -> Result<(), AudioFileError> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer = WavWriter::create(path, spec)?;
+    for &sample in samples {
+        let clamped = sample.clamp(-1.0, 1.0);
+        writer.write_sample((clamped * i16::MAX as f32) as i16)?;
+    }
+    writer.finalize()?;
+
+    Ok(())
+}
+
+/// Decode the first audio track and downmix it to mono
+fn decode_to_mono(path: &Path) -> Result<(Vec<f32>, u32), AudioFileError> {
+    let file = File::open(path)?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|ext| ext.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| AudioFileError::UnsupportedFormat(e.to_string()))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| AudioFileError::DecodeError("No audio track found".to_string()))?;
+    let track_id = track.id;
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| AudioFileError::DecodeError("Unknown sample rate".to_string()))?;
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| AudioFileError::UnsupportedFormat(e.to_string()))?;
+
+    let mut mono = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            // End of stream
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            Err(e) => return Err(AudioFileError::DecodeError(e.to_string())),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // Corrupt packets are skipped, the rest of the file is still usable
+            Err(SymphoniaError::DecodeError(e)) => {
+                warn!("Skipping undecodable packet: {}", e);
+                continue;
+            }
+            Err(e) => return Err(AudioFileError::DecodeError(e.to_string())),
+        };
+
+        let spec = *decoded.spec();
+        let channels = spec.channels.count().max(1);
+        let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        buffer.copy_interleaved_ref(decoded);
+
+        // Average all channels into mono
+        mono.extend(
+            buffer
+                .samples()
+                .chunks(channels)
+                .map(|frame| frame.iter().sum::<f32>() / channels as f32),
+        );
+    }
+
+    Ok((mono, sample_rate))
+}
+
+/// Resample mono samples to 16kHz using the FFT resampler
+fn resample_to_16k(samples: &[f32], input_rate: u32) -> Result<Vec<f32>, AudioFileError> {
+    if input_rate == SAMPLE_RATE {
+        return Ok(samples.to_vec());
+    }
+
+    let mut resampler = FftFixedInOut::<f32>::new(
+        input_rate as usize,
+        SAMPLE_RATE as usize,
+        RESAMPLER_CHUNK_SIZE,
+        1,
+    )
+    .map_err(|e| AudioFileError::ResampleError(e.to_string()))?;
+
+    let expected_len = (samples.len() as u64 * SAMPLE_RATE as u64 / input_rate as u64) as usize;
+    let delay = resampler.output_delay();
+    let mut output = Vec::with_capacity(expected_len + delay);
+
+    let mut position = 0;
+    while output.len() < expected_len + delay {
+        let chunk_size = resampler.input_frames_next();
+        // Pad the tail (and flush the resampler delay) with silence
+        let mut chunk = vec![0.0; chunk_size];
+        if position < samples.len() {
+            let end = (position + chunk_size).min(samples.len());
+            chunk[..end - position].copy_from_slice(&samples[position..end]);
+        }
+        position += chunk_size;
+
+        let resampled = resampler
+            .process(&[chunk.as_slice()], None)
+            .map_err(|e| AudioFileError::ResampleError(e.to_string()))?;
+        output.extend_from_slice(&resampled[0]);
+    }
+
+    // Drop the resampler delay at the start and the padding at the end
+    Ok(output[delay..delay + expected_len].to_vec())
+}
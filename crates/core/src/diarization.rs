@@ -0,0 +1,304 @@
+//! Speaker diarization of two-party recordings (`diarization` feature).
+//!
+//! Each transcribed segment gets a speaker embedding from a WeSpeaker ONNX model
+//! (Kaldi-style log-mel filterbank input), and the embeddings are clustered into at
+//! most two speakers. Lightweight enough to run after a meeting on the CPU.
+
+use std::f32::consts::PI;
+use std::path::Path;
+
+use ort::session::Session;
+use ort::value::Value;
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use thiserror::Error;
+
+use crate::recorder::SAMPLE_RATE;
+
+/// 25ms analysis window at 16kHz
+const FRAME_LENGTH: usize = 400;
+/// 10ms hop at 16kHz
+const FRAME_SHIFT: usize = 160;
+const FFT_SIZE: usize = 512;
+const MEL_BINS: usize = 80;
+const LOW_FREQ_HZ: f32 = 20.0;
+const PREEMPHASIS: f32 = 0.97;
+
+/// Embeddings at least this similar (cosine) belong to the same speaker
+const SAME_SPEAKER_SIMILARITY: f32 = 0.5;
+const MAX_ITERATIONS: usize = 20;
+
+#[derive(Debug, Error)]
+pub enum DiarizationError {
+    #[error("Failed to load speaker model: {0}")]
+    InitError(String),
+
+    #[error("Speaker embedding failed: {0}")]
+    ComputeError(String),
+
+    #[error("Audio too short for a speaker embedding")]
+    TooShort,
+}
+
+/// Speaker embeddings of audio segments
+pub struct SpeakerEmbedder {
+    session: Session,
+}
+
+impl SpeakerEmbedder {
+    /// Load a WeSpeaker model (input `feats` [batch, frames, 80], output embeddings)
+    pub fn new<P: AsRef<Path>>(model_path: P) -> Result<Self, DiarizationError> {
+        let session = Session::builder()
+            .map_err(|e| DiarizationError::InitError(e.to_string()))?
+            .commit_from_file(model_path)
+            .map_err(|e| DiarizationError::InitError(e.to_string()))?;
+        Ok(Self { session })
+    }
+
+    /// Embedding of 16kHz mono `samples`
+    pub fn embed(&mut self, samples: &[f32]) -> Result<Vec<f32>, DiarizationError> {
+        let features = fbank(samples);
+        if features.is_empty() {
+            return Err(DiarizationError::TooShort);
+        }
+
+        let shape = vec![1, features.len(), MEL_BINS];
+        let data: Vec<f32> = features.into_iter().flatten().collect();
+        let feats = Value::from_array((shape, data))
+            .map_err(|e| DiarizationError::ComputeError(e.to_string()))?;
+
+        let outputs = self
+            .session
+            .run([(&feats).into()])
+            .map_err(|e| DiarizationError::ComputeError(e.to_string()))?;
+        let (_, embedding) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| DiarizationError::ComputeError(e.to_string()))?;
+        Ok(embedding.to_vec())
+    }
+}
+
+/// Speaker index (0 or 1) of each embedding, 0 being whoever speaks first
+///
+/// `None` embeddings (segments that could not be embedded) stay unlabeled. Everything
+/// is attributed to one speaker when the two clusters sound alike.
+pub fn assign_two_speakers(embeddings: &[Option<Vec<f32>>]) -> Vec<Option<usize>> {
+    let normalized: Vec<Option<Vec<f32>>> = embeddings
+        .iter()
+        .map(|embedding| embedding.as_deref().map(normalize))
+        .collect();
+    let known: Vec<&Vec<f32>> = normalized.iter().flatten().collect();
+    let Some(first) = known.first() else {
+        return vec![None; embeddings.len()];
+    };
+
+    // Start from the first segment and the one least like it
+    let mut centroids = [(*first).clone(), (*first).clone()];
+    if let Some(farthest) = known
+        .iter()
+        .min_by(|a, b| similarity(first, a).total_cmp(&similarity(first, b)))
+    {
+        centroids[1] = (*farthest).clone();
+    }
+
+    let mut labels: Vec<usize> = vec![0; known.len()];
+    if similarity(&centroids[0], &centroids[1]) < SAME_SPEAKER_SIMILARITY {
+        for _ in 0..MAX_ITERATIONS {
+            let next: Vec<usize> = known
+                .iter()
+                .map(|embedding| {
+                    usize::from(
+                        similarity(embedding, &centroids[1]) > similarity(embedding, &centroids[0]),
+                    )
+                })
+                .collect();
+            if next == labels {
+                break;
+            }
+            labels = next;
+            for (speaker, centroid) in centroids.iter_mut().enumerate() {
+                if let Some(mean) = mean(
+                    known
+                        .iter()
+                        .zip(&labels)
+                        .filter(|(_, label)| **label == speaker)
+                        .map(|(embedding, _)| embedding.as_slice()),
+                ) {
+                    *centroid = normalize(&mean);
+                }
+            }
+        }
+
+        if similarity(&centroids[0], &centroids[1]) >= SAME_SPEAKER_SIMILARITY {
+            labels = vec![0; known.len()];
+        }
+    }
+
+    // Number speakers in order of appearance
+    let swap = labels.first() == Some(&1);
+    let mut labels = labels.into_iter().map(|label| label ^ usize::from(swap));
+    normalized
+        .iter()
+        .map(|embedding| embedding.as_ref().and_then(|_| labels.next()))
+        .collect()
+}
+
+fn normalize(vector: &[f32]) -> Vec<f32> {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|x| x / norm).collect()
+}
+
+/// Cosine similarity of two normalized vectors
+fn similarity(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn mean<'a>(vectors: impl Iterator<Item = &'a [f32]>) -> Option<Vec<f32>> {
+    let mut sum: Option<Vec<f32>> = None;
+    let mut count = 0;
+    for vector in vectors {
+        let sum = sum.get_or_insert_with(|| vec![0.0; vector.len()]);
+        for (total, x) in sum.iter_mut().zip(vector) {
+            *total += x;
+        }
+        count += 1;
+    }
+    sum.map(|sum| sum.into_iter().map(|total| total / count as f32).collect())
+}
+
+/// Kaldi-compatible log-mel filterbank (80 bins, 25ms/10ms, Hamming window), with the
+/// mean of each bin subtracted, as the WeSpeaker models expect
+fn fbank(samples: &[f32]) -> Vec<[f32; MEL_BINS]> {
+    if samples.len() < FRAME_LENGTH {
+        return Vec::new();
+    }
+    let frame_count = 1 + (samples.len() - FRAME_LENGTH) / FRAME_SHIFT;
+
+    let window: Vec<f32> = (0..FRAME_LENGTH)
+        .map(|i| 0.54 - 0.46 * (2.0 * PI * i as f32 / (FRAME_LENGTH - 1) as f32).cos())
+        .collect();
+    let mel_banks = mel_banks();
+    let fft = FftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+
+    let mut features = Vec::with_capacity(frame_count);
+    let mut buffer = vec![Complex::new(0.0, 0.0); FFT_SIZE];
+    for frame_index in 0..frame_count {
+        let start = frame_index * FRAME_SHIFT;
+        // The models were trained on 16-bit sample values
+        let mut frame: Vec<f32> = samples[start..start + FRAME_LENGTH]
+            .iter()
+            .map(|sample| sample * 32768.0)
+            .collect();
+
+        let dc = frame.iter().sum::<f32>() / FRAME_LENGTH as f32;
+        frame.iter_mut().for_each(|sample| *sample -= dc);
+        for i in (1..FRAME_LENGTH).rev() {
+            frame[i] -= PREEMPHASIS * frame[i - 1];
+        }
+        frame[0] -= PREEMPHASIS * frame[0];
+
+        for (i, value) in buffer.iter_mut().enumerate() {
+            let sample = if i < FRAME_LENGTH {
+                frame[i] * window[i]
+            } else {
+                0.0
+            };
+            *value = Complex::new(sample, 0.0);
+        }
+        fft.process(&mut buffer);
+
+        let power: Vec<f32> = buffer[..=FFT_SIZE / 2]
+            .iter()
+            .map(|bin| bin.norm_sqr())
+            .collect();
+        let mut energies = [0.0f32; MEL_BINS];
+        for (energy, bank) in energies.iter_mut().zip(&mel_banks) {
+            let sum: f32 = bank.iter().zip(&power).map(|(weight, p)| weight * p).sum();
+            *energy = sum.max(f32::EPSILON).ln();
+        }
+        features.push(energies);
+    }
+
+    for bin in 0..MEL_BINS {
+        let mean = features.iter().map(|frame| frame[bin]).sum::<f32>() / frame_count as f32;
+        features.iter_mut().for_each(|frame| frame[bin] -= mean);
+    }
+    features
+}
+
+/// Triangular mel filters over the FFT bins
+fn mel_banks() -> Vec<Vec<f32>> {
+    fn mel(hz: f32) -> f32 {
+        1127.0 * (1.0 + hz / 700.0).ln()
+    }
+
+    let nyquist = SAMPLE_RATE as f32 / 2.0;
+    let low_mel = mel(LOW_FREQ_HZ);
+    let mel_step = (mel(nyquist) - low_mel) / (MEL_BINS + 1) as f32;
+    let bin_hz = SAMPLE_RATE as f32 / FFT_SIZE as f32;
+
+    (0..MEL_BINS)
+        .map(|bank| {
+            let left = low_mel + bank as f32 * mel_step;
+            let center = left + mel_step;
+            let right = center + mel_step;
+            (0..=FFT_SIZE / 2)
+                .map(|bin| {
+                    let bin_mel = mel(bin as f32 * bin_hz);
+                    if bin_mel > left && bin_mel <= center {
+                        (bin_mel - left) / (center - left)
+                    } else if bin_mel > center && bin_mel < right {
+                        (right - bin_mel) / (right - center)
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assign_two_speakers() {
+        let a = Some(vec![1.0, 0.1, 0.0]);
+        let b = Some(vec![0.0, 0.2, 1.0]);
+        let a2 = Some(vec![0.9, 0.0, 0.1]);
+        assert_eq!(
+            assign_two_speakers(&[b.clone(), a.clone(), None, a2, b]),
+            vec![Some(0), Some(1), None, Some(1), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_similar_voices_are_one_speaker() {
+        let embeddings = [
+            Some(vec![1.0, 0.1, 0.0]),
+            Some(vec![0.9, 0.2, 0.0]),
+            Some(vec![1.0, 0.0, 0.1]),
+        ];
+        assert_eq!(
+            assign_two_speakers(&embeddings),
+            vec![Some(0), Some(0), Some(0)]
+        );
+        assert_eq!(assign_two_speakers(&[None]), vec![None]);
+    }
+
+    #[test]
+    fn test_fbank_frames() {
+        assert!(fbank(&[0.0; FRAME_LENGTH - 1]).is_empty());
+        // 1s of audio: 1 + (16000 - 400) / 160 frames
+        let tone: Vec<f32> = (0..SAMPLE_RATE as usize)
+            .map(|i| (2.0 * PI * 440.0 * i as f32 / SAMPLE_RATE as f32).sin() * 0.1)
+            .collect();
+        let features = fbank(&tone);
+        assert_eq!(features.len(), 98);
+        assert!(features.iter().flatten().all(|value| value.is_finite()));
+    }
+}
@@ -0,0 +1,56 @@
+//! Directories the pipeline needs from the program embedding it.
+//!
+//! The app resolves them from the Tauri path resolver and the config (a relocated
+//! models directory), the CLI from environment variables. Events and settings need no
+//! trait: the building blocks take callbacks ([`crate::recorder::RecordingOptions`],
+//! download progress) and plain settings structs ([`crate::http::HttpSettings`]).
+
+use std::path::PathBuf;
+
+/// Where the embedding program keeps models, recordings and the bundled VAD model
+pub trait AppPaths {
+    /// Directory of the downloaded models
+    fn models_dir(&self) -> PathBuf;
+
+    /// Directory where recordings are written until they are transcribed
+    fn recordings_dir(&self) -> PathBuf;
+
+    /// Silero VAD model, `None` to record without VAD filtering
+    fn vad_model_path(&self) -> Option<PathBuf>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{find_model, ModelStore};
+    use crate::recorder::AudioRecorder;
+
+    struct TestPaths(PathBuf);
+
+    impl AppPaths for TestPaths {
+        fn models_dir(&self) -> PathBuf {
+            self.0.join("models")
+        }
+
+        fn recordings_dir(&self) -> PathBuf {
+            self.0.join("recordings")
+        }
+
+        fn vad_model_path(&self) -> Option<PathBuf> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_building_blocks_for_app() {
+        let paths = TestPaths(PathBuf::from("/data"));
+
+        let store = ModelStore::for_app(&paths);
+        assert_eq!(store.models_dir(), PathBuf::from("/data/models"));
+        let entry = find_model("whisper-small").unwrap();
+        assert!(store.model_dir(&entry).starts_with("/data/models"));
+
+        let recorder = AudioRecorder::for_app(&paths);
+        assert_eq!(recorder.audio_dir(), PathBuf::from("/data/recordings"));
+    }
+}
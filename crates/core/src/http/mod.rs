@@ -0,0 +1,272 @@
+//! Settings of the HTTP clients (transcription APIs, post-processing, model downloads).
+//!
+//! By default reqwest follows the system proxy settings (macOS network preferences)
+//! and the `HTTP_PROXY`/`HTTPS_PROXY` variables, which covers most corporate setups.
+//! [`HttpProxy`] lets the user override that with a direct connection or a manual proxy.
+//! [`TlsSettings`] adds a corporate root CA (for proxies that inspect TLS) and can pin
+//! the roots trusted for specific hosts.
+//!
+//! API clients send their requests through an [`HttpTransport`], so they can be tested
+//! against a [`MockTransport`].
+
+mod transport;
+
+pub use transport::{
+    FormPart, HttpBody, HttpRequest, HttpResponse, HttpTransport, MockTransport, ReqwestTransport,
+    TransportError,
+};
+
+use std::path::{Path, PathBuf};
+
+use secrecy::{ExposeSecret, SecretString};
+use thiserror::Error;
+
+/// Errors applying [`HttpSettings`] to a client
+#[derive(Debug, Error)]
+pub enum HttpSettingsError {
+    #[error("Failed to read certificate file {}: {source}", path.display())]
+    CertificateFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid certificate file {}: {source}", path.display())]
+    InvalidCertificate {
+        path: PathBuf,
+        source: reqwest::Error,
+    },
+
+    #[error("Invalid proxy: {0}")]
+    Proxy(reqwest::Error),
+}
+
+/// How outbound HTTP requests reach the internet
+#[derive(Debug, Clone, Default)]
+pub enum HttpProxy {
+    /// System proxy settings and proxy environment variables
+    #[default]
+    System,
+    /// Direct connections, even when a system proxy is configured
+    Direct,
+    /// Every request through the proxy at `url` (e.g. `http://proxy.corp:8080`)
+    Manual {
+        url: String,
+        /// Basic authentication (username, password)
+        credentials: Option<(String, SecretString)>,
+    },
+}
+
+/// Certificates trusted for TLS, on top of (or instead of) the system roots
+#[derive(Debug, Clone, Default)]
+pub struct TlsSettings {
+    /// PEM file with extra root certificates, trusted next to the system ones
+    pub custom_ca_path: Option<PathBuf>,
+    /// PEM files of the only roots trusted for a host, by host name
+    /// (e.g. `api.openai.com`). Redirects must chain to the same roots.
+    pub pinned_roots: Vec<(String, PathBuf)>,
+}
+
+impl TlsSettings {
+    /// Pinned roots of the host of `url`
+    fn pinned_roots_for(&self, url: &str) -> Option<&Path> {
+        let url = reqwest::Url::parse(url).ok()?;
+        let host = url.host_str()?;
+        self.pinned_roots
+            .iter()
+            .find(|(pinned_host, _)| pinned_host.eq_ignore_ascii_case(host))
+            .map(|(_, path)| path.as_path())
+    }
+}
+
+/// Proxy and TLS settings of the HTTP clients
+#[derive(Debug, Clone, Default)]
+pub struct HttpSettings {
+    pub proxy: HttpProxy,
+    pub tls: TlsSettings,
+}
+
+impl HttpSettings {
+    /// Apply to a blocking client sending requests to `url`
+    pub fn configure_blocking(
+        &self,
+        mut builder: reqwest::blocking::ClientBuilder,
+        url: &str,
+    ) -> Result<reqwest::blocking::ClientBuilder, HttpSettingsError> {
+        builder = match &self.proxy {
+            HttpProxy::System => builder,
+            HttpProxy::Direct => builder.no_proxy(),
+            HttpProxy::Manual { url, credentials } => {
+                builder.proxy(manual_proxy(url, credentials.as_ref())?)
+            }
+        };
+
+        if let Some(path) = self.tls.pinned_roots_for(url) {
+            builder = builder.tls_built_in_root_certs(false);
+            for certificate in load_certificates(path)? {
+                builder = builder.add_root_certificate(certificate);
+            }
+        } else if let Some(path) = &self.tls.custom_ca_path {
+            for certificate in load_certificates(path)? {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        Ok(builder)
+    }
+
+    /// Apply to an async client sending requests to `url`
+    pub fn configure(
+        &self,
+        mut builder: reqwest::ClientBuilder,
+        url: &str,
+    ) -> Result<reqwest::ClientBuilder, HttpSettingsError> {
+        builder = match &self.proxy {
+            HttpProxy::System => builder,
+            HttpProxy::Direct => builder.no_proxy(),
+            HttpProxy::Manual { url, credentials } => {
+                builder.proxy(manual_proxy(url, credentials.as_ref())?)
+            }
+        };
+
+        if let Some(path) = self.tls.pinned_roots_for(url) {
+            builder = builder.tls_built_in_root_certs(false);
+            for certificate in load_certificates(path)? {
+                builder = builder.add_root_certificate(certificate);
+            }
+        } else if let Some(path) = &self.tls.custom_ca_path {
+            for certificate in load_certificates(path)? {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        Ok(builder)
+    }
+}
+
+fn manual_proxy(
+    url: &str,
+    credentials: Option<&(String, SecretString)>,
+) -> Result<reqwest::Proxy, HttpSettingsError> {
+    let proxy = reqwest::Proxy::all(url).map_err(HttpSettingsError::Proxy)?;
+    Ok(match credentials {
+        Some((username, password)) => proxy.basic_auth(username, password.expose_secret()),
+        None => proxy,
+    })
+}
+
+/// Certificates of a PEM file (one or more `BEGIN CERTIFICATE` blocks)
+pub fn load_certificates(path: &Path) -> Result<Vec<reqwest::Certificate>, HttpSettingsError> {
+    let pem = std::fs::read(path).map_err(|source| HttpSettingsError::CertificateFile {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    reqwest::Certificate::from_pem_bundle(&pem).map_err(|source| {
+        HttpSettingsError::InvalidCertificate {
+            path: path.to_path_buf(),
+            source,
+        }
+    })
+}
+
+/// Message of a failed request. reqwest only says "error sending request", so
+/// TLS validation failures (usually a proxy inspecting traffic) are spelled out.
+pub fn describe_error(error: &reqwest::Error) -> String {
+    match certificate_failure(error) {
+        Some(message) => {
+            let host = error
+                .url()
+                .and_then(|url| url.host_str())
+                .unwrap_or("the server");
+            format!(
+                "TLS certificate validation failed for {}: {}. If a proxy inspects your \
+                 network traffic, add its root certificate in the network settings",
+                host, message
+            )
+        }
+        None => error.to_string(),
+    }
+}
+
+/// The certificate failure among `error` and its sources, the innermost (most precise)
+/// one when several errors of the chain mention it
+fn certificate_failure(error: &(dyn std::error::Error + 'static)) -> Option<String> {
+    let mut failure = None;
+    let mut current = Some(error);
+    while let Some(error) = current {
+        let message = error.to_string();
+        if is_certificate_failure(&message) {
+            failure = Some(message);
+        }
+        current = error.source();
+    }
+    failure
+}
+
+fn is_certificate_failure(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("certificate") || message.contains("not trusted")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Error with a message and an optional source, like the layers of a reqwest error
+    #[derive(Debug)]
+    struct Layer(&'static str, Option<Box<Layer>>);
+
+    impl std::fmt::Display for Layer {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl std::error::Error for Layer {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            self.1.as_deref().map(|source| source as _)
+        }
+    }
+
+    #[test]
+    fn test_certificate_failure_walks_the_sources() {
+        let error = Layer(
+            "error sending request",
+            Some(Box::new(Layer(
+                "client error (Connect)",
+                Some(Box::new(Layer(
+                    "invalid peer certificate: UnknownIssuer",
+                    Some(Box::new(Layer("certificate is not trusted", None))),
+                ))),
+            ))),
+        );
+        assert_eq!(
+            certificate_failure(&error).as_deref(),
+            Some("certificate is not trusted")
+        );
+
+        let error = Layer(
+            "error sending request",
+            Some(Box::new(Layer("connection refused", None))),
+        );
+        assert_eq!(certificate_failure(&error), None);
+    }
+
+    #[test]
+    fn test_pinned_roots_match_the_url_host() {
+        let tls = TlsSettings {
+            custom_ca_path: None,
+            pinned_roots: vec![(
+                "api.openai.com".to_string(),
+                PathBuf::from("/pins/openai.pem"),
+            )],
+        };
+
+        assert_eq!(
+            tls.pinned_roots_for("https://API.openai.com/v1/audio/transcriptions"),
+            Some(Path::new("/pins/openai.pem"))
+        );
+        assert_eq!(
+            tls.pinned_roots_for("https://huggingface.co/model.bin"),
+            None
+        );
+        assert_eq!(tls.pinned_roots_for("not a url"), None);
+    }
+}
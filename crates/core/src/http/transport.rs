@@ -0,0 +1,240 @@
+//! Sending of API requests, behind a trait so clients can be tested without network.
+//!
+//! [`ReqwestTransport`] sends requests with [`HttpSettings`] applied. [`MockTransport`]
+//! answers with scripted responses and records the requests it got.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use secrecy::{ExposeSecret, SecretString};
+use thiserror::Error;
+
+use super::{describe_error, HttpSettings};
+
+/// Body of a POST request
+#[derive(Debug, Clone)]
+pub enum HttpBody {
+    Json(serde_json::Value),
+    /// `multipart/form-data`
+    Multipart(Vec<FormPart>),
+}
+
+/// Field of a multipart form
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormPart {
+    Text {
+        name: String,
+        value: String,
+    },
+    /// Contents of a file, read when the request is sent
+    File {
+        name: String,
+        path: PathBuf,
+    },
+}
+
+impl FormPart {
+    pub fn text(name: impl Into<String>, value: impl Into<String>) -> Self {
+        FormPart::Text {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// A POST request to an API
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub url: String,
+    /// Extra headers, e.g. authentication (kept secret in logs)
+    pub headers: Vec<(String, SecretString)>,
+    pub body: HttpBody,
+    pub timeout: Duration,
+}
+
+impl HttpRequest {
+    pub fn post(url: impl Into<String>, body: HttpBody, timeout: Duration) -> Self {
+        Self {
+            url: url.into(),
+            headers: Vec::new(),
+            body,
+            timeout,
+        }
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: SecretString) -> Self {
+        self.headers.push((name.into(), value));
+        self
+    }
+
+    /// Value of header `name`, for tests
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.expose_secret())
+    }
+
+    /// Text field `name` of a multipart body
+    pub fn form_text(&self, name: &str) -> Option<&str> {
+        let HttpBody::Multipart(parts) = &self.body else {
+            return None;
+        };
+        parts.iter().find_map(|part| match part {
+            FormPart::Text { name: field, value } if field == name => Some(value.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// Response to an [`HttpRequest`], any status
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: body.into(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::from_str(&self.body)
+    }
+}
+
+/// A request that got no response
+#[derive(Debug, Error)]
+pub enum TransportError {
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
+
+    /// A file of the multipart body can't be read
+    #[error("Failed to read {}: {source}", path.display())]
+    File {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// Connection, TLS or proxy failure (or invalid [`HttpSettings`])
+    #[error("{0}")]
+    Request(String),
+}
+
+/// Sends API requests
+pub trait HttpTransport: Send + Sync {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError>;
+}
+
+/// Blocking reqwest client with proxy and TLS settings
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    settings: HttpSettings,
+}
+
+impl ReqwestTransport {
+    pub fn new(settings: HttpSettings) -> Self {
+        Self { settings }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        let client = self
+            .settings
+            .configure_blocking(
+                reqwest::blocking::Client::builder().timeout(request.timeout),
+                &request.url,
+            )
+            .map_err(|e| TransportError::Request(e.to_string()))?
+            .build()
+            .map_err(|e| TransportError::Request(format!("Failed to create HTTP client: {}", e)))?;
+
+        let mut builder = client.post(&request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.expose_secret());
+        }
+        builder = match request.body {
+            HttpBody::Json(json) => builder
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(json.to_string()),
+            HttpBody::Multipart(parts) => {
+                let mut form = reqwest::blocking::multipart::Form::new();
+                for part in parts {
+                    form = match part {
+                        FormPart::Text { name, value } => form.text(name, value),
+                        FormPart::File { name, path } => form
+                            .file(name, &path)
+                            .map_err(|source| TransportError::File { path, source })?,
+                    };
+                }
+                builder.multipart(form)
+            }
+        };
+
+        let response = builder.send().map_err(|e| {
+            if e.is_timeout() {
+                TransportError::Timeout(request.timeout)
+            } else {
+                TransportError::Request(describe_error(&e))
+            }
+        })?;
+
+        let status = response.status().as_u16();
+        let body = response
+            .text()
+            .map_err(|e| TransportError::Request(describe_error(&e)))?;
+        Ok(HttpResponse { status, body })
+    }
+}
+
+/// Transport for tests: answers requests with scripted responses, in order
+#[derive(Default)]
+pub struct MockTransport {
+    responses: Mutex<VecDeque<Result<HttpResponse, TransportError>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer the next request with `status` and `body`
+    pub fn respond(self, status: u16, body: impl Into<String>) -> Self {
+        self.respond_with(Ok(HttpResponse::new(status, body)))
+    }
+
+    /// Answer the next request with `result`, e.g. a timeout
+    pub fn respond_with(self, result: Result<HttpResponse, TransportError>) -> Self {
+        self.responses.lock().unwrap().push_back(result);
+        self
+    }
+
+    /// Requests sent so far
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        let url = request.url.clone();
+        self.requests.lock().unwrap().push(request);
+        self.responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| panic!("MockTransport: no response scripted for {}", url))
+    }
+}
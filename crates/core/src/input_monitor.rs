@@ -0,0 +1,133 @@
+//! Notifications when the system default input device changes.
+//!
+//! Connecting or disconnecting AirPods (or picking another microphone in System
+//! Settings) changes the default input device. The [`Recording`](crate::recorder::Recording)
+//! keeps using the device it was started on, so the owner listens for changes and
+//! decides whether to [switch](crate::recorder::Recording::switch_to_default_input).
+//!
+//! Only implemented on macOS (CoreAudio property listener).
+
+/// Callback invoked on a CoreAudio thread when the default input device changes
+pub type DefaultInputChangedCallback = Box<dyn Fn() + Send + Sync + 'static>;
+
+/// Watches the default input device for as long as it is alive
+pub struct DefaultInputMonitor {
+    /// Boxed twice so the listener's client data pointer stays valid when the monitor moves
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    callback: Box<DefaultInputChangedCallback>,
+}
+
+impl DefaultInputMonitor {
+    /// Start watching, `None` if unsupported on this platform or registration failed
+    pub fn start(callback: DefaultInputChangedCallback) -> Option<Self> {
+        #[cfg(target_os = "macos")]
+        {
+            let monitor = Self {
+                callback: Box::new(callback),
+            };
+            if let Err(status) = macos::add_listener(monitor.client_data()) {
+                log::error!(
+                    "Failed to watch the default input device (OSStatus {})",
+                    status
+                );
+                return None;
+            }
+            log::info!("Watching default input device changes");
+            Some(monitor)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = callback;
+            log::info!("Default input device monitoring is not supported on this platform");
+            None
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn client_data(&self) -> *mut std::ffi::c_void {
+        &*self.callback as *const DefaultInputChangedCallback as *mut std::ffi::c_void
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl Drop for DefaultInputMonitor {
+    fn drop(&mut self) {
+        macos::remove_listener(self.client_data());
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::ffi::c_void;
+    use std::ptr::NonNull;
+
+    use objc2_core_audio::{
+        kAudioHardwarePropertyDefaultInputDevice, kAudioObjectPropertyElementMain,
+        kAudioObjectPropertyScopeGlobal, kAudioObjectSystemObject, AudioObjectAddPropertyListener,
+        AudioObjectID, AudioObjectPropertyAddress, AudioObjectRemovePropertyListener,
+    };
+
+    use super::DefaultInputChangedCallback;
+
+    fn default_input_address() -> AudioObjectPropertyAddress {
+        AudioObjectPropertyAddress {
+            mSelector: kAudioHardwarePropertyDefaultInputDevice,
+            mScope: kAudioObjectPropertyScopeGlobal,
+            mElement: kAudioObjectPropertyElementMain,
+        }
+    }
+
+    /// Register the listener, `client_data` must point to a `DefaultInputChangedCallback`
+    /// that outlives the registration
+    pub(super) fn add_listener(client_data: *mut c_void) -> Result<(), i32> {
+        let mut address = default_input_address();
+        // SAFETY: `address` is valid for the call; the monitor keeps `client_data`
+        // alive until `remove_listener` runs in its Drop
+        let status = unsafe {
+            AudioObjectAddPropertyListener(
+                kAudioObjectSystemObject as AudioObjectID,
+                NonNull::from(&mut address),
+                Some(default_input_changed),
+                client_data,
+            )
+        };
+        if status == 0 {
+            Ok(())
+        } else {
+            Err(status)
+        }
+    }
+
+    pub(super) fn remove_listener(client_data: *mut c_void) {
+        let mut address = default_input_address();
+        // SAFETY: same listener proc and client data as registered in `add_listener`
+        let status = unsafe {
+            AudioObjectRemovePropertyListener(
+                kAudioObjectSystemObject as AudioObjectID,
+                NonNull::from(&mut address),
+                Some(default_input_changed),
+                client_data,
+            )
+        };
+        if status != 0 {
+            log::warn!(
+                "Failed to remove default input listener (OSStatus {})",
+                status
+            );
+        }
+    }
+
+    unsafe extern "C-unwind" fn default_input_changed(
+        _object_id: AudioObjectID,
+        _address_count: u32,
+        _addresses: NonNull<AudioObjectPropertyAddress>,
+        client_data: *mut c_void,
+    ) -> i32 {
+        // SAFETY: registered with a pointer to the monitor's callback, which is
+        // unregistered before being freed
+        let callback = unsafe { &*(client_data as *const DefaultInputChangedCallback) };
+        callback();
+        0
+    }
+}
@@ -0,0 +1,90 @@
+//! Coalescing of input levels before they reach the [`LevelCallback`].
+//!
+//! Audio buffers arrive every 10-20ms; forwarding each level wakes the webview
+//! that often for a meter nobody can read that fast. Levels are held within a
+//! window and only the peak is reported, so short syllables still show up.
+
+use std::time::{Duration, Instant};
+
+use crate::recorder::LevelCallback;
+
+pub struct LevelThrottle {
+    callback: LevelCallback,
+    interval: Duration,
+    last_sent: Option<Instant>,
+    /// Highest level since the last report
+    peak: f32,
+}
+
+impl LevelThrottle {
+    /// Report at most `max_hz` levels per second, 0 = every buffer
+    pub fn new(callback: LevelCallback, max_hz: u32) -> Self {
+        let interval = match max_hz {
+            0 => Duration::ZERO,
+            max_hz => Duration::from_secs(1) / max_hz,
+        };
+        Self {
+            callback,
+            interval,
+            last_sent: None,
+            peak: 0.0,
+        }
+    }
+
+    pub fn push(&mut self, level: f32) {
+        self.push_at(level, Instant::now());
+    }
+
+    fn push_at(&mut self, level: f32, now: Instant) {
+        self.peak = self.peak.max(level);
+        if self
+            .last_sent
+            .is_some_and(|last_sent| now.duration_since(last_sent) < self.interval)
+        {
+            return;
+        }
+
+        (self.callback)(self.peak);
+        self.peak = 0.0;
+        self.last_sent = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn recording_throttle(max_hz: u32) -> (LevelThrottle, Arc<Mutex<Vec<f32>>>) {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&sent);
+        let callback: LevelCallback = Box::new(move |level| sink.lock().unwrap().push(level));
+        (LevelThrottle::new(callback, max_hz), sent)
+    }
+
+    #[test]
+    fn test_reports_the_peak_once_per_window() {
+        let (mut throttle, sent) = recording_throttle(50); // 20ms windows
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+
+        throttle.push_at(0.1, ms(0));
+        throttle.push_at(0.8, ms(5));
+        throttle.push_at(0.2, ms(10));
+        throttle.push_at(0.3, ms(20));
+        throttle.push_at(0.4, ms(25));
+
+        assert_eq!(*sent.lock().unwrap(), vec![0.1, 0.8]);
+    }
+
+    #[test]
+    fn test_zero_rate_reports_every_level() {
+        let (mut throttle, sent) = recording_throttle(0);
+        let now = Instant::now();
+
+        throttle.push_at(0.5, now);
+        throttle.push_at(0.2, now);
+
+        assert_eq!(*sent.lock().unwrap(), vec![0.5, 0.2]);
+    }
+}
@@ -0,0 +1,38 @@
+//! Core dictation pipeline shared by the Dictara app and the `dictara-cli` binary.
+//!
+//! This crate has no Tauri dependency. It contains:
+//! - [`audio_file`]: decoding of existing WAV/MP3/M4A files into 16kHz mono
+//! - [`recorder`]: microphone capture (optionally mixed with system audio), resampling to
+//!   16kHz mono and VAD filtering into a WAV file
+//! - [`input_monitor`]: notifications when the default input device changes
+//! - [`vad`]: Silero voice activity detection
+//! - [`transcription`]: API (OpenAI, Azure) and local (Whisper, Parakeet) transcription
+//! - [`models`]: catalog of downloadable local models and their layout on disk
+//! - `diarization`: speaker labels of two-party recordings (`diarization` feature)
+//! - [`normalize`]: written forms of spoken numbers, dates, units and Markdown cues
+//! - [`words`]: word tokenizer of the text passes (formatting, snippets, redaction)
+//! - [`subtitles`]: SRT/WebVTT export of timestamped transcripts
+//! - [`paste`]: clipboard based auto-paste into the focused application
+//! - [`host`]: directories of the embedding program (models, recordings, VAD model)
+//! - [`http`]: proxy and TLS trust settings of the HTTP clients, mockable request transport
+//!
+//! App-specific concerns (config store, keychain, events, window management) stay in the
+//! Tauri crate, which wires these building blocks together: directories come through the
+//! [`host::AppPaths`] trait, events through callbacks and settings as plain structs.
+
+pub mod audio_file;
+#[cfg(feature = "diarization")]
+pub mod diarization;
+pub mod host;
+pub mod http;
+pub mod input_monitor;
+mod level_meter;
+mod mixer;
+pub mod models;
+pub mod normalize;
+pub mod paste;
+pub mod recorder;
+pub mod subtitles;
+pub mod transcription;
+pub mod vad;
+pub mod words;
@@ -0,0 +1,106 @@
+//! Mixing of the microphone with a loopback (system audio) source.
+//!
+//! Both sources run on their own audio callbacks and clocks. Each is resampled to
+//! 16kHz mono first; the loopback samples are queued here and the microphone
+//! callback drives the output, mixing in whatever loopback audio is available.
+//! Missing loopback audio is treated as silence and the queue is bounded, so
+//! clock drift between the two devices never delays or grows the recording.
+
+use std::collections::VecDeque;
+
+/// Maximum loopback audio kept waiting for the microphone (1s at 16kHz)
+const MAX_QUEUED_SAMPLES: usize = 16000;
+
+/// Gain applied to each source so speech on both doesn't clip
+const SOURCE_GAIN: f32 = 0.7;
+
+#[derive(Debug, Default)]
+pub struct LoopbackMixer {
+    queued: VecDeque<f32>,
+}
+
+impl LoopbackMixer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue resampled mono samples from the loopback source
+    pub fn push_loopback(&mut self, samples: &[f32]) {
+        self.queued.extend(samples);
+
+        // Drop the oldest audio if the microphone fell behind
+        let overflow = self.queued.len().saturating_sub(MAX_QUEUED_SAMPLES);
+        if overflow > 0 {
+            self.queued.drain(..overflow);
+        }
+    }
+
+    /// Mix resampled mono microphone samples with the queued loopback audio
+    pub fn mix(&mut self, mic: &[f32]) -> Vec<f32> {
+        mic.iter()
+            .map(|&sample| {
+                let loopback = self.queued.pop_front().unwrap_or(0.0);
+                ((sample + loopback) * SOURCE_GAIN).clamp(-1.0, 1.0)
+            })
+            .collect()
+    }
+}
+
+/// Average the channels of a resampled block (one buffer per channel) down to mono
+pub fn downmix(channels: &[Vec<f32>]) -> Vec<f32> {
+    match channels {
+        [] => Vec::new(),
+        [mono] => mono.clone(),
+        _ => {
+            let frames = channels.iter().map(Vec::len).min().unwrap_or(0);
+            (0..frames)
+                .map(|i| {
+                    channels.iter().map(|channel| channel[i]).sum::<f32>() / channels.len() as f32
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mix_with_missing_loopback_audio() {
+        let mut mixer = LoopbackMixer::new();
+        mixer.push_loopback(&[0.5]);
+
+        let mixed = mixer.mix(&[0.5, 0.5]);
+        assert_eq!(mixed, vec![1.0 * SOURCE_GAIN, 0.5 * SOURCE_GAIN]);
+    }
+
+    #[test]
+    fn test_mix_clamps() {
+        let mut mixer = LoopbackMixer::new();
+        mixer.push_loopback(&[1.0]);
+
+        assert_eq!(mixer.mix(&[1.0]), vec![1.0]);
+    }
+
+    #[test]
+    fn test_queue_is_bounded() {
+        let mut mixer = LoopbackMixer::new();
+        mixer.push_loopback(&vec![0.1; MAX_QUEUED_SAMPLES]);
+        mixer.push_loopback(&[0.2]);
+
+        assert_eq!(mixer.queued.len(), MAX_QUEUED_SAMPLES);
+        assert_eq!(mixer.queued.back(), Some(&0.2));
+    }
+
+    #[test]
+    fn test_downmix_averages_every_channel() {
+        assert_eq!(downmix(&[vec![0.5, -0.5]]), vec![0.5, -0.5]);
+        assert_eq!(downmix(&[vec![0.2, 0.4], vec![0.4, 0.0]]), vec![0.3, 0.2]);
+        // 4-channel loopback device, e.g. a virtual device with two stereo pairs
+        assert_eq!(
+            downmix(&[vec![1.0], vec![0.0], vec![0.5], vec![0.5]]),
+            vec![0.5]
+        );
+    }
+}
@@ -0,0 +1,441 @@
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+pub use crate::transcription::ModelType;
+
+/// Individual file within a multi-file model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct ModelFile {
+    /// Filename to save as
+    pub filename: String,
+    /// Download URL
+    pub url: String,
+    /// SHA-256 checksum (hex string)
+    pub sha256: String,
+}
+
+/// Checksum of a file that isn't pinned yet (`scripts/pin-model-hashes.sh` prints the
+/// real ones). Such a file can't be verified.
+pub const UNPINNED_SHA256: &str = "TBD";
+
+impl ModelFile {
+    /// Whether the catalog has the real checksum of the file
+    pub fn is_pinned(&self) -> bool {
+        self.sha256 != UNPINNED_SHA256
+    }
+}
+
+/// Zipped Core ML encoder (`.mlmodelc`) for a Whisper model, Apple Silicon only
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct CoreMlEncoder {
+    /// Zip archive, extracted next to the ggml file
+    pub file: ModelFile,
+    /// Approximate download size in bytes
+    pub size_bytes: u64,
+}
+
+/// Expected transcription speed on Apple Silicon, relative to the other models
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub enum SpeedClass {
+    Fastest,
+    Fast,
+    Balanced,
+    Slow,
+}
+
+/// Quantization of a ggml Whisper model (smaller and faster, slightly less accurate)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub enum Quantization {
+    /// 5-bit (ggml q5_0 / q5_1)
+    Q5,
+    /// 8-bit (ggml q8_0)
+    Q8,
+}
+
+/// Static information about a model available for download.
+/// This is hardcoded and never changes at runtime.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCatalogEntry {
+    /// Unique identifier, no spaces (e.g., "whisper-small")
+    pub name: String,
+    /// Human-readable name (e.g., "Whisper Small")
+    pub display_name: String,
+    /// Description of the model
+    pub description: String,
+    /// Type of model (Whisper or Parakeet)
+    pub model_type: ModelType,
+    /// Filename on disk (e.g., "ggml-small.bin" for single-file, "parakeet-v3" for multi-file directory)
+    pub filename: String,
+    /// Actual size on disk in bytes
+    pub size_bytes: u64,
+    /// Approximate RAM usage when loaded in MB
+    pub estimated_ram_mb: u64,
+    /// Files to download (single file for Whisper, multiple files for Parakeet)
+    pub files: Vec<ModelFile>,
+    /// Expected speed, to recommend faster models on low-memory Macs
+    pub speed_class: SpeedClass,
+    /// Supported language codes (e.g. "en"), empty = all Whisper languages
+    pub languages: Vec<String>,
+    /// Quantization of the weights, `None` = full precision
+    pub quantization: Option<Quantization>,
+    /// Optional Core ML encoder (Whisper only), speeds up transcription on Apple Silicon
+    pub coreml_encoder: Option<CoreMlEncoder>,
+}
+
+/// Core ML encoder of a whisper.cpp model (e.g. "small"), shared by its quantized variants
+fn coreml_encoder(model: &str, size_bytes: u64) -> Option<CoreMlEncoder> {
+    let filename = format!("ggml-{}-encoder.mlmodelc.zip", model);
+    Some(CoreMlEncoder {
+        file: ModelFile {
+            url: format!(
+                "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{}",
+                filename
+            ),
+            filename,
+            // Not pinned yet: the encoder is executed, so it isn't offered until it is
+            sha256: UNPINNED_SHA256.into(),
+        },
+        size_bytes,
+    })
+}
+
+/// The 25 European languages of Parakeet TDT v3
+fn parakeet_v3_languages() -> Vec<String> {
+    [
+        "bg", "cs", "da", "de", "el", "en", "es", "et", "fi", "fr", "hr", "hu", "it", "lt", "lv",
+        "mt", "nl", "pl", "pt", "ro", "ru", "sk", "sl", "sv", "uk",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+/// Check if a model name exists in the catalog
+pub fn is_model_in_catalog(name: &str) -> bool {
+    catalog().iter().any(|e| e.name == name)
+}
+
+/// Catalog entry of a model by name
+pub fn find_model(name: &str) -> Option<ModelCatalogEntry> {
+    catalog().iter().find(|e| e.name == name).cloned()
+}
+
+/// Catalog of available transcription models
+pub fn get_model_catalog() -> Vec<ModelCatalogEntry> {
+    catalog().to_vec()
+}
+
+/// The catalog, built on first use and kept for the app lifetime
+fn catalog() -> &'static [ModelCatalogEntry] {
+    static CATALOG: OnceLock<Vec<ModelCatalogEntry>> = OnceLock::new();
+    CATALOG.get_or_init(build_catalog)
+}
+
+/// Hardcoded catalog of available transcription models
+/// Whisper SHA-256 hashes from: https://huggingface.co/ggerganov/whisper.cpp
+/// Parakeet models from NVIDIA via Hugging Face
+fn build_catalog() -> Vec<ModelCatalogEntry> {
+    vec![
+        // Parakeet models - multi-file downloads (25 languages, faster CPU inference)
+        ModelCatalogEntry {
+            name: "parakeet-tdt-0.6b-v3-int8".into(),
+            display_name: "Parakeet V3 INT8".into(),
+            description: "Fast multilingual model optimized for CPU (25 languages). Recommended for 8GB RAM.".into(),
+            model_type: ModelType::Parakeet,
+            filename: "parakeet-tdt-0.6b-v3-int8".into(), // Directory name
+            size_bytes: 670_479_942, // ~639 MB total
+            estimated_ram_mb: 1200,
+            files: vec![
+                ModelFile {
+                    filename: "encoder-model.int8.onnx".into(),
+                    url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/encoder-model.int8.onnx".into(),
+                    sha256: "6139d2fa7e1b086097b277c7149725edbab89cc7c7ae64b23c741be4055aff09".into(),
+                },
+                ModelFile {
+                    filename: "decoder_joint-model.int8.onnx".into(),
+                    url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/decoder_joint-model.int8.onnx".into(),
+                    sha256: "eea7483ee3d1a30375daedc8ed83e3960c91b098812127a0d99d1c8977667a70".into(),
+                },
+                ModelFile {
+                    filename: "vocab.txt".into(),
+                    url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/vocab.txt".into(),
+                    sha256: "d58544679ea4bc6ac563d1f545eb7d474bd6cfa467f0a6e2c1dc1c7d37e3c35d".into(),
+                },
+            ],
+            speed_class: SpeedClass::Fastest,
+            languages: parakeet_v3_languages(),
+            quantization: None,
+            coreml_encoder: None,
+        },
+        ModelCatalogEntry {
+            name: "parakeet-tdt-0.6b-v3-fp32".into(),
+            display_name: "Parakeet V3 FP32".into(),
+            description: "High-accuracy multilingual model (25 languages). Best quality. Recommended for 16GB+ RAM.".into(),
+            model_type: ModelType::Parakeet,
+            filename: "parakeet-tdt-0.6b-v3-fp32".into(), // Directory name
+            size_bytes: 2_549_805_858, // ~2.37 GB total
+            estimated_ram_mb: 3200,
+            files: vec![
+                ModelFile {
+                    filename: "encoder-model.onnx".into(),
+                    url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/encoder-model.onnx".into(),
+                    sha256: "98a74b21b4cc0017c1e7030319a4a96f4a9506e50f0708f3a516d02a77c96bb1".into(),
+                },
+                ModelFile {
+                    filename: "encoder-model.onnx.data".into(),
+                    url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/encoder-model.onnx.data".into(),
+                    sha256: "9a22d372c51455c34f13405da2520baefb7125bd16981397561423ed32d24f36".into(),
+                },
+                ModelFile {
+                    filename: "decoder_joint-model.onnx".into(),
+                    url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/decoder_joint-model.onnx".into(),
+                    sha256: "e978ddf6688527182c10fde2eb4b83068421648985ef23f7a86be732be8706c1".into(),
+                },
+                ModelFile {
+                    filename: "vocab.txt".into(),
+                    url: "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/vocab.txt".into(),
+                    sha256: "d58544679ea4bc6ac563d1f545eb7d474bd6cfa467f0a6e2c1dc1c7d37e3c35d".into(),
+                },
+            ],
+            speed_class: SpeedClass::Fast,
+            languages: parakeet_v3_languages(),
+            quantization: None,
+            coreml_encoder: None,
+        },
+
+        // Whisper models - single-file downloads
+        ModelCatalogEntry {
+            name: "whisper-small".into(),
+            display_name: "Whisper Small".into(),
+            description: "Fast, good for most use cases. Recommended for 8GB RAM.".into(),
+            model_type: ModelType::Whisper,
+            filename: "ggml-small.bin".into(),
+            size_bytes: 487_601_967, // ~465 MB
+            estimated_ram_mb: 800,
+            files: vec![ModelFile {
+                filename: "ggml-small.bin".into(),
+                url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin"
+                    .into(),
+                sha256: "1be3a9b2063867b937e64e2ec7483364a79917e157fa98c5d94b5c1fffea987b"
+                    .into(),
+            }],
+            speed_class: SpeedClass::Fast,
+            languages: vec![],
+            quantization: None,
+            coreml_encoder: coreml_encoder("small", 163_000_000),
+        },
+        ModelCatalogEntry {
+            name: "whisper-medium".into(),
+            display_name: "Whisper Medium".into(),
+            description: "Better accuracy, requires more RAM. Recommended for 16GB RAM.".into(),
+            model_type: ModelType::Whisper,
+            filename: "ggml-medium.bin".into(),
+            size_bytes: 1_533_763_059, // ~1.43 GB
+            estimated_ram_mb: 2200,
+            files: vec![ModelFile {
+                filename: "ggml-medium.bin".into(),
+                url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.bin"
+                    .into(),
+                sha256: "6c14d5adee5f86394037b4e4e8b59f1673b6cee10e3cf0b11bbdbee79c156208"
+                    .into(),
+            }],
+            speed_class: SpeedClass::Balanced,
+            languages: vec![],
+            quantization: None,
+            coreml_encoder: coreml_encoder("medium", 567_000_000),
+        },
+        ModelCatalogEntry {
+            name: "whisper-large-v3-turbo".into(),
+            display_name: "Whisper Large v3 Turbo".into(),
+            description: "Fast large model variant. Recommended for 16GB RAM.".into(),
+            model_type: ModelType::Whisper,
+            filename: "ggml-large-v3-turbo.bin".into(),
+            size_bytes: 1_624_555_275, // ~1.51 GB
+            estimated_ram_mb: 2500,
+            files: vec![ModelFile {
+                filename: "ggml-large-v3-turbo.bin".into(),
+                url:
+                    "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3-turbo.bin"
+                        .into(),
+                sha256: "1fc70f774d38eb169993ac391eea357ef47c88757ef72ee5943879b7e8e2bc69"
+                    .into(),
+            }],
+            speed_class: SpeedClass::Balanced,
+            languages: vec![],
+            quantization: None,
+            coreml_encoder: coreml_encoder("large-v3-turbo", 1_170_000_000),
+        },
+        ModelCatalogEntry {
+            name: "whisper-large-v3".into(),
+            display_name: "Whisper Large v3".into(),
+            description: "Best accuracy, requires significant RAM. Recommended for 16GB+ RAM."
+                .into(),
+            model_type: ModelType::Whisper,
+            filename: "ggml-large-v3.bin".into(),
+            size_bytes: 3_095_033_483, // ~2.88 GB
+            estimated_ram_mb: 4000,
+            files: vec![ModelFile {
+                filename: "ggml-large-v3.bin".into(),
+                url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3.bin"
+                    .into(),
+                sha256: "64d182b440b98d5203c4f9bd541544d84c605196c4f7b845dfa11fb23594d1e2"
+                    .into(),
+            }],
+            speed_class: SpeedClass::Slow,
+            languages: vec![],
+            quantization: None,
+            coreml_encoder: coreml_encoder("large-v3", 1_170_000_000),
+        },
+
+        // Quantized and distilled Whisper models - faster options for 8GB RAM
+        // Checksums are not pinned yet (the downloads can't be verified and
+        // `verify_model` reports them unverifiable), sizes are approximate
+        ModelCatalogEntry {
+            name: "whisper-base-q5_1".into(),
+            display_name: "Whisper Base (Q5)".into(),
+            description: "Smallest and fastest, lower accuracy. Good for short dictations on 8GB RAM."
+                .into(),
+            model_type: ModelType::Whisper,
+            filename: "ggml-base-q5_1.bin".into(),
+            size_bytes: 60_000_000, // ~57 MB
+            estimated_ram_mb: 200,
+            files: vec![ModelFile {
+                filename: "ggml-base-q5_1.bin".into(),
+                url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base-q5_1.bin"
+                    .into(),
+                sha256: UNPINNED_SHA256.into(),
+            }],
+            speed_class: SpeedClass::Fastest,
+            languages: vec![],
+            quantization: Some(Quantization::Q5),
+            coreml_encoder: coreml_encoder("base", 38_000_000),
+        },
+        ModelCatalogEntry {
+            name: "whisper-small-q5_1".into(),
+            display_name: "Whisper Small (Q5)".into(),
+            description: "Quantized Whisper Small, faster with similar accuracy. Recommended for 8GB RAM."
+                .into(),
+            model_type: ModelType::Whisper,
+            filename: "ggml-small-q5_1.bin".into(),
+            size_bytes: 190_000_000, // ~181 MB
+            estimated_ram_mb: 450,
+            files: vec![ModelFile {
+                filename: "ggml-small-q5_1.bin".into(),
+                url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small-q5_1.bin"
+                    .into(),
+                sha256: UNPINNED_SHA256.into(),
+            }],
+            speed_class: SpeedClass::Fast,
+            languages: vec![],
+            quantization: Some(Quantization::Q5),
+            coreml_encoder: coreml_encoder("small", 163_000_000),
+        },
+        ModelCatalogEntry {
+            name: "whisper-medium-q5_0".into(),
+            display_name: "Whisper Medium (Q5)".into(),
+            description: "Quantized Whisper Medium, better accuracy in a third of the RAM. Recommended for 8GB RAM."
+                .into(),
+            model_type: ModelType::Whisper,
+            filename: "ggml-medium-q5_0.bin".into(),
+            size_bytes: 539_000_000, // ~514 MB
+            estimated_ram_mb: 900,
+            files: vec![ModelFile {
+                filename: "ggml-medium-q5_0.bin".into(),
+                url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium-q5_0.bin"
+                    .into(),
+                sha256: UNPINNED_SHA256.into(),
+            }],
+            speed_class: SpeedClass::Balanced,
+            languages: vec![],
+            quantization: Some(Quantization::Q5),
+            coreml_encoder: coreml_encoder("medium", 567_000_000),
+        },
+        ModelCatalogEntry {
+            name: "whisper-large-v3-turbo-q5_0".into(),
+            display_name: "Whisper Large v3 Turbo (Q5)".into(),
+            description: "Quantized Large v3 Turbo, near large-model accuracy. Recommended for 8GB RAM."
+                .into(),
+            model_type: ModelType::Whisper,
+            filename: "ggml-large-v3-turbo-q5_0.bin".into(),
+            size_bytes: 574_000_000, // ~547 MB
+            estimated_ram_mb: 1000,
+            files: vec![ModelFile {
+                filename: "ggml-large-v3-turbo-q5_0.bin".into(),
+                url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3-turbo-q5_0.bin"
+                    .into(),
+                sha256: UNPINNED_SHA256.into(),
+            }],
+            speed_class: SpeedClass::Balanced,
+            languages: vec![],
+            quantization: Some(Quantization::Q5),
+            coreml_encoder: coreml_encoder("large-v3-turbo", 1_170_000_000),
+        },
+        ModelCatalogEntry {
+            name: "whisper-large-v3-turbo-q8_0".into(),
+            display_name: "Whisper Large v3 Turbo (Q8)".into(),
+            description: "8-bit Large v3 Turbo, closest to full precision. Recommended for 8GB+ RAM."
+                .into(),
+            model_type: ModelType::Whisper,
+            filename: "ggml-large-v3-turbo-q8_0.bin".into(),
+            size_bytes: 874_000_000, // ~834 MB
+            estimated_ram_mb: 1300,
+            files: vec![ModelFile {
+                filename: "ggml-large-v3-turbo-q8_0.bin".into(),
+                url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3-turbo-q8_0.bin"
+                    .into(),
+                sha256: UNPINNED_SHA256.into(),
+            }],
+            speed_class: SpeedClass::Balanced,
+            languages: vec![],
+            quantization: Some(Quantization::Q8),
+            coreml_encoder: coreml_encoder("large-v3-turbo", 1_170_000_000),
+        },
+        ModelCatalogEntry {
+            name: "distil-whisper-large-v3".into(),
+            display_name: "Distil-Whisper Large v3".into(),
+            description: "Distilled Large v3, about 6x faster with close accuracy. English only."
+                .into(),
+            model_type: ModelType::Whisper,
+            filename: "ggml-distil-large-v3.bin".into(),
+            size_bytes: 1_520_000_000, // ~1.42 GB
+            estimated_ram_mb: 2000,
+            files: vec![ModelFile {
+                filename: "ggml-distil-large-v3.bin".into(),
+                url: "https://huggingface.co/distil-whisper/distil-large-v3-ggml/resolve/main/ggml-distil-large-v3.bin"
+                    .into(),
+                sha256: UNPINNED_SHA256.into(),
+            }],
+            speed_class: SpeedClass::Fast,
+            languages: vec!["en".into()],
+            quantization: None,
+            coreml_encoder: None,
+        },
+    ]
+}
+
+/// Name of the speaker embedding model in download events and the models directory
+pub const SPEAKER_MODEL_NAME: &str = "speaker-embedding";
+
+/// Approximate download size of the speaker embedding model
+pub const SPEAKER_MODEL_SIZE_BYTES: u64 = 26_500_000;
+
+/// Speaker embedding model of meeting diarization (WeSpeaker ResNet34, VoxCeleb)
+pub fn speaker_model_file() -> ModelFile {
+    ModelFile {
+        filename: "voxceleb_resnet34_LM.onnx".into(),
+        url: "https://huggingface.co/Wespeaker/wespeaker-voxceleb-resnet34-LM/resolve/main/voxceleb_resnet34_LM.onnx".into(),
+        // Not pinned yet: the model is executed, so it isn't downloaded or loaded until it is
+        sha256: UNPINNED_SHA256.into(),
+    }
+}
@@ -0,0 +1,17 @@
+//! Local transcription models.
+//!
+//! - catalog: the models available for download ([`get_model_catalog`]) and the speaker
+//!   embedding model of diarization
+//! - [`ModelStore`]: where downloaded models live in a models directory
+//!
+//! Downloading, verification and loading into memory stay with the caller.
+
+mod catalog;
+mod store;
+
+pub use catalog::{
+    find_model, get_model_catalog, is_model_in_catalog, speaker_model_file, CoreMlEncoder,
+    ModelCatalogEntry, ModelFile, ModelType, Quantization, SpeedClass, SPEAKER_MODEL_NAME,
+    SPEAKER_MODEL_SIZE_BYTES, UNPINNED_SHA256,
+};
+pub use store::ModelStore;
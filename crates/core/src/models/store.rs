@@ -0,0 +1,248 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::catalog::{speaker_model_file, ModelCatalogEntry, SPEAKER_MODEL_NAME};
+use crate::host::AppPaths;
+use crate::transcription::coreml_encoder_path;
+
+/// Layout of downloaded models in a models directory.
+///
+/// New structure: `models_dir/{model.name}/{file.filename}`
+/// Old structure: `models_dir/{model.filename}` (single-file) or
+/// `models_dir/{model.filename}/` (multi-file), still found until it is migrated.
+///
+/// Only resolves paths, downloading is up to the caller.
+#[derive(Debug, Clone)]
+pub struct ModelStore {
+    models_dir: PathBuf,
+}
+
+impl ModelStore {
+    pub fn new(models_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            models_dir: models_dir.into(),
+        }
+    }
+
+    /// Layout of the models directory of the embedding program
+    pub fn for_app(paths: &impl AppPaths) -> Self {
+        Self::new(paths.models_dir())
+    }
+
+    pub fn models_dir(&self) -> &Path {
+        &self.models_dir
+    }
+
+    /// Directory of a model, trying the new structure first then falling back to the old.
+    ///
+    /// For an old single-file model this is the models directory itself. Without any
+    /// download, the new structure path (where a download goes).
+    pub fn model_dir(&self, entry: &ModelCatalogEntry) -> PathBuf {
+        let new_dir = self.models_dir.join(&entry.name);
+
+        if new_dir.exists() {
+            return new_dir;
+        }
+
+        if entry.files.len() == 1 {
+            // Old single-file: models_dir/ggml-small.bin
+            if self.models_dir.join(&entry.filename).exists() {
+                return self.models_dir.clone();
+            }
+        } else {
+            // Old multi-file: models_dir/parakeet-tdt-0.6b-v3-int8/
+            let old_dir = self.models_dir.join(&entry.filename);
+            if old_dir.exists() {
+                return old_dir;
+            }
+        }
+
+        new_dir
+    }
+
+    /// Path of the (first) model file, where whisper.cpp looks for the Core ML encoder
+    pub fn model_file_path(&self, entry: &ModelCatalogEntry) -> PathBuf {
+        self.model_dir(entry).join(&entry.files[0].filename)
+    }
+
+    /// Path that `LocalClient::new` expects: the .bin file of a single-file model
+    /// (Whisper), the directory of a multi-file model (Parakeet).
+    ///
+    /// Without any download, the new structure path (for error messages).
+    pub fn client_path(&self, entry: &ModelCatalogEntry) -> PathBuf {
+        let new_dir = self.models_dir.join(&entry.name);
+
+        if entry.files.len() == 1 {
+            let new_file = new_dir.join(&entry.files[0].filename);
+            if new_file.exists() {
+                return new_file;
+            }
+            let old_file = self.models_dir.join(&entry.filename);
+            if old_file.exists() {
+                return old_file;
+            }
+            new_file
+        } else {
+            if new_dir.is_dir() {
+                return new_dir;
+            }
+            // Might be the same as the new structure for current Parakeet models
+            let old_dir = self.models_dir.join(&entry.filename);
+            if old_dir.is_dir() {
+                return old_dir;
+            }
+            new_dir
+        }
+    }
+
+    /// Whether all files of a model are on disk, in the new or the old structure
+    pub fn is_downloaded(&self, entry: &ModelCatalogEntry) -> bool {
+        let has_files =
+            |dir: &Path| dir.is_dir() && entry.files.iter().all(|f| dir.join(&f.filename).exists());
+
+        if has_files(&self.models_dir.join(&entry.name)) {
+            return true;
+        }
+        if entry.files.len() == 1 {
+            self.models_dir.join(&entry.filename).exists()
+        } else {
+            has_files(&self.models_dir.join(&entry.filename))
+        }
+    }
+
+    /// Bytes of interrupted downloads (`.partial` files) of a model
+    pub fn partial_bytes(&self, entry: &ModelCatalogEntry) -> u64 {
+        let new_dir = self.models_dir.join(&entry.name);
+        let size = |path: PathBuf| path.metadata().map(|m| m.len()).unwrap_or(0);
+
+        entry
+            .files
+            .iter()
+            .map(|file| {
+                let new_partial = new_dir.join(format!("{}.partial", file.filename));
+                if new_partial.exists() {
+                    size(new_partial)
+                } else if entry.files.len() == 1 {
+                    // Old location of single-file models
+                    size(self.models_dir.join(format!("{}.partial", entry.filename)))
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+
+    /// Bytes on disk of a model in the new and the old structure, including
+    /// interrupted downloads and the Core ML encoder
+    pub fn disk_bytes(&self, entry: &ModelCatalogEntry) -> u64 {
+        let new_dir = self.models_dir.join(&entry.name);
+        let mut bytes = disk_bytes(&new_dir);
+
+        if entry.files.len() == 1 {
+            let old_file = self.models_dir.join(&entry.filename);
+            bytes += disk_bytes(&old_file)
+                + disk_bytes(&self.models_dir.join(format!("{}.partial", entry.filename)))
+                + disk_bytes(&coreml_encoder_path(&old_file));
+        } else {
+            let old_dir = self.models_dir.join(&entry.filename);
+            if old_dir != new_dir {
+                bytes += disk_bytes(&old_dir);
+            }
+        }
+
+        bytes
+    }
+
+    /// Path of the speaker embedding model (meeting diarization)
+    pub fn speaker_model_path(&self) -> PathBuf {
+        self.models_dir
+            .join(SPEAKER_MODEL_NAME)
+            .join(speaker_model_file().filename)
+    }
+
+    /// Bytes on disk of the speaker embedding model, including an interrupted download
+    pub fn speaker_model_disk_bytes(&self) -> u64 {
+        disk_bytes(&self.models_dir.join(SPEAKER_MODEL_NAME))
+    }
+}
+
+/// Size of a file or everything in a directory, 0 if it doesn't exist
+fn disk_bytes(path: &Path) -> u64 {
+    let Ok(metadata) = path.symlink_metadata() else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| disk_bytes(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{find_model, get_model_catalog};
+    use std::fs;
+
+    fn temp_store(name: &str) -> ModelStore {
+        let dir = std::env::temp_dir().join(format!(
+            "dictara-model-store-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        ModelStore::new(dir)
+    }
+
+    #[test]
+    fn test_new_and_old_structure() {
+        let store = temp_store("layout");
+        let entry = find_model("whisper-small").unwrap();
+        let new_file = store.models_dir().join(&entry.name).join(&entry.filename);
+
+        // Not downloaded: the new structure, where a download goes
+        assert!(!store.is_downloaded(&entry));
+        assert_eq!(store.client_path(&entry), new_file);
+
+        // Old single-file location
+        let old_file = store.models_dir().join(&entry.filename);
+        fs::write(&old_file, b"ggml").unwrap();
+        assert!(store.is_downloaded(&entry));
+        assert_eq!(store.client_path(&entry), old_file);
+        assert_eq!(store.model_file_path(&entry), old_file);
+
+        // The new structure wins once migrated
+        fs::create_dir_all(new_file.parent().unwrap()).unwrap();
+        fs::rename(&old_file, &new_file).unwrap();
+        assert!(store.is_downloaded(&entry));
+        assert_eq!(store.client_path(&entry), new_file);
+
+        fs::remove_dir_all(store.models_dir()).unwrap();
+    }
+
+    #[test]
+    fn test_partial_multi_file_download() {
+        let store = temp_store("partial");
+        let entry = get_model_catalog()
+            .into_iter()
+            .find(|e| e.files.len() > 1)
+            .unwrap();
+        let dir = store.models_dir().join(&entry.name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(&entry.files[0].filename), b"done").unwrap();
+        fs::write(
+            dir.join(format!("{}.partial", entry.files[1].filename)),
+            b"half",
+        )
+        .unwrap();
+
+        assert!(!store.is_downloaded(&entry));
+        assert_eq!(store.partial_bytes(&entry), 4);
+        assert_eq!(store.disk_bytes(&entry), 8);
+        assert_eq!(store.client_path(&entry), dir);
+
+        fs::remove_dir_all(store.models_dir()).unwrap();
+    }
+}
@@ -0,0 +1,195 @@
+//! German: "dreiundzwanzigster März" → "23. März", "drei komma fünf Megabyte" → "3,5 MB"
+//!
+//! German writes numbers below a million as one word ("zweitausendvierhundert"), so
+//! numbers are parsed from a single word.
+
+use super::{Cue, Rules, Word};
+
+pub(super) static RULES: Rules = Rules {
+    parse_number,
+    parse_digit,
+    parse_date,
+    decimal_word: "komma",
+    decimal_separator: ',',
+    // Duden: numbers up to twelve are spelled out
+    min_standalone: 13,
+    units: &[
+        ("prozent", "%"),
+        ("kilobyte", "KB"),
+        ("megabyte", "MB"),
+        ("gigabyte", "GB"),
+        ("terabyte", "TB"),
+        ("kilometer", "km"),
+        ("kilogramm", "kg"),
+        ("millisekunde", "ms"),
+        ("millisekunden", "ms"),
+    ],
+    space_before_percent: true,
+    markdown_cues: &[
+        ("aufzählungspunkt", Cue::Bullet),
+        ("nummerierter punkt", Cue::NumberedItem),
+        ("überschrift eins", Cue::Heading(1)),
+        ("überschrift zwei", Cue::Heading(2)),
+        ("überschrift drei", Cue::Heading(3)),
+        ("codeblock anfang", Cue::CodeBlockStart),
+        ("codeblock ende", Cue::CodeBlockEnd),
+        ("neuer absatz", Cue::Paragraph),
+    ],
+};
+
+/// Zero to nineteen, standalone forms
+const ONES: [&str; 20] = [
+    "null",
+    "eins",
+    "zwei",
+    "drei",
+    "vier",
+    "fünf",
+    "sechs",
+    "sieben",
+    "acht",
+    "neun",
+    "zehn",
+    "elf",
+    "zwölf",
+    "dreizehn",
+    "vierzehn",
+    "fünfzehn",
+    "sechzehn",
+    "siebzehn",
+    "achtzehn",
+    "neunzehn",
+];
+
+/// Multiples of ten from twenty
+const TENS: [&str; 8] = [
+    "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig", "neunzig",
+];
+
+const MONTHS: [&str; 12] = [
+    "Januar",
+    "Februar",
+    "März",
+    "April",
+    "Mai",
+    "Juni",
+    "Juli",
+    "August",
+    "September",
+    "Oktober",
+    "November",
+    "Dezember",
+];
+
+/// Declension endings of ordinals ("dritte", "dritten", "dritter", ...)
+const ORDINAL_ENDINGS: [&str; 5] = ["e", "en", "er", "es", "em"];
+
+/// One to nine as the first part of a compound ("einundzwanzig", "einhundert")
+fn compound_ones(word: &str) -> Option<u64> {
+    match word {
+        "ein" => Some(1),
+        _ => ONES[1..10]
+            .iter()
+            .position(|w| *w == word)
+            .map(|i| i as u64 + 1),
+    }
+}
+
+fn below_hundred(word: &str) -> Option<u64> {
+    if let Some(value) = ONES.iter().position(|w| *w == word) {
+        return Some(value as u64);
+    }
+    let tens = |word: &str| {
+        TENS.iter()
+            .position(|w| *w == word)
+            .map(|i| 20 + 10 * i as u64)
+    };
+    match word.split_once("und") {
+        Some((ones, rest)) => Some(compound_ones(ones)? + tens(rest)?),
+        None => tens(word),
+    }
+}
+
+fn below_thousand(word: &str) -> Option<u64> {
+    let Some((hundreds, rest)) = word.split_once("hundert") else {
+        return below_hundred(word);
+    };
+    let hundreds = match hundreds {
+        "" => 1,
+        ones => compound_ones(ones)?,
+    };
+    let rest = match rest {
+        "" => 0,
+        rest => below_hundred(rest).filter(|value| *value > 0)?,
+    };
+    Some(hundreds * 100 + rest)
+}
+
+fn number(word: &str) -> Option<u64> {
+    let Some((thousands, rest)) = word.split_once("tausend") else {
+        return below_thousand(word);
+    };
+    let thousands = match thousands {
+        "" => 1,
+        "ein" => 1,
+        thousands => below_thousand(thousands).filter(|value| *value > 0)?,
+    };
+    let rest = match rest {
+        "" => 0,
+        rest => below_thousand(rest).filter(|value| *value > 0)?,
+    };
+    Some(thousands * 1_000 + rest)
+}
+
+fn parse_number(words: &[Word]) -> Option<(u64, usize)> {
+    number(&words.first()?.text).map(|value| (value, 1))
+}
+
+fn parse_digit(word: &str) -> Option<u8> {
+    ONES[..10].iter().position(|w| *w == word).map(|d| d as u8)
+}
+
+/// Cardinal of a day of the month, as ordinals are built from it
+fn day_cardinal(day: u32) -> String {
+    match day {
+        0..=19 => ONES[day as usize].to_string(),
+        _ => {
+            let tens = TENS[(day / 10 - 2) as usize];
+            match day % 10 {
+                0 => tens.to_string(),
+                1 => format!("einund{}", tens),
+                ones => format!("{}und{}", ONES[ones as usize], tens),
+            }
+        }
+    }
+}
+
+/// Day of the month spelled as an ordinal: "dritte", "dreiundzwanzigsten"
+fn ordinal(word: &str) -> Option<u32> {
+    (1..=31).find(|&day| {
+        let stem = match day {
+            1 => "ers".to_string(),
+            3 => "drit".to_string(),
+            7 => "sieb".to_string(),
+            8 => "ach".to_string(),
+            _ => day_cardinal(day),
+        };
+        let stem = if day < 20 {
+            format!("{}t", stem)
+        } else {
+            format!("{}st", stem)
+        };
+        word.strip_prefix(stem.as_str())
+            .is_some_and(|ending| ORDINAL_ENDINGS.contains(&ending))
+    })
+}
+
+/// "dreiundzwanzigster März", written "23. März"
+fn parse_date(words: &[Word]) -> Option<(String, usize)> {
+    let day = ordinal(&words.first()?.text)?;
+    let month = words.get(1)?;
+    let month = MONTHS
+        .iter()
+        .find(|name| name.to_lowercase() == month.text)?;
+    Some((format!("{}. {}", day, month), 2))
+}
@@ -0,0 +1,267 @@
+//! English: "twenty third of march" → "March 23", "three point five megabytes" → "3.5 MB"
+
+use super::{digit_ordinal, Cue, Rules, Word};
+
+pub(super) static RULES: Rules = Rules {
+    parse_number,
+    parse_digit,
+    parse_date,
+    decimal_word: "point",
+    decimal_separator: '.',
+    min_standalone: 10,
+    units: &[
+        ("percent", "%"),
+        ("kilobyte", "KB"),
+        ("kilobytes", "KB"),
+        ("megabyte", "MB"),
+        ("megabytes", "MB"),
+        ("gigabyte", "GB"),
+        ("gigabytes", "GB"),
+        ("terabyte", "TB"),
+        ("terabytes", "TB"),
+        ("kilometer", "km"),
+        ("kilometers", "km"),
+        ("kilometre", "km"),
+        ("kilometres", "km"),
+        ("kilogram", "kg"),
+        ("kilograms", "kg"),
+        ("millisecond", "ms"),
+        ("milliseconds", "ms"),
+    ],
+    space_before_percent: false,
+    markdown_cues: &[
+        ("bullet point", Cue::Bullet),
+        ("numbered item", Cue::NumberedItem),
+        ("heading one", Cue::Heading(1)),
+        ("heading two", Cue::Heading(2)),
+        ("heading three", Cue::Heading(3)),
+        ("code block start", Cue::CodeBlockStart),
+        ("start code block", Cue::CodeBlockStart),
+        ("code block end", Cue::CodeBlockEnd),
+        ("end code block", Cue::CodeBlockEnd),
+        ("new paragraph", Cue::Paragraph),
+    ],
+};
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+/// Multiples of ten from twenty
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const ORDINALS: [&str; 19] = [
+    "first",
+    "second",
+    "third",
+    "fourth",
+    "fifth",
+    "sixth",
+    "seventh",
+    "eighth",
+    "ninth",
+    "tenth",
+    "eleventh",
+    "twelfth",
+    "thirteenth",
+    "fourteenth",
+    "fifteenth",
+    "sixteenth",
+    "seventeenth",
+    "eighteenth",
+    "nineteenth",
+];
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const SCALES: [(&str, u64); 3] = [
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+];
+
+/// Previous word of a number, deciding which words may follow
+#[derive(Clone, Copy, PartialEq)]
+enum Last {
+    None,
+    Ones,
+    Teen,
+    Tens,
+    Hundred,
+    Scale,
+}
+
+/// Number below 100 written as one word: "seven", "fifteen", "forty", "forty-two"
+fn small_number(word: &str) -> Option<(u64, Last)> {
+    if let Some(value) = ONES.iter().position(|w| *w == word) {
+        let last = if value < 10 { Last::Ones } else { Last::Teen };
+        return Some((value as u64, last));
+    }
+    if let Some(tens) = TENS.iter().position(|w| *w == word) {
+        return Some((20 + 10 * tens as u64, Last::Tens));
+    }
+    let (tens, ones) = word.split_once('-')?;
+    let tens = TENS.iter().position(|w| *w == tens)?;
+    let ones = ONES[1..10].iter().position(|w| *w == ones)?;
+    Some((20 + 10 * tens as u64 + 1 + ones as u64, Last::Teen))
+}
+
+fn parse_number(words: &[Word]) -> Option<(u64, usize)> {
+    let mut total = 0;
+    let mut current = 0;
+    let mut last = Last::None;
+    let mut used = 0;
+
+    while let Some(word) = words.get(used) {
+        let word = word.text.as_str();
+        if word == "zero" {
+            if last == Last::None {
+                return Some((0, 1));
+            }
+            break;
+        }
+
+        if let Some((value, kind)) = small_number(word) {
+            let follows = match kind {
+                Last::Ones => matches!(last, Last::None | Last::Tens | Last::Hundred | Last::Scale),
+                _ => matches!(last, Last::None | Last::Hundred | Last::Scale),
+            };
+            if !follows {
+                break;
+            }
+            current += value;
+            last = kind;
+        } else if word == "hundred" {
+            // "five hundred", "nineteen hundred"
+            if !matches!(last, Last::Ones | Last::Teen) || current >= 100 {
+                break;
+            }
+            current *= 100;
+            last = Last::Hundred;
+        } else if let Some(&(_, scale)) = SCALES.iter().find(|(w, _)| *w == word) {
+            let smaller_than_previous = match last {
+                Last::Scale | Last::None => false,
+                _ => total == 0 || total % (scale * 1_000) == 0,
+            };
+            if !smaller_than_previous {
+                break;
+            }
+            total += current * scale;
+            current = 0;
+            last = Last::Scale;
+        } else if word == "and" && matches!(last, Last::Hundred | Last::Scale) {
+            // "one hundred and five", but not "one hundred and counting"
+            let continues = words
+                .get(used + 1)
+                .and_then(|next| small_number(&next.text))
+                .is_some_and(|(value, _)| value > 0);
+            if !continues {
+                break;
+            }
+        } else {
+            break;
+        }
+        used += 1;
+    }
+
+    (used > 0).then_some((total + current, used))
+}
+
+fn parse_digit(word: &str) -> Option<u8> {
+    ONES[..10].iter().position(|w| *w == word).map(|d| d as u8)
+}
+
+/// Day of the month spelled as an ordinal: "third", "twenty third", "twenty-third", "23rd"
+fn parse_ordinal(words: &[Word]) -> Option<(u32, usize)> {
+    let first = &words.first()?.text;
+    let ordinal = |word: &str| {
+        ORDINALS
+            .iter()
+            .position(|w| *w == word)
+            .map(|i| i as u32 + 1)
+    };
+    let tens = |word: &str| match word {
+        "twenty" => Some(20),
+        "thirty" => Some(30),
+        _ => None,
+    };
+
+    if let Some(day) = ordinal(first).or_else(|| digit_ordinal(first, &["st", "nd", "rd", "th"])) {
+        return Some((day, 1));
+    }
+    match first.as_str() {
+        "twentieth" => return Some((20, 1)),
+        "thirtieth" => return Some((30, 1)),
+        _ => {}
+    }
+    if let Some((t, o)) = first.split_once('-') {
+        let day = tens(t)? + ordinal(o).filter(|o| *o < 10)?;
+        return (day <= 31).then_some((day, 1));
+    }
+    let day = tens(first)? + ordinal(&words.get(1)?.text).filter(|o| *o < 10)?;
+    (day <= 31).then_some((day, 2))
+}
+
+fn month(word: &Word) -> Option<&'static str> {
+    MONTHS
+        .iter()
+        .find(|month| month.eq_ignore_ascii_case(&word.text))
+        .copied()
+}
+
+/// "[the] twenty third of march" or "March [the] twenty third", written "March 23"
+fn parse_date(words: &[Word]) -> Option<(String, usize)> {
+    let skip_the = |at: usize| usize::from(words.get(at).is_some_and(|w| w.text == "the"));
+
+    // "May" as a verb is far more common than the month, so month-first needs the
+    // capital the transcriber gives month names
+    if let Some(month) = words.first().filter(|w| w.capitalized).and_then(month) {
+        let at = 1 + skip_the(1);
+        if let Some((day, used)) = parse_ordinal(&words[at.min(words.len())..]) {
+            return Some((format!("{} {}", month, day), at + used));
+        }
+    }
+
+    let at = skip_the(0);
+    let (day, used) = parse_ordinal(&words[at..])?;
+    let of = at + used;
+    if words.get(of)?.text != "of" {
+        return None;
+    }
+    let month = month(words.get(of + 1)?)?;
+    Some((format!("{} {}", month, day), of + 2))
+}
@@ -0,0 +1,141 @@
+//! Spoken formatting cues ("bullet point", "heading two", "code block start") written as
+//! Markdown, for dictating into editors.
+
+use super::{rules_for, words, Word};
+
+/// Formatting a spoken cue stands for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Cue {
+    Bullet,
+    NumberedItem,
+    /// Heading of the level, 1 to 3
+    Heading(usize),
+    CodeBlockStart,
+    CodeBlockEnd,
+    Paragraph,
+}
+
+impl Cue {
+    /// Separator before the cue's syntax (unless it starts the text), its syntax, and the
+    /// separator before the text that follows it
+    fn markdown(self) -> (&'static str, String, &'static str) {
+        match self {
+            Cue::Bullet => ("\n", "- ".to_string(), ""),
+            Cue::NumberedItem => ("\n", "1. ".to_string(), ""),
+            Cue::Heading(level) => ("\n\n", format!("{} ", "#".repeat(level)), ""),
+            Cue::CodeBlockStart => ("\n\n", "```".to_string(), "\n"),
+            Cue::CodeBlockEnd => ("\n", "```".to_string(), "\n\n"),
+            Cue::Paragraph => ("\n\n", String::new(), ""),
+        }
+    }
+}
+
+/// Write the spoken formatting cues of `text` in `language` as Markdown. Languages
+/// without rules are left unchanged.
+pub fn apply_markdown_cues(text: &str, language: &str) -> String {
+    let Some(rules) = rules_for(language) else {
+        return text.to_string();
+    };
+    let mut cues: Vec<(Vec<&str>, Cue)> = rules
+        .markdown_cues
+        .iter()
+        .map(|(phrase, cue)| (phrase.split(' ').collect(), *cue))
+        .collect();
+    cues.sort_by_key(|(phrase, _)| std::cmp::Reverse(phrase.len()));
+
+    let words = words(text);
+    let mut output = String::with_capacity(text.len());
+    let mut copied_to = 0;
+    let mut heading = false;
+    let mut i = 0;
+    while i < words.len() {
+        let Some((phrase, cue)) = cues
+            .iter()
+            .find(|(phrase, _)| starts_with(&words[i..], phrase))
+        else {
+            i += 1;
+            continue;
+        };
+
+        // The line ends at the cue, without the comma or heading period the transcriber added
+        output.push_str(&text[copied_to..words[i].start]);
+        close_line(&mut output, heading);
+
+        let (before, syntax, after) = cue.markdown();
+        if !output.is_empty() {
+            output.push_str(before);
+        }
+        output.push_str(&syntax);
+        output.push_str(after);
+        heading = matches!(cue, Cue::Heading(_));
+
+        let cue_end = words[i + phrase.len() - 1].end;
+        let rest = &text[cue_end..];
+        let skipped = rest.len()
+            - rest
+                .trim_start_matches(|c: char| {
+                    c.is_whitespace() || matches!(c, ',' | '.' | ':' | ';')
+                })
+                .len();
+        copied_to = cue_end + skipped;
+        i += phrase.len();
+    }
+
+    let rest = &text[copied_to..];
+    if rest.trim().is_empty() {
+        close_line(&mut output, heading);
+        output.truncate(output.trim_end().len());
+    } else {
+        output.push_str(rest);
+    }
+    output
+}
+
+fn starts_with(words: &[Word], phrase: &[&str]) -> bool {
+    words.len() >= phrase.len()
+        && phrase
+            .iter()
+            .zip(words)
+            .all(|(expected, word)| *expected == word.text)
+}
+
+/// Trim the end of the current line of `output`
+fn close_line(output: &mut String, heading: bool) {
+    let trimmed = output.trim_end_matches(|c: char| {
+        (c.is_whitespace() && c != '\n') || c == ',' || (heading && c == '.')
+    });
+    output.truncate(trimmed.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_cues() {
+        let en = |text| apply_markdown_cues(text, "en");
+        assert_eq!(
+            en("Heading two, Groceries. Bullet point milk, bullet point eggs."),
+            "## Groceries\n- milk\n- eggs."
+        );
+        assert_eq!(
+            en("Run this: code block start cargo test code block end then check the output"),
+            "Run this:\n\n```\ncargo test\n```\n\nthen check the output"
+        );
+        assert_eq!(
+            en("First point. New paragraph. Second point."),
+            "First point.\n\nSecond point."
+        );
+        // Whole words only
+        assert_eq!(en("the bullet pointed north"), "the bullet pointed north");
+    }
+
+    #[test]
+    fn test_german_markdown_cues() {
+        assert_eq!(
+            apply_markdown_cues("Überschrift eins Einkauf Aufzählungspunkt Milch", "de"),
+            "# Einkauf\n- Milch"
+        );
+        assert_eq!(apply_markdown_cues("heading one", "fr"), "heading one");
+    }
+}
@@ -0,0 +1,253 @@
+//! Deterministic formatting of spoken numbers, dates and units.
+//!
+//! Converts what the recognizer writes out as spoken ("twenty third of march",
+//! "three point five megabytes") into written conventions ("March 23", "3.5 MB"),
+//! with per-locale rules (see [`en`] and [`de`]). Languages without rules are left
+//! unchanged. Unlike LLM post-processing this never rewords anything else.
+//!
+//! Small standalone numbers stay spelled out ("two kids"), as do ambiguous runs of
+//! number words like years or times ("twenty twenty four", "nine thirty").
+//!
+//! [`apply_markdown_cues`] is a separate pass writing spoken formatting cues ("bullet
+//! point", "heading two") as Markdown, for dictating into editors.
+
+mod de;
+mod en;
+mod markdown;
+
+pub use markdown::apply_markdown_cues;
+use markdown::Cue;
+
+/// A word of the text (see [`crate::words`]), lowercased for matching
+struct Word {
+    start: usize,
+    end: usize,
+    /// Lowercase letters, digits and inner hyphens ("twenty-three")
+    text: String,
+    /// Starts with an uppercase letter in the original text
+    capitalized: bool,
+}
+
+/// Spoken-form conventions of a language
+struct Rules {
+    /// Integer spelled out at the start of the words, with the number of words used
+    parse_number: fn(&[Word]) -> Option<(u64, usize)>,
+    /// Single digit word ("five")
+    parse_digit: fn(&str) -> Option<u8>,
+    /// Day and month at the start of the words, written out, with the number of words used
+    parse_date: fn(&[Word]) -> Option<(String, usize)>,
+    /// Word between the integer and the decimals ("point")
+    decimal_word: &'static str,
+    decimal_separator: char,
+    /// Numbers below this are kept spelled out, unless followed by decimals or a unit
+    min_standalone: u64,
+    /// Spoken unit and its symbol, "%" for percent
+    units: &'static [(&'static str, &'static str)],
+    /// "50 %" instead of "50%"
+    space_before_percent: bool,
+    /// Spoken formatting cue, as lowercase words separated by spaces, and its formatting
+    markdown_cues: &'static [(&'static str, Cue)],
+}
+
+/// What to do with the words at a position
+struct Match {
+    /// Written form, `None` to keep the words as spoken
+    replacement: Option<String>,
+    /// Number of words covered
+    words: usize,
+}
+
+/// Whether [`normalize`] and [`apply_markdown_cues`] have rules for `language` (ISO-639-1 code, e.g. "en")
+pub fn supports_language(language: &str) -> bool {
+    rules_for(language).is_some()
+}
+
+/// Write spoken numbers, dates and units of `text` in `language` in written form
+pub fn normalize(text: &str, language: &str) -> String {
+    let Some(rules) = rules_for(language) else {
+        return text.to_string();
+    };
+
+    let words = words(text);
+    let mut output = String::with_capacity(text.len());
+    let mut copied_to = 0;
+    let mut i = 0;
+    while i < words.len() {
+        let run = &words[i..i + contiguous_words(text, &words[i..])];
+        match match_at(rules, run) {
+            Some(Match {
+                replacement: Some(replacement),
+                words: used,
+            }) => {
+                output.push_str(&text[copied_to..words[i].start]);
+                output.push_str(&replacement);
+                copied_to = words[i + used - 1].end;
+                i += used;
+            }
+            Some(Match { words: used, .. }) => i += used,
+            None => i += 1,
+        }
+    }
+    output.push_str(&text[copied_to..]);
+    output
+}
+
+fn rules_for(language: &str) -> Option<&'static Rules> {
+    let language = language.split(['-', '_']).next().unwrap_or_default();
+    match language.to_lowercase().as_str() {
+        "en" => Some(&en::RULES),
+        "de" => Some(&de::RULES),
+        _ => None,
+    }
+}
+
+fn words(text: &str) -> Vec<Word> {
+    crate::words::word_ranges(text)
+        .into_iter()
+        .map(|range| {
+            let word = &text[range.clone()];
+            Word {
+                start: range.start,
+                end: range.end,
+                text: word
+                    .chars()
+                    .filter(|c| c.is_alphanumeric() || *c == '-')
+                    .flat_map(char::to_lowercase)
+                    .collect(),
+                capitalized: word.starts_with(char::is_uppercase),
+            }
+        })
+        .collect()
+}
+
+/// Number of words at the start of `words` separated by whitespace only (a phrase
+/// doesn't span punctuation)
+fn contiguous_words(text: &str, words: &[Word]) -> usize {
+    1 + words
+        .windows(2)
+        .take_while(|pair| text[pair[0].end..pair[1].start].trim().is_empty())
+        .count()
+}
+
+fn match_at(rules: &Rules, words: &[Word]) -> Option<Match> {
+    if let Some((date, used)) = (rules.parse_date)(words) {
+        return Some(Match {
+            replacement: Some(date),
+            words: used,
+        });
+    }
+
+    let (value, mut used) = (rules.parse_number)(words)?;
+    let mut number = value.to_string();
+    let mut has_decimals = false;
+    if words
+        .get(used)
+        .is_some_and(|w| w.text == rules.decimal_word)
+    {
+        let decimals: String = words[used + 1..]
+            .iter()
+            .map_while(|w| (rules.parse_digit)(&w.text))
+            .map(|digit| char::from(b'0' + digit))
+            .collect();
+        if !decimals.is_empty() {
+            number = format!("{}{}{}", number, rules.decimal_separator, decimals);
+            used += 1 + decimals.len();
+            has_decimals = true;
+        }
+    }
+
+    let unit = words.get(used).and_then(|w| {
+        rules
+            .units
+            .iter()
+            .find(|(spoken, _)| *spoken == w.text)
+            .map(|(_, symbol)| *symbol)
+    });
+    if let Some(symbol) = unit {
+        let written = if symbol == "%" && !rules.space_before_percent {
+            format!("{}%", number)
+        } else {
+            format!("{} {}", number, symbol)
+        };
+        return Some(Match {
+            replacement: Some(written),
+            words: used + 1,
+        });
+    }
+
+    // Consecutive numbers are years, times or lists: keep the whole run as spoken
+    if !has_decimals && (rules.parse_number)(&words[used..]).is_some() {
+        while let Some((_, more)) = (rules.parse_number)(&words[used..]) {
+            used += more;
+        }
+        return Some(Match {
+            replacement: None,
+            words: used,
+        });
+    }
+
+    let replacement = (has_decimals || value >= rules.min_standalone).then_some(number);
+    Some(Match {
+        replacement,
+        words: used,
+    })
+}
+
+/// Day of a digit ordinal ("23rd", "1st"), 1 to 31
+fn digit_ordinal(word: &str, suffixes: &[&str]) -> Option<u32> {
+    let digits = suffixes
+        .iter()
+        .find_map(|suffix| word.strip_suffix(suffix))?;
+    let day: u32 = digits.parse().ok()?;
+    (1..=31).contains(&day).then_some(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english() {
+        let en = |text| normalize(text, "en");
+        assert_eq!(
+            en("Let's meet on the twenty third of march."),
+            "Let's meet on March 23."
+        );
+        assert_eq!(en("Due March thirty-first"), "Due March 31");
+        assert_eq!(
+            en("The file is three point five megabytes, about forty percent."),
+            "The file is 3.5 MB, about 40%."
+        );
+        assert_eq!(en("one hundred and twenty five people"), "125 people");
+        // Small numbers, years and times stay spelled out
+        assert_eq!(en("I have two kids"), "I have two kids");
+        assert_eq!(
+            en("back in twenty twenty four"),
+            "back in twenty twenty four"
+        );
+        assert_eq!(en("at nine thirty"), "at nine thirty");
+        // "may" the verb is not a month
+        assert_eq!(en("you may first ask"), "you may first ask");
+    }
+
+    #[test]
+    fn test_german() {
+        let de = |text| normalize(text, "de");
+        assert_eq!(
+            de("Wir treffen uns am dreiundzwanzigsten März."),
+            "Wir treffen uns am 23. März."
+        );
+        assert_eq!(
+            de("Die Datei hat drei komma fünf Megabyte und fünfzig Prozent."),
+            "Die Datei hat 3,5 MB und 50 %."
+        );
+        assert_eq!(de("zweitausendvierhundert Leute"), "2400 Leute");
+        assert_eq!(de("Ich habe zwei Kinder"), "Ich habe zwei Kinder");
+    }
+
+    #[test]
+    fn test_unsupported_language_unchanged() {
+        assert!(!supports_language("fr"));
+        assert_eq!(normalize("vingt-trois mars", "fr"), "vingt-trois mars");
+    }
+}
@@ -0,0 +1,95 @@
+//! Audio on the clipboard: a copied audio file, its path copied as text, or
+//! (macOS) the audio data itself, e.g. dragged from Messages or Voice Memos.
+
+use std::path::{Path, PathBuf};
+
+use crate::audio_file::is_supported_audio_file;
+
+/// Audio found on the clipboard, see [`clipboard_audio`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardAudio {
+    /// An existing audio file
+    File(PathBuf),
+    /// Encoded audio with the file extension of its format (e.g. "m4a")
+    Data {
+        bytes: Vec<u8>,
+        extension: &'static str,
+    },
+}
+
+/// Audio on the clipboard, `None` if there is none in a supported format
+/// (see [`crate::audio_file::SUPPORTED_EXTENSIONS`])
+pub fn clipboard_audio() -> Option<ClipboardAudio> {
+    #[cfg(target_os = "macos")]
+    {
+        super::macos::clipboard_audio()
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        clipboard_text().and_then(|text| audio_file_path(&text).map(ClipboardAudio::File))
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn clipboard_text() -> Option<String> {
+    if let Ok(Some(wayland)) = super::linux::wayland() {
+        return wayland.get_text().ok();
+    }
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .ok()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn clipboard_text() -> Option<String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .ok()
+}
+
+/// Existing audio file at `text`, an absolute path or a `file://` URL
+pub(super) fn audio_file_path(text: &str) -> Option<PathBuf> {
+    let text = text.trim();
+    let path = if text.starts_with("file://") {
+        reqwest::Url::parse(text).ok()?.to_file_path().ok()?
+    } else {
+        PathBuf::from(text)
+    };
+    (is_audio_path(&path) && path.is_file()).then_some(path)
+}
+
+fn is_audio_path(path: &Path) -> bool {
+    path.is_absolute() && is_supported_audio_file(path)
+}
+
+/// File extension of audio data of the pasteboard type (UTI) `data_type`
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(super) fn audio_type_extension(data_type: &str) -> Option<&'static str> {
+    match data_type {
+        "com.microsoft.waveform-audio" => Some("wav"),
+        "public.mp3" => Some("mp3"),
+        "com.apple.m4a-audio" | "public.mpeg-4-audio" => Some("m4a"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_type_extension() {
+        assert_eq!(audio_type_extension("com.apple.m4a-audio"), Some("m4a"));
+        assert_eq!(audio_type_extension("public.mp3"), Some("mp3"));
+        assert_eq!(audio_type_extension("public.utf8-plain-text"), None);
+    }
+
+    #[test]
+    fn test_audio_path_must_be_absolute_and_supported() {
+        assert!(is_audio_path(Path::new("/Users/me/Memo.M4A")));
+        assert!(!is_audio_path(Path::new("Memo.m4a")));
+        assert!(!is_audio_path(Path::new("/Users/me/notes.txt")));
+        assert_eq!(audio_file_path("just some copied text"), None);
+    }
+}
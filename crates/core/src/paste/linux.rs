@@ -0,0 +1,159 @@
+//! Linux paste backends.
+//!
+//! - X11: the clipboard through arboard and Ctrl+V through enigo (XTest).
+//! - Wayland: clients can't inject input or own the clipboard without focus, so
+//!   the clipboard goes through `wl-copy`/`wl-paste` (wl-clipboard, data-control
+//!   protocol) and Ctrl+V through `wtype` (virtual keyboard protocol).
+//!
+//! What is available is detected up front, so errors say what to install instead
+//! of failing somewhere inside a tool.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use super::ClipboardPasteError;
+
+/// Display server of the session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayServer {
+    X11,
+    Wayland,
+    /// Neither `WAYLAND_DISPLAY` nor `DISPLAY` is set (e.g. a TTY or SSH session)
+    Unknown,
+}
+
+/// Tools and protocols the paste backends can use in this session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinuxPasteCapabilities {
+    pub display_server: DisplayServer,
+    /// An X server is reachable for XTest (also true under XWayland)
+    pub xtest: bool,
+    /// `wl-copy` and `wl-paste` are installed
+    pub wl_clipboard: bool,
+    /// `wtype` is installed (virtual keyboard protocol)
+    pub virtual_keyboard: bool,
+}
+
+impl LinuxPasteCapabilities {
+    /// Why pasting can't work in this session, `None` if it can
+    pub fn missing(&self) -> Option<String> {
+        match self.display_server {
+            DisplayServer::X11 => None,
+            DisplayServer::Wayland => {
+                let mut missing = Vec::new();
+                if !self.wl_clipboard {
+                    missing.push("wl-clipboard (wl-copy/wl-paste) for the clipboard");
+                }
+                if !self.virtual_keyboard {
+                    missing.push("wtype for the paste shortcut (virtual keyboard protocol)");
+                }
+                (!missing.is_empty())
+                    .then(|| format!("Wayland session, please install {}", missing.join(" and ")))
+            }
+            DisplayServer::Unknown => Some(
+                "No graphical session found (neither WAYLAND_DISPLAY nor DISPLAY is set)".into(),
+            ),
+        }
+    }
+}
+
+/// Detect the display server and the available tools
+pub fn detect_capabilities() -> LinuxPasteCapabilities {
+    let wayland = env_set("WAYLAND_DISPLAY")
+        || std::env::var("XDG_SESSION_TYPE").is_ok_and(|session| session == "wayland");
+    let xtest = env_set("DISPLAY");
+
+    LinuxPasteCapabilities {
+        display_server: if wayland {
+            DisplayServer::Wayland
+        } else if xtest {
+            DisplayServer::X11
+        } else {
+            DisplayServer::Unknown
+        },
+        xtest,
+        wl_clipboard: has_command("wl-copy") && has_command("wl-paste"),
+        virtual_keyboard: has_command("wtype"),
+    }
+}
+
+fn env_set(name: &str) -> bool {
+    std::env::var_os(name).is_some_and(|value| !value.is_empty())
+}
+
+fn has_command(name: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(name).is_file()))
+}
+
+/// Wayland backend, `None` outside Wayland (X11 uses arboard and enigo)
+pub(super) fn wayland() -> Result<Option<Wayland>, ClipboardPasteError> {
+    let capabilities = detect_capabilities();
+    if let Some(missing) = capabilities.missing() {
+        return Err(ClipboardPasteError::Unsupported(missing));
+    }
+    Ok((capabilities.display_server == DisplayServer::Wayland).then_some(Wayland))
+}
+
+/// Clipboard and key injection through wl-clipboard and wtype
+pub(super) struct Wayland;
+
+impl Wayland {
+    pub(super) fn get_text(&self) -> Result<String, ClipboardPasteError> {
+        let output = Command::new("wl-paste")
+            .args(["--no-newline", "--type", "text/plain"])
+            .output()
+            .map_err(|e| tool_error("wl-paste", e))?;
+        if !output.status.success() {
+            return Err(ClipboardPasteError::ToolFailed(format!(
+                "wl-paste exited with {}",
+                output.status
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    pub(super) fn set_text(&self, text: &str) -> Result<(), ClipboardPasteError> {
+        let mut child = Command::new("wl-copy")
+            .args(["--type", "text/plain;charset=utf-8"])
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| tool_error("wl-copy", e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .map_err(|e| tool_error("wl-copy", e))?;
+        }
+        // wl-copy forks a server that keeps serving the clipboard and exits right away
+        let status = child.wait().map_err(|e| tool_error("wl-copy", e))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(ClipboardPasteError::ToolFailed(format!(
+                "wl-copy exited with {}",
+                status
+            )))
+        }
+    }
+
+    /// Ctrl+V through the virtual keyboard protocol
+    pub(super) fn simulate_paste(&self) -> Result<(), ClipboardPasteError> {
+        let status = Command::new("wtype")
+            .args(["-M", "ctrl", "v", "-m", "ctrl"])
+            .status()
+            .map_err(|e| tool_error("wtype", e))?;
+        if status.success() {
+            Ok(())
+        } else {
+            // wtype fails on compositors without the virtual keyboard protocol (GNOME)
+            Err(ClipboardPasteError::Unsupported(format!(
+                "wtype exited with {}: the compositor may not support the virtual keyboard protocol",
+                status
+            )))
+        }
+    }
+}
+
+fn tool_error(tool: &str, error: std::io::Error) -> ClipboardPasteError {
+    ClipboardPasteError::ToolFailed(format!("{}: {}", tool, error))
+}
@@ -0,0 +1,183 @@
+//! Direct NSPasteboard access so clipboard writes can carry nspasteboard.org markers
+//! and the previous content can be restored with all of its types, and direct
+//! unicode text insertion with keyboard events.
+
+use std::{thread, time::Duration};
+
+use objc2::rc::Retained;
+use objc2::runtime::ProtocolObject;
+use objc2_app_kit::{NSPasteboard, NSPasteboardItem, NSPasteboardTypeString, NSPasteboardWriting};
+use objc2_core_graphics::{CGEvent, CGEventField, CGEventFlags, CGEventTapLocation};
+use objc2_foundation::{NSArray, NSData, NSString};
+
+use super::audio::{audio_file_path, audio_type_extension, ClipboardAudio};
+use super::{unicode_chunks, ClipboardMarker, ClipboardPasteError};
+
+/// Every item on the pasteboard with the data of each type it provides
+///
+/// Copied out of the pasteboard objects, so snapshots can be kept across threads.
+#[derive(Clone, PartialEq)]
+pub(super) struct ClipboardSnapshot {
+    items: Vec<Vec<(String, Vec<u8>)>>,
+}
+
+impl ClipboardSnapshot {
+    /// Marked as sensitive (e.g. copied from a password manager)
+    pub(super) fn is_concealed(&self) -> bool {
+        self.items
+            .iter()
+            .flatten()
+            .any(|(data_type, _)| data_type == CONCEALED_TYPE)
+    }
+}
+
+const TRANSIENT_TYPE: &str = "org.nspasteboard.TransientType";
+const CONCEALED_TYPE: &str = "org.nspasteboard.ConcealedType";
+
+/// Save all pasteboard items, `None` if the pasteboard is empty
+pub(super) fn snapshot_clipboard() -> Option<ClipboardSnapshot> {
+    let pasteboard = NSPasteboard::generalPasteboard();
+    let items: Vec<_> = pasteboard
+        .pasteboardItems()?
+        .iter()
+        .map(|item| {
+            item.types()
+                .iter()
+                .filter_map(|data_type| {
+                    let data = item.dataForType(&data_type)?;
+                    Some((data_type.to_string(), data.to_vec()))
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(|types| !types.is_empty())
+        .collect();
+
+    if items.is_empty() {
+        None
+    } else {
+        Some(ClipboardSnapshot { items })
+    }
+}
+
+/// Put the saved items back on the pasteboard
+pub(super) fn restore_clipboard(
+    snapshot: ClipboardSnapshot,
+    marker: ClipboardMarker,
+) -> Result<(), ClipboardPasteError> {
+    let items = snapshot
+        .items
+        .iter()
+        .map(|types| {
+            let item = NSPasteboardItem::new();
+            for (data_type, data) in types {
+                item.setData_forType(&NSData::with_bytes(data), &NSString::from_str(data_type));
+            }
+            add_marker(&item, marker);
+            item
+        })
+        .collect();
+
+    write_items(items)
+}
+
+/// Counter incremented whenever the pasteboard content changes
+pub(super) fn change_count() -> Option<isize> {
+    Some(NSPasteboard::generalPasteboard().changeCount())
+}
+
+/// Replace the pasteboard content with plain text
+pub(super) fn set_clipboard_text(
+    text: &str,
+    marker: ClipboardMarker,
+) -> Result<(), ClipboardPasteError> {
+    let item = NSPasteboardItem::new();
+    // SAFETY: NSPasteboardTypeString is an immutable AppKit constant
+    item.setString_forType(&NSString::from_str(text), unsafe { NSPasteboardTypeString });
+    add_marker(&item, marker);
+
+    write_items(vec![item])
+}
+
+/// Audio data, a copied audio file or an audio file path on the pasteboard
+pub(super) fn clipboard_audio() -> Option<ClipboardAudio> {
+    let pasteboard = NSPasteboard::generalPasteboard();
+    for item in pasteboard.pasteboardItems()?.iter() {
+        for data_type in item.types().iter() {
+            let Some(extension) = audio_type_extension(&data_type.to_string()) else {
+                continue;
+            };
+            if let Some(data) = item.dataForType(&data_type) {
+                return Some(ClipboardAudio::Data {
+                    bytes: data.to_vec(),
+                    extension,
+                });
+            }
+        }
+
+        for text_type in ["public.file-url", "public.utf8-plain-text"] {
+            let text = item.stringForType(&NSString::from_str(text_type));
+            if let Some(path) = text.and_then(|text| audio_file_path(&text.to_string())) {
+                return Some(ClipboardAudio::File(path));
+            }
+        }
+    }
+    None
+}
+
+/// Tag an item with the marker type (the data of marker types is irrelevant)
+fn add_marker(item: &NSPasteboardItem, marker: ClipboardMarker) {
+    let marker_type = match marker {
+        ClipboardMarker::None => return,
+        ClipboardMarker::Transient => TRANSIENT_TYPE,
+        ClipboardMarker::Concealed => CONCEALED_TYPE,
+    };
+    item.setData_forType(&NSData::new(), &NSString::from_str(marker_type));
+}
+
+fn write_items(items: Vec<Retained<NSPasteboardItem>>) -> Result<(), ClipboardPasteError> {
+    let pasteboard = NSPasteboard::generalPasteboard();
+    pasteboard.clearContents();
+
+    let objects: Vec<Retained<ProtocolObject<dyn NSPasteboardWriting>>> = items
+        .into_iter()
+        .map(ProtocolObject::from_retained)
+        .collect();
+
+    if pasteboard.writeObjects(&NSArray::from_retained_slice(&objects)) {
+        Ok(())
+    } else {
+        Err(ClipboardPasteError::PasteboardWriteFailed)
+    }
+}
+
+/// Type `text` with keyboard events carrying the characters themselves
+///
+/// Independent of the keyboard layout and the clipboard. A keyboard event carries
+/// at most 20 UTF-16 code units, longer text is sent in chunks.
+pub(super) fn type_unicode_text(text: &str) -> Result<(), ClipboardPasteError> {
+    for chunk in unicode_chunks(text) {
+        for key_down in [true, false] {
+            let event = CGEvent::new_keyboard_event(None, 0, key_down).ok_or_else(|| {
+                ClipboardPasteError::KeyEventFailed("failed to create keyboard event".to_string())
+            })?;
+            // Modifiers still held (e.g. the recording shortcut) must not apply to the text
+            CGEvent::set_flags(Some(&event), CGEventFlags::empty());
+            // Ignored by our own event tap
+            CGEvent::set_integer_value_field(
+                Some(&event),
+                CGEventField::EventSourceUserData,
+                dictara_keyboard::SYNTHETIC_EVENT_TAG,
+            );
+            // SAFETY: `chunk` outlives the call and its length is passed along
+            unsafe {
+                CGEvent::keyboard_set_unicode_string(Some(&event), chunk.len() as _, chunk.as_ptr())
+            };
+            CGEvent::post(CGEventTapLocation::HIDEventTap, Some(&event));
+        }
+
+        // Let the target application keep up with the events
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    Ok(())
+}
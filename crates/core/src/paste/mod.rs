@@ -0,0 +1,656 @@
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+use arboard::Clipboard;
+#[cfg(not(target_os = "windows"))]
+use enigo::{Direction, Key};
+use enigo::{Enigo, Keyboard, Settings};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+mod audio;
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+
+pub use audio::{clipboard_audio, ClipboardAudio};
+#[cfg(target_os = "linux")]
+pub use linux::{detect_capabilities, DisplayServer, LinuxPasteCapabilities};
+
+/// Text shorter than this (in characters) is typed instead of pasted, see [`insert_text_with_marker`]
+pub const DIRECT_TYPING_MAX_CHARS: usize = 64;
+
+/// Most UTF-16 code units a single keyboard event can carry
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const MAX_UNICODE_EVENT_LEN: usize = 20;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClipboardPasteError {
+    #[error("Failed to initialize enigo: {0}")]
+    EnigoInitFailed(String),
+    #[error("Failed to simulate key event: {0}")]
+    KeyEventFailed(String),
+    #[error("Empty text")]
+    EmptyText,
+    #[error("Clipboard error: {0}")]
+    ClipboardError(#[from] arboard::Error),
+    #[error("Failed to write to the pasteboard")]
+    PasteboardWriteFailed,
+    /// The session lacks what pasting needs (e.g. Wayland tools), the message says what
+    #[error("Pasting is not supported here: {0}")]
+    Unsupported(String),
+    #[error("Clipboard tool failed: {0}")]
+    ToolFailed(String),
+    /// Another process kept the clipboard open (Windows)
+    #[error("The clipboard is in use by another application")]
+    ClipboardBusy,
+}
+
+/// Pasteboard metadata that tells clipboard managers (Maccy, Paste, Raycast, ...)
+/// to keep our temporary clipboard writes out of their history.
+///
+/// See <http://nspasteboard.org> for the conventions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardMarker {
+    /// No marker, transcriptions show up in clipboard history
+    None,
+    /// `org.nspasteboard.TransientType`: content is replaced right away, don't record it
+    #[default]
+    Transient,
+    /// `org.nspasteboard.ConcealedType`: content is sensitive, don't record or display it
+    Concealed,
+}
+
+/// Bounds of the wait between the simulated paste and the clipboard restore
+///
+/// Restoring too early makes slow apps (Electron, remote desktops) paste the
+/// previous clipboard content or a truncated text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase", default)]
+pub struct PasteTiming {
+    /// Never restore earlier than this after the paste
+    pub min_restore_delay_ms: u64,
+    /// Restore at the latest after this, even if the paste wasn't confirmed
+    pub max_restore_delay_ms: u64,
+}
+
+impl Default for PasteTiming {
+    fn default() -> Self {
+        Self {
+            min_restore_delay_ms: 100,
+            max_restore_delay_ms: 1500,
+        }
+    }
+}
+
+impl PasteTiming {
+    /// Bounds with `min <= max`
+    fn normalized(self) -> Self {
+        Self {
+            min_restore_delay_ms: self.min_restore_delay_ms.min(self.max_restore_delay_ms),
+            max_restore_delay_ms: self.max_restore_delay_ms,
+        }
+    }
+}
+
+/// How a paste is performed and when the clipboard is restored
+#[derive(Clone, Copy, Default)]
+pub struct PasteOptions<'a> {
+    pub marker: ClipboardMarker,
+    pub timing: PasteTiming,
+    /// Whether the target app consumed the paste (e.g. its focused text changed).
+    /// Polled between the timing bounds; without it, [`DEFAULT_RESTORE_DELAY_MS`]
+    /// (or the maximum if `slow_target` is set) is waited.
+    pub consumed: Option<&'a dyn Fn() -> bool>,
+    /// The target app is known to respond slowly, wait the maximum without a probe
+    pub slow_target: bool,
+    /// Clipboard content saved ahead of the paste, used if the clipboard is unchanged
+    pub prepared: Option<&'a PreparedClipboard>,
+}
+
+/// Clipboard content saved before the text to paste is ready, see [`prepare_clipboard`]
+pub struct PreparedClipboard {
+    snapshot: Option<ClipboardSnapshot>,
+    change_count: Option<isize>,
+}
+
+impl PreparedClipboard {
+    /// Whether nothing was copied since the snapshot. Without a change count
+    /// (Linux) that can't be told, the snapshot is never used.
+    fn is_current(&self) -> bool {
+        self.change_count.is_some() && self.change_count == change_count()
+    }
+}
+
+/// Wait before restoring the clipboard when the paste can't be confirmed
+///
+/// 250ms is chosen because:
+/// - Some apps read clipboard asynchronously on dispatch queues
+/// - Clipboard managers like Maccy poll every 500ms by default
+/// - Too short a delay can cause race conditions (e.g., app crashes)
+pub const DEFAULT_RESTORE_DELAY_MS: u64 = 250;
+
+/// Interval between checks of the `consumed` probe
+const CONSUMED_POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// Clipboard contents kept from before the last pastes, see [`restore_previous_clipboard`]
+pub const CLIPBOARD_QUARANTINE_SIZE: usize = 5;
+
+/// Clipboard contents replaced by pastes, newest last (in memory only)
+static QUARANTINE: Mutex<VecDeque<ClipboardSnapshot>> = Mutex::new(VecDeque::new());
+
+/// Auto-paste text, marking the temporary clipboard content as transient
+///
+/// See [`paste_text_with_marker`].
+pub fn paste_text(text: &str) -> Result<(), ClipboardPasteError> {
+    paste_text_with_marker(text, ClipboardMarker::default())
+}
+
+/// Auto-paste text
+///
+/// This function:
+/// 1. Saves the current clipboard content
+/// 2. Sets the transcribed text to clipboard (tagged with `marker`)
+/// 3. Simulates Cmd+V (macOS) or Ctrl+V (Windows/Linux), see [`simulate_paste`]
+/// 4. Restores the original clipboard after a delay
+///
+/// On macOS and Windows every format of the original content is restored (rich
+/// text, images, files), elsewhere only plain text is restored. The restore is tagged
+/// with `marker` too, so clipboard managers don't record it as a new entry.
+///
+/// Returns Ok(()) on success, Err on clipboard or keyboard simulation failure
+pub fn paste_text_with_marker(
+    text: &str,
+    marker: ClipboardMarker,
+) -> Result<(), ClipboardPasteError> {
+    paste_text_with_options(
+        text,
+        &PasteOptions {
+            marker,
+            ..PasteOptions::default()
+        },
+    )
+}
+
+/// Auto-paste text, restoring the clipboard once the paste was consumed
+///
+/// Like [`paste_text_with_marker`], but the restore waits for `options.consumed`
+/// within the timing bounds. If another app changed the clipboard in the meantime
+/// (the user copied something), the previous content is not restored over it.
+///
+/// The previous content is also kept in memory, so it can be recovered with
+/// [`restore_previous_clipboard`] if the restore lost a race.
+pub fn paste_text_with_options(
+    text: &str,
+    options: &PasteOptions,
+) -> Result<(), ClipboardPasteError> {
+    // Guard: Don't paste empty text
+    if text.is_empty() {
+        return Err(ClipboardPasteError::EmptyText);
+    }
+
+    // Save current clipboard content (if any), unless it was saved ahead of time
+    let previous_clipboard = match options.prepared.filter(|prepared| prepared.is_current()) {
+        Some(prepared) => prepared.snapshot.clone(),
+        None => snapshot_clipboard(),
+    };
+    if let Some(previous_clipboard) = &previous_clipboard {
+        quarantine(previous_clipboard);
+    }
+
+    // Set transcribed text to clipboard
+    set_clipboard_text(text, options.marker)?;
+    let our_change_count = change_count();
+
+    // Simulate paste, putting the previous content back if it fails
+    if let Err(e) = simulate_paste() {
+        if let Some(previous_clipboard) = previous_clipboard {
+            if let Err(restore_error) = restore_clipboard(previous_clipboard, options.marker) {
+                warn!(
+                    "Failed to set previous clipboard content after a failed paste: {}",
+                    restore_error
+                );
+            }
+        }
+        return Err(e);
+    }
+
+    // Give the target application time to process the paste event
+    // before restoring the original clipboard content.
+    wait_for_paste(options);
+
+    if change_count() != our_change_count {
+        warn!("Clipboard changed while pasting, not restoring the previous content");
+        return Ok(());
+    }
+
+    // Restore previous clipboard content
+    if let Some(previous_clipboard) = previous_clipboard {
+        if let Err(e) = restore_clipboard(previous_clipboard, options.marker) {
+            warn!("Failed to set previous clipboard content: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Save the clipboard content ahead of a paste, e.g. while the text is post-processed
+///
+/// Large content (images, files) takes a while to read; a paste given the prepared
+/// content through [`PasteOptions::prepared`] doesn't read it again, unless something
+/// was copied in the meantime.
+pub fn prepare_clipboard() -> PreparedClipboard {
+    PreparedClipboard {
+        // Read first, so a copy during the snapshot makes it stale
+        change_count: change_count(),
+        snapshot: snapshot_clipboard(),
+    }
+}
+
+/// Keep a copy of clipboard content about to be replaced by a paste.
+/// Concealed content (passwords) is not kept.
+fn quarantine(snapshot: &ClipboardSnapshot) {
+    if snapshot.is_concealed() {
+        return;
+    }
+    let Ok(mut quarantine) = QUARANTINE.lock() else {
+        return;
+    };
+    if quarantine.back() == Some(snapshot) {
+        return;
+    }
+    if quarantine.len() == CLIPBOARD_QUARANTINE_SIZE {
+        quarantine.pop_front();
+    }
+    quarantine.push_back(snapshot.clone());
+}
+
+/// Put back clipboard content replaced by a paste, in case the paste failed or its
+/// restore raced with another app
+///
+/// Content equal to the current clipboard is skipped, so each call goes one paste
+/// further back (up to [`CLIPBOARD_QUARANTINE_SIZE`]). Returns whether anything was
+/// restored.
+pub fn restore_previous_clipboard() -> Result<bool, ClipboardPasteError> {
+    let current = snapshot_clipboard();
+    let snapshot = {
+        let Ok(mut quarantine) = QUARANTINE.lock() else {
+            return Ok(false);
+        };
+        loop {
+            match quarantine.pop_back() {
+                Some(snapshot) if Some(&snapshot) == current.as_ref() => continue,
+                snapshot => break snapshot,
+            }
+        }
+    };
+    let Some(snapshot) = snapshot else {
+        return Ok(false);
+    };
+
+    // An explicit recovery, so no marker: clipboard managers may record it
+    if let Err(e) = restore_clipboard(snapshot.clone(), ClipboardMarker::None) {
+        if let Ok(mut quarantine) = QUARANTINE.lock() {
+            quarantine.push_back(snapshot);
+        }
+        return Err(e);
+    }
+    Ok(true)
+}
+
+/// Sleep until the paste is consumed, within the timing bounds
+fn wait_for_paste(options: &PasteOptions) {
+    let timing = options.timing.normalized();
+    let min = Duration::from_millis(timing.min_restore_delay_ms);
+    let max = Duration::from_millis(timing.max_restore_delay_ms);
+
+    let Some(consumed) = options.consumed else {
+        let delay = if options.slow_target {
+            max
+        } else {
+            Duration::from_millis(DEFAULT_RESTORE_DELAY_MS).clamp(min, max)
+        };
+        thread::sleep(delay);
+        return;
+    };
+
+    let start = Instant::now();
+    thread::sleep(min);
+    while start.elapsed() < max {
+        if consumed() {
+            debug!("Paste consumed after {:?}", start.elapsed());
+            return;
+        }
+        thread::sleep(CONSUMED_POLL_INTERVAL);
+    }
+    warn!(
+        "Paste not confirmed after {:?}, restoring the clipboard",
+        max
+    );
+}
+
+/// Put text on the clipboard and leave it there (an explicit copy, so no marker)
+pub fn copy_text(text: &str) -> Result<(), ClipboardPasteError> {
+    if text.is_empty() {
+        return Err(ClipboardPasteError::EmptyText);
+    }
+
+    set_clipboard_text(text, ClipboardMarker::None)?;
+    Ok(())
+}
+
+/// Insert text into the focused application
+///
+/// Short single-line text is typed directly ([`type_text`]): apps that don't accept
+/// pasted rich text sometimes garble pasted emoji and other non-ASCII text, and
+/// typing leaves the clipboard alone. Longer text, or text that couldn't be typed,
+/// is pasted with [`paste_text_with_marker`].
+pub fn insert_text_with_marker(
+    text: &str,
+    marker: ClipboardMarker,
+) -> Result<(), ClipboardPasteError> {
+    insert_text_with_options(
+        text,
+        &PasteOptions {
+            marker,
+            ..PasteOptions::default()
+        },
+    )
+}
+
+/// Insert text into the focused application, see [`insert_text_with_marker`] and
+/// [`paste_text_with_options`]
+pub fn insert_text_with_options(
+    text: &str,
+    options: &PasteOptions,
+) -> Result<(), ClipboardPasteError> {
+    if text.chars().count() < DIRECT_TYPING_MAX_CHARS && !text.contains('\n') {
+        match type_text(text) {
+            Ok(()) => return Ok(()),
+            Err(e) => warn!("Failed to type text, pasting instead: {}", e),
+        }
+    }
+
+    paste_text_with_options(text, options)
+}
+
+/// Type text directly, without going through the clipboard
+///
+/// On macOS the characters are posted as unicode keyboard events
+/// (`CGEventKeyboardSetUnicodeString`), so the keyboard layout doesn't matter.
+pub fn type_text(text: &str) -> Result<(), ClipboardPasteError> {
+    if text.is_empty() {
+        return Err(ClipboardPasteError::EmptyText);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::type_unicode_text(text)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        enigo()?
+            .text(text)
+            .map_err(|e| ClipboardPasteError::KeyEventFailed(format!("text: {}", e)))
+    }
+}
+
+/// Split text into UTF-16 chunks that fit in a keyboard event, keeping surrogate pairs together
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn unicode_chunks(text: &str) -> Vec<Vec<u16>> {
+    let mut chunks = Vec::new();
+    let mut chunk = Vec::with_capacity(MAX_UNICODE_EVENT_LEN);
+    let mut buf = [0u16; 2];
+
+    for c in text.chars() {
+        let units = c.encode_utf16(&mut buf);
+        if chunk.len() + units.len() > MAX_UNICODE_EVENT_LEN {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+        chunk.extend_from_slice(units);
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+
+    chunks
+}
+
+#[cfg(target_os = "macos")]
+use macos::{change_count, restore_clipboard, set_clipboard_text, snapshot_clipboard};
+#[cfg(target_os = "windows")]
+use windows::{change_count, restore_clipboard, set_clipboard_text, snapshot_clipboard};
+
+/// Only tracked on macOS and Windows: elsewhere the restore can't tell whether another app wrote
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn change_count() -> Option<isize> {
+    None
+}
+
+/// Previous clipboard content (plain text only)
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+#[derive(Clone, PartialEq)]
+struct ClipboardSnapshot(String);
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+impl ClipboardSnapshot {
+    /// Plain text carries no sensitivity marker
+    fn is_concealed(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn snapshot_clipboard() -> Option<ClipboardSnapshot> {
+    #[cfg(target_os = "linux")]
+    if let Ok(Some(wayland)) = linux::wayland() {
+        return match wayland.get_text() {
+            Ok(text) => Some(ClipboardSnapshot(text)),
+            Err(e) => {
+                warn!("Failed to get current clipboard content: {}", e);
+                None
+            }
+        };
+    }
+
+    match Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+        Ok(text) => Some(ClipboardSnapshot(text)),
+        Err(_) => {
+            warn!("Failed to get current clipboard content");
+            None
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn restore_clipboard(
+    snapshot: ClipboardSnapshot,
+    marker: ClipboardMarker,
+) -> Result<(), ClipboardPasteError> {
+    set_clipboard_text(&snapshot.0, marker)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn set_clipboard_text(text: &str, marker: ClipboardMarker) -> Result<(), ClipboardPasteError> {
+    // wl-copy has no history exclusion hint, the marker is dropped on Wayland
+    #[cfg(target_os = "linux")]
+    if let Some(wayland) = linux::wayland()? {
+        return wayland.set_text(text);
+    }
+
+    #[cfg(target_os = "linux")]
+    use arboard::SetExtLinux;
+
+    let mut clipboard = Clipboard::new()?;
+    let set = clipboard.set();
+    // X11 doesn't distinguish transient from concealed content
+    let set = match marker {
+        ClipboardMarker::None => set,
+        ClipboardMarker::Transient | ClipboardMarker::Concealed => set.exclude_from_history(),
+    };
+    Ok(set.text(text.to_string())?)
+}
+
+/// Enigo whose events are tagged with [`dictara_keyboard::SYNTHETIC_EVENT_TAG`], so
+/// our own keyboard listener doesn't react to them
+fn enigo() -> Result<Enigo, ClipboardPasteError> {
+    let settings = Settings {
+        event_source_user_data: Some(dictara_keyboard::SYNTHETIC_EVENT_TAG),
+        ..Settings::default()
+    };
+    Enigo::new(&settings).map_err(|e| ClipboardPasteError::EnigoInitFailed(e.to_string()))
+}
+
+/// Simulate Cmd+V (macOS) or Ctrl+V (Windows/Linux)
+/// Uses virtual key codes to work regardless of keyboard layout
+///
+/// Sent with enigo on macOS and X11, with `SendInput` on Windows and with `wtype`
+/// on Wayland, see [`detect_capabilities`].
+pub fn simulate_paste() -> Result<(), ClipboardPasteError> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::simulate_paste()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        #[cfg(target_os = "linux")]
+        if let Some(wayland) = linux::wayland()? {
+            return wayland.simulate_paste();
+        }
+
+        simulate_paste_enigo()
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn simulate_paste_enigo() -> Result<(), ClipboardPasteError> {
+    let mut enigo = enigo()?;
+
+    // Platform-specific key definitions
+    // Use Key::Other with virtual key codes for layout-independent V key
+    #[cfg(target_os = "macos")]
+    let (modifier_key, v_key) = (Key::Meta, Key::Other(9)); // Cmd + V (keycode 9)
+    #[cfg(target_os = "linux")]
+    let (modifier_key, v_key) = (Key::Control, Key::Unicode('v')); // Ctrl + v
+
+    // Press modifier
+    enigo
+        .key(modifier_key, Direction::Press)
+        .map_err(|e| ClipboardPasteError::KeyEventFailed(format!("modifier press: {}", e)))?;
+
+    // Click V key
+    enigo
+        .key(v_key, Direction::Click)
+        .map_err(|e| ClipboardPasteError::KeyEventFailed(format!("V click: {}", e)))?;
+
+    // Small delay for reliability
+    thread::sleep(Duration::from_millis(50));
+
+    // Release modifier
+    enigo
+        .key(modifier_key, Direction::Release)
+        .map_err(|e| ClipboardPasteError::KeyEventFailed(format!("modifier release: {}", e)))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paste_timing_normalized() {
+        let timing = PasteTiming {
+            min_restore_delay_ms: 2000,
+            max_restore_delay_ms: 500,
+        }
+        .normalized();
+        assert_eq!(timing.min_restore_delay_ms, 500);
+        assert_eq!(timing.max_restore_delay_ms, 500);
+    }
+
+    #[test]
+    fn test_wait_for_paste_stops_when_consumed() {
+        let polls = std::cell::Cell::new(0);
+        let consumed = || {
+            polls.set(polls.get() + 1);
+            polls.get() >= 2
+        };
+        let options = PasteOptions {
+            timing: PasteTiming {
+                min_restore_delay_ms: 0,
+                max_restore_delay_ms: 5000,
+            },
+            consumed: Some(&consumed),
+            ..PasteOptions::default()
+        };
+
+        let start = Instant::now();
+        wait_for_paste(&options);
+        assert_eq!(polls.get(), 2);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_wayland_missing_tools_are_explained() {
+        let capabilities = LinuxPasteCapabilities {
+            display_server: DisplayServer::Wayland,
+            xtest: true,
+            wl_clipboard: true,
+            virtual_keyboard: false,
+        };
+        assert!(capabilities.missing().unwrap().contains("wtype"));
+
+        let x11 = LinuxPasteCapabilities {
+            display_server: DisplayServer::X11,
+            xtest: true,
+            wl_clipboard: false,
+            virtual_keyboard: false,
+        };
+        assert_eq!(x11.missing(), None);
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn test_quarantine_keeps_recent_distinct_snapshots() {
+        for i in 0..CLIPBOARD_QUARANTINE_SIZE + 2 {
+            quarantine(&ClipboardSnapshot(format!("copied {}", i)));
+            quarantine(&ClipboardSnapshot(format!("copied {}", i)));
+        }
+
+        let kept: Vec<String> = QUARANTINE
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|snapshot| snapshot.0.clone())
+            .collect();
+        assert_eq!(kept.len(), CLIPBOARD_QUARANTINE_SIZE);
+        assert_eq!(kept.first().unwrap(), "copied 2");
+        assert_eq!(kept.last().unwrap(), "copied 6");
+    }
+
+    #[test]
+    fn test_unicode_chunks_keep_surrogate_pairs() {
+        // 19 ASCII characters, then an emoji that needs 2 UTF-16 code units
+        let text = format!("{}😀é", "a".repeat(19));
+        let chunks = unicode_chunks(&text);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].len(), 19);
+        assert_eq!(String::from_utf16(&chunks[1]).unwrap(), "😀é");
+        assert!(unicode_chunks("").is_empty());
+    }
+}
@@ -0,0 +1,263 @@
+//! Win32 clipboard and `SendInput` paste, so the previous clipboard content can be
+//! restored with all of its formats and transient writes stay out of the clipboard
+//! history (Win+V) and cloud clipboard.
+
+use std::{ptr, thread, time::Duration};
+
+use windows_sys::Win32::Foundation::{GlobalFree, HANDLE};
+use windows_sys::Win32::System::DataExchange::{
+    CloseClipboard, EmptyClipboard, EnumClipboardFormats, GetClipboardData,
+    GetClipboardSequenceNumber, OpenClipboard, RegisterClipboardFormatW, SetClipboardData,
+};
+use windows_sys::Win32::System::Memory::{
+    GlobalAlloc, GlobalLock, GlobalSize, GlobalUnlock, GMEM_MOVEABLE,
+};
+use windows_sys::Win32::System::Ole::CF_UNICODETEXT;
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, VIRTUAL_KEY,
+    VK_CONTROL, VK_V,
+};
+
+use super::{ClipboardMarker, ClipboardPasteError};
+
+/// Attempts to open the clipboard, another process may hold it briefly
+const OPEN_ATTEMPTS: u32 = 10;
+const OPEN_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Formats whose data is a GDI handle or owner-drawn, not an `HGLOBAL` that can be copied
+const NON_GLOBAL_FORMATS: [u32; 8] = [
+    2,    // CF_BITMAP
+    3,    // CF_METAFILEPICT
+    9,    // CF_PALETTE
+    14,   // CF_ENHMETAFILE
+    0x80, // CF_OWNERDISPLAY
+    0x82, // CF_DSPBITMAP
+    0x83, // CF_DSPMETAFILEPICT
+    0x8E, // CF_DSPENHMETAFILE
+];
+
+/// Every copyable format on the clipboard with its data
+#[derive(Clone, PartialEq)]
+pub(super) struct ClipboardSnapshot {
+    formats: Vec<(u32, Vec<u8>)>,
+}
+
+impl ClipboardSnapshot {
+    /// Hidden from clipboard monitors (e.g. copied from a password manager)
+    pub(super) fn is_concealed(&self) -> bool {
+        let excluded = register_format("ExcludeClipboardContentFromMonitorProcessing");
+        self.formats.iter().any(|(format, _)| *format == excluded)
+    }
+}
+
+/// Clipboard opened by this thread, closed on drop
+struct OpenClipboardGuard;
+
+impl OpenClipboardGuard {
+    fn open() -> Result<Self, ClipboardPasteError> {
+        for _ in 0..OPEN_ATTEMPTS {
+            // SAFETY: a null owner window associates the clipboard with this task
+            if unsafe { OpenClipboard(ptr::null_mut()) } != 0 {
+                return Ok(Self);
+            }
+            thread::sleep(OPEN_RETRY_DELAY);
+        }
+        Err(ClipboardPasteError::ClipboardBusy)
+    }
+}
+
+impl Drop for OpenClipboardGuard {
+    fn drop(&mut self) {
+        // SAFETY: the clipboard was opened by this thread in `open`
+        unsafe { CloseClipboard() };
+    }
+}
+
+/// Counter incremented whenever the clipboard content changes
+pub(super) fn change_count() -> Option<isize> {
+    // SAFETY: no preconditions
+    Some(unsafe { GetClipboardSequenceNumber() } as isize)
+}
+
+/// Save all copyable clipboard formats, `None` if the clipboard is empty
+pub(super) fn snapshot_clipboard() -> Option<ClipboardSnapshot> {
+    let _clipboard = OpenClipboardGuard::open().ok()?;
+
+    let mut formats = Vec::new();
+    let mut format = 0;
+    loop {
+        // SAFETY: the clipboard is open, 0 starts the enumeration
+        format = unsafe { EnumClipboardFormats(format) };
+        if format == 0 {
+            break;
+        }
+        if NON_GLOBAL_FORMATS.contains(&format) {
+            continue;
+        }
+        // SAFETY: the clipboard is open and `format` is on it
+        let handle = unsafe { GetClipboardData(format) };
+        if let Some(data) = read_global(handle) {
+            formats.push((format, data));
+        }
+    }
+
+    if formats.is_empty() {
+        None
+    } else {
+        Some(ClipboardSnapshot { formats })
+    }
+}
+
+/// Put the saved formats back on the clipboard
+pub(super) fn restore_clipboard(
+    snapshot: ClipboardSnapshot,
+    marker: ClipboardMarker,
+) -> Result<(), ClipboardPasteError> {
+    let _clipboard = OpenClipboardGuard::open()?;
+    // SAFETY: the clipboard is open
+    if unsafe { EmptyClipboard() } == 0 {
+        return Err(ClipboardPasteError::PasteboardWriteFailed);
+    }
+    for (format, data) in &snapshot.formats {
+        set_data(*format, data)?;
+    }
+    add_marker(marker)
+}
+
+/// Replace the clipboard content with plain text
+pub(super) fn set_clipboard_text(
+    text: &str,
+    marker: ClipboardMarker,
+) -> Result<(), ClipboardPasteError> {
+    let wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+    let bytes: Vec<u8> = wide.iter().flat_map(|unit| unit.to_ne_bytes()).collect();
+
+    let _clipboard = OpenClipboardGuard::open()?;
+    // SAFETY: the clipboard is open
+    if unsafe { EmptyClipboard() } == 0 {
+        return Err(ClipboardPasteError::PasteboardWriteFailed);
+    }
+    set_data(CF_UNICODETEXT as u32, &bytes)?;
+    add_marker(marker)
+}
+
+/// Keep transient content out of the clipboard history and the cloud clipboard
+///
+/// See "Clipboard Formats" in the Win32 docs: `ExcludeClipboardContentFromMonitorProcessing`
+/// hides the content from clipboard monitors, the `CanInclude...`/`CanUpload...` formats
+/// (a DWORD 0) opt out of the history and the sync.
+fn add_marker(marker: ClipboardMarker) -> Result<(), ClipboardPasteError> {
+    if marker == ClipboardMarker::None {
+        return Ok(());
+    }
+    set_data(
+        register_format("ExcludeClipboardContentFromMonitorProcessing"),
+        &[0],
+    )?;
+    set_data(
+        register_format("CanIncludeInClipboardHistory"),
+        &0u32.to_ne_bytes(),
+    )?;
+    set_data(
+        register_format("CanUploadToCloudClipboard"),
+        &0u32.to_ne_bytes(),
+    )
+}
+
+fn register_format(name: &str) -> u32 {
+    let wide: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+    // SAFETY: `wide` is a null-terminated UTF-16 string
+    unsafe { RegisterClipboardFormatW(wide.as_ptr()) }
+}
+
+/// Copy the bytes of a global memory handle
+fn read_global(handle: HANDLE) -> Option<Vec<u8>> {
+    if handle.is_null() {
+        return None;
+    }
+    // SAFETY: clipboard data handles are HGLOBALs (GDI formats are skipped), the
+    // memory is only read while locked and the handle stays owned by the clipboard
+    unsafe {
+        let size = GlobalSize(handle);
+        let data = GlobalLock(handle) as *const u8;
+        if data.is_null() {
+            return None;
+        }
+        let bytes = std::slice::from_raw_parts(data, size).to_vec();
+        GlobalUnlock(handle);
+        Some(bytes)
+    }
+}
+
+/// Put `data` on the open clipboard as `format`
+fn set_data(format: u32, data: &[u8]) -> Result<(), ClipboardPasteError> {
+    // SAFETY: the allocation is at least `data.len()` bytes (1 for empty data) and
+    // written while locked; on success the clipboard owns it, otherwise it is freed
+    unsafe {
+        let memory = GlobalAlloc(GMEM_MOVEABLE, data.len().max(1));
+        if memory.is_null() {
+            return Err(ClipboardPasteError::PasteboardWriteFailed);
+        }
+        let target = GlobalLock(memory) as *mut u8;
+        if target.is_null() {
+            GlobalFree(memory);
+            return Err(ClipboardPasteError::PasteboardWriteFailed);
+        }
+        ptr::copy_nonoverlapping(data.as_ptr(), target, data.len());
+        GlobalUnlock(memory);
+
+        if SetClipboardData(format, memory).is_null() {
+            GlobalFree(memory);
+            return Err(ClipboardPasteError::PasteboardWriteFailed);
+        }
+    }
+    Ok(())
+}
+
+/// Ctrl+V with `SendInput`, as virtual keys so the keyboard layout doesn't matter
+pub(super) fn simulate_paste() -> Result<(), ClipboardPasteError> {
+    send_keys(&[
+        key_input(VK_CONTROL, false),
+        key_input(VK_V, false),
+        key_input(VK_V, true),
+    ])?;
+    // Small delay for reliability
+    thread::sleep(Duration::from_millis(50));
+    send_keys(&[key_input(VK_CONTROL, true)])
+}
+
+fn key_input(key: VIRTUAL_KEY, key_up: bool) -> INPUT {
+    INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: key,
+                wScan: 0,
+                dwFlags: if key_up { KEYEVENTF_KEYUP } else { 0 },
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    }
+}
+
+fn send_keys(inputs: &[INPUT]) -> Result<(), ClipboardPasteError> {
+    // SAFETY: `inputs` is a valid slice of initialized INPUT structs
+    let sent = unsafe {
+        SendInput(
+            inputs.len() as u32,
+            inputs.as_ptr(),
+            std::mem::size_of::<INPUT>() as i32,
+        )
+    };
+    if sent as usize == inputs.len() {
+        Ok(())
+    } else {
+        // Blocked by UIPI when the target runs elevated
+        Err(ClipboardPasteError::KeyEventFailed(format!(
+            "SendInput sent {} of {} events",
+            sent,
+            inputs.len()
+        )))
+    }
+}
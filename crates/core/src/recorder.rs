@@ -0,0 +1,1029 @@
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, Sample};
+use hound::{WavSpec, WavWriter};
+use log::{error, info, warn};
+use rubato::{FftFixedInOut, Resampler};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+
+use crate::host::AppPaths;
+use crate::level_meter::LevelThrottle;
+use crate::mixer::{downmix, LoopbackMixer};
+use crate::vad::{SileroVad, SmoothedVad, VadFrame, VoiceActivityDetector, FRAME_SAMPLES};
+
+/// Sample rate for transcription (16kHz mono)
+pub const SAMPLE_RATE: u32 = 16000;
+
+/// Bytes per second of the WAV files produced by the recorder (16kHz mono 16-bit, ~32KB/s)
+pub const AUDIO_BYTES_PER_SECOND: u64 = SAMPLE_RATE as u64 * 2;
+
+/// Callback receiving the input level (0.0-1.0), at most [`RecordingOptions::level_max_hz`]
+/// times per second
+///
+/// The Tauri app forwards it to the recording popup through an IPC channel,
+/// the CLI can simply ignore it.
+pub type LevelCallback = Box<dyn Fn(f32) + Send + Sync + 'static>;
+
+/// Callback invoked (at most once per input stream) when the input device disappears
+///
+/// cpal reports the error from its audio thread and the stream delivers no more
+/// samples afterwards, so the owner of the [`Recording`] should stop it or
+/// [switch](Recording::switch_to_default_input) to another device.
+pub type DeviceLostCallback = Box<dyn Fn() + Send + Sync + 'static>;
+
+/// Callback receiving the speech samples (16kHz mono, after VAD filtering) as they are
+/// written to the WAV file, e.g. to transcribe while recording
+///
+/// Called from the audio thread: it must return quickly (hand the samples to a channel).
+pub type SpeechCallback = Box<dyn Fn(&[f32]) + Send + Sync + 'static>;
+
+/// Default of [`RecordingOptions::level_max_hz`], smooth enough for a level meter
+pub const DEFAULT_LEVEL_MAX_HZ: u32 = 30;
+
+/// Interval between WAV header updates while recording (see [`FlushPolicy`])
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How the WAV file is persisted while recording
+///
+/// The header holds the data size, so a file that was never finalized looks
+/// empty. Periodic flushes keep it readable: a crash loses at most
+/// [`FLUSH_INTERVAL`] of audio.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub enum FlushPolicy {
+    /// Write the header when the recording stops only, a crash loses the whole take
+    Never,
+    /// Update the header and hand the data to the OS periodically (survives an app crash)
+    #[default]
+    Periodic,
+    /// Like `Periodic`, plus fsync (survives a power loss or kernel panic)
+    PeriodicSync,
+}
+
+/// Options of a recording session
+pub struct RecordingOptions {
+    /// Receives the microphone level, the peak of each window when throttled
+    pub level_callback: Option<LevelCallback>,
+    /// Most level callbacks per second, 0 = one per audio buffer (every 10-20ms)
+    pub level_max_hz: u32,
+    /// Called if the microphone is disconnected mid-recording
+    pub device_lost_callback: Option<DeviceLostCallback>,
+    /// Input device capturing system audio (e.g. a BlackHole or aggregate device),
+    /// mixed with the microphone. Recording continues without it if it isn't found.
+    pub loopback_device: Option<String>,
+    /// How often the WAV file is flushed to disk while recording
+    pub flush_policy: FlushPolicy,
+    /// Receives the speech samples as they are recorded
+    pub speech_callback: Option<SpeechCallback>,
+}
+
+impl Default for RecordingOptions {
+    fn default() -> Self {
+        Self {
+            level_callback: None,
+            level_max_hz: DEFAULT_LEVEL_MAX_HZ,
+            device_lost_callback: None,
+            loopback_device: None,
+            flush_policy: FlushPolicy::default(),
+            speech_callback: None,
+        }
+    }
+}
+
+/// VAD threshold - probability above which a frame is considered speech
+/// Silero V6 is well-calibrated, 0.5 is the standard threshold
+/// Lower = more sensitive to quiet speech, but may pick up noise
+/// Higher = stricter, may miss whispers
+pub const VAD_THRESHOLD: f32 = 0.5;
+
+/// Number of frames to buffer before speech onset (lookback)
+/// 14 frames × 32ms = 448ms of audio captured before speech onset
+pub const VAD_PREFILL_FRAMES: usize = 14;
+
+/// Number of silent frames allowed during speech before ending segment (~448ms at 32ms frames)
+pub const VAD_HANGOVER_FRAMES: usize = 14;
+
+/// Number of consecutive speech frames required to trigger onset
+/// 2 frames × 32ms = 64ms of consecutive speech required to trigger
+pub const VAD_ONSET_FRAMES: usize = 2;
+
+/// Debug: save raw audio file before VAD filtering
+/// When true, saves both raw and VAD-filtered files for comparison
+/// TODO: Set back to false after debugging VAD
+const SAVE_RAW_AUDIO_DEBUG: bool = false;
+
+#[derive(Debug, Clone)]
+pub struct RecordingResult {
+    pub file_path: String,
+    /// Total wall-clock duration of the recording in milliseconds (for logging/debug)
+    #[allow(dead_code)]
+    pub duration_ms: u64,
+    /// Duration of detected speech in milliseconds (after VAD filtering)
+    pub speech_duration_ms: u64,
+}
+
+/// WAV writer shared between the stream callback and the [`Recording`]
+type SharedWavWriter = Arc<Mutex<WavWriter<BufWriter<File>>>>;
+
+/// Everything the input stream callback writes into
+///
+/// Shared by all streams of a recording, so the input can move to another
+/// device mid-recording and keep appending to the same file.
+#[derive(Clone)]
+struct StreamSinks {
+    writer: SharedWavWriter,
+    /// Optional raw audio writer (before VAD) for debugging
+    raw_writer: Option<SharedWavWriter>,
+    level_throttle: Option<Arc<Mutex<LevelThrottle>>>,
+    device_lost_callback: Option<Arc<DeviceLostCallback>>,
+    vad: Option<Arc<Mutex<Box<dyn VoiceActivityDetector>>>>,
+    /// Count of speech samples written (for calculating speech duration)
+    speech_sample_count: Arc<AtomicUsize>,
+    speech_callback: Option<Arc<SpeechCallback>>,
+    /// Set when a loopback source is mixed into the microphone
+    mixer: Option<Arc<Mutex<LoopbackMixer>>>,
+}
+
+/// Which source an input stream captures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreamSource {
+    /// Drives the recording: levels, device loss, mixing and writing
+    Microphone,
+    /// Only queued into the mixer
+    Loopback,
+}
+
+/// Active recording session - owns all recording state and lifecycle
+pub struct Recording {
+    stream: cpal::Stream,
+    /// System audio stream mixed into the microphone, if enabled
+    loopback_stream: Option<cpal::Stream>,
+    sinks: StreamSinks,
+    /// Periodic WAV flushes, `None` with [`FlushPolicy::Never`]
+    flusher: Option<Flusher>,
+    start_timestamp: SystemTime,
+    file_path: PathBuf,
+}
+
+impl Recording {
+    /// Stop the recording and return the result
+    pub fn stop(self) -> Result<RecordingResult, RecorderError> {
+        use cpal::traits::StreamTrait;
+
+        // Pause and drop the streams
+        self.stream.pause().ok();
+        drop(self.stream);
+        if let Some(loopback_stream) = self.loopback_stream {
+            loopback_stream.pause().ok();
+            drop(loopback_stream);
+        }
+
+        // Release the flusher's writer handle so the writer can be finalized
+        if let Some(flusher) = self.flusher {
+            flusher.stop();
+        }
+
+        let StreamSinks {
+            writer,
+            raw_writer,
+            speech_sample_count,
+            ..
+        } = self.sinks;
+
+        // Finalize VAD-filtered WAV file
+        if let Ok(writer_mutex) = Arc::try_unwrap(writer) {
+            if let Ok(writer) = writer_mutex.into_inner() {
+                if let Err(e) = writer.finalize() {
+                    error!("Error finalizing WAV: {}", e);
+                }
+            }
+        }
+
+        // Finalize raw WAV file (if debug mode enabled)
+        if let Some(raw_writer) = raw_writer {
+            if let Ok(writer_mutex) = Arc::try_unwrap(raw_writer) {
+                if let Ok(writer) = writer_mutex.into_inner() {
+                    if let Err(e) = writer.finalize() {
+                        error!("Error finalizing raw WAV: {}", e);
+                    }
+                }
+            }
+        }
+
+        // Calculate wall-clock duration
+        let duration_ms = SystemTime::now()
+            .duration_since(self.start_timestamp)
+            .unwrap()
+            .as_millis() as u64;
+
+        // Calculate speech duration from VAD-filtered samples
+        let speech_samples = speech_sample_count.load(Ordering::Relaxed);
+        let speech_duration_ms = (speech_samples as u64 * 1000) / SAMPLE_RATE as u64;
+
+        info!(
+            "Recording stopped: wall-clock={}ms, speech={}ms ({} samples)",
+            duration_ms, speech_duration_ms, speech_samples
+        );
+
+        Ok(RecordingResult {
+            file_path: self.file_path.to_string_lossy().to_string(),
+            duration_ms,
+            speech_duration_ms,
+        })
+    }
+
+    /// Continue the recording on the current system default input device
+    ///
+    /// Audio keeps going into the same file. Returns the name of the new device.
+    /// On error the previous stream is kept (it may be dead if its device was unplugged).
+    pub fn switch_to_default_input(&mut self) -> Result<String, RecorderError> {
+        let device = cpal::default_host()
+            .default_input_device()
+            .ok_or(RecorderError::NoInputDevice)?;
+        let name = device.name().unwrap_or_else(|_| "unknown".to_string());
+
+        // Replacing the field drops (and stops) the previous stream
+        self.stream = open_input_stream(&device, &self.sinks, StreamSource::Microphone)?;
+
+        info!("Recording switched to input device '{}'", name);
+        Ok(name)
+    }
+}
+
+/// Background thread flushing the WAV file while recording
+struct Flusher {
+    /// Dropped to stop the thread
+    stop_tx: mpsc::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl Flusher {
+    fn start(writer: &SharedWavWriter, file_path: &Path, policy: FlushPolicy) -> Option<Self> {
+        if policy == FlushPolicy::Never {
+            return None;
+        }
+
+        // hound owns the writer's file, fsync goes through a second handle to the same file
+        let sync_file = if policy == FlushPolicy::PeriodicSync {
+            match fs::OpenOptions::new().write(true).open(file_path) {
+                Ok(file) => Some(file),
+                Err(e) => {
+                    warn!("Failed to open recording for fsync, flushing only: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let writer = Arc::clone(writer);
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let handle = std::thread::Builder::new()
+            .name("wav-flusher".to_string())
+            .spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(FLUSH_INTERVAL) {
+                    flush_wav(&writer, sync_file.as_ref());
+                }
+            });
+
+        match handle {
+            Ok(handle) => Some(Self { stop_tx, handle }),
+            Err(e) => {
+                error!("Failed to start WAV flusher: {}", e);
+                None
+            }
+        }
+    }
+
+    fn stop(self) {
+        drop(self.stop_tx);
+        if self.handle.join().is_err() {
+            error!("WAV flusher thread panicked");
+        }
+    }
+}
+
+/// Update the WAV header and flush buffered samples, then fsync if requested
+fn flush_wav(writer: &SharedWavWriter, sync_file: Option<&File>) {
+    match writer.lock() {
+        Ok(mut guard) => {
+            if let Err(e) = guard.flush() {
+                warn!("Failed to flush recording: {}", e);
+                return;
+            }
+        }
+        Err(_) => return,
+    }
+
+    // Outside the lock, fsync can take a while and the audio callback needs the writer
+    if let Some(file) = sync_file {
+        if let Err(e) = file.sync_data() {
+            warn!("Failed to sync recording to disk: {}", e);
+        }
+    }
+}
+
+pub struct AudioRecorder {
+    /// Directory where recordings are written
+    audio_dir: PathBuf,
+    /// VAD instance - created once, reused across recordings
+    vad: Option<Arc<Mutex<Box<dyn VoiceActivityDetector>>>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RecorderError {
+    #[error("No input device")]
+    NoInputDevice,
+
+    #[error("Device error")]
+    DeviceError,
+
+    #[error("Failed to build stream: {0}")]
+    BuildStreamError(#[from] cpal::BuildStreamError),
+
+    #[error("Failed to play stream: {0}")]
+    PlayStreamError(#[from] cpal::PlayStreamError),
+
+    #[error("Failed to pause stream: {0}")]
+    PauseStreamError(#[from] cpal::PauseStreamError),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] io::Error),
+
+    #[error("Audio encoding error: {0}")]
+    EncodingError(#[from] hound::Error),
+}
+
+// TODO: this should be moved to the controller layer
+impl RecorderError {
+    /// Returns a user-friendly error message suitable for display in the UI
+    pub fn user_message(&self) -> String {
+        match self {
+            RecorderError::NoInputDevice | RecorderError::DeviceError => {
+                "No microphone found. Please connect one and try again.".to_string()
+            }
+            RecorderError::BuildStreamError(_)
+            | RecorderError::PlayStreamError(_)
+            | RecorderError::PauseStreamError(_) => {
+                "Microphone error. Check your audio settings.".to_string()
+            }
+            RecorderError::IoError(_) | RecorderError::EncodingError(_) => {
+                "Failed to save recording. Check disk space.".to_string()
+            }
+        }
+    }
+}
+
+impl AudioRecorder {
+    /// Create a new AudioRecorder with VAD initialized
+    ///
+    /// # Arguments
+    /// * `audio_dir` - Directory where recordings are written (created on demand)
+    /// * `vad_model_path` - Path to the Silero V6 ONNX model, `None` records without VAD
+    pub fn new(audio_dir: PathBuf, vad_model_path: Option<&Path>) -> Self {
+        let vad = vad_model_path.and_then(Self::create_vad);
+        AudioRecorder { audio_dir, vad }
+    }
+
+    /// Recorder writing into the recordings directory of the embedding program
+    pub fn for_app(paths: &impl AppPaths) -> Self {
+        Self::new(paths.recordings_dir(), paths.vad_model_path().as_deref())
+    }
+
+    /// Directory where recordings are written
+    pub fn audio_dir(&self) -> &Path {
+        &self.audio_dir
+    }
+
+    /// Start a new recording session
+    pub fn start(&self, options: RecordingOptions) -> Result<Recording, RecorderError> {
+        // Ensure audio directory exists
+        let audio_dir = ensure_audio_dir_exists(&self.audio_dir)?;
+
+        // Get audio host and device first
+        let host = cpal::default_host();
+        let device = host
+            .default_input_device()
+            .ok_or(RecorderError::NoInputDevice)?;
+
+        // Generate filename
+        let filename = generate_filename();
+        let file_path = audio_dir.join(&filename);
+
+        // Always write 16kHz mono to file (optimal for speech transcription)
+        let spec = WavSpec {
+            channels: 1,              // Always mono
+            sample_rate: SAMPLE_RATE, // Always 16kHz
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+
+        let writer = AudioRecorder::create_wav_writer(file_path.clone(), spec)?;
+        let writer = Arc::new(Mutex::new(writer));
+
+        // Create raw audio writer for debugging (before VAD filtering)
+        let raw_writer = if SAVE_RAW_AUDIO_DEBUG {
+            let raw_filename = filename.replace(".wav", "_raw.wav");
+            let raw_path = audio_dir.join(&raw_filename);
+            match AudioRecorder::create_wav_writer(raw_path, spec) {
+                Ok(w) => {
+                    info!("Debug: saving raw audio to {}", raw_filename);
+                    Some(Arc::new(Mutex::new(w)))
+                }
+                Err(e) => {
+                    warn!("Failed to create raw audio writer: {:?}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Reset and clone VAD for this recording session
+        let vad = self.vad.clone();
+        if let Some(ref vad_arc) = vad {
+            if let Ok(mut vad_guard) = vad_arc.lock() {
+                vad_guard.reset();
+            }
+        }
+
+        let loopback_device = options
+            .loopback_device
+            .as_deref()
+            .and_then(|name| find_loopback_device(&host, name));
+
+        let sinks = StreamSinks {
+            writer,
+            raw_writer,
+            level_throttle: options.level_callback.map(|callback| {
+                Arc::new(Mutex::new(LevelThrottle::new(
+                    callback,
+                    options.level_max_hz,
+                )))
+            }),
+            device_lost_callback: options.device_lost_callback.map(Arc::new),
+            vad,
+            // Speech sample counter for tracking VAD-filtered duration
+            speech_sample_count: Arc::new(AtomicUsize::new(0)),
+            speech_callback: options.speech_callback.map(Arc::new),
+            mixer: loopback_device
+                .as_ref()
+                .map(|_| Arc::new(Mutex::new(LoopbackMixer::new()))),
+        };
+
+        let stream = open_input_stream(&device, &sinks, StreamSource::Microphone)?;
+
+        // System audio is best-effort: record the microphone alone if it fails
+        let loopback_stream = loopback_device.and_then(|device| {
+            match open_input_stream(&device, &sinks, StreamSource::Loopback) {
+                Ok(stream) => Some(stream),
+                Err(e) => {
+                    warn!(
+                        "Failed to open loopback device, recording microphone only: {:?}",
+                        e
+                    );
+                    None
+                }
+            }
+        });
+
+        let flusher = Flusher::start(&sinks.writer, &file_path, options.flush_policy);
+
+        // Record start timestamp
+        let start_timestamp = SystemTime::now();
+
+        // Return Recording session
+        Ok(Recording {
+            stream,
+            loopback_stream,
+            sinks,
+            flusher,
+            start_timestamp,
+            file_path,
+        })
+    }
+
+    /// Create VAD instance for filtering silence
+    ///
+    /// The VAD chain is: SmoothedVad → SileroVad
+    /// - SileroVad: Silero V6 neural network-based voice detection (official implementation)
+    /// - SmoothedVad: Adds hysteresis (prefill, hangover, onset) for smooth detection
+    fn create_vad(vad_path: &Path) -> Option<Arc<Mutex<Box<dyn VoiceActivityDetector>>>> {
+        if !vad_path.exists() {
+            warn!(
+                "VAD model not found at {:?}, recording without VAD",
+                vad_path
+            );
+            return None;
+        }
+
+        // Create Silero VAD V6 (official implementation)
+        let silero = match SileroVad::new(vad_path, VAD_THRESHOLD) {
+            Ok(vad) => vad,
+            Err(e) => {
+                warn!("Failed to create Silero VAD: {}, recording without VAD", e);
+                return None;
+            }
+        };
+
+        // Wrap in SmoothedVad for hysteresis (prefill, hangover, onset filtering)
+        let smoothed = SmoothedVad::new(
+            Box::new(silero),
+            VAD_PREFILL_FRAMES,
+            VAD_HANGOVER_FRAMES,
+            VAD_ONSET_FRAMES,
+        );
+
+        info!("VAD V6 initialized from {:?}", vad_path);
+        Some(Arc::new(Mutex::new(
+            Box::new(smoothed) as Box<dyn VoiceActivityDetector>
+        )))
+    }
+
+    fn create_wav_writer(
+        file_path: PathBuf,
+        spec: WavSpec,
+    ) -> Result<WavWriter<io::BufWriter<fs::File>>, RecorderError> {
+        let file = fs::File::create(file_path)?;
+        let buf_writer = io::BufWriter::new(file);
+        Ok(WavWriter::new(buf_writer, spec)?)
+    }
+}
+
+fn ensure_audio_dir_exists(audio_dir: &Path) -> Result<PathBuf, RecorderError> {
+    if !audio_dir.exists() {
+        fs::create_dir_all(audio_dir)?;
+    }
+    Ok(audio_dir.to_path_buf())
+}
+
+/// Names of the available input devices (microphones, loopback and aggregate devices)
+pub fn input_device_names() -> Vec<String> {
+    match cpal::default_host().input_devices() {
+        Ok(devices) => devices.filter_map(|device| device.name().ok()).collect(),
+        Err(e) => {
+            error!("Failed to list input devices: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Find the input device named `name` for loopback capture
+fn find_loopback_device(host: &cpal::Host, name: &str) -> Option<cpal::Device> {
+    let device = host
+        .input_devices()
+        .ok()?
+        .find(|device| device.name().is_ok_and(|device_name| device_name == name));
+
+    match &device {
+        Some(_) => info!("Mixing system audio from loopback device '{}'", name),
+        None => warn!(
+            "Loopback device '{}' not found, recording microphone only",
+            name
+        ),
+    }
+    device
+}
+
+/// Name of the current system default input device, `None` if there is none
+pub fn default_input_name() -> Option<String> {
+    cpal::default_host()
+        .default_input_device()
+        .and_then(|device| device.name().ok())
+}
+
+/// Clean up a recording file
+/// Logs errors but doesn't fail - cleanup is best-effort
+pub fn cleanup_recording_file(file_path: &str) {
+    if let Err(e) = fs::remove_file(file_path) {
+        error!("Failed to cleanup recording file {}: {}", file_path, e);
+    }
+}
+
+/// Clean up old recording files on app startup
+/// Only deletes files matching pattern: recording_*.wav, except `keep`
+pub fn cleanup_old_recordings(recordings_dir: &Path, keep: Option<&Path>) {
+    let mut cleaned = 0;
+    for path in recording_files(recordings_dir) {
+        if keep == Some(path.as_path()) {
+            continue;
+        }
+        if fs::remove_file(&path).is_ok() {
+            cleaned += 1;
+        }
+    }
+
+    if cleaned > 0 {
+        info!("Cleaned up {} old recording(s)", cleaned);
+    }
+}
+
+/// Newest recording left by a previous session that still holds audio
+///
+/// Recordings are deleted once transcribed, so a leftover file is a take that was
+/// interrupted (app crash, quit while transcribing) or whose transcription failed.
+/// Thanks to the periodic flushes (see [`FlushPolicy`]) its header is readable.
+pub fn find_interrupted_recording(recordings_dir: &Path) -> Option<PathBuf> {
+    let newest = recording_files(recordings_dir)
+        .into_iter()
+        // Debug captures of the device audio, not a take
+        .filter(|path| !path.to_string_lossy().ends_with("_raw.wav"))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((modified, path))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)?;
+
+    match hound::WavReader::open(&newest) {
+        Ok(reader) if reader.duration() > 0 => {
+            info!(
+                "Found interrupted recording {:?} ({}ms)",
+                newest,
+                reader.duration() as u64 * 1000 / SAMPLE_RATE as u64
+            );
+            Some(newest)
+        }
+        _ => None,
+    }
+}
+
+/// Recording files (recording_*.wav) in `recordings_dir`
+fn recording_files(recordings_dir: &Path) -> Vec<PathBuf> {
+    let entries = match fs::read_dir(recordings_dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(), // Directory doesn't exist yet
+    };
+
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            filename.starts_with("recording_") && filename.ends_with(".wav")
+        })
+        .collect()
+}
+
+fn generate_filename() -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    format!("recording_{}.wav", timestamp)
+}
+
+/// Open and start an input stream on `device`, writing into `sinks`
+fn open_input_stream(
+    device: &cpal::Device,
+    sinks: &StreamSinks,
+    source: StreamSource,
+) -> Result<cpal::Stream, RecorderError> {
+    // Get default device config - we'll always resample to 16kHz
+    let config = device
+        .default_input_config()
+        .map_err(|_| RecorderError::DeviceError)?;
+
+    // Always create resampler (device sample rate → 16kHz)
+    let input_rate = config.sample_rate().0 as usize;
+    let output_rate = SAMPLE_RATE as usize;
+    let channels = config.channels() as usize;
+
+    let (resampler, required_chunk_size) =
+        match FftFixedInOut::<f32>::new(input_rate, output_rate, 1024, channels) {
+            Ok(r) => {
+                let input_frames = r.input_frames_next();
+                (Arc::new(Mutex::new(r)), input_frames)
+            }
+            Err(e) => {
+                error!("Failed to create resampler: {:?}", e);
+                return Err(RecorderError::DeviceError);
+            }
+        };
+
+    // Create sample buffer for accumulating samples before resampling
+    let sample_buffer: Arc<Mutex<Vec<Vec<f32>>>> = Arc::new(Mutex::new(vec![Vec::new(); channels]));
+
+    let stream_config = config.config();
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::I8 => build_input_stream::<i8>(
+            device,
+            &stream_config,
+            sinks.clone(),
+            source,
+            resampler,
+            sample_buffer,
+            required_chunk_size,
+            channels,
+        )?,
+        cpal::SampleFormat::I16 => build_input_stream::<i16>(
+            device,
+            &stream_config,
+            sinks.clone(),
+            source,
+            resampler,
+            sample_buffer,
+            required_chunk_size,
+            channels,
+        )?,
+        cpal::SampleFormat::I32 => build_input_stream::<i32>(
+            device,
+            &stream_config,
+            sinks.clone(),
+            source,
+            resampler,
+            sample_buffer,
+            required_chunk_size,
+            channels,
+        )?,
+        cpal::SampleFormat::F32 => build_input_stream::<f32>(
+            device,
+            &stream_config,
+            sinks.clone(),
+            source,
+            resampler,
+            sample_buffer,
+            required_chunk_size,
+            channels,
+        )?,
+        _ => return Err(RecorderError::DeviceError),
+    };
+
+    // Start the stream
+    stream.play()?;
+
+    Ok(stream)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_input_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    sinks: StreamSinks,
+    source: StreamSource,
+    resampler: Arc<Mutex<FftFixedInOut<f32>>>,
+    sample_buffer: Arc<Mutex<Vec<Vec<f32>>>>,
+    required_chunk_size: usize,
+    channels: usize,
+) -> Result<cpal::Stream, RecorderError>
+where
+    T: Sample + FromSample<i16> + FromSample<f32> + std::fmt::Debug + cpal::SizedSample,
+    i16: FromSample<T>,
+    f32: FromSample<T>,
+{
+    // The loopback device going away only stops the system audio
+    let device_lost_callback = match source {
+        StreamSource::Microphone => sinks.device_lost_callback.clone(),
+        StreamSource::Loopback => None,
+    };
+    let mut device_lost_reported = false;
+    let err_fn = move |err: cpal::StreamError| {
+        error!("Stream error: {}", err);
+
+        // Unplugged device: the stream is dead, report it once so the recording gets stopped or moved
+        if matches!(err, cpal::StreamError::DeviceNotAvailable) && !device_lost_reported {
+            device_lost_reported = true;
+            if let Some(callback) = &device_lost_callback {
+                callback();
+            }
+        }
+    };
+
+    // VAD frame buffer for accumulating samples into FRAME_SAMPLES chunks
+    let vad_buffer: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let stream = device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            write_input_data::<T>(
+                data,
+                &sinks,
+                source,
+                &resampler,
+                &sample_buffer,
+                required_chunk_size,
+                channels,
+                &vad_buffer,
+            );
+        },
+        err_fn,
+        None,
+    )?;
+
+    Ok(stream)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_input_data<T>(
+    input: &[T],
+    sinks: &StreamSinks,
+    source: StreamSource,
+    resampler: &Arc<Mutex<FftFixedInOut<f32>>>,
+    sample_buffer: &Arc<Mutex<Vec<Vec<f32>>>>,
+    required_chunk_size: usize,
+    channels: usize,
+    vad_buffer: &Arc<Mutex<Vec<f32>>>,
+) where
+    T: Sample,
+    i16: FromSample<T>,
+    f32: FromSample<T>,
+{
+    // Calculate RMS (Root Mean Square) for audio level visualization (use original samples)
+    if !input.is_empty() {
+        let sum_of_squares: f32 = input
+            .iter()
+            .map(|&sample| {
+                let sample_f32: f32 = sample.to_sample();
+                sample_f32 * sample_f32
+            })
+            .sum();
+        let rms = (sum_of_squares / input.len() as f32).sqrt();
+
+        // The level meter shows the microphone only
+        if let (StreamSource::Microphone, Some(throttle)) = (source, &sinks.level_throttle) {
+            let level = (rms * 100.0).min(1.0);
+            if let Ok(mut throttle) = throttle.lock() {
+                throttle.push(level);
+            }
+        }
+    }
+
+    // Convert samples to f32 and deinterleave them by channel into the buffer
+    let mut buffer_guard = match sample_buffer.lock() {
+        Ok(guard) => guard,
+        Err(_) => return,
+    };
+
+    // Append incoming samples to buffer
+    for (i, &sample) in input.iter().enumerate() {
+        let channel_idx = i % channels;
+        let sample_f32: f32 = sample.to_sample();
+        buffer_guard[channel_idx].push(sample_f32);
+    }
+
+    // Process complete chunks of required_chunk_size samples
+    while buffer_guard[0].len() >= required_chunk_size {
+        // Extract required_chunk_size samples from each channel
+        let channel_chunks: Vec<Vec<f32>> = buffer_guard
+            .iter_mut()
+            .map(|ch| ch.drain(..required_chunk_size).collect())
+            .collect();
+
+        // Release buffer lock before resampling (to avoid holding multiple locks)
+        drop(buffer_guard);
+
+        // Resample the chunk
+        let resampled = {
+            let mut resampler_guard = match resampler.lock() {
+                Ok(guard) => guard,
+                Err(_) => return,
+            };
+
+            let channel_refs: Vec<&[f32]> = channel_chunks.iter().map(|v| v.as_slice()).collect();
+
+            match resampler_guard.process(&channel_refs, None) {
+                Ok(resampled) => resampled,
+                Err(_) => return,
+            }
+        };
+
+        // Convert to mono (average of every channel)
+        let mono_samples = downmix(&resampled);
+
+        // Loopback audio waits in the mixer, the microphone callback writes the mix
+        let mono_samples = match (source, &sinks.mixer) {
+            (StreamSource::Loopback, Some(mixer)) => {
+                if let Ok(mut mixer) = mixer.lock() {
+                    mixer.push_loopback(&mono_samples);
+                }
+                Vec::new()
+            }
+            (StreamSource::Microphone, Some(mixer)) => match mixer.lock() {
+                Ok(mut mixer) => mixer.mix(&mono_samples),
+                Err(_) => mono_samples,
+            },
+            _ => mono_samples,
+        };
+
+        if !mono_samples.is_empty() {
+            // Write raw audio before VAD (for debugging)
+            if let Some(raw_w) = &sinks.raw_writer {
+                write_samples_to_raw_wav(&mono_samples, raw_w);
+            }
+
+            // Process through VAD and write only speech frames
+            process_through_vad_and_write(&mono_samples, sinks, vad_buffer);
+        }
+
+        // Re-acquire buffer lock for next iteration
+        buffer_guard = match sample_buffer.lock() {
+            Ok(guard) => guard,
+            Err(_) => return,
+        };
+    }
+    // Remaining samples (< required_chunk_size) stay in buffer for next call
+}
+
+/// Process mono samples through VAD and write only speech to WAV
+fn process_through_vad_and_write(
+    mono_samples: &[f32],
+    sinks: &StreamSinks,
+    vad_buffer: &Arc<Mutex<Vec<f32>>>,
+) {
+    // If no VAD, write everything (fallback behavior)
+    let Some(vad_arc) = &sinks.vad else {
+        write_speech_samples(mono_samples, sinks);
+        return;
+    };
+
+    // Accumulate samples in VAD buffer
+    let mut vad_buf = match vad_buffer.lock() {
+        Ok(guard) => guard,
+        Err(_) => {
+            // On lock failure, write everything as fallback
+            write_speech_samples(mono_samples, sinks);
+            return;
+        }
+    };
+
+    vad_buf.extend_from_slice(mono_samples);
+
+    // Process complete FRAME_SAMPLES chunks through VAD
+    while vad_buf.len() >= FRAME_SAMPLES {
+        let frame: Vec<f32> = vad_buf.drain(..FRAME_SAMPLES).collect();
+
+        // Process frame through VAD - copy speech samples while holding lock
+        let speech_samples: Option<Vec<f32>> = {
+            let mut vad_guard = match vad_arc.lock() {
+                Ok(guard) => guard,
+                Err(_) => {
+                    // On lock failure, assume speech
+                    write_speech_samples(&frame, sinks);
+                    continue;
+                }
+            };
+
+            match vad_guard.push_frame(&frame) {
+                Ok(VadFrame::Speech(buf)) => {
+                    // Copy the buffer while holding the lock
+                    Some(buf.to_vec())
+                }
+                Ok(VadFrame::Noise) => None,
+                Err(e) => {
+                    warn!("VAD error: {}, assuming speech", e);
+                    Some(frame.clone())
+                }
+            }
+        }; // vad_guard released here
+
+        // Write speech samples after releasing VAD lock
+        if let Some(samples) = speech_samples {
+            write_speech_samples(&samples, sinks);
+        }
+    }
+    // Remaining samples (< FRAME_SAMPLES) stay in vad_buffer for next call
+}
+
+/// Write speech samples to the WAV file and hand them to the speech callback
+fn write_speech_samples(samples: &[f32], sinks: &StreamSinks) {
+    write_samples_to_wav(samples, &sinks.writer, &sinks.speech_sample_count);
+    if let Some(callback) = &sinks.speech_callback {
+        callback(samples);
+    }
+}
+
+/// Write samples to WAV file and update speech sample count
+fn write_samples_to_wav(
+    samples: &[f32],
+    writer: &SharedWavWriter,
+    speech_sample_count: &Arc<AtomicUsize>,
+) {
+    if let Ok(mut guard) = writer.lock() {
+        for sample_f32 in samples.iter() {
+            let clamped = sample_f32.clamp(-1.0, 1.0);
+            let sample_i16 = (clamped * 32767.0) as i16;
+            guard.write_sample(sample_i16).ok();
+        }
+        // Track how many samples we've written (for speech duration calculation)
+        speech_sample_count.fetch_add(samples.len(), Ordering::Relaxed);
+    }
+}
+
+/// Write samples to raw WAV file (debug - before VAD filtering)
+fn write_samples_to_raw_wav(samples: &[f32], writer: &SharedWavWriter) {
+    if let Ok(mut guard) = writer.lock() {
+        for sample_f32 in samples.iter() {
+            let clamped = sample_f32.clamp(-1.0, 1.0);
+            let sample_i16 = (clamped * 32767.0) as i16;
+            guard.write_sample(sample_i16).ok();
+        }
+    }
+}
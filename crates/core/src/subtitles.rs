@@ -0,0 +1,127 @@
+//! Subtitle export (SubRip `.srt` and WebVTT `.vtt`) for timestamped transcripts.
+
+use serde::{Deserialize, Serialize};
+
+use crate::transcription::TranscriptSegment;
+
+/// Supported subtitle formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "lowercase")]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+}
+
+impl SubtitleFormat {
+    /// File extension (without the dot)
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::Vtt => "vtt",
+        }
+    }
+
+    /// Render segments in this format
+    pub fn render(&self, segments: &[TranscriptSegment]) -> String {
+        match self {
+            SubtitleFormat::Srt => to_srt(segments),
+            SubtitleFormat::Vtt => to_vtt(segments),
+        }
+    }
+}
+
+/// Format segments as SubRip (`.srt`) subtitles
+pub fn to_srt(segments: &[TranscriptSegment]) -> String {
+    segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            format!(
+                "{}\n{} --> {}\n{}\n",
+                index + 1,
+                format_timestamp(segment.start_ms, ','),
+                format_timestamp(segment.end_ms, ','),
+                segment.text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format segments as WebVTT (`.vtt`) subtitles
+pub fn to_vtt(segments: &[TranscriptSegment]) -> String {
+    let cues = segments
+        .iter()
+        .map(|segment| {
+            format!(
+                "{} --> {}\n{}\n",
+                format_timestamp(segment.start_ms, '.'),
+                format_timestamp(segment.end_ms, '.'),
+                // A blank line would end the cue early
+                segment.text.replace("\n\n", "\n")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!("WEBVTT\n\n{}", cues)
+}
+
+/// `HH:MM:SS<separator>mmm` (`,` for SRT, `.` for WebVTT)
+fn format_timestamp(ms: u64, separator: char) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        (ms / 60_000) % 60,
+        (ms / 1000) % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_ms: u64, end_ms: u64, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0, ','), "00:00:00,000");
+        assert_eq!(format_timestamp(61_005, ','), "00:01:01,005");
+        assert_eq!(format_timestamp(3_723_456, '.'), "01:02:03.456");
+        // Hours aren't wrapped for recordings longer than a day
+        assert_eq!(format_timestamp(100 * 3_600_000, '.'), "100:00:00.000");
+    }
+
+    #[test]
+    fn test_to_srt_numbers_cues() {
+        let segments = [segment(0, 1_500, "Hello"), segment(1_500, 3_000, "world")];
+        assert_eq!(
+            to_srt(&segments),
+            "1\n00:00:00,000 --> 00:00:01,500\nHello\n\n2\n00:00:01,500 --> 00:00:03,000\nworld\n"
+        );
+    }
+
+    #[test]
+    fn test_to_vtt_keeps_cues_together() {
+        let segments = [segment(0, 2_000, "First line\n\nsecond line")];
+        assert_eq!(
+            to_vtt(&segments),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:02.000\nFirst line\nsecond line\n"
+        );
+    }
+
+    #[test]
+    fn test_render_empty() {
+        assert_eq!(SubtitleFormat::Srt.render(&[]), "");
+        assert_eq!(SubtitleFormat::Vtt.render(&[]), "WEBVTT\n\n");
+    }
+}
@@ -0,0 +1,312 @@
+//! API-based transcription service implementation.
+//!
+//! Handles transcription via HTTP APIs (OpenAI, Azure OpenAI).
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::{error, info};
+
+use super::client::TranscriptionClient;
+use super::error::TranscriptionError;
+use super::language::normalize_language_code;
+use super::options::TranscriptionOptions;
+use super::service::{Transcription, TranscriptionService};
+use crate::http::{
+    FormPart, HttpBody, HttpRequest, HttpSettings, HttpTransport, ReqwestTransport, TransportError,
+};
+
+/// Timeout for transcription requests in seconds (applies to all providers)
+pub const TRANSCRIPTION_TIMEOUT_SECS: u64 = 10;
+
+/// API-based transcription service.
+///
+/// Uses HTTP APIs (OpenAI Whisper API or Azure OpenAI) for transcription.
+pub struct ApiTranscriber {
+    client: Box<dyn TranscriptionClient>,
+    transport: Arc<dyn HttpTransport>,
+}
+
+impl ApiTranscriber {
+    /// Create a new API transcriber with the given client.
+    pub fn new(client: Box<dyn TranscriptionClient>) -> Self {
+        Self {
+            client,
+            transport: Arc::new(ReqwestTransport::default()),
+        }
+    }
+
+    /// Send the requests with `http_settings` (proxy, custom root CA, pinned roots)
+    /// instead of the system defaults.
+    pub fn with_http_settings(self, http_settings: HttpSettings) -> Self {
+        self.with_transport(Arc::new(ReqwestTransport::new(http_settings)))
+    }
+
+    /// Send the requests through `transport` (e.g. a `MockTransport` in tests)
+    pub fn with_transport(mut self, transport: Arc<dyn HttpTransport>) -> Self {
+        self.transport = transport;
+        self
+    }
+}
+
+impl TranscriptionService for ApiTranscriber {
+    fn transcribe_detailed(
+        &self,
+        audio_path: &Path,
+        options: &TranscriptionOptions,
+    ) -> Result<Transcription, TranscriptionError> {
+        let mut parts = vec![FormPart::File {
+            name: "file".to_string(),
+            path: audio_path.to_path_buf(),
+        }];
+        // Without a pinned language the API auto-detects it
+        parts.extend(self.client.form_fields(options));
+
+        let (auth_name, auth_value) = self.client.auth_header();
+        let request = HttpRequest::post(
+            self.client.transcription_url(),
+            HttpBody::Multipart(parts),
+            Duration::from_secs(TRANSCRIPTION_TIMEOUT_SECS),
+        )
+        .header(auth_name, auth_value);
+
+        let response = self.transport.send(request).map_err(|e| match e {
+            TransportError::Timeout(_) => {
+                error!(
+                    "API request timed out after {}s",
+                    TRANSCRIPTION_TIMEOUT_SECS
+                );
+                TranscriptionError::TranscriptionTimeout(TRANSCRIPTION_TIMEOUT_SECS)
+            }
+            TransportError::File { source, .. } => TranscriptionError::IoError(
+                std::io::Error::other(format!("Failed to read file: {}", source)),
+            ),
+            TransportError::Request(message) => {
+                error!("API request error: {}", message);
+                TranscriptionError::ApiError(format!("Request failed: {}", message))
+            }
+        })?;
+
+        // Check response status
+        if !response.is_success() {
+            error!(
+                "API error response ({}): {}",
+                response.status, response.body
+            );
+            return Err(TranscriptionError::HttpStatus {
+                status: response.status,
+                body: response.body,
+            });
+        }
+
+        // Parse JSON response
+        let json = response.json().map_err(|e| {
+            error!("Failed to parse response: {}", e);
+            TranscriptionError::ApiError(format!("Failed to parse response: {}", e))
+        })?;
+
+        let text = json["text"].as_str().unwrap_or("").to_string();
+
+        // verbose_json reports the language as a full name ("english"), a pinned
+        // language is requested as plain json without it
+        let detected_language = json["language"].as_str().and_then(normalize_language_code);
+        let detected = options.language.is_none() && detected_language.is_some();
+        let language = detected_language.or_else(|| options.language.clone());
+
+        info!(
+            "API transcription successful: {} characters, language: {:?}",
+            text.len(),
+            language
+        );
+
+        Ok(Transcription {
+            text,
+            language,
+            detected,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::MockTransport;
+    use crate::transcription::{AzureClient, OpenAIClient};
+    use secrecy::SecretString;
+
+    fn transcriber(
+        client: Box<dyn TranscriptionClient>,
+        transport: &Arc<MockTransport>,
+    ) -> ApiTranscriber {
+        ApiTranscriber::new(client).with_transport(transport.clone())
+    }
+
+    fn openai() -> Box<dyn TranscriptionClient> {
+        Box::new(OpenAIClient::new(SecretString::from("sk-test")))
+    }
+
+    #[test]
+    fn test_openai_request_and_response() {
+        let transport = Arc::new(MockTransport::new().respond(200, r#"{"text": "Hello there"}"#));
+        let transcription = transcriber(openai(), &transport)
+            .transcribe_detailed(
+                Path::new("/tmp/audio.wav"),
+                &TranscriptionOptions::with_language(Some("en")),
+            )
+            .unwrap();
+
+        assert_eq!(transcription.text, "Hello there");
+        assert_eq!(transcription.language.as_deref(), Some("en"));
+        assert!(!transcription.detected);
+
+        let request = &transport.requests()[0];
+        assert_eq!(
+            request.url,
+            "https://api.openai.com/v1/audio/transcriptions"
+        );
+        assert_eq!(
+            request.header_value("authorization"),
+            Some("Bearer sk-test")
+        );
+        assert_eq!(request.form_text("model"), Some("whisper-1"));
+        assert_eq!(request.form_text("language"), Some("en"));
+        assert_eq!(request.form_text("temperature"), Some("0"));
+        // The language is known, no need for the verbose response
+        assert_eq!(request.form_text("response_format"), Some("json"));
+        assert_eq!(request.form_text("prompt"), None);
+    }
+
+    #[test]
+    fn test_request_options() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(200, r#"{"text": ""}"#)
+                .respond(200, r#"{"text": ""}"#),
+        );
+        let options = TranscriptionOptions {
+            model: Some("gpt-4o-transcribe".to_string()),
+            language: None,
+            prompt: Some("Dictara, Tauri, whisper.cpp".to_string()),
+            temperature: 0.2,
+        };
+        let azure = AzureClient::new(
+            SecretString::from("azure-key"),
+            "https://example.azure.com/transcriptions".into(),
+        );
+        for client in [openai(), Box::new(azure) as Box<dyn TranscriptionClient>] {
+            transcriber(client, &transport)
+                .transcribe_detailed(Path::new("/tmp/audio.wav"), &options)
+                .unwrap();
+        }
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].form_text("model"), Some("gpt-4o-transcribe"));
+        assert_eq!(requests[1].form_text("model"), None);
+        for request in &requests {
+            assert_eq!(
+                request.form_text("prompt"),
+                Some("Dictara, Tauri, whisper.cpp")
+            );
+            assert_eq!(request.form_text("temperature"), Some("0.2"));
+            assert_eq!(request.form_text("language"), None);
+        }
+    }
+
+    #[test]
+    fn test_azure_request() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(200, r#"{"text": "", "language": "german"}"#)
+                .respond(200, r#"{"text": ""}"#),
+        );
+        let client = AzureClient::new(
+            SecretString::from("azure-key"),
+            "https://example.azure.com/openai/deployments/whisper/audio/transcriptions/".into(),
+        );
+        let service = transcriber(Box::new(client), &transport);
+        let transcription = service
+            .transcribe_detailed(
+                Path::new("/tmp/audio.wav"),
+                &TranscriptionOptions::default(),
+            )
+            .unwrap();
+
+        assert_eq!(transcription.text, "");
+        assert_eq!(transcription.language.as_deref(), Some("de"));
+        assert!(transcription.detected);
+        let request = &transport.requests()[0];
+        assert!(request
+            .url
+            .ends_with("/transcriptions?api-version=2024-06-01"));
+        assert_eq!(request.header_value("api-key"), Some("azure-key"));
+        assert_eq!(request.form_text("model"), None);
+        assert_eq!(request.form_text("language"), None);
+        // Auto-detected: the verbose response carries the language
+        assert_eq!(request.form_text("response_format"), Some("verbose_json"));
+
+        let transcription = service
+            .transcribe_detailed(
+                Path::new("/tmp/audio.wav"),
+                &TranscriptionOptions::with_language(Some("fr")),
+            )
+            .unwrap();
+        assert_eq!(transcription.language.as_deref(), Some("fr"));
+        assert!(!transcription.detected);
+        let request = &transport.requests()[1];
+        assert_eq!(request.form_text("response_format"), Some("json"));
+    }
+
+    #[test]
+    fn test_error_statuses() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(401, "invalid key")
+                .respond(429, "rate limited")
+                .respond(400, "unsupported audio"),
+        );
+        let service = transcriber(openai(), &transport);
+        let transcribe = || service.transcribe(Path::new("/tmp/audio.wav")).unwrap_err();
+
+        let unauthorized = transcribe();
+        assert!(matches!(
+            unauthorized,
+            TranscriptionError::HttpStatus { status: 401, .. }
+        ));
+        assert!(unauthorized.user_message().contains("Invalid API key"));
+
+        let rate_limited = transcribe();
+        assert!(rate_limited.is_retryable());
+        assert!(rate_limited.user_message().contains("Rate limit"));
+
+        // Another provider would reject the audio too
+        assert!(!transcribe().is_retryable());
+    }
+
+    #[test]
+    fn test_transport_failures() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond_with(Err(TransportError::Timeout(Duration::from_secs(10))))
+                .respond_with(Err(TransportError::Request("connection refused".into())))
+                .respond(200, "<html>proxy login</html>"),
+        );
+        let service = transcriber(openai(), &transport);
+        let transcribe = || service.transcribe(Path::new("/tmp/audio.wav"));
+
+        assert!(matches!(
+            transcribe(),
+            Err(TranscriptionError::TranscriptionTimeout(
+                TRANSCRIPTION_TIMEOUT_SECS
+            ))
+        ));
+        assert!(matches!(
+            transcribe(),
+            Err(TranscriptionError::ApiError(msg)) if msg.contains("connection refused")
+        ));
+        assert!(matches!(
+            transcribe(),
+            Err(TranscriptionError::ApiError(msg)) if msg.contains("Failed to parse response")
+        ));
+    }
+}
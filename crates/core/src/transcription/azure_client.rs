@@ -1,9 +1,8 @@
-use std::path::Path;
-
-use secrecy::{ExposeSecret, SecretString};
+use secrecy::SecretString;
 
 use super::client::TranscriptionClient;
-use super::error::TranscriptionError;
+use super::options::TranscriptionOptions;
+use crate::http::FormPart;
 
 const AZURE_API_VERSION: &str = "2024-06-01";
 
@@ -30,29 +29,12 @@ impl TranscriptionClient for AzureClient {
         )
     }
 
-    fn add_auth(
-        &self,
-        request: reqwest::blocking::RequestBuilder,
-    ) -> reqwest::blocking::RequestBuilder {
-        request.header("api-key", self.api_key.expose_secret())
+    fn auth_header(&self) -> (&'static str, SecretString) {
+        ("api-key", self.api_key.clone())
     }
 
-    fn build_form_from_path(
-        &self,
-        file_path: &Path,
-    ) -> Result<reqwest::blocking::multipart::Form, TranscriptionError> {
+    fn form_fields(&self, options: &TranscriptionOptions) -> Vec<FormPart> {
         // Azure doesn't need model in form - it's embedded in the endpoint URL
-        let form = reqwest::blocking::multipart::Form::new()
-            .file("file", file_path)
-            .map_err(|e| {
-                TranscriptionError::IoError(std::io::Error::other(format!(
-                    "Failed to read file: {}",
-                    e
-                )))
-            })?
-            .text("temperature", "0.0")
-            .text("response_format", "json");
-
-        Ok(form)
+        options.form_fields()
     }
 }
@@ -0,0 +1,23 @@
+use secrecy::SecretString;
+
+use super::options::TranscriptionOptions;
+use crate::http::FormPart;
+
+/// Trait for transcription API clients (OpenAI, Azure, etc.)
+///
+/// Each implementation knows:
+/// - the API URL
+/// - the authentication header
+/// - the form fields sent next to the audio file for the [`TranscriptionOptions`]
+///
+/// [`super::ApiTranscriber`] sends the request.
+pub trait TranscriptionClient: Send + Sync {
+    /// Get the transcription API endpoint URL
+    fn transcription_url(&self) -> String;
+
+    /// Authentication header (name, value)
+    fn auth_header(&self) -> (&'static str, SecretString);
+
+    /// Form fields sent next to the audio file
+    fn form_fields(&self, options: &TranscriptionOptions) -> Vec<FormPart>;
+}
@@ -6,10 +6,16 @@ pub enum TranscriptionError {
     FileNotFound(String),
     #[error("API error: {0}")]
     ApiError(String),
+    /// The API answered with an error status
+    #[error("API returned status {status}: {body}")]
+    HttpStatus { status: u16, body: String },
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
     #[error("API key not configured")]
     ApiKeyMissing,
+    /// The API key is stored but can't be read (e.g. locked keychain), with user guidance
+    #[error("Credentials unavailable: {0}")]
+    CredentialsUnavailable(String),
     #[error("Transcription timed out after {0} seconds")]
     TranscriptionTimeout(u64),
     // Local model errors
@@ -23,6 +29,25 @@ pub enum TranscriptionError {
     ModelLoadFailed(String),
     #[error("Local transcription failed: {0}")]
     LocalTranscriptionFailed(String),
+    #[error("Transcription cancelled")]
+    Cancelled,
+}
+
+impl TranscriptionError {
+    /// Whether another provider might succeed where this one failed: network and
+    /// server errors, rate limits, timeouts, missing credentials or models.
+    /// Problems with the audio itself and cancellation aren't retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            TranscriptionError::FileTooLarge { .. }
+            | TranscriptionError::FileNotFound(_)
+            | TranscriptionError::IoError(_)
+            | TranscriptionError::Cancelled => false,
+            // The request itself was rejected (e.g. unsupported audio)
+            TranscriptionError::HttpStatus { status, .. } => !matches!(status, 400 | 413),
+            _ => true,
+        }
+    }
 }
 
 // TODO: this should be moved to the controller layer
@@ -37,22 +62,21 @@ impl TranscriptionError {
             TranscriptionError::FileNotFound(_) => {
                 "Audio file not found. Please try recording again.".to_string()
             }
-            TranscriptionError::ApiError(msg) => {
-                // Parse for specific errors
-                if msg.contains("429") || msg.to_lowercase().contains("rate limit") {
-                    "Rate limit reached. Please wait and retry.".to_string()
-                } else if msg.contains("401") {
-                    "Invalid API key. Check your settings.".to_string()
-                } else {
-                    format!("Transcription failed: {}", msg)
-                }
+            TranscriptionError::ApiError(msg) => format!("Transcription failed: {}", msg),
+            TranscriptionError::HttpStatus { status: 429, .. } => {
+                "Rate limit reached. Please wait and retry.".to_string()
+            }
+            TranscriptionError::HttpStatus { status: 401, .. } => {
+                "Invalid API key. Check your settings.".to_string()
             }
+            TranscriptionError::HttpStatus { .. } => format!("Transcription failed: {}", self),
             TranscriptionError::IoError(_) => {
                 "Failed to read audio file. Please try again.".to_string()
             }
             TranscriptionError::ApiKeyMissing => {
                 "API key not configured. Please add it in Preferences.".to_string()
             }
+            TranscriptionError::CredentialsUnavailable(guidance) => guidance.clone(),
             TranscriptionError::TranscriptionTimeout(_) => {
                 "Transcription took too long. Try again.".to_string()
             }
@@ -74,6 +98,7 @@ impl TranscriptionError {
             TranscriptionError::LocalTranscriptionFailed(msg) => {
                 format!("Local transcription failed: {}", msg)
             }
+            TranscriptionError::Cancelled => "Transcription cancelled.".to_string(),
         }
     }
 }
@@ -0,0 +1,9 @@
+/// Normalize a language reported by a backend to its ISO-639-1 code
+///
+/// Whisper models report codes ("en"), the OpenAI/Azure `verbose_json` response
+/// reports full names ("english"). Both map to the same whisper.cpp language table.
+pub fn normalize_language_code(language: &str) -> Option<String> {
+    let language = language.trim().to_lowercase();
+    let id = whisper_rs::get_lang_id(&language)?;
+    whisper_rs::get_lang_str(id).map(str::to_string)
+}
@@ -0,0 +1,506 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use log::{debug, error, info, warn};
+use parakeet_rs::{ParakeetTDT, TimestampMode, Transcriber};
+use serde::{Deserialize, Serialize};
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+use super::error::TranscriptionError;
+use super::service::Transcription;
+use crate::audio_file;
+use crate::recorder::SAMPLE_RATE;
+
+/// Receives the transcription progress in percent (0-100), called on the transcribing thread
+pub type ProgressCallback = Arc<dyn Fn(u8) + Send + Sync + 'static>;
+
+/// Parakeet reports no progress, long audio is transcribed in chunks of this length
+const PARAKEET_CHUNK_SECS: usize = 60;
+
+/// Type of transcription model
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "lowercase")]
+pub enum ModelType {
+    Whisper,
+    Parakeet,
+}
+
+/// Whether this build runs Whisper's encoder with Core ML (Apple Silicon only)
+pub fn coreml_supported() -> bool {
+    cfg!(all(target_os = "macos", target_arch = "aarch64"))
+}
+
+/// Core ML encoder whisper.cpp loads for a ggml model, if present
+///
+/// Mirrors whisper.cpp: `ggml-small-q5_1.bin` uses `ggml-small-encoder.mlmodelc`
+/// in the same directory (quantization doesn't change the encoder).
+pub fn coreml_encoder_path(model_path: &Path) -> PathBuf {
+    let mut stem = model_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    // Strip a "-qX_Y" quantization suffix
+    if let Some(pos) = stem.rfind('-') {
+        let suffix = stem[pos..].as_bytes();
+        if suffix.len() == 5 && suffix[1] == b'q' && suffix[3] == b'_' {
+            stem.truncate(pos);
+        }
+    }
+
+    model_path.with_file_name(format!("{}-encoder.mlmodelc", stem))
+}
+
+/// Per-model transcription options
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase", default)]
+pub struct LocalModelOptions {
+    /// Language to transcribe when none is pinned for the session, `None` to auto-detect
+    pub language: Option<String>,
+    /// Compute timestamps (Whisper segments, Parakeet word timings)
+    pub timestamps: bool,
+    /// Whisper beam search width, `None` or 1 for greedy decoding (Parakeet is always greedy)
+    pub beam_size: Option<u32>,
+}
+
+impl Default for LocalModelOptions {
+    fn default() -> Self {
+        Self {
+            language: None,
+            timestamps: true,
+            beam_size: None,
+        }
+    }
+}
+
+/// Unified transcription engine supporting multiple backends
+enum TranscriptionEngine {
+    Whisper(WhisperContext),
+    Parakeet(Box<ParakeetTDT>),
+}
+
+/// Local transcription client for offline transcription.
+/// Supports both Whisper (via whisper.cpp with Metal) and Parakeet (via ONNX Runtime).
+pub struct LocalClient {
+    engine: TranscriptionEngine,
+    model_type: ModelType,
+    /// Languages the model was trained on, empty = multilingual
+    supported_languages: Vec<String>,
+    options: LocalModelOptions,
+}
+
+impl LocalClient {
+    /// Load a transcription model into memory.
+    /// This is a blocking operation that can take several seconds for large models.
+    ///
+    /// # Arguments
+    /// * `model_path` - Path to the model file (Whisper: .bin file, Parakeet: directory)
+    /// * `model_type` - Type of model (Whisper or Parakeet)
+    ///
+    /// # Returns
+    /// * `Ok(LocalClient)` - Model loaded successfully
+    /// * `Err(TranscriptionError)` - Failed to load model
+    pub fn new(model_path: &Path, model_type: ModelType) -> Result<Self, TranscriptionError> {
+        info!("Loading {:?} model from: {:?}", model_type, model_path);
+
+        if !model_path.exists() {
+            return Err(TranscriptionError::ModelNotDownloaded(
+                model_path.to_string_lossy().to_string(),
+            ));
+        }
+
+        let engine = match model_type {
+            ModelType::Whisper => {
+                // whisper.cpp picks up the Core ML encoder next to the model by itself
+                if coreml_supported() {
+                    let encoder_path = coreml_encoder_path(model_path);
+                    if encoder_path.exists() {
+                        info!("Using Core ML encoder: {:?}", encoder_path);
+                    } else {
+                        debug!("No Core ML encoder at {:?}, using Metal", encoder_path);
+                    }
+                }
+
+                let params = WhisperContextParameters::default();
+                let ctx = WhisperContext::new_with_params(&model_path.to_string_lossy(), params)
+                    .map_err(|e| TranscriptionError::ModelLoadFailed(e.to_string()))?;
+                TranscriptionEngine::Whisper(ctx)
+            }
+            ModelType::Parakeet => {
+                // Parakeet expects a directory containing model files
+                let parakeet = ParakeetTDT::from_pretrained(model_path, None)
+                    .map_err(|e| TranscriptionError::ModelLoadFailed(e.to_string()))?;
+                TranscriptionEngine::Parakeet(Box::new(parakeet))
+            }
+        };
+
+        info!("{:?} model loaded successfully", model_type);
+        Ok(Self {
+            engine,
+            model_type,
+            supported_languages: Vec::new(),
+            options: LocalModelOptions::default(),
+        })
+    }
+
+    /// Restrict the model to the languages it supports (e.g. `["en"]` for
+    /// distil-whisper and `.en` models).
+    ///
+    /// Whisper then skips language detection for single-language models and
+    /// ignores pinned languages the model can't transcribe.
+    pub fn with_supported_languages(mut self, languages: Vec<String>) -> Self {
+        self.supported_languages = languages;
+        self
+    }
+
+    /// Set the options used by the next transcriptions
+    pub fn set_options(&mut self, options: LocalModelOptions) {
+        self.options = options;
+    }
+
+    /// Language to pass to Whisper for a pinned (or auto-detect) `language`
+    fn whisper_language(&self, language: Option<&str>) -> Option<String> {
+        if self.supported_languages.is_empty() {
+            return language.map(str::to_string);
+        }
+
+        if let Some(language) = language {
+            if self.supported_languages.iter().any(|l| l == language) {
+                return Some(language.to_string());
+            }
+            warn!(
+                "Model doesn't support language '{}', supported: {:?}",
+                language, self.supported_languages
+            );
+        }
+
+        match self.supported_languages.as_slice() {
+            [only] => Some(only.clone()),
+            _ => None,
+        }
+    }
+
+    /// Transcribe an audio file to text.
+    ///
+    /// # Arguments
+    /// * `audio_path` - Path to the audio file (WAV format, 16kHz mono preferred)
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transcribed text
+    /// * `Err(TranscriptionError)` - Transcription failed
+    pub fn transcribe_file(&mut self, audio_path: &Path) -> Result<String, TranscriptionError> {
+        self.transcribe_file_with_language(audio_path, None)
+            .map(|transcription| transcription.text)
+    }
+
+    /// Transcribe an audio file, pinning or auto-detecting the language.
+    ///
+    /// # Arguments
+    /// * `audio_path` - Path to the audio file (WAV format, 16kHz mono)
+    /// * `language` - Language code to pin (e.g. "en"), `None` to auto-detect
+    ///
+    /// Only Whisper detects the language. Parakeet is multilingual without
+    /// language identification or conditioning, it reports the pinned language
+    /// (or the model's language hint) as is.
+    pub fn transcribe_file_with_language(
+        &mut self,
+        audio_path: &Path,
+        language: Option<&str>,
+    ) -> Result<Transcription, TranscriptionError> {
+        self.transcribe_file_with_progress(audio_path, language, None, None)
+    }
+
+    /// Transcribe an audio file, reporting progress to `on_progress`.
+    ///
+    /// Whisper reports whisper.cpp's own progress, Parakeet the share of
+    /// transcribed chunks (audio longer than a minute is split into chunks).
+    ///
+    /// Setting `cancel` aborts the transcription with [`TranscriptionError::Cancelled`]:
+    /// Whisper stops at its next abort check, Parakeet after the current chunk.
+    pub fn transcribe_file_with_progress(
+        &mut self,
+        audio_path: &Path,
+        language: Option<&str>,
+        on_progress: Option<ProgressCallback>,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> Result<Transcription, TranscriptionError> {
+        debug!(
+            "Transcribing file with {:?}: {:?}",
+            self.model_type, audio_path
+        );
+
+        // Load audio samples (both engines use the same format)
+        let samples = self.load_audio(audio_path)?;
+
+        // The session's pinned language wins over the model's language hint
+        let language = language
+            .map(str::to_string)
+            .or_else(|| self.options.language.clone());
+        let whisper_language = self.whisper_language(language.as_deref());
+        let timestamps = self.options.timestamps;
+        let beam_size = self.options.beam_size;
+
+        let (text, transcription_language, detected) = match &mut self.engine {
+            TranscriptionEngine::Whisper(ctx) => {
+                // Create transcription state
+                let mut state = ctx
+                    .create_state()
+                    .map_err(|e| TranscriptionError::LocalTranscriptionFailed(e.to_string()))?;
+
+                // Configure transcription parameters
+                let strategy = match beam_size {
+                    Some(beam_size) if beam_size > 1 => SamplingStrategy::BeamSearch {
+                        beam_size: beam_size as i32,
+                        patience: -1.0,
+                    },
+                    _ => SamplingStrategy::Greedy { best_of: 1 },
+                };
+                let mut params = FullParams::new(strategy);
+
+                // Use the pinned language or auto-detect
+                params.set_language(Some(whisper_language.as_deref().unwrap_or("auto")));
+
+                // Disable printing to stdout
+                params.set_print_special(false);
+                params.set_print_progress(false);
+                params.set_print_realtime(false);
+                params.set_print_timestamps(false);
+
+                params.set_no_timestamps(!timestamps);
+
+                if let Some(on_progress) = on_progress {
+                    params.set_progress_callback_safe(move |progress: i32| {
+                        on_progress(progress.clamp(0, 100) as u8)
+                    });
+                }
+
+                if let Some(cancel) = cancel.clone() {
+                    params.set_abort_callback_safe(move || cancel.load(Ordering::Relaxed));
+                }
+
+                // Run transcription
+                let result = state.full(params, &samples);
+                check_cancelled(cancel.as_deref())?;
+                result.map_err(|e| TranscriptionError::LocalTranscriptionFailed(e.to_string()))?;
+
+                // Language whisper transcribed with (detected or pinned)
+                let transcription_language = state
+                    .full_lang_id_from_state()
+                    .ok()
+                    .and_then(whisper_rs::get_lang_str)
+                    .map(str::to_string);
+
+                // Extract text from segments
+                (
+                    self.extract_whisper_text(&state)?,
+                    transcription_language,
+                    // Detected only when whisper ran with "auto"
+                    whisper_language.is_none(),
+                )
+            }
+            TranscriptionEngine::Parakeet(parakeet) => (
+                Self::transcribe_parakeet(
+                    parakeet,
+                    audio_path,
+                    &samples,
+                    timestamps.then_some(TimestampMode::Words),
+                    on_progress,
+                    cancel.as_deref(),
+                )?,
+                language,
+                false,
+            ),
+        };
+
+        info!(
+            "Transcription complete: {} characters, language: {:?} (detected: {})",
+            text.len(),
+            transcription_language,
+            detected
+        );
+        Ok(Transcription {
+            text,
+            detected: detected && transcription_language.is_some(),
+            language: transcription_language,
+        })
+    }
+
+    /// Transcribe with Parakeet, chunk by chunk for long audio to report progress
+    fn transcribe_parakeet(
+        parakeet: &mut ParakeetTDT,
+        audio_path: &Path,
+        samples: &[f32],
+        timestamp_mode: Option<TimestampMode>,
+        on_progress: Option<ProgressCallback>,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<String, TranscriptionError> {
+        let chunk_samples = PARAKEET_CHUNK_SECS * SAMPLE_RATE as usize;
+
+        // Use the file path directly (parakeet handles audio loading internally)
+        if samples.len() <= chunk_samples {
+            let result = parakeet.transcribe_file(audio_path, timestamp_mode);
+            check_cancelled(cancel)?;
+            let result =
+                result.map_err(|e| TranscriptionError::LocalTranscriptionFailed(e.to_string()))?;
+            if let Some(on_progress) = &on_progress {
+                on_progress(100);
+            }
+            return Ok(result.text);
+        }
+
+        let chunk_path = audio_path.with_extension("chunk.wav");
+        let total_chunks = samples.len().div_ceil(chunk_samples);
+        let mut texts = Vec::with_capacity(total_chunks);
+
+        for (index, chunk) in samples.chunks(chunk_samples).enumerate() {
+            check_cancelled(cancel)?;
+            audio_file::write_wav_16k_mono(&chunk_path, chunk)
+                .map_err(|e| TranscriptionError::LocalTranscriptionFailed(e.to_string()))?;
+            let result = parakeet.transcribe_file(&chunk_path, timestamp_mode);
+            let _ = std::fs::remove_file(&chunk_path);
+            check_cancelled(cancel)?;
+
+            let result =
+                result.map_err(|e| TranscriptionError::LocalTranscriptionFailed(e.to_string()))?;
+            texts.push(result.text);
+
+            if let Some(on_progress) = &on_progress {
+                on_progress(((index + 1) * 100 / total_chunks) as u8);
+            }
+        }
+
+        Ok(stitch_chunks(&texts))
+    }
+
+    /// Load audio file as f32 samples.
+    /// Expects 16kHz mono WAV (as produced by the recording layer).
+    fn load_audio(&self, audio_path: &Path) -> Result<Vec<f32>, TranscriptionError> {
+        let reader = hound::WavReader::open(audio_path).map_err(|e| {
+            error!("Failed to open audio file: {}", e);
+            TranscriptionError::IoError(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Failed to open audio file: {}", e),
+            ))
+        })?;
+
+        let spec = reader.spec();
+        debug!(
+            "Audio spec: {} Hz, {} channels, {} bits",
+            spec.sample_rate, spec.channels, spec.bits_per_sample
+        );
+
+        // Verify expected format (recording layer provides 16kHz mono)
+        if spec.sample_rate != 16000 {
+            error!(
+                "Audio format error: sample rate {} Hz (expected 16000 Hz)",
+                spec.sample_rate
+            );
+            return Err(TranscriptionError::LocalTranscriptionFailed(format!(
+                "Audio file has unsupported sample rate ({} Hz). \
+                The recording system should produce 16kHz audio. \
+                This may indicate a recording configuration issue.",
+                spec.sample_rate
+            )));
+        }
+        if spec.channels != 1 {
+            error!(
+                "Audio format error: {} channels (expected 1 mono)",
+                spec.channels
+            );
+            return Err(TranscriptionError::LocalTranscriptionFailed(format!(
+                "Audio file has {} channels but mono (1 channel) is required. \
+                The recording system should produce mono audio. \
+                This may indicate a recording configuration issue.",
+                spec.channels
+            )));
+        }
+
+        // Read samples based on format
+        let samples: Vec<f32> = match spec.sample_format {
+            hound::SampleFormat::Int => {
+                let max_val = (1 << (spec.bits_per_sample - 1)) as f32;
+                reader
+                    .into_samples::<i32>()
+                    .filter_map(Result::ok)
+                    .map(|s| s as f32 / max_val)
+                    .collect()
+            }
+            hound::SampleFormat::Float => reader
+                .into_samples::<f32>()
+                .filter_map(Result::ok)
+                .collect(),
+        };
+
+        debug!("Loaded {} audio samples", samples.len());
+        Ok(samples)
+    }
+
+    /// Extract transcribed text from all Whisper segments.
+    fn extract_whisper_text(
+        &self,
+        state: &whisper_rs::WhisperState,
+    ) -> Result<String, TranscriptionError> {
+        let num_segments = state.full_n_segments().map_err(|e| {
+            TranscriptionError::LocalTranscriptionFailed(format!("Failed to get segments: {}", e))
+        })?;
+
+        let mut text = String::new();
+
+        for i in 0..num_segments {
+            let segment_text = state.full_get_segment_text(i).map_err(|e| {
+                TranscriptionError::LocalTranscriptionFailed(format!(
+                    "Failed to get segment text: {}",
+                    e
+                ))
+            })?;
+            text.push_str(&segment_text);
+        }
+
+        Ok(text.trim().to_string())
+    }
+}
+
+/// `Err(Cancelled)` once `cancel` is set
+fn check_cancelled(cancel: Option<&AtomicBool>) -> Result<(), TranscriptionError> {
+    match cancel {
+        Some(cancel) if cancel.load(Ordering::Relaxed) => Err(TranscriptionError::Cancelled),
+        _ => Ok(()),
+    }
+}
+
+/// Join the transcripts of consecutive chunks, skipping chunks without speech
+fn stitch_chunks(texts: &[String]) -> String {
+    texts
+        .iter()
+        .map(|text| text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stitch_chunks() {
+        let texts = [" Ask not what", "", "your country ", "  "].map(String::from);
+        assert_eq!(stitch_chunks(&texts), "Ask not what your country");
+        assert_eq!(stitch_chunks(&[]), "");
+    }
+
+    #[test]
+    fn test_coreml_encoder_path() {
+        assert_eq!(
+            coreml_encoder_path(Path::new("/models/whisper-small/ggml-small.bin")),
+            PathBuf::from("/models/whisper-small/ggml-small-encoder.mlmodelc")
+        );
+        assert_eq!(
+            coreml_encoder_path(Path::new("/models/ggml-large-v3-turbo-q5_0.bin")),
+            PathBuf::from("/models/ggml-large-v3-turbo-encoder.mlmodelc")
+        );
+    }
+}
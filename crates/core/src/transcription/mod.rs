@@ -0,0 +1,32 @@
+//! Transcription building blocks.
+//!
+//! - [`TranscriptionService`]: high-level "audio file in, text out" abstraction
+//! - [`ApiTranscriber`] + [`TranscriptionClient`]: HTTP based providers (OpenAI, Azure OpenAI)
+//! - [`TranscriptionOptions`]: model, language, prompt and temperature of a request
+//! - [`LocalClient`]: on-device models (Whisper via whisper.cpp, Parakeet via ONNX Runtime)
+//! - [`TranscriptSegment`]: timestamped piece of a transcript (file transcription, subtitles)
+
+mod api_transcriber;
+mod azure_client;
+mod client;
+mod error;
+mod language;
+mod local_client;
+mod openai_client;
+mod options;
+mod segment;
+mod service;
+
+pub use api_transcriber::{ApiTranscriber, TRANSCRIPTION_TIMEOUT_SECS};
+pub use azure_client::AzureClient;
+pub use client::TranscriptionClient;
+pub use error::TranscriptionError;
+pub use language::normalize_language_code;
+pub use local_client::{
+    coreml_encoder_path, coreml_supported, LocalClient, LocalModelOptions, ModelType,
+    ProgressCallback,
+};
+pub use openai_client::OpenAIClient;
+pub use options::TranscriptionOptions;
+pub use segment::TranscriptSegment;
+pub use service::{Transcription, TranscriptionService};
@@ -0,0 +1,39 @@
+use secrecy::{ExposeSecret, SecretString};
+
+use super::client::TranscriptionClient;
+use super::options::TranscriptionOptions;
+use crate::http::FormPart;
+
+const OPENAI_TRANSCRIPTION_URL: &str = "https://api.openai.com/v1/audio/transcriptions";
+const OPENAI_MODEL: &str = "whisper-1";
+
+/// OpenAI Whisper API client
+pub struct OpenAIClient {
+    api_key: SecretString,
+}
+
+impl OpenAIClient {
+    pub fn new(api_key: SecretString) -> Self {
+        Self { api_key }
+    }
+}
+
+impl TranscriptionClient for OpenAIClient {
+    fn transcription_url(&self) -> String {
+        OPENAI_TRANSCRIPTION_URL.to_string()
+    }
+
+    fn auth_header(&self) -> (&'static str, SecretString) {
+        (
+            "Authorization",
+            SecretString::from(format!("Bearer {}", self.api_key.expose_secret())),
+        )
+    }
+
+    fn form_fields(&self, options: &TranscriptionOptions) -> Vec<FormPart> {
+        let model = options.model.as_deref().unwrap_or(OPENAI_MODEL);
+        let mut fields = vec![FormPart::text("model", model)];
+        fields.extend(options.form_fields());
+        fields
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+use crate::http::FormPart;
+
+/// Response format including the detected language (and segments), requested only
+/// when the language is auto-detected
+const VERBOSE_RESPONSE_FORMAT: &str = "verbose_json";
+
+/// Response format with the text alone. The only one some Azure deployments accept
+/// (gpt-4o-transcribe), and smaller to send back.
+const RESPONSE_FORMAT: &str = "json";
+
+/// Options of a transcription request, the same for every provider.
+///
+/// API clients send them as form fields; local models only use the language.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase", default)]
+pub struct TranscriptionOptions {
+    /// Model to transcribe with, `None` for the provider default (OpenAI: whisper-1).
+    /// Azure ignores it, the deployment in the endpoint picks the model.
+    pub model: Option<String>,
+    /// Language code to pin (e.g. "en"), `None` to auto-detect
+    pub language: Option<String>,
+    /// Text the transcript continues, guides spelling of names and jargon
+    pub prompt: Option<String>,
+    /// Sampling temperature from 0.0 (most deterministic) to 1.0
+    pub temperature: f32,
+}
+
+impl Default for TranscriptionOptions {
+    fn default() -> Self {
+        Self {
+            model: None,
+            language: None,
+            prompt: None,
+            temperature: 0.0,
+        }
+    }
+}
+
+impl TranscriptionOptions {
+    /// Highest accepted temperature
+    pub const MAX_TEMPERATURE: f32 = 1.0;
+
+    /// Options pinning `language`
+    pub fn with_language(language: Option<&str>) -> Self {
+        Self {
+            language: language.map(str::to_string),
+            ..Self::default()
+        }
+    }
+
+    /// Reject options the APIs would refuse
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=Self::MAX_TEMPERATURE).contains(&self.temperature) {
+            return Err(format!(
+                "Temperature must be between 0 and {}",
+                Self::MAX_TEMPERATURE
+            ));
+        }
+        if self
+            .model
+            .as_deref()
+            .is_some_and(|model| model.trim().is_empty())
+        {
+            return Err("Model name can't be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Form fields shared by all API providers (the model is up to the client)
+    pub(super) fn form_fields(&self) -> Vec<FormPart> {
+        let response_format = match self.language {
+            Some(_) => RESPONSE_FORMAT,
+            None => VERBOSE_RESPONSE_FORMAT,
+        };
+        let mut fields = vec![
+            FormPart::text("temperature", self.temperature.to_string()),
+            FormPart::text("response_format", response_format),
+        ];
+        if let Some(prompt) = self.prompt.as_deref().filter(|p| !p.trim().is_empty()) {
+            fields.push(FormPart::text("prompt", prompt));
+        }
+        if let Some(language) = &self.language {
+            fields.push(FormPart::text("language", language.as_str()));
+        }
+        fields
+    }
+}
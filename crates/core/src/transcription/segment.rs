@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// A piece of transcribed text with its position in the source audio
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptSegment {
+    /// Start of the segment in milliseconds from the beginning of the audio
+    pub start_ms: u64,
+    /// End of the segment in milliseconds from the beginning of the audio
+    pub end_ms: u64,
+    pub text: String,
+}
@@ -0,0 +1,52 @@
+//! High-level transcription service abstraction.
+//!
+//! This trait abstracts away the transcription implementation details,
+//! allowing uniform handling of both API-based (OpenAI, Azure) and
+//! local (Whisper) transcription.
+
+use std::path::Path;
+
+use super::error::TranscriptionError;
+use super::options::TranscriptionOptions;
+
+/// Result of a transcription with the metadata reported by the backend
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transcription {
+    /// Transcribed text
+    pub text: String,
+    /// Language code (ISO-639-1, e.g. "en") of the transcription, if the backend reports one
+    pub language: Option<String>,
+    /// Whether the backend detected `language`, rather than transcribing with the pinned
+    /// language (or the model's language hint)
+    pub detected: bool,
+}
+
+/// High-level transcription service abstraction.
+///
+/// Implementations can be API-based (OpenAI, Azure) or local (Whisper).
+/// The caller doesn't need to know which implementation is being used.
+pub trait TranscriptionService: Send + Sync {
+    /// Transcribe audio file to text.
+    ///
+    /// # Arguments
+    /// * `audio_path` - Path to the audio file (WAV format, 16kHz mono)
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transcribed text
+    /// * `Err(TranscriptionError)` - Transcription failed
+    fn transcribe(&self, audio_path: &Path) -> Result<String, TranscriptionError> {
+        self.transcribe_detailed(audio_path, &TranscriptionOptions::default())
+            .map(|transcription| transcription.text)
+    }
+
+    /// Transcribe audio file, reporting the detected language.
+    ///
+    /// # Arguments
+    /// * `audio_path` - Path to the audio file (WAV format, 16kHz mono)
+    /// * `options` - Model, pinned language, prompt and temperature of the request
+    fn transcribe_detailed(
+        &self,
+        audio_path: &Path,
+        options: &TranscriptionOptions,
+    ) -> Result<Transcription, TranscriptionError>;
+}
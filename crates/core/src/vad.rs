@@ -360,4 +360,48 @@ mod tests {
         // Context size is 64 for 16kHz
         assert_eq!(CONTEXT_SIZE, 64);
     }
+
+    /// Reports speech for frames whose first sample is non-zero
+    struct ScriptedVad;
+
+    impl VoiceActivityDetector for ScriptedVad {
+        fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> Result<VadFrame<'a>, VadError> {
+            Ok(if frame[0] != 0.0 {
+                VadFrame::Speech(frame)
+            } else {
+                VadFrame::Noise
+            })
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    /// Samples kept by `vad` for frames tagged with their index (0 = silence)
+    fn run(vad: &mut SmoothedVad, speech: &[bool]) -> Vec<f32> {
+        let mut kept = Vec::new();
+        for (index, is_speech) in speech.iter().enumerate() {
+            let value = if *is_speech { index as f32 + 1.0 } else { 0.0 };
+            let frame = [value, -(index as f32)];
+            if let VadFrame::Speech(samples) = vad.push_frame(&frame).unwrap() {
+                kept.extend_from_slice(samples);
+            }
+        }
+        kept
+    }
+
+    #[test]
+    fn test_smoothed_vad_prefill_and_hangover() {
+        let mut vad = SmoothedVad::new(Box::new(ScriptedVad), 1, 1, 2);
+        let kept = run(&mut vad, &[false, false, true, true, false, false, false]);
+
+        // Onset on frame 3 returns the prefill (frame 2) with it, the hangover keeps
+        // silent frame 4
+        assert_eq!(kept, vec![3.0, -2.0, 4.0, -3.0, 0.0, -4.0]);
+    }
+
+    #[test]
+    fn test_smoothed_vad_ignores_blips() {
+        let mut vad = SmoothedVad::new(Box::new(ScriptedVad), 1, 1, 2);
+        assert!(run(&mut vad, &[true, false, true, false]).is_empty());
+    }
 }
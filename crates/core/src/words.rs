@@ -0,0 +1,78 @@
+//! Word tokenizer of the text passes run on a transcript.
+//!
+//! Number formatting ([`crate::normalize`]), voice snippets and the redaction filter of
+//! the app all match on the same words: whitespace separated tokens trimmed of the
+//! punctuation the transcriber adds around them ("Hello," is "Hello"). Inner
+//! punctuation stays part of the word ("twenty-three", "jane@example.com").
+
+use std::ops::Range;
+
+/// Byte ranges of the words of `text`, from the first to the last alphanumeric
+/// character of each token. Punctuation-only tokens are skipped.
+pub fn word_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    // Start and end of the alphanumeric characters of the current token
+    let mut word: Option<Range<usize>> = None;
+    for (index, c) in text.char_indices() {
+        if c.is_whitespace() {
+            ranges.extend(word.take());
+        } else if c.is_alphanumeric() {
+            let end = index + c.len_utf8();
+            match &mut word {
+                Some(word) => word.end = end,
+                None => word = Some(index..end),
+            }
+        }
+    }
+    ranges.extend(word);
+    ranges
+}
+
+/// Lowercase letters and digits of `text`, to compare words ignoring case and punctuation
+pub fn normalized(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<&str> {
+        word_ranges(text).into_iter().map(|r| &text[r]).collect()
+    }
+
+    #[test]
+    fn test_word_ranges() {
+        assert_eq!(
+            words("  Hello, world! (twenty-three)  "),
+            ["Hello", "world", "twenty-three"]
+        );
+        assert_eq!(
+            words("Mail jane@example.com."),
+            ["Mail", "jane@example.com"]
+        );
+        assert_eq!(words("Café — ça va ?"), ["Café", "ça", "va"]);
+        assert_eq!(
+            words("über\tStraße\nNächste"),
+            ["über", "Straße", "Nächste"]
+        );
+        assert!(words(" ... -- ").is_empty());
+        assert!(words("").is_empty());
+    }
+
+    #[test]
+    fn test_word_ranges_of_repeated_words() {
+        // Offsets point at each occurrence, not the first one
+        assert_eq!(word_ranges("no, no no"), [0..2, 4..6, 7..9]);
+    }
+
+    #[test]
+    fn test_normalized() {
+        assert_eq!(normalized("Insert,"), "insert");
+        assert_eq!(normalized("Twenty-Three"), "twentythree");
+        assert_eq!(normalized("ÉTÉ"), "été");
+    }
+}
@@ -0,0 +1,123 @@
+//! Fixtures and comparison helpers of the integration tests.
+//!
+//! Fixtures live in `tests/fixtures`: `<name>.wav` (16kHz mono) with the expected
+//! transcript in `<name>.<language>.txt`. Models aren't checked in, see
+//! `scripts/fetch-test-fixtures.sh`.
+
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+/// Word error rate a golden transcript may differ by (the tiny model misses a word here
+/// and there, a regression garbles whole sentences)
+pub const MAX_WORD_ERROR_RATE: f64 = 0.15;
+
+pub fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/// Model file from `env_var`, or `default` in the fixtures directory
+pub fn model_path(env_var: &str, default: &str) -> PathBuf {
+    let path = std::env::var_os(env_var)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| fixtures_dir().join(default));
+    assert!(
+        path.exists(),
+        "{} not found, run scripts/fetch-test-fixtures.sh or set {}",
+        path.display(),
+        env_var
+    );
+    path
+}
+
+/// Silero VAD model bundled with the app
+pub fn vad_model_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../src-tauri/resources/models/silero_vad_v6.onnx")
+}
+
+/// A fixture recording with its expected transcript
+pub struct GoldenFixture {
+    pub name: String,
+    pub audio: PathBuf,
+    pub language: String,
+    pub transcript: String,
+}
+
+/// All fixtures with a golden transcript, sorted by name
+pub fn golden_fixtures() -> Vec<GoldenFixture> {
+    let mut fixtures: Vec<GoldenFixture> = std::fs::read_dir(fixtures_dir())
+        .expect("fixtures directory")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            // jfk.en.txt -> jfk.wav, "en"
+            let stem = Path::new(path.file_stem()?);
+            let language = stem.extension()?.to_str()?.to_string();
+            let name = stem.file_stem()?.to_str()?.to_string();
+            let audio = fixtures_dir().join(format!("{}.wav", name));
+            assert!(
+                audio.exists(),
+                "{} has no audio, run scripts/fetch-test-fixtures.sh",
+                path.display()
+            );
+            Some(GoldenFixture {
+                name,
+                audio,
+                language,
+                transcript: std::fs::read_to_string(&path).ok()?,
+            })
+        })
+        .collect();
+    fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+    assert!(!fixtures.is_empty(), "no golden transcripts in fixtures");
+    fixtures
+}
+
+/// Words of a transcript, ignoring case and punctuation
+pub fn normalized_words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric() || *c == '\'')
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Word-level edit distance of `actual` to `expected`, relative to the expected length
+pub fn word_error_rate(expected: &str, actual: &str) -> f64 {
+    let expected = normalized_words(expected);
+    let actual = normalized_words(actual);
+    if expected.is_empty() {
+        return if actual.is_empty() { 0.0 } else { 1.0 };
+    }
+
+    let mut previous: Vec<usize> = (0..=actual.len()).collect();
+    for (i, expected_word) in expected.iter().enumerate() {
+        let mut current = vec![i + 1; actual.len() + 1];
+        for (j, actual_word) in actual.iter().enumerate() {
+            let substitution = previous[j] + usize::from(expected_word != actual_word);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[actual.len()] as f64 / expected.len() as f64
+}
+
+/// Fail with both transcripts if they differ by more than [`MAX_WORD_ERROR_RATE`]
+pub fn assert_matches_golden(fixture: &GoldenFixture, actual: &str) {
+    let wer = word_error_rate(&fixture.transcript, actual);
+    assert!(
+        wer <= MAX_WORD_ERROR_RATE,
+        "{}: word error rate {:.2}\n  expected: {}\n  actual:   {}",
+        fixture.name,
+        wer,
+        fixture.transcript.trim(),
+        actual.trim()
+    );
+}
@@ -0,0 +1,76 @@
+//! Local transcription of the fixture recordings against their golden transcripts.
+//!
+//! `cargo test -p dictara-core --features integration-tests`
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::{assert_matches_golden, golden_fixtures, model_path};
+use dictara_core::audio_file::{load_audio_file, write_wav_16k_mono};
+use dictara_core::transcription::{LocalClient, ModelType};
+
+#[test]
+fn test_whisper_tiny_matches_golden_transcripts() {
+    let model = model_path("DICTARA_TEST_WHISPER_MODEL", "ggml-tiny.bin");
+    let mut client = LocalClient::new(&model, ModelType::Whisper).expect("load Whisper tiny");
+
+    for fixture in golden_fixtures() {
+        let transcription = client
+            .transcribe_file_with_language(&fixture.audio, Some(&fixture.language))
+            .unwrap_or_else(|e| panic!("{}: {}", fixture.name, e));
+        assert_matches_golden(&fixture, &transcription.text);
+    }
+}
+
+/// Audio longer than a chunk is transcribed chunk by chunk and stitched back together
+#[test]
+#[ignore = "needs a Parakeet model in DICTARA_TEST_PARAKEET_MODEL"]
+fn test_parakeet_stitches_long_audio() {
+    let model = model_path("DICTARA_TEST_PARAKEET_MODEL", "parakeet-tdt-0.6b-v3-int8");
+    let mut client = LocalClient::new(&model, ModelType::Parakeet).expect("load Parakeet");
+
+    let fixture = golden_fixtures().remove(0);
+    let samples = load_audio_file(&fixture.audio).expect("decode fixture");
+    // Just over a minute: two chunks, the second one short
+    let repeats = 60 * 16_000 / samples.len() + 1;
+    let long: Vec<f32> = samples
+        .iter()
+        .copied()
+        .cycle()
+        .take(samples.len() * repeats)
+        .collect();
+
+    let dir = std::env::temp_dir().join(format!("dictara-golden-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let long_path = dir.join("long.wav");
+    write_wav_16k_mono(&long_path, &long).expect("write long fixture");
+
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let progress_log = progress.clone();
+    let transcription = client
+        .transcribe_file_with_progress(
+            &long_path,
+            Some(&fixture.language),
+            Some(Arc::new(move |percent: u8| {
+                progress_log.lock().unwrap().push(percent)
+            })),
+            None,
+        )
+        .expect("transcribe long fixture");
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(*progress.lock().unwrap(), vec![50, 100]);
+    // Chunk boundaries cut through a word, so only the first repetition is compared
+    let first = transcription
+        .text
+        .split_whitespace()
+        .take(common::normalized_words(&fixture.transcript).len())
+        .collect::<Vec<_>>()
+        .join(" ");
+    assert_matches_golden(&fixture, &first);
+    assert!(
+        !transcription.text.contains("  "),
+        "stitched with doubled spaces"
+    );
+}
@@ -0,0 +1,84 @@
+//! Silero VAD with the recorder's smoothing on fixture audio.
+//!
+//! `cargo test -p dictara-core --features integration-tests`
+
+mod common;
+
+use common::{golden_fixtures, vad_model_path};
+use dictara_core::audio_file::load_audio_file;
+use dictara_core::recorder::{
+    SAMPLE_RATE, VAD_HANGOVER_FRAMES, VAD_ONSET_FRAMES, VAD_PREFILL_FRAMES, VAD_THRESHOLD,
+};
+use dictara_core::vad::{SileroVad, SmoothedVad, VadFrame, VoiceActivityDetector, FRAME_SAMPLES};
+
+const ONE_SECOND: usize = SAMPLE_RATE as usize;
+
+/// Silero + smoothing as set up by the recorder
+fn recorder_vad() -> SmoothedVad {
+    let silero = SileroVad::new(vad_model_path(), VAD_THRESHOLD).expect("load Silero VAD");
+    SmoothedVad::new(
+        Box::new(silero),
+        VAD_PREFILL_FRAMES,
+        VAD_HANGOVER_FRAMES,
+        VAD_ONSET_FRAMES,
+    )
+}
+
+/// Samples the VAD keeps, frame by frame like the recorder (a trailing partial frame
+/// is dropped)
+fn speech_samples(vad: &mut dyn VoiceActivityDetector, samples: &[f32]) -> Vec<f32> {
+    let mut speech = Vec::new();
+    for frame in samples.chunks_exact(FRAME_SAMPLES) {
+        if let VadFrame::Speech(kept) = vad.push_frame(frame).unwrap() {
+            speech.extend_from_slice(kept);
+        }
+    }
+    speech
+}
+
+#[test]
+fn test_silence_is_dropped() {
+    let mut vad = recorder_vad();
+    let silence = vec![0.0; ONE_SECOND * 3];
+    assert!(speech_samples(&mut vad, &silence).is_empty());
+}
+
+#[test]
+fn test_speech_is_kept_and_padding_dropped() {
+    for fixture in golden_fixtures() {
+        let samples = load_audio_file(&fixture.audio).expect("decode fixture");
+
+        let mut padded = vec![0.0; ONE_SECOND * 2];
+        padded.extend_from_slice(&samples);
+        padded.resize(padded.len() + ONE_SECOND * 2, 0.0);
+
+        let mut vad = recorder_vad();
+        let speech = speech_samples(&mut vad, &padded);
+
+        // Most of the recording survives, the added silence (minus hangover) doesn't
+        assert!(
+            speech.len() > samples.len() / 2,
+            "{}: kept {} of {} samples",
+            fixture.name,
+            speech.len(),
+            samples.len()
+        );
+        assert!(
+            speech.len() < padded.len() - ONE_SECOND * 2,
+            "{}: silence padding not dropped",
+            fixture.name
+        );
+    }
+}
+
+#[test]
+fn test_reset_between_recordings() {
+    let fixture = golden_fixtures().remove(0);
+    let samples = load_audio_file(&fixture.audio).expect("decode fixture");
+    let mut vad = recorder_vad();
+
+    // Stop mid-speech, the next recording starts in silence
+    speech_samples(&mut vad, &samples[..samples.len() / 2]);
+    vad.reset();
+    assert!(speech_samples(&mut vad, &vec![0.0; ONE_SECOND]).is_empty());
+}
@@ -0,0 +1,97 @@
+//! Physical keyboard that generated an event.
+//!
+//! Lets applications tell the built-in keyboard from external ones, e.g. to use
+//! different shortcuts on keyboards without a usable Fn key.
+
+/// A physical keyboard, identified by its USB/Bluetooth vendor and product ids
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyboardDevice {
+    pub vendor_id: u32,
+    pub product_id: u32,
+    /// Product name reported by the keyboard
+    pub name: Option<String>,
+    /// Built into the computer (e.g. a MacBook keyboard)
+    pub built_in: bool,
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) use macos::device_for_sender;
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::ffi::{c_char, c_void};
+    use std::ptr::NonNull;
+
+    use objc2_core_foundation::{CFBoolean, CFNumber, CFRetained, CFString, CFType};
+
+    use super::KeyboardDevice;
+
+    /// `MACH_PORT_NULL`: use the default IOKit main port
+    const DEFAULT_MAIN_PORT: u32 = 0;
+    /// `kIORegistryIterateRecursively | kIORegistryIterateParents`: the HID event
+    /// service inherits the device properties from its parents
+    const SEARCH_PARENTS: u32 = 0x1 | 0x2;
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IORegistryEntryIDMatching(entry_id: u64) -> *mut c_void;
+        fn IOServiceGetMatchingService(main_port: u32, matching: *mut c_void) -> u32;
+        fn IORegistryEntrySearchCFProperty(
+            entry: u32,
+            plane: *const c_char,
+            key: &CFString,
+            allocator: *const c_void,
+            options: u32,
+        ) -> Option<NonNull<CFType>>;
+        fn IOObjectRelease(object: u32) -> i32;
+    }
+
+    /// Look up the keyboard behind an IOHIDService registry entry id
+    pub(crate) fn device_for_sender(sender_id: u64) -> Option<KeyboardDevice> {
+        // SAFETY: the matching dictionary is consumed by IOServiceGetMatchingService,
+        // the service is released below and properties are retained by CFRetained
+        unsafe {
+            let matching = IORegistryEntryIDMatching(sender_id);
+            if matching.is_null() {
+                return None;
+            }
+            let service = IOServiceGetMatchingService(DEFAULT_MAIN_PORT, matching);
+            if service == 0 {
+                return None;
+            }
+
+            let property = |key: &str| {
+                IORegistryEntrySearchCFProperty(
+                    service,
+                    c"IOService".as_ptr(),
+                    &CFString::from_str(key),
+                    std::ptr::null(),
+                    SEARCH_PARENTS,
+                )
+                .map(|value| CFRetained::from_raw(value))
+            };
+            let number = |key: &str| {
+                property(key).and_then(|value| value.downcast_ref::<CFNumber>()?.as_i64())
+            };
+
+            let device = match (number("VendorID"), number("ProductID")) {
+                (Some(vendor_id), Some(product_id)) => Some(KeyboardDevice {
+                    vendor_id: vendor_id as u32,
+                    product_id: product_id as u32,
+                    name: property("Product")
+                        .and_then(|value| Some(value.downcast_ref::<CFString>()?.to_string())),
+                    built_in: property("Built-In").is_some_and(|value| {
+                        match value.downcast_ref::<CFBoolean>() {
+                            Some(built_in) => built_in.as_bool(),
+                            None => number("Built-In").is_some_and(|built_in| built_in != 0),
+                        }
+                    }),
+                }),
+                _ => None,
+            };
+
+            IOObjectRelease(service);
+            device
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Event types for keyboard events.
 
-use crate::Key;
+use crate::{Key, KeyboardDevice};
 use std::time::SystemTime;
 
 /// Type of keyboard event.
@@ -26,6 +26,10 @@ pub struct Event {
     /// taking into account the current keyboard layout and modifiers.
     /// This is `None` for non-character keys like Shift, Ctrl, etc.
     pub name: Option<String>,
+
+    /// The physical keyboard that generated the event, `None` for synthetic
+    /// events or when the device couldn't be identified.
+    pub device: Option<KeyboardDevice>,
 }
 
 impl Event {
@@ -35,6 +39,7 @@ impl Event {
             time: SystemTime::now(),
             event_type,
             name: None,
+            device: None,
         }
     }
 
@@ -44,6 +49,7 @@ impl Event {
             time: SystemTime::now(),
             event_type,
             name,
+            device: None,
         }
     }
 }
@@ -1,5 +1,12 @@
 //! Key definitions based on physical keyboard layout.
 
+/// Offset of the synthetic keycodes given to media keys that have no virtual
+/// keycode on macOS (`MEDIA_KEYCODE_BASE + NX_KEYTYPE_*`).
+///
+/// Real virtual keycodes are 8-bit, so these never collide with a physical key.
+#[cfg(target_os = "macos")]
+const MEDIA_KEYCODE_BASE: u32 = 0x100;
+
 /// Physical keyboard keys.
 ///
 /// Keys are named based on their physical position on a QWERTY keyboard.
@@ -154,6 +161,9 @@ pub enum Key {
     KpDivide,
 
     // Media keys
+    //
+    // On Mac keyboards these are the F-row media functions, delivered as
+    // NX_SYSDEFINED events rather than key events.
     VolumeUp,
     VolumeDown,
     VolumeMute,
@@ -162,6 +172,11 @@ pub enum Key {
     PreviousTrack,
     NextTrack,
     PlayPause,
+    FastForward,
+    Rewind,
+    KeyboardBrightnessUp,
+    KeyboardBrightnessDown,
+    KeyboardBrightnessToggle,
 
     // macOS-specific keys
     /// Mission Control key (some keyboards)
@@ -301,10 +316,36 @@ impl Key {
             125 => Key::DownArrow,
             126 => Key::UpArrow,
             160 => Key::MissionControl,
+            c if c >= MEDIA_KEYCODE_BASE => {
+                Key::from_nx_key_type(c - MEDIA_KEYCODE_BASE).unwrap_or(Key::Unknown(code))
+            }
             _ => Key::Unknown(code),
         }
     }
 
+    /// Convert an `NX_KEYTYPE_*` media key code (from `<IOKit/hidsystem/ev_keymap.h>`) to a Key.
+    ///
+    /// Returns `None` for key types that aren't exposed (Caps Lock, power, eject, ...).
+    #[cfg(target_os = "macos")]
+    pub(crate) fn from_nx_key_type(key_type: u32) -> Option<Self> {
+        match key_type {
+            0 => Some(Key::VolumeUp),
+            1 => Some(Key::VolumeDown),
+            2 => Some(Key::BrightnessUp),
+            3 => Some(Key::BrightnessDown),
+            7 => Some(Key::VolumeMute),
+            16 => Some(Key::PlayPause),
+            17 => Some(Key::NextTrack),
+            18 => Some(Key::PreviousTrack),
+            19 => Some(Key::FastForward),
+            20 => Some(Key::Rewind),
+            21 => Some(Key::KeyboardBrightnessUp),
+            22 => Some(Key::KeyboardBrightnessDown),
+            23 => Some(Key::KeyboardBrightnessToggle),
+            _ => None,
+        }
+    }
+
     /// Convert a Key to macOS keycode
     #[cfg(target_os = "macos")]
     pub fn to_macos_keycode(&self) -> u32 {
@@ -430,12 +471,82 @@ impl Key {
             Key::MissionControl => 160,
             Key::Unknown(code) => *code,
             Key::PrintScreen | Key::Pause | Key::ScrollLock => 0, // No direct macOS equivalent
-            Key::BrightnessUp | Key::BrightnessDown | Key::PreviousTrack | Key::NextTrack
-            | Key::PlayPause => 0, // Media keys not commonly used for shortcuts
+            // Media keys without a virtual keycode (see `MEDIA_KEYCODE_BASE`)
+            Key::BrightnessUp => MEDIA_KEYCODE_BASE + 2,
+            Key::BrightnessDown => MEDIA_KEYCODE_BASE + 3,
+            Key::PlayPause => MEDIA_KEYCODE_BASE + 16,
+            Key::NextTrack => MEDIA_KEYCODE_BASE + 17,
+            Key::PreviousTrack => MEDIA_KEYCODE_BASE + 18,
+            Key::FastForward => MEDIA_KEYCODE_BASE + 19,
+            Key::Rewind => MEDIA_KEYCODE_BASE + 20,
+            Key::KeyboardBrightnessUp => MEDIA_KEYCODE_BASE + 21,
+            Key::KeyboardBrightnessDown => MEDIA_KEYCODE_BASE + 22,
+            Key::KeyboardBrightnessToggle => MEDIA_KEYCODE_BASE + 23,
         }
     }
 
-    /// Get a human-readable label for the key
+    /// Whether the key types a character whose label depends on the keyboard layout
+    /// (letters, number row, punctuation and the ISO/JIS character keys)
+    pub fn is_character_key(&self) -> bool {
+        matches!(
+            self,
+            Key::KeyA
+                | Key::KeyB
+                | Key::KeyC
+                | Key::KeyD
+                | Key::KeyE
+                | Key::KeyF
+                | Key::KeyG
+                | Key::KeyH
+                | Key::KeyI
+                | Key::KeyJ
+                | Key::KeyK
+                | Key::KeyL
+                | Key::KeyM
+                | Key::KeyN
+                | Key::KeyO
+                | Key::KeyP
+                | Key::KeyQ
+                | Key::KeyR
+                | Key::KeyS
+                | Key::KeyT
+                | Key::KeyU
+                | Key::KeyV
+                | Key::KeyW
+                | Key::KeyX
+                | Key::KeyY
+                | Key::KeyZ
+                | Key::Num0
+                | Key::Num1
+                | Key::Num2
+                | Key::Num3
+                | Key::Num4
+                | Key::Num5
+                | Key::Num6
+                | Key::Num7
+                | Key::Num8
+                | Key::Num9
+                | Key::BackQuote
+                | Key::Minus
+                | Key::Equal
+                | Key::LeftBracket
+                | Key::RightBracket
+                | Key::BackSlash
+                | Key::SemiColon
+                | Key::Quote
+                | Key::Comma
+                | Key::Dot
+                | Key::Slash
+                | Key::ISOSection
+                | Key::JISYen
+                | Key::JISUnderscore
+        )
+    }
+
+    /// Get a human-readable label for the key (US layout)
+    ///
+    /// Use [`KeyboardLayout::label`](crate::KeyboardLayout::label) to get the
+    /// character the key types on the user's layout.
     pub fn to_label(&self) -> String {
         match self {
             // Modifier keys
@@ -577,6 +688,11 @@ impl Key {
             Key::PreviousTrack => "Previous Track".to_string(),
             Key::NextTrack => "Next Track".to_string(),
             Key::PlayPause => "Play/Pause".to_string(),
+            Key::FastForward => "Fast Forward".to_string(),
+            Key::Rewind => "Rewind".to_string(),
+            Key::KeyboardBrightnessUp => "Keyboard Brightness Up".to_string(),
+            Key::KeyboardBrightnessDown => "Keyboard Brightness Down".to_string(),
+            Key::KeyboardBrightnessToggle => "Keyboard Brightness Toggle".to_string(),
 
             // International keyboard keys
             Key::ISOSection => "§".to_string(),
@@ -0,0 +1,159 @@
+//! Keyboard layout-aware key labels.
+//!
+//! [`Key`] values are physical positions (keycodes), which is what shortcut
+//! matching uses. For display, the same position produces different characters
+//! on AZERTY, QWERTZ or JIS layouts, so the label of character keys is looked up
+//! in the active keyboard layout.
+
+use std::collections::HashMap;
+
+use crate::Key;
+
+/// Labels of the character keys for one keyboard layout.
+#[derive(Debug, Clone, Default)]
+pub struct KeyboardLayout {
+    /// Character produced by each character key (uppercased), keyed by macOS keycode
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    labels: HashMap<u32, String>,
+}
+
+impl KeyboardLayout {
+    /// Read the active keyboard layout.
+    ///
+    /// Returns an empty layout (US labels) if the layout can't be read or on
+    /// unsupported platforms.
+    ///
+    /// # Platform notes
+    ///
+    /// - **macOS**: Text Input Sources must be queried from the main thread.
+    pub fn current() -> Self {
+        #[cfg(target_os = "macos")]
+        {
+            Self {
+                labels: macos::current_layout_labels(),
+            }
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Self::default()
+        }
+    }
+
+    /// Label for a key: the character it types in this layout for character
+    /// keys, [`Key::to_label`] for everything else.
+    pub fn label(&self, key: Key) -> String {
+        if !key.is_character_key() {
+            return key.to_label();
+        }
+
+        #[cfg(target_os = "macos")]
+        if let Some(label) = self.labels.get(&key.to_macos_keycode()) {
+            return label.clone();
+        }
+
+        key.to_label()
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::collections::HashMap;
+    use std::ffi::c_void;
+
+    use crate::Key;
+
+    /// `kUCKeyActionDisplay`: the character shown on the key cap
+    const UC_KEY_ACTION_DISPLAY: u16 = 3;
+    /// `1 << kUCKeyTranslateNoDeadKeysBit`: return dead keys as their base character
+    const UC_KEY_TRANSLATE_NO_DEAD_KEYS: u32 = 1;
+
+    #[link(name = "Carbon", kind = "framework")]
+    extern "C" {
+        static kTISPropertyUnicodeKeyLayoutData: *const c_void;
+
+        fn TISCopyCurrentKeyboardLayoutInputSource() -> *mut c_void;
+        fn TISGetInputSourceProperty(source: *mut c_void, key: *const c_void) -> *const c_void;
+        fn LMGetKbdType() -> u8;
+        fn UCKeyTranslate(
+            key_layout: *const c_void,
+            virtual_key_code: u16,
+            key_action: u16,
+            modifier_key_state: u32,
+            keyboard_type: u32,
+            key_translate_options: u32,
+            dead_key_state: *mut u32,
+            max_string_length: usize,
+            actual_string_length: *mut usize,
+            unicode_string: *mut u16,
+        ) -> i32;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFDataGetBytePtr(data: *const c_void) -> *const u8;
+        fn CFRelease(cf: *const c_void);
+    }
+
+    /// Translate every character key with the active layout (no modifiers)
+    pub(super) fn current_layout_labels() -> HashMap<u32, String> {
+        let mut labels = HashMap::new();
+
+        // SAFETY: the input source is released below, the layout data pointer is
+        // only used while the input source is alive
+        unsafe {
+            let source = TISCopyCurrentKeyboardLayoutInputSource();
+            if source.is_null() {
+                log::warn!("No keyboard layout input source, using US labels");
+                return labels;
+            }
+
+            // Layout data is missing for some input methods (e.g. CJK IMEs)
+            let layout_data = TISGetInputSourceProperty(source, kTISPropertyUnicodeKeyLayoutData);
+            if layout_data.is_null() {
+                log::warn!("Keyboard layout has no unicode layout data, using US labels");
+                CFRelease(source);
+                return labels;
+            }
+
+            let layout = CFDataGetBytePtr(layout_data) as *const c_void;
+            let keyboard_type = LMGetKbdType() as u32;
+
+            for keycode in 0..=127u32 {
+                let key = Key::from_macos_keycode(keycode);
+                if !key.is_character_key() {
+                    continue;
+                }
+
+                let mut dead_key_state = 0u32;
+                let mut chars = [0u16; 4];
+                let mut length = 0usize;
+                let status = UCKeyTranslate(
+                    layout,
+                    keycode as u16,
+                    UC_KEY_ACTION_DISPLAY,
+                    0,
+                    keyboard_type,
+                    UC_KEY_TRANSLATE_NO_DEAD_KEYS,
+                    &mut dead_key_state,
+                    chars.len(),
+                    &mut length,
+                    chars.as_mut_ptr(),
+                );
+
+                if status != 0 || length == 0 {
+                    continue;
+                }
+
+                let label = String::from_utf16_lossy(&chars[..length]);
+                if !label.trim().is_empty() {
+                    labels.insert(keycode, label.to_uppercase());
+                }
+            }
+
+            CFRelease(source);
+        }
+
+        labels
+    }
+}
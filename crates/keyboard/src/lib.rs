@@ -7,32 +7,52 @@
 //! # Example
 //!
 //! ```no_run
-//! use dictara_keyboard::{grab, Event, EventType, Key};
+//! use dictara_keyboard::{grab, EventType, GrabDecision, Key};
 //!
 //! fn main() -> Result<(), dictara_keyboard::GrabError> {
 //!     grab(|event| {
 //!         match event.event_type {
 //!             EventType::KeyPress(Key::Function) => {
 //!                 println!("Fn key pressed!");
-//!                 None // Swallow the event
+//!                 GrabDecision::Swallow
 //!             }
-//!             _ => Some(event) // Pass through
+//!             _ => GrabDecision::Pass,
 //!         }
 //!     })
 //! }
 //! ```
 
+mod device;
 mod event;
 mod key;
+mod layout;
+mod poll;
+mod secure_input;
+mod stats;
 
 #[cfg(target_os = "macos")]
 mod macos;
 
+pub use device::KeyboardDevice;
 pub use event::{Event, EventType};
 pub use key::Key;
+pub use layout::KeyboardLayout;
+pub use secure_input::{secure_input_state, SecureInputState};
+pub use stats::{grab_stats, reset_grab_stats, GrabStats, LATENCY_BUCKET_BOUNDS_US};
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use thiserror::Error;
 
+/// Set while events pass through without reaching the grab callback
+static GRAB_SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+/// Tag of the events an application simulates itself (macOS `kCGEventSourceUserData`).
+///
+/// Events carrying it pass through the grab without reaching the callback, so
+/// simulated input (e.g. the Cmd+V of a paste) can't change the tracked modifiers
+/// or trigger a shortcut.
+pub const SYNTHETIC_EVENT_TAG: i64 = 0x4449_4354; // "DICT"
+
 /// Errors that can occur when grabbing keyboard events.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -53,16 +73,94 @@ pub enum GrabError {
     #[error("Failed to get current run loop")]
     RunLoopError,
 
+    /// Failed to create a key event to post (macOS).
+    #[error("Failed to create key event")]
+    PostEventError,
+
     /// Platform not supported.
     #[error("Platform not supported")]
     UnsupportedPlatform,
 }
 
-/// Callback type for the grab function.
+/// Where in the event stream the grab taps keyboard events.
+///
+/// On macOS this maps to `CGEventTapLocation`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TapLocation {
+    /// Where events enter the window server from the hardware (`kCGHIDEventTap`).
+    ///
+    /// Sees events before any other application, including events typed into
+    /// secure input fields (password prompts), which the system may block.
+    #[default]
+    Hid,
+    /// Where events enter the login session (`kCGSessionEventTap`).
+    ///
+    /// Stays out of the way of secure input, but other session level taps
+    /// (e.g. key remappers) may see or change events first.
+    Session,
+    /// Where session events are annotated for delivery to an application
+    /// (`kCGAnnotatedSessionEventTap`). Sees the latest, most processed events.
+    AnnotatedSession,
+}
+
+/// Whether the grab can block events or only observe them.
+///
+/// On macOS this maps to `CGEventTapOptions`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TapMode {
+    /// The callback can swallow events (`kCGEventTapOptionDefault`).
+    #[default]
+    Intercept,
+    /// The callback only observes events, its return value is ignored
+    /// (`kCGEventTapOptionListenOnly`). A listen-only tap doesn't delay input
+    /// when the callback is slow and is never disabled by timeout.
+    ListenOnly,
+}
+
+/// Configuration of [`grab_with_config`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GrabConfig {
+    pub location: TapLocation,
+    pub mode: TapMode,
+}
+
+/// What the grab does with an event, returned by the callback.
+#[derive(Debug, Clone)]
+pub enum GrabDecision {
+    /// Deliver the event to the system unchanged.
+    Pass,
+    /// Block the event.
+    Swallow,
+    /// Deliver this event instead, e.g. a press of F13 for a press of Fn, for apps
+    /// that need a real key.
+    ///
+    /// On macOS the keycode and modifier flags of the original event are rewritten.
+    /// Only the event type and key are used, the other fields are ignored.
+    Replace(Event),
+}
+
+/// Callback type for the grab function, deciding what happens to each event.
+pub type GrabCallback = dyn FnMut(Event) -> GrabDecision;
+
+/// Pass every event through untouched, without calling the grab callback,
+/// until [`resume_grab`].
 ///
-/// Return `Some(event)` to pass the event through to the system.
-/// Return `None` to swallow/block the event.
-pub type GrabCallback = dyn FnMut(Event) -> Option<Event>;
+/// The tap stays installed, so resuming doesn't depend on the accessibility
+/// permission being checked again. Meant for periods where the input belongs to
+/// someone else, e.g. another user's login session after fast user switching.
+pub fn suspend_grab() {
+    GRAB_SUSPENDED.store(true, Ordering::SeqCst);
+}
+
+/// Call the grab callback again after [`suspend_grab`].
+pub fn resume_grab() {
+    GRAB_SUSPENDED.store(false, Ordering::SeqCst);
+}
+
+/// Whether the grab is suspended (see [`suspend_grab`]).
+pub fn is_grab_suspended() -> bool {
+    GRAB_SUSPENDED.load(Ordering::SeqCst)
+}
 
 /// Start grabbing keyboard events.
 ///
@@ -71,8 +169,8 @@ pub type GrabCallback = dyn FnMut(Event) -> Option<Event>;
 ///
 /// # Arguments
 ///
-/// * `callback` - A closure that receives each event and returns `Some(event)`
-///   to pass it through or `None` to swallow it.
+/// * `callback` - A closure that receives each event and returns what to do with
+///   it, see [`GrabDecision`].
 ///
 /// # Platform Support
 ///
@@ -83,28 +181,57 @@ pub type GrabCallback = dyn FnMut(Event) -> Option<Event>;
 /// # Example
 ///
 /// ```no_run
-/// use dictara_keyboard::{grab, Event, EventType, Key};
+/// use dictara_keyboard::{grab, Event, EventType, GrabDecision, Key};
 ///
-/// grab(|event| {
-///     if let EventType::KeyPress(Key::Function) = event.event_type {
-///         println!("Fn pressed!");
-///         return None; // Block the event
+/// // Fn acts as F13
+/// grab(|event| match event.event_type {
+///     EventType::KeyPress(Key::Function) => {
+///         GrabDecision::Replace(Event::new(EventType::KeyPress(Key::F13)))
+///     }
+///     EventType::KeyRelease(Key::Function) => {
+///         GrabDecision::Replace(Event::new(EventType::KeyRelease(Key::F13)))
 ///     }
-///     Some(event) // Pass through
+///     _ => GrabDecision::Pass,
 /// }).expect("Failed to grab");
 /// ```
-#[cfg(target_os = "macos")]
 pub fn grab<F>(callback: F) -> Result<(), GrabError>
 where
-    F: FnMut(Event) -> Option<Event> + 'static,
+    F: FnMut(Event) -> GrabDecision + 'static,
+{
+    grab_with_config(GrabConfig::default(), callback)
+}
+
+/// Start grabbing keyboard events with an explicit tap location and mode.
+///
+/// [`grab`] taps at HID level and can swallow events. See [`TapLocation`] and
+/// [`TapMode`] for the trade-offs of the alternatives.
+#[cfg(target_os = "macos")]
+pub fn grab_with_config<F>(config: GrabConfig, callback: F) -> Result<(), GrabError>
+where
+    F: FnMut(Event) -> GrabDecision + 'static,
 {
-    macos::grab(callback)
+    macos::grab(config, callback)
 }
 
 #[cfg(not(target_os = "macos"))]
-pub fn grab<F>(_callback: F) -> Result<(), GrabError>
+pub fn grab_with_config<F>(_config: GrabConfig, _callback: F) -> Result<(), GrabError>
 where
-    F: FnMut(Event) -> Option<Event> + 'static,
+    F: FnMut(Event) -> GrabDecision + 'static,
 {
     Err(GrabError::UnsupportedPlatform)
 }
+
+/// Post key presses and releases as if typed, e.g. events a callback swallowed and
+/// delivers after all.
+///
+/// The events carry [`SYNTHETIC_EVENT_TAG`], so the grab lets them through without
+/// calling the callback. Meant for regular and modifier keys, not media keys.
+#[cfg(target_os = "macos")]
+pub fn post_events(events: &[EventType]) -> Result<(), GrabError> {
+    macos::post_events(events)
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn post_events(_events: &[EventType]) -> Result<(), GrabError> {
+    Err(GrabError::UnsupportedPlatform)
+}
@@ -1,21 +1,56 @@
 //! macOS implementation using CGEvent tap.
 
-use crate::{Event, EventType, GrabError, Key};
+use crate::device::device_for_sender;
+use crate::poll::{poll, Backoff, PollEnd, PollSignal};
+use crate::stats;
+use crate::{
+    Event, EventType, GrabConfig, GrabDecision, GrabError, Key, KeyboardDevice, TapLocation,
+    TapMode,
+};
 use log::{error, info, warn};
-use objc2_core_foundation::{kCFRunLoopCommonModes, CFMachPort, CFRunLoop};
+use objc2::rc::autoreleasepool;
+use objc2_app_kit::NSEvent;
+use objc2_core_foundation::{
+    kCFRunLoopCommonModes, CFDictionary, CFMachPort, CFNotificationCenter,
+    CFNotificationSuspensionBehavior, CFRetained, CFRunLoop, CFString,
+};
 use objc2_core_graphics::{
-    kCGEventMaskForAllEvents, CGEvent, CGEventField, CGEventTapCallBack, CGEventTapLocation,
-    CGEventTapOptions, CGEventTapPlacement, CGEventTapProxy, CGEventType,
+    kCGEventMaskForAllEvents, CGEvent, CGEventField, CGEventFlags, CGEventSource,
+    CGEventSourceStateID, CGEventTapCallBack, CGEventTapLocation, CGEventTapOptions,
+    CGEventTapPlacement, CGEventTapProxy, CGEventType,
 };
+use std::collections::HashMap;
 use std::ffi::c_void;
 use std::ptr::NonNull;
-use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicPtr, Ordering};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// Interval of the accessibility check after the tap started or a permission-related
+/// event, doubled after each check until [`ACCESSIBILITY_POLL_MAX_INTERVAL`].
+const ACCESSIBILITY_POLL_MIN_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Interval of the accessibility check once the permission has been stable for a while.
+const ACCESSIBILITY_POLL_MAX_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Distributed notification posted when the accessibility trust of an app changes.
+/// It arrives before the permission is updated, so it brings back the fast checks
+/// instead of triggering one.
+const ACCESSIBILITY_NOTIFICATION: &str = "com.apple.accessibility.api";
+
+/// `NX_SYSDEFINED` event type, used for media keys (not part of `CGEventType`).
+const NX_SYSDEFINED: u32 = 14;
 
-/// How often to check if accessibility permission is still granted.
-const ACCESSIBILITY_POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// `NX_SUBTYPE_AUX_CONTROL_BUTTONS`: NX_SYSDEFINED subtype of media key events.
+const NX_SUBTYPE_AUX_CONTROL_BUTTONS: i16 = 8;
+
+/// Media key states in `data1` (`NX_KEYDOWN` / `NX_KEYUP`).
+const NX_KEYSTATE_DOWN: isize = 0x0A;
+const NX_KEYSTATE_UP: isize = 0x0B;
+
+/// Undocumented event field holding the registry entry id of the IOHIDService
+/// that sent the event (0 for synthetic events).
+const EVENT_SENDER_ID_FIELD: CGEventField = CGEventField(87);
 
 /// Global reference to the event tap for re-enabling after timeout.
 /// This is safe because we only have one tap per process.
@@ -30,9 +65,50 @@ static TAP_REF: AtomicPtr<CFMachPort> = AtomicPtr::new(std::ptr::null_mut());
 /// main thread only clears this pointer AFTER joining the polling thread.
 static RUN_LOOP_REF: AtomicPtr<CFRunLoop> = AtomicPtr::new(std::ptr::null_mut());
 
+/// Wakes the accessibility polling thread early: to stop it, or to check at the
+/// fast interval again after a permission-related event.
+static ACCESSIBILITY_POLL: PollSignal = PollSignal::new();
+
+/// Called for [`ACCESSIBILITY_NOTIFICATION`], on whichever run loop delivers it.
+unsafe extern "C-unwind" fn accessibility_changed(
+    _center: *mut CFNotificationCenter,
+    _observer: *mut c_void,
+    _name: *const CFString,
+    _object: *const c_void,
+    _user_info: *const CFDictionary,
+) {
+    info!("Accessibility permissions changed, checking again");
+    ACCESSIBILITY_POLL.kick();
+}
+
+/// Observer id of the accessibility notification
+fn accessibility_observer() -> *const c_void {
+    &ACCESSIBILITY_POLL as *const PollSignal as *const c_void
+}
+
+/// Listen for [`ACCESSIBILITY_NOTIFICATION`], polling alone still works without it
+fn observe_accessibility_changes() -> Option<CFRetained<CFNotificationCenter>> {
+    let Some(center) = CFNotificationCenter::distributed_center() else {
+        warn!("No distributed notification center, accessibility is only polled");
+        return None;
+    };
+    let name = CFString::from_str(ACCESSIBILITY_NOTIFICATION);
+    // SAFETY: the observer id is a static address, the callback only touches statics
+    unsafe {
+        center.add_observer(
+            accessibility_observer(),
+            Some(accessibility_changed),
+            Some(&name),
+            std::ptr::null(),
+            CFNotificationSuspensionBehavior::DeliverImmediately,
+        );
+    }
+    Some(center)
+}
+
 /// State passed to the CGEvent callback.
 struct CallbackState {
-    callback: Box<dyn FnMut(Event) -> Option<Event>>,
+    callback: Box<dyn FnMut(Event) -> GrabDecision>,
     /// Track modifier key states for FlagsChanged events
     fn_down: bool,
     control_left_down: bool,
@@ -41,6 +117,23 @@ struct CallbackState {
     alt_gr_down: bool,
     meta_left_down: bool,
     meta_right_down: bool,
+    /// Keyboards by sender id, looked up once per sender
+    devices: HashMap<u64, Option<KeyboardDevice>>,
+    /// Events were passed through by a suspended grab since the last callback
+    suspended: bool,
+}
+
+impl CallbackState {
+    /// Forget the tracked modifiers, whose changes may have gone unseen
+    fn reset_modifiers(&mut self) {
+        self.fn_down = false;
+        self.control_left_down = false;
+        self.control_right_down = false;
+        self.alt_down = false;
+        self.alt_gr_down = false;
+        self.meta_left_down = false;
+        self.meta_right_down = false;
+    }
 }
 
 /// Check if accessibility permission is currently granted.
@@ -52,9 +145,9 @@ fn check_accessibility() -> bool {
 /// Start grabbing keyboard events using CGEvent tap.
 ///
 /// This function blocks the current thread.
-pub fn grab<F>(callback: F) -> Result<(), GrabError>
+pub fn grab<F>(config: GrabConfig, callback: F) -> Result<(), GrabError>
 where
-    F: FnMut(Event) -> Option<Event> + 'static,
+    F: FnMut(Event) -> GrabDecision + 'static,
 {
     // Check accessibility permission upfront for a clear error
     if !check_accessibility() {
@@ -71,15 +164,28 @@ where
             alt_gr_down: false,
             meta_left_down: false,
             meta_right_down: false,
+            devices: HashMap::new(),
+            suspended: false,
         });
         let user_info = Box::into_raw(state) as *mut c_void;
 
         let tap_callback: CGEventTapCallBack = Some(event_tap_callback);
 
+        let location = match config.location {
+            TapLocation::Hid => CGEventTapLocation::HIDEventTap,
+            TapLocation::Session => CGEventTapLocation::SessionEventTap,
+            TapLocation::AnnotatedSession => CGEventTapLocation::AnnotatedSessionEventTap,
+        };
+        let options = match config.mode {
+            TapMode::Intercept => CGEventTapOptions::Default,
+            TapMode::ListenOnly => CGEventTapOptions::ListenOnly,
+        };
+        info!("Creating event tap: {:?}", config);
+
         let tap = CGEvent::tap_create(
-            CGEventTapLocation::HIDEventTap,
+            location,
             CGEventTapPlacement::HeadInsertEventTap,
-            CGEventTapOptions::Default,
+            options,
             kCGEventMaskForAllEvents.into(),
             tap_callback,
             user_info,
@@ -107,29 +213,31 @@ where
         RUN_LOOP_REF.store(run_loop_ptr, Ordering::SeqCst);
 
         // Start accessibility polling thread
-        // This thread checks every 200ms if accessibility permission is still granted
-        // If permission is revoked, it stops the run loop to prevent system freeze
-        let stop_polling = Arc::new(AtomicBool::new(false));
-        let stop_polling_clone = Arc::clone(&stop_polling);
+        // If permission is revoked, it stops the run loop to prevent system freeze.
+        // Checks start every 200ms and back off while the permission stays granted,
+        // the accessibility notification and tap-disabled events bring them back.
+        ACCESSIBILITY_POLL.reset();
+        let notification_center = observe_accessibility_changes();
 
         let polling_thread = thread::Builder::new()
             .name("accessibility-poll".to_string())
             .spawn(move || {
                 info!("Accessibility polling thread started");
-                while !stop_polling_clone.load(Ordering::SeqCst) {
-                    thread::sleep(ACCESSIBILITY_POLL_INTERVAL);
-
-                    if !check_accessibility() {
-                        error!("Accessibility permission lost (detected by polling), stopping event tap");
-                        let rl_ptr = RUN_LOOP_REF.load(Ordering::SeqCst);
-                        if !rl_ptr.is_null() {
-                            // SAFETY: The run loop pointer is valid because:
-                            // 1. The main thread is blocked on CFRunLoop::run()
-                            // 2. Cleanup only happens AFTER this thread is joined
-                            // CFRunLoop::stop is thread-safe
-                            (*rl_ptr).stop();
-                        }
-                        break;
+                let backoff = Backoff::new(
+                    ACCESSIBILITY_POLL_MIN_INTERVAL,
+                    ACCESSIBILITY_POLL_MAX_INTERVAL,
+                );
+                if poll(&ACCESSIBILITY_POLL, backoff, check_accessibility) == PollEnd::CheckFailed {
+                    error!(
+                        "Accessibility permission lost (detected by polling), stopping event tap"
+                    );
+                    let rl_ptr = RUN_LOOP_REF.load(Ordering::SeqCst);
+                    if !rl_ptr.is_null() {
+                        // SAFETY: The run loop pointer is valid because:
+                        // 1. The main thread is blocked on CFRunLoop::run()
+                        // 2. Cleanup only happens AFTER this thread is joined
+                        // CFRunLoop::stop is thread-safe
+                        (*rl_ptr).stop();
                     }
                 }
                 info!("Accessibility polling thread stopped");
@@ -140,7 +248,10 @@ where
         CFRunLoop::run();
 
         // Signal the polling thread to stop and wait for it
-        stop_polling.store(true, Ordering::SeqCst);
+        if let Some(center) = notification_center {
+            center.remove_observer(accessibility_observer(), None, std::ptr::null());
+        }
+        ACCESSIBILITY_POLL.stop();
         let _ = polling_thread.join();
 
         // Cleanup - safe to clear now since polling thread has been joined
@@ -169,6 +280,8 @@ unsafe extern "C-unwind" fn event_tap_callback(
     match event_type {
         CGEventType::TapDisabledByTimeout => {
             warn!("Event tap disabled by timeout, checking accessibility...");
+            stats::record_tap_timeout();
+            ACCESSIBILITY_POLL.kick();
             // Check if we still have accessibility permission before re-enabling
             if check_accessibility() {
                 let tap_ptr = TAP_REF.load(Ordering::SeqCst);
@@ -187,6 +300,7 @@ unsafe extern "C-unwind" fn event_tap_callback(
         }
         CGEventType::TapDisabledByUserInput => {
             warn!("Event tap disabled by user input, checking accessibility...");
+            ACCESSIBILITY_POLL.kick();
             // Check if accessibility permission is still granted
             if check_accessibility() {
                 // Still have permission, re-enable the tap
@@ -209,6 +323,57 @@ unsafe extern "C-unwind" fn event_tap_callback(
 
     let state = &mut *(user_info as *mut CallbackState);
 
+    // Keep out of the input entirely while suspended (see `suspend_grab`).
+    // Modifier changes meanwhile go unseen, so their tracking starts over on resume.
+    if crate::is_grab_suspended() {
+        state.suspended = true;
+        return cg_event.as_ptr();
+    }
+    if std::mem::take(&mut state.suspended) {
+        state.reset_modifiers();
+    }
+
+    // Our own simulated input, see `SYNTHETIC_EVENT_TAG`
+    let user_data =
+        CGEvent::integer_value_field(Some(cg_event.as_ref()), CGEventField::EventSourceUserData);
+    if user_data == crate::SYNTHETIC_EVENT_TAG {
+        return cg_event.as_ptr();
+    }
+
+    let started = Instant::now();
+    let result = handle_event(state, event_type, cg_event);
+    stats::record_event(result.is_null(), started.elapsed());
+    result
+}
+
+/// Convert the event and run the user's callback, null = swallow
+///
+/// # Safety
+///
+/// `cg_event` must be the event of the running tap callback.
+unsafe fn handle_event(
+    state: &mut CallbackState,
+    event_type: CGEventType,
+    cg_event: NonNull<CGEvent>,
+) -> *mut CGEvent {
+    // Media keys (F-row media functions) arrive as NX_SYSDEFINED events, not key events.
+    // They go through the same callback, so swallowing them follows the same rules as
+    // regular keys; everything else (other subtypes, unmapped keys) passes through.
+    if event_type == CGEventType(NX_SYSDEFINED) {
+        let Some(mut event) = media_key_event(cg_event.as_ref()) else {
+            return cg_event.as_ptr();
+        };
+        event.device = event_device(state, cg_event.as_ref());
+        let original = event.event_type.clone();
+        return match (state.callback)(event) {
+            GrabDecision::Pass => cg_event.as_ptr(),
+            GrabDecision::Swallow => std::ptr::null_mut(),
+            GrabDecision::Replace(replacement) => {
+                new_key_event(cg_event, &original, &replacement.event_type)
+            }
+        };
+    }
+
     // Get the keycode
     let keycode =
         CGEvent::integer_value_field(Some(cg_event.as_ref()), CGEventField::KeyboardEventKeycode);
@@ -253,14 +418,149 @@ unsafe extern "C-unwind" fn event_tap_callback(
     };
 
     // If we got a keyboard event, call the user's callback
-    if let Some(event) = event {
-        let result = (state.callback)(event);
-        if result.is_none() {
-            // User wants to swallow this event
-            return std::ptr::null_mut();
+    if let Some(mut event) = event {
+        event.device = event_device(state, cg_event.as_ref());
+        let original = event.event_type.clone();
+        match (state.callback)(event) {
+            GrabDecision::Pass => {}
+            GrabDecision::Swallow => return std::ptr::null_mut(),
+            GrabDecision::Replace(replacement) => {
+                rewrite_event(cg_event.as_ref(), &original, &replacement.event_type);
+            }
         }
     }
 
     // Pass the event through
     cg_event.as_ptr()
 }
+
+/// Key of an event and whether it is pressed
+fn key_state(event_type: &EventType) -> (Key, bool) {
+    match event_type {
+        EventType::KeyPress(key) => (*key, true),
+        EventType::KeyRelease(key) => (*key, false),
+    }
+}
+
+/// Modifier flag held while a modifier key is down
+fn modifier_flag(key: Key) -> Option<CGEventFlags> {
+    match key {
+        Key::Function => Some(CGEventFlags::MaskSecondaryFn),
+        Key::ControlLeft | Key::ControlRight => Some(CGEventFlags::MaskControl),
+        Key::Alt | Key::AltGr => Some(CGEventFlags::MaskAlternate),
+        Key::MetaLeft | Key::MetaRight => Some(CGEventFlags::MaskCommand),
+        Key::ShiftLeft | Key::ShiftRight => Some(CGEventFlags::MaskShift),
+        Key::CapsLock => Some(CGEventFlags::MaskAlphaShift),
+        _ => None,
+    }
+}
+
+/// Turn `cg_event` into the press or release of another key ([`GrabDecision::Replace`])
+///
+/// Modifier keys are sent as `FlagsChanged` events, other keys as `KeyDown`/`KeyUp`.
+/// The flag of a replaced modifier is cleared, so Fn replaced by F13 isn't Fn+F13.
+fn rewrite_event(cg_event: &CGEvent, original: &EventType, replacement: &EventType) {
+    let (original_key, _) = key_state(original);
+    let mut flags = CGEvent::flags(Some(cg_event)).0;
+    if let Some(flag) = modifier_flag(original_key) {
+        flags &= !flag.0;
+    }
+    set_key(cg_event, replacement, &mut flags);
+}
+
+/// Make `cg_event` the press or release of a key, on top of the modifier `flags`
+/// (updated when the key is a modifier)
+fn set_key(cg_event: &CGEvent, event_type: &EventType, flags: &mut u64) {
+    let (key, down) = key_state(event_type);
+    let cg_event_type = match modifier_flag(key) {
+        Some(flag) if down => {
+            *flags |= flag.0;
+            CGEventType::FlagsChanged
+        }
+        Some(flag) => {
+            *flags &= !flag.0;
+            CGEventType::FlagsChanged
+        }
+        None if down => CGEventType::KeyDown,
+        None => CGEventType::KeyUp,
+    };
+
+    CGEvent::set_type(Some(cg_event), cg_event_type);
+    CGEvent::set_integer_value_field(
+        Some(cg_event),
+        CGEventField::KeyboardEventKeycode,
+        key.to_macos_keycode() as i64,
+    );
+    CGEvent::set_flags(Some(cg_event), CGEventFlags(*flags));
+}
+
+/// Post key events, tagged so our own tap lets them through (see [`crate::post_events`])
+///
+/// Modifiers start from the ones currently held and follow the posted modifier keys,
+/// so a posted Fn press applies to the keys posted after it.
+pub fn post_events(events: &[EventType]) -> Result<(), GrabError> {
+    let mut flags = CGEventSource::flags_state(CGEventSourceStateID::HIDSystemState).0;
+    for event_type in events {
+        let event = CGEvent::new_keyboard_event(None, 0, true).ok_or(GrabError::PostEventError)?;
+        set_key(&event, event_type, &mut flags);
+        CGEvent::set_integer_value_field(
+            Some(&event),
+            CGEventField::EventSourceUserData,
+            crate::SYNTHETIC_EVENT_TAG,
+        );
+        CGEvent::post(CGEventTapLocation::HIDEventTap, Some(&event));
+    }
+    Ok(())
+}
+
+/// Key event replacing a media key event, which can't be rewritten into one
+///
+/// The tap releases a returned new event along with the original. Falls back to the
+/// original event if the new one can't be created.
+fn new_key_event(
+    cg_event: NonNull<CGEvent>,
+    original: &EventType,
+    replacement: &EventType,
+) -> *mut CGEvent {
+    let Some(event) = CGEvent::new_keyboard_event(None, 0, true) else {
+        warn!("Failed to create replacement key event");
+        return cg_event.as_ptr();
+    };
+    rewrite_event(&event, original, replacement);
+    CFRetained::into_raw(event).as_ptr()
+}
+
+/// Keyboard that generated the event
+fn event_device(state: &mut CallbackState, cg_event: &CGEvent) -> Option<KeyboardDevice> {
+    let sender_id = CGEvent::integer_value_field(Some(cg_event), EVENT_SENDER_ID_FIELD) as u64;
+    if sender_id == 0 {
+        return None;
+    }
+
+    state
+        .devices
+        .entry(sender_id)
+        .or_insert_with(|| device_for_sender(sender_id))
+        .clone()
+}
+
+/// Decode an NX_SYSDEFINED event into a media key press or release.
+///
+/// `data1` packs the `NX_KEYTYPE_*` code in bits 16-31 and the key state in bits 8-15.
+/// Key repeats are reported as presses, like regular key repeats.
+fn media_key_event(cg_event: &CGEvent) -> Option<Event> {
+    autoreleasepool(|_| {
+        let ns_event = NSEvent::eventWithCGEvent(cg_event)?;
+        if ns_event.subtype().0 != NX_SUBTYPE_AUX_CONTROL_BUTTONS {
+            return None;
+        }
+
+        let data1 = ns_event.data1();
+        let key = Key::from_nx_key_type(((data1 >> 16) & 0xFFFF) as u32)?;
+        match (data1 >> 8) & 0xFF {
+            NX_KEYSTATE_DOWN => Some(Event::new(EventType::KeyPress(key))),
+            NX_KEYSTATE_UP => Some(Event::new(EventType::KeyRelease(key))),
+            _ => None,
+        }
+    })
+}
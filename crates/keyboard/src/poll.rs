@@ -0,0 +1,255 @@
+//! Wake-up signal and backoff of the accessibility polling thread (macOS).
+//!
+//! Kept apart from the tap so the state machine can be tested on every platform:
+//! checks start at the fast interval and back off while they pass, a kick (a
+//! permission-related event) brings the fast interval back, a stop ends the polling.
+
+#![cfg_attr(not(target_os = "macos"), allow(dead_code))]
+
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Why [`PollSignal::wait`] returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PollWake {
+    /// The interval elapsed, time to check
+    Timeout,
+    /// A permission-related event, go back to the fast interval
+    Kicked,
+    /// The tap stopped
+    Stop,
+}
+
+/// How [`poll`] ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PollEnd {
+    /// [`PollSignal::stop`] was called
+    Stopped,
+    /// The check failed
+    CheckFailed,
+}
+
+pub(crate) struct PollSignal {
+    state: Mutex<PollState>,
+    changed: Condvar,
+}
+
+struct PollState {
+    kicked: bool,
+    stop: bool,
+}
+
+impl PollSignal {
+    pub(crate) const fn new() -> Self {
+        Self {
+            state: Mutex::new(PollState {
+                kicked: false,
+                stop: false,
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PollState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Forget a previous tap's signals
+    pub(crate) fn reset(&self) {
+        let mut state = self.lock();
+        state.kicked = false;
+        state.stop = false;
+    }
+
+    pub(crate) fn kick(&self) {
+        self.lock().kicked = true;
+        self.changed.notify_all();
+    }
+
+    pub(crate) fn stop(&self) {
+        self.lock().stop = true;
+        self.changed.notify_all();
+    }
+
+    /// Sleep for `timeout` unless kicked or stopped first
+    pub(crate) fn wait(&self, timeout: Duration) -> PollWake {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+        loop {
+            if state.stop {
+                return PollWake::Stop;
+            }
+            if std::mem::take(&mut state.kicked) {
+                return PollWake::Kicked;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return PollWake::Timeout;
+            }
+            state = self
+                .changed
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+}
+
+/// Interval between two checks, doubled after each passed check up to `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Backoff {
+    min: Duration,
+    max: Duration,
+    interval: Duration,
+}
+
+impl Backoff {
+    pub(crate) fn new(min: Duration, max: Duration) -> Self {
+        Self {
+            min,
+            max,
+            interval: min,
+        }
+    }
+
+    pub(crate) fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Back to the fast interval
+    pub(crate) fn reset(&mut self) {
+        self.interval = self.min;
+    }
+
+    /// Slow down after a passed check
+    pub(crate) fn grow(&mut self) {
+        self.interval = (self.interval * 2).min(self.max);
+    }
+}
+
+/// Run `check` after each interval of `backoff` until it fails or `signal` stops.
+pub(crate) fn poll(
+    signal: &PollSignal,
+    mut backoff: Backoff,
+    mut check: impl FnMut() -> bool,
+) -> PollEnd {
+    loop {
+        match signal.wait(backoff.interval()) {
+            PollWake::Stop => return PollEnd::Stopped,
+            PollWake::Kicked => {
+                backoff.reset();
+                continue;
+            }
+            PollWake::Timeout => {}
+        }
+
+        if !check() {
+            return PollEnd::CheckFailed;
+        }
+        backoff.grow();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn test_backoff() {
+        let mut backoff = Backoff::new(200 * MS, 1000 * MS);
+        assert_eq!(backoff.interval(), 200 * MS);
+        backoff.grow();
+        assert_eq!(backoff.interval(), 400 * MS);
+        backoff.grow();
+        backoff.grow();
+        assert_eq!(backoff.interval(), 1000 * MS);
+        backoff.grow();
+        assert_eq!(backoff.interval(), 1000 * MS);
+        backoff.reset();
+        assert_eq!(backoff.interval(), 200 * MS);
+    }
+
+    #[test]
+    fn test_wait() {
+        let signal = PollSignal::new();
+        assert_eq!(signal.wait(MS), PollWake::Timeout);
+
+        // A kick is taken once
+        signal.kick();
+        assert_eq!(signal.wait(Duration::from_secs(10)), PollWake::Kicked);
+        assert_eq!(signal.wait(MS), PollWake::Timeout);
+
+        // A stop stays and wins over a kick
+        signal.kick();
+        signal.stop();
+        assert_eq!(signal.wait(Duration::from_secs(10)), PollWake::Stop);
+        assert_eq!(signal.wait(Duration::from_secs(10)), PollWake::Stop);
+
+        signal.reset();
+        assert_eq!(signal.wait(MS), PollWake::Timeout);
+    }
+
+    #[test]
+    fn test_wait_wakes_up_early() {
+        let signal = Arc::new(PollSignal::new());
+        let kicker = {
+            let signal = signal.clone();
+            thread::spawn(move || {
+                thread::sleep(10 * MS);
+                signal.kick();
+            })
+        };
+
+        let started = Instant::now();
+        assert_eq!(signal.wait(Duration::from_secs(10)), PollWake::Kicked);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        kicker.join().unwrap();
+    }
+
+    #[test]
+    fn test_poll_until_the_check_fails() {
+        let signal = PollSignal::new();
+        let mut checks = 0;
+        let end = poll(&signal, Backoff::new(MS, 4 * MS), || {
+            checks += 1;
+            checks < 5
+        });
+        assert_eq!(end, PollEnd::CheckFailed);
+        assert_eq!(checks, 5);
+    }
+
+    #[test]
+    fn test_poll_until_stopped() {
+        let signal = Arc::new(PollSignal::new());
+        let stopper = {
+            let signal = signal.clone();
+            thread::spawn(move || {
+                thread::sleep(10 * MS);
+                signal.kick();
+                thread::sleep(10 * MS);
+                signal.stop();
+            })
+        };
+
+        let end = poll(&signal, Backoff::new(MS, 2 * MS), || true);
+        assert_eq!(end, PollEnd::Stopped);
+        stopper.join().unwrap();
+    }
+
+    #[test]
+    fn test_poll_stops_without_checking() {
+        // Stopped before the first interval elapsed: no check
+        let signal = PollSignal::new();
+        signal.stop();
+        let end = poll(
+            &signal,
+            Backoff::new(Duration::from_secs(10), Duration::from_secs(10)),
+            || panic!("checked after the stop"),
+        );
+        assert_eq!(end, PollEnd::Stopped);
+    }
+}
@@ -0,0 +1,106 @@
+//! macOS Secure Input detection.
+//!
+//! While any application has Secure Input enabled (password fields, some
+//! terminals with "Secure Keyboard Entry"), event taps receive no key events,
+//! so shortcuts silently stop working.
+
+/// Secure Input state of the login session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SecureInputState {
+    pub enabled: bool,
+    /// Process that enabled Secure Input, when the system reports it
+    pub pid: Option<i32>,
+}
+
+/// Read the current Secure Input state. Always disabled on other platforms.
+#[cfg(target_os = "macos")]
+pub fn secure_input_state() -> SecureInputState {
+    let enabled = macos::is_secure_input_enabled();
+    SecureInputState {
+        enabled,
+        pid: if enabled {
+            macos::secure_input_pid()
+        } else {
+            None
+        },
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn secure_input_state() -> SecureInputState {
+    SecureInputState::default()
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::ffi::c_void;
+    use std::ptr::NonNull;
+
+    use objc2_core_foundation::{CFArray, CFDictionary, CFNumber, CFRetained, CFString, CFType};
+
+    /// `MACH_PORT_NULL`: use the default IOKit main port
+    const DEFAULT_MAIN_PORT: u32 = 0;
+
+    #[link(name = "Carbon", kind = "framework")]
+    extern "C" {
+        fn IsSecureEventInputEnabled() -> u8;
+    }
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IORegistryGetRootEntry(main_port: u32) -> u32;
+        fn IORegistryEntryCreateCFProperty(
+            entry: u32,
+            key: &CFString,
+            allocator: *const c_void,
+            options: u32,
+        ) -> Option<NonNull<CFType>>;
+        fn IOObjectRelease(object: u32) -> i32;
+    }
+
+    pub(super) fn is_secure_input_enabled() -> bool {
+        // SAFETY: no arguments, only reads the session state
+        unsafe { IsSecureEventInputEnabled() != 0 }
+    }
+
+    /// The window server publishes the Secure Input owner as
+    /// `kCGSSessionSecureInputPID` in the console users of the registry root
+    /// (undocumented, the same value `ioreg -l` shows).
+    pub(super) fn secure_input_pid() -> Option<i32> {
+        // SAFETY: the root entry is released below, the property is returned
+        // with +1 retain count and owned by CFRetained
+        let users = unsafe {
+            let root = IORegistryGetRootEntry(DEFAULT_MAIN_PORT);
+            if root == 0 {
+                return None;
+            }
+            let users = IORegistryEntryCreateCFProperty(
+                root,
+                &CFString::from_static_str("IOConsoleUsers"),
+                std::ptr::null(),
+                0,
+            )
+            .map(|value| CFRetained::from_raw(value));
+            IOObjectRelease(root);
+            users?
+        };
+
+        let users = users.downcast_ref::<CFArray>()?;
+        let key = CFString::from_static_str("kCGSSessionSecureInputPID");
+        (0..users.count()).find_map(|index| {
+            // SAFETY: the index is in bounds and the array holds CF objects,
+            // kept alive by `users` while they are used
+            let user = unsafe { &*users.value_at_index(index).cast::<CFType>() };
+            let user = user.downcast_ref::<CFDictionary>()?;
+            // SAFETY: the key is a CFString, the value (if any) is a CF object
+            let pid = unsafe { user.value((&*key as *const CFString).cast()) };
+            if pid.is_null() {
+                return None;
+            }
+            let pid = unsafe { &*pid.cast::<CFType>() };
+            pid.downcast_ref::<CFNumber>()?
+                .as_i32()
+                .filter(|pid| *pid > 0)
+        })
+    }
+}
@@ -0,0 +1,133 @@
+//! Event suppression statistics of the grab.
+//!
+//! Counted process-wide (there is one tap per process) with relaxed atomics, so
+//! the tap callback never blocks on them.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Upper bounds (inclusive, microseconds) of the callback latency buckets.
+/// The last bucket of [`GrabStats::latency_buckets`] counts everything slower.
+pub const LATENCY_BUCKET_BOUNDS_US: [u64; 8] = [50, 100, 250, 500, 1_000, 5_000, 25_000, 100_000];
+
+const BUCKET_COUNT: usize = LATENCY_BUCKET_BOUNDS_US.len() + 1;
+
+/// Snapshot of the grab counters since the process started (or the last [`reset_grab_stats`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GrabStats {
+    /// Events that reached the callback (the macOS tap receives every event type)
+    pub events_seen: u64,
+    /// Events the callback swallowed
+    pub swallowed: u64,
+    /// Events passed through to the system
+    pub passed: u64,
+    /// Times the system disabled the tap because the callback was too slow (macOS)
+    pub tap_timeouts: u64,
+    /// Callback latency histogram, see [`LATENCY_BUCKET_BOUNDS_US`]
+    pub latency_buckets: [u64; BUCKET_COUNT],
+    /// Sum of all callback latencies, for the mean
+    pub total_latency_us: u64,
+    /// Slowest callback
+    pub max_latency_us: u64,
+}
+
+impl GrabStats {
+    /// Mean callback latency, `None` before the first event
+    pub fn mean_latency_us(&self) -> Option<u64> {
+        self.total_latency_us.checked_div(self.events_seen)
+    }
+}
+
+static EVENTS_SEEN: AtomicU64 = AtomicU64::new(0);
+static SWALLOWED: AtomicU64 = AtomicU64::new(0);
+static TAP_TIMEOUTS: AtomicU64 = AtomicU64::new(0);
+static LATENCY_BUCKETS: [AtomicU64; BUCKET_COUNT] = [const { AtomicU64::new(0) }; BUCKET_COUNT];
+static TOTAL_LATENCY_US: AtomicU64 = AtomicU64::new(0);
+static MAX_LATENCY_US: AtomicU64 = AtomicU64::new(0);
+
+/// Current counters of the grab.
+pub fn grab_stats() -> GrabStats {
+    let events_seen = EVENTS_SEEN.load(Ordering::Relaxed);
+    let swallowed = SWALLOWED.load(Ordering::Relaxed);
+    GrabStats {
+        events_seen,
+        swallowed,
+        passed: events_seen.saturating_sub(swallowed),
+        tap_timeouts: TAP_TIMEOUTS.load(Ordering::Relaxed),
+        latency_buckets: std::array::from_fn(|i| LATENCY_BUCKETS[i].load(Ordering::Relaxed)),
+        total_latency_us: TOTAL_LATENCY_US.load(Ordering::Relaxed),
+        max_latency_us: MAX_LATENCY_US.load(Ordering::Relaxed),
+    }
+}
+
+/// Reset all counters, e.g. before reproducing a lag report.
+pub fn reset_grab_stats() {
+    for counter in [
+        &EVENTS_SEEN,
+        &SWALLOWED,
+        &TAP_TIMEOUTS,
+        &TOTAL_LATENCY_US,
+        &MAX_LATENCY_US,
+    ] {
+        counter.store(0, Ordering::Relaxed);
+    }
+    for bucket in &LATENCY_BUCKETS {
+        bucket.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Count an event handled by the callback in `latency`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn record_event(swallowed: bool, latency: Duration) {
+    let latency_us = latency.as_micros().min(u64::MAX as u128) as u64;
+    let bucket = LATENCY_BUCKET_BOUNDS_US
+        .iter()
+        .position(|bound| latency_us <= *bound)
+        .unwrap_or(BUCKET_COUNT - 1);
+
+    EVENTS_SEEN.fetch_add(1, Ordering::Relaxed);
+    if swallowed {
+        SWALLOWED.fetch_add(1, Ordering::Relaxed);
+    }
+    LATENCY_BUCKETS[bucket].fetch_add(1, Ordering::Relaxed);
+    TOTAL_LATENCY_US.fetch_add(latency_us, Ordering::Relaxed);
+    MAX_LATENCY_US.fetch_max(latency_us, Ordering::Relaxed);
+}
+
+/// Count the system disabling the tap for a slow callback.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn record_tap_timeout() {
+    TAP_TIMEOUTS.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The counters are process-wide, so everything is checked in one test
+    #[test]
+    fn test_record_and_reset() {
+        reset_grab_stats();
+        assert_eq!(grab_stats(), GrabStats::default());
+        assert_eq!(grab_stats().mean_latency_us(), None);
+
+        record_event(true, Duration::from_micros(50));
+        record_event(false, Duration::from_micros(51));
+        record_event(false, Duration::from_millis(200));
+        record_tap_timeout();
+
+        let stats = grab_stats();
+        assert_eq!(stats.events_seen, 3);
+        assert_eq!(stats.swallowed, 1);
+        assert_eq!(stats.passed, 2);
+        assert_eq!(stats.tap_timeouts, 1);
+        // Bounds are inclusive, slower than the last bound lands in the overflow bucket
+        assert_eq!(stats.latency_buckets, [1, 1, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(stats.total_latency_us, 200_101);
+        assert_eq!(stats.max_latency_us, 200_000);
+        assert_eq!(stats.mean_latency_us(), Some(66_700));
+
+        reset_grab_stats();
+        assert_eq!(grab_stats(), GrabStats::default());
+    }
+}
@@ -0,0 +1,21 @@
+#!/bin/bash
+# Download the audio fixtures and the Whisper tiny model of the dictara-core integration tests
+# Usage: ./scripts/fetch-test-fixtures.sh
+# Then: cargo test -p dictara-core --features integration-tests
+
+set -e
+
+FIXTURES="$(cd "$(dirname "$0")/.." && pwd)/crates/core/tests/fixtures"
+
+fetch() {
+  local url="$1" file="$FIXTURES/$2"
+  if [ -f "$file" ]; then
+    return
+  fi
+  echo "Downloading $2"
+  curl -fL --retry 3 -o "$file.partial" "$url"
+  mv "$file.partial" "$file"
+}
+
+fetch "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin" ggml-tiny.bin
+fetch "https://github.com/ggerganov/whisper.cpp/raw/master/samples/jfk.wav" jfk.wav
@@ -0,0 +1,21 @@
+#!/bin/bash
+# Print the SHA-256 of the model files of the catalog, as published by Hugging Face
+# (LFS object ids), to replace the UNPINNED_SHA256 checksums of crates/core/src/models/catalog.rs
+# Usage: ./scripts/pin-model-hashes.sh [filter]
+# Requires curl and jq
+
+set -e
+
+REPOS=(
+  "ggerganov/whisper.cpp"
+  "distil-whisper/distil-large-v3-ggml"
+  "Wespeaker/wespeaker-voxceleb-resnet34-LM"
+)
+FILTER="${1:-}"
+
+for repo in "${REPOS[@]}"; do
+  curl -fsSL --retry 3 "https://huggingface.co/api/models/$repo/tree/main" |
+    jq -r --arg repo "$repo" \
+      '.[] | select(.lfs != null) | "\(.lfs.oid)  \($repo)/\(.path)"' |
+    grep -- "$FILTER" || true
+done
@@ -0,0 +1,244 @@
+//! Audit log of outbound API requests, for users in regulated environments.
+//!
+//! When enabled, every transcription and post-processing request sent to a
+//! provider is appended as one JSON line to `<app data>/audit/audit.jsonl`:
+//! provider, endpoint, duration, request size and status. Audio is never
+//! logged, transcribed text only when `include_text` is set.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use log::error;
+use serde::{Deserialize, Serialize};
+
+const AUDIT_FILE: &str = "audit.jsonl";
+
+/// Audit log settings
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AuditLogConfig {
+    pub enabled: bool,
+    /// Also log the transcribed / post-processed text
+    pub include_text: bool,
+}
+
+/// Kind of outbound request
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AuditRequestKind {
+    Transcription,
+    PostProcessing,
+}
+
+/// One outbound request
+pub struct AuditEntry<'a> {
+    pub kind: AuditRequestKind,
+    pub provider: &'a str,
+    pub endpoint: &'a str,
+    pub duration: Duration,
+    pub request_bytes: u64,
+    /// HTTP status, `None` if no response was received
+    pub status: Option<u16>,
+    pub error: Option<String>,
+    /// Response text, only written when `include_text` is enabled
+    pub text: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditRecord<'a> {
+    timestamp: String,
+    kind: AuditRequestKind,
+    provider: &'a str,
+    endpoint: &'a str,
+    duration_ms: u64,
+    request_bytes: u64,
+    status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<&'a str>,
+}
+
+/// Append-only audit log (managed state)
+pub struct AuditLog {
+    dir: PathBuf,
+    /// Current settings, also serializes writes to the file
+    config: Mutex<AuditLogConfig>,
+}
+
+impl AuditLog {
+    pub fn new(dir: PathBuf, config: AuditLogConfig) -> Self {
+        Self {
+            dir,
+            config: Mutex::new(config),
+        }
+    }
+
+    pub fn set_config(&self, config: AuditLogConfig) {
+        *self.config.lock().unwrap() = config;
+    }
+
+    /// Append `entry` if the audit log is enabled. Failures are logged, never returned:
+    /// auditing must not break transcription.
+    pub fn record(&self, entry: AuditEntry) {
+        let config = self.config.lock().unwrap();
+        if !config.enabled {
+            return;
+        }
+
+        let record = AuditRecord {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            kind: entry.kind,
+            provider: entry.provider,
+            endpoint: entry.endpoint,
+            duration_ms: entry.duration.as_millis() as u64,
+            request_bytes: entry.request_bytes,
+            status: entry.status,
+            error: entry.error,
+            text: entry.text.filter(|_| config.include_text),
+        };
+        if let Err(e) = self.append(&record) {
+            error!("Failed to write audit log: {}", e);
+        }
+    }
+
+    fn append(&self, record: &AuditRecord) -> Result<(), String> {
+        fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+        let mut line = serde_json::to_string(record).map_err(|e| e.to_string())?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(AUDIT_FILE))
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| e.to_string())
+    }
+
+    /// Move the current file aside (`audit-<timestamp>-<sequence>.jsonl`) so a new one
+    /// is started. The sequence number keeps the files rotated within the same second.
+    /// Returns the rotated file, `None` if there was nothing to rotate.
+    pub fn rotate(&self) -> Result<Option<PathBuf>, String> {
+        let _guard = self.config.lock().unwrap();
+        let current = self.dir.join(AUDIT_FILE);
+        if !current.exists() {
+            return Ok(None);
+        }
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let rotated = (0..)
+            .map(|sequence| {
+                self.dir
+                    .join(format!("audit-{}-{:03}.jsonl", timestamp, sequence))
+            })
+            .find(|path| !path.exists())
+            .expect("unbounded sequence");
+        fs::rename(&current, &rotated).map_err(|e| format!("Failed to rotate audit log: {}", e))?;
+        Ok(Some(rotated))
+    }
+
+    /// Write all audit files, oldest first, into one file at `destination`
+    pub fn export(&self, destination: &Path) -> Result<(), String> {
+        let _guard = self.config.lock().unwrap();
+        let mut rotated: Vec<PathBuf> = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with("audit-") && name.ends_with(".jsonl"))
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        // Timestamped names sort chronologically
+        rotated.sort();
+        rotated.push(self.dir.join(AUDIT_FILE));
+
+        let mut output = fs::File::create(destination)
+            .map_err(|e| format!("Failed to create {:?}: {}", destination, e))?;
+        for path in rotated.iter().filter(|path| path.exists()) {
+            let content =
+                fs::read(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+            output
+                .write_all(&content)
+                .map_err(|e| format!("Failed to write {:?}: {}", destination, e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("dictara-audit-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn entry(provider: &str) -> AuditEntry<'_> {
+        AuditEntry {
+            kind: AuditRequestKind::Transcription,
+            provider,
+            endpoint: "https://api.openai.com/v1/audio/transcriptions",
+            duration: Duration::from_millis(800),
+            request_bytes: 1024,
+            status: Some(200),
+            error: None,
+            text: Some("secret text"),
+        }
+    }
+
+    #[test]
+    fn test_rotate_keeps_every_file() {
+        let dir = temp_dir("rotate");
+        let log = AuditLog::new(
+            dir.clone(),
+            AuditLogConfig {
+                enabled: true,
+                include_text: false,
+            },
+        );
+        assert_eq!(log.rotate().unwrap(), None);
+
+        // Rotated within the same second, nothing is overwritten
+        let mut rotated = Vec::new();
+        for provider in ["first", "second", "third"] {
+            log.record(entry(provider));
+            rotated.push(log.rotate().unwrap().unwrap());
+        }
+        log.record(entry("current"));
+        rotated.sort();
+        rotated.dedup();
+        assert_eq!(rotated.len(), 3);
+
+        let exported = dir.join("export.jsonl");
+        log.export(&exported).unwrap();
+        let providers: Vec<String> = fs::read_to_string(&exported)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                let record: serde_json::Value = serde_json::from_str(line).unwrap();
+                assert!(record.get("text").is_none());
+                record["provider"].as_str().unwrap().to_string()
+            })
+            .collect();
+        // Oldest first
+        assert_eq!(providers, ["first", "second", "third", "current"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_disabled_log_writes_nothing() {
+        let dir = temp_dir("disabled");
+        let log = AuditLog::new(dir.clone(), AuditLogConfig::default());
+        log.record(entry("openai"));
+        assert!(!dir.join(AUDIT_FILE).exists());
+        assert_eq!(log.rotate().unwrap(), None);
+    }
+}
@@ -26,8 +26,70 @@ pub fn setup_autolaunch_if_needed(
     } else {
         info!("Autostart enabled successfully");
         app_config.autostart_initial_setup_done = true;
+        app_config.autostart_enabled = true;
         if let Err(e) = config_store.set(&ConfigKey::APP, app_config.clone()) {
             error!("Failed to save autostart setup flag: {}", e);
         }
     }
 }
+
+/// Whether the app is registered to launch at login. The system state is the truth
+/// (the user can remove the login item in System Settings): the stored preference is
+/// updated to match it.
+pub fn autostart_enabled(
+    app: &tauri::AppHandle,
+    config_store: &config::Config,
+) -> Result<bool, String> {
+    let enabled = app.autolaunch().is_enabled().map_err(|e| {
+        error!("Failed to check autostart status: {}", e);
+        format!("Failed to check launch at login status: {}", e)
+    })?;
+    sync_autostart_config(config_store, enabled, false);
+    Ok(enabled)
+}
+
+/// Register or unregister the login item, returning the resulting system state
+pub fn set_autostart_enabled(
+    app: &tauri::AppHandle,
+    config_store: &config::Config,
+    enabled: bool,
+) -> Result<bool, String> {
+    let autostart_manager = app.autolaunch();
+    let result = if enabled {
+        autostart_manager.enable()
+    } else {
+        autostart_manager.disable()
+    };
+    if let Err(e) = result {
+        let action = if enabled { "enable" } else { "disable" };
+        error!("Failed to {} autostart: {}", action, e);
+        // Keep the stored preference in line with whatever the system ended up with
+        let _ = autostart_enabled(app, config_store);
+        return Err(format!("Failed to {} launch at login: {}", action, e));
+    }
+
+    info!(
+        "Launch at login {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    let enabled = app.autolaunch().is_enabled().unwrap_or(enabled);
+    sync_autostart_config(config_store, enabled, true);
+    Ok(enabled)
+}
+
+/// Store the system state. An explicit choice also marks the first-launch setup as
+/// done, so it isn't overridden on the next launch.
+fn sync_autostart_config(config_store: &config::Config, enabled: bool, explicit: bool) {
+    let mut app_config: AppConfig = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    let setup_done = app_config.autostart_initial_setup_done || explicit;
+    if app_config.autostart_enabled == enabled
+        && app_config.autostart_initial_setup_done == setup_done
+    {
+        return;
+    }
+    app_config.autostart_enabled = enabled;
+    app_config.autostart_initial_setup_done = setup_done;
+    if let Err(e) = config_store.set(&ConfigKey::APP, app_config) {
+        error!("Failed to save autostart state: {}", e);
+    }
+}
@@ -0,0 +1,260 @@
+//! Time-of-day automation rules.
+//!
+//! A rule applies a few settings (a profile, the provider, the local model,
+//! post-processing) over a time window, e.g. "Azure on weekdays from 09:00 to
+//! 17:00" and "local model from 17:00 to 09:00". Rules are checked every
+//! [`CHECK_INTERVAL`]: a rule that started since the last check has its settings
+//! written to the config, and an [`AutomationRuleActivated`] event is emitted.
+//! Nothing is undone when the window ends, another rule takes over; settings
+//! changed by hand in the meantime stay until the next rule starts.
+//!
+//! Rules active at launch are applied then, in their order (later ones win).
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::config::{
+    self, AppConfig, AutomationRule, ConfigKey, ConfigOverlay, ConfigStore, Weekday,
+};
+use crate::profiles;
+
+/// Time between two checks of the rules
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Fired when an automation rule started and its settings were applied
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationRuleActivated {
+    pub rule_id: String,
+    pub name: String,
+}
+
+/// Check the rules in the background for the app lifetime
+pub fn start_scheduler(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut active = HashSet::new();
+        loop {
+            active = check_rules(&app_handle, &active);
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Apply the rules started since the last check, returns the ids of the active rules
+fn check_rules(app_handle: &AppHandle, previously_active: &HashSet<String>) -> HashSet<String> {
+    let store = app_handle.state::<config::Config>();
+    let rules = store.get(&ConfigKey::AUTOMATION).unwrap_or_default().rules;
+    let (started, active) = started_rules(&rules, Local::now().naive_local(), previously_active);
+
+    for rule in started {
+        info!("Automation rule \"{}\" started", rule.name);
+        if let Err(e) = apply_overlay(store.inner(), &rule.overlay) {
+            error!("Failed to apply automation rule \"{}\": {}", rule.name, e);
+            continue;
+        }
+        let event = AutomationRuleActivated {
+            rule_id: rule.id.clone(),
+            name: rule.name.clone(),
+        };
+        if let Err(e) = event.emit(app_handle) {
+            error!("Failed to emit AutomationRuleActivated: {}", e);
+        }
+    }
+    active
+}
+
+/// Rules active at `now` that weren't in `previously_active`, and the ids of all
+/// active rules
+fn started_rules<'a>(
+    rules: &'a [AutomationRule],
+    now: NaiveDateTime,
+    previously_active: &HashSet<String>,
+) -> (Vec<&'a AutomationRule>, HashSet<String>) {
+    let active: Vec<&AutomationRule> = rules
+        .iter()
+        .filter(|rule| rule.enabled && is_active(rule, now))
+        .collect();
+    let started = active
+        .iter()
+        .copied()
+        .filter(|rule| !previously_active.contains(&rule.id))
+        .collect();
+    let ids = active.iter().map(|rule| rule.id.clone()).collect();
+    (started, ids)
+}
+
+/// Whether `now` is within the window of `rule`
+fn is_active(rule: &AutomationRule, now: NaiveDateTime) -> bool {
+    let (Ok(start), Ok(end)) = (parse_time(&rule.start), parse_time(&rule.end)) else {
+        return false;
+    };
+    let runs_on = |date: NaiveDate| {
+        rule.days.is_empty()
+            || rule
+                .days
+                .iter()
+                .any(|day| to_chrono(*day) == date.weekday())
+    };
+
+    let (date, time) = (now.date(), now.time());
+    if start < end {
+        runs_on(date) && start <= time && time < end
+    } else {
+        // Past midnight (or all day when the start is the end): the part after
+        // midnight belongs to the day before
+        (time >= start && runs_on(date)) || (time < end && date.pred_opt().is_some_and(runs_on))
+    }
+}
+
+/// Reject rules that can't be applied
+pub fn validate_rule(rule: &AutomationRule) -> Result<(), String> {
+    if rule.name.trim().is_empty() {
+        return Err("Rule name cannot be empty".to_string());
+    }
+    parse_time(&rule.start)?;
+    parse_time(&rule.end)?;
+    if rule.overlay == ConfigOverlay::default() {
+        return Err("The rule doesn't change any setting".to_string());
+    }
+    Ok(())
+}
+
+fn parse_time(time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M")
+        .map_err(|_| format!("Invalid time \"{}\", expected HH:MM", time))
+}
+
+fn to_chrono(day: Weekday) -> chrono::Weekday {
+    match day {
+        Weekday::Monday => chrono::Weekday::Mon,
+        Weekday::Tuesday => chrono::Weekday::Tue,
+        Weekday::Wednesday => chrono::Weekday::Wed,
+        Weekday::Thursday => chrono::Weekday::Thu,
+        Weekday::Friday => chrono::Weekday::Fri,
+        Weekday::Saturday => chrono::Weekday::Sat,
+        Weekday::Sunday => chrono::Weekday::Sun,
+    }
+}
+
+/// Write the settings of `overlay` to the config, the subsystems pick them up from there
+fn apply_overlay(store: &config::Config, overlay: &ConfigOverlay) -> Result<(), String> {
+    if let Some(profile) = &overlay.profile {
+        profiles::switch(store, profile)?;
+    }
+
+    if overlay.active_provider.is_some() || overlay.post_process_enabled.is_some() {
+        let mut app_config: AppConfig = store.get(&ConfigKey::APP).unwrap_or_default();
+        if let Some(provider) = &overlay.active_provider {
+            app_config.active_provider = Some(provider.clone());
+        }
+        if let Some(enabled) = overlay.post_process_enabled {
+            app_config.post_process_enabled = enabled;
+        }
+        store.set(&ConfigKey::APP, app_config)?;
+    }
+
+    if let Some(model) = &overlay.local_model {
+        let mut local_config = store.get(&ConfigKey::LOCAL_MODEL).unwrap_or_default();
+        local_config.selected_model = Some(model.clone());
+        store.set(&ConfigKey::LOCAL_MODEL, local_config)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Provider;
+
+    fn rule(id: &str, days: Vec<Weekday>, start: &str, end: &str) -> AutomationRule {
+        AutomationRule {
+            id: id.to_string(),
+            name: id.to_string(),
+            enabled: true,
+            days,
+            start: start.to_string(),
+            end: end.to_string(),
+            overlay: ConfigOverlay {
+                active_provider: Some(Provider::AzureOpenAI),
+                ..ConfigOverlay::default()
+            },
+        }
+    }
+
+    /// 2025-03-07 is a Friday
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 3, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_is_active() {
+        let weekdays = rule(
+            "work",
+            vec![
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Thursday,
+                Weekday::Friday,
+            ],
+            "09:00",
+            "17:00",
+        );
+        assert!(is_active(&weekdays, at(7, 9, 0)));
+        assert!(is_active(&weekdays, at(7, 16, 59)));
+        assert!(!is_active(&weekdays, at(7, 17, 0)));
+        assert!(!is_active(&weekdays, at(8, 10, 0)));
+
+        // Friday night until Saturday morning
+        let night = rule("night", vec![Weekday::Friday], "22:00", "06:00");
+        assert!(is_active(&night, at(7, 23, 0)));
+        assert!(is_active(&night, at(8, 5, 59)));
+        assert!(!is_active(&night, at(8, 23, 0)));
+        assert!(!is_active(&night, at(7, 5, 0)));
+
+        assert!(!is_active(
+            &rule("invalid", vec![], "9am", "17:00"),
+            at(7, 10, 0)
+        ));
+    }
+
+    #[test]
+    fn test_started_rules() {
+        let rules = vec![
+            rule("work", vec![], "09:00", "17:00"),
+            rule("evening", vec![], "17:00", "09:00"),
+        ];
+
+        let (started, active) = started_rules(&rules, at(7, 10, 0), &HashSet::new());
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].id, "work");
+
+        // Still active, not applied again
+        let (started, active) = started_rules(&rules, at(7, 11, 0), &active);
+        assert!(started.is_empty());
+
+        let (started, _) = started_rules(&rules, at(7, 17, 0), &active);
+        assert_eq!(started[0].id, "evening");
+    }
+
+    #[test]
+    fn test_validate_rule() {
+        assert!(validate_rule(&rule("work", vec![], "09:00", "17:00")).is_ok());
+        assert!(validate_rule(&rule("work", vec![], "25:00", "17:00")).is_err());
+        assert!(validate_rule(&rule(" ", vec![], "09:00", "17:00")).is_err());
+
+        let mut no_change = rule("work", vec![], "09:00", "17:00");
+        no_change.overlay = ConfigOverlay::default();
+        assert!(validate_rule(&no_change).is_err());
+    }
+}
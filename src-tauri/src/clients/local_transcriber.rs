@@ -1,16 +1,20 @@
 //! Local transcription service implementation.
 //!
-//! Handles transcription via local Whisper model.
+//! Handles transcription via local Whisper model, on the worker pool with
+//! interactive priority.
 
 use std::path::Path;
 use std::sync::Arc;
 
 use log::info;
 
-use crate::models::ModelLoader;
+use dictara_core::transcription::{
+    LocalModelOptions, Transcription, TranscriptionError, TranscriptionOptions,
+    TranscriptionService,
+};
 
-use super::error::TranscriptionError;
-use super::service::TranscriptionService;
+use crate::models::ModelLoader;
+use crate::worker_pool::{WorkPriority, WorkerPool};
 
 /// Local transcription service using Whisper model.
 ///
@@ -19,32 +23,64 @@ use super::service::TranscriptionService;
 /// conditions where the model could be unloaded or swapped.
 pub struct LocalTranscriber {
     loader: Arc<ModelLoader>,
+    pool: Arc<WorkerPool>,
     selected_model: String,
+    options: LocalModelOptions,
 }
 
 impl LocalTranscriber {
-    /// Create a new local transcriber with the given model loader and selected model.
-    pub fn new(loader: Arc<ModelLoader>, selected_model: String) -> Self {
+    /// Create a new local transcriber with the given model loader, worker pool,
+    /// selected model and its transcription options.
+    pub fn new(
+        loader: Arc<ModelLoader>,
+        pool: Arc<WorkerPool>,
+        selected_model: String,
+        options: LocalModelOptions,
+    ) -> Self {
         Self {
             loader,
+            pool,
             selected_model,
+            options,
         }
     }
 }
 
 impl TranscriptionService for LocalTranscriber {
-    fn transcribe(&self, audio_path: &Path) -> Result<String, TranscriptionError> {
+    fn transcribe_detailed(
+        &self,
+        audio_path: &Path,
+        options: &TranscriptionOptions,
+    ) -> Result<Transcription, TranscriptionError> {
         // Use transcribe_with_model which handles:
         // 1. Loading the model if not already loaded
         // 2. Verifying the correct model is loaded (handles race conditions)
         // 3. Transcribing the audio
-        let text = self
-            .loader
-            .transcribe_with_model(&self.selected_model, audio_path)
-            .map_err(TranscriptionError::LocalTranscriptionFailed)?;
+        // The model, prompt and temperature of the options are for API providers
+        let loader = self.loader.clone();
+        let model_name = self.selected_model.clone();
+        let audio_path = audio_path.to_path_buf();
+        let language = options.language.clone();
+        let model_options = self.options.clone();
+        let cancel = self.loader.new_transcription_job();
+        let transcription = self
+            .pool
+            .run(WorkPriority::Interactive, move || {
+                loader.transcribe_with_model(
+                    &model_name,
+                    &audio_path,
+                    language.as_deref(),
+                    &model_options,
+                    cancel,
+                )
+            })
+            .map_err(TranscriptionError::LocalTranscriptionFailed)??;
 
-        info!("Local transcription successful: {} characters", text.len());
+        info!(
+            "Local transcription successful: {} characters",
+            transcription.text.len()
+        );
 
-        Ok(text)
+        Ok(transcription)
     }
 }
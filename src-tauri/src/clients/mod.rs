@@ -1,16 +1,13 @@
-mod api_transcriber;
-mod azure_client;
-mod client;
 mod config;
-mod error;
 mod local_transcriber;
-mod openai_client;
-mod service;
+mod post_process_guard;
+mod provider_health;
 mod text_post_processor;
 mod transcriber;
 
 // Re-export public types
 pub use config::ApiConfig;
-pub use error::TranscriptionError;
-pub use text_post_processor::post_process_with_openai;
-pub use transcriber::Transcriber;
+pub use dictara_core::transcription::TranscriptionError;
+pub use provider_health::{ProviderHealth, ProviderHealthReport};
+pub use text_post_processor::{complete_with_openai, post_process_with_openai};
+pub use transcriber::{validate_transcription_config, Transcriber, MAX_FILE_SIZE_BYTES};
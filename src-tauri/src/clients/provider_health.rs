@@ -0,0 +1,112 @@
+//! Recent successes and failures of each transcription provider.
+//!
+//! With provider failover, the preferences page uses this to show which provider
+//! actually served recent transcriptions and which ones are failing.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use dictara_core::transcription::TranscriptionError;
+use serde::Serialize;
+
+use crate::config::Provider;
+
+/// Number of recent attempts kept per provider
+const HISTORY_LEN: usize = 20;
+
+#[derive(Default)]
+struct ProviderStats {
+    /// Outcome of the recent attempts, oldest first
+    recent: VecDeque<bool>,
+    last_success_at: Option<SystemTime>,
+    last_failure_at: Option<SystemTime>,
+    last_error: Option<String>,
+}
+
+#[derive(Default)]
+struct HealthState {
+    stats: HashMap<Provider, ProviderStats>,
+    /// Provider that served the last successful transcription
+    last_served: Option<Provider>,
+}
+
+/// Health of one provider, for the preferences page
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHealthReport {
+    pub provider: Provider,
+    /// Outcome counts of the last attempts (at most 20)
+    pub recent_successes: u32,
+    pub recent_failures: u32,
+    /// Unix time in milliseconds
+    pub last_success_at: Option<u64>,
+    pub last_failure_at: Option<u64>,
+    pub last_error: Option<String>,
+    /// This provider served the last successful transcription
+    pub served_last_request: bool,
+}
+
+/// Success/failure history of the transcription providers (managed state)
+#[derive(Default)]
+pub struct ProviderHealth {
+    state: Mutex<HealthState>,
+}
+
+impl ProviderHealth {
+    pub fn record_success(&self, provider: &Provider) {
+        let mut state = self.state.lock().unwrap();
+        let stats = Self::push(&mut state, provider, true);
+        stats.last_success_at = Some(SystemTime::now());
+        state.last_served = Some(provider.clone());
+    }
+
+    pub fn record_failure(&self, provider: &Provider, error: &TranscriptionError) {
+        let mut state = self.state.lock().unwrap();
+        let stats = Self::push(&mut state, provider, false);
+        stats.last_failure_at = Some(SystemTime::now());
+        stats.last_error = Some(error.to_string());
+    }
+
+    /// Providers that were tried at least once
+    pub fn report(&self) -> Vec<ProviderHealthReport> {
+        let state = self.state.lock().unwrap();
+        let mut reports: Vec<_> = state
+            .stats
+            .iter()
+            .map(|(provider, stats)| {
+                let successes = stats.recent.iter().filter(|ok| **ok).count() as u32;
+                ProviderHealthReport {
+                    provider: provider.clone(),
+                    recent_successes: successes,
+                    recent_failures: stats.recent.len() as u32 - successes,
+                    last_success_at: stats.last_success_at.and_then(unix_millis),
+                    last_failure_at: stats.last_failure_at.and_then(unix_millis),
+                    last_error: stats.last_error.clone(),
+                    served_last_request: state.last_served.as_ref() == Some(provider),
+                }
+            })
+            .collect();
+        reports.sort_by_key(|report| std::cmp::Reverse(report.last_success_at));
+        reports
+    }
+
+    fn push<'a>(
+        state: &'a mut HealthState,
+        provider: &Provider,
+        ok: bool,
+    ) -> &'a mut ProviderStats {
+        let stats = state.stats.entry(provider.clone()).or_default();
+        if stats.recent.len() == HISTORY_LEN {
+            stats.recent.pop_front();
+        }
+        stats.recent.push_back(ok);
+        stats
+    }
+}
+
+fn unix_millis(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_millis() as u64)
+}
@@ -1,20 +1,33 @@
 use std::time::Duration;
 use std::time::Instant;
 
+use dictara_core::http::{HttpBody, HttpRequest, HttpSettings, HttpTransport, ReqwestTransport};
 use log::{error, info, warn};
+use secrecy::{ExposeSecret, SecretString};
 use serde_json::{json, Value};
 
-use crate::config::OpenAIConfig;
+use super::post_process_guard;
+use crate::audit::{AuditEntry, AuditLog, AuditRequestKind};
+use crate::config::{OpenAIConfig, PostProcessParams};
 use crate::keychain::{self, ProviderAccount};
 
 const OPENAI_RESPONSES_URL: &str = "https://api.openai.com/v1/responses";
-const OPENAI_POST_PROCESS_TIMEOUT_SECS: u64 = 10;
 
 /// Best-effort post-processing with OpenAI Responses API.
 ///
 /// If OpenAI key/config is missing or request/parsing fails, returns original text unchanged.
-pub fn post_process_with_openai(text: &str, model: &str, prompt: &str) -> String {
-    let started_at = Instant::now();
+/// The transcription is sent as delimited data, and outputs that don't look like a
+/// rewrite of it are rejected too (see [`post_process_guard`]).
+/// The request is sent with `http_settings` (proxy, TLS trust) and is recorded in `audit`
+/// when the audit log is enabled.
+pub fn post_process_with_openai(
+    text: &str,
+    model: &str,
+    prompt: &str,
+    params: PostProcessParams,
+    http_settings: &HttpSettings,
+    audit: Option<&AuditLog>,
+) -> String {
     let trimmed = text.trim();
     let trimmed_model = model.trim();
     let trimmed_prompt = prompt.trim();
@@ -32,82 +45,230 @@ pub fn post_process_with_openai(text: &str, model: &str, prompt: &str) -> String
         return text.to_string();
     }
 
-    let openai_config =
-        match keychain::load_provider_config::<OpenAIConfig>(ProviderAccount::OpenAI) {
-            Ok(Some(config)) => config,
-            Ok(None) => {
-                warn!("OpenAI config not found, skipping post-processing");
-                return text.to_string();
-            }
-            Err(e) => {
-                error!("Failed to load OpenAI config from keychain: {e}");
-                return text.to_string();
-            }
-        };
+    let api_key = match load_api_key() {
+        Ok(api_key) => api_key,
+        Err(e) => {
+            warn!("{e}, skipping post-processing");
+            return text.to_string();
+        }
+    };
+
+    let transport = ReqwestTransport::new(http_settings.clone());
+    rewrite(
+        text,
+        api_key,
+        trimmed_model,
+        trimmed_prompt,
+        params,
+        &transport,
+        audit,
+    )
+}
+
+/// Run `prompt` over `text` with the Responses API and return the model's answer.
+///
+/// Unlike [`post_process_with_openai`], `prompt` is sent as is, without the rewrite guard,
+/// so the answer can be anything the prompt asks for (a summary, a list of action items).
+/// Failures are returned as errors instead of falling back to `text`.
+pub fn complete_with_openai(
+    text: &str,
+    model: &str,
+    prompt: &str,
+    params: PostProcessParams,
+    http_settings: &HttpSettings,
+    audit: Option<&AuditLog>,
+) -> Result<String, String> {
+    let trimmed = text.trim();
+    let trimmed_model = model.trim();
+    if trimmed.is_empty() {
+        return Err("Text is empty".to_string());
+    }
+    if trimmed_model.is_empty() {
+        return Err("Model is empty".to_string());
+    }
+
+    let api_key = load_api_key()?;
+    let transport = ReqwestTransport::new(http_settings.clone());
+    complete(
+        trimmed,
+        api_key,
+        trimmed_model,
+        prompt.trim(),
+        params,
+        &transport,
+        audit,
+    )
+}
 
-    let http_client = match reqwest::blocking::Client::builder()
-        .timeout(Duration::from_secs(OPENAI_POST_PROCESS_TIMEOUT_SECS))
-        .build()
-    {
-        Ok(client) => client,
+fn load_api_key() -> Result<SecretString, String> {
+    match keychain::load_provider_config::<OpenAIConfig>(ProviderAccount::OpenAI) {
+        Ok(Some(config)) => Ok(SecretString::from(config.api_key)),
+        Ok(None) => Err("OpenAI config not found".to_string()),
         Err(e) => {
-            error!("Failed to create HTTP client for post-processing: {e}");
+            error!("Failed to load OpenAI config from keychain: {e}");
+            Err(format!("Failed to load OpenAI config: {e}"))
+        }
+    }
+}
+
+/// Send the transcription to the Responses API, returning `text` unchanged on failure
+fn rewrite(
+    text: &str,
+    api_key: SecretString,
+    model: &str,
+    prompt: &str,
+    params: PostProcessParams,
+    transport: &dyn HttpTransport,
+    audit: Option<&AuditLog>,
+) -> String {
+    let started_at = Instant::now();
+    let trimmed = text.trim();
+
+    let payload = request_payload(
+        model,
+        &post_process_guard::guarded_instructions(prompt),
+        &post_process_guard::user_message(trimmed),
+        params,
+    );
+    let output_text = match send(payload, api_key, params, transport, audit) {
+        Ok(output_text) => output_text,
+        Err(e) => {
+            warn!(
+                "Post-processing failed after {}ms ({e}), using original transcription",
+                started_at.elapsed().as_millis()
+            );
             return text.to_string();
         }
     };
 
-    let payload = json!({
-        "model": trimmed_model,
-        "instructions": trimmed_prompt,
-        "input": trimmed
+    let output_text = if params.guard_output {
+        match post_process_guard::check_output(trimmed, &output_text) {
+            Ok(output_text) => output_text,
+            Err(reason) => {
+                warn!("Post-processing output rejected ({reason}), using original transcription");
+                return text.to_string();
+            }
+        }
+    } else {
+        output_text
+    };
+    info!(
+        "Post-processing succeeded in {}ms (input_len={}, output_len={}, changed={})",
+        started_at.elapsed().as_millis(),
+        trimmed.len(),
+        output_text.len(),
+        output_text != trimmed
+    );
+    output_text
+}
+
+/// Send `text` with `prompt` as the instructions, as is
+fn complete(
+    text: &str,
+    api_key: SecretString,
+    model: &str,
+    prompt: &str,
+    params: PostProcessParams,
+    transport: &dyn HttpTransport,
+    audit: Option<&AuditLog>,
+) -> Result<String, String> {
+    let started_at = Instant::now();
+    let payload = request_payload(model, prompt, text, params);
+    let output_text = send(payload, api_key, params, transport, audit)?;
+    info!(
+        "Completion succeeded in {}ms (input_len={}, output_len={})",
+        started_at.elapsed().as_millis(),
+        text.len(),
+        output_text.len()
+    );
+    Ok(output_text)
+}
+
+fn request_payload(
+    model: &str,
+    instructions: &str,
+    input: &str,
+    params: PostProcessParams,
+) -> Value {
+    let mut payload = json!({
+        "model": model,
+        "instructions": instructions,
+        "input": input
     });
+    // Only sent when configured: reasoning models reject `temperature`
+    if let Some(temperature) = params.temperature {
+        payload["temperature"] = json!(temperature);
+    }
+    if let Some(max_output_tokens) = params.max_output_tokens {
+        payload["max_output_tokens"] = json!(max_output_tokens);
+    }
+    payload
+}
+
+/// Send a Responses API request and return its output text, recorded in `audit`
+fn send(
+    payload: Value,
+    api_key: SecretString,
+    params: PostProcessParams,
+    transport: &dyn HttpTransport,
+    audit: Option<&AuditLog>,
+) -> Result<String, String> {
+    let request_bytes = payload.to_string().len() as u64;
+    let request_started_at = Instant::now();
+    let audit_request = |status: Option<u16>, error: Option<String>, output: Option<&str>| {
+        if let Some(audit) = audit {
+            audit.record(AuditEntry {
+                kind: AuditRequestKind::PostProcessing,
+                provider: "OpenAI",
+                endpoint: OPENAI_RESPONSES_URL,
+                duration: request_started_at.elapsed(),
+                request_bytes,
+                status,
+                error,
+                text: output,
+            });
+        }
+    };
 
-    let response = match http_client
-        .post(OPENAI_RESPONSES_URL)
-        .bearer_auth(openai_config.api_key)
-        .json(&payload)
-        .send()
-    {
+    let request = HttpRequest::post(
+        OPENAI_RESPONSES_URL,
+        HttpBody::Json(payload),
+        Duration::from_secs(params.timeout_secs),
+    )
+    .header(
+        "Authorization",
+        SecretString::from(format!("Bearer {}", api_key.expose_secret())),
+    );
+
+    let response = match transport.send(request) {
         Ok(response) => response,
         Err(e) => {
-            error!("Post-processing request failed: {e}");
-            return text.to_string();
+            let message = e.to_string();
+            error!("Post-processing request failed: {message}");
+            audit_request(None, Some(message.clone()), None);
+            return Err(message);
         }
     };
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let body = response
-            .text()
-            .unwrap_or_else(|_| "Unknown error body".to_string());
-        error!("Post-processing API error ({status}): {body}");
-        return text.to_string();
+    let status = response.status;
+    if !response.is_success() {
+        error!("Post-processing API error ({status}): {}", response.body);
+        audit_request(Some(status), Some(response.body), None);
+        return Err(format!("API error ({status})"));
     }
 
-    let json: Value = match response.json() {
+    let json = match response.json() {
         Ok(json) => json,
         Err(e) => {
             error!("Failed to parse post-processing response JSON: {e}");
-            return text.to_string();
+            audit_request(Some(status), Some(e.to_string()), None);
+            return Err(format!("Invalid response: {e}"));
         }
     };
 
-    if let Some(output_text) = extract_output_text(&json) {
-        info!(
-            "Post-processing succeeded in {}ms (input_len={}, output_len={}, changed={})",
-            started_at.elapsed().as_millis(),
-            trimmed.len(),
-            output_text.len(),
-            output_text != trimmed
-        );
-        return output_text;
-    }
-
-    warn!(
-        "Post-processing response had no output text after {}ms, using original transcription",
-        started_at.elapsed().as_millis()
-    );
-    text.to_string()
+    let output_text = extract_output_text(&json);
+    audit_request(Some(status), None, output_text.as_deref());
+    output_text.ok_or_else(|| "Response had no output text".to_string())
 }
 
 fn extract_output_text(response_json: &Value) -> Option<String> {
@@ -158,3 +319,119 @@ fn extract_output_text(response_json: &Value) -> Option<String> {
         Some(merged)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dictara_core::http::{MockTransport, TransportError};
+
+    const TEXT: &str = "so um the meeting is moved to thursday";
+
+    fn rewrite_with(transport: &MockTransport, params: PostProcessParams) -> String {
+        rewrite(
+            TEXT,
+            SecretString::from("sk-test"),
+            "gpt-4o-mini",
+            "Clean up the transcription",
+            params,
+            transport,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_rewrite_request_and_output() {
+        let transport = MockTransport::new().respond(
+            200,
+            r#"{"output": [{"content": [
+                {"type": "output_text", "text": "The meeting is moved to Thursday."}
+            ]}]}"#,
+        );
+        let params = PostProcessParams {
+            temperature: Some(0.2),
+            ..PostProcessParams::default()
+        };
+
+        assert_eq!(
+            rewrite_with(&transport, params),
+            "The meeting is moved to Thursday."
+        );
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.url, OPENAI_RESPONSES_URL);
+        assert_eq!(
+            request.header_value("Authorization"),
+            Some("Bearer sk-test")
+        );
+        assert_eq!(request.timeout, Duration::from_secs(params.timeout_secs));
+        let HttpBody::Json(payload) = &request.body else {
+            panic!("expected a JSON body");
+        };
+        assert_eq!(payload["model"], "gpt-4o-mini");
+        assert!(payload["temperature"].is_number());
+        assert!(payload.get("max_output_tokens").is_none());
+    }
+
+    #[test]
+    fn test_failures_keep_the_transcription() {
+        let transport = MockTransport::new()
+            .respond(500, "server error")
+            .respond(401, "invalid key")
+            .respond(200, "not json")
+            .respond(200, r#"{"output": []}"#)
+            .respond_with(Err(TransportError::Timeout(Duration::from_secs(10))));
+
+        for _ in 0..5 {
+            assert_eq!(rewrite_with(&transport, PostProcessParams::default()), TEXT);
+        }
+        assert_eq!(transport.requests().len(), 5);
+    }
+
+    #[test]
+    fn test_derailed_output_is_rejected() {
+        let derailed = r#"{"output_text": "I'm sorry, I can't help with scheduling meetings, but here is a long essay about calendars instead, covering their history, the Gregorian reform and more."}"#;
+        let transport = MockTransport::new()
+            .respond(200, derailed)
+            .respond(200, derailed);
+
+        assert_eq!(rewrite_with(&transport, PostProcessParams::default()), TEXT);
+
+        let unguarded = PostProcessParams {
+            guard_output: false,
+            ..PostProcessParams::default()
+        };
+        assert_ne!(rewrite_with(&transport, unguarded), TEXT);
+    }
+
+    #[test]
+    fn test_completion_is_sent_without_the_guard() {
+        let transport = MockTransport::new()
+            .respond(200, r#"{"output_text": "- Meeting moved to Thursday"}"#)
+            .respond(500, "server error")
+            .respond(200, r#"{"output": []}"#);
+        let complete_with = |transport: &MockTransport| {
+            complete(
+                TEXT,
+                SecretString::from("sk-test"),
+                "gpt-4o-mini",
+                "Summarize the meeting",
+                PostProcessParams::default(),
+                transport,
+                None,
+            )
+        };
+
+        assert_eq!(
+            complete_with(&transport).as_deref(),
+            Ok("- Meeting moved to Thursday")
+        );
+        let HttpBody::Json(payload) = &transport.requests()[0].body else {
+            panic!("expected a JSON body");
+        };
+        assert_eq!(payload["instructions"], "Summarize the meeting");
+        assert_eq!(payload["input"], TEXT);
+
+        assert!(complete_with(&transport).is_err());
+        assert!(complete_with(&transport).is_err());
+    }
+}
@@ -1,30 +1,31 @@
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Instant;
 
-use log::{error, warn};
+use dictara_core::http::HttpSettings;
+use dictara_core::transcription::{
+    ApiTranscriber, AzureClient, OpenAIClient, Transcription, TranscriptionClient,
+    TranscriptionError, TranscriptionOptions, TranscriptionService,
+};
+use log::{error, info, warn};
 use secrecy::{ExposeSecret, SecretString};
 use tauri::{AppHandle, Manager};
 
+use crate::audit::{AuditEntry, AuditLog, AuditRequestKind};
 use crate::config::{
     self, AzureOpenAIConfig, ConfigKey, ConfigStore, LocalModelConfig, OpenAIConfig, Provider,
 };
-use crate::keychain::{self, ProviderAccount};
+use crate::keychain::{self, KeychainError, ProviderAccount};
 use crate::models::{is_model_in_catalog, ModelLoader, ModelManager};
+use crate::network;
+use crate::worker_pool::WorkerPool;
 
-use super::api_transcriber::ApiTranscriber;
-use super::azure_client::AzureClient;
-use super::client::TranscriptionClient;
 use super::config::ApiConfig;
-use super::error::TranscriptionError;
 use super::local_transcriber::LocalTranscriber;
-use super::openai_client::OpenAIClient;
-use super::service::TranscriptionService;
+use super::provider_health::ProviderHealth;
 
 const MIN_AUDIO_DURATION_MS: u64 = 500; // Minimum 0.5 seconds
-const MAX_FILE_SIZE_BYTES: u64 = 25 * 1024 * 1024; // 25MB limit
-
-/// Timeout for transcription requests in seconds (applies to all providers)
-pub const TRANSCRIPTION_TIMEOUT_SECS: u64 = 10;
+pub const MAX_FILE_SIZE_BYTES: u64 = 25 * 1024 * 1024; // 25MB limit
 
 // Pre-generated 1-second silent WAV file (16kHz, mono) for API testing
 static SILENT_WAV: &[u8] = include_bytes!("../../assets/silent_1s.wav");
@@ -34,12 +35,28 @@ static SILENT_WAV: &[u8] = include_bytes!("../../assets/silent_1s.wav");
 /// Abstracts away the transcription implementation details - the caller
 /// doesn't need to know whether it's using an API or local model.
 pub struct Transcriber {
+    /// Services in failover order, the active provider first
+    services: Vec<ProviderService>,
+    health: Option<Arc<ProviderHealth>>,
+    audit: Option<Arc<AuditLog>>,
+}
+
+struct ProviderService {
+    provider: Provider,
     service: Box<dyn TranscriptionService>,
+    /// Configured options of the provider's requests
+    options: TranscriptionOptions,
+    /// API endpoint, `None` for local transcription (not audited)
+    endpoint: Option<String>,
 }
 
 impl Transcriber {
     /// Create a new Transcriber from application config and app handle.
     ///
+    /// The active provider is followed by the configured fallback providers. A
+    /// provider that can't be set up (e.g. missing key) is skipped, the error is
+    /// returned only when none can be.
+    ///
     /// The app handle is needed for local provider to access ModelLoader state.
     pub fn from_app(app: &AppHandle) -> Result<Self, TranscriptionError> {
         let config_store = app.state::<config::Config>();
@@ -51,8 +68,52 @@ impl Transcriber {
             .as_ref()
             .ok_or(TranscriptionError::ApiKeyMissing)?;
 
-        let service = Self::create_service(provider, app)?;
-        Ok(Self { service })
+        let health = app
+            .try_state::<Arc<ProviderHealth>>()
+            .map(|health| health.inner().clone());
+        let audit = app
+            .try_state::<Arc<AuditLog>>()
+            .map(|audit| audit.inner().clone());
+
+        let mut providers = vec![provider.clone()];
+        for fallback in &app_config.fallback_providers {
+            if !providers.contains(fallback) {
+                providers.push(fallback.clone());
+            }
+        }
+
+        let mut services = Vec::new();
+        let mut first_error = None;
+        for provider in providers {
+            match Self::create_service(&provider, app) {
+                Ok((service, endpoint)) => services.push(ProviderService {
+                    options: app_config
+                        .transcription_options
+                        .get(&provider)
+                        .cloned()
+                        .unwrap_or_default(),
+                    provider,
+                    service,
+                    endpoint,
+                }),
+                Err(e) => {
+                    warn!("Provider {:?} unavailable: {}", provider, e);
+                    if let Some(health) = &health {
+                        health.record_failure(&provider, &e);
+                    }
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) if services.is_empty() => Err(e),
+            _ => Ok(Self {
+                services,
+                health,
+                audit,
+            }),
+        }
     }
 
     /// Test API credentials without creating a persistent instance.
@@ -63,9 +124,12 @@ impl Transcriber {
     /// * `Ok(true)` - Credentials are valid
     /// * `Ok(false)` - Credentials are invalid (401 Unauthorized)
     /// * `Err(TranscriptionError)` - Network or other API error
-    pub fn test_api_key(config: &ApiConfig) -> Result<bool, TranscriptionError> {
+    pub fn test_api_key(
+        config: &ApiConfig,
+        http_settings: HttpSettings,
+    ) -> Result<bool, TranscriptionError> {
         let client = Self::create_client_from_explicit_config(config);
-        let service = ApiTranscriber::new(client);
+        let service = ApiTranscriber::new(client).with_http_settings(http_settings);
 
         // Create temp file for static audio
         let temp_path = std::env::temp_dir().join("dictara_test_audio.wav");
@@ -78,7 +142,7 @@ impl Transcriber {
 
         let result = match service.transcribe(&temp_path) {
             Ok(_) => Ok(true),
-            Err(TranscriptionError::ApiError(msg)) if msg.contains("401") => {
+            Err(TranscriptionError::HttpStatus { status: 401, .. }) => {
                 warn!("API key is invalid (401 Unauthorized)");
                 Ok(false)
             }
@@ -111,59 +175,169 @@ impl Transcriber {
         file_path: PathBuf,
         duration_ms: u64,
     ) -> Result<String, TranscriptionError> {
+        self.transcribe_detailed(file_path, duration_ms, None)
+            .map(|transcription| transcription.text)
+    }
+
+    /// Transcribe audio file, reporting the language the provider detected.
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the audio file (WAV, MP3, etc.)
+    /// * `duration_ms` - Duration of the recording in milliseconds (for validation)
+    /// * `language` - Language code pinned for the session, overrides the configured
+    ///   language of the provider's options
+    pub fn transcribe_detailed(
+        &self,
+        file_path: PathBuf,
+        duration_ms: u64,
+        language: Option<&str>,
+    ) -> Result<Transcription, TranscriptionError> {
         // Validate minimum duration
         if duration_ms < MIN_AUDIO_DURATION_MS {
             warn!(
                 "Audio too short: {}ms < {}ms minimum",
                 duration_ms, MIN_AUDIO_DURATION_MS
             );
-            return Ok(String::new());
+            return Ok(Transcription::default());
         }
 
         // Validate file
         self.validate_file(&file_path)?;
 
-        // Transcribe using the appropriate service
-        self.service.transcribe(&file_path)
+        // Try the providers in order until one succeeds or the error isn't retryable
+        let mut first_error = None;
+        for ProviderService {
+            provider,
+            service,
+            options,
+            endpoint,
+        } in &self.services
+        {
+            let started_at = Instant::now();
+            let result = match language {
+                Some(language) => service.transcribe_detailed(
+                    &file_path,
+                    &TranscriptionOptions {
+                        language: Some(language.to_string()),
+                        ..options.clone()
+                    },
+                ),
+                None => service.transcribe_detailed(&file_path, options),
+            };
+            if let (Some(audit), Some(endpoint)) = (&self.audit, endpoint) {
+                Self::audit_request(audit, provider, endpoint, &file_path, started_at, &result);
+            }
+
+            match result {
+                Ok(transcription) => {
+                    if first_error.is_some() {
+                        info!("Transcribed with fallback provider {:?}", provider);
+                    }
+                    if let Some(health) = &self.health {
+                        health.record_success(provider);
+                    }
+                    return Ok(transcription);
+                }
+                Err(e) => {
+                    if let Some(health) = &self.health {
+                        health.record_failure(provider, &e);
+                    }
+                    if !e.is_retryable() {
+                        return Err(e);
+                    }
+                    warn!("Provider {:?} failed: {}", provider, e);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        Err(first_error.unwrap_or(TranscriptionError::ApiKeyMissing))
     }
 
     // ========== Private methods ==========
 
-    /// Create the appropriate transcription service based on provider.
+    /// Record an API transcription request in the audit log (metadata only, never audio)
+    fn audit_request(
+        audit: &AuditLog,
+        provider: &Provider,
+        endpoint: &str,
+        file_path: &Path,
+        started_at: Instant,
+        result: &Result<Transcription, TranscriptionError>,
+    ) {
+        let (status, error) = match result {
+            Ok(_) => (Some(200), None),
+            Err(e @ TranscriptionError::HttpStatus { status, .. }) => {
+                (Some(*status), Some(e.to_string()))
+            }
+            Err(e) => (None, Some(e.to_string())),
+        };
+        audit.record(AuditEntry {
+            kind: AuditRequestKind::Transcription,
+            provider: &format!("{:?}", provider),
+            endpoint,
+            duration: started_at.elapsed(),
+            request_bytes: std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0),
+            status,
+            error,
+            text: result.as_ref().ok().map(|t| t.text.as_str()),
+        });
+    }
+
+    /// Create the appropriate transcription service based on provider,
+    /// with its API endpoint (`None` for local transcription).
     fn create_service(
         provider: &Provider,
         app: &AppHandle,
-    ) -> Result<Box<dyn TranscriptionService>, TranscriptionError> {
+    ) -> Result<(Box<dyn TranscriptionService>, Option<String>), TranscriptionError> {
         match provider {
             Provider::OpenAI | Provider::AzureOpenAI => {
-                let client = Self::create_api_client(provider)?;
-                Ok(Box::new(ApiTranscriber::new(client)))
+                if app
+                    .state::<config::Config>()
+                    .managed()
+                    .cloud_providers_disabled()
+                {
+                    return Err(TranscriptionError::ApiError(
+                        "Cloud providers are disabled by your organization".to_string(),
+                    ));
+                }
+                let client = Self::create_api_client(provider, app)?;
+                let endpoint = client.transcription_url();
+                let http_settings = network::http_settings(app.state::<config::Config>().inner());
+                let service = ApiTranscriber::new(client).with_http_settings(http_settings);
+                Ok((Box::new(service), Some(endpoint)))
             }
-            Provider::Local => Self::create_local_service(app),
+            Provider::Local => Ok((Self::create_local_service(app)?, None)),
         }
     }
 
     /// Create API client from keychain credentials.
+    ///
+    /// Keychain failures other than a missing key are reported to the UI, so the
+    /// user is told to unlock the keychain instead of re-entering the key.
     fn create_api_client(
         provider: &Provider,
+        app: &AppHandle,
     ) -> Result<Box<dyn TranscriptionClient>, TranscriptionError> {
         match provider {
             Provider::OpenAI => {
-                let config: OpenAIConfig = keychain::load_provider_config(ProviderAccount::OpenAI)
-                    .map_err(|_| TranscriptionError::ApiKeyMissing)?
-                    .ok_or(TranscriptionError::ApiKeyMissing)?;
+                let config: OpenAIConfig = Self::load_credentials(ProviderAccount::OpenAI, app)?;
                 Ok(Box::new(OpenAIClient::new(SecretString::from(
                     config.api_key,
                 ))))
             }
             Provider::AzureOpenAI => {
                 let config: AzureOpenAIConfig =
-                    keychain::load_provider_config(ProviderAccount::AzureOpenAI)
-                        .map_err(|_| TranscriptionError::ApiKeyMissing)?
-                        .ok_or(TranscriptionError::ApiKeyMissing)?;
+                    Self::load_credentials(ProviderAccount::AzureOpenAI, app)?;
+                // An endpoint set by IT wins over the one the user entered
+                let endpoint = app
+                    .state::<config::Config>()
+                    .managed()
+                    .azure_open_ai_endpoint()
+                    .map_or(config.endpoint, str::to_string);
                 Ok(Box::new(AzureClient::new(
                     SecretString::from(config.api_key),
-                    config.endpoint,
+                    endpoint,
                 )))
             }
             Provider::Local => Err(TranscriptionError::ApiError(
@@ -172,6 +346,20 @@ impl Transcriber {
         }
     }
 
+    fn load_credentials<T: serde::de::DeserializeOwned>(
+        account: ProviderAccount,
+        app: &AppHandle,
+    ) -> Result<T, TranscriptionError> {
+        match keychain::load_provider_config(account) {
+            Ok(Some(config)) => Ok(config),
+            Ok(None) | Err(KeychainError::NotFound) => Err(TranscriptionError::ApiKeyMissing),
+            Err(e) => {
+                keychain::report_access_error(app, account, &e);
+                Err(TranscriptionError::CredentialsUnavailable(e.user_message()))
+            }
+        }
+    }
+
     /// Create local transcription service with validation.
     fn create_local_service(
         app: &AppHandle,
@@ -179,9 +367,12 @@ impl Transcriber {
         // Load local model config
         let config_store = app.state::<config::Config>();
 
-        let local_config: Option<LocalModelConfig> = config_store.get(&ConfigKey::LOCAL_MODEL);
+        let local_config: LocalModelConfig = config_store
+            .get(&ConfigKey::LOCAL_MODEL)
+            .unwrap_or_default();
         let selected_model = local_config
-            .and_then(|c| c.selected_model)
+            .selected_model
+            .clone()
             .ok_or(TranscriptionError::NoModelSelected)?;
 
         // Validate model exists in catalog
@@ -198,9 +389,14 @@ impl Transcriber {
         // Get ModelLoader from Tauri state
         let loader = app.state::<Arc<ModelLoader>>();
 
+        let pool = app.state::<Arc<WorkerPool>>();
+
+        let options = local_config.options_for(&selected_model);
         Ok(Box::new(LocalTranscriber::new(
             loader.inner().clone(),
+            pool.inner().clone(),
             selected_model,
+            options,
         )))
     }
 
@@ -247,3 +443,79 @@ impl Transcriber {
         Ok(())
     }
 }
+
+/// Transcriber of a background `feature` (meeting mode, file transcription, live
+/// captions), to fail fast on config problems (missing API key, no model selected, ...)
+/// before the feature starts. The error is the message for the user.
+pub fn validate_transcription_config(
+    app: &AppHandle,
+    feature: &str,
+) -> Result<Transcriber, String> {
+    Transcriber::from_app(app).map_err(|e| {
+        error!("Failed to create transcriber for {}: {}", feature, e);
+        e.user_message()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dictara_core::http::MockTransport;
+
+    fn api_service(provider: Provider, transport: &Arc<MockTransport>) -> ProviderService {
+        let client = Transcriber::create_client_from_explicit_config(&ApiConfig {
+            provider: provider.clone(),
+            api_key: SecretString::from("key"),
+            endpoint: "https://example.azure.com/transcriptions".to_string(),
+        });
+        ProviderService {
+            provider,
+            service: Box::new(ApiTranscriber::new(client).with_transport(transport.clone())),
+            options: TranscriptionOptions::default(),
+            endpoint: None,
+        }
+    }
+
+    /// Transcribe the silent test audio with OpenAI, falling back to Azure
+    fn transcribe(
+        openai: &Arc<MockTransport>,
+        azure: &Arc<MockTransport>,
+    ) -> Result<String, TranscriptionError> {
+        let transcriber = Transcriber {
+            services: vec![
+                api_service(Provider::OpenAI, openai),
+                api_service(Provider::AzureOpenAI, azure),
+            ],
+            health: None,
+            audit: None,
+        };
+        let path =
+            std::env::temp_dir().join(format!("dictara-failover-{}.wav", std::process::id()));
+        std::fs::write(&path, SILENT_WAV).unwrap();
+        let result = transcriber.transcribe(path.clone(), 1000);
+        std::fs::remove_file(&path).unwrap();
+        result
+    }
+
+    #[test]
+    fn test_falls_back_after_server_error() {
+        let openai = Arc::new(MockTransport::new().respond(503, "overloaded"));
+        let azure = Arc::new(MockTransport::new().respond(200, r#"{"text": "hello"}"#));
+
+        assert_eq!(transcribe(&openai, &azure).unwrap(), "hello");
+        assert_eq!(openai.requests().len(), 1);
+        assert_eq!(azure.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_rejected_audio_is_not_retried() {
+        let openai = Arc::new(MockTransport::new().respond(400, "invalid file format"));
+        let azure = Arc::new(MockTransport::new());
+
+        assert!(matches!(
+            transcribe(&openai, &azure),
+            Err(TranscriptionError::HttpStatus { status: 400, .. })
+        ));
+        assert!(azure.requests().is_empty());
+    }
+}
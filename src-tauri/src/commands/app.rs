@@ -1,3 +1,19 @@
+use crate::config::{self, UiPrefs};
+use crate::diagnostics::DiagnosticsReport;
+use crate::settings_index::{self, SettingMetadata};
+use crate::snapshot::StateSnapshot;
+use crate::startup::{StartupProfiler, StartupReport};
+use crate::ui::prefs;
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+/// Current app state for windows opened mid-session (also emitted when a window loads)
+#[tauri::command]
+#[specta::specta]
+pub fn get_app_snapshot(app_handle: AppHandle) -> StateSnapshot {
+    StateSnapshot::collect(&app_handle)
+}
+
 /// Get the application version with -local suffix when running in debug mode
 #[tauri::command]
 #[specta::specta]
@@ -10,3 +26,46 @@ pub fn get_app_version() -> String {
         version.to_string()
     }
 }
+
+/// Get the appearance preferences (popup style, opacity, theme)
+#[tauri::command]
+#[specta::specta]
+pub fn get_ui_prefs(app: AppHandle) -> UiPrefs {
+    prefs::load_ui_prefs(&app)
+}
+
+/// Save the appearance preferences, all windows receive `UiPrefsChanged`
+#[tauri::command]
+#[specta::specta]
+pub fn set_ui_prefs(app: AppHandle, prefs: UiPrefs) -> Result<(), String> {
+    prefs::save_ui_prefs(&app, prefs)
+}
+
+/// Searchable index of the preferences (id, title, keywords, category) for the settings search
+#[tauri::command]
+#[specta::specta]
+pub fn list_settings_metadata() -> Vec<SettingMetadata> {
+    settings_index::settings_metadata()
+}
+
+/// Ids of the settings locked by a managed (MDM) configuration, shown read-only
+#[tauri::command]
+#[specta::specta]
+pub fn list_managed_settings(config_store: State<config::Config>) -> Vec<String> {
+    config_store.managed().locked_settings()
+}
+
+/// Timings of the startup phases, including the tasks deferred after setup
+#[tauri::command]
+#[specta::specta]
+pub fn get_startup_report(profiler: State<Arc<StartupProfiler>>) -> StartupReport {
+    profiler.report()
+}
+
+/// Version, platform, startup timings, local inference resource usage, keyboard tap
+/// statistics and recent state transitions, for bug reports
+#[tauri::command]
+#[specta::specta]
+pub fn get_diagnostics_report(app: AppHandle) -> DiagnosticsReport {
+    DiagnosticsReport::collect(&app)
+}
@@ -0,0 +1,62 @@
+use crate::automation;
+use crate::config::{self, AutomationConfig, AutomationRule, ConfigKey, ConfigStore};
+use tauri::State;
+
+/// Get the time-of-day automation rules
+#[tauri::command]
+#[specta::specta]
+pub fn list_automation_rules(config_store: State<config::Config>) -> AutomationConfig {
+    config_store.get(&ConfigKey::AUTOMATION).unwrap_or_default()
+}
+
+/// Create an automation rule, returns it with its generated id
+#[tauri::command]
+#[specta::specta]
+pub fn create_automation_rule(
+    config_store: State<config::Config>,
+    rule: AutomationRule,
+) -> Result<AutomationRule, String> {
+    automation::validate_rule(&rule)?;
+    let rule = AutomationRule {
+        id: uuid::Uuid::new_v4().to_string(),
+        ..rule
+    };
+
+    let mut config = config_store.get(&ConfigKey::AUTOMATION).unwrap_or_default();
+    config.rules.push(rule.clone());
+    config_store.set(&ConfigKey::AUTOMATION, config)?;
+
+    Ok(rule)
+}
+
+/// Replace an existing automation rule (matched by id)
+#[tauri::command]
+#[specta::specta]
+pub fn update_automation_rule(
+    config_store: State<config::Config>,
+    rule: AutomationRule,
+) -> Result<(), String> {
+    automation::validate_rule(&rule)?;
+
+    let mut config = config_store.get(&ConfigKey::AUTOMATION).unwrap_or_default();
+    let existing = config
+        .rules
+        .iter_mut()
+        .find(|r| r.id == rule.id)
+        .ok_or_else(|| format!("Automation rule not found: {}", rule.id))?;
+    *existing = rule;
+
+    config_store.set(&ConfigKey::AUTOMATION, config)
+}
+
+/// Delete an automation rule
+#[tauri::command]
+#[specta::specta]
+pub fn delete_automation_rule(
+    config_store: State<config::Config>,
+    id: String,
+) -> Result<(), String> {
+    let mut config = config_store.get(&ConfigKey::AUTOMATION).unwrap_or_default();
+    config.rules.retain(|r| r.id != id);
+    config_store.set(&ConfigKey::AUTOMATION, config)
+}
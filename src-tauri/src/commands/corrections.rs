@@ -0,0 +1,56 @@
+use crate::config::{self, ConfigKey, ConfigStore, LearnedCorrection};
+use crate::corrections;
+use crate::recording::LastRecordingState;
+use std::time::SystemTime;
+use tauri::State;
+
+/// Learn from the user's edited version of the last dictation (review flow)
+///
+/// Only accepted within two minutes of the dictation. Returns the corrections that
+/// this edit made frequent enough to be added to the post-processing prompt.
+#[tauri::command]
+#[specta::specta]
+pub fn submit_dictation_correction(
+    config_store: State<config::Config>,
+    last_recording_state: State<LastRecordingState>,
+    corrected_text: String,
+) -> Result<Vec<LearnedCorrection>, String> {
+    let (text, timestamp) = {
+        let last_recording = last_recording_state
+            .lock()
+            .map_err(|e| format!("Failed to lock last recording: {}", e))?;
+        (last_recording.text.clone(), last_recording.timestamp)
+    };
+    let (Some(text), Some(timestamp)) = (text, timestamp) else {
+        return Err("No dictation to correct".to_string());
+    };
+
+    let age = SystemTime::now()
+        .duration_since(timestamp)
+        .unwrap_or_default();
+    if age > corrections::CORRECTION_WINDOW {
+        return Err("The last dictation is too old to learn from".to_string());
+    }
+
+    corrections::record_correction(config_store.inner(), &text, &corrected_text)
+}
+
+/// List the recorded corrections of dictated text, most recent first
+#[tauri::command]
+#[specta::specta]
+pub fn get_learned_corrections(config_store: State<config::Config>) -> Vec<LearnedCorrection> {
+    config_store
+        .get(&ConfigKey::LEARNED_CORRECTIONS)
+        .unwrap_or_default()
+        .corrections
+}
+
+/// Forget the corrections of a misrecognized word or phrase
+#[tauri::command]
+#[specta::specta]
+pub fn forget_learned_correction(
+    config_store: State<config::Config>,
+    original: String,
+) -> Result<(), String> {
+    corrections::forget_correction(config_store.inner(), &original)
+}
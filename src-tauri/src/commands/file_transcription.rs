@@ -0,0 +1,105 @@
+use crate::config::{self, ConfigKey, ConfigStore};
+use crate::file_transcription::FileTranscriber;
+use crate::insertion;
+use crate::recording::LastRecordingState;
+use dictara_core::subtitles::SubtitleFormat;
+use dictara_core::transcription::TranscriptSegment;
+use log::error;
+use std::sync::Arc;
+use tauri::{Manager, State};
+
+/// Transcribe existing audio files (wav/mp3/m4a) with the active provider
+///
+/// Runs in the background and writes `.txt`/`.srt` files next to each source file.
+/// Progress is reported through `FileTranscriptionStateChanged` events.
+#[tauri::command]
+#[specta::specta]
+pub fn transcribe_files(
+    app: tauri::AppHandle,
+    file_transcriber: State<Arc<FileTranscriber>>,
+    paths: Vec<String>,
+) -> Result<(), String> {
+    file_transcriber.start(app, paths)
+}
+
+/// Transcribe the audio on the clipboard (a voice memo file, a copied path, or audio
+/// copied from Messages or Voice Memos) with the active provider
+///
+/// Returns the text, and also inserts it into the focused app when `paste` is set.
+#[tauri::command]
+#[specta::specta]
+pub async fn transcribe_clipboard_audio(
+    app: tauri::AppHandle,
+    paste: bool,
+) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || {
+        let text = app
+            .state::<Arc<FileTranscriber>>()
+            .transcribe_clipboard_audio(&app)?;
+
+        if paste && !text.is_empty() {
+            let app_config = app
+                .state::<config::Config>()
+                .get(&ConfigKey::APP)
+                .unwrap_or_default();
+            insertion::insert_text_verified(
+                &text,
+                app_config.clipboard_marker,
+                app_config.paste_timing,
+                None,
+            )?;
+        }
+        Ok(text)
+    })
+    .await
+    .map_err(|e| format!("Clipboard transcription task failed: {}", e))?
+}
+
+// ===== SUBTITLE EXPORT COMMANDS =====
+
+/// Export a file transcribed with `transcribe_files` as subtitles next to the source file
+///
+/// Returns the path of the written subtitle file.
+#[tauri::command]
+#[specta::specta]
+pub fn export_file_transcription(
+    file_transcriber: State<Arc<FileTranscriber>>,
+    file_path: String,
+    format: SubtitleFormat,
+) -> Result<String, String> {
+    file_transcriber
+        .export_subtitles(&file_path, format)
+        .map(|path| path.to_string_lossy().to_string())
+        .inspect_err(|e| error!("{}", e))
+}
+
+/// Export the last dictation as subtitles (a single cue spanning the speech duration)
+#[tauri::command]
+#[specta::specta]
+pub fn export_last_transcription(
+    last_recording_state: State<LastRecordingState>,
+    format: SubtitleFormat,
+    output_path: String,
+) -> Result<(), String> {
+    let segment = {
+        let last_recording = last_recording_state
+            .lock()
+            .map_err(|e| format!("Failed to lock last recording state: {}", e))?;
+
+        let text = last_recording
+            .text
+            .clone()
+            .ok_or("No transcription to export")?;
+
+        TranscriptSegment {
+            start_ms: 0,
+            end_ms: last_recording.duration_ms.unwrap_or_default(),
+            text,
+        }
+    };
+
+    std::fs::write(&output_path, format.render(&[segment])).map_err(|e| {
+        error!("Failed to write subtitles to {}: {}", output_path, e);
+        format!("Failed to write subtitles: {}", e)
+    })
+}
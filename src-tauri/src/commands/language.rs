@@ -0,0 +1,99 @@
+use crate::config::{self, AppLanguages, ConfigKey, ConfigStore, LanguageStats};
+use crate::language::{self, SessionLanguage};
+use dictara_core::transcription::normalize_language_code;
+use std::sync::Arc;
+use tauri::State;
+
+/// Get the number of transcriptions per detected language
+#[tauri::command]
+#[specta::specta]
+pub fn get_language_stats(config_store: State<config::Config>) -> LanguageStats {
+    language::load_language_stats(config_store.inner())
+}
+
+/// Clear the per-language statistics
+#[tauri::command]
+#[specta::specta]
+pub fn reset_language_stats(config_store: State<config::Config>) -> Result<(), String> {
+    config_store.delete(&ConfigKey::LANGUAGE_STATS)
+}
+
+/// Get the language pinned for this session (`null` = auto-detect)
+#[tauri::command]
+#[specta::specta]
+pub fn get_session_language(session_language: State<Arc<SessionLanguage>>) -> Option<String> {
+    session_language.pinned()
+}
+
+/// Pin a language for this session (e.g. "en" or "english"), `null` to auto-detect again
+#[tauri::command]
+#[specta::specta]
+pub fn set_session_language(
+    session_language: State<Arc<SessionLanguage>>,
+    language: Option<String>,
+) -> Result<(), String> {
+    let language = match language {
+        Some(language) => Some(
+            normalize_language_code(&language)
+                .ok_or_else(|| format!("Unknown language: {}", language))?,
+        ),
+        None => None,
+    };
+
+    session_language.pin(language);
+    Ok(())
+}
+
+/// Get the language remembered for each app, keyed by bundle identifier
+#[tauri::command]
+#[specta::specta]
+pub fn get_app_languages(config_store: State<config::Config>) -> AppLanguages {
+    language::load_app_languages(config_store.inner())
+}
+
+/// Forget the language remembered for an app, so its next dictation is auto-detected
+#[tauri::command]
+#[specta::specta]
+pub fn forget_app_language(
+    config_store: State<config::Config>,
+    app_id: String,
+) -> Result<(), String> {
+    language::forget_app_language(config_store.inner(), &app_id)
+}
+
+/// Forget the languages remembered for all apps
+#[tauri::command]
+#[specta::specta]
+pub fn clear_app_languages(config_store: State<config::Config>) -> Result<(), String> {
+    config_store.delete(&ConfigKey::APP_LANGUAGES)
+}
+
+/// Set the post-processing prompt for transcriptions in a language (e.g. "ja" or
+/// "japanese"), `null` to use the default prompt for it again
+#[tauri::command]
+#[specta::specta]
+pub fn set_language_post_process_prompt(
+    config_store: State<config::Config>,
+    language: String,
+    prompt: Option<String>,
+) -> Result<(), String> {
+    let code = normalize_language_code(&language)
+        .ok_or_else(|| format!("Unknown language: {}", language))?;
+
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    match prompt {
+        Some(prompt) => {
+            let prompt = prompt.trim();
+            if prompt.is_empty() {
+                return Err("Post-process prompt cannot be empty".to_string());
+            }
+            config
+                .post_process_prompts_by_language
+                .insert(code, prompt.to_string());
+        }
+        None => {
+            config.post_process_prompts_by_language.remove(&code);
+        }
+    }
+    config_store.set(&ConfigKey::APP, config)
+}
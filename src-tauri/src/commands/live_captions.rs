@@ -0,0 +1,29 @@
+use crate::live_captions::LiveCaptions;
+use tauri::State;
+
+/// Open the floating captions window and start transcribing the microphone
+///
+/// Caption lines arrive as `CaptionLine` events, the end of the session as
+/// `LiveCaptionsStateChanged`. Nothing is pasted.
+#[tauri::command]
+#[specta::specta]
+pub fn start_live_captions(
+    app: tauri::AppHandle,
+    live_captions: State<LiveCaptions>,
+) -> Result<(), String> {
+    live_captions.start(&app)
+}
+
+/// Stop the live captions and close their window
+#[tauri::command]
+#[specta::specta]
+pub fn stop_live_captions(app: tauri::AppHandle, live_captions: State<LiveCaptions>) {
+    live_captions.stop(&app);
+}
+
+/// Whether live captions are running
+#[tauri::command]
+#[specta::specta]
+pub fn is_live_captions_running(live_captions: State<LiveCaptions>) -> bool {
+    live_captions.is_running()
+}
@@ -0,0 +1,69 @@
+use crate::config::{self, ConfigKey, ConfigStore};
+use crate::diarization;
+use crate::meeting::MeetingRecorder;
+use crate::models::ModelManager;
+use std::sync::Arc;
+use tauri::State;
+
+/// Start recording a meeting, transcribed in segments in the background
+///
+/// Progress arrives as `MeetingStateChanged` events.
+#[tauri::command]
+#[specta::specta]
+pub fn start_meeting(app: tauri::AppHandle, meeting: State<MeetingRecorder>) -> Result<(), String> {
+    meeting.start(&app)
+}
+
+/// Stop the meeting; its summary is generated in the background
+#[tauri::command]
+#[specta::specta]
+pub fn stop_meeting(meeting: State<MeetingRecorder>) {
+    meeting.stop();
+}
+
+/// Whether a meeting is being recorded
+#[tauri::command]
+#[specta::specta]
+pub fn is_meeting_running(meeting: State<MeetingRecorder>) -> bool {
+    meeting.is_running()
+}
+
+/// Save the summary and transcript of the last meeting as a Markdown file at `path`
+#[tauri::command]
+#[specta::specta]
+pub fn save_meeting_notes(meeting: State<MeetingRecorder>, path: String) -> Result<(), String> {
+    meeting.save_notes(std::path::Path::new(&path))
+}
+
+/// Label meeting transcripts with Speaker 1/Speaker 2, applied to the next meeting.
+/// Requires the speaker model (`download_speaker_model`) and a build with diarization.
+#[tauri::command]
+#[specta::specta]
+pub fn set_meeting_diarization(
+    config_store: State<config::Config>,
+    model_manager: State<Arc<ModelManager>>,
+    enabled: bool,
+) -> Result<(), String> {
+    if enabled && !diarization::AVAILABLE {
+        return Err("Speaker labels are not available in this build".to_string());
+    }
+    if enabled && !model_manager.speaker_model_path().exists() {
+        return Err("Download the speaker model first".to_string());
+    }
+
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.meeting_diarization = enabled;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Download the speaker model of meeting speaker labels (~26MB)
+///
+/// Progress arrives as `ModelDownloadStateChanged` events for "speaker-embedding".
+#[tauri::command]
+#[specta::specta]
+pub async fn download_speaker_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    model_manager.download_speaker_model(app).await
+}
@@ -1,11 +1,33 @@
 mod app;
+mod automation;
+mod corrections;
+mod file_transcription;
+mod language;
+mod live_captions;
+mod meeting;
 pub mod onboarding;
 pub mod preferences;
+mod profiles;
 mod recording;
 pub mod registry;
+mod sinks;
+mod snippets;
+mod templates;
+mod usage;
 
 // Re-export all commands for convenience
 pub use app::*;
+pub use automation::*;
+pub use corrections::*;
+pub use file_transcription::*;
+pub use language::*;
+pub use live_captions::*;
+pub use meeting::*;
 pub use onboarding::*;
 pub use preferences::*;
+pub use profiles::*;
 pub use recording::*;
+pub use sinks::*;
+pub use snippets::*;
+pub use templates::*;
+pub use usage::*;
@@ -1,5 +1,9 @@
-use crate::config::{self, ConfigKey, ConfigStore, Provider};
+use crate::clients::{ProviderHealth, ProviderHealthReport};
+use crate::config::{self, ConfigKey, ConfigStore, Provider, TranscriptionOptions};
+use crate::keychain::{self, KeychainErrorKind, ProviderAccount};
 use log::error;
+use serde::Serialize;
+use std::sync::Arc;
 use tauri::State;
 
 // ===== PROVIDER SELECTION COMMANDS =====
@@ -48,3 +52,73 @@ pub fn clear_current_provider(config_store: State<config::Config>) -> Result<(),
 
     config_store.set(&ConfigKey::APP, config)
 }
+
+/// Set the providers tried, in order, when the active provider fails
+#[tauri::command]
+#[specta::specta]
+pub fn set_fallback_providers(
+    config_store: State<config::Config>,
+    providers: Vec<Provider>,
+) -> Result<(), String> {
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.fallback_providers = providers;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set the model, language, prompt and temperature of a provider's transcription requests
+#[tauri::command]
+#[specta::specta]
+pub fn set_transcription_options(
+    config_store: State<config::Config>,
+    provider: Provider,
+    options: TranscriptionOptions,
+) -> Result<(), String> {
+    options.validate()?;
+
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    if options == TranscriptionOptions::default() {
+        config.transcription_options.remove(&provider);
+    } else {
+        config.transcription_options.insert(provider, options);
+    }
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Recent successes and failures of each provider, and which one served the last request
+#[tauri::command]
+#[specta::specta]
+pub fn get_provider_health(health: State<Arc<ProviderHealth>>) -> Vec<ProviderHealthReport> {
+    health.report()
+}
+
+// ===== KEYCHAIN COMMANDS =====
+
+/// Result of reading one keychain account
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct KeychainAccessStatus {
+    /// Keychain account (e.g. "openAI")
+    pub account: String,
+    /// `None` when the item could be read
+    pub error: Option<KeychainErrorKind>,
+    /// What the user should do about the error
+    pub user_message: Option<String>,
+}
+
+/// Try to read every provider's keychain item, so the UI can tell a locked or
+/// denied keychain apart from a missing API key
+#[tauri::command]
+#[specta::specta]
+pub fn test_keychain_access() -> Vec<KeychainAccessStatus> {
+    ProviderAccount::ALL
+        .into_iter()
+        .map(|account| {
+            let result = keychain::check_access(account);
+            KeychainAccessStatus {
+                account: account.as_ref().to_string(),
+                error: result.as_ref().err().map(|e| e.kind()),
+                user_message: result.err().map(|e| e.user_message()),
+            }
+        })
+        .collect()
+}
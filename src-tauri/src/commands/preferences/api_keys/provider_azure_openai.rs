@@ -1,9 +1,11 @@
 use secrecy::SecretString;
 use serde::Serialize;
+use tauri::State;
 
 use crate::clients::{ApiConfig, Transcriber};
-use crate::config::{AzureOpenAIConfig, Provider};
+use crate::config::{self, AzureOpenAIConfig, Provider};
 use crate::keychain::{self, ProviderAccount};
+use crate::network;
 use log::error;
 
 /// Frontend-facing status for Azure OpenAI provider (never exposes API key)
@@ -12,13 +14,18 @@ use log::error;
 pub struct AzureOpenAIConfigStatus {
     pub configured: bool,
     pub endpoint: String,
+    /// The endpoint is set by IT and can't be changed
+    pub endpoint_managed: bool,
 }
 
 // ===== AZURE OPENAI PROVIDER COMMANDS =====
 
 #[tauri::command]
 #[specta::specta]
-pub fn load_azure_openai_config() -> Result<Option<AzureOpenAIConfigStatus>, String> {
+pub fn load_azure_openai_config(
+    config_store: State<config::Config>,
+) -> Result<Option<AzureOpenAIConfigStatus>, String> {
+    let managed_endpoint = config_store.managed().azure_open_ai_endpoint();
     let config = keychain::load_provider_config::<AzureOpenAIConfig>(ProviderAccount::AzureOpenAI)
         .map_err(|e| {
             let err = format!("Failed to load Azure OpenAI config: {}", e);
@@ -28,13 +35,23 @@ pub fn load_azure_openai_config() -> Result<Option<AzureOpenAIConfigStatus>, Str
 
     Ok(config.map(|c| AzureOpenAIConfigStatus {
         configured: true,
-        endpoint: c.endpoint,
+        endpoint: managed_endpoint.map_or(c.endpoint, str::to_string),
+        endpoint_managed: managed_endpoint.is_some(),
     }))
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn save_azure_openai_config(api_key: String, endpoint: String) -> Result<(), String> {
+pub fn save_azure_openai_config(
+    config_store: State<config::Config>,
+    api_key: String,
+    endpoint: String,
+) -> Result<(), String> {
+    if let Some(managed_endpoint) = config_store.managed().azure_open_ai_endpoint() {
+        if endpoint != managed_endpoint {
+            return Err("The Azure OpenAI endpoint is managed by your organization".to_string());
+        }
+    }
     let config = AzureOpenAIConfig { api_key, endpoint };
 
     keychain::save_provider_config(ProviderAccount::AzureOpenAI, &config).map_err(|e| {
@@ -56,14 +73,18 @@ pub fn delete_azure_openai_config() -> Result<(), String> {
 
 #[tauri::command]
 #[specta::specta]
-pub fn test_azure_openai_config(api_key: String, endpoint: String) -> Result<bool, String> {
+pub fn test_azure_openai_config(
+    config_store: State<config::Config>,
+    api_key: String,
+    endpoint: String,
+) -> Result<bool, String> {
     let config = ApiConfig {
         provider: Provider::AzureOpenAI,
         api_key: SecretString::from(api_key),
         endpoint,
     };
 
-    Transcriber::test_api_key(&config).map_err(|e| {
+    Transcriber::test_api_key(&config, network::http_settings(config_store.inner())).map_err(|e| {
         let err = format!("Failed to test Azure OpenAI config: {}", e);
         error!("{}", err);
         err
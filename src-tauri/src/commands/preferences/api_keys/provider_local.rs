@@ -1,5 +1,13 @@
-use crate::config::{self, ConfigKey, ConfigStore, LocalModelConfig};
-use crate::models::{ModelInfo, ModelLoader, ModelManager};
+use crate::config::{
+    self, ConfigKey, ConfigStore, LocalModelConfig, LocalModelOptions, ModelMirrorConfig,
+};
+use crate::models::{
+    self, MigrationStatus, MirrorSourceHealth, ModelInfo, ModelLoader, ModelManager,
+    ModelVerification, ModelsDiskUsage, ResourceReport,
+};
+use crate::network;
+use crate::ui::engine_menu;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::State;
 
@@ -23,7 +31,11 @@ pub async fn download_model(
     app: tauri::AppHandle,
     model_name: String,
 ) -> Result<(), String> {
-    model_manager.download_model(&model_name, app).await
+    model_manager
+        .download_model(&model_name, app.clone())
+        .await?;
+    engine_menu::refresh_engine_menu(&app);
+    Ok(())
 }
 
 /// Cancel an ongoing model download
@@ -36,15 +48,101 @@ pub fn cancel_model_download(
     model_manager.cancel_download(&model_name)
 }
 
-/// Delete a downloaded model
+/// Delete a downloaded model, to the Trash if `deleteToTrash` is set
 #[tauri::command]
 #[specta::specta]
 pub fn delete_model(
     model_manager: State<Arc<ModelManager>>,
     model_loader: State<Arc<ModelLoader>>,
+    config_store: State<config::Config>,
+    app: tauri::AppHandle,
     model_name: String,
 ) -> Result<(), String> {
-    model_manager.delete_model(&model_name, &model_loader)
+    let to_trash = config_store
+        .get(&ConfigKey::LOCAL_MODEL)
+        .unwrap_or_default()
+        .delete_to_trash;
+    model_manager.delete_model(&model_name, &model_loader, to_trash)?;
+    engine_menu::refresh_engine_menu(&app);
+    Ok(())
+}
+
+/// Check a downloaded model's files against their checksums,
+/// re-downloading only the corrupted ones if `repair` is set
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    model_loader: State<'_, Arc<ModelLoader>>,
+    app: tauri::AppHandle,
+    model_name: String,
+    repair: bool,
+) -> Result<ModelVerification, String> {
+    model_manager
+        .verify_model(&model_name, repair, &model_loader, app)
+        .await
+}
+
+/// Download the Core ML encoder of a Whisper model (Apple Silicon only)
+#[tauri::command]
+#[specta::specta]
+pub async fn download_coreml_encoder(
+    model_manager: State<'_, Arc<ModelManager>>,
+    app: tauri::AppHandle,
+    model_name: String,
+) -> Result<(), String> {
+    model_manager
+        .download_coreml_encoder(&model_name, app)
+        .await
+}
+
+/// Delete the Core ML encoder of a model (falls back to Metal)
+#[tauri::command]
+#[specta::specta]
+pub fn delete_coreml_encoder(
+    model_manager: State<Arc<ModelManager>>,
+    model_loader: State<Arc<ModelLoader>>,
+    model_name: String,
+) -> Result<(), String> {
+    model_manager.delete_coreml_encoder(&model_name, &model_loader)
+}
+
+/// Get the disk space used by each downloaded model, including interrupted downloads
+#[tauri::command]
+#[specta::specta]
+pub fn get_models_disk_usage(model_manager: State<Arc<ModelManager>>) -> ModelsDiskUsage {
+    model_manager.disk_usage()
+}
+
+/// Get the directory the models are stored in
+#[tauri::command]
+#[specta::specta]
+pub fn get_models_directory(model_manager: State<Arc<ModelManager>>) -> String {
+    model_manager
+        .location()
+        .models_dir()
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Move the downloaded models to `path` (e.g. an external drive), new models are
+/// downloaded there too. `path` must be empty or not exist yet.
+/// Returns the new models directory.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_models_directory(
+    model_manager: State<'_, Arc<ModelManager>>,
+    path: String,
+) -> Result<String, String> {
+    let models_dir = model_manager.relocate(PathBuf::from(path.trim())).await?;
+    Ok(models_dir.to_string_lossy().to_string())
+}
+
+/// Get the progress of the startup migration to the per-model directory structure
+#[tauri::command]
+#[specta::specta]
+pub fn get_migration_status(model_manager: State<Arc<ModelManager>>) -> MigrationStatus {
+    model_manager.migration_status()
 }
 
 /// Load a model into memory for transcription
@@ -72,6 +170,13 @@ pub fn get_loaded_model(model_loader: State<Arc<ModelLoader>>) -> Option<String>
     model_loader.get_loaded_model_name()
 }
 
+/// Get the memory, swap and GPU usage of the last model load and local transcription
+#[tauri::command]
+#[specta::specta]
+pub fn get_resource_report(model_loader: State<Arc<ModelLoader>>) -> ResourceReport {
+    model_loader.resource_report()
+}
+
 /// Load local model configuration
 #[tauri::command]
 #[specta::specta]
@@ -90,14 +195,100 @@ pub fn save_local_model_config(
 ) -> Result<(), String> {
     let config = LocalModelConfig {
         selected_model: Some(model_name),
+        ..config_store
+            .get(&ConfigKey::LOCAL_MODEL)
+            .unwrap_or_default()
     };
 
     config_store.set(&ConfigKey::LOCAL_MODEL, config)
 }
 
+/// Save the transcription options of a local model (language hint, timestamps, beam size)
+#[tauri::command]
+#[specta::specta]
+pub fn set_local_model_options(
+    config_store: State<config::Config>,
+    model_name: String,
+    options: LocalModelOptions,
+) -> Result<(), String> {
+    let mut config: LocalModelConfig = config_store
+        .get(&ConfigKey::LOCAL_MODEL)
+        .unwrap_or_default();
+    config.model_options.insert(model_name, options);
+
+    config_store.set(&ConfigKey::LOCAL_MODEL, config)
+}
+
+/// Move deleted models to the Trash instead of removing them for good
+#[tauri::command]
+#[specta::specta]
+pub fn set_delete_models_to_trash(
+    config_store: State<config::Config>,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut config: LocalModelConfig = config_store
+        .get(&ConfigKey::LOCAL_MODEL)
+        .unwrap_or_default();
+    config.delete_to_trash = enabled;
+
+    config_store.set(&ConfigKey::LOCAL_MODEL, config)
+}
+
+/// Set the mirrors model files are downloaded from
+#[tauri::command]
+#[specta::specta]
+pub fn set_model_download_mirror(
+    config_store: State<config::Config>,
+    mirror: ModelMirrorConfig,
+) -> Result<(), String> {
+    mirror.validate()?;
+
+    let mut config: LocalModelConfig = config_store
+        .get(&ConfigKey::LOCAL_MODEL)
+        .unwrap_or_default();
+    config.download_mirror = mirror;
+
+    config_store.set(&ConfigKey::LOCAL_MODEL, config)
+}
+
+/// Save the Hugging Face token sent with model downloads, `None` to delete it
+#[tauri::command]
+#[specta::specta]
+pub fn set_hugging_face_token(token: Option<String>) -> Result<(), String> {
+    models::save_hugging_face_token(token.filter(|token| !token.trim().is_empty()))
+}
+
+/// Check that every model source is reachable through the configured mirror
+#[tauri::command]
+#[specta::specta]
+pub async fn check_model_mirror(
+    config_store: State<'_, config::Config>,
+) -> Result<Vec<MirrorSourceHealth>, String> {
+    let mirror = config_store
+        .get(&ConfigKey::LOCAL_MODEL)
+        .unwrap_or_default()
+        .download_mirror;
+    let http_settings = network::http_settings(config_store.inner());
+    Ok(models::check_mirror_sources(&mirror, &http_settings).await)
+}
+
 /// Delete local model configuration
+///
+/// A relocated models directory is kept, the downloaded models are still there.
 #[tauri::command]
 #[specta::specta]
 pub fn delete_local_model_config(config_store: State<config::Config>) -> Result<(), String> {
-    config_store.delete(&ConfigKey::LOCAL_MODEL)
+    let models_dir = config_store
+        .get(&ConfigKey::LOCAL_MODEL)
+        .and_then(|config| config.models_dir);
+    match models_dir {
+        Some(models_dir) => config_store.set(
+            &ConfigKey::LOCAL_MODEL,
+            LocalModelConfig {
+                models_dir: Some(models_dir),
+                ..LocalModelConfig::default()
+            },
+        ),
+        None => config_store.delete(&ConfigKey::LOCAL_MODEL),
+    }
 }
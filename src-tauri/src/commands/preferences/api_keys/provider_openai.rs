@@ -1,9 +1,11 @@
 use secrecy::SecretString;
 use serde::Serialize;
+use tauri::State;
 
 use crate::clients::{ApiConfig, Transcriber};
-use crate::config::{OpenAIConfig, Provider};
+use crate::config::{self, OpenAIConfig, Provider};
 use crate::keychain::{self, ProviderAccount};
+use crate::network;
 use log::error;
 
 /// Frontend-facing status for OpenAI provider (never exposes API key)
@@ -52,14 +54,17 @@ pub fn delete_openai_config() -> Result<(), String> {
 
 #[tauri::command]
 #[specta::specta]
-pub fn test_openai_config(api_key: String) -> Result<bool, String> {
+pub fn test_openai_config(
+    config_store: State<config::Config>,
+    api_key: String,
+) -> Result<bool, String> {
     let config = ApiConfig {
         provider: Provider::OpenAI,
         api_key: SecretString::from(api_key),
         endpoint: String::new(),
     };
 
-    Transcriber::test_api_key(&config).map_err(|e| {
+    Transcriber::test_api_key(&config, network::http_settings(config_store.inner())).map_err(|e| {
         let err = format!("Failed to test OpenAI config: {}", e);
         error!("{}", err);
         err
@@ -1,8 +1,13 @@
+use crate::audit::AuditLog;
 use crate::config::{
-    self, AppConfig, ConfigKey, ConfigStore, Provider, RecordingTrigger,
-    MAX_ALLOWED_SPEECH_DURATION_MS, MIN_ALLOWED_SPEECH_DURATION_MS,
+    self, AppConfig, AuditLogConfig, ConfigKey, ConfigStore, FlushPolicy, InputDeviceChangePolicy,
+    KeyboardTapConfig, MarkdownModeConfig, PostProcessParams, Provider, ProxyConfig,
+    RecordingTrigger, RedactionConfig, StatePushConfig, SystemSleepPolicy, TlsConfig,
+    MAX_ALLOWED_SPEECH_DURATION_MS, MAX_AUDIO_LEVEL_HZ, MIN_ALLOWED_SPEECH_DURATION_MS,
 };
+use crate::worker_pool::MAX_WORKER_THREADS;
 use log::error;
+use std::sync::Arc;
 use tauri::State;
 
 // ===== GENERAL APP CONFIGURATION COMMANDS =====
@@ -24,6 +29,7 @@ pub fn save_app_config(
     post_process_enabled: Option<bool>,
     post_process_model: Option<String>,
     post_process_prompt: Option<String>,
+    post_process_params: Option<PostProcessParams>,
     min_speech_duration_ms: Option<u64>,
 ) -> Result<(), String> {
     // Load existing config to preserve fields that aren't being updated
@@ -70,6 +76,12 @@ pub fn save_app_config(
         config.post_process_prompt = prompt.to_string();
     }
 
+    // Update post-processing timeout and sampling parameters if specified
+    if let Some(params) = post_process_params {
+        params.validate()?;
+        config.post_process_params = params;
+    }
+
     // Update minimum speech duration if specified
     if let Some(duration_ms) = min_speech_duration_ms {
         if !(MIN_ALLOWED_SPEECH_DURATION_MS..=MAX_ALLOWED_SPEECH_DURATION_MS).contains(&duration_ms)
@@ -84,3 +96,272 @@ pub fn save_app_config(
 
     config_store.set(&ConfigKey::APP, config)
 }
+
+/// Set consent for reading the frontmost window (title and optionally selected text)
+/// into the post-processing prompt
+#[tauri::command]
+#[specta::specta]
+pub fn set_document_context_consent(
+    config_store: State<config::Config>,
+    enabled: bool,
+    include_selection: bool,
+) -> Result<(), String> {
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.document_context_enabled = enabled;
+    config.document_context_include_selection = enabled && include_selection;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Enable or disable writing spoken numbers, dates and units in written form
+#[tauri::command]
+#[specta::specta]
+pub fn set_format_normalization(
+    config_store: State<config::Config>,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.format_normalization = enabled;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set what happens when the default input device changes while recording
+#[tauri::command]
+#[specta::specta]
+pub fn set_input_device_change_policy(
+    config_store: State<config::Config>,
+    policy: InputDeviceChangePolicy,
+) -> Result<(), String> {
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.input_device_change_policy = policy;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set what happens to a running recording when the Mac sleeps or the screen locks
+#[tauri::command]
+#[specta::specta]
+pub fn set_system_sleep_policy(
+    config_store: State<config::Config>,
+    policy: SystemSleepPolicy,
+) -> Result<(), String> {
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.system_sleep_policy = policy;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set how often recordings are flushed to disk while recording
+#[tauri::command]
+#[specta::specta]
+pub fn set_recording_flush_policy(
+    config_store: State<config::Config>,
+    policy: FlushPolicy,
+) -> Result<(), String> {
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.recording_flush_policy = policy;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set the most audio level updates per second sent to the recording popup
+/// (0 = every audio buffer), applied to the next recording
+#[tauri::command]
+#[specta::specta]
+pub fn set_audio_level_max_hz(
+    config_store: State<config::Config>,
+    max_hz: u32,
+) -> Result<(), String> {
+    if max_hz > MAX_AUDIO_LEVEL_HZ {
+        return Err(format!(
+            "Audio level rate must be at most {} Hz",
+            MAX_AUDIO_LEVEL_HZ
+        ));
+    }
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.audio_level_max_hz = max_hz;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set after how many seconds a recording is stopped and transcribed (0 = only at the
+/// transcription file limit), applied to the next tick of the running recording
+#[tauri::command]
+#[specta::specta]
+pub fn set_max_recording_secs(
+    config_store: State<config::Config>,
+    max_secs: u32,
+) -> Result<(), String> {
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.max_recording_secs = max_secs;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set the number of worker threads, 0 = automatic (applied on next launch)
+#[tauri::command]
+#[specta::specta]
+pub fn set_worker_threads(config_store: State<config::Config>, threads: u32) -> Result<(), String> {
+    if threads > MAX_WORKER_THREADS {
+        return Err(format!(
+            "At most {} worker threads can be used",
+            MAX_WORKER_THREADS
+        ));
+    }
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.worker_threads = threads;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set the event tap used by the keyboard listener (applied on next launch)
+#[tauri::command]
+#[specta::specta]
+pub fn set_keyboard_tap_config(
+    config_store: State<config::Config>,
+    tap: KeyboardTapConfig,
+) -> Result<(), String> {
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.keyboard_tap = tap;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set the proxy of outbound HTTP requests. `password` replaces the stored proxy
+/// password (`None` keeps it), which is deleted when there is no username.
+#[tauri::command]
+#[specta::specta]
+pub fn set_proxy_config(
+    config_store: State<config::Config>,
+    proxy: ProxyConfig,
+    password: Option<String>,
+) -> Result<(), String> {
+    proxy.validate()?;
+    if proxy.username.is_none() {
+        crate::network::save_password(None)?;
+    } else if password.is_some() {
+        crate::network::save_password(password)?;
+    }
+
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.proxy = proxy;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set the custom root CA and pinned certificates of outbound HTTPS requests.
+/// Every certificate file must exist and contain PEM certificates.
+#[tauri::command]
+#[specta::specta]
+pub fn set_tls_config(config_store: State<config::Config>, tls: TlsConfig) -> Result<(), String> {
+    tls.validate()?;
+
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.tls = tls;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set the push of the recording state over MQTT or a local WebSocket.
+/// `mqtt_password` replaces the stored broker password (`None` keeps it), which is
+/// deleted when there is no username.
+#[tauri::command]
+#[specta::specta]
+pub fn set_state_push_config(
+    config_store: State<config::Config>,
+    state_push: StatePushConfig,
+    mqtt_password: Option<String>,
+) -> Result<(), String> {
+    state_push.validate()?;
+    if state_push.mqtt.username.is_none() {
+        crate::recording::save_mqtt_password(None)?;
+    } else if mqtt_password.is_some() {
+        crate::recording::save_mqtt_password(mqtt_password)?;
+    }
+
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.state_push = state_push;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set the profanity and sensitive data filter of pasted dictations
+#[tauri::command]
+#[specta::specta]
+pub fn set_redaction_config(
+    config_store: State<config::Config>,
+    mut redaction: RedactionConfig,
+) -> Result<(), String> {
+    for words in [&mut redaction.blocked_words, &mut redaction.allowed_words] {
+        *words = words
+            .iter()
+            .map(|word| word.trim().to_string())
+            .filter(|word| !word.is_empty())
+            .collect();
+    }
+
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.redaction = redaction;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Set which apps get spoken formatting cues written as Markdown
+#[tauri::command]
+#[specta::specta]
+pub fn set_markdown_mode(
+    config_store: State<config::Config>,
+    mut markdown_mode: MarkdownModeConfig,
+) -> Result<(), String> {
+    markdown_mode.app_overrides = markdown_mode
+        .app_overrides
+        .into_iter()
+        .map(|(app_id, enabled)| (app_id.trim().to_string(), enabled))
+        .filter(|(app_id, _)| !app_id.is_empty())
+        .collect();
+
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.markdown_mode = markdown_mode;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// List the input devices that can be used as loopback (system audio) source
+#[tauri::command]
+#[specta::specta]
+pub fn list_input_devices() -> Vec<String> {
+    dictara_core::recorder::input_device_names()
+}
+
+/// Mix system audio from `device_name` into recordings, `None` to record the microphone only
+#[tauri::command]
+#[specta::specta]
+pub fn set_loopback_device(
+    config_store: State<config::Config>,
+    device_name: Option<String>,
+) -> Result<(), String> {
+    let device_name = device_name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.loopback_device = device_name;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+// ===== AUDIT LOG COMMANDS =====
+
+/// Enable or disable the audit log of outbound API requests
+#[tauri::command]
+#[specta::specta]
+pub fn set_audit_log_config(
+    config_store: State<config::Config>,
+    audit_log: AuditLogConfig,
+) -> Result<(), String> {
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.audit_log = audit_log;
+    config_store.set(&ConfigKey::APP, config)
+}
+
+/// Start a new audit log file, returning the path of the previous one (if any)
+#[tauri::command]
+#[specta::specta]
+pub fn rotate_audit_log(audit: State<Arc<AuditLog>>) -> Result<Option<String>, String> {
+    Ok(audit
+        .rotate()?
+        .map(|path| path.to_string_lossy().to_string()))
+}
+
+/// Export all audit log files, oldest first, into a single file at `destination`
+#[tauri::command]
+#[specta::specta]
+pub fn export_audit_log(audit: State<Arc<AuditLog>>, destination: String) -> Result<(), String> {
+    audit.export(std::path::Path::new(&destination))
+}
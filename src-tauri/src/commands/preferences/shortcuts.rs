@@ -1,7 +1,12 @@
-use crate::config::{self, ConfigKey, ConfigStore, ShortcutsConfig};
+use crate::config::{
+    self, ConfigKey, ConfigStore, ShortcutsConfig, MAX_DEBOUNCE_MS, MAX_HOLD_THRESHOLD_MS,
+};
+use crate::crash_guard::SystemGuard;
 use crate::keyboard_listener::KeyListener;
+use crate::shortcuts::events::{KeyboardGrabStats, SecureInputChanged};
+use dictara_keyboard::KeyboardLayout;
 use log::info;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 
 #[tauri::command]
 #[specta::specta]
@@ -15,35 +20,42 @@ pub fn load_shortcuts_config(
 #[specta::specta]
 pub fn save_shortcuts_config(
     config_store: State<config::Config>,
-    key_listener: State<KeyListener>,
     config: ShortcutsConfig,
 ) -> Result<(), String> {
     // Validate all shortcuts
     config.push_to_record.validate()?;
     config.hands_free.validate()?;
+    if let Some(raw_mode) = &config.raw_mode {
+        raw_mode.validate()?;
+    }
+    for device in &config.device_shortcuts {
+        device
+            .push_to_record
+            .validate()
+            .and_then(|_| device.hands_free.validate())
+            .map_err(|e| format!("{}: {}", device.name, e))?;
+    }
+    if config.hold_threshold_ms > MAX_HOLD_THRESHOLD_MS {
+        return Err(format!(
+            "Hold threshold must be at most {} ms",
+            MAX_HOLD_THRESHOLD_MS
+        ));
+    }
+    if config.min_start_interval_ms > MAX_DEBOUNCE_MS || config.bounce_ms > MAX_DEBOUNCE_MS {
+        return Err(format!(
+            "Shortcut debounce times must be at most {} ms",
+            MAX_DEBOUNCE_MS
+        ));
+    }
 
-    // Load old config for Fn key change detection
-    let old_config = config_store.get(&ConfigKey::SHORTCUTS).unwrap_or_default();
-    let old_uses_fn = KeyListener::uses_fn_key(&old_config);
-    let new_uses_fn = KeyListener::uses_fn_key(&config);
-
-    // Save to persistent storage
+    // Save to persistent storage, the KeyListener hot-swaps it on the config change
+    // and the Globe key is changed or restored depending on whether Fn is used
     config_store.set(&ConfigKey::SHORTCUTS, config.clone())?;
     info!(
         "Shortcuts config saved: push_to_record={:?}, hands_free={:?}",
         config.push_to_record.keys, config.hands_free.keys
     );
 
-    // Hot-swap runtime config via channel (NO RESTART NEEDED!)
-    key_listener.update_shortcuts(config)?;
-    info!("Shortcuts config hot-swapped to KeyListener");
-
-    // Update globe key fix if Fn usage changed
-    if !old_uses_fn && new_uses_fn {
-        crate::globe_key::fix_globe_key_if_needed();
-        info!("Globe key fix applied (Fn key now in use)");
-    }
-
     Ok(())
 }
 
@@ -51,15 +63,22 @@ pub fn save_shortcuts_config(
 #[specta::specta]
 pub fn reset_shortcuts_config(
     config_store: State<config::Config>,
-    key_listener: State<KeyListener>,
 ) -> Result<ShortcutsConfig, String> {
     let defaults = ShortcutsConfig::default();
     config_store.set(&ConfigKey::SHORTCUTS, defaults.clone())?;
-    key_listener.update_shortcuts(defaults.clone())?;
-    info!("Shortcuts config reset to defaults and hot-swapped to KeyListener");
+    info!("Shortcuts config reset to defaults");
     Ok(defaults)
 }
 
+/// Put back the Globe key behavior the user had before Dictara changed it.
+/// Returns `false` when it wasn't changed. With a shortcut still using Fn, the
+/// next launch or shortcut change sets it to "Do Nothing" again.
+#[tauri::command]
+#[specta::specta]
+pub fn restore_globe_key_behavior(system_guard: State<SystemGuard>) -> Result<bool, String> {
+    system_guard.restore()
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn start_key_capture(
@@ -67,8 +86,10 @@ pub fn start_key_capture(
     key_listener: State<KeyListener>,
 ) -> Result<(), String> {
     info!("Entering key capture mode");
+    // Sync commands run on the main thread, where the keyboard layout can be read
+    let layout = KeyboardLayout::current();
     // Switch KeyListener to capture mode
-    key_listener.enter_capture_mode(app_handle)
+    key_listener.enter_capture_mode(app_handle, layout)
 }
 
 #[tauri::command]
@@ -86,3 +107,35 @@ pub fn stop_key_capture(
     // Switch KeyListener back to normal mode
     key_listener.exit_capture_mode(shortcuts)
 }
+
+/// Whether an app currently has macOS Secure Input enabled (shortcuts don't work then)
+#[tauri::command]
+#[specta::specta]
+pub fn get_secure_input_state() -> SecureInputChanged {
+    KeyListener::current_secure_input()
+}
+
+/// Whether the keyboard listener is running. False while it waits to be restarted
+/// after a failure, or when it was never started (no accessibility permission).
+#[tauri::command]
+#[specta::specta]
+pub fn is_listener_healthy(app_handle: AppHandle) -> bool {
+    app_handle
+        .try_state::<KeyListener>()
+        .is_some_and(|listener| listener.is_healthy())
+}
+
+/// Event counters and callback latency of the keyboard tap since launch (or the last reset)
+#[tauri::command]
+#[specta::specta]
+pub fn get_keyboard_grab_stats() -> KeyboardGrabStats {
+    dictara_keyboard::grab_stats().into()
+}
+
+/// Reset the keyboard tap statistics, e.g. before reproducing input lag
+#[tauri::command]
+#[specta::specta]
+pub fn reset_keyboard_grab_stats() {
+    info!("Resetting keyboard grab statistics");
+    dictara_keyboard::reset_grab_stats();
+}
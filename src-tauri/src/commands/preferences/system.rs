@@ -1,3 +1,4 @@
+use crate::autolaunch;
 use crate::config::{self, AppConfig, ConfigKey, ConfigStore};
 use log::error;
 use tauri::{AppHandle, State};
@@ -38,6 +39,28 @@ pub fn is_autostart_enabled(app: AppHandle) -> Result<bool, String> {
     })
 }
 
+/// Whether the app launches at login, as currently registered with the system
+/// (reflects changes made in System Settings)
+#[tauri::command]
+#[specta::specta]
+pub fn get_autostart_enabled(
+    app: AppHandle,
+    config_store: State<config::Config>,
+) -> Result<bool, String> {
+    autolaunch::autostart_enabled(&app, config_store.inner())
+}
+
+/// Turn launch at login on or off, returning the resulting system state
+#[tauri::command]
+#[specta::specta]
+pub fn set_autostart_enabled(
+    app: AppHandle,
+    config_store: State<config::Config>,
+    enabled: bool,
+) -> Result<bool, String> {
+    autolaunch::set_autostart_enabled(&app, config_store.inner(), enabled)
+}
+
 /// Mark that initial autostart setup has been completed
 /// This is called after enabling autostart on first launch
 #[tauri::command]
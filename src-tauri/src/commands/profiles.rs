@@ -0,0 +1,32 @@
+use crate::config::{self, ProfilesConfig};
+use crate::profiles;
+use tauri::State;
+
+/// Get the saved profiles and the active one
+#[tauri::command]
+#[specta::specta]
+pub fn list_profiles(config_store: State<config::Config>) -> ProfilesConfig {
+    profiles::load_profiles(config_store.inner())
+}
+
+/// Save the current settings as a profile (replacing one of the same name) and make
+/// it the active profile
+#[tauri::command]
+#[specta::specta]
+pub fn save_profile(config_store: State<config::Config>, name: String) -> Result<(), String> {
+    profiles::save_current(config_store.inner(), &name)
+}
+
+/// Switch to another profile, the current settings are kept in the active one
+#[tauri::command]
+#[specta::specta]
+pub fn switch_profile(config_store: State<config::Config>, name: String) -> Result<(), String> {
+    profiles::switch(config_store.inner(), &name)
+}
+
+/// Delete a profile, the current settings stay as they are
+#[tauri::command]
+#[specta::specta]
+pub fn delete_profile(config_store: State<config::Config>, name: String) -> Result<(), String> {
+    profiles::delete(config_store.inner(), &name)
+}
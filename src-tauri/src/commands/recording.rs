@@ -1,5 +1,11 @@
-use crate::recording::{LastRecordingState, RecordingCommand};
+use crate::models::ModelLoader;
+use crate::notes::NoteTarget;
+use crate::recording::{
+    events::RecordingStateChanged, LastRecordingState, RawMode, RecordingCommand, RecordingState,
+    RecordingStateManager, ResultWaiter, StateHistoryEntry,
+};
 use crate::setup::{AudioLevelChannel, RecordingCommandSender};
+use std::sync::Arc;
 use tauri::ipc::Channel;
 use tauri::State;
 
@@ -16,9 +22,42 @@ pub fn stop_recording(sender: State<RecordingCommandSender>) -> Result<(), Strin
     Ok(())
 }
 
+/// Stop the current recording and wait until it is transcribed and pasted. Resolves
+/// with the final state: `stopped` with the text, `cancelled`, or `error` with the
+/// error details.
 #[tauri::command]
 #[specta::specta]
-pub fn cancel_recording(sender: State<RecordingCommandSender>) -> Result<(), String> {
+pub async fn stop_recording_and_wait(
+    sender: State<'_, RecordingCommandSender>,
+) -> Result<RecordingStateChanged, String> {
+    let (waiter, outcome) = ResultWaiter::new();
+    sender
+        .sender
+        .send(RecordingCommand::StopRecordingAndWait(waiter))
+        .await
+        .map_err(|e| format!("Failed to send StopRecording command: {}", e))?;
+
+    outcome
+        .await
+        .map_err(|_| "No recording in progress".to_string())
+}
+
+/// Cancel the current recording, or abort the local transcription in progress
+///
+/// The controller is busy while transcribing, so a local transcription is aborted
+/// directly through the model loader. API transcriptions can't be aborted.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_recording(
+    sender: State<RecordingCommandSender>,
+    state_manager: State<Arc<RecordingStateManager>>,
+    model_loader: State<Arc<ModelLoader>>,
+) -> Result<(), String> {
+    if state_manager.current() == RecordingState::Transcribing {
+        model_loader.cancel_transcription();
+        return Ok(());
+    }
+
     sender
         .sender
         .blocking_send(RecordingCommand::Cancel)
@@ -27,6 +66,52 @@ pub fn cancel_recording(sender: State<RecordingCommandSender>) -> Result<(), Str
     Ok(())
 }
 
+/// Lock the current recording into hands-free mode (same as pressing the hands-free shortcut)
+#[tauri::command]
+#[specta::specta]
+pub fn lock_recording(sender: State<RecordingCommandSender>) -> Result<(), String> {
+    sender
+        .sender
+        .blocking_send(RecordingCommand::LockRecording)
+        .map_err(|e| format!("Failed to send LockRecording command: {}", e))?;
+
+    Ok(())
+}
+
+/// Start a hands-free dictation into a new note in `target`, instead of pasting
+///
+/// Stops like any hands-free recording (Fn or the stop button). The note is titled
+/// after the first sentence of the text. Ignored while a recording is in progress.
+#[tauri::command]
+#[specta::specta]
+pub fn dictate_to_new_note(
+    sender: State<RecordingCommandSender>,
+    target: NoteTarget,
+) -> Result<(), String> {
+    sender
+        .sender
+        .blocking_send(RecordingCommand::StartNoteDictation(target))
+        .map_err(|e| format!("Failed to start the note dictation: {}", e))?;
+
+    Ok(())
+}
+
+/// Current recording state, for windows that open after the last `RecordingStateChanged`
+#[tauri::command]
+#[specta::specta]
+pub fn get_recording_state(state_manager: State<Arc<RecordingStateManager>>) -> RecordingState {
+    state_manager.current()
+}
+
+/// The last recording state transitions with timestamps, oldest first, for bug reports
+#[tauri::command]
+#[specta::specta]
+pub fn dump_state_history(
+    state_manager: State<Arc<RecordingStateManager>>,
+) -> Vec<StateHistoryEntry> {
+    state_manager.history()
+}
+
 // ===== AUDIO MONITORING =====
 
 #[tauri::command]
@@ -61,10 +146,7 @@ pub fn dismiss_error(
 ) -> Result<(), String> {
     // Delete audio file if exists
     if let Ok(mut last_recording) = last_recording_state.lock() {
-        if let Some(path) = last_recording.audio_file_path.take() {
-            crate::recording::cleanup_recording_file(&path);
-        }
-        last_recording.audio_file_path = None;
+        last_recording.discard_audio();
     }
 
     // Close popup
@@ -72,9 +154,63 @@ pub fn dismiss_error(
         .map_err(|e| format!("Failed to close popup: {}", e))
 }
 
+/// Discard a failed recording from the error popup
+///
+/// Unlike `dismiss_error`, the audio kept for retry is deleted by the recording
+/// controller, in order with the other recording commands. It refuses to run while a
+/// recording or transcription is still in progress.
+#[tauri::command]
+#[specta::specta]
+pub fn discard_error(
+    app: tauri::AppHandle,
+    sender: State<RecordingCommandSender>,
+    state_manager: State<Arc<RecordingStateManager>>,
+) -> Result<(), String> {
+    if state_manager.is_busy() {
+        return Err(format!(
+            "Cannot discard while in {} state",
+            state_manager.current()
+        ));
+    }
+
+    sender
+        .sender
+        .blocking_send(RecordingCommand::DiscardError)
+        .map_err(|e| format!("Failed to send DiscardError command: {}", e))?;
+
+    crate::ui::window::close_recording_popup(&app)
+        .map_err(|e| format!("Failed to close popup: {}", e))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn resize_popup_for_error(app: tauri::AppHandle) -> Result<(), String> {
     crate::ui::window::resize_recording_popup_for_error(&app)
         .map_err(|e| format!("Failed to resize popup: {}", e))
 }
+
+// ===== RAW MODE COMMANDS =====
+
+/// Whether raw (verbatim) dictation mode is enabled
+#[tauri::command]
+#[specta::specta]
+pub fn get_raw_mode(raw_mode: State<Arc<RawMode>>) -> bool {
+    raw_mode.is_enabled()
+}
+
+/// Enable or disable raw (verbatim) dictation mode: no post-processing, no templates
+#[tauri::command]
+#[specta::specta]
+pub fn set_raw_mode(raw_mode: State<Arc<RawMode>>, enabled: bool) {
+    raw_mode.set(enabled);
+}
+
+// ===== CLIPBOARD COMMANDS =====
+
+/// Put back clipboard content replaced by a paste (one paste further back per call).
+/// Returns whether anything was restored.
+#[tauri::command]
+#[specta::specta]
+pub fn restore_previous_clipboard() -> Result<bool, String> {
+    crate::text_paster::restore_previous_clipboard().map_err(|e| e.to_string())
+}
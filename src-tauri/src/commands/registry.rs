@@ -6,6 +6,13 @@ macro_rules! with_commands {
         $($wrapper)*![
             // App
             $crate::commands::get_app_version,
+            $crate::commands::get_app_snapshot,
+            $crate::commands::get_ui_prefs,
+            $crate::commands::set_ui_prefs,
+            $crate::commands::list_settings_metadata,
+            $crate::commands::list_managed_settings,
+            $crate::commands::get_startup_report,
+            $crate::commands::get_diagnostics_report,
             // Accessibility
             $crate::commands::check_accessibility_permission,
             $crate::commands::request_accessibility_permission,
@@ -16,10 +23,38 @@ macro_rules! with_commands {
             // App configuration
             $crate::commands::load_app_config,
             $crate::commands::save_app_config,
+            $crate::commands::set_document_context_consent,
+            $crate::commands::set_format_normalization,
+            $crate::commands::set_markdown_mode,
+            $crate::commands::set_redaction_config,
+            $crate::commands::set_input_device_change_policy,
+            $crate::commands::set_system_sleep_policy,
+            $crate::commands::set_keyboard_tap_config,
+            $crate::commands::set_worker_threads,
+            // Audio input
+            $crate::commands::list_input_devices,
+            $crate::commands::set_loopback_device,
+            $crate::commands::set_recording_flush_policy,
+            $crate::commands::set_audio_level_max_hz,
+            $crate::commands::set_max_recording_secs,
+            // Network
+            $crate::commands::set_proxy_config,
+            $crate::commands::set_tls_config,
+            // Recording state push
+            $crate::commands::set_state_push_config,
+            // Audit log
+            $crate::commands::set_audit_log_config,
+            $crate::commands::rotate_audit_log,
+            $crate::commands::export_audit_log,
             // Provider selection
             $crate::commands::get_current_provider,
             $crate::commands::set_current_provider,
             $crate::commands::clear_current_provider,
+            $crate::commands::set_fallback_providers,
+            $crate::commands::set_transcription_options,
+            $crate::commands::get_provider_health,
+            // Keychain
+            $crate::commands::test_keychain_access,
             // OpenAI provider
             $crate::commands::load_openai_config,
             $crate::commands::save_openai_config,
@@ -35,19 +70,100 @@ macro_rules! with_commands {
             $crate::commands::download_model,
             $crate::commands::cancel_model_download,
             $crate::commands::delete_model,
+            $crate::commands::verify_model,
+            $crate::commands::download_coreml_encoder,
+            $crate::commands::delete_coreml_encoder,
+            $crate::commands::get_migration_status,
+            $crate::commands::get_models_directory,
+            $crate::commands::get_models_disk_usage,
+            $crate::commands::set_models_directory,
             $crate::commands::load_model,
             $crate::commands::unload_model,
             $crate::commands::get_loaded_model,
+            $crate::commands::get_resource_report,
             $crate::commands::load_local_model_config,
             $crate::commands::save_local_model_config,
+            $crate::commands::set_local_model_options,
+            $crate::commands::set_model_download_mirror,
+            $crate::commands::set_delete_models_to_trash,
+            $crate::commands::set_hugging_face_token,
+            $crate::commands::check_model_mirror,
             $crate::commands::delete_local_model_config,
             // Recording
             $crate::commands::stop_recording,
+            $crate::commands::stop_recording_and_wait,
             $crate::commands::cancel_recording,
+            $crate::commands::lock_recording,
+            $crate::commands::dictate_to_new_note,
+            $crate::commands::get_recording_state,
+            $crate::commands::dump_state_history,
             $crate::commands::retry_transcription,
             $crate::commands::dismiss_error,
+            $crate::commands::discard_error,
             $crate::commands::resize_popup_for_error,
             $crate::commands::register_audio_level_channel,
+            // Raw mode
+            $crate::commands::get_raw_mode,
+            $crate::commands::set_raw_mode,
+            // Clipboard recovery
+            $crate::commands::restore_previous_clipboard,
+            // File transcription
+            $crate::commands::transcribe_files,
+            $crate::commands::transcribe_clipboard_audio,
+            // Live captions
+            $crate::commands::start_live_captions,
+            $crate::commands::stop_live_captions,
+            $crate::commands::is_live_captions_running,
+            // Meeting mode
+            $crate::commands::start_meeting,
+            $crate::commands::stop_meeting,
+            $crate::commands::is_meeting_running,
+            $crate::commands::save_meeting_notes,
+            $crate::commands::set_meeting_diarization,
+            $crate::commands::download_speaker_model,
+            // Subtitle export
+            $crate::commands::export_file_transcription,
+            $crate::commands::export_last_transcription,
+            // Language detection
+            $crate::commands::get_language_stats,
+            $crate::commands::reset_language_stats,
+            $crate::commands::get_session_language,
+            $crate::commands::set_session_language,
+            $crate::commands::get_app_languages,
+            $crate::commands::forget_app_language,
+            $crate::commands::clear_app_languages,
+            $crate::commands::set_language_post_process_prompt,
+            // Daily word goal
+            $crate::commands::get_goal_progress,
+            $crate::commands::set_daily_word_goal,
+            // Dictation templates
+            $crate::commands::list_templates,
+            $crate::commands::create_template,
+            $crate::commands::update_template,
+            $crate::commands::delete_template,
+            $crate::commands::set_active_template,
+            // Configuration profiles
+            $crate::commands::list_profiles,
+            $crate::commands::save_profile,
+            $crate::commands::switch_profile,
+            $crate::commands::delete_profile,
+            // Automation rules
+            $crate::commands::list_automation_rules,
+            $crate::commands::create_automation_rule,
+            $crate::commands::update_automation_rule,
+            $crate::commands::delete_automation_rule,
+            // Output sinks
+            $crate::commands::get_output_sinks_config,
+            $crate::commands::save_output_sinks_config,
+            // Voice snippets
+            $crate::commands::list_snippets,
+            $crate::commands::create_snippet,
+            $crate::commands::update_snippet,
+            $crate::commands::delete_snippet,
+            // Learned corrections
+            $crate::commands::submit_dictation_correction,
+            $crate::commands::get_learned_corrections,
+            $crate::commands::forget_learned_correction,
             // Onboarding
             $crate::commands::restart_app,
             $crate::commands::load_onboarding_config,
@@ -60,12 +176,19 @@ macro_rules! with_commands {
             $crate::commands::load_shortcuts_config,
             $crate::commands::save_shortcuts_config,
             $crate::commands::reset_shortcuts_config,
+            $crate::commands::restore_globe_key_behavior,
+            $crate::commands::get_secure_input_state,
+            $crate::commands::is_listener_healthy,
+            $crate::commands::get_keyboard_grab_stats,
+            $crate::commands::reset_keyboard_grab_stats,
             $crate::commands::start_key_capture,
             $crate::commands::stop_key_capture,
             // System
             $crate::commands::enable_autostart,
             $crate::commands::disable_autostart,
             $crate::commands::is_autostart_enabled,
+            $crate::commands::get_autostart_enabled,
+            $crate::commands::set_autostart_enabled,
             $crate::commands::mark_autostart_setup_done,
             $crate::commands::is_autostart_setup_done,
         ]
@@ -0,0 +1,32 @@
+use crate::config::{self, ConfigKey, ConfigStore, OutputSinksConfig};
+use crate::sinks;
+use tauri::State;
+
+/// Get the settings of the output sinks (journal file, webhook)
+#[tauri::command]
+#[specta::specta]
+pub fn get_output_sinks_config(config_store: State<config::Config>) -> OutputSinksConfig {
+    config_store
+        .get(&ConfigKey::OUTPUT_SINKS)
+        .unwrap_or_default()
+}
+
+/// Save the settings of the output sinks, applied from the next dictation.
+/// `webhook_secret` replaces the stored signing secret (`None` keeps it, empty
+/// deletes it); it is deleted when the webhook has no URL.
+#[tauri::command]
+#[specta::specta]
+pub fn save_output_sinks_config(
+    config_store: State<config::Config>,
+    config: OutputSinksConfig,
+    webhook_secret: Option<String>,
+) -> Result<(), String> {
+    config.validate()?;
+    if config.webhook.url.is_none() {
+        sinks::save_webhook_secret(None)?;
+    } else if let Some(secret) = webhook_secret {
+        sinks::save_webhook_secret(Some(secret).filter(|secret| !secret.is_empty()))?;
+    }
+
+    config_store.set(&ConfigKey::OUTPUT_SINKS, config)
+}
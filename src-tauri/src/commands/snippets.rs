@@ -0,0 +1,87 @@
+use crate::config::{self, ConfigKey, ConfigStore, SnippetsConfig, VoiceSnippet};
+use crate::snippets;
+use tauri::State;
+
+/// Get all voice snippets
+#[tauri::command]
+#[specta::specta]
+pub fn list_snippets(config_store: State<config::Config>) -> SnippetsConfig {
+    snippets::load_snippets(config_store.inner())
+}
+
+/// Create a voice snippet, returns it with its generated id
+#[tauri::command]
+#[specta::specta]
+pub fn create_snippet(
+    config_store: State<config::Config>,
+    trigger: String,
+    text: String,
+) -> Result<VoiceSnippet, String> {
+    let mut config = snippets::load_snippets(config_store.inner());
+    let snippet = validate_snippet(
+        VoiceSnippet {
+            id: uuid::Uuid::new_v4().to_string(),
+            trigger,
+            text,
+        },
+        &config,
+    )?;
+
+    config.snippets.push(snippet.clone());
+    config_store.set(&ConfigKey::SNIPPETS, config)?;
+
+    Ok(snippet)
+}
+
+/// Replace an existing voice snippet (matched by id)
+#[tauri::command]
+#[specta::specta]
+pub fn update_snippet(
+    config_store: State<config::Config>,
+    snippet: VoiceSnippet,
+) -> Result<(), String> {
+    let mut config = snippets::load_snippets(config_store.inner());
+    let snippet = validate_snippet(snippet, &config)?;
+
+    let existing = config
+        .snippets
+        .iter_mut()
+        .find(|s| s.id == snippet.id)
+        .ok_or_else(|| format!("Snippet not found: {}", snippet.id))?;
+    *existing = snippet;
+
+    config_store.set(&ConfigKey::SNIPPETS, config)
+}
+
+/// Delete a voice snippet
+#[tauri::command]
+#[specta::specta]
+pub fn delete_snippet(config_store: State<config::Config>, id: String) -> Result<(), String> {
+    let mut config = snippets::load_snippets(config_store.inner());
+    config.snippets.retain(|s| s.id != id);
+    config_store.set(&ConfigKey::SNIPPETS, config)
+}
+
+/// Trim the trigger and reject snippets that can't be used
+fn validate_snippet(
+    snippet: VoiceSnippet,
+    config: &SnippetsConfig,
+) -> Result<VoiceSnippet, String> {
+    let trigger = snippet.trigger.trim().to_string();
+    if !trigger.chars().any(char::is_alphanumeric) {
+        return Err("Snippet trigger phrase cannot be empty".to_string());
+    }
+    if snippet.text.trim().is_empty() {
+        return Err("Snippet text cannot be empty".to_string());
+    }
+
+    let duplicate = config
+        .snippets
+        .iter()
+        .any(|s| s.id != snippet.id && s.trigger.to_lowercase() == trigger.to_lowercase());
+    if duplicate {
+        return Err(format!("A snippet for \"{}\" already exists", trigger));
+    }
+
+    Ok(VoiceSnippet { trigger, ..snippet })
+}
@@ -0,0 +1,101 @@
+use crate::config::{self, DictationTemplate, TemplatesConfig};
+use crate::templates;
+use tauri::{AppHandle, State};
+
+/// Get all dictation templates and the active one
+#[tauri::command]
+#[specta::specta]
+pub fn list_templates(config_store: State<config::Config>) -> TemplatesConfig {
+    templates::load_templates(config_store.inner())
+}
+
+/// Create a template, returns it with its generated id
+#[tauri::command]
+#[specta::specta]
+pub fn create_template(
+    app: AppHandle,
+    config_store: State<config::Config>,
+    name: String,
+    keyword: Option<String>,
+    body: String,
+) -> Result<DictationTemplate, String> {
+    let template = validate_template(DictationTemplate {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        keyword,
+        body,
+    })?;
+
+    let mut config = templates::load_templates(config_store.inner());
+    config.templates.push(template.clone());
+    templates::save_templates(&app, config)?;
+
+    Ok(template)
+}
+
+/// Replace an existing template (matched by id)
+#[tauri::command]
+#[specta::specta]
+pub fn update_template(
+    app: AppHandle,
+    config_store: State<config::Config>,
+    template: DictationTemplate,
+) -> Result<(), String> {
+    let template = validate_template(template)?;
+
+    let mut config = templates::load_templates(config_store.inner());
+    let existing = config
+        .templates
+        .iter_mut()
+        .find(|t| t.id == template.id)
+        .ok_or_else(|| format!("Template not found: {}", template.id))?;
+    *existing = template;
+
+    templates::save_templates(&app, config)
+}
+
+/// Delete a template, deactivating it if it was active
+#[tauri::command]
+#[specta::specta]
+pub fn delete_template(
+    app: AppHandle,
+    config_store: State<config::Config>,
+    id: String,
+) -> Result<(), String> {
+    let mut config = templates::load_templates(config_store.inner());
+    config.templates.retain(|t| t.id != id);
+    if config.active_template_id.as_deref() == Some(id.as_str()) {
+        config.active_template_id = None;
+    }
+
+    templates::save_templates(&app, config)
+}
+
+/// Set the template applied to every dictation (`null` = none)
+#[tauri::command]
+#[specta::specta]
+pub fn set_active_template(app: AppHandle, id: Option<String>) -> Result<(), String> {
+    templates::set_active_template(&app, id)
+}
+
+/// Trim fields and reject templates that can't be used
+fn validate_template(template: DictationTemplate) -> Result<DictationTemplate, String> {
+    let name = template.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Template name cannot be empty".to_string());
+    }
+    if template.body.trim().is_empty() {
+        return Err("Template text cannot be empty".to_string());
+    }
+
+    let keyword = template
+        .keyword
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty());
+
+    Ok(DictationTemplate {
+        name,
+        keyword,
+        ..template
+    })
+}
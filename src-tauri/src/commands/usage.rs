@@ -0,0 +1,30 @@
+use crate::config::{self, ConfigKey, ConfigStore};
+use crate::usage::{self, GoalProgress};
+use tauri::State;
+
+/// Get the words dictated today and the progress towards the daily goal
+#[tauri::command]
+#[specta::specta]
+pub fn get_goal_progress(config_store: State<config::Config>) -> GoalProgress {
+    let goal = config_store
+        .get(&ConfigKey::APP)
+        .unwrap_or_default()
+        .daily_word_goal;
+    usage::goal_progress(
+        config_store.inner(),
+        goal,
+        chrono::Local::now().date_naive(),
+    )
+}
+
+/// Set the daily dictated-words goal, `null` (or 0) to turn it off
+#[tauri::command]
+#[specta::specta]
+pub fn set_daily_word_goal(
+    config_store: State<config::Config>,
+    goal: Option<u32>,
+) -> Result<(), String> {
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.daily_word_goal = goal.filter(|goal| *goal > 0);
+    config_store.set(&ConfigKey::APP, config)
+}
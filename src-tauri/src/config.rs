@@ -1,5 +1,13 @@
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
+use crate::managed_config::ManagedConfig;
+
+pub use crate::audit::AuditLogConfig;
+pub use crate::transcription_eta::TranscriptionSpeed;
+pub use dictara_core::paste::{ClipboardMarker, PasteTiming};
+pub use dictara_core::recorder::{FlushPolicy, DEFAULT_LEVEL_MAX_HZ};
+pub use dictara_core::transcription::{LocalModelOptions, TranscriptionOptions};
+
 pub const DEFAULT_POST_PROCESS_MODEL: &str = "gpt-4.1-nano";
 pub const DEFAULT_POST_PROCESS_PROMPT: &str = r#"You are a text post-processor for speech transcription.
 Rewrite the user text with these rules:
@@ -28,8 +36,90 @@ fn default_min_speech_duration_ms() -> u64 {
     DEFAULT_MIN_SPEECH_DURATION_MS
 }
 
+/// Highest accepted `audio_level_max_hz` (above, the meter can't show more)
+pub const MAX_AUDIO_LEVEL_HZ: u32 = 120;
+
+fn default_audio_level_max_hz() -> u32 {
+    DEFAULT_LEVEL_MAX_HZ
+}
+
+pub const DEFAULT_MIN_START_INTERVAL_MS: u32 = 250;
+pub const DEFAULT_BOUNCE_MS: u32 = 30;
+/// Longest accepted [`ShortcutsConfig::min_start_interval_ms`] and [`ShortcutsConfig::bounce_ms`]
+pub const MAX_DEBOUNCE_MS: u32 = 1000;
+
+fn default_min_start_interval_ms() -> u32 {
+    DEFAULT_MIN_START_INTERVAL_MS
+}
+
+fn default_bounce_ms() -> u32 {
+    DEFAULT_BOUNCE_MS
+}
+
+pub const MIN_POST_PROCESS_TIMEOUT_SECS: u64 = 1;
+pub const MAX_POST_PROCESS_TIMEOUT_SECS: u64 = 120;
+pub const MAX_POST_PROCESS_TEMPERATURE: f32 = 2.0;
+/// Lowest `max_output_tokens` accepted by the Responses API
+pub const MIN_POST_PROCESS_MAX_OUTPUT_TOKENS: u32 = 16;
+
+/// Request parameters of the post-processing call
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PostProcessParams {
+    /// Request timeout, the transcription is pasted unprocessed when it expires
+    pub timeout_secs: u64,
+    /// Sampling temperature, `None` = model default (reasoning models reject it)
+    pub temperature: Option<f32>,
+    /// Output token limit, `None` = model default
+    pub max_output_tokens: Option<u32>,
+    /// Reject outputs whose length or script diverges from the transcription (the
+    /// dictation derailed the model). Disable for prompts that translate or summarize.
+    pub guard_output: bool,
+}
+
+impl Default for PostProcessParams {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 10,
+            temperature: None,
+            max_output_tokens: None,
+            guard_output: true,
+        }
+    }
+}
+
+impl PostProcessParams {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(MIN_POST_PROCESS_TIMEOUT_SECS..=MAX_POST_PROCESS_TIMEOUT_SECS)
+            .contains(&self.timeout_secs)
+        {
+            return Err(format!(
+                "timeoutSecs must be between {} and {}",
+                MIN_POST_PROCESS_TIMEOUT_SECS, MAX_POST_PROCESS_TIMEOUT_SECS
+            ));
+        }
+        if let Some(temperature) = self.temperature {
+            if !(0.0..=MAX_POST_PROCESS_TEMPERATURE).contains(&temperature) {
+                return Err(format!(
+                    "temperature must be between 0 and {}",
+                    MAX_POST_PROCESS_TEMPERATURE
+                ));
+            }
+        }
+        if let Some(max_output_tokens) = self.max_output_tokens {
+            if max_output_tokens < MIN_POST_PROCESS_MAX_OUTPUT_TOKENS {
+                return Err(format!(
+                    "maxOutputTokens must be at least {}",
+                    MIN_POST_PROCESS_MAX_OUTPUT_TOKENS
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Provider types supported by the application
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, specta::Type)]
 pub enum Provider {
     #[serde(rename = "open_ai", alias = "openai", alias = "open_a_i")]
     OpenAI,
@@ -55,6 +145,286 @@ pub enum RecordingTrigger {
     Command,
 }
 
+/// What to do when the default input device changes (e.g. AirPods connected) mid-recording
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum InputDeviceChangePolicy {
+    /// Continue the recording on the new default device
+    #[default]
+    Follow,
+    /// Keep recording on the current device and tell the user
+    Notify,
+    /// Keep recording on the current device
+    Ignore,
+}
+
+/// What to do with a running recording when the Mac goes to sleep or the screen locks
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum SystemSleepPolicy {
+    /// Stop the recording and transcribe what was captured
+    #[default]
+    Transcribe,
+    /// Discard the recording
+    Cancel,
+}
+
+/// How outbound HTTP requests (APIs, model downloads, updates) reach the internet
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ProxyMode {
+    /// System proxy settings and `HTTP(S)_PROXY` variables
+    #[default]
+    System,
+    /// Direct connections, ignoring the system proxy
+    Direct,
+    /// The proxy in [`ProxyConfig`]
+    Manual,
+}
+
+/// Proxy settings. The password of `username` is stored in the keychain.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProxyConfig {
+    pub mode: ProxyMode,
+    /// Manual proxy host, e.g. "proxy.corp.example"
+    pub host: String,
+    pub port: u16,
+    /// Basic authentication user, `None` = no authentication
+    pub username: Option<String>,
+}
+
+impl ProxyConfig {
+    /// Check that a manual proxy has a host and port
+    pub fn validate(&self) -> Result<(), String> {
+        if self.mode != ProxyMode::Manual {
+            return Ok(());
+        }
+        if self.host.trim().is_empty() {
+            return Err("Proxy host is required".to_string());
+        }
+        if self.port == 0 {
+            return Err("Proxy port is required".to_string());
+        }
+        Ok(())
+    }
+
+    /// URL of the manual proxy (without credentials)
+    pub fn url(&self) -> String {
+        let host = self.host.trim();
+        if host.contains("://") {
+            format!("{}:{}", host, self.port)
+        } else {
+            format!("http://{}:{}", host, self.port)
+        }
+    }
+}
+
+/// Recording state published to an MQTT broker. The password of `username` is stored
+/// in the keychain.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MqttPushConfig {
+    pub enabled: bool,
+    /// Broker host, e.g. "homeassistant.local"
+    pub host: String,
+    pub port: u16,
+    /// Topic the state is published to (retained)
+    pub topic: String,
+    /// `None` = no authentication
+    pub username: Option<String>,
+}
+
+impl Default for MqttPushConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: String::new(),
+            port: 1883,
+            topic: "dictara/state".to_string(),
+            username: None,
+        }
+    }
+}
+
+/// Recording state sent to the clients of a WebSocket server on 127.0.0.1
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WebSocketPushConfig {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for WebSocketPushConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 47361,
+        }
+    }
+}
+
+/// Push of the recording state for home automation (e.g. a light turning red while
+/// recording)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StatePushConfig {
+    pub mqtt: MqttPushConfig,
+    pub websocket: WebSocketPushConfig,
+}
+
+impl StatePushConfig {
+    /// Check that the enabled channels have what they need to connect
+    pub fn validate(&self) -> Result<(), String> {
+        if self.mqtt.enabled {
+            if self.mqtt.host.trim().is_empty() {
+                return Err("MQTT broker host is required".to_string());
+            }
+            if self.mqtt.port == 0 {
+                return Err("MQTT broker port is required".to_string());
+            }
+            let topic = self.mqtt.topic.trim();
+            if topic.is_empty() || topic.contains(['#', '+']) {
+                return Err(format!("Invalid MQTT topic: {}", self.mqtt.topic));
+            }
+        }
+        if self.websocket.enabled && self.websocket.port < 1024 {
+            return Err("WebSocket port must be between 1024 and 65535".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Which profanity the redaction filter catches
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum RedactionStrictness {
+    /// Strong profanity and slurs
+    #[default]
+    Standard,
+    /// Also mild swearing ("damn", "hell", "crap")
+    Strict,
+}
+
+/// How the redaction filter hides what it catches
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum RedactionStyle {
+    /// Mask words ("f***") and replace sensitive data with a placeholder ("[email]")
+    #[default]
+    Mask,
+    /// Leave them out of the text
+    Remove,
+}
+
+/// Filter of profanity and sensitive data applied to dictations before pasting
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RedactionConfig {
+    pub enabled: bool,
+    pub strictness: RedactionStrictness,
+    pub style: RedactionStyle,
+    /// Numbers that look like payment card numbers (13-19 digits passing the Luhn check)
+    pub redact_card_numbers: bool,
+    pub redact_emails: bool,
+    /// Additional words to filter
+    pub blocked_words: Vec<String>,
+    /// Words never filtered, e.g. names or email addresses caught by mistake
+    pub allowed_words: Vec<String>,
+}
+
+/// Markdown dictation mode: spoken formatting cues ("bullet point", "heading two") are
+/// written as Markdown. Great in editors, harmful in plain chat boxes, hence per app.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MarkdownModeConfig {
+    /// Whether apps without an override get Markdown
+    pub enabled: bool,
+    /// Bundle identifier of an app (e.g. "md.obsidian") to whether it gets Markdown
+    pub app_overrides: std::collections::BTreeMap<String, bool>,
+}
+
+impl MarkdownModeConfig {
+    /// Whether dictations into the app with bundle identifier `app_id` get Markdown
+    pub fn enabled_for(&self, app_id: Option<&str>) -> bool {
+        app_id
+            .and_then(|app_id| self.app_overrides.get(app_id))
+            .copied()
+            .unwrap_or(self.enabled)
+    }
+}
+
+/// Certificates trusted for outbound HTTPS, on top of the system roots
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TlsConfig {
+    /// PEM file with a corporate root CA (e.g. of a proxy inspecting TLS)
+    pub custom_ca_path: Option<String>,
+    /// Host (e.g. "api.openai.com") to the PEM file of the only roots trusted for it
+    pub pinned_certificates: std::collections::BTreeMap<String, String>,
+}
+
+impl TlsConfig {
+    /// Check that the certificate files exist and parse
+    pub fn validate(&self) -> Result<(), String> {
+        let paths = self
+            .custom_ca_path
+            .iter()
+            .chain(self.pinned_certificates.values());
+        for path in paths {
+            dictara_core::http::load_certificates(std::path::Path::new(path))
+                .map_err(|e| e.to_string())?;
+        }
+        if self
+            .pinned_certificates
+            .keys()
+            .any(|host| host.trim().is_empty() || host.contains('/'))
+        {
+            return Err("Pinned certificates need a host name, e.g. api.openai.com".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Where the keyboard listener taps key events (macOS `CGEventTapLocation`)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyboardTapLocation {
+    /// Hardware level: sees every key first, but may interfere with secure input
+    /// (password fields)
+    #[default]
+    Hid,
+    /// Login session level: leaves secure input alone, but runs after other
+    /// session taps such as key remappers
+    Session,
+}
+
+/// Event tap used by the keyboard listener. Applied when the listener starts.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct KeyboardTapConfig {
+    pub location: KeyboardTapLocation,
+    /// Only observe key events. Shortcut keys are no longer swallowed
+    /// (e.g. Fn still opens the emoji picker), but input is never delayed by the listener.
+    pub listen_only: bool,
+}
+
+impl KeyboardTapConfig {
+    pub fn to_grab_config(self) -> dictara_keyboard::GrabConfig {
+        dictara_keyboard::GrabConfig {
+            location: match self.location {
+                KeyboardTapLocation::Hid => dictara_keyboard::TapLocation::Hid,
+                KeyboardTapLocation::Session => dictara_keyboard::TapLocation::Session,
+            },
+            mode: if self.listen_only {
+                dictara_keyboard::TapMode::ListenOnly
+            } else {
+                dictara_keyboard::TapMode::Intercept
+            },
+        }
+    }
+}
+
 impl RecordingTrigger {
     /// Convert to the keyboard crate's Key type
     #[allow(dead_code)]
@@ -114,6 +484,61 @@ pub struct ShortcutsConfig {
     pub push_to_record: Shortcut,
     /// Hands-free: Press to toggle (start/stop)
     pub hands_free: Shortcut,
+    /// Raw mode: Press to toggle verbatim dictation (no post-processing), `None` = no shortcut
+    #[serde(default)]
+    pub raw_mode: Option<Shortcut>,
+    /// Shortcuts for specific keyboards (e.g. external keyboards without a usable Fn key)
+    #[serde(default)]
+    pub device_shortcuts: Vec<DeviceShortcuts>,
+    /// Push-to-talk presses shorter than this (ms) are passed to the system instead of
+    /// recording, so a tap of Fn keeps its normal behavior. 0 = record on press.
+    #[serde(default)]
+    pub hold_threshold_ms: u32,
+    /// Recording starts less than this (ms) after the previous one are ignored, so rapid
+    /// tapping can't interleave starts and stops. 0 = no limit.
+    #[serde(default = "default_min_start_interval_ms")]
+    pub min_start_interval_ms: u32,
+    /// A push-to-talk key pressed again less than this (ms) after its release is key
+    /// bounce, and the recording goes on. 0 = stop on release.
+    #[serde(default = "default_bounce_ms")]
+    pub bounce_ms: u32,
+}
+
+/// Longest accepted [`ShortcutsConfig::hold_threshold_ms`]
+pub const MAX_HOLD_THRESHOLD_MS: u32 = 1000;
+
+impl ShortcutsConfig {
+    /// Push-to-talk and hands-free shortcuts for keys pressed on `device`
+    pub fn for_device(
+        &self,
+        device: Option<&dictara_keyboard::KeyboardDevice>,
+    ) -> (&Shortcut, &Shortcut) {
+        match device.and_then(|device| self.device_shortcuts.iter().find(|s| s.matches(device))) {
+            Some(overrides) => (&overrides.push_to_record, &overrides.hands_free),
+            None => (&self.push_to_record, &self.hands_free),
+        }
+    }
+}
+
+/// Push-to-talk and hands-free shortcuts used instead of the defaults on one keyboard
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceShortcuts {
+    /// USB/Bluetooth vendor id of the keyboard
+    pub vendor_id: u32,
+    /// USB/Bluetooth product id of the keyboard
+    pub product_id: u32,
+    /// Keyboard name shown in settings
+    pub name: String,
+    pub push_to_record: Shortcut,
+    pub hands_free: Shortcut,
+}
+
+impl DeviceShortcuts {
+    /// Whether these shortcuts apply to keys pressed on `device`
+    pub fn matches(&self, device: &dictara_keyboard::KeyboardDevice) -> bool {
+        self.vendor_id == device.vendor_id && self.product_id == device.product_id
+    }
 }
 
 impl Default for ShortcutsConfig {
@@ -140,6 +565,11 @@ impl Default for ShortcutsConfig {
                     },
                 ],
             },
+            raw_mode: None,
+            device_shortcuts: Vec::new(),
+            hold_threshold_ms: 0,
+            min_start_interval_ms: DEFAULT_MIN_START_INTERVAL_MS,
+            bounce_ms: DEFAULT_BOUNCE_MS,
         }
     }
 }
@@ -166,6 +596,11 @@ impl<T> ConfigKey<T> {
     pub fn key_name(&self) -> &'static str {
         self.name
     }
+
+    /// Whether a change notification is about this key
+    pub fn matches(&self, change: &ConfigChanged) -> bool {
+        change.key == self.name
+    }
 }
 
 // ===== App Configuration =====
@@ -184,6 +619,9 @@ pub struct AppConfig {
     /// This prevents re-enabling autostart after user manually disables it
     #[serde(default)]
     pub autostart_initial_setup_done: bool,
+    /// Last known launch-at-login state, synced with the system whenever it is read
+    #[serde(default)]
+    pub autostart_enabled: bool,
     /// Whether to run LLM post-processing after transcription
     #[serde(
         default = "default_post_process_enabled",
@@ -196,12 +634,113 @@ pub struct AppConfig {
     /// Prompt used for transcription post-processing
     #[serde(default = "default_post_process_prompt", alias = "post_process_prompt")]
     pub post_process_prompt: String,
+    /// Prompts by ISO-639-1 language code (e.g. "ja"), used instead of `post_process_prompt`
+    /// when the transcription is in that language
+    #[serde(default)]
+    pub post_process_prompts_by_language: std::collections::BTreeMap<String, String>,
+    /// Timeout and sampling parameters of the post-processing request
+    #[serde(default)]
+    pub post_process_params: PostProcessParams,
     /// Minimum speech duration required before running transcription (milliseconds)
     #[serde(
         default = "default_min_speech_duration_ms",
         alias = "min_speech_duration_ms"
     )]
     pub min_speech_duration_ms: u64,
+    /// Marker added to the temporary clipboard content so clipboard managers ignore it
+    #[serde(default)]
+    pub clipboard_marker: ClipboardMarker,
+    /// Write the recording state to `<app cache>/status.json` for external status bars
+    #[serde(default)]
+    pub status_file_enabled: bool,
+    /// Post the recording state as a macOS distributed notification
+    #[serde(default)]
+    pub status_notification_enabled: bool,
+    /// Publish the recording state over MQTT or a local WebSocket
+    #[serde(default)]
+    pub state_push: StatePushConfig,
+    /// Add the frontmost window title (read via Accessibility) to the post-processing prompt
+    #[serde(default)]
+    pub document_context_enabled: bool,
+    /// Also add the selected text of the frontmost window (requires `document_context_enabled`)
+    #[serde(default)]
+    pub document_context_include_selection: bool,
+    /// Reaction to a default input device change (or unplugged microphone) while recording
+    #[serde(default)]
+    pub input_device_change_policy: InputDeviceChangePolicy,
+    /// Input device capturing system audio (e.g. BlackHole or an aggregate device),
+    /// mixed with the microphone for call transcription. `None` = microphone only.
+    #[serde(default)]
+    pub loopback_device: Option<String>,
+    /// How often the recording file is flushed to disk, so a crash loses at most a few seconds
+    #[serde(default)]
+    pub recording_flush_policy: FlushPolicy,
+    /// Most audio level updates per second sent to the popup (peak of each window),
+    /// 0 = one per audio buffer
+    #[serde(default = "default_audio_level_max_hz")]
+    pub audio_level_max_hz: u32,
+    /// Recordings are stopped and transcribed after this many seconds, 0 = only at the
+    /// 25MB transcription file limit (about 13 minutes)
+    #[serde(default)]
+    pub max_recording_secs: u32,
+    /// Reaction to system sleep or a screen lock while recording
+    #[serde(default)]
+    pub system_sleep_policy: SystemSleepPolicy,
+    /// Event tap used by the keyboard listener (requires a restart)
+    #[serde(default)]
+    pub keyboard_tap: KeyboardTapConfig,
+    /// Providers tried in order when the active provider fails with a retryable error
+    #[serde(default)]
+    pub fallback_providers: Vec<Provider>,
+    /// Local log of outbound API requests (metadata only unless text is included)
+    #[serde(default)]
+    pub audit_log: AuditLogConfig,
+    /// Daily dictated-words target, `None` = no goal
+    #[serde(default)]
+    pub daily_word_goal: Option<u32>,
+    /// Don't paste if the frontmost app changed since the recording started,
+    /// post a notification instead
+    #[serde(default)]
+    pub hold_paste_on_focus_change: bool,
+    /// Bounds of the wait before the clipboard is restored after a paste
+    #[serde(default)]
+    pub paste_timing: PasteTiming,
+    /// Proxy of outbound HTTP requests
+    #[serde(default)]
+    pub proxy: ProxyConfig,
+    /// Custom root CA and pinned certificates of outbound HTTPS requests
+    #[serde(default)]
+    pub tls: TlsConfig,
+    /// Label meeting transcripts with Speaker 1/Speaker 2 (requires the speaker model and
+    /// a build with the `diarization` feature)
+    #[serde(default)]
+    pub meeting_diarization: bool,
+    /// Write spoken numbers, dates and units in written form ("twenty third of march" →
+    /// "March 23") before post-processing, for languages with formatting rules
+    #[serde(default)]
+    pub format_normalization: bool,
+    /// Profanity and sensitive data filter of pasted dictations
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+    /// Spoken formatting cues written as Markdown, per app
+    #[serde(default)]
+    pub markdown_mode: MarkdownModeConfig,
+    /// Model, language, prompt and temperature of the transcription requests, per provider
+    #[serde(default)]
+    pub transcription_options: std::collections::HashMap<Provider, TranscriptionOptions>,
+    /// Threads running model loading, local transcription and checksum hashing,
+    /// 0 = automatic (requires a restart)
+    #[serde(default)]
+    pub worker_threads: u32,
+}
+
+impl AppConfig {
+    /// Post-processing prompt for a transcription in `language` (detected or pinned)
+    pub fn post_process_prompt_for(&self, language: Option<&str>) -> &str {
+        language
+            .and_then(|language| self.post_process_prompts_by_language.get(language))
+            .unwrap_or(&self.post_process_prompt)
+    }
 }
 
 impl Default for AppConfig {
@@ -210,10 +749,39 @@ impl Default for AppConfig {
             active_provider: None,
             recording_trigger: RecordingTrigger::default(),
             autostart_initial_setup_done: false,
+            autostart_enabled: false,
             post_process_enabled: default_post_process_enabled(),
             post_process_model: default_post_process_model(),
             post_process_prompt: default_post_process_prompt(),
+            post_process_prompts_by_language: Default::default(),
+            post_process_params: PostProcessParams::default(),
             min_speech_duration_ms: default_min_speech_duration_ms(),
+            clipboard_marker: ClipboardMarker::default(),
+            status_file_enabled: false,
+            status_notification_enabled: false,
+            state_push: StatePushConfig::default(),
+            document_context_enabled: false,
+            document_context_include_selection: false,
+            input_device_change_policy: InputDeviceChangePolicy::default(),
+            loopback_device: None,
+            recording_flush_policy: FlushPolicy::default(),
+            audio_level_max_hz: default_audio_level_max_hz(),
+            max_recording_secs: 0,
+            system_sleep_policy: SystemSleepPolicy::default(),
+            keyboard_tap: KeyboardTapConfig::default(),
+            fallback_providers: Vec::new(),
+            audit_log: AuditLogConfig::default(),
+            daily_word_goal: None,
+            hold_paste_on_focus_change: false,
+            paste_timing: PasteTiming::default(),
+            proxy: ProxyConfig::default(),
+            tls: TlsConfig::default(),
+            meeting_diarization: false,
+            format_normalization: false,
+            redaction: RedactionConfig::default(),
+            markdown_mode: MarkdownModeConfig::default(),
+            transcription_options: Default::default(),
+            worker_threads: 0,
         }
     }
 }
@@ -274,6 +842,54 @@ impl ConfigKey<OnboardingConfig> {
 pub struct LocalModelConfig {
     /// Name of the selected model (e.g., "whisper-small")
     pub selected_model: Option<String>,
+    /// Transcription options by model name, defaults for models not listed
+    #[serde(default)]
+    pub model_options: std::collections::HashMap<String, LocalModelOptions>,
+    /// Where model files are downloaded from
+    #[serde(default)]
+    pub download_mirror: ModelMirrorConfig,
+    /// Directory of the downloaded models, `None` for the app data directory.
+    /// Changed with `set_models_directory`, which moves the models.
+    #[serde(default)]
+    pub models_dir: Option<String>,
+    /// Deleted models go to the Trash instead of being removed for good
+    #[serde(default)]
+    pub delete_to_trash: bool,
+}
+
+impl LocalModelConfig {
+    /// Options to transcribe with `model_name`
+    pub fn options_for(&self, model_name: &str) -> LocalModelOptions {
+        self.model_options
+            .get(model_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Mirrors of the model downloads, for networks that block huggingface.co.
+/// Downloaded files are still verified against the catalog checksums.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ModelMirrorConfig {
+    /// Replaces `https://huggingface.co` in every model URL (e.g. "https://hf-mirror.com")
+    pub base_url: Option<String>,
+    /// Catalog source (Hugging Face repository, e.g. "ggerganov/whisper.cpp") to the URL
+    /// its files are downloaded from, instead of `<repository>/resolve/main`
+    pub source_urls: std::collections::BTreeMap<String, String>,
+}
+
+impl ModelMirrorConfig {
+    /// Check that the mirror URLs are http(s) URLs
+    pub fn validate(&self) -> Result<(), String> {
+        for url in self.base_url.iter().chain(self.source_urls.values()) {
+            match tauri::Url::parse(url) {
+                Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+                _ => return Err(format!("Invalid mirror URL: {}", url)),
+            }
+        }
+        Ok(())
+    }
 }
 
 impl ConfigKey<LocalModelConfig> {
@@ -308,6 +924,416 @@ impl ConfigKey<ShortcutsConfig> {
     pub const SHORTCUTS: Self = Self::new("shortcutsConfig");
 }
 
+// ===== Globe Key =====
+
+/// The user's Globe key setting, stored while Dictara has it changed (see `crash_guard`)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobeKeyOriginal {
+    /// `AppleFnUsageType` before the change, `None` when it wasn't set
+    pub behavior: Option<i32>,
+}
+
+impl ConfigKey<GlobeKeyOriginal> {
+    pub const GLOBE_KEY_ORIGINAL: Self = Self::new("globeKeyOriginal");
+}
+
+// ===== Language Statistics =====
+
+/// Number of transcriptions per detected language (stored locally)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageStats {
+    /// Transcription count keyed by ISO-639-1 language code (e.g. "en")
+    pub counts: std::collections::HashMap<String, u32>,
+}
+
+impl ConfigKey<LanguageStats> {
+    pub const LANGUAGE_STATS: Self = Self::new("languageStats");
+}
+
+/// Transcription language last used in each app (stored locally)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLanguages {
+    /// ISO-639-1 language code keyed by app bundle identifier (e.g. "com.tinyspeck.slackmacgap")
+    pub languages: std::collections::BTreeMap<String, String>,
+}
+
+impl ConfigKey<AppLanguages> {
+    pub const APP_LANGUAGES: Self = Self::new("appLanguages");
+}
+
+// ===== UI Preferences =====
+
+/// Size of the recording popup
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum PopupStyle {
+    /// Only the recording indicator
+    Compact,
+    /// Indicator and controls
+    #[default]
+    Full,
+}
+
+/// What the recording popup shows while recording
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum PopupContent {
+    /// Live audio level waveform
+    #[default]
+    Waveform,
+    /// Status text only
+    TextOnly,
+}
+
+/// Appearance of the recording popup and the other windows (stored locally)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UiPrefs {
+    pub popup_style: PopupStyle,
+    pub popup_content: PopupContent,
+    /// Popup opacity, from 0.2 to 1.0
+    pub popup_opacity: f32,
+    /// Follow the system light/dark appearance, light theme otherwise
+    pub follow_system_theme: bool,
+}
+
+pub const MIN_POPUP_OPACITY: f32 = 0.2;
+
+impl Default for UiPrefs {
+    fn default() -> Self {
+        Self {
+            popup_style: PopupStyle::default(),
+            popup_content: PopupContent::default(),
+            popup_opacity: 1.0,
+            follow_system_theme: true,
+        }
+    }
+}
+
+impl ConfigKey<UiPrefs> {
+    pub const UI_PREFS: Self = Self::new("uiPrefs");
+}
+
+// ===== Usage Statistics =====
+
+/// Dictated words per day and transcription times (stored locally)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageStats {
+    /// Word count keyed by local date ("2025-03-07")
+    pub words_by_day: std::collections::BTreeMap<String, u32>,
+    /// Last day the daily word goal was reached (notified once per day)
+    pub goal_reached_on: Option<String>,
+    /// Transcription time model by engine ("local/whisper-small", "open_ai"), for the
+    /// estimate shown while transcribing
+    #[serde(default)]
+    pub transcription_speed: std::collections::BTreeMap<String, TranscriptionSpeed>,
+}
+
+impl ConfigKey<UsageStats> {
+    pub const USAGE_STATS: Self = Self::new("usageStats");
+}
+
+// ===== Learned Corrections =====
+
+/// A word or short phrase the user corrected in dictated text
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LearnedCorrection {
+    /// As recognized
+    pub original: String,
+    /// As corrected by the user
+    pub corrected: String,
+    /// How often the user made this correction
+    pub count: u32,
+}
+
+/// Corrections of dictated text (stored locally), most recent first
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LearnedCorrections {
+    pub corrections: Vec<LearnedCorrection>,
+}
+
+impl ConfigKey<LearnedCorrections> {
+    pub const LEARNED_CORRECTIONS: Self = Self::new("learnedCorrections");
+}
+
+// ===== Dictation Templates =====
+
+/// User-defined snippet the dictated text is inserted into
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DictationTemplate {
+    /// Stable identifier (UUID)
+    pub id: String,
+    /// Display name (tray menu, settings)
+    pub name: String,
+    /// Spoken keyword that selects the template when it starts the dictation
+    /// (e.g. "standup update"), `None` = tray menu selection only
+    pub keyword: Option<String>,
+    /// Template text with placeholders: `{summary}` (the dictated text), `{date}`, `{time}`
+    pub body: String,
+}
+
+/// Dictation templates (stored locally)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplatesConfig {
+    pub templates: Vec<DictationTemplate>,
+    /// Template applied to every dictation (selected in the tray menu), `None` = off
+    pub active_template_id: Option<String>,
+}
+
+impl ConfigKey<TemplatesConfig> {
+    pub const TEMPLATES: Self = Self::new("templatesConfig");
+}
+
+// ===== Voice Snippets =====
+
+/// Stored text inserted wherever its trigger phrase is spoken within a dictation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct VoiceSnippet {
+    /// Stable identifier (UUID)
+    pub id: String,
+    /// Spoken phrase replaced by the snippet (e.g. "insert my address")
+    pub trigger: String,
+    /// Text inserted in place of the trigger phrase
+    pub text: String,
+}
+
+/// Voice snippets (stored locally)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetsConfig {
+    pub snippets: Vec<VoiceSnippet>,
+}
+
+impl ConfigKey<SnippetsConfig> {
+    pub const SNIPPETS: Self = Self::new("snippetsConfig");
+}
+
+// ===== Configuration Profiles =====
+
+/// Named set of settings switched as a whole (e.g. "Work" and "Personal"), see `profiles`.
+/// Fields missing from a profile saved by an older version take their default.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ConfigProfile {
+    pub name: String,
+    pub active_provider: Option<Provider>,
+    pub fallback_providers: Vec<Provider>,
+    pub transcription_options: std::collections::HashMap<Provider, TranscriptionOptions>,
+    pub post_process_enabled: bool,
+    pub post_process_model: String,
+    pub post_process_prompt: String,
+    pub post_process_prompts_by_language: std::collections::BTreeMap<String, String>,
+    pub post_process_params: PostProcessParams,
+    pub format_normalization: bool,
+    pub redaction: RedactionConfig,
+    pub markdown_mode: MarkdownModeConfig,
+    pub local_model: ProfileLocalModel,
+    pub shortcuts: ShortcutsConfig,
+    /// Vocabulary: learned corrections and voice snippets
+    pub corrections: LearnedCorrections,
+    pub snippets: SnippetsConfig,
+}
+
+impl Default for ConfigProfile {
+    fn default() -> Self {
+        let app = AppConfig::default();
+        Self {
+            name: String::new(),
+            active_provider: app.active_provider,
+            fallback_providers: app.fallback_providers,
+            transcription_options: app.transcription_options,
+            post_process_enabled: app.post_process_enabled,
+            post_process_model: app.post_process_model,
+            post_process_prompt: app.post_process_prompt,
+            post_process_prompts_by_language: app.post_process_prompts_by_language,
+            post_process_params: app.post_process_params,
+            format_normalization: app.format_normalization,
+            redaction: app.redaction,
+            markdown_mode: app.markdown_mode,
+            local_model: ProfileLocalModel::default(),
+            shortcuts: ShortcutsConfig::default(),
+            corrections: LearnedCorrections::default(),
+            snippets: SnippetsConfig::default(),
+        }
+    }
+}
+
+/// Local model settings of a profile. The device-level ones (models directory,
+/// download mirror, Trash) stay with the machine.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProfileLocalModel {
+    pub selected_model: Option<String>,
+    pub model_options: std::collections::HashMap<String, LocalModelOptions>,
+}
+
+/// Saved configuration profiles (stored locally)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfilesConfig {
+    /// Profile the current settings belong to, `None` until a profile is switched to
+    pub active: Option<String>,
+    pub profiles: Vec<ConfigProfile>,
+}
+
+impl ConfigKey<ProfilesConfig> {
+    pub const PROFILES: Self = Self::new("profilesConfig");
+}
+
+// ===== Automation Rules =====
+
+/// Day of the week of an automation rule
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+/// Settings an automation rule applies when it starts, `None` fields are left alone
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ConfigOverlay {
+    /// Configuration profile to switch to, applied before the other fields
+    pub profile: Option<String>,
+    pub active_provider: Option<Provider>,
+    /// Local model to select
+    pub local_model: Option<String>,
+    pub post_process_enabled: Option<bool>,
+}
+
+/// Settings applied at a time of day, e.g. Azure on weekdays from 09:00 to 17:00
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationRule {
+    /// Stable identifier (UUID)
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    /// Days the rule starts on, empty = every day
+    pub days: Vec<Weekday>,
+    /// Local time the rule starts, "HH:MM"
+    pub start: String,
+    /// Local time the rule ends, "HH:MM". Before `start`, the rule runs past midnight.
+    pub end: String,
+    pub overlay: ConfigOverlay,
+}
+
+/// Time-of-day automation rules (stored locally), see `automation`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationConfig {
+    pub rules: Vec<AutomationRule>,
+}
+
+impl ConfigKey<AutomationConfig> {
+    pub const AUTOMATION: Self = Self::new("automationConfig");
+}
+
+// ===== Output Sinks =====
+
+/// Entry format of the journal when none is set
+pub const DEFAULT_JOURNAL_ENTRY_TEMPLATE: &str = "- **{time}** ({app}): {text}";
+
+/// Daily Markdown journal: every dictation appended to `<folder>/YYYY-MM-DD.md`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct JournalSinkConfig {
+    pub enabled: bool,
+    /// Folder of the daily files, e.g. an Obsidian vault, `None` until chosen
+    pub folder: Option<String>,
+    /// Format of an entry with placeholders: `{text}`, `{date}` (YYYY-MM-DD),
+    /// `{time}` (HH:MM), `{app}`, `{language}`
+    pub entry_template: String,
+}
+
+impl Default for JournalSinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            folder: None,
+            entry_template: DEFAULT_JOURNAL_ENTRY_TEMPLATE.to_string(),
+        }
+    }
+}
+
+/// Every dictation POSTed as JSON to a URL, signed with the secret stored in the
+/// keychain (`WebhookCredentials`) if there is one
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WebhookSinkConfig {
+    pub enabled: bool,
+    /// http(s) URL the dictations are sent to, `None` until set
+    pub url: Option<String>,
+    /// Show a notification when a dictation couldn't be delivered after the retries
+    pub notify_on_failure: bool,
+}
+
+impl Default for WebhookSinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: None,
+            notify_on_failure: true,
+        }
+    }
+}
+
+/// Where dictations go besides being pasted (stored locally), see `sinks`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OutputSinksConfig {
+    pub journal: JournalSinkConfig,
+    pub webhook: WebhookSinkConfig,
+}
+
+impl OutputSinksConfig {
+    /// Reject enabled sinks that can't deliver
+    pub fn validate(&self) -> Result<(), String> {
+        let journal = &self.journal;
+        if journal.enabled {
+            if journal
+                .folder
+                .as_deref()
+                .is_none_or(|folder| folder.trim().is_empty())
+            {
+                return Err("Choose a folder for the journal".to_string());
+            }
+            if !journal.entry_template.contains("{text}") {
+                return Err("The journal entry format must contain {text}".to_string());
+            }
+        }
+
+        match &self.webhook.url {
+            Some(url) => match tauri::Url::parse(url) {
+                Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+                _ => return Err(format!("Invalid webhook URL: {}", url)),
+            },
+            None if self.webhook.enabled => return Err("Enter the webhook URL".to_string()),
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+impl ConfigKey<OutputSinksConfig> {
+    pub const OUTPUT_SINKS: Self = Self::new("outputSinksConfig");
+}
+
 // ===== Keychain-stored Configurations (no keys) =====
 
 /// OpenAI provider configuration (stored in keychain)
@@ -342,49 +1368,284 @@ impl std::fmt::Debug for AzureOpenAIConfig {
     }
 }
 
+/// Password of the proxy user (stored in keychain)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyCredentials {
+    pub password: String,
+}
+
+impl std::fmt::Debug for ProxyCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProxyCredentials")
+            .field("password", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Access token of Hugging Face (or a private mirror) for model downloads (stored in keychain)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HuggingFaceCredentials {
+    pub token: String,
+}
+
+impl std::fmt::Debug for HuggingFaceCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HuggingFaceCredentials")
+            .field("token", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Secret the webhook requests are signed with (stored in keychain)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookCredentials {
+    pub secret: String,
+}
+
+impl std::fmt::Debug for WebhookCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookCredentials")
+            .field("secret", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Password of the MQTT broker user (stored in keychain)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MqttCredentials {
+    pub password: String,
+}
+
+impl std::fmt::Debug for MqttCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MqttCredentials")
+            .field("password", &"[REDACTED]")
+            .finish()
+    }
+}
+
 // ===== Type-Safe Config Store =====
 
 pub trait ConfigStore {
     fn get<T: DeserializeOwned>(&self, key: &ConfigKey<T>) -> Option<T>;
     fn set<T: Serialize>(&self, key: &ConfigKey<T>, value: T) -> Result<(), String>;
     fn delete<T>(&self, key: &ConfigKey<T>) -> Result<(), String>;
+
+    /// `value` with the fields of `key` managed by IT applied, to create a missing entry
+    /// that [`ConfigStore::set`] accepts. Unchanged for stores without managed values.
+    fn with_managed<T: Serialize + DeserializeOwned>(&self, _key: &ConfigKey<T>, value: T) -> T {
+        value
+    }
+}
+
+/// A config entry was saved or deleted. Sent to subscribers of [`ConfigChangeBus`]
+/// and to the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChanged {
+    /// Store key of the entry (e.g. "appConfig", "shortcutsConfig")
+    pub key: String,
+    pub deleted: bool,
+}
+
+type ConfigSubscriber = std::sync::Arc<dyn Fn(&ConfigChanged) + Send + Sync>;
+
+/// Notifies subsystems of config changes so they can reload settings without a restart
+#[derive(Clone, Default)]
+pub struct ConfigChangeBus {
+    subscribers: std::sync::Arc<std::sync::Mutex<Vec<ConfigSubscriber>>>,
+}
+
+impl ConfigChangeBus {
+    /// Call `subscriber` after every successful change, on the thread that made it
+    pub fn subscribe(&self, subscriber: impl Fn(&ConfigChanged) + Send + Sync + 'static) {
+        self.subscribers
+            .lock()
+            .unwrap()
+            .push(std::sync::Arc::new(subscriber));
+    }
+
+    pub fn notify(&self, change: ConfigChanged) {
+        // Subscribers run without the lock held, so they may read or write the config
+        let subscribers = self.subscribers.lock().unwrap().clone();
+        for subscriber in subscribers {
+            subscriber(&change);
+        }
+    }
 }
 
 /// Type-safe configuration store that wraps the Tauri plugin store
+///
+/// Values managed by IT ([`ManagedConfig`]) are merged over the stored ones and
+/// can't be changed.
 #[derive(Clone)]
 pub struct Config {
     store: std::sync::Arc<tauri_plugin_store::Store<tauri::Wry>>,
+    managed: std::sync::Arc<ManagedConfig>,
+    changes: ConfigChangeBus,
 }
 
 impl Config {
-    pub fn new(store: std::sync::Arc<tauri_plugin_store::Store<tauri::Wry>>) -> Self {
-        Self { store }
+    pub fn new(
+        store: std::sync::Arc<tauri_plugin_store::Store<tauri::Wry>>,
+        managed: ManagedConfig,
+    ) -> Self {
+        Self {
+            store,
+            managed: std::sync::Arc::new(managed),
+            changes: ConfigChangeBus::default(),
+        }
+    }
+
+    /// Configuration deployed by IT
+    pub fn managed(&self) -> &ManagedConfig {
+        &self.managed
+    }
+
+    /// Change notifications of this store
+    pub fn changes(&self) -> &ConfigChangeBus {
+        &self.changes
+    }
+
+    /// Write the store to disk (writes through [`ConfigStore::set`] are saved already)
+    pub fn flush(&self) -> Result<(), String> {
+        self.store.save().map_err(|e| e.to_string())
     }
 }
 
 impl ConfigStore for Config {
     fn get<T: DeserializeOwned>(&self, key: &ConfigKey<T>) -> Option<T> {
-        self.store
-            .get(key.key_name())
+        self.managed
+            .apply(key.key_name(), self.store.get(key.key_name()))
             .and_then(|v| serde_json::from_value(v).ok())
     }
 
     fn set<T: Serialize>(&self, key: &ConfigKey<T>, value: T) -> Result<(), String> {
-        let val = serde_json::to_value(value).map_err(|e| e.to_string())?;
+        let mut val = serde_json::to_value(value).map_err(|e| e.to_string())?;
+        self.managed.check_unchanged(key.key_name(), &val)?;
+        // `val` was read with the managed fields merged in: store the user's own values
+        let stored = self.store.get(key.key_name());
+        self.managed
+            .restore_user_values(key.key_name(), &mut val, stored.as_ref());
         self.store.set(key.key_name(), val);
         self.store.save().map_err(|e| e.to_string())?;
+        self.changes.notify(ConfigChanged {
+            key: key.key_name().to_string(),
+            deleted: false,
+        });
         Ok(())
     }
 
     fn delete<T>(&self, key: &ConfigKey<T>) -> Result<(), String> {
         self.store.delete(key.key_name());
         self.store.save().map_err(|e| e.to_string())?;
+        self.changes.notify(ConfigChanged {
+            key: key.key_name().to_string(),
+            deleted: true,
+        });
         Ok(())
     }
+
+    fn with_managed<T: Serialize + DeserializeOwned>(&self, key: &ConfigKey<T>, value: T) -> T {
+        self.managed.apply_over(key.key_name(), value)
+    }
+}
+
+// ===== Schema Versioning =====
+
+/// Schema version written by this build. To change the stored config, add a
+/// migration to [`migrations`] and bump this, instead of adding serde aliases.
+pub const CONFIG_VERSION: u32 = 2;
+
+impl ConfigKey<u32> {
+    /// Schema version of the stored config (missing = 0, before versioning)
+    pub const VERSION: Self = Self::new("configVersion");
+}
+
+/// One step of the config schema, upgrading the store to `version`
+struct Migration<S> {
+    version: u32,
+    description: &'static str,
+    run: fn(&S) -> Result<(), String>,
+}
+
+/// All migrations, in version order
+fn migrations<S: ConfigStore>() -> Vec<Migration<S>> {
+    vec![
+        Migration {
+            version: 1,
+            description: "recording trigger to shortcuts",
+            run: migrate_trigger_to_shortcuts,
+        },
+        Migration {
+            version: 2,
+            description: "rewrite snake_case entries in camelCase",
+            run: rewrite_legacy_entries,
+        },
+    ]
+}
+
+/// Run the migrations newer than the stored schema version, in order, and return
+/// the resulting version. `backup` is called with the stored version before the
+/// first migration runs, so the previous config can be restored by hand.
+pub fn run_migrations<S: ConfigStore>(
+    store: &S,
+    backup: impl FnOnce(u32) -> Result<(), String>,
+) -> Result<u32, String> {
+    let stored_version = store.get(&ConfigKey::VERSION).unwrap_or(0);
+    if stored_version > CONFIG_VERSION {
+        log::warn!(
+            "Config version {} is newer than this build ({}), skipping migrations",
+            stored_version,
+            CONFIG_VERSION
+        );
+        return Ok(stored_version);
+    }
+
+    let pending: Vec<_> = migrations::<S>()
+        .into_iter()
+        .filter(|migration| migration.version > stored_version)
+        .collect();
+    if pending.is_empty() {
+        return Ok(stored_version);
+    }
+
+    backup(stored_version)?;
+    for migration in pending {
+        log::info!(
+            "Migrating config to version {}: {}",
+            migration.version,
+            migration.description
+        );
+        (migration.run)(store).map_err(|e| {
+            format!(
+                "Config migration {} ({}) failed: {}",
+                migration.version, migration.description, e
+            )
+        })?;
+        store.set(&ConfigKey::VERSION, migration.version)?;
+    }
+    Ok(CONFIG_VERSION)
 }
 
-/// Migrate from RecordingTrigger to ShortcutsConfig (run once on startup)
-pub fn migrate_trigger_to_shortcuts(store: &impl ConfigStore) -> Result<(), String> {
+/// Re-save entries written with snake_case keys by old versions, so only the
+/// camelCase form remains on disk (the existing aliases read the old form)
+fn rewrite_legacy_entries<S: ConfigStore>(store: &S) -> Result<(), String> {
+    if let Some(app_config) = store.get(&ConfigKey::APP) {
+        store.set(&ConfigKey::APP, app_config)?;
+    }
+    if let Some(onboarding_config) = store.get(&ConfigKey::ONBOARDING) {
+        store.set(&ConfigKey::ONBOARDING, onboarding_config)?;
+    }
+    Ok(())
+}
+
+/// Migrate from RecordingTrigger to ShortcutsConfig (config version 1)
+fn migrate_trigger_to_shortcuts<S: ConfigStore>(store: &S) -> Result<(), String> {
     // Skip if already migrated
     if store
         .get(&ConfigKey::<ShortcutsConfig>::SHORTCUTS)
@@ -418,25 +1679,30 @@ pub fn migrate_trigger_to_shortcuts(store: &impl ConfigStore) -> Result<(), Stri
                 },
             ],
         },
+        raw_mode: None,
+        device_shortcuts: Vec::new(),
+        hold_threshold_ms: 0,
+        min_start_interval_ms: DEFAULT_MIN_START_INTERVAL_MS,
+        bounce_ms: DEFAULT_BOUNCE_MS,
     };
 
     store.set(&ConfigKey::<ShortcutsConfig>::SHORTCUTS, shortcuts)?;
     Ok(())
 }
 
+/// In-memory store for the tests of modules working on a [`ConfigStore`]
 #[cfg(test)]
-mod tests {
+pub(crate) mod mock {
     use super::*;
     use std::cell::RefCell;
     use std::collections::HashMap;
 
-    // Simple in-memory mock store for testing
-    struct MockConfigStore {
+    pub(crate) struct MockConfigStore {
         data: RefCell<HashMap<String, serde_json::Value>>,
     }
 
     impl MockConfigStore {
-        fn new() -> Self {
+        pub(crate) fn new() -> Self {
             Self {
                 data: RefCell::new(HashMap::new()),
             }
@@ -464,6 +1730,13 @@ mod tests {
             Ok(())
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockConfigStore;
+    use super::*;
+    use std::collections::HashMap;
 
     #[test]
     fn test_app_config_store() {
@@ -474,10 +1747,90 @@ mod tests {
                 active_provider: Some(Provider::OpenAI),
                 recording_trigger: RecordingTrigger::Control,
                 autostart_initial_setup_done: false,
+                autostart_enabled: true,
                 post_process_enabled: true,
                 post_process_model: DEFAULT_POST_PROCESS_MODEL.to_string(),
                 post_process_prompt: DEFAULT_POST_PROCESS_PROMPT.to_string(),
+                post_process_prompts_by_language: [(
+                    "ja".to_string(),
+                    "日本語の句読点（、。）を使ってください。".to_string(),
+                )]
+                .into(),
+                post_process_params: PostProcessParams {
+                    timeout_secs: 20,
+                    temperature: Some(0.2),
+                    max_output_tokens: Some(1024),
+                    guard_output: false,
+                },
                 min_speech_duration_ms: DEFAULT_MIN_SPEECH_DURATION_MS,
+                clipboard_marker: ClipboardMarker::Concealed,
+                status_file_enabled: true,
+                status_notification_enabled: false,
+                state_push: StatePushConfig::default(),
+                document_context_enabled: true,
+                document_context_include_selection: true,
+                input_device_change_policy: InputDeviceChangePolicy::Notify,
+                loopback_device: Some("BlackHole 2ch".to_string()),
+                recording_flush_policy: FlushPolicy::PeriodicSync,
+                audio_level_max_hz: 15,
+                max_recording_secs: 300,
+                system_sleep_policy: SystemSleepPolicy::Cancel,
+                keyboard_tap: KeyboardTapConfig {
+                    location: KeyboardTapLocation::Session,
+                    listen_only: true,
+                },
+                fallback_providers: vec![Provider::AzureOpenAI, Provider::Local],
+                audit_log: AuditLogConfig {
+                    enabled: true,
+                    include_text: false,
+                },
+                daily_word_goal: Some(500),
+                hold_paste_on_focus_change: true,
+                paste_timing: PasteTiming {
+                    min_restore_delay_ms: 200,
+                    max_restore_delay_ms: 3000,
+                },
+                proxy: ProxyConfig {
+                    mode: ProxyMode::Manual,
+                    host: "proxy.corp.example".to_string(),
+                    port: 8080,
+                    username: Some("jdoe".to_string()),
+                },
+                tls: TlsConfig {
+                    custom_ca_path: Some("/Library/Corp/root-ca.pem".to_string()),
+                    pinned_certificates: std::collections::BTreeMap::from([(
+                        "api.openai.com".to_string(),
+                        "/Library/Corp/openai-roots.pem".to_string(),
+                    )]),
+                },
+                meeting_diarization: true,
+                format_normalization: true,
+                redaction: RedactionConfig {
+                    enabled: true,
+                    strictness: RedactionStrictness::Strict,
+                    style: RedactionStyle::Remove,
+                    redact_card_numbers: true,
+                    redact_emails: true,
+                    blocked_words: vec!["frak".to_string()],
+                    allowed_words: vec!["Dick".to_string()],
+                },
+                markdown_mode: MarkdownModeConfig {
+                    enabled: false,
+                    app_overrides: std::collections::BTreeMap::from([(
+                        "md.obsidian".to_string(),
+                        true,
+                    )]),
+                },
+                transcription_options: std::collections::HashMap::from([(
+                    Provider::OpenAI,
+                    TranscriptionOptions {
+                        model: Some("gpt-4o-transcribe".to_string()),
+                        language: None,
+                        prompt: Some("Dictara, Tauri".to_string()),
+                        temperature: 0.2,
+                    },
+                )]),
+                worker_threads: 3,
             },
         )];
 
@@ -534,6 +1887,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_templates_config_store() {
+        let test_cases = vec![
+            (
+                "TemplatesConfig with active template",
+                ConfigKey::TEMPLATES,
+                TemplatesConfig {
+                    templates: vec![DictationTemplate {
+                        id: "standup".to_string(),
+                        name: "Standup".to_string(),
+                        keyword: Some("standup update".to_string()),
+                        body: "Standup {date}\n{summary}".to_string(),
+                    }],
+                    active_template_id: Some("standup".to_string()),
+                },
+            ),
+            (
+                "TemplatesConfig empty",
+                ConfigKey::TEMPLATES,
+                TemplatesConfig::default(),
+            ),
+        ];
+
+        for (description, key, config) in test_cases {
+            let store = MockConfigStore::new();
+            test_config_lifecycle(&store, &key, config, description);
+        }
+    }
+
     #[test]
     fn test_local_model_config_store() {
         let test_cases = vec![
@@ -542,6 +1924,23 @@ mod tests {
                 ConfigKey::LOCAL_MODEL,
                 LocalModelConfig {
                     selected_model: Some("whisper-small".to_string()),
+                    model_options: HashMap::from([(
+                        "parakeet-tdt-0.6b-v3-int8".to_string(),
+                        LocalModelOptions {
+                            language: Some("de".to_string()),
+                            timestamps: false,
+                            beam_size: None,
+                        },
+                    )]),
+                    download_mirror: ModelMirrorConfig {
+                        base_url: Some("https://hf-mirror.com".to_string()),
+                        source_urls: std::collections::BTreeMap::from([(
+                            "ggerganov/whisper.cpp".to_string(),
+                            "https://artifacts.corp.example/whisper".to_string(),
+                        )]),
+                    },
+                    models_dir: None,
+                    delete_to_trash: false,
                 },
             ),
             (
@@ -549,6 +1948,10 @@ mod tests {
                 ConfigKey::LOCAL_MODEL,
                 LocalModelConfig {
                     selected_model: None,
+                    model_options: HashMap::new(),
+                    download_mirror: ModelMirrorConfig::default(),
+                    models_dir: None,
+                    delete_to_trash: false,
                 },
             ),
             (
@@ -556,6 +1959,10 @@ mod tests {
                 ConfigKey::LOCAL_MODEL,
                 LocalModelConfig {
                     selected_model: Some("whisper-large-v3".to_string()),
+                    model_options: HashMap::new(),
+                    download_mirror: ModelMirrorConfig::default(),
+                    models_dir: Some("/Volumes/External/Dictara Models".to_string()),
+                    delete_to_trash: true,
                 },
             ),
         ];
@@ -566,6 +1973,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_shortcuts_for_device() {
+        let right_command = dictara_keyboard::Key::MetaRight;
+        let external_shortcut = Shortcut {
+            keys: vec![ShortcutKey {
+                keycode: right_command.to_macos_keycode(),
+                label: right_command.to_label(),
+            }],
+        };
+        let config = ShortcutsConfig {
+            device_shortcuts: vec![DeviceShortcuts {
+                vendor_id: 0x046d,
+                product_id: 0xc52b,
+                name: "External Keyboard".to_string(),
+                push_to_record: external_shortcut.clone(),
+                hands_free: external_shortcut.clone(),
+            }],
+            ..ShortcutsConfig::default()
+        };
+        let device = |vendor_id, product_id| dictara_keyboard::KeyboardDevice {
+            vendor_id,
+            product_id,
+            name: None,
+            built_in: false,
+        };
+
+        let (push_to_record, _) = config.for_device(Some(&device(0x046d, 0xc52b)));
+        assert_eq!(push_to_record, &external_shortcut);
+
+        let (push_to_record, hands_free) = config.for_device(Some(&device(0x05ac, 0x0342)));
+        assert_eq!(push_to_record, &config.push_to_record);
+        assert_eq!(hands_free, &config.hands_free);
+
+        let (push_to_record, _) = config.for_device(None);
+        assert_eq!(push_to_record, &config.push_to_record);
+    }
+
     // Helper function to check if a string is in camelCase format
     fn is_camel_case(s: &str) -> bool {
         if s.is_empty() {
@@ -616,6 +2060,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_post_process_params_validation() {
+        assert!(PostProcessParams::default().validate().is_ok());
+
+        let invalid = [
+            PostProcessParams {
+                timeout_secs: 0,
+                ..PostProcessParams::default()
+            },
+            PostProcessParams {
+                temperature: Some(2.5),
+                ..PostProcessParams::default()
+            },
+            PostProcessParams {
+                max_output_tokens: Some(8),
+                ..PostProcessParams::default()
+            },
+        ];
+        for params in invalid {
+            assert!(params.validate().is_err(), "{:?} should be invalid", params);
+        }
+    }
+
+    #[test]
+    fn test_state_push_validation() {
+        assert!(StatePushConfig::default().validate().is_ok());
+
+        let mqtt = |host: &str, topic: &str| StatePushConfig {
+            mqtt: MqttPushConfig {
+                enabled: true,
+                host: host.to_string(),
+                topic: topic.to_string(),
+                ..MqttPushConfig::default()
+            },
+            ..StatePushConfig::default()
+        };
+        assert!(mqtt("homeassistant.local", "dictara/state")
+            .validate()
+            .is_ok());
+        assert!(mqtt(" ", "dictara/state").validate().is_err());
+        assert!(mqtt("homeassistant.local", "dictara/#").validate().is_err());
+
+        let websocket = StatePushConfig {
+            websocket: WebSocketPushConfig {
+                enabled: true,
+                port: 80,
+            },
+            ..StatePushConfig::default()
+        };
+        assert!(websocket.validate().is_err());
+    }
+
     // Helper function to test the full lifecycle of a config
     fn test_config_lifecycle<T>(
         store: &MockConfigStore,
@@ -668,4 +2164,70 @@ mod tests {
             description
         );
     }
+
+    #[test]
+    fn test_config_change_bus() {
+        let bus = ConfigChangeBus::default();
+        let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let sink = received.clone();
+        bus.subscribe(move |change| {
+            if ConfigKey::<ShortcutsConfig>::SHORTCUTS.matches(change) {
+                sink.lock().unwrap().push(change.deleted);
+            }
+        });
+
+        // A subscriber may subscribe or notify again without deadlocking
+        let nested = bus.clone();
+        bus.subscribe(move |_| nested.subscribe(|_| {}));
+
+        bus.notify(ConfigChanged {
+            key: ConfigKey::<AppConfig>::APP.key_name().to_string(),
+            deleted: false,
+        });
+        bus.notify(ConfigChanged {
+            key: ConfigKey::<ShortcutsConfig>::SHORTCUTS
+                .key_name()
+                .to_string(),
+            deleted: true,
+        });
+
+        assert_eq!(*received.lock().unwrap(), vec![true]);
+    }
+
+    #[test]
+    fn test_run_migrations() {
+        let store = MockConfigStore::new();
+        store.data.borrow_mut().insert(
+            "appConfig".to_string(),
+            serde_json::json!({ "recording_trigger": "control", "active_provider": "open_ai" }),
+        );
+
+        let mut backups = Vec::new();
+        let version = run_migrations(&store, |version| {
+            backups.push(version);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(version, CONFIG_VERSION);
+        assert_eq!(backups, vec![0]);
+        assert_eq!(store.get(&ConfigKey::VERSION), Some(CONFIG_VERSION));
+
+        // v1: the legacy trigger became the push-to-record shortcut
+        let shortcuts = store.get(&ConfigKey::SHORTCUTS).unwrap();
+        let control = dictara_keyboard::Key::ControlLeft.to_macos_keycode();
+        assert_eq!(shortcuts.push_to_record.keys[0].keycode, control);
+
+        // v2: entries are stored in camelCase only
+        verify_camel_case(&store, &ConfigKey::APP);
+        assert_eq!(
+            store.get(&ConfigKey::APP).unwrap().active_provider,
+            Some(Provider::OpenAI)
+        );
+
+        // Up to date: nothing runs, no backup
+        let version = run_migrations(&store, |_| panic!("unexpected backup")).unwrap();
+        assert_eq!(version, CONFIG_VERSION);
+    }
 }
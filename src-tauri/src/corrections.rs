@@ -0,0 +1,194 @@
+//! Learning from corrections of dictated text.
+//!
+//! When the user submits an edited version of the last dictation shortly after it was
+//! pasted, the replaced words are recorded locally as original→corrected pairs. Pairs
+//! corrected repeatedly are added to the post-processing prompt, so the model fixes
+//! those misrecognitions on its own.
+
+use std::time::Duration;
+
+use log::info;
+
+use crate::config::{ConfigKey, ConfigStore, LearnedCorrection};
+use crate::recording::{diff_words, DiffKind};
+
+/// Edits submitted later than this after the dictation are not learned from
+pub const CORRECTION_WINDOW: Duration = Duration::from_secs(120);
+
+/// Corrections made this often are added to the post-processing prompt
+const MIN_OCCURRENCES: u32 = 2;
+
+/// Longer replacements are rewrites, not misrecognized terms
+const MAX_CORRECTION_WORDS: usize = 4;
+
+/// Most corrections kept, the least recent are dropped first
+const MAX_CORRECTIONS: usize = 200;
+
+/// Most corrections added to the prompt, most frequent first
+const MAX_PROMPT_CORRECTIONS: usize = 50;
+
+/// Replaced words of `corrected` compared to `original`, as (original, corrected) pairs
+pub fn correction_pairs(original: &str, corrected: &str) -> Vec<(String, String)> {
+    let diff = diff_words(original, corrected);
+    let mut pairs = Vec::new();
+    let mut removed: Option<&str> = None;
+
+    for segment in &diff.segments {
+        match segment.kind {
+            DiffKind::Removed => removed = Some(segment.text.as_str()),
+            DiffKind::Added => {
+                // Insertions without a removed counterpart aren't corrections
+                if let Some(removed) = removed.take() {
+                    if let Some(pair) = correction_pair(removed, &segment.text) {
+                        pairs.push(pair);
+                    }
+                }
+            }
+            DiffKind::Unchanged => removed = None,
+        }
+    }
+    pairs
+}
+
+fn correction_pair(original: &str, corrected: &str) -> Option<(String, String)> {
+    let trim = |text: &str| {
+        text.trim_matches(|c: char| c.is_ascii_punctuation())
+            .to_string()
+    };
+    let (original, corrected) = (trim(original), trim(corrected));
+    let too_long = |text: &str| text.split_whitespace().count() > MAX_CORRECTION_WORDS;
+
+    let is_correction = !original.is_empty()
+        && !corrected.is_empty()
+        && original != corrected
+        && !too_long(&original)
+        && !too_long(&corrected);
+    is_correction.then_some((original, corrected))
+}
+
+/// Record the corrections between `original` and `corrected` dictated text. Returns the
+/// corrections that reached [`MIN_OCCURRENCES`] with this edit.
+pub fn record_correction(
+    store: &impl ConfigStore,
+    original: &str,
+    corrected: &str,
+) -> Result<Vec<LearnedCorrection>, String> {
+    let pairs = correction_pairs(original, corrected);
+    if pairs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut learned = store
+        .get(&ConfigKey::LEARNED_CORRECTIONS)
+        .unwrap_or_default();
+    let mut newly_learned = Vec::new();
+    for (original, corrected) in pairs {
+        let position = learned
+            .corrections
+            .iter()
+            .position(|c| c.original == original && c.corrected == corrected);
+        let mut correction = match position {
+            Some(index) => learned.corrections.remove(index),
+            None => LearnedCorrection {
+                original,
+                corrected,
+                count: 0,
+            },
+        };
+        correction.count = correction.count.saturating_add(1);
+        if correction.count == MIN_OCCURRENCES {
+            info!(
+                "Learned correction: \"{}\" -> \"{}\"",
+                correction.original, correction.corrected
+            );
+            newly_learned.push(correction.clone());
+        }
+        learned.corrections.insert(0, correction);
+    }
+    learned.corrections.truncate(MAX_CORRECTIONS);
+
+    store.set(&ConfigKey::LEARNED_CORRECTIONS, learned)?;
+    Ok(newly_learned)
+}
+
+/// Corrections made at least [`MIN_OCCURRENCES`] times, most frequent first
+pub fn learned_corrections(store: &impl ConfigStore) -> Vec<LearnedCorrection> {
+    let mut learned: Vec<LearnedCorrection> = match store.get(&ConfigKey::LEARNED_CORRECTIONS) {
+        Some(learned) => learned.corrections,
+        None => return Vec::new(),
+    };
+    learned.retain(|c| c.count >= MIN_OCCURRENCES);
+    learned.sort_by(|a, b| b.count.cmp(&a.count));
+    learned
+}
+
+/// Forget every correction of `original`
+pub fn forget_correction(store: &impl ConfigStore, original: &str) -> Result<(), String> {
+    let mut learned = store
+        .get(&ConfigKey::LEARNED_CORRECTIONS)
+        .unwrap_or_default();
+    learned.corrections.retain(|c| c.original != original);
+    store.set(&ConfigKey::LEARNED_CORRECTIONS, learned)
+}
+
+/// Add the learned corrections to a post-processing prompt
+pub fn augment_prompt(prompt: &str, corrections: &[LearnedCorrection]) -> String {
+    if corrections.is_empty() {
+        return prompt.to_string();
+    }
+
+    let mut augmented = format!(
+        "{}\n\nThe speech recognizer often gets these terms wrong. Replace the misrecognized \
+         form with the correct one where it fits the context:",
+        prompt.trim()
+    );
+    for correction in corrections.iter().take(MAX_PROMPT_CORRECTIONS) {
+        augmented.push_str(&format!(
+            "\n- \"{}\" → \"{}\"",
+            correction.original, correction.corrected
+        ));
+    }
+    augmented
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correction_pairs() {
+        assert_eq!(
+            correction_pairs(
+                "Ask Shawn about the cube cuttle rollout.",
+                "Ask Sean about the kubectl rollout."
+            ),
+            vec![
+                ("Shawn".to_string(), "Sean".to_string()),
+                ("cube cuttle".to_string(), "kubectl".to_string()),
+            ]
+        );
+        // Added words and punctuation-only edits are not corrections
+        assert!(correction_pairs("Hello world", "Hello big world").is_empty());
+        assert!(correction_pairs("Hello world", "Hello world!").is_empty());
+        // Rewrites are not corrections
+        assert!(correction_pairs(
+            "one two three four five",
+            "this sentence was rewritten entirely"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_augment_prompt() {
+        assert_eq!(augment_prompt("Fix grammar.", &[]), "Fix grammar.");
+
+        let corrections = [LearnedCorrection {
+            original: "cube cuttle".to_string(),
+            corrected: "kubectl".to_string(),
+            count: 3,
+        }];
+        let prompt = augment_prompt("Fix grammar.\n", &corrections);
+        assert!(prompt.starts_with("Fix grammar.\n\nThe speech recognizer"));
+        assert!(prompt.ends_with("\n- \"cube cuttle\" → \"kubectl\""));
+    }
+}
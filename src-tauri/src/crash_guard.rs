@@ -0,0 +1,295 @@
+//! Puts back the system settings Dictara changed.
+//!
+//! Before the Globe key behavior is changed, the user's original value is stored in
+//! the config (`globeKeyOriginal`). It is restored when no shortcut uses Fn anymore,
+//! with `restore_globe_key_behavior`, when the app quits (see `shutdown`) and when it
+//! is terminated by SIGTERM, SIGINT or SIGHUP. After a crash none of these run: the
+//! next launch finds the original still stored and restores it before anything is
+//! changed again.
+
+use std::sync::Mutex;
+
+use log::{error, info, warn};
+use tauri::{AppHandle, Manager};
+
+use crate::config::{self, ConfigKey, ConfigStore, GlobeKeyOriginal};
+use crate::globe_key::{self, GlobeKeyBehavior};
+
+/// Where the Globe key behavior is read and written
+pub trait GlobeKeySetting {
+    fn get(&self) -> Option<GlobeKeyBehavior>;
+    fn set(&self, behavior: GlobeKeyBehavior) -> Result<(), String>;
+    /// Remove the setting, back to the system default
+    fn delete(&self) -> Result<(), String>;
+}
+
+/// The Globe key behavior of the macOS preferences
+pub struct SystemGlobeKey;
+
+impl GlobeKeySetting for SystemGlobeKey {
+    fn get(&self) -> Option<GlobeKeyBehavior> {
+        globe_key::get_globe_key_behavior()
+    }
+
+    fn set(&self, behavior: GlobeKeyBehavior) -> Result<(), String> {
+        globe_key::set_globe_key_behavior(behavior)
+    }
+
+    fn delete(&self) -> Result<(), String> {
+        globe_key::delete_globe_key_behavior()
+    }
+}
+
+/// Changes the system settings for the app, remembering the originals
+pub struct SystemGuard<C = config::Config, K = SystemGlobeKey> {
+    config: C,
+    globe_key: K,
+    /// Serializes changing and restoring
+    lock: Mutex<()>,
+}
+
+impl<C: ConfigStore, K: GlobeKeySetting> SystemGuard<C, K> {
+    /// Guard of this session, first restoring what a crashed session left changed
+    pub fn start(config: C, globe_key: K) -> Self {
+        let guard = Self {
+            config,
+            globe_key,
+            lock: Mutex::new(()),
+        };
+        if guard.config.get(&ConfigKey::GLOBE_KEY_ORIGINAL).is_some() {
+            warn!("The last session didn't exit cleanly, restoring the system settings it changed");
+            if let Err(e) = guard.restore() {
+                error!("{}", e);
+            }
+        }
+        guard
+    }
+
+    /// Set the Globe key to "Do Nothing" unless it already leaves Fn alone, so Fn
+    /// shortcuts don't open the emoji picker. Returns whether it was changed.
+    pub fn fix_globe_key(&self) -> bool {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let current = self.globe_key.get();
+        if globe_key::is_compatible(current) {
+            info!(
+                "Globe key behavior is already {:?}, no change needed",
+                current
+            );
+            return false;
+        }
+
+        // Saved before the change: a crash right after it still gets it restored.
+        // Kept from an earlier change, the current value is Dictara's own.
+        if self.config.get(&ConfigKey::GLOBE_KEY_ORIGINAL).is_none() {
+            let original = GlobeKeyOriginal {
+                behavior: current.map(|behavior| behavior as i32),
+            };
+            if let Err(e) = self.config.set(&ConfigKey::GLOBE_KEY_ORIGINAL, original) {
+                warn!("Failed to save the original Globe key behavior: {}", e);
+            }
+        }
+
+        match self.globe_key.set(GlobeKeyBehavior::DoNothing) {
+            Ok(()) => {
+                info!("Globe key behavior changed from {:?} to DoNothing", current);
+                true
+            }
+            Err(e) => {
+                warn!("Failed to change Globe key behavior: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Put back the user's Globe key behavior if Dictara changed it. Returns whether
+    /// there was something to restore.
+    pub fn restore(&self) -> Result<bool, String> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(original) = self.config.get(&ConfigKey::GLOBE_KEY_ORIGINAL) else {
+            return Ok(false);
+        };
+
+        match original.behavior.and_then(GlobeKeyBehavior::from_i32) {
+            Some(behavior) => self.globe_key.set(behavior),
+            None => self.globe_key.delete(),
+        }
+        .map_err(|e| format!("Failed to restore the Globe key behavior: {}", e))?;
+
+        self.config.delete(&ConfigKey::GLOBE_KEY_ORIGINAL)?;
+        info!("Globe key behavior restored to {:?}", original.behavior);
+        Ok(true)
+    }
+
+    /// [`Self::restore`] on the way out, where a failure can only be logged
+    pub fn restore_on_exit(&self) {
+        if let Err(e) = self.restore() {
+            error!("{}", e);
+        }
+    }
+}
+
+/// Quit like from the menu on SIGTERM, SIGINT or SIGHUP, instead of dying with the
+/// system settings still changed
+#[cfg(unix)]
+pub fn watch_exit_signals(app_handle: &AppHandle) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let signals = [
+            SignalKind::terminate(),
+            SignalKind::interrupt(),
+            SignalKind::hangup(),
+        ]
+        .map(signal);
+        let [Ok(mut terminate), Ok(mut interrupt), Ok(mut hangup)] = signals else {
+            error!("Failed to listen for exit signals");
+            return;
+        };
+
+        tokio::select! {
+            _ = terminate.recv() => info!("Received SIGTERM"),
+            _ = interrupt.recv() => info!("Received SIGINT"),
+            _ = hangup.recv() => info!("Received SIGHUP"),
+        }
+
+        // Restored right away in case the event loop is stuck, then a normal exit
+        if let Some(guard) = app_handle.try_state::<SystemGuard>() {
+            guard.restore_on_exit();
+        }
+        app_handle.exit(0);
+    });
+}
+
+#[cfg(not(unix))]
+pub fn watch_exit_signals(_app_handle: &AppHandle) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::mock::MockConfigStore;
+    use std::cell::Cell;
+
+    /// Globe key setting in memory, `fail` makes writes fail
+    #[derive(Default)]
+    struct MockGlobeKey {
+        behavior: Cell<Option<GlobeKeyBehavior>>,
+        fail: Cell<bool>,
+    }
+
+    impl GlobeKeySetting for &MockGlobeKey {
+        fn get(&self) -> Option<GlobeKeyBehavior> {
+            self.behavior.get()
+        }
+
+        fn set(&self, behavior: GlobeKeyBehavior) -> Result<(), String> {
+            if self.fail.get() {
+                return Err("defaults failed".to_string());
+            }
+            self.behavior.set(Some(behavior));
+            Ok(())
+        }
+
+        fn delete(&self) -> Result<(), String> {
+            if self.fail.get() {
+                return Err("defaults failed".to_string());
+            }
+            self.behavior.set(None);
+            Ok(())
+        }
+    }
+
+    fn stored_original(config: &MockConfigStore) -> Option<GlobeKeyOriginal> {
+        config.get(&ConfigKey::GLOBE_KEY_ORIGINAL)
+    }
+
+    #[test]
+    fn test_fix_and_restore() {
+        let globe_key = MockGlobeKey::default();
+        globe_key.behavior.set(Some(GlobeKeyBehavior::ShowEmoji));
+        let guard = SystemGuard::start(MockConfigStore::new(), &globe_key);
+
+        assert!(guard.fix_globe_key());
+        assert_eq!(globe_key.behavior.get(), Some(GlobeKeyBehavior::DoNothing));
+        assert_eq!(
+            stored_original(&guard.config),
+            Some(GlobeKeyOriginal {
+                behavior: Some(GlobeKeyBehavior::ShowEmoji as i32)
+            })
+        );
+        // Already compatible, the original stays
+        assert!(!guard.fix_globe_key());
+
+        assert_eq!(guard.restore(), Ok(true));
+        assert_eq!(globe_key.behavior.get(), Some(GlobeKeyBehavior::ShowEmoji));
+        assert_eq!(stored_original(&guard.config), None);
+        assert_eq!(guard.restore(), Ok(false));
+    }
+
+    #[test]
+    fn test_restore_of_an_unset_behavior_deletes_it() {
+        let globe_key = MockGlobeKey::default();
+        let guard = SystemGuard::start(MockConfigStore::new(), &globe_key);
+
+        assert!(guard.fix_globe_key());
+        assert_eq!(
+            stored_original(&guard.config),
+            Some(GlobeKeyOriginal { behavior: None })
+        );
+        assert_eq!(guard.restore(), Ok(true));
+        assert_eq!(globe_key.behavior.get(), None);
+    }
+
+    #[test]
+    fn test_compatible_behavior_is_left_alone() {
+        let globe_key = MockGlobeKey::default();
+        globe_key
+            .behavior
+            .set(Some(GlobeKeyBehavior::ChangeInputSource));
+        let guard = SystemGuard::start(MockConfigStore::new(), &globe_key);
+
+        assert!(!guard.fix_globe_key());
+        assert_eq!(stored_original(&guard.config), None);
+        assert_eq!(guard.restore(), Ok(false));
+    }
+
+    #[test]
+    fn test_start_restores_after_a_crash() {
+        // Left changed by a session that didn't exit cleanly
+        let config = MockConfigStore::new();
+        config
+            .set(
+                &ConfigKey::GLOBE_KEY_ORIGINAL,
+                GlobeKeyOriginal {
+                    behavior: Some(GlobeKeyBehavior::StartDictation as i32),
+                },
+            )
+            .unwrap();
+        let globe_key = MockGlobeKey::default();
+        globe_key.behavior.set(Some(GlobeKeyBehavior::DoNothing));
+
+        let guard = SystemGuard::start(config, &globe_key);
+        assert_eq!(
+            globe_key.behavior.get(),
+            Some(GlobeKeyBehavior::StartDictation)
+        );
+        assert_eq!(stored_original(&guard.config), None);
+    }
+
+    #[test]
+    fn test_failed_restore_keeps_the_original() {
+        let globe_key = MockGlobeKey::default();
+        globe_key.behavior.set(Some(GlobeKeyBehavior::ShowEmoji));
+        let guard = SystemGuard::start(MockConfigStore::new(), &globe_key);
+        assert!(guard.fix_globe_key());
+
+        globe_key.fail.set(true);
+        assert!(guard.restore().is_err());
+        // Still there for the next attempt (or the next launch)
+        assert!(stored_original(&guard.config).is_some());
+
+        globe_key.fail.set(false);
+        assert_eq!(guard.restore(), Ok(true));
+        assert_eq!(globe_key.behavior.get(), Some(GlobeKeyBehavior::ShowEmoji));
+    }
+}
@@ -0,0 +1,58 @@
+//! Diagnostics report for bug reports.
+//!
+//! Collects what support usually asks for in one place: the app version and
+//! platform, the startup timings, the memory/swap/GPU usage of the last local
+//! model load and transcription, the keyboard tap statistics and the last
+//! recording state transitions.
+
+use crate::commands::get_app_version;
+use crate::models::{ModelLoader, ResourceReport};
+use crate::recording::{RecordingStateManager, StateHistoryEntry};
+use crate::shortcuts::events::KeyboardGrabStats;
+use crate::startup::{StartupProfiler, StartupReport};
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsReport {
+    pub app_version: String,
+    /// `std::env::consts::OS` ("macos", "windows", "linux")
+    pub os: String,
+    pub arch: String,
+    /// `None` until the startup profiler is set up
+    pub startup: Option<StartupReport>,
+    /// Resource usage of local inference, empty until a local model was used
+    pub local_inference: ResourceReport,
+    pub keyboard_grab: KeyboardGrabStats,
+    /// Oldest first
+    pub state_history: Vec<StateHistoryEntry>,
+}
+
+impl DiagnosticsReport {
+    /// Read the current diagnostics. Managers that aren't set up yet report defaults.
+    pub fn collect(app: &AppHandle) -> Self {
+        let startup = app
+            .try_state::<Arc<StartupProfiler>>()
+            .map(|profiler| profiler.report());
+        let local_inference = app
+            .try_state::<Arc<ModelLoader>>()
+            .map(|loader| loader.resource_report())
+            .unwrap_or_default();
+        let state_history = app
+            .try_state::<Arc<RecordingStateManager>>()
+            .map(|state_manager| state_manager.history())
+            .unwrap_or_default();
+
+        Self {
+            app_version: get_app_version(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            startup,
+            local_inference,
+            keyboard_grab: dictara_keyboard::grab_stats().into(),
+            state_history,
+        }
+    }
+}
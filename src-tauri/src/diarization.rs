@@ -0,0 +1,111 @@
+//! Speaker labels of meeting transcripts, see [`Diarizer`].
+//!
+//! Only built with the `diarization` feature; otherwise [`Diarizer::from_app`] always
+//! returns `None` and meetings are transcribed without speaker labels.
+
+use std::sync::Arc;
+
+#[cfg(feature = "diarization")]
+use dictara_core::diarization::{self, SpeakerEmbedder};
+use dictara_core::models::speaker_model_file;
+use log::warn;
+use tauri::{AppHandle, Manager};
+
+use crate::config::{self, ConfigKey, ConfigStore};
+use crate::models::ModelManager;
+
+/// Whether this build can label speakers
+pub const AVAILABLE: bool = cfg!(feature = "diarization");
+
+/// Collects a speaker embedding per transcribed segment, then labels the speakers
+#[cfg_attr(not(feature = "diarization"), allow(dead_code))]
+pub struct Diarizer {
+    #[cfg(feature = "diarization")]
+    embedder: SpeakerEmbedder,
+    embeddings: Vec<Option<Vec<f32>>>,
+}
+
+impl Diarizer {
+    /// A diarizer when enabled in the settings, built in and the speaker model is
+    /// downloaded, `None` otherwise
+    pub fn from_app(app: &AppHandle) -> Option<Self> {
+        let app_config = app
+            .state::<config::Config>()
+            .get(&ConfigKey::APP)
+            .unwrap_or_default();
+        if !app_config.meeting_diarization {
+            return None;
+        }
+        if !AVAILABLE {
+            warn!("Speaker labels are enabled, but this build has no diarization support");
+            return None;
+        }
+
+        // The model is executed, an unverifiable file (downloaded before) isn't run
+        if !speaker_model_file().is_pinned() {
+            warn!("Speaker labels are enabled, but the speaker model can't be verified");
+            return None;
+        }
+        let model_path = app.state::<Arc<ModelManager>>().speaker_model_path();
+        if !model_path.exists() {
+            warn!("Speaker labels are enabled, but the speaker model is not downloaded");
+            return None;
+        }
+        Self::load(&model_path)
+    }
+
+    #[cfg(feature = "diarization")]
+    fn load(model_path: &std::path::Path) -> Option<Self> {
+        match SpeakerEmbedder::new(model_path) {
+            Ok(embedder) => {
+                log::info!("Speaker model loaded");
+                Some(Self {
+                    embedder,
+                    embeddings: Vec::new(),
+                })
+            }
+            Err(e) => {
+                log::error!("{}", e);
+                None
+            }
+        }
+    }
+
+    #[cfg(not(feature = "diarization"))]
+    fn load(_model_path: &std::path::Path) -> Option<Self> {
+        None
+    }
+
+    /// Embed the speech of the next transcribed segment
+    pub fn add_segment(&mut self, samples: &[f32]) {
+        #[cfg(feature = "diarization")]
+        let embedding = match self.embedder.embed(samples) {
+            Ok(embedding) => Some(embedding),
+            Err(e) => {
+                warn!("Segment without speaker label: {}", e);
+                None
+            }
+        };
+        #[cfg(not(feature = "diarization"))]
+        let embedding = {
+            let _ = samples;
+            None
+        };
+        self.embeddings.push(embedding);
+    }
+
+    /// Speaker number (1 or 2, in order of appearance) of each added segment
+    pub fn speakers(&self) -> Vec<Option<u32>> {
+        #[cfg(feature = "diarization")]
+        {
+            diarization::assign_two_speakers(&self.embeddings)
+                .into_iter()
+                .map(|speaker| speaker.map(|speaker| speaker as u32 + 1))
+                .collect()
+        }
+        #[cfg(not(feature = "diarization"))]
+        {
+            vec![None; self.embeddings.len()]
+        }
+    }
+}
@@ -16,7 +16,7 @@ pub enum Error {
     Tauri(tauri::Error),
 
     #[from]
-    Keyring(keyring::Error),
+    Keychain(crate::keychain::KeychainError),
 
     #[from]
     SerdeJson(serde_json::Error),
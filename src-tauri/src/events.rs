@@ -0,0 +1,136 @@
+//! Catalog of the events sent to the frontend.
+//!
+//! Events are defined next to the code that emits them (`recording/events.rs`,
+//! `models/events.rs`, ...). This is the single list registered with tauri-specta,
+//! so every event gets a TypeScript type (with its doc comment) in the bindings.
+//! The test below fails when an event type is defined but missing here, or has
+//! no doc comment.
+
+/// Single source of truth for all events
+/// This macro takes a wrapper macro path and applies it to the event list
+#[macro_export]
+macro_rules! with_events {
+    ($($wrapper:tt)*) => {
+        $($wrapper)*![
+            // Recording
+            $crate::recording::events::RecordingStateChanged,
+            $crate::recording::events::RawModeChanged,
+            $crate::recording::events::InputDeviceChanged,
+            $crate::recording::events::RecordingTick,
+            $crate::recording::events::TranscriptionHeld,
+            $crate::recording::events::TranscriptionResult,
+            $crate::insertion::TextInserted,
+            // Models (discriminated unions for state machine patterns)
+            $crate::models::events::ModelDownloadStateChanged,
+            $crate::models::events::ModelLoadingStateChanged,
+            $crate::models::events::ModelMigrationStateChanged,
+            $crate::models::events::LocalInferenceResourceUsage,
+            $crate::models::events::TranscriptionProgress,
+            // Shortcuts
+            $crate::shortcuts::events::KeyCaptureEvent,
+            $crate::shortcuts::events::SecureInputChanged,
+            $crate::shortcuts::events::KeyListenerHealthChanged,
+            // File transcription
+            $crate::file_transcription::events::FileTranscriptionStateChanged,
+            // Live captions
+            $crate::live_captions::CaptionLine,
+            $crate::live_captions::LiveCaptionsStateChanged,
+            // Meeting mode
+            $crate::meeting::MeetingStateChanged,
+            // Keychain read failures (locked, access denied)
+            $crate::keychain::KeychainAccessFailed,
+            // Saved or deleted config entries
+            $crate::config::ConfigChanged,
+            // Appearance preferences
+            $crate::ui::prefs::UiPrefsChanged,
+            // Time-of-day automation
+            $crate::automation::AutomationRuleActivated,
+            // Daily word goal
+            $crate::usage::WordGoalReached,
+            // App state for windows opened mid-session
+            $crate::snapshot::StateSnapshot,
+        ]
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::Path;
+
+    /// Type names of the registered events
+    fn registered_events() -> BTreeSet<String> {
+        with_events!(stringify)
+            .trim_matches(|c| c == '[' || c == ']')
+            .split(',')
+            .filter_map(|path| path.rsplit("::").next())
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    /// Type names deriving `tauri_specta::Event` in `dir`, with whether they are documented
+    fn defined_events(dir: &Path, events: &mut Vec<(String, bool)>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                defined_events(&path, events);
+                continue;
+            }
+            if path.extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+
+            let source = fs::read_to_string(&path).unwrap();
+            let lines: Vec<&str> = source.lines().map(str::trim).collect();
+            for (i, line) in lines.iter().enumerate() {
+                if !(line.starts_with("#[derive(") && line.contains("tauri_specta::Event")) {
+                    continue;
+                }
+                let name = lines[i + 1..]
+                    .iter()
+                    .find_map(|line| {
+                        let rest = line
+                            .strip_prefix("pub struct ")
+                            .or_else(|| line.strip_prefix("pub enum "))?;
+                        rest.split(|c: char| !c.is_alphanumeric() && c != '_')
+                            .next()
+                    })
+                    .unwrap()
+                    .to_string();
+                let documented = lines[..i]
+                    .iter()
+                    .rev()
+                    .find(|line| !line.starts_with("#["))
+                    .is_some_and(|line| line.starts_with("///"));
+                events.push((name, documented));
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_events_are_registered_and_documented() {
+        let mut defined = Vec::new();
+        defined_events(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut defined,
+        );
+        let registered = registered_events();
+
+        for (name, documented) in &defined {
+            assert!(
+                registered.contains(name),
+                "{} derives tauri_specta::Event but is missing from with_events!",
+                name
+            );
+            assert!(documented, "{} has no doc comment for the bindings", name);
+        }
+        assert_eq!(
+            registered.len(),
+            defined.len(),
+            "with_events! lists types that are not events: {:?}",
+            registered
+        );
+    }
+}
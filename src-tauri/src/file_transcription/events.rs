@@ -0,0 +1,54 @@
+//! Typesafe events for the file transcription module.
+//!
+//! These events are emitted from Rust and can be listened to in TypeScript
+//! with full type safety via tauri-specta.
+
+use serde::{Deserialize, Serialize};
+
+/// File transcription state change event - single event stream for a whole batch
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum FileTranscriptionStateChanged {
+    /// Transcription of a file has started
+    #[serde(rename = "started")]
+    Started {
+        #[serde(rename = "filePath")]
+        file_path: String,
+        /// Zero-based position of the file in the batch
+        #[serde(rename = "fileIndex")]
+        file_index: u32,
+        #[serde(rename = "totalFiles")]
+        total_files: u32,
+    },
+    /// A chunk of the file has been transcribed
+    #[serde(rename = "progress")]
+    Progress {
+        #[serde(rename = "filePath")]
+        file_path: String,
+        #[serde(rename = "completedChunks")]
+        completed_chunks: u32,
+        #[serde(rename = "totalChunks")]
+        total_chunks: u32,
+        percentage: f64,
+    },
+    /// File transcribed, outputs written next to the source file
+    #[serde(rename = "complete")]
+    Complete {
+        #[serde(rename = "filePath")]
+        file_path: String,
+        #[serde(rename = "textPath")]
+        text_path: String,
+        #[serde(rename = "srtPath")]
+        srt_path: String,
+    },
+    /// Transcription of a file failed, the batch continues with the next file
+    #[serde(rename = "error")]
+    Error {
+        #[serde(rename = "filePath")]
+        file_path: String,
+        error: String,
+    },
+    /// All files of the batch have been processed
+    #[serde(rename = "finished")]
+    Finished { succeeded: u32, failed: u32 },
+}
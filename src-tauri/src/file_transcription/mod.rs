@@ -0,0 +1,424 @@
+//! Batch transcription of existing audio files (meetings, voice memos, ...).
+//!
+//! Files are decoded and resampled to 16kHz mono, split into fixed-length chunks
+//! and sent chunk by chunk to the active provider. Each chunk becomes one
+//! timestamped segment, which gives us `.srt` subtitles for every provider.
+//! Segments are kept for the session so files can be re-exported (e.g. as WebVTT).
+//! Audio on the clipboard is transcribed the same way, without writing any files.
+
+pub mod events;
+
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use dictara_core::audio_file::{self, AudioFileError};
+use dictara_core::recorder::SAMPLE_RATE;
+use dictara_core::subtitles::SubtitleFormat;
+use dictara_core::transcription::TranscriptSegment;
+use log::{error, info, warn};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::clients::{validate_transcription_config, Transcriber, TranscriptionError};
+use crate::text_paster::{self, ClipboardAudio};
+
+use events::FileTranscriptionStateChanged;
+
+/// Length of the chunks sent to the provider (~960KB of WAV, well below API limits)
+const CHUNK_DURATION_MS: u64 = 30_000;
+
+/// Numbered names tried for an output file before giving up (`<name> (999).txt`)
+const MAX_OUTPUT_NAME_ATTEMPTS: u32 = 1000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum FileTranscriptionError {
+    #[error(transparent)]
+    Audio(#[from] AudioFileError),
+
+    #[error(transparent)]
+    Transcription(#[from] TranscriptionError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl FileTranscriptionError {
+    /// Returns a user-friendly error message suitable for display in the UI
+    pub fn user_message(&self) -> String {
+        match self {
+            FileTranscriptionError::Audio(e) => e.user_message(),
+            FileTranscriptionError::Transcription(e) => e.user_message(),
+            FileTranscriptionError::Io(_) => {
+                "Failed to write transcription files. Check folder permissions.".to_string()
+            }
+        }
+    }
+}
+
+/// Segments of the files transcribed in this session, keyed by source file path
+type TranscribedFiles = Arc<Mutex<HashMap<String, Vec<TranscriptSegment>>>>;
+
+/// Runs file transcription batches in the background, one batch at a time
+pub struct FileTranscriber {
+    is_running: Arc<AtomicBool>,
+    transcribed_files: TranscribedFiles,
+}
+
+impl FileTranscriber {
+    pub fn new() -> Self {
+        Self {
+            is_running: Arc::new(AtomicBool::new(false)),
+            transcribed_files: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Start transcribing `paths` in a background thread
+    ///
+    /// Progress and results are reported via [`FileTranscriptionStateChanged`] events.
+    pub fn start(&self, app: AppHandle, paths: Vec<String>) -> Result<(), String> {
+        if paths.is_empty() {
+            return Err("No files to transcribe".to_string());
+        }
+
+        if self
+            .is_running
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err("A file transcription is already running".to_string());
+        }
+
+        let transcriber = match validate_transcription_config(&app, "file transcription") {
+            Ok(transcriber) => transcriber,
+            Err(e) => {
+                self.is_running.store(false, Ordering::SeqCst);
+                return Err(e);
+            }
+        };
+
+        let is_running = self.is_running.clone();
+        let transcribed_files = self.transcribed_files.clone();
+        std::thread::spawn(move || {
+            run_batch(&app, &transcriber, &paths, &transcribed_files);
+            is_running.store(false, Ordering::SeqCst);
+        });
+
+        Ok(())
+    }
+
+    /// Transcribe the audio on the clipboard (a voice memo file, a copied path or the
+    /// audio itself) with the active provider and return the text
+    ///
+    /// Blocks until done, and is refused while a file transcription is running.
+    pub fn transcribe_clipboard_audio(&self, app: &AppHandle) -> Result<String, String> {
+        let audio =
+            text_paster::clipboard_audio().ok_or("No audio file or audio data on the clipboard")?;
+
+        if self
+            .is_running
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err("A file transcription is already running".to_string());
+        }
+
+        let result = Transcriber::from_app(app)
+            .map_err(FileTranscriptionError::from)
+            .and_then(|transcriber| {
+                let work_dir = work_dir(app);
+                let samples = load_clipboard_audio(audio, &work_dir)?;
+                transcribe_samples(&transcriber, &samples, &work_dir, |_, _| {})
+            });
+        self.is_running.store(false, Ordering::SeqCst);
+
+        let segments = result.map_err(|e| {
+            error!("Failed to transcribe clipboard audio: {}", e);
+            e.user_message()
+        })?;
+        info!("Transcribed clipboard audio: {} segments", segments.len());
+        Ok(segments_text(&segments))
+    }
+
+    /// Export the transcription of a previously transcribed file as subtitles
+    ///
+    /// Writes `<file>.<srt|vtt>` next to the source file, `<file> (1).<srt|vtt>` etc.
+    /// if it exists, and returns its path.
+    pub fn export_subtitles(
+        &self,
+        file_path: &str,
+        format: SubtitleFormat,
+    ) -> Result<PathBuf, String> {
+        let segments = self
+            .transcribed_files
+            .lock()
+            .map_err(|e| format!("Failed to lock transcribed files: {}", e))?
+            .get(file_path)
+            .cloned()
+            .ok_or_else(|| format!("No transcription found for {}", file_path))?;
+
+        write_new_file(
+            Path::new(file_path),
+            format.extension(),
+            &format.render(&segments),
+        )
+        .map_err(|e| format!("Failed to write subtitles: {}", e))
+    }
+}
+
+impl Default for FileTranscriber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn run_batch(
+    app: &AppHandle,
+    transcriber: &Transcriber,
+    paths: &[String],
+    transcribed_files: &TranscribedFiles,
+) {
+    let work_dir = work_dir(app);
+    let total_files = paths.len() as u32;
+    let mut succeeded = 0;
+    let mut failed = 0;
+
+    for (index, file_path) in paths.iter().enumerate() {
+        emit(
+            app,
+            FileTranscriptionStateChanged::Started {
+                file_path: file_path.clone(),
+                file_index: index as u32,
+                total_files,
+            },
+        );
+
+        match transcribe_file(app, transcriber, Path::new(file_path), &work_dir) {
+            Ok((segments, text_path, srt_path)) => {
+                succeeded += 1;
+                if let Ok(mut files) = transcribed_files.lock() {
+                    files.insert(file_path.clone(), segments);
+                }
+                emit(
+                    app,
+                    FileTranscriptionStateChanged::Complete {
+                        file_path: file_path.clone(),
+                        text_path: text_path.to_string_lossy().to_string(),
+                        srt_path: srt_path.to_string_lossy().to_string(),
+                    },
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                error!("Failed to transcribe {}: {}", file_path, e);
+                emit(
+                    app,
+                    FileTranscriptionStateChanged::Error {
+                        file_path: file_path.clone(),
+                        error: e.user_message(),
+                    },
+                );
+            }
+        }
+    }
+
+    info!(
+        "File transcription finished: {} succeeded, {} failed",
+        succeeded, failed
+    );
+    emit(
+        app,
+        FileTranscriptionStateChanged::Finished { succeeded, failed },
+    );
+}
+
+/// Transcribe one file and write `.txt`/`.srt` next to it, without replacing
+/// existing files (see [`write_new_file`])
+///
+/// Returns the segments and the paths of both output files.
+fn transcribe_file(
+    app: &AppHandle,
+    transcriber: &Transcriber,
+    path: &Path,
+    work_dir: &Path,
+) -> Result<(Vec<TranscriptSegment>, PathBuf, PathBuf), FileTranscriptionError> {
+    let samples = audio_file::load_audio_file(path)?;
+    let file_path = path.to_string_lossy().to_string();
+
+    let segments = transcribe_samples(
+        transcriber,
+        &samples,
+        work_dir,
+        |completed_chunks, total_chunks| {
+            emit(
+                app,
+                FileTranscriptionStateChanged::Progress {
+                    file_path: file_path.clone(),
+                    completed_chunks,
+                    total_chunks,
+                    percentage: completed_chunks as f64 / total_chunks as f64 * 100.0,
+                },
+            );
+        },
+    )?;
+
+    let text_path = write_new_file(path, "txt", &segments_text(&segments))?;
+    let srt_path = write_new_file(path, "srt", &SubtitleFormat::Srt.render(&segments))?;
+
+    info!("Transcribed {:?}: {} segments", path, segments.len());
+
+    Ok((segments, text_path, srt_path))
+}
+
+/// Write `contents` next to `source` with `extension`, never replacing a file: an
+/// earlier transcript or the user's own subtitles get `<name> (1).<extension>` etc.
+fn write_new_file(source: &Path, extension: &str, contents: &str) -> std::io::Result<PathBuf> {
+    let stem = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    for attempt in 0..MAX_OUTPUT_NAME_ATTEMPTS {
+        let name = match attempt {
+            0 => format!("{}.{}", stem, extension),
+            n => format!("{} ({}).{}", stem, n, extension),
+        };
+        let path = source.with_file_name(name);
+        // Created only if missing, so a file appearing in between isn't replaced either
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                if let Err(e) = file.write_all(contents.as_bytes()) {
+                    // Don't leave a truncated transcript behind
+                    drop(file);
+                    let _ = std::fs::remove_file(&path);
+                    return Err(e);
+                }
+                return Ok(path);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(std::io::Error::new(
+        std::io::ErrorKind::AlreadyExists,
+        format!(
+            "No free file name for {}.{} after {} attempts",
+            stem, extension, MAX_OUTPUT_NAME_ATTEMPTS
+        ),
+    ))
+}
+
+/// Transcribe 16kHz mono samples chunk by chunk, one segment per non-empty chunk
+///
+/// `on_progress` is called with the completed and total chunk counts after each chunk.
+fn transcribe_samples(
+    transcriber: &Transcriber,
+    samples: &[f32],
+    work_dir: &Path,
+    mut on_progress: impl FnMut(u32, u32),
+) -> Result<Vec<TranscriptSegment>, FileTranscriptionError> {
+    let chunk_samples = (CHUNK_DURATION_MS * SAMPLE_RATE as u64 / 1000) as usize;
+    let total_chunks = samples.len().div_ceil(chunk_samples).max(1) as u32;
+
+    std::fs::create_dir_all(work_dir)?;
+    let chunk_path = work_dir.join("chunk.wav");
+
+    let mut segments = Vec::new();
+    for (index, chunk) in samples.chunks(chunk_samples).enumerate() {
+        let start_ms = index as u64 * CHUNK_DURATION_MS;
+        let duration_ms = chunk.len() as u64 * 1000 / SAMPLE_RATE as u64;
+
+        audio_file::write_wav_16k_mono(&chunk_path, chunk)?;
+        let text = transcriber.transcribe(chunk_path.clone(), duration_ms);
+        if let Err(e) = std::fs::remove_file(&chunk_path) {
+            warn!("Failed to clean up chunk file: {}", e);
+        }
+
+        let text = text?;
+        if !text.trim().is_empty() {
+            segments.push(TranscriptSegment {
+                start_ms,
+                end_ms: start_ms + duration_ms,
+                text: text.trim().to_string(),
+            });
+        }
+
+        on_progress(index as u32 + 1, total_chunks);
+    }
+
+    Ok(segments)
+}
+
+fn segments_text(segments: &[TranscriptSegment]) -> String {
+    segments
+        .iter()
+        .map(|s| s.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Decode the clipboard audio, writing audio data to `work_dir` first
+fn load_clipboard_audio(
+    audio: ClipboardAudio,
+    work_dir: &Path,
+) -> Result<Vec<f32>, FileTranscriptionError> {
+    match audio {
+        ClipboardAudio::File(path) => Ok(audio_file::load_audio_file(&path)?),
+        ClipboardAudio::Data { bytes, extension } => {
+            std::fs::create_dir_all(work_dir)?;
+            let path = work_dir.join("clipboard").with_extension(extension);
+            std::fs::write(&path, bytes)?;
+            let samples = audio_file::load_audio_file(&path);
+            if let Err(e) = std::fs::remove_file(&path) {
+                warn!("Failed to clean up clipboard audio file: {}", e);
+            }
+            Ok(samples?)
+        }
+    }
+}
+
+/// Scratch directory for the chunk WAV files
+fn work_dir(app: &AppHandle) -> PathBuf {
+    match app.path().app_cache_dir() {
+        Ok(cache_dir) => cache_dir.join("file_transcription"),
+        Err(_) => std::env::temp_dir()
+            .join("dictara")
+            .join("file_transcription"),
+    }
+}
+
+fn emit(app: &AppHandle, event: FileTranscriptionStateChanged) {
+    if let Err(e) = event.emit(app) {
+        error!("Failed to emit FileTranscriptionStateChanged event: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_new_file_keeps_existing_outputs() {
+        let dir = std::env::temp_dir().join(format!("dictara-file-output-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("meeting.m4a");
+        std::fs::write(dir.join("meeting.txt"), "earlier transcript").unwrap();
+
+        let first = write_new_file(&source, "txt", "new transcript").unwrap();
+        let second = write_new_file(&source, "txt", "another transcript").unwrap();
+        let subtitles = write_new_file(&source, "srt", "subtitles").unwrap();
+
+        assert_eq!(first, dir.join("meeting (1).txt"));
+        assert_eq!(second, dir.join("meeting (2).txt"));
+        assert_eq!(subtitles, dir.join("meeting.srt"));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("meeting.txt")).unwrap(),
+            "earlier transcript"
+        );
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "new transcript");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
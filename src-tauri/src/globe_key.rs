@@ -8,8 +8,11 @@
 //! - 1: Change Input Source
 //! - 2: Show Emoji & Symbols (default on most Macs)
 //! - 3: Start Dictation
+//!
+//! The setting is changed through the `SystemGuard` (see `crash_guard`), which
+//! remembers the original value and puts it back when the app exits.
 
-use log::{info, warn};
+#[cfg(target_os = "macos")]
 use std::process::Command;
 
 /// Globe key behavior options
@@ -28,7 +31,7 @@ pub enum GlobeKeyBehavior {
 
 impl GlobeKeyBehavior {
     /// Convert from integer value
-    fn from_i32(value: i32) -> Option<Self> {
+    pub fn from_i32(value: i32) -> Option<Self> {
         match value {
             0 => Some(Self::DoNothing),
             1 => Some(Self::ChangeInputSource),
@@ -85,53 +88,38 @@ pub fn set_globe_key_behavior(behavior: GlobeKeyBehavior) -> Result<(), String>
     }
 }
 
-/// Fix the Globe key setting if it would interfere with Dictara's Fn-based recording.
-///
-/// This is called during onboarding to silently configure the Globe key.
-/// We change to "Do Nothing" unless it's already set to "Do Nothing" or "Change Input Source"
-/// (both of which are compatible with Dictara).
-///
-/// "Show Emoji" and "Start Dictation" both interfere because they trigger system actions
-/// when the Fn key is pressed.
-///
-/// Returns `true` if a change was made, `false` if no change was needed.
+/// Delete the Globe key setting, back to the macOS default (Show Emoji & Symbols).
 #[cfg(target_os = "macos")]
-pub fn fix_globe_key_if_needed() -> bool {
-    let current = get_globe_key_behavior();
-
-    // Only these values are compatible with Dictara:
-    // - DoNothing (0): Fn key does nothing, perfect for us
-    // - ChangeInputSource (1): Fn key switches input source, doesn't show UI
-    //
-    // These values interfere and need to be fixed:
-    // - None (not set, defaults to ShowEmoji on most Macs)
-    // - ShowEmoji (2): Shows emoji picker popup
-    // - StartDictation (3): Starts macOS dictation
-    let is_compatible = matches!(
-        current,
-        Some(GlobeKeyBehavior::DoNothing) | Some(GlobeKeyBehavior::ChangeInputSource)
-    );
-
-    if !is_compatible {
-        match set_globe_key_behavior(GlobeKeyBehavior::DoNothing) {
-            Ok(()) => {
-                info!("Globe key behavior changed from {:?} to DoNothing", current);
-                true
-            }
-            Err(e) => {
-                warn!("Failed to change Globe key behavior: {}", e);
-                false
-            }
-        }
+pub fn delete_globe_key_behavior() -> Result<(), String> {
+    let status = Command::new("defaults")
+        .args(["delete", "com.apple.HIToolbox", "AppleFnUsageType"])
+        .status()
+        .map_err(|e| format!("Failed to run defaults command: {}", e))?;
+
+    if status.success() {
+        Ok(())
     } else {
-        info!(
-            "Globe key behavior is already {:?}, no change needed",
-            current
-        );
-        false
+        Err("defaults delete command failed".into())
     }
 }
 
+/// Whether the Globe key setting leaves the Fn key to Dictara.
+///
+/// Only these values are compatible with Dictara:
+/// - DoNothing (0): Fn key does nothing, perfect for us
+/// - ChangeInputSource (1): Fn key switches input source, doesn't show UI
+///
+/// These values interfere and need to be fixed:
+/// - None (not set, defaults to ShowEmoji on most Macs)
+/// - ShowEmoji (2): Shows emoji picker popup
+/// - StartDictation (3): Starts macOS dictation
+pub fn is_compatible(behavior: Option<GlobeKeyBehavior>) -> bool {
+    matches!(
+        behavior,
+        Some(GlobeKeyBehavior::DoNothing) | Some(GlobeKeyBehavior::ChangeInputSource)
+    )
+}
+
 // Stub implementations for non-macOS platforms
 #[cfg(not(target_os = "macos"))]
 pub fn get_globe_key_behavior() -> Option<GlobeKeyBehavior> {
@@ -144,8 +132,8 @@ pub fn set_globe_key_behavior(_behavior: GlobeKeyBehavior) -> Result<(), String>
 }
 
 #[cfg(not(target_os = "macos"))]
-pub fn fix_globe_key_if_needed() -> bool {
-    false
+pub fn delete_globe_key_behavior() -> Result<(), String> {
+    Ok(())
 }
 
 #[cfg(test)]
@@ -174,6 +162,15 @@ mod tests {
         assert_eq!(GlobeKeyBehavior::from_i32(-1), None);
     }
 
+    #[test]
+    fn test_is_compatible() {
+        assert!(is_compatible(Some(GlobeKeyBehavior::DoNothing)));
+        assert!(is_compatible(Some(GlobeKeyBehavior::ChangeInputSource)));
+        assert!(!is_compatible(Some(GlobeKeyBehavior::ShowEmoji)));
+        assert!(!is_compatible(Some(GlobeKeyBehavior::StartDictation)));
+        assert!(!is_compatible(None));
+    }
+
     #[test]
     fn test_globe_key_behavior_to_i32() {
         assert_eq!(GlobeKeyBehavior::DoNothing as i32, 0);
@@ -0,0 +1,230 @@
+//! Verified text insertion with fallbacks.
+//!
+//! Cmd+V sometimes lands nowhere (no focused text field) and the transcription
+//! would silently vanish. When the focused element exposes its value through
+//! Accessibility, the insertion is verified by comparing the value before and
+//! after, and the next strategy is tried only when it definitely didn't land (the
+//! strategy failed, or the same element still shows the same value once the app had
+//! [`VERIFY_TIMEOUT`] to apply it):
+//!
+//! 1. paste (or type short text), as before
+//! 2. Accessibility insert (set the selected text of the focused element)
+//! 3. keystroke typing
+//! 4. keep the text on the clipboard, the caller notifies the user
+//!
+//! Apps that don't expose the value (terminals, many Electron apps) can't be
+//! verified, nor can an insertion after which the focus moved or the value can't
+//! be read anymore: the insertion is trusted and reported as unverified, since
+//! falling back would insert the text twice.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ClipboardMarker, PasteTiming};
+use crate::text_paster::{self, PasteOptions, PreparedClipboard};
+
+/// Time the target app gets to apply an insertion before it counts as not inserted
+const VERIFY_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Interval between two reads of the focused value while verifying
+const VERIFY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// What the focused element shows after an insertion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Check {
+    /// The value changed
+    Inserted,
+    /// Same element, same value: the insertion didn't land
+    NotInserted,
+    /// The focus moved or the value can't be read anymore
+    Unknown,
+}
+
+/// Compare the focused element and its value after an insertion to `before`
+fn check<E: PartialEq>(before: &(E, String), after: Option<&(E, String)>) -> Check {
+    match after {
+        Some((element, _)) if *element != before.0 => Check::Unknown,
+        Some((_, value)) if *value != before.1 => Check::Inserted,
+        Some(_) => Check::NotInserted,
+        None => Check::Unknown,
+    }
+}
+
+/// Check the focused element until the insertion shows up or [`VERIFY_TIMEOUT`] passes
+fn verify(before: &(FocusedElement, String)) -> Check {
+    let started_at = Instant::now();
+    loop {
+        let result = check(before, focused_text().as_ref());
+        if result != Check::NotInserted || started_at.elapsed() >= VERIFY_TIMEOUT {
+            return result;
+        }
+        thread::sleep(VERIFY_INTERVAL);
+    }
+}
+
+/// How the text ended up being inserted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum InsertionMethod {
+    /// Pasted or typed by the default strategy
+    Paste,
+    /// Inserted through Accessibility
+    AccessibilityInsert,
+    /// Typed as keystrokes after the paste didn't land
+    Typing,
+    /// Nothing worked, the text was left on the clipboard
+    Clipboard,
+}
+
+/// Result of an insertion, reported to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct TextInserted {
+    pub method: InsertionMethod,
+    /// Whether the insertion was confirmed via Accessibility
+    pub verified: bool,
+}
+
+/// Insert `text` into the focused app, falling back when the insertion didn't land
+///
+/// The clipboard is restored once the focused text changed (within `timing`). Apps
+/// with `AXEnhancedUserInterface` set (an assistive app requested the full tree,
+/// which makes them respond slower) get the maximum delay when unverifiable.
+/// `prepared` is the clipboard saved ahead of time, if any.
+pub fn insert_text_verified(
+    text: &str,
+    marker: ClipboardMarker,
+    timing: PasteTiming,
+    prepared: Option<&PreparedClipboard>,
+) -> Result<TextInserted, String> {
+    // Electron apps only expose their text fields once asked to
+    enable_manual_accessibility();
+
+    let Some(before) = focused_text() else {
+        // Not verifiable: trust the default strategy
+        let options = PasteOptions {
+            marker,
+            timing,
+            consumed: None,
+            slow_target: enhanced_user_interface(),
+            prepared,
+        };
+        text_paster::insert_text_with_options(text, &options).map_err(|e| e.to_string())?;
+        return Ok(TextInserted {
+            method: InsertionMethod::Paste,
+            verified: false,
+        });
+    };
+
+    let consumed = || check(&before, focused_text().as_ref()) != Check::NotInserted;
+    let options = PasteOptions {
+        marker,
+        timing,
+        consumed: Some(&consumed),
+        slow_target: false,
+        prepared,
+    };
+    let strategies: [(InsertionMethod, &dyn Fn() -> Result<(), String>); 3] = [
+        (InsertionMethod::Paste, &|| {
+            text_paster::insert_text_with_options(text, &options).map_err(|e| e.to_string())
+        }),
+        (InsertionMethod::AccessibilityInsert, &|| {
+            if insert_at_selection(text) {
+                Ok(())
+            } else {
+                Err("Accessibility insert rejected".to_string())
+            }
+        }),
+        (InsertionMethod::Typing, &|| {
+            text_paster::type_text(text).map_err(|e| e.to_string())
+        }),
+    ];
+
+    for (method, insert) in strategies {
+        if let Err(e) = insert() {
+            warn!("Text insertion via {:?} failed: {}", method, e);
+            continue;
+        }
+        match verify(&before) {
+            Check::Inserted => {
+                info!("Text insertion verified ({:?})", method);
+                return Ok(TextInserted {
+                    method,
+                    verified: true,
+                });
+            }
+            Check::Unknown => {
+                info!("Text insertion via {:?} could not be verified", method);
+                return Ok(TextInserted {
+                    method,
+                    verified: false,
+                });
+            }
+            Check::NotInserted => warn!(
+                "Text insertion via {:?} not visible in the focused element",
+                method
+            ),
+        }
+    }
+
+    text_paster::copy_text(text).map_err(|e| e.to_string())?;
+    warn!("Text could not be inserted, left on the clipboard");
+    Ok(TextInserted {
+        method: InsertionMethod::Clipboard,
+        verified: false,
+    })
+}
+
+#[cfg(target_os = "macos")]
+use crate::document_context::ax::{
+    enable_manual_accessibility, enhanced_user_interface, focused_text, insert_at_selection,
+    FocusedElement,
+};
+
+#[cfg(not(target_os = "macos"))]
+type FocusedElement = ();
+
+#[cfg(not(target_os = "macos"))]
+fn enable_manual_accessibility() {}
+
+#[cfg(not(target_os = "macos"))]
+fn enhanced_user_interface() -> bool {
+    false
+}
+
+#[cfg(not(target_os = "macos"))]
+fn focused_text() -> Option<(FocusedElement, String)> {
+    None
+}
+
+#[cfg(not(target_os = "macos"))]
+fn insert_at_selection(_text: &str) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let before = (1, "Hello".to_string());
+        assert_eq!(
+            check(&before, Some(&(1, "Hello world".to_string()))),
+            Check::Inserted
+        );
+        assert_eq!(
+            check(&before, Some(&(1, "Hello".to_string()))),
+            Check::NotInserted
+        );
+        // Another element got the focus, or the value is gone: can't tell
+        assert_eq!(
+            check(&before, Some(&(2, "Hello world".to_string()))),
+            Check::Unknown
+        );
+        assert_eq!(check(&before, None), Check::Unknown);
+    }
+}
@@ -1,76 +1,271 @@
-use crate::config::ShortcutsConfig;
-use crate::recording::{RecordingCommand, RecordingStateManager};
-use crate::shortcuts::events::KeyCaptureEvent;
-use dictara_keyboard::{grab, Event, EventType};
-use log::{error, info};
+use crate::config::{Shortcut, ShortcutsConfig};
+use crate::recording::{RawMode, RecordingCommand, RecordingStateManager};
+use crate::shortcuts::debounce::Debouncer;
+use crate::shortcuts::events::{
+    CapturedKeyboard, KeyCaptureEvent, KeyListenerHealthChanged, SecureInputChanged,
+};
+use crate::shortcuts::hold::HoldGate;
+use dictara_keyboard::{
+    grab_with_config, resume_grab, secure_input_state, suspend_grab, Event, EventType, GrabConfig,
+    GrabDecision, GrabError, KeyboardLayout, SecureInputState, TapMode,
+};
+use log::{error, info, warn};
+use std::cell::RefCell;
 use std::collections::HashSet;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 use tauri::AppHandle;
 use tauri_specta::Event as EventTrait;
 use tokio::sync::mpsc;
 
+/// How often Secure Input is checked. Taps receive no events while it's enabled,
+/// so it can't be noticed from the event callback.
+const SECURE_INPUT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Delay before restarting a failed event tap, doubled on each consecutive failure
+const RESTART_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const RESTART_MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// A tap that ran at least this long before failing counts as recovered:
+/// the next restart starts over with the initial backoff
+const STABLE_RUN_DURATION: Duration = Duration::from_secs(30);
+
 /// Operating mode for the keyboard listener
 enum ListenerMode {
     /// Normal mode: match shortcuts and trigger recording
     Normal { shortcuts: ShortcutsConfig },
     /// Capture mode: emit key events to frontend for configuration
-    Capture { app_handle: AppHandle },
+    Capture {
+        app_handle: AppHandle,
+        /// Active keyboard layout, used for key labels
+        layout: KeyboardLayout,
+    },
+}
+
+/// Event callback state, kept across event tap restarts
+struct ListenerState {
+    mode: ListenerMode,
+    pressed_keys: HashSet<u32>,
+    mode_rx: mpsc::Receiver<ListenerMode>,
+    /// Push-to-talk press withheld until it's clearly a hold
+    hold: HoldGate,
+    /// Rate limit of starts, bounce filter of releases
+    debouncer: Debouncer,
 }
 
 /// Keyboard listener that detects key events and emits recording commands
 pub struct KeyListener {
     _thread_handle: Option<JoinHandle<()>>,
+    _secure_input_watcher: JoinHandle<()>,
     mode_tx: mpsc::Sender<ListenerMode>, // Send mode updates to thread
+    /// False while the event tap is down and waiting to be restarted
+    healthy: Arc<AtomicBool>,
+    /// Set on resume: keys pressed before the suspension may have been released unseen
+    keys_stale: Arc<AtomicBool>,
 }
 
 impl KeyListener {
     pub fn start(
         command_tx: mpsc::Sender<RecordingCommand>,
         state_manager: Arc<RecordingStateManager>,
+        raw_mode: Arc<RawMode>,
         initial_config: ShortcutsConfig,
+        grab_config: GrabConfig,
+        app_handle: AppHandle,
     ) -> Self {
         info!(
-            "Starting KeyListener with initial config: push_to_record={:?}, hands_free={:?}",
-            initial_config.push_to_record.keys, initial_config.hands_free.keys
+            "Starting KeyListener with initial config: push_to_record={:?}, hands_free={:?}, tap={:?}",
+            initial_config.push_to_record.keys, initial_config.hands_free.keys, grab_config
         );
 
-        let (mode_tx, mut mode_rx) = mpsc::channel(10);
+        let (mode_tx, mode_rx) = mpsc::channel(10);
+        let healthy = Arc::new(AtomicBool::new(true));
+        let keys_stale = Arc::new(AtomicBool::new(false));
 
+        let thread_healthy = healthy.clone();
+        let thread_keys_stale = keys_stale.clone();
+        let thread_app_handle = app_handle.clone();
         let thread_handle = thread::spawn(move || {
-            let mut mode = ListenerMode::Normal {
-                shortcuts: initial_config,
-            };
-            let mut pressed_keys: HashSet<u32> = HashSet::new();
-
-            if let Err(err) = grab(move |event| {
-                // Phase 1: Sync to latest mode from control channel
-                Self::sync_mode(&mut mode, &mut mode_rx, &mut pressed_keys);
-
-                // Phase 2: Process event with fresh mode
-                match &mode {
-                    ListenerMode::Normal { shortcuts } => Self::handle_normal_mode(
-                        event,
-                        shortcuts,
-                        &mut pressed_keys,
-                        &command_tx,
-                        &state_manager,
-                    ),
-                    ListenerMode::Capture { app_handle } => {
-                        Self::handle_capture_mode(event, app_handle)
+            let state = Rc::new(RefCell::new(ListenerState {
+                mode: ListenerMode::Normal {
+                    shortcuts: initial_config,
+                },
+                pressed_keys: HashSet::new(),
+                mode_rx,
+                hold: HoldGate::new(grab_config.mode == TapMode::Intercept),
+                debouncer: Debouncer::new(),
+            }));
+            let mut backoff = RESTART_INITIAL_BACKOFF;
+
+            // Supervise the event tap: restart it with exponential backoff whenever it
+            // fails or stops (e.g. accessibility revoked, or not yet effective after a grant)
+            loop {
+                let started_at = Instant::now();
+                let callback_state = state.clone();
+                let command_tx = command_tx.clone();
+                let state_manager = state_manager.clone();
+                let raw_mode = raw_mode.clone();
+                let healthy = thread_healthy.clone();
+                let keys_stale = thread_keys_stale.clone();
+                let app_handle = thread_app_handle.clone();
+
+                let result = grab_with_config(grab_config, move |event| {
+                    // The first event after a restart proves the tap works again
+                    if !healthy.swap(true, Ordering::SeqCst) {
+                        info!("KeyListener recovered");
+                        Self::emit_health(&app_handle, true, None, None);
+                    }
+
+                    let mut state = callback_state.borrow_mut();
+                    let ListenerState {
+                        mode,
+                        pressed_keys,
+                        mode_rx,
+                        hold,
+                        debouncer,
+                    } = &mut *state;
+
+                    if keys_stale.swap(false, Ordering::SeqCst) {
+                        pressed_keys.clear();
+                        hold.take();
+                        debouncer.reset();
+                    }
+
+                    // Phase 1: Sync to latest mode from control channel
+                    Self::sync_mode(mode, mode_rx, pressed_keys, hold, debouncer);
+
+                    // Phase 2: Process event with fresh mode
+                    match mode {
+                        ListenerMode::Normal { shortcuts } => Self::handle_normal_mode(
+                            event,
+                            shortcuts,
+                            pressed_keys,
+                            hold,
+                            debouncer,
+                            &command_tx,
+                            &state_manager,
+                            &raw_mode,
+                        ),
+                        ListenerMode::Capture { app_handle, layout } => {
+                            Self::handle_capture_mode(event, app_handle, layout)
+                        }
                     }
+                });
+
+                let reason = match result {
+                    Err(GrabError::UnsupportedPlatform) => {
+                        error!("Keyboard grab is not supported on this platform. Keyboard shortcuts will not work.");
+                        thread_healthy.store(false, Ordering::SeqCst);
+                        return;
+                    }
+                    Err(err) => err.to_string(),
+                    Ok(()) => "Event tap stopped".to_string(),
+                };
+
+                if started_at.elapsed() >= STABLE_RUN_DURATION {
+                    backoff = RESTART_INITIAL_BACKOFF;
                 }
-            }) {
-                error!(
-                    "Keyboard grab failed: {}. Keyboard shortcuts will not work.",
-                    err
+                // Keys pressed before the failure won't be released through this tap
+                let mut stale = state.borrow_mut();
+                stale.pressed_keys.clear();
+                stale.hold.take();
+                stale.debouncer.reset();
+                drop(stale);
+
+                warn!(
+                    "Keyboard grab failed: {}. Restarting in {:?}",
+                    reason, backoff
                 );
+                thread_healthy.store(false, Ordering::SeqCst);
+                Self::emit_health(&thread_app_handle, false, Some(reason), Some(backoff));
+
+                thread::sleep(backoff);
+                backoff = (backoff * 2).min(RESTART_MAX_BACKOFF);
             }
         });
 
+        let secure_input_watcher = thread::spawn(move || Self::watch_secure_input(app_handle));
+
         Self {
             _thread_handle: Some(thread_handle),
+            _secure_input_watcher: secure_input_watcher,
             mode_tx,
+            healthy,
+            keys_stale,
+        }
+    }
+
+    /// Ignore all key events (they pass through untouched) until [`Self::resume`]
+    pub fn suspend(&self) {
+        info!("KeyListener suspended");
+        suspend_grab();
+    }
+
+    /// Handle key events again after [`Self::suspend`]
+    pub fn resume(&self) {
+        info!("KeyListener resumed");
+        self.keys_stale.store(true, Ordering::SeqCst);
+        resume_grab();
+    }
+
+    /// Whether the event tap is running (false while waiting for a restart)
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::SeqCst)
+    }
+
+    fn emit_health(
+        app_handle: &AppHandle,
+        healthy: bool,
+        error: Option<String>,
+        retry_in: Option<Duration>,
+    ) {
+        let event = KeyListenerHealthChanged {
+            healthy,
+            error,
+            retry_in_ms: retry_in.map(|delay| delay.as_millis() as u64),
+        };
+        if let Err(e) = event.emit(app_handle) {
+            error!("Failed to emit KeyListenerHealthChanged: {}", e);
+        }
+    }
+
+    /// Emit `SecureInputChanged` whenever an app enables or disables Secure Input
+    fn watch_secure_input(app_handle: AppHandle) {
+        let mut last = SecureInputState::default();
+        loop {
+            let state = secure_input_state();
+            if state != last {
+                let status = Self::secure_input_status(state);
+                if status.enabled {
+                    warn!(
+                        "Secure Input enabled by {} (pid {:?}), keyboard shortcuts are unavailable",
+                        status.app_name.as_deref().unwrap_or("an unknown app"),
+                        status.pid
+                    );
+                } else {
+                    info!("Secure Input disabled, keyboard shortcuts are available again");
+                }
+                if let Err(e) = status.emit(&app_handle) {
+                    error!("Failed to emit SecureInputChanged: {}", e);
+                }
+                last = state;
+            }
+            thread::sleep(SECURE_INPUT_POLL_INTERVAL);
+        }
+    }
+
+    /// Current Secure Input state with the name of the app that enabled it
+    pub fn current_secure_input() -> SecureInputChanged {
+        Self::secure_input_status(secure_input_state())
+    }
+
+    fn secure_input_status(state: SecureInputState) -> SecureInputChanged {
+        SecureInputChanged {
+            enabled: state.enabled,
+            pid: state.pid,
+            app_name: state.pid.and_then(app_name_for_pid),
         }
     }
 
@@ -80,6 +275,8 @@ impl KeyListener {
         mode: &mut ListenerMode,
         mode_rx: &mut mpsc::Receiver<ListenerMode>,
         pressed_keys: &mut HashSet<u32>,
+        hold: &HoldGate,
+        debouncer: &mut Debouncer,
     ) {
         while let Ok(new_mode) = mode_rx.try_recv() {
             match &new_mode {
@@ -95,6 +292,8 @@ impl KeyListener {
             }
             *mode = new_mode;
             pressed_keys.clear(); // Reset on mode change
+            hold.take();
+            debouncer.reset();
         }
     }
 
@@ -103,96 +302,176 @@ impl KeyListener {
         event: Event,
         shortcuts: &ShortcutsConfig,
         pressed_keys: &mut HashSet<u32>,
+        hold: &mut HoldGate,
+        debouncer: &mut Debouncer,
         command_tx: &mpsc::Sender<RecordingCommand>,
         state_manager: &Arc<RecordingStateManager>,
-    ) -> Option<Event> {
+        raw_mode: &RawMode,
+    ) -> GrabDecision {
+        // Keyboards can have their own shortcuts (e.g. external keyboards without Fn)
+        let (push_to_record, hands_free) = shortcuts.for_device(event.device.as_ref());
+        let min_start_interval = Duration::from_millis(shortcuts.min_start_interval_ms.into());
+
         match event.event_type {
             EventType::KeyPress(key) => {
                 let keycode = key.to_macos_keycode();
 
+                // Key repeat of the withheld press
+                if pressed_keys.contains(&keycode) && hold.is_pending() {
+                    return GrabDecision::Swallow;
+                }
+
+                // Bounce of a push-to-talk release: the recording goes on
+                if debouncer.is_bounce(keycode) {
+                    pressed_keys.insert(keycode);
+                    return GrabDecision::Pass;
+                }
+
                 // Check if shortcut was matched BEFORE inserting new key (rising edge detection)
-                let was_push_to_record = shortcuts.push_to_record.matches(pressed_keys);
-                let was_hands_free = shortcuts.hands_free.matches(pressed_keys);
+                let was_push_to_record = push_to_record.matches(pressed_keys);
+                let was_hands_free = hands_free.matches(pressed_keys);
+                let was_raw_mode = Self::matches_raw_mode(shortcuts, pressed_keys);
 
                 pressed_keys.insert(keycode);
 
+                // Another key pressed during the wait: not a hold of the shortcut alone
+                let mut withheld = hold.take();
+
+                // Raw mode: Rising edge toggles verbatim dictation, swallow the completing key
+                if !was_raw_mode && Self::matches_raw_mode(shortcuts, pressed_keys) {
+                    raw_mode.toggle();
+                    return GrabDecision::Swallow;
+                }
+
                 // Push-to-talk: Rising edge detected
-                if !was_push_to_record && shortcuts.push_to_record.matches(pressed_keys) {
+                if !was_push_to_record && push_to_record.matches(pressed_keys) {
                     if state_manager.is_recording_locked() {
                         // Stop hands-free mode (push-to-talk can stop hands-free)
                         let _ = command_tx.blocking_send(RecordingCommand::StopRecording);
+                    } else if shortcuts.hold_threshold_ms > 0 {
+                        // Tap or hold? Decided on release or when the threshold passes
+                        let threshold = Duration::from_millis(shortcuts.hold_threshold_ms.into());
+                        hold.withhold(event.event_type, threshold, command_tx);
+                        return GrabDecision::Swallow;
                     } else {
                         // Start push-to-talk recording
-                        let _ = command_tx.blocking_send(RecordingCommand::StartRecording);
+                        debouncer.start(
+                            &[RecordingCommand::StartRecording],
+                            min_start_interval,
+                            command_tx,
+                        );
                     }
                 }
 
                 // Hands-free: Rising edge detected (toggle behavior)
-                if !was_hands_free && shortcuts.hands_free.matches(pressed_keys) {
+                if !was_hands_free && hands_free.matches(pressed_keys) {
                     if state_manager.is_recording_locked() {
                         // Toggle off: Stop hands-free
                         let _ = command_tx.blocking_send(RecordingCommand::StopRecording);
                     } else {
                         // Toggle on: Start hands-free
-                        let _ = command_tx.blocking_send(RecordingCommand::StartRecording);
-                        let _ = command_tx.blocking_send(RecordingCommand::LockRecording);
+                        let commands = [
+                            RecordingCommand::StartRecording,
+                            RecordingCommand::LockRecording,
+                        ];
+                        if was_push_to_record {
+                            // Same press as the push-to-talk start, already rate limited
+                            debouncer.continue_press(&commands, command_tx);
+                        } else {
+                            debouncer.start(&commands, min_start_interval, command_tx);
+                        }
                     }
+                    // A withheld push-to-talk press is part of the combo
+                    withheld = None;
 
                     // Swallow Space if it's in the combo
-                    if shortcuts.hands_free.keys.iter().any(|k| k.keycode == 49) {
-                        return None;
+                    if hands_free.keys.iter().any(|k| k.keycode == 49) {
+                        return GrabDecision::Swallow;
                     }
                 }
 
+                if let Some(press) = withheld {
+                    hold.release(press, event.event_type);
+                    return GrabDecision::Swallow;
+                }
+
                 // Swallow all keys while push-to-record is active
-                if shortcuts.push_to_record.matches(pressed_keys) {
-                    return None;
+                if push_to_record.matches(pressed_keys) {
+                    return GrabDecision::Swallow;
                 }
 
-                Some(event)
+                GrabDecision::Pass
             }
             EventType::KeyRelease(key) => {
                 let keycode = key.to_macos_keycode();
 
                 // Check push-to-record BEFORE removing key
-                let was_push_to_record = shortcuts.push_to_record.matches(pressed_keys);
+                let was_push_to_record = push_to_record.matches(pressed_keys);
 
                 pressed_keys.remove(&keycode);
 
+                // Released before the threshold: a tap, not meant for us
+                if let Some(press) = hold.take() {
+                    hold.release(press, event.event_type);
+                    return GrabDecision::Swallow;
+                }
+
                 // Release stops recording (unless locked)
                 if was_push_to_record && !state_manager.is_recording_locked() {
-                    let _ = command_tx.blocking_send(RecordingCommand::StopRecording);
+                    let bounce = Duration::from_millis(shortcuts.bounce_ms.into());
+                    debouncer.stop_on_release(keycode, bounce, command_tx);
                 }
 
-                Some(event)
+                GrabDecision::Pass
             }
         }
     }
 
     /// Handle keyboard events in capture mode (emit to frontend, swallow all)
-    fn handle_capture_mode(event: Event, app_handle: &AppHandle) -> Option<Event> {
+    fn handle_capture_mode(
+        event: Event,
+        app_handle: &AppHandle,
+        layout: &KeyboardLayout,
+    ) -> GrabDecision {
+        let device = event.device.map(CapturedKeyboard::from);
         match event.event_type {
             EventType::KeyPress(key) => {
                 let keycode = key.to_macos_keycode();
-                let label = key.to_label();
-                let _ = KeyCaptureEvent::KeyDown { keycode, label }.emit(app_handle);
+                let label = layout.label(key);
+                let _ = KeyCaptureEvent::KeyDown {
+                    keycode,
+                    label,
+                    device,
+                }
+                .emit(app_handle);
             }
             EventType::KeyRelease(key) => {
                 let keycode = key.to_macos_keycode();
-                let label = key.to_label();
-                let _ = KeyCaptureEvent::KeyUp { keycode, label }.emit(app_handle);
+                let label = layout.label(key);
+                let _ = KeyCaptureEvent::KeyUp {
+                    keycode,
+                    label,
+                    device,
+                }
+                .emit(app_handle);
             }
         }
 
         // Swallow ALL events in capture mode (prevent Cmd+Q, etc.)
-        None
+        GrabDecision::Swallow
     }
 
     /// Enter capture mode to configure shortcuts
-    pub fn enter_capture_mode(&self, app_handle: AppHandle) -> Result<(), String> {
+    ///
+    /// Key labels are resolved with `layout` (read on the main thread by the caller).
+    pub fn enter_capture_mode(
+        &self,
+        app_handle: AppHandle,
+        layout: KeyboardLayout,
+    ) -> Result<(), String> {
         info!("Sending mode change request: Capture");
         self.mode_tx
-            .blocking_send(ListenerMode::Capture { app_handle })
+            .blocking_send(ListenerMode::Capture { app_handle, layout })
             .map_err(|_| "KeyListener thread is not running".to_string())
     }
 
@@ -223,11 +502,35 @@ impl KeyListener {
     /// Check if any shortcut uses Fn key (for globe key fix)
     pub fn uses_fn_key(config: &ShortcutsConfig) -> bool {
         let fn_code = 63u32;
-        config
-            .push_to_record
-            .keys
-            .iter()
-            .any(|k| k.keycode == fn_code)
-            || config.hands_free.keys.iter().any(|k| k.keycode == fn_code)
+        let uses_fn = |shortcut: &Shortcut| shortcut.keys.iter().any(|k| k.keycode == fn_code);
+
+        uses_fn(&config.push_to_record)
+            || uses_fn(&config.hands_free)
+            || config.raw_mode.as_ref().is_some_and(uses_fn)
+            || config
+                .device_shortcuts
+                .iter()
+                .any(|s| uses_fn(&s.push_to_record) || uses_fn(&s.hands_free))
     }
+
+    fn matches_raw_mode(shortcuts: &ShortcutsConfig, pressed_keys: &HashSet<u32>) -> bool {
+        shortcuts
+            .raw_mode
+            .as_ref()
+            .is_some_and(|shortcut| shortcut.matches(pressed_keys))
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn app_name_for_pid(pid: i32) -> Option<String> {
+    use objc2_app_kit::NSRunningApplication;
+
+    NSRunningApplication::runningApplicationWithProcessIdentifier(pid)
+        .and_then(|app| app.localizedName())
+        .map(|name| name.to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn app_name_for_pid(_pid: i32) -> Option<String> {
+    None
 }
@@ -1,7 +1,8 @@
 use keyring::Entry;
-use serde::{de::DeserializeOwned, Serialize};
-
-use crate::error;
+use log::error;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_specta::Event;
 
 #[cfg(debug_assertions)]
 const BUNDLE: &str = "app.dictara.dev";
@@ -9,18 +10,154 @@ const BUNDLE: &str = "app.dictara.dev";
 #[cfg(not(debug_assertions))]
 const BUNDLE: &str = "app.dictara";
 
-#[derive(strum::AsRefStr, strum::EnumString)]
+#[derive(Debug, Clone, Copy, strum::AsRefStr, strum::EnumString)]
 #[strum(serialize_all = "camelCase")]
 pub enum ProviderAccount {
     OpenAI,
     AzureOpenAI,
+    /// Proxy password, not a transcription provider (not in `ALL`)
+    Proxy,
+    /// Model download token, not a transcription provider (not in `ALL`)
+    HuggingFace,
+    /// Webhook signing secret, not a transcription provider (not in `ALL`)
+    Webhook,
+    /// MQTT broker password, not a transcription provider (not in `ALL`)
+    Mqtt,
+}
+
+impl ProviderAccount {
+    pub const ALL: [ProviderAccount; 2] = [ProviderAccount::OpenAI, ProviderAccount::AzureOpenAI];
+}
+
+/// Why the keychain couldn't be used, for the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum KeychainErrorKind {
+    /// The keychain is locked, the user must unlock it (re-entering keys won't help)
+    Locked,
+    /// The user denied access, or Dictara isn't allowed to use the item
+    AccessDenied,
+    /// No item stored for the account
+    NotFound,
+    Other,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeychainError {
+    #[error("Keychain is locked")]
+    Locked,
+    #[error("Keychain access denied")]
+    AccessDenied,
+    #[error("Keychain item not found")]
+    NotFound,
+    #[error("Keychain error: {0}")]
+    Other(String),
+}
+
+impl KeychainError {
+    pub fn kind(&self) -> KeychainErrorKind {
+        match self {
+            KeychainError::Locked => KeychainErrorKind::Locked,
+            KeychainError::AccessDenied => KeychainErrorKind::AccessDenied,
+            KeychainError::NotFound => KeychainErrorKind::NotFound,
+            KeychainError::Other(_) => KeychainErrorKind::Other,
+        }
+    }
+
+    /// What the user should do about it
+    pub fn user_message(&self) -> String {
+        match self {
+            KeychainError::Locked => {
+                "Your keychain is locked. Unlock it (e.g. in Keychain Access) and try again."
+                    .to_string()
+            }
+            KeychainError::AccessDenied => {
+                "Dictara was denied access to the keychain. Allow access when prompted, or \
+                 re-save your API key in Preferences."
+                    .to_string()
+            }
+            KeychainError::NotFound => {
+                "API key not configured. Please add it in Preferences.".to_string()
+            }
+            KeychainError::Other(msg) => format!("Couldn't read the keychain: {}", msg),
+        }
+    }
+}
+
+// macOS Security framework status codes
+const ERR_SEC_USER_CANCELED: i32 = -128;
+const ERR_SEC_AUTH_FAILED: i32 = -25293;
+const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+const ERR_SEC_INTERACTION_NOT_ALLOWED: i32 = -25308;
+
+impl From<keyring::Error> for KeychainError {
+    fn from(err: keyring::Error) -> Self {
+        match &err {
+            keyring::Error::NoEntry => KeychainError::NotFound,
+            keyring::Error::NoStorageAccess(inner) | keyring::Error::PlatformFailure(inner) => {
+                match os_status(inner.as_ref()) {
+                    Some(ERR_SEC_INTERACTION_NOT_ALLOWED) => KeychainError::Locked,
+                    Some(ERR_SEC_AUTH_FAILED | ERR_SEC_USER_CANCELED) => {
+                        KeychainError::AccessDenied
+                    }
+                    Some(ERR_SEC_ITEM_NOT_FOUND) => KeychainError::NotFound,
+                    _ => KeychainError::Other(err.to_string()),
+                }
+            }
+            _ => KeychainError::Other(err.to_string()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for KeychainError {
+    fn from(err: serde_json::Error) -> Self {
+        KeychainError::Other(format!("Invalid stored configuration: {}", err))
+    }
+}
+
+/// Security framework status code of a platform error.
+///
+/// keyring boxes the security-framework error of the macOS keychain.
+#[cfg(target_os = "macos")]
+fn os_status(err: &(dyn std::error::Error + Send + Sync)) -> Option<i32> {
+    err.downcast_ref::<security_framework::base::Error>()
+        .map(|err| err.code())
+}
+
+/// No Security framework status codes on other platforms
+#[cfg(not(target_os = "macos"))]
+fn os_status(_err: &(dyn std::error::Error + Send + Sync)) -> Option<i32> {
+    None
+}
+
+/// The keychain couldn't be read: tells the UI to guide the user (e.g. unlock the keychain)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct KeychainAccessFailed {
+    /// Keychain account (e.g. "openAI")
+    pub account: String,
+    pub kind: KeychainErrorKind,
+    pub user_message: String,
+}
+
+/// Emit [`KeychainAccessFailed`] for a keychain error
+pub fn report_access_error(app: &AppHandle, account: ProviderAccount, err: &KeychainError) {
+    error!("Keychain access failed for {}: {}", account.as_ref(), err);
+    let event = KeychainAccessFailed {
+        account: account.as_ref().to_string(),
+        kind: err.kind(),
+        user_message: err.user_message(),
+    };
+    if let Err(e) = event.emit(app) {
+        error!("Failed to emit KeychainAccessFailed: {}", e);
+    }
 }
 
 /// Save provider configuration as JSON to keychain
 pub fn save_provider_config<T: Serialize>(
     account: ProviderAccount,
     config: &T,
-) -> Result<(), error::Error> {
+) -> Result<(), KeychainError> {
     let account_name = account.as_ref();
     let entry = Entry::new(BUNDLE, account_name)?;
     let json = serde_json::to_string(config)?;
@@ -31,7 +168,7 @@ pub fn save_provider_config<T: Serialize>(
 /// Load provider configuration from keychain as JSON
 pub fn load_provider_config<T: DeserializeOwned>(
     account: ProviderAccount,
-) -> Result<Option<T>, error::Error> {
+) -> Result<Option<T>, KeychainError> {
     let account_name = account.as_ref();
     let entry = Entry::new(BUNDLE, account_name)?;
 
@@ -46,7 +183,7 @@ pub fn load_provider_config<T: DeserializeOwned>(
 }
 
 /// Delete provider configuration from keychain
-pub fn delete_provider_config(account: ProviderAccount) -> Result<(), error::Error> {
+pub fn delete_provider_config(account: ProviderAccount) -> Result<(), KeychainError> {
     let account_name = account.as_ref();
     let entry = Entry::new(BUNDLE, account_name)?;
 
@@ -55,3 +192,13 @@ pub fn delete_provider_config(account: ProviderAccount) -> Result<(), error::Err
         Err(e) => Err(e.into()),
     }
 }
+
+/// Check that the stored configuration of `account` can be read.
+///
+/// Unlike [`load_provider_config`], a missing item is reported as
+/// [`KeychainError::NotFound`].
+pub fn check_access(account: ProviderAccount) -> Result<(), KeychainError> {
+    let entry = Entry::new(BUNDLE, account.as_ref())?;
+    entry.get_password()?;
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! Language detection bookkeeping.
+//!
+//! Keeps per-language statistics of what the provider detected and lets the
+//! user pin a language for the current session (not persisted across restarts).
+//! The language last used in each app is remembered and selected for the next
+//! dictation in that app, unless a session language is pinned.
+
+use std::sync::Mutex;
+
+use log::{error, info};
+
+use crate::config::{AppLanguages, ConfigKey, ConfigStore, LanguageStats};
+
+/// Language pinned for the current session
+pub struct SessionLanguage {
+    pinned: Mutex<Option<String>>,
+}
+
+impl SessionLanguage {
+    pub fn new() -> Self {
+        Self {
+            pinned: Mutex::new(None),
+        }
+    }
+
+    /// Language code pinned for this session, `None` = auto-detect
+    pub fn pinned(&self) -> Option<String> {
+        self.pinned.lock().unwrap().clone()
+    }
+
+    /// Pin a language code for this session, `None` to go back to auto-detect
+    pub fn pin(&self, language: Option<String>) {
+        info!("Session language pinned to {:?}", language);
+        *self.pinned.lock().unwrap() = language;
+    }
+}
+
+impl Default for SessionLanguage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Count a transcription for the detected language
+pub fn record_detected_language(store: &impl ConfigStore, language: &str) {
+    let mut stats = store.get(&ConfigKey::LANGUAGE_STATS).unwrap_or_default();
+    *stats.counts.entry(language.to_string()).or_insert(0) += 1;
+
+    if let Err(e) = store.set(&ConfigKey::LANGUAGE_STATS, stats) {
+        error!("Failed to save language stats: {}", e);
+    }
+}
+
+/// Language statistics, empty if nothing has been detected yet
+pub fn load_language_stats(store: &impl ConfigStore) -> LanguageStats {
+    store.get(&ConfigKey::LANGUAGE_STATS).unwrap_or_default()
+}
+
+/// Remember `language` as the one used in the app with bundle identifier `app_id`
+pub fn remember_app_language(store: &impl ConfigStore, app_id: &str, language: &str) {
+    let mut app_languages = load_app_languages(store);
+    if app_languages.languages.get(app_id).map(String::as_str) == Some(language) {
+        return;
+    }
+    info!("Remembering language {} for {}", language, app_id);
+    app_languages
+        .languages
+        .insert(app_id.to_string(), language.to_string());
+
+    if let Err(e) = store.set(&ConfigKey::APP_LANGUAGES, app_languages) {
+        error!("Failed to save app languages: {}", e);
+    }
+}
+
+/// Language last used in the app with bundle identifier `app_id`, if any
+pub fn app_language(store: &impl ConfigStore, app_id: &str) -> Option<String> {
+    load_app_languages(store).languages.remove(app_id)
+}
+
+/// Languages remembered per app, empty if none yet
+pub fn load_app_languages(store: &impl ConfigStore) -> AppLanguages {
+    store.get(&ConfigKey::APP_LANGUAGES).unwrap_or_default()
+}
+
+/// Forget the language of the app with bundle identifier `app_id`
+pub fn forget_app_language(store: &impl ConfigStore, app_id: &str) -> Result<(), String> {
+    let mut app_languages = load_app_languages(store);
+    app_languages.languages.remove(app_id);
+    store.set(&ConfigKey::APP_LANGUAGES, app_languages)
+}
@@ -1,36 +1,87 @@
+mod audit;
 mod autolaunch;
+mod automation;
 mod clients;
 mod commands;
 mod config;
+mod corrections;
+mod crash_guard;
+mod diagnostics;
+mod diarization;
+mod document_context;
 mod error;
+mod events;
+mod file_transcription;
 mod globe_key;
+mod insertion;
 mod keyboard_listener;
 mod keychain;
+mod language;
+mod live_captions;
 mod log;
+mod login_session;
+mod managed_config;
+mod meeting;
 mod models;
+mod network;
+mod notes;
+mod profiles;
 mod recording;
+mod redaction;
+mod segmented_transcription;
+mod settings_index;
 mod setup;
 mod shortcuts;
+mod shutdown;
+mod sinks;
+mod snapshot;
+mod snippets;
 mod specta;
+mod startup;
 mod telemetry;
-mod text_paster;
+mod templates;
+mod transcription_eta;
 mod ui;
 mod updater;
+mod usage;
+mod worker_pool;
+
+// Tauri-free pipeline pieces live in the dictara-core crate
+use dictara_core::paste as text_paster;
 
 pub fn run() {
     tauri::Builder::default()
+        // Must come first: a second instance (two event taps, duplicated pastes)
+        // exits before any other plugin starts
+        .plugin(tauri_plugin_single_instance::init(
+            setup::focus_existing_instance,
+        ))
         .plugin(log::create_plugin().build())
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_autostart::init(
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
             None,
         ))
         .setup(setup::setup_app)
+        // Windows created mid-session start from the current state
+        .on_page_load(|webview, payload| {
+            use tauri::Manager;
+            if payload.event() == tauri::webview::PageLoadEvent::Finished {
+                snapshot::emit_snapshot(webview.app_handle());
+            }
+        })
         .invoke_handler(with_commands!(tauri::generate_handler))
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Quitting from the Dock, Cmd+Q or an update restart
+            if let tauri::RunEvent::Exit = event {
+                shutdown::shutdown(app);
+            }
+        });
 }
@@ -0,0 +1,187 @@
+//! Live captions: a floating always-on-top window showing what the microphone hears.
+//!
+//! A worker thread transcribes the microphone in short segments (see
+//! [`crate::segmented_transcription`]) and emits a [`CaptionLine`] per segment.
+//! Nothing is pasted.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::clients::{validate_transcription_config, Transcriber};
+use crate::segmented_transcription::{transcribe_microphone, Segmentation};
+use crate::ui::window;
+
+/// Caption lines of up to 5s of speech, cut short after a 1s pause
+const SEGMENTATION: Segmentation = Segmentation {
+    max_segment_ms: 5_000,
+    pause: Duration::from_millis(1_000),
+};
+
+/// A transcribed segment of live captions
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptionLine {
+    pub text: String,
+    /// Offset from the start of the session, in milliseconds
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// Live captions started or stopped (by command, closing the window or an error)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveCaptionsStateChanged {
+    pub running: bool,
+    /// Why the captions stopped on their own
+    pub error: Option<String>,
+}
+
+/// The running captions session, if any
+pub struct LiveCaptions {
+    is_running: Arc<AtomicBool>,
+    /// Dropped to stop the worker thread
+    stop_tx: Mutex<Option<Sender<()>>>,
+}
+
+impl LiveCaptions {
+    pub fn new() -> Self {
+        Self {
+            is_running: Arc::new(AtomicBool::new(false)),
+            stop_tx: Mutex::new(None),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.is_running.load(Ordering::SeqCst)
+    }
+
+    /// Open the captions window and start transcribing the microphone
+    pub fn start(&self, app: &AppHandle) -> Result<(), String> {
+        if self
+            .is_running
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err("Live captions are already running".to_string());
+        }
+
+        let result = self.spawn(app);
+        if result.is_err() {
+            self.is_running.store(false, Ordering::SeqCst);
+        }
+        result
+    }
+
+    fn spawn(&self, app: &AppHandle) -> Result<(), String> {
+        let transcriber = validate_transcription_config(app, "live captions")?;
+
+        let captions_window = window::open_captions_window(app)
+            .map_err(|e| format!("Failed to open the captions window: {}", e))?;
+        // Closing the window stops the captions
+        let app_for_window = app.clone();
+        captions_window.on_window_event(move |event| {
+            if let tauri::WindowEvent::Destroyed = event {
+                app_for_window.state::<LiveCaptions>().stop_worker();
+            }
+        });
+
+        let (stop_tx, stop_rx) = mpsc::channel();
+        let is_running = self.is_running.clone();
+        let app_for_worker = app.clone();
+        std::thread::Builder::new()
+            .name("live-captions".to_string())
+            .spawn(move || {
+                let error = run(&app_for_worker, &transcriber, &stop_rx).err();
+                if let Some(error) = &error {
+                    error!("Live captions stopped: {}", error);
+                }
+                is_running.store(false, Ordering::SeqCst);
+                emit(
+                    &app_for_worker,
+                    LiveCaptionsStateChanged {
+                        running: false,
+                        error,
+                    },
+                );
+            })
+            .map_err(|e| format!("Failed to start live captions: {}", e))?;
+
+        if let Ok(mut slot) = self.stop_tx.lock() {
+            *slot = Some(stop_tx);
+        }
+        emit(
+            app,
+            LiveCaptionsStateChanged {
+                running: true,
+                error: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stop transcribing (the last segment is still transcribed) and close the window
+    pub fn stop(&self, app: &AppHandle) {
+        self.stop_worker();
+        if let Err(e) = window::close_captions_window(app) {
+            warn!("Failed to close the captions window: {}", e);
+        }
+    }
+
+    fn stop_worker(&self) {
+        if let Ok(mut stop_tx) = self.stop_tx.lock() {
+            stop_tx.take();
+        }
+    }
+}
+
+impl Default for LiveCaptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Record and transcribe until the sender of `stop_rx` is dropped
+fn run(app: &AppHandle, transcriber: &Transcriber, stop_rx: &Receiver<()>) -> Result<(), String> {
+    info!("Live captions started");
+    let result = transcribe_microphone(
+        app,
+        transcriber,
+        SEGMENTATION,
+        &work_dir(app),
+        stop_rx,
+        |segment, _| {
+            emit(
+                app,
+                CaptionLine {
+                    text: segment.text,
+                    start_ms: segment.start_ms,
+                    end_ms: segment.end_ms,
+                },
+            )
+        },
+    );
+    info!("Live captions stopped");
+    result
+}
+
+/// Scratch directory for the captions recording and segments
+fn work_dir(app: &AppHandle) -> PathBuf {
+    match app.path().app_cache_dir() {
+        Ok(cache_dir) => cache_dir.join("live_captions"),
+        Err(_) => std::env::temp_dir().join("dictara").join("live_captions"),
+    }
+}
+
+fn emit<E: Event + Serialize + Clone>(app: &AppHandle, event: E) {
+    if let Err(e) = event.emit(app) {
+        error!("Failed to emit live captions event: {}", e);
+    }
+}
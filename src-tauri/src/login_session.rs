@@ -0,0 +1,82 @@
+//! Steps aside while another user's login session is in front (fast user switching).
+//!
+//! The app keeps running in the background session, and its event tap could
+//! still see or delay the other user's keystrokes. When our session resigns,
+//! the keyboard listener is suspended and a running recording is cancelled
+//! (the microphone now hears someone else). Both come back when the session
+//! is active again.
+
+use log::{info, warn};
+use std::sync::Arc;
+use tauri::Manager;
+use tokio::sync::mpsc::Sender;
+
+use crate::keyboard_listener::KeyListener;
+use crate::recording::{RecordingCommand, RecordingState, RecordingStateManager};
+
+/// Suspend and resume the app with the login session, for the app lifetime
+pub fn watch_login_session(
+    app_handle: tauri::AppHandle,
+    command_tx: Sender<RecordingCommand>,
+    state_manager: Arc<RecordingStateManager>,
+) {
+    let resign_handle = app_handle.clone();
+    observe(
+        move || {
+            info!("Login session switched away");
+            if let Some(listener) = resign_handle.try_state::<KeyListener>() {
+                listener.suspend();
+            }
+            if matches!(
+                state_manager.current(),
+                RecordingState::Recording | RecordingState::RecordingLocked
+            ) {
+                if let Err(e) = command_tx.try_send(RecordingCommand::Cancel) {
+                    warn!("Failed to cancel the recording on session switch: {}", e);
+                }
+            }
+        },
+        move || {
+            info!("Login session active again");
+            if let Some(listener) = app_handle.try_state::<KeyListener>() {
+                listener.resume();
+            }
+        },
+    );
+}
+
+#[cfg(target_os = "macos")]
+fn observe(on_resign: impl Fn() + 'static, on_activate: impl Fn() + 'static) {
+    use block2::RcBlock;
+    use objc2_app_kit::{
+        NSWorkspace, NSWorkspaceSessionDidBecomeActiveNotification,
+        NSWorkspaceSessionDidResignActiveNotification,
+    };
+    use objc2_foundation::NSNotification;
+    use std::ptr::NonNull;
+
+    let center = NSWorkspace::sharedWorkspace().notificationCenter();
+    let on_resign = RcBlock::new(move |_: NonNull<NSNotification>| on_resign());
+    let on_activate = RcBlock::new(move |_: NonNull<NSNotification>| on_activate());
+
+    // SAFETY: AppKit extern statics, no sender filter and no queue (the blocks run
+    // on the main thread, where NSWorkspace posts). The center keeps the observers
+    // registered for the app lifetime.
+    unsafe {
+        let _ = center.addObserverForName_object_queue_usingBlock(
+            Some(NSWorkspaceSessionDidResignActiveNotification),
+            None,
+            None,
+            &on_resign,
+        );
+        let _ = center.addObserverForName_object_queue_usingBlock(
+            Some(NSWorkspaceSessionDidBecomeActiveNotification),
+            None,
+            None,
+            &on_activate,
+        );
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn observe(_on_resign: impl Fn() + 'static, _on_activate: impl Fn() + 'static) {}
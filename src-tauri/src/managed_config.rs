@@ -0,0 +1,386 @@
+//! Read-only configuration deployed by IT, for enterprise installs.
+//!
+//! [`crate::config::Config`] merges the managed values over the user's config and
+//! refuses to change them. Sources, later ones winning:
+//! - `/Library/Managed Preferences/<bundle id>.plist` (computer-wide MDM profile)
+//! - `/Library/Managed Preferences/<user>/<bundle id>.plist` (per-user MDM profile)
+//! - `/Library/Application Support/Dictara/managed.json`
+//!
+//! Top-level keys are store keys holding the fields to lock, next to a few policies
+//! that don't map to a single field:
+//! ```json
+//! {
+//!   "appConfig": { "activeProvider": "azure_open_ai", "postProcessEnabled": false },
+//!   "telemetryConfig": { "telemetryEnabled": false },
+//!   "azureOpenAIEndpoint": "https://contoso.openai.azure.com/openai/deployments/whisper/audio/transcriptions",
+//!   "disableCloudProviders": false
+//! }
+//! ```
+
+use log::{info, warn};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::path::Path;
+
+const MANAGED_PREFERENCES_DIR: &str = "/Library/Managed Preferences";
+const MANAGED_JSON_PATH: &str = "/Library/Application Support/Dictara/managed.json";
+
+/// Policy: endpoint used for Azure OpenAI, whatever the user entered
+const AZURE_ENDPOINT_POLICY: &str = "azureOpenAIEndpoint";
+/// Policy: only local transcription, no post-processing
+const DISABLE_CLOUD_POLICY: &str = "disableCloudProviders";
+
+/// Locked configuration values and policies
+#[derive(Debug, Clone, Default)]
+pub struct ManagedConfig {
+    /// Locked fields by store key, with the policies expanded into fields
+    values: Map<String, Value>,
+    azure_open_ai_endpoint: Option<String>,
+    cloud_providers_disabled: bool,
+}
+
+impl ManagedConfig {
+    /// Read the managed sources of the app with bundle `identifier`.
+    /// Unreadable sources are logged and skipped.
+    pub fn load(identifier: &str) -> Self {
+        let plist_name = format!("{}.plist", identifier);
+        let mut sources = vec![Path::new(MANAGED_PREFERENCES_DIR).join(&plist_name)];
+        if let Ok(user) = std::env::var("USER") {
+            sources.push(
+                Path::new(MANAGED_PREFERENCES_DIR)
+                    .join(user)
+                    .join(&plist_name),
+            );
+        }
+        sources.push(Path::new(MANAGED_JSON_PATH).to_path_buf());
+
+        let mut merged = Value::Object(Map::new());
+        for path in sources.iter().filter(|path| path.exists()) {
+            match read_source(path) {
+                Ok(value) => {
+                    info!("Applying managed configuration from {}", path.display());
+                    merge(&mut merged, &value);
+                }
+                Err(e) => warn!("Ignoring managed configuration {}: {}", path.display(), e),
+            }
+        }
+        Self::from_value(merged)
+    }
+
+    fn from_value(value: Value) -> Self {
+        let Value::Object(mut entries) = value else {
+            return Self::default();
+        };
+
+        let azure_open_ai_endpoint = entries
+            .remove(AZURE_ENDPOINT_POLICY)
+            .and_then(|endpoint| endpoint.as_str().map(str::to_string));
+        let cloud_providers_disabled = entries
+            .remove(DISABLE_CLOUD_POLICY)
+            .and_then(|disabled| disabled.as_bool())
+            .unwrap_or(false);
+
+        let mut values = Value::Object(entries);
+        if cloud_providers_disabled {
+            merge(
+                &mut values,
+                &json!({
+                    "appConfig": {
+                        "activeProvider": "local",
+                        "fallbackProviders": [],
+                        "postProcessEnabled": false,
+                    }
+                }),
+            );
+        }
+        let Value::Object(mut values) = values else {
+            unreachable!("merging objects yields an object");
+        };
+        // Only objects can be merged over a stored entry
+        values.retain(|key, value| {
+            let is_object = value.is_object();
+            if !is_object {
+                warn!("Ignoring managed value {}: expected an object", key);
+            }
+            is_object
+        });
+
+        Self {
+            values,
+            azure_open_ai_endpoint,
+            cloud_providers_disabled,
+        }
+    }
+
+    /// Stored entry of `key` with the managed fields merged over it. A missing entry
+    /// with managed fields becomes an object of those fields (the others default).
+    pub fn apply(&self, key: &str, stored: Option<Value>) -> Option<Value> {
+        let Some(managed) = self.values.get(key) else {
+            return stored;
+        };
+        let mut value = stored.unwrap_or_else(|| Value::Object(Map::new()));
+        merge(&mut value, managed);
+        Some(value)
+    }
+
+    /// `value` with the managed fields of `key` merged over it. For entries that don't
+    /// exist yet: [`Self::apply`] on a missing entry doesn't deserialize when a field
+    /// without a default (e.g. the telemetry device id) isn't managed.
+    pub fn apply_over<T: Serialize + DeserializeOwned>(&self, key: &str, value: T) -> T {
+        if !self.values.contains_key(key) {
+            return value;
+        }
+        let merged = serde_json::to_value(&value)
+            .ok()
+            .and_then(|stored| self.apply(key, Some(stored)))
+            .and_then(|merged| serde_json::from_value(merged).ok());
+        match merged {
+            Some(merged) => merged,
+            None => {
+                warn!("Managed value {} doesn't fit its entry, ignored", key);
+                value
+            }
+        }
+    }
+
+    /// Fail if saving `value` as `key` would change a managed field
+    pub fn check_unchanged(&self, key: &str, value: &Value) -> Result<(), String> {
+        let Some(managed) = self.values.get(key) else {
+            return Ok(());
+        };
+        let mut locked = Vec::new();
+        leaf_paths(managed, key.to_string(), &mut locked);
+        for (path, managed_value) in locked {
+            let field = path
+                .split('.')
+                .skip(1)
+                .try_fold(value, |value, field| value.get(field));
+            if !field.is_some_and(|field| same_value(field, managed_value)) {
+                return Err(format!(
+                    "{} is managed by your organization and can't be changed",
+                    path
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Put the user's own values of the managed fields of `key` back into `value`
+    /// before it's stored, so lifting the policy restores them. Fields the user never
+    /// stored keep the managed value (removing them could leave an entry that no longer
+    /// deserializes).
+    pub fn restore_user_values(&self, key: &str, value: &mut Value, stored: Option<&Value>) {
+        let (Some(managed), Some(stored)) = (self.values.get(key), stored) else {
+            return;
+        };
+        let mut locked = Vec::new();
+        leaf_paths(managed, key.to_string(), &mut locked);
+        for (path, _) in locked {
+            let fields: Vec<&str> = path.split('.').skip(1).collect();
+            let Some(user_value) = fields
+                .iter()
+                .try_fold(stored, |stored, field| stored.get(field))
+            else {
+                continue;
+            };
+            if let Some(field) = fields
+                .iter()
+                .try_fold(&mut *value, |value, field| value.get_mut(field))
+            {
+                *field = user_value.clone();
+            }
+        }
+    }
+
+    /// Ids of the locked settings (`<store key>.<field path>`, as in the settings index)
+    pub fn locked_settings(&self) -> Vec<String> {
+        let mut locked = Vec::new();
+        for (key, managed) in &self.values {
+            leaf_paths(managed, key.clone(), &mut locked);
+        }
+        let mut ids: Vec<String> = locked.into_iter().map(|(path, _)| path).collect();
+        if self.azure_open_ai_endpoint.is_some() {
+            ids.push("azureOpenAIConfig.endpoint".to_string());
+        }
+        ids
+    }
+
+    /// Azure OpenAI endpoint set by IT, used instead of the user's
+    pub fn azure_open_ai_endpoint(&self) -> Option<&str> {
+        self.azure_open_ai_endpoint.as_deref()
+    }
+
+    /// Whether only local transcription is allowed
+    pub fn cloud_providers_disabled(&self) -> bool {
+        self.cloud_providers_disabled
+    }
+}
+
+fn read_source(path: &Path) -> Result<Value, String> {
+    let contents = if path.extension().is_some_and(|ext| ext == "plist") {
+        plist_to_json(path)?
+    } else {
+        std::fs::read_to_string(path).map_err(|e| e.to_string())?
+    };
+    serde_json::from_str(&contents).map_err(|e| e.to_string())
+}
+
+/// Convert a (binary or XML) property list to JSON with `plutil`
+#[cfg(target_os = "macos")]
+fn plist_to_json(path: &Path) -> Result<String, String> {
+    let output = std::process::Command::new("plutil")
+        .args(["-convert", "json", "-o", "-"])
+        .arg(path)
+        .output()
+        .map_err(|e| format!("Failed to run plutil: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    String::from_utf8(output.stdout).map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn plist_to_json(_path: &Path) -> Result<String, String> {
+    Err("Property lists are only read on macOS".to_string())
+}
+
+/// Merge `overlay` into `base`: objects field by field, anything else replaced
+fn merge(base: &mut Value, overlay: &Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                merge(base.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
+
+/// Non-object values under `value` with their dotted paths
+fn leaf_paths<'a>(value: &'a Value, path: String, leaves: &mut Vec<(String, &'a Value)>) {
+    match value {
+        Value::Object(fields) => {
+            for (field, value) in fields {
+                leaf_paths(value, format!("{}.{}", path, field), leaves);
+            }
+        }
+        value => leaves.push((path, value)),
+    }
+}
+
+/// Equality that tolerates `f32` fields serialized with a rounding error (0.3 vs 0.30000001)
+fn same_value(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => (a - b).abs() <= f64::from(f32::EPSILON) * a.abs().max(1.0),
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TelemetryConfig;
+
+    fn managed() -> ManagedConfig {
+        ManagedConfig::from_value(json!({
+            "appConfig": { "postProcessParams": { "temperature": 0.3 } },
+            "telemetryConfig": { "telemetryEnabled": false },
+            "disableCloudProviders": true,
+        }))
+    }
+
+    #[test]
+    fn test_managed_values_override_and_lock() {
+        let managed = managed();
+
+        let stored = json!({ "activeProvider": "open_ai", "minSpeechDurationMs": 500 });
+        let app_config = managed.apply("appConfig", Some(stored)).unwrap();
+        assert_eq!(app_config["activeProvider"], "local");
+        assert_eq!(app_config["minSpeechDurationMs"], 500);
+        assert_eq!(app_config["postProcessParams"]["temperature"], 0.3);
+        assert_eq!(managed.apply("uiPrefs", None), None);
+
+        // Re-saving the merged entry (with an f32 rounding error) is fine
+        let mut resaved = app_config.clone();
+        resaved["postProcessParams"]["temperature"] = json!(0.3f32 as f64);
+        resaved["minSpeechDurationMs"] = json!(800);
+        assert!(managed.check_unchanged("appConfig", &resaved).is_ok());
+
+        let mut changed = app_config;
+        changed["activeProvider"] = json!("azure_open_ai");
+        assert!(managed
+            .check_unchanged("appConfig", &changed)
+            .unwrap_err()
+            .contains("appConfig.activeProvider"));
+    }
+
+    #[test]
+    fn test_user_values_survive_the_policy() {
+        let managed = managed();
+        let stored = json!({
+            "activeProvider": "open_ai",
+            "postProcessEnabled": true,
+            "minSpeechDurationMs": 500,
+        });
+
+        // Save while managed: the user changes an unmanaged field of the merged entry
+        let mut saved = managed.apply("appConfig", Some(stored.clone())).unwrap();
+        saved["minSpeechDurationMs"] = json!(800);
+        assert!(managed.check_unchanged("appConfig", &saved).is_ok());
+        managed.restore_user_values("appConfig", &mut saved, Some(&stored));
+
+        // Policy lifted: the user's values come back, with the new change kept
+        let unmanaged = ManagedConfig::default()
+            .apply("appConfig", Some(saved))
+            .unwrap();
+        assert_eq!(unmanaged["activeProvider"], "open_ai");
+        assert_eq!(unmanaged["postProcessEnabled"], true);
+        assert_eq!(unmanaged["minSpeechDurationMs"], 800);
+        // Never stored by the user: keeps the managed value
+        assert_eq!(unmanaged["fallbackProviders"], json!([]));
+    }
+
+    #[test]
+    fn test_apply_over_a_new_entry() {
+        let managed = managed();
+        let telemetry = TelemetryConfig {
+            device_id: "device".to_string(),
+            telemetry_enabled: true,
+            last_session_start: None,
+        };
+
+        // The partial managed object alone isn't a valid entry (no device id)
+        let partial = managed.apply("telemetryConfig", None).unwrap();
+        assert!(serde_json::from_value::<TelemetryConfig>(partial).is_err());
+
+        let applied = managed.apply_over("telemetryConfig", telemetry.clone());
+        assert_eq!(applied.device_id, "device");
+        assert!(!applied.telemetry_enabled);
+        // Accepted by the store
+        let value = serde_json::to_value(&applied).unwrap();
+        assert!(managed.check_unchanged("telemetryConfig", &value).is_ok());
+
+        assert_eq!(
+            ManagedConfig::default().apply_over("telemetryConfig", telemetry.clone()),
+            telemetry
+        );
+    }
+
+    #[test]
+    fn test_locked_settings() {
+        let mut locked = managed().locked_settings();
+        locked.sort();
+        assert_eq!(
+            locked,
+            vec![
+                "appConfig.activeProvider",
+                "appConfig.fallbackProviders",
+                "appConfig.postProcessEnabled",
+                "appConfig.postProcessParams.temperature",
+                "telemetryConfig.telemetryEnabled",
+            ]
+        );
+        assert!(ManagedConfig::default().locked_settings().is_empty());
+    }
+}
@@ -0,0 +1,422 @@
+//! Meeting mode: long-form recording transcribed in the background, with a summary.
+//!
+//! A worker thread transcribes the microphone (and system audio, when a loopback device
+//! is configured) in segments cut at pauses (see [`crate::segmented_transcription`]).
+//! On stop, the transcript is summarized through the post-processing model and the
+//! notes can be saved as Markdown with [`MeetingRecorder::save_notes`]. Segments are
+//! labeled Speaker 1/Speaker 2 when speaker labels are enabled (see [`Diarizer`]).
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use dictara_core::transcription::TranscriptSegment;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::audit::AuditLog;
+use crate::clients::{complete_with_openai, validate_transcription_config};
+use crate::config::{self, ConfigKey, ConfigStore, PostProcessParams};
+use crate::diarization::Diarizer;
+use crate::network;
+use crate::segmented_transcription::{transcribe_microphone, Segmentation};
+
+/// Segments of up to 30s of speech, cut at pauses between sentences
+const SEGMENTATION: Segmentation = Segmentation {
+    max_segment_ms: 30_000,
+    pause: Duration::from_millis(1_500),
+};
+
+/// A whole meeting transcript takes much longer to summarize than a dictation
+const SUMMARY_TIMEOUT_SECS: u64 = 120;
+
+const SUMMARY_PROMPT: &str = "You summarize meeting transcripts. Write a concise summary \
+in Markdown with the sections \"Key points\", \"Decisions\" and \"Action items\" (with owners \
+when mentioned), as bullet lists. Write in the language of the transcript. Output only the \
+summary.";
+
+/// Meeting mode state change event - single event stream for a meeting
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum MeetingStateChanged {
+    /// Recording has started
+    #[serde(rename = "started")]
+    Started,
+    /// A segment of the meeting has been transcribed
+    #[serde(rename = "segment")]
+    Segment {
+        text: String,
+        /// Offset from the start of the meeting, in milliseconds
+        #[serde(rename = "startMs")]
+        start_ms: u64,
+        #[serde(rename = "endMs")]
+        end_ms: u64,
+    },
+    /// Recording has stopped, the transcript is being summarized
+    #[serde(rename = "summarizing")]
+    Summarizing,
+    /// The notes are ready to be saved
+    #[serde(rename = "finished")]
+    Finished {
+        segments: u32,
+        #[serde(rename = "hasSummary")]
+        has_summary: bool,
+        /// Distinct speakers labeled, 0 without speaker labels
+        speakers: u32,
+    },
+    /// Recording stopped on an error, the segments transcribed so far are kept
+    #[serde(rename = "error")]
+    Error { error: String },
+}
+
+/// Transcript and summary of the last meeting
+struct MeetingNotes {
+    started_at: DateTime<Local>,
+    duration: Duration,
+    segments: Vec<TranscriptSegment>,
+    /// Speaker number of each segment, empty without speaker labels
+    speakers: Vec<Option<u32>>,
+    summary: Option<String>,
+}
+
+/// The running meeting, if any, and the notes of the last one
+pub struct MeetingRecorder {
+    is_running: Arc<AtomicBool>,
+    /// Dropped to stop the worker thread
+    stop_tx: Mutex<Option<Sender<()>>>,
+    notes: Arc<Mutex<Option<MeetingNotes>>>,
+}
+
+impl MeetingRecorder {
+    pub fn new() -> Self {
+        Self {
+            is_running: Arc::new(AtomicBool::new(false)),
+            stop_tx: Mutex::new(None),
+            notes: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.is_running.load(Ordering::SeqCst)
+    }
+
+    /// Start recording a meeting, discarding the notes of the previous one
+    ///
+    /// Progress is reported via [`MeetingStateChanged`] events.
+    pub fn start(&self, app: &AppHandle) -> Result<(), String> {
+        if self
+            .is_running
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err("A meeting is already being recorded".to_string());
+        }
+
+        let transcriber = match validate_transcription_config(app, "meeting mode") {
+            Ok(transcriber) => transcriber,
+            Err(e) => {
+                self.is_running.store(false, Ordering::SeqCst);
+                return Err(e);
+            }
+        };
+
+        if let Ok(mut notes) = self.notes.lock() {
+            *notes = Some(MeetingNotes {
+                started_at: Local::now(),
+                duration: Duration::ZERO,
+                segments: Vec::new(),
+                speakers: Vec::new(),
+                summary: None,
+            });
+        }
+
+        let (stop_tx, stop_rx) = mpsc::channel();
+        let is_running = self.is_running.clone();
+        let notes = self.notes.clone();
+        let app_for_worker = app.clone();
+        let spawned = std::thread::Builder::new()
+            .name("meeting".to_string())
+            .spawn(move || {
+                info!("Meeting recording started");
+                let started_at = Instant::now();
+                let mut diarizer = Diarizer::from_app(&app_for_worker);
+                let result = transcribe_microphone(
+                    &app_for_worker,
+                    &transcriber,
+                    SEGMENTATION,
+                    &work_dir(&app_for_worker),
+                    &stop_rx,
+                    |segment, samples| {
+                        if let Some(diarizer) = diarizer.as_mut() {
+                            diarizer.add_segment(samples);
+                        }
+                        emit(
+                            &app_for_worker,
+                            MeetingStateChanged::Segment {
+                                text: segment.text.clone(),
+                                start_ms: segment.start_ms,
+                                end_ms: segment.end_ms,
+                            },
+                        );
+                        if let Ok(mut notes) = notes.lock() {
+                            if let Some(notes) = notes.as_mut() {
+                                notes.segments.push(segment);
+                            }
+                        }
+                    },
+                );
+                info!("Meeting recording stopped");
+
+                if let Err(e) = result {
+                    error!("Meeting recording failed: {}", e);
+                    emit(&app_for_worker, MeetingStateChanged::Error { error: e });
+                }
+                let speakers = diarizer
+                    .map(|diarizer| diarizer.speakers())
+                    .unwrap_or_default();
+                finish(&app_for_worker, &notes, started_at.elapsed(), speakers);
+                is_running.store(false, Ordering::SeqCst);
+            });
+
+        if let Err(e) = spawned {
+            self.is_running.store(false, Ordering::SeqCst);
+            return Err(format!("Failed to start meeting mode: {}", e));
+        }
+        if let Ok(mut slot) = self.stop_tx.lock() {
+            *slot = Some(stop_tx);
+        }
+        emit(app, MeetingStateChanged::Started);
+        Ok(())
+    }
+
+    /// Stop recording; the last segment is transcribed and the summary generated in
+    /// the background, see [`MeetingStateChanged::Finished`]
+    pub fn stop(&self) {
+        if let Ok(mut stop_tx) = self.stop_tx.lock() {
+            stop_tx.take();
+        }
+    }
+
+    /// Write the notes of the last meeting as Markdown to `path`
+    pub fn save_notes(&self, path: &Path) -> Result<(), String> {
+        if self.is_running() {
+            return Err("Stop the meeting before saving its notes".to_string());
+        }
+        let markdown = {
+            let notes = self
+                .notes
+                .lock()
+                .map_err(|_| "Meeting notes are unavailable".to_string())?;
+            match notes.as_ref() {
+                Some(notes) if !notes.segments.is_empty() => render_markdown(notes),
+                _ => return Err("No meeting transcript to save".to_string()),
+            }
+        };
+        std::fs::write(path, markdown)
+            .map_err(|e| format!("Failed to save meeting notes to {}: {}", path.display(), e))
+    }
+}
+
+impl MeetingNotes {
+    /// Speaker of the segment at `index`, `None` without speaker labels
+    fn speaker(&self, index: usize) -> Option<u32> {
+        self.speakers.get(index).copied().flatten()
+    }
+}
+
+impl Default for MeetingRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Summarize the transcript (when post-processing is enabled) and report the notes ready
+fn finish(
+    app: &AppHandle,
+    notes: &Mutex<Option<MeetingNotes>>,
+    duration: Duration,
+    speakers: Vec<Option<u32>>,
+) {
+    let transcript = match notes.lock() {
+        Ok(mut notes) => match notes.as_mut() {
+            Some(notes) => {
+                notes.duration = duration;
+                notes.speakers = speakers;
+                transcript_text(notes)
+            }
+            None => return,
+        },
+        Err(_) => return,
+    };
+
+    let summary = if transcript.is_empty() {
+        None
+    } else {
+        summarize(app, &transcript)
+    };
+
+    let (segments, has_summary, speakers) = match notes.lock() {
+        Ok(mut notes) => match notes.as_mut() {
+            Some(notes) => {
+                notes.summary = summary;
+                let mut speakers: Vec<u32> = notes.speakers.iter().flatten().copied().collect();
+                speakers.sort_unstable();
+                speakers.dedup();
+                (
+                    notes.segments.len() as u32,
+                    notes.summary.is_some(),
+                    speakers.len() as u32,
+                )
+            }
+            None => return,
+        },
+        Err(_) => return,
+    };
+    emit(
+        app,
+        MeetingStateChanged::Finished {
+            segments,
+            has_summary,
+            speakers,
+        },
+    );
+}
+
+/// Summary of the transcript by the post-processing model, `None` when post-processing
+/// is disabled or the request failed
+fn summarize(app: &AppHandle, transcript: &str) -> Option<String> {
+    let config_store = app.state::<config::Config>();
+    let app_config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    if !app_config.post_process_enabled {
+        return None;
+    }
+
+    emit(app, MeetingStateChanged::Summarizing);
+    let audit = app.try_state::<Arc<AuditLog>>();
+    let summary = complete_with_openai(
+        transcript,
+        &app_config.post_process_model,
+        SUMMARY_PROMPT,
+        PostProcessParams {
+            timeout_secs: SUMMARY_TIMEOUT_SECS,
+            ..app_config.post_process_params
+        },
+        &network::http_settings(config_store.inner()),
+        audit.as_ref().map(|audit| audit.inner().as_ref()),
+    );
+
+    match summary {
+        Ok(summary) => Some(summary),
+        Err(e) => {
+            warn!("Meeting summary failed ({e}), saving the transcript only");
+            None
+        }
+    }
+}
+
+/// Transcript sent to the summary model, one segment per line
+fn transcript_text(notes: &MeetingNotes) -> String {
+    notes
+        .segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| match notes.speaker(index) {
+            Some(speaker) => format!("Speaker {}: {}", speaker, segment.text),
+            None => segment.text.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Markdown notes: title with date and duration, summary, timestamped transcript
+fn render_markdown(notes: &MeetingNotes) -> String {
+    let mut markdown = format!(
+        "# Meeting notes – {}\n\nDuration: {}\n",
+        notes.started_at.format("%Y-%m-%d %H:%M"),
+        format_timestamp(notes.duration.as_millis() as u64)
+    );
+    if let Some(summary) = &notes.summary {
+        markdown.push_str("\n## Summary\n\n");
+        markdown.push_str(summary);
+        markdown.push('\n');
+    }
+    markdown.push_str("\n## Transcript\n\n");
+    for (index, segment) in notes.segments.iter().enumerate() {
+        let timestamp = format_timestamp(segment.start_ms);
+        markdown.push_str(&match notes.speaker(index) {
+            Some(speaker) => format!(
+                "**[{}] Speaker {}:** {}\n\n",
+                timestamp, speaker, segment.text
+            ),
+            None => format!("**[{}]** {}\n\n", timestamp, segment.text),
+        });
+    }
+    markdown
+}
+
+/// `hh:mm:ss`
+fn format_timestamp(ms: u64) -> String {
+    let seconds = ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Scratch directory for the meeting recording and segments
+fn work_dir(app: &AppHandle) -> PathBuf {
+    match app.path().app_cache_dir() {
+        Ok(cache_dir) => cache_dir.join("meeting"),
+        Err(_) => std::env::temp_dir().join("dictara").join("meeting"),
+    }
+}
+
+fn emit(app: &AppHandle, event: MeetingStateChanged) {
+    if let Err(e) = event.emit(app) {
+        error!("Failed to emit meeting event: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_markdown() {
+        let notes = MeetingNotes {
+            started_at: Local.with_ymd_and_hms(2026, 3, 2, 14, 30, 0).unwrap(),
+            duration: Duration::from_secs(3_725),
+            segments: vec![
+                TranscriptSegment {
+                    start_ms: 0,
+                    end_ms: 4_000,
+                    text: "Let's get started.".to_string(),
+                },
+                TranscriptSegment {
+                    start_ms: 3_661_000,
+                    end_ms: 3_665_000,
+                    text: "Thanks, everyone.".to_string(),
+                },
+            ],
+            speakers: vec![Some(1), Some(2)],
+            summary: Some("- Kickoff".to_string()),
+        };
+
+        assert_eq!(
+            render_markdown(&notes),
+            "# Meeting notes – 2026-03-02 14:30\n\nDuration: 01:02:05\n\
+             \n## Summary\n\n- Kickoff\n\
+             \n## Transcript\n\n\
+             **[00:00:00] Speaker 1:** Let's get started.\n\n\
+             **[01:01:01] Speaker 2:** Thanks, everyone.\n\n"
+        );
+    }
+}
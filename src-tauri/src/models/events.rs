@@ -8,6 +8,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::resources::InferencePhase;
+
 /// Model download state change event - single event stream for all download state transitions
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
 #[serde(tag = "state", rename_all = "camelCase")]
@@ -23,11 +25,16 @@ pub enum ModelDownloadStateChanged {
         total_bytes: u64,
         percentage: f64,
     },
-    /// Download complete, verifying checksum
+    /// Checksums are being verified, files not hashed during their download are read again
     #[serde(rename = "verifying")]
     Verifying {
         #[serde(rename = "modelName")]
         model_name: String,
+        #[serde(rename = "verifiedBytes")]
+        verified_bytes: u64,
+        #[serde(rename = "totalBytes")]
+        total_bytes: u64,
+        percentage: f64,
     },
     /// Download completed successfully
     #[serde(rename = "complete")]
@@ -68,3 +75,63 @@ pub enum ModelLoadingStateChanged {
         error: String,
     },
 }
+
+/// Model migration state change event - progress of moving single-file models
+/// into the per-model directory structure at startup
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum ModelMigrationStateChanged {
+    /// Migration has started
+    #[serde(rename = "started")]
+    Started {
+        #[serde(rename = "totalModels")]
+        total_models: u32,
+    },
+    /// A model was migrated and verified
+    #[serde(rename = "progress")]
+    Progress {
+        #[serde(rename = "modelName")]
+        model_name: String,
+        #[serde(rename = "migratedModels")]
+        migrated_models: u32,
+        #[serde(rename = "totalModels")]
+        total_models: u32,
+    },
+    /// A model couldn't be migrated, it stays in the old location
+    #[serde(rename = "error")]
+    Error {
+        #[serde(rename = "modelName")]
+        model_name: String,
+        error: String,
+    },
+    /// Migration finished
+    #[serde(rename = "complete")]
+    Complete {
+        #[serde(rename = "migratedModels")]
+        migrated_models: u32,
+        #[serde(rename = "failedModels")]
+        failed_models: Vec<String>,
+    },
+}
+
+/// Resource usage sampled about every second while a local model loads or transcribes
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceResourceUsage {
+    pub phase: InferencePhase,
+    pub model_name: String,
+    /// Process resident memory
+    pub rss_bytes: Option<u64>,
+    /// Swap used by the system
+    pub swap_used_bytes: Option<u64>,
+    /// System wide GPU utilization in percent (macOS only)
+    pub gpu_utilization: Option<u8>,
+}
+
+/// Progress of a local transcription, lets the popup show a progress bar for long audio
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionProgress {
+    pub model_name: String,
+    pub percentage: f64,
+}
@@ -1,13 +1,18 @@
-use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
+use dictara_core::models::get_model_catalog;
+use dictara_core::transcription::{
+    LocalClient, LocalModelOptions, ProgressCallback, Transcription, TranscriptionError,
+};
 use log::{debug, error, info, warn};
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 use tauri_specta::Event;
 
-use super::catalog::get_model_catalog;
-use super::events::ModelLoadingStateChanged;
-use super::local_client::LocalClient;
+use super::events::{ModelLoadingStateChanged, TranscriptionProgress};
+use super::location::ModelsLocation;
+use super::resources::{InferencePhase, ResourceReport, ResourceSampler};
+use crate::worker_pool::{WorkPriority, WorkerPool};
 
 /// Loaded model state
 struct LoadedModel {
@@ -22,61 +27,85 @@ struct LoadedModel {
 pub struct ModelLoader {
     current_model: Arc<Mutex<Option<LoadedModel>>>,
     loading: Arc<Mutex<Option<String>>>,
-    models_dir: PathBuf,
+    location: ModelsLocation,
+    /// For resource usage events
+    app: AppHandle,
+    /// Resource usage of the last model load and transcription
+    resource_report: Mutex<ResourceReport>,
+    /// Cancel token of the latest transcription job, set to abort it
+    transcription_cancel: Mutex<Arc<AtomicBool>>,
+    /// Loads models with interactive priority
+    pool: Arc<WorkerPool>,
 }
 
 impl ModelLoader {
     /// Create a new ModelLoader.
-    pub fn new(models_dir: PathBuf) -> Self {
+    pub fn new(location: ModelsLocation, app: AppHandle) -> Self {
         Self {
+            pool: app.state::<Arc<WorkerPool>>().inner().clone(),
             current_model: Arc::new(Mutex::new(None)),
             loading: Arc::new(Mutex::new(None)),
-            models_dir,
+            location,
+            app,
+            resource_report: Mutex::new(ResourceReport::default()),
+            transcription_cancel: Mutex::new(Arc::new(AtomicBool::new(false))),
         }
     }
 
-    /// Resolve model path with backward compatibility.
+    /// Cancel token of a new transcription job, for `transcribe_with_model`.
     ///
-    /// New structure: models_dir/{model_name}/{files}
-    /// Old structure: models_dir/{filename} (single) or models_dir/{filename}/ (multi)
-    ///
-    /// Returns the path that LocalClient::new expects:
-    /// - For single-file (Whisper): path to the .bin file
-    /// - For multi-file (Parakeet): path to the directory containing model files
-    fn resolve_model_path(&self, entry: &super::catalog::ModelCatalogEntry) -> PathBuf {
-        // New structure
-        let new_dir = self.models_dir.join(&entry.name);
-
-        if entry.files.len() == 1 {
-            // Single-file model (Whisper)
-            let new_file = new_dir.join(&entry.files[0].filename);
-            if new_file.exists() {
-                return new_file; // Return file path for LocalClient::new
-            }
+    /// Created when the job is queued, so a cancel while it waits for a worker
+    /// applies to it, and a cancel of an earlier job doesn't.
+    pub fn new_transcription_job(&self) -> Arc<AtomicBool> {
+        let cancel = Arc::new(AtomicBool::new(false));
+        *self.transcription_cancel.lock().unwrap() = cancel.clone();
+        cancel
+    }
 
-            // Fall back to old structure
-            let old_file = self.models_dir.join(&entry.filename);
-            if old_file.exists() {
-                return old_file;
-            }
+    /// Abort the latest transcription job, queued or in progress.
+    ///
+    /// `transcribe_with_model` then fails with `TranscriptionError::Cancelled`.
+    pub fn cancel_transcription(&self) {
+        info!("Cancelling local transcription");
+        self.transcription_cancel
+            .lock()
+            .unwrap()
+            .store(true, Ordering::Relaxed);
+    }
 
-            // Return new path for error messages
-            new_file
-        } else {
-            // Multi-file model (Parakeet)
-            if new_dir.is_dir() {
-                return new_dir; // Return directory path for LocalClient::new
-            }
+    /// Resource usage of the last model load and transcription
+    pub fn resource_report(&self) -> ResourceReport {
+        self.resource_report.lock().unwrap().clone()
+    }
 
-            // Fall back to old structure (might be same as new for current Parakeet models)
-            let old_dir = self.models_dir.join(&entry.filename);
-            if old_dir.is_dir() {
-                return old_dir;
-            }
+    /// Run `f` while sampling resource usage, recording the summary in the report
+    fn with_resource_sampling<T>(
+        &self,
+        phase: InferencePhase,
+        model_name: &str,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        let sampler = ResourceSampler::start(self.app.clone(), phase, model_name);
+        let result = f();
+        self.finish_resource_sampling(phase, sampler);
+        result
+    }
 
-            // Return new path for error messages
-            new_dir
-        }
+    /// Stop sampling and record the summary in the report
+    fn finish_resource_sampling(&self, phase: InferencePhase, sampler: Option<ResourceSampler>) {
+        let Some(summary) = sampler.and_then(ResourceSampler::stop) else {
+            return;
+        };
+        info!(
+            "{:?} of '{}' took {}ms, peak RSS {:?} bytes, max GPU {:?}%, swap growth {:?} bytes",
+            phase,
+            summary.model_name,
+            summary.duration_ms,
+            summary.peak_rss_bytes,
+            summary.max_gpu_utilization,
+            summary.swap_growth_bytes
+        );
+        self.resource_report.lock().unwrap().record(phase, summary);
     }
 
     /// Load a model into memory.
@@ -124,7 +153,7 @@ impl ModelLoader {
             .find(|e| e.name == model_name)
             .ok_or_else(|| format!("Model '{}' not found in catalog", model_name))?;
 
-        let model_path = self.resolve_model_path(&entry);
+        let model_path = self.location.store().client_path(&entry);
         debug!("Model path resolved: {:?}", model_path);
 
         // Verify model exists
@@ -144,11 +173,21 @@ impl ModelLoader {
         // Unload current model first
         self.unload_model();
 
-        // Load model in blocking task (model loading is CPU-intensive)
+        // Load model on the worker pool (model loading is CPU-intensive), ahead of
+        // background work so the next dictation doesn't wait for it
         let model_name_clone = model_name.to_string();
         let model_type = entry.model_type;
-        let result =
-            tokio::task::spawn_blocking(move || LocalClient::new(&model_path, model_type)).await;
+        let languages = entry.languages.clone();
+        let sampler =
+            ResourceSampler::start(self.app.clone(), InferencePhase::ModelLoad, model_name);
+        let result = self
+            .pool
+            .spawn(WorkPriority::Interactive, move || {
+                LocalClient::new(&model_path, model_type)
+                    .map(|client| client.with_supported_languages(languages))
+            })
+            .await;
+        self.finish_resource_sampling(InferencePhase::ModelLoad, sampler);
 
         // Clear loading state
         {
@@ -187,7 +226,7 @@ impl ModelLoader {
                 Err(error)
             }
             Err(e) => {
-                let error = format!("Task panicked while loading model: {}", e);
+                let error = format!("Model loading failed: {}", e);
                 error!("{}", error);
                 let _ = ModelLoadingStateChanged::Error {
                     model_name: model_name.to_string(),
@@ -281,15 +320,27 @@ impl ModelLoader {
     /// # Arguments
     /// * `model_name` - The expected model name to use for transcription
     /// * `audio_path` - Path to the audio file
+    /// * `language` - Language code to pin, `None` to auto-detect
+    /// * `options` - Transcription options of the model
+    /// * `cancel` - Token of the job, from `new_transcription_job`
     ///
     /// # Returns
-    /// * `Ok(String)` - Transcribed text
-    /// * `Err(String)` - Error if loading failed or model mismatch detected
+    /// * `Ok(Transcription)` - Transcribed text and detected language
+    /// * `Err(TranscriptionError)` - Loading failed, model mismatch detected or
+    ///   the transcription was cancelled (`cancel_transcription`)
     pub fn transcribe_with_model(
         &self,
         model_name: &str,
         audio_path: &std::path::Path,
-    ) -> Result<String, String> {
+        language: Option<&str>,
+        options: &LocalModelOptions,
+        cancel: Arc<AtomicBool>,
+    ) -> Result<Transcription, TranscriptionError> {
+        // Cancelled while waiting for a worker
+        if cancel.load(Ordering::Relaxed) {
+            return Err(TranscriptionError::Cancelled);
+        }
+
         // First, check if the correct model is loaded (without holding lock long)
         let needs_load = {
             let current = self.current_model.lock().unwrap();
@@ -304,7 +355,13 @@ impl ModelLoader {
                 "Model '{}' not loaded, loading on-demand for transcription",
                 model_name
             );
-            self.load_model_sync(model_name)?;
+            self.load_model_sync(model_name)
+                .map_err(TranscriptionError::LocalTranscriptionFailed)?;
+
+            // Cancelled while the model was loading
+            if cancel.load(Ordering::Relaxed) {
+                return Err(TranscriptionError::Cancelled);
+            }
         }
 
         // Now transcribe - recheck that model is still correct to handle race conditions
@@ -312,10 +369,16 @@ impl ModelLoader {
         match current.as_mut() {
             Some(model) if model.name == model_name => {
                 debug!("Transcribing with verified model '{}'", model_name);
-                model
-                    .client
-                    .transcribe_file(audio_path)
-                    .map_err(|e| e.to_string())
+                let on_progress = self.progress_callback(model_name);
+                model.client.set_options(options.clone());
+                self.with_resource_sampling(InferencePhase::Transcription, model_name, || {
+                    model.client.transcribe_file_with_progress(
+                        audio_path,
+                        language,
+                        Some(on_progress),
+                        Some(cancel),
+                    )
+                })
             }
             Some(model) => {
                 // Race condition: another thread loaded a different model
@@ -323,19 +386,34 @@ impl ModelLoader {
                     "Model mismatch detected: expected '{}' but '{}' is loaded",
                     model_name, model.name
                 );
-                Err(format!(
+                Err(TranscriptionError::LocalTranscriptionFailed(format!(
                     "Model changed during transcription: expected '{}' but '{}' is loaded. Please try again.",
                     model_name, model.name
-                ))
+                )))
             }
             None => {
                 // Race condition: model was unloaded after we loaded it
                 warn!("Model was unloaded during transcription setup");
-                Err("Model was unloaded during transcription. Please try again.".to_string())
+                Err(TranscriptionError::LocalTranscriptionFailed(
+                    "Model was unloaded during transcription. Please try again.".to_string(),
+                ))
             }
         }
     }
 
+    /// Emit `TranscriptionProgress` events for a transcription with `model_name`
+    fn progress_callback(&self, model_name: &str) -> ProgressCallback {
+        let app = self.app.clone();
+        let model_name = model_name.to_string();
+        Arc::new(move |percentage: u8| {
+            let _ = TranscriptionProgress {
+                model_name: model_name.clone(),
+                percentage: percentage as f64,
+            }
+            .emit(&app);
+        })
+    }
+
     /// Load a model synchronously (blocking).
     ///
     /// Used for on-demand loading during transcription when the model
@@ -377,7 +455,7 @@ impl ModelLoader {
             .find(|e| e.name == model_name)
             .ok_or_else(|| format!("Model '{}' not found in catalog", model_name))?;
 
-        let model_path = self.resolve_model_path(&entry);
+        let model_path = self.location.store().client_path(&entry);
         debug!("Model path resolved (sync): {:?}", model_path);
 
         // Verify model exists
@@ -393,7 +471,10 @@ impl ModelLoader {
         // Load model (blocking)
         let model_type = entry.model_type;
         debug!("Starting model initialization ({:?})...", model_type);
-        let result = LocalClient::new(&model_path, model_type);
+        let result = self.with_resource_sampling(InferencePhase::ModelLoad, model_name, || {
+            LocalClient::new(&model_path, model_type)
+                .map(|client| client.with_supported_languages(entry.languages.clone()))
+        });
 
         // Clear loading state
         {
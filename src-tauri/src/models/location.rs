@@ -0,0 +1,280 @@
+//! Location of the downloaded models.
+//!
+//! Models live in `<app data>/models` unless the user moved them elsewhere (an
+//! external drive for a small internal disk) with `set_models_directory`. The
+//! directory is read from the config on every use, so the `ModelManager` and the
+//! `ModelLoader` follow a relocation at once.
+//!
+//! A relocation moves every entry of the models directory: renamed when the target
+//! is on the same volume, otherwise copied and removed once everything is copied.
+//! A failure puts back what was already moved.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use dictara_core::models::ModelStore;
+use log::{info, warn};
+
+use crate::config::{self, ConfigKey, ConfigStore};
+
+/// Resolves the models directory from the config
+#[derive(Clone)]
+pub struct ModelsLocation {
+    config: config::Config,
+    /// `<app data>/models`, used unless another directory is configured
+    default_dir: PathBuf,
+}
+
+impl ModelsLocation {
+    pub fn new(config: config::Config, default_dir: PathBuf) -> Self {
+        Self {
+            config,
+            default_dir,
+        }
+    }
+
+    /// Current models directory
+    pub fn models_dir(&self) -> PathBuf {
+        self.config
+            .get(&ConfigKey::LOCAL_MODEL)
+            .unwrap_or_default()
+            .models_dir
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.default_dir.clone())
+    }
+
+    /// Layout of the models in the current directory
+    pub fn store(&self) -> ModelStore {
+        ModelStore::new(self.models_dir())
+    }
+
+    /// The current models directory, creating the default one if needed.
+    ///
+    /// A configured directory is never created: missing, its drive is disconnected
+    /// and models would end up on the internal disk under the mount point.
+    pub fn ensure_available(&self) -> Result<PathBuf, String> {
+        let models_dir = self.models_dir();
+        if models_dir == self.default_dir {
+            fs::create_dir_all(&models_dir)
+                .map_err(|e| format!("Failed to create models directory: {}", e))?;
+        } else if !models_dir.is_dir() {
+            return Err(format!(
+                "Models directory {} is not available, is its drive connected?",
+                models_dir.display()
+            ));
+        }
+        Ok(models_dir)
+    }
+
+    /// Store `models_dir` as the models directory (`None` when it is the default one)
+    fn save(&self, models_dir: &Path) -> Result<(), String> {
+        let mut local_config = self.config.get(&ConfigKey::LOCAL_MODEL).unwrap_or_default();
+        local_config.models_dir = if models_dir == self.default_dir {
+            None
+        } else {
+            Some(models_dir.to_string_lossy().to_string())
+        };
+        self.config.set(&ConfigKey::LOCAL_MODEL, local_config)
+    }
+
+    /// Move the models to `target` and make it the models directory. Blocking.
+    pub fn relocate(&self, target: &Path) -> Result<PathBuf, String> {
+        let current = self.models_dir();
+        check_target(&current, target)?;
+
+        fs::create_dir_all(target)
+            .map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+        info!("Moving models from {:?} to {:?}", current, target);
+        let moved = move_entries(&current, target)?;
+
+        if let Err(e) = self.save(target) {
+            undo(&moved);
+            return Err(e);
+        }
+        remove_sources(&moved);
+
+        // Leave no empty directory behind on an external drive
+        if current != self.default_dir {
+            let _ = fs::remove_dir(&current);
+        }
+
+        info!("Models moved to {:?} ({} entries)", target, moved.len());
+        Ok(target.to_path_buf())
+    }
+}
+
+/// Check that `target` can receive the models of `current`
+fn check_target(current: &Path, target: &Path) -> Result<(), String> {
+    if !target.is_absolute() {
+        return Err("The models directory must be an absolute path".to_string());
+    }
+
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let (current, target_path) = (canonical(current), canonical(target));
+    if target_path == current {
+        return Err("Models are already stored there".to_string());
+    }
+    if target_path.starts_with(&current) || current.starts_with(&target_path) {
+        return Err("The models directory can't contain or be inside the current one".to_string());
+    }
+
+    if target.exists() {
+        let mut entries =
+            fs::read_dir(target).map_err(|e| format!("Can't open {}: {}", target.display(), e))?;
+        if entries.next().is_some() {
+            return Err(format!("{} is not empty", target.display()));
+        }
+    }
+    Ok(())
+}
+
+/// An entry of the models directory moved to the new one
+struct MovedEntry {
+    source: PathBuf,
+    destination: PathBuf,
+    /// Copied to another volume, the source is still there
+    copied: bool,
+}
+
+/// Move every entry of `from` into `to`, all or nothing
+fn move_entries(from: &Path, to: &Path) -> Result<Vec<MovedEntry>, String> {
+    let entries = match fs::read_dir(from) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("Can't open {}: {}", from.display(), e)),
+    };
+
+    let mut moved = vec![];
+    for entry in entries {
+        let result = entry.and_then(|entry| {
+            let source = entry.path();
+            let destination = to.join(entry.file_name());
+            let copied = fs::rename(&source, &destination).is_err();
+            if copied {
+                // Another volume (or a file in use), copy instead
+                copy_all(&source, &destination).inspect_err(|_| {
+                    let _ = remove_all(&destination);
+                })?;
+            }
+            moved.push(MovedEntry {
+                source,
+                destination,
+                copied,
+            });
+            Ok(())
+        });
+
+        if let Err(e) = result {
+            undo(&moved);
+            return Err(format!("Failed to move models: {}", e));
+        }
+    }
+    Ok(moved)
+}
+
+/// Put the moved entries back where they were
+fn undo(moved: &[MovedEntry]) {
+    for entry in moved.iter().rev() {
+        let result = if entry.copied {
+            remove_all(&entry.destination)
+        } else {
+            fs::rename(&entry.destination, &entry.source)
+        };
+        if let Err(e) = result {
+            warn!("Failed to move back {:?}: {}", entry.destination, e);
+        }
+    }
+}
+
+/// Remove the originals of the entries copied to another volume
+fn remove_sources(moved: &[MovedEntry]) {
+    for entry in moved.iter().filter(|entry| entry.copied) {
+        if let Err(e) = remove_all(&entry.source) {
+            warn!(
+                "Failed to remove {:?} after copying it: {}",
+                entry.source, e
+            );
+        }
+    }
+}
+
+fn copy_all(source: &Path, destination: &Path) -> io::Result<()> {
+    if source.is_dir() {
+        fs::create_dir_all(destination)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_all(&entry.path(), &destination.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(source, destination).map(|_| ())
+    }
+}
+
+fn remove_all(path: &Path) -> io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "dictara-models-location-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_check_target() {
+        let current = temp_dir("current");
+        let target = temp_dir("target");
+        fs::create_dir_all(&current).unwrap();
+
+        assert!(check_target(&current, Path::new("models")).is_err());
+        assert!(check_target(&current, &current).is_err());
+        assert!(check_target(&current, &current.join("nested")).is_err());
+        assert!(check_target(&current, &target).is_ok());
+
+        fs::create_dir_all(&target).unwrap();
+        assert!(check_target(&current, &target).is_ok());
+        fs::write(target.join("other.txt"), b"other").unwrap();
+        assert!(check_target(&current, &target).is_err());
+
+        fs::remove_dir_all(&current).unwrap();
+        fs::remove_dir_all(&target).unwrap();
+    }
+
+    #[test]
+    fn test_move_entries_and_undo() {
+        let from = temp_dir("from");
+        let to = temp_dir("to");
+        fs::create_dir_all(from.join("whisper-small")).unwrap();
+        fs::write(from.join("whisper-small").join("ggml-small.bin"), b"ggml").unwrap();
+        fs::write(from.join("ggml-base.bin.partial"), b"gg").unwrap();
+        fs::create_dir_all(&to).unwrap();
+
+        let moved = move_entries(&from, &to).unwrap();
+        assert_eq!(moved.len(), 2);
+        assert!(to.join("whisper-small").join("ggml-small.bin").exists());
+        assert!(to.join("ggml-base.bin.partial").exists());
+        assert!(!from.join("whisper-small").exists());
+
+        undo(&moved);
+        assert!(from.join("whisper-small").join("ggml-small.bin").exists());
+        assert!(from.join("ggml-base.bin.partial").exists());
+        assert_eq!(fs::read_dir(&to).unwrap().count(), 0);
+
+        fs::remove_dir_all(&from).unwrap();
+        fs::remove_dir_all(&to).unwrap();
+    }
+}
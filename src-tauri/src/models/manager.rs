@@ -1,95 +1,127 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
 use futures_util::StreamExt;
 use log::{debug, error, info, warn};
-use sha2::{Digest, Sha256};
+use secrecy::ExposeSecret;
 use tauri::{AppHandle, Manager};
 use tauri_specta::Event;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::AsyncWriteExt;
 use tokio_util::sync::CancellationToken;
 
-use super::catalog::{get_model_catalog, ModelCatalogEntry};
-use super::events::ModelDownloadStateChanged;
+use dictara_core::http;
+use dictara_core::models::{
+    get_model_catalog, speaker_model_file, CoreMlEncoder, ModelCatalogEntry, ModelStore,
+    SPEAKER_MODEL_NAME, SPEAKER_MODEL_SIZE_BYTES,
+};
+use dictara_core::transcription::{coreml_encoder_path, coreml_supported};
+
+use crate::config::{self, ConfigKey, ConfigStore};
+use crate::network;
+use crate::worker_pool::{WorkPriority, WorkerPool};
+
+use super::events::{ModelDownloadStateChanged, ModelMigrationStateChanged};
 use super::loader::ModelLoader;
+use super::location::ModelsLocation;
+use super::migration::{self, MigrationState, MigrationStatus};
+use super::mirror;
 use super::status::ModelStatus;
-use super::ModelInfo;
+use super::verification::{verify_files, FileCheck, FileStatus, StreamingHash};
+use super::{ModelDiskUsage, ModelInfo, ModelVerification, ModelsDiskUsage};
 
 /// Manages model downloads, storage, and status tracking.
 /// Does NOT handle model loading into memory - that's ModelLoader's job.
 pub struct ModelManager {
-    models_dir: PathBuf,
+    location: ModelsLocation,
     /// In-memory state tracking which models are currently downloading
     downloading: Arc<Mutex<HashMap<String, bool>>>,
     /// Cancellation tokens for active downloads
     cancel_tokens: Arc<Mutex<HashMap<String, CancellationToken>>>,
+    /// Set when the app quits: cancelled downloads keep their .partial files to resume
+    stopping: AtomicBool,
+    /// Progress of the startup migration to the unified directory structure
+    migration_status: Mutex<MigrationStatus>,
+    /// Set while the models are moved to another directory, downloads wait for it
+    relocating: AtomicBool,
+    /// Runs the blocking file work (moving, extracting, hashing) in the background
+    pool: Arc<WorkerPool>,
 }
 
 impl ModelManager {
     /// Create a new ModelManager.
     ///
     /// # Arguments
-    /// * `app` - Tauri app handle to get the app data directory
+    /// * `app` - Tauri app handle to get the app data directory, the config and the
+    ///   worker pool
     pub fn new(app: &AppHandle) -> Result<Self, String> {
         let app_data_dir = app
             .path()
             .app_data_dir()
             .map_err(|e| format!("Failed to get app data dir: {}", e))?;
 
-        let models_dir = app_data_dir.join("models");
-
-        // Create models directory if it doesn't exist
-        std::fs::create_dir_all(&models_dir)
-            .map_err(|e| format!("Failed to create models directory: {}", e))?;
+        let location = ModelsLocation::new(
+            app.state::<config::Config>().inner().clone(),
+            app_data_dir.join("models"),
+        );
 
-        info!("Models directory: {:?}", models_dir);
+        // Create the default models directory if it doesn't exist
+        match location.ensure_available() {
+            Ok(models_dir) => info!("Models directory: {:?}", models_dir),
+            Err(e) => warn!("{}", e),
+        }
 
         Ok(Self {
-            models_dir,
+            location,
             downloading: Arc::new(Mutex::new(HashMap::new())),
             cancel_tokens: Arc::new(Mutex::new(HashMap::new())),
+            stopping: AtomicBool::new(false),
+            migration_status: Mutex::new(MigrationStatus::default()),
+            relocating: AtomicBool::new(false),
+            pool: app.state::<Arc<WorkerPool>>().inner().clone(),
         })
     }
 
-    /// Get the models directory path
-    pub fn models_dir(&self) -> &PathBuf {
-        &self.models_dir
+    /// Where the models are stored, shared with the ModelLoader
+    pub fn location(&self) -> &ModelsLocation {
+        &self.location
     }
 
-    /// Get the path to a model, trying new structure first then falling back to old.
-    ///
-    /// New structure: models_dir/{model.name}/{file.filename}
-    /// Old structure: models_dir/{model.filename} (single-file) or models_dir/{model.filename}/ (multi-file)
-    ///
-    /// Returns the directory path (not individual file path).
-    fn get_model_path_with_fallback(&self, entry: &ModelCatalogEntry) -> PathBuf {
-        let new_dir = self.models_dir.join(&entry.name);
-
-        // Check if new structure exists
-        if new_dir.exists() {
-            return new_dir;
-        }
+    /// Layout of the downloaded models in the current models directory
+    pub fn store(&self) -> ModelStore {
+        self.location.store()
+    }
 
-        // Fall back to old structure
-        if entry.files.len() == 1 {
-            // Old single-file: models_dir/ggml-small.bin (file, not directory)
-            // But we need to return directory for consistency, check if old file exists
-            let old_file_path = self.models_dir.join(&entry.filename);
-            if old_file_path.exists() {
-                // Return the parent directory (models_dir) - caller will handle file vs dir
-                return self.models_dir.clone();
+    /// Move all downloaded models to `target` and download new ones there.
+    ///
+    /// Refused during downloads and the startup migration. Returns the new models
+    /// directory.
+    pub async fn relocate(&self, target: PathBuf) -> Result<PathBuf, String> {
+        {
+            let downloading = self.downloading.lock().unwrap();
+            if downloading.values().any(|&active| active) {
+                return Err("Models can't be moved during a download".to_string());
             }
-        } else {
-            // Old multi-file: models_dir/parakeet-tdt-0.6b-v3-int8/ (directory)
-            let old_dir = self.models_dir.join(&entry.filename);
-            if old_dir.exists() {
-                return old_dir;
+            if self.migration_status().state == MigrationState::Running {
+                return Err("Models can't be moved while they are migrated".to_string());
+            }
+            if self.relocating.swap(true, Ordering::SeqCst) {
+                return Err("Models are already being moved".to_string());
             }
         }
 
-        // Neither exists, return new structure path (for new downloads)
-        new_dir
+        // Copying gigabytes to another drive is blocking I/O
+        let location = self.location.clone();
+        let result = self
+            .pool
+            .spawn(WorkPriority::Background, move || location.relocate(&target))
+            .await
+            .map_err(|e| format!("Relocation task failed: {}", e))
+            .and_then(|result| result);
+
+        self.relocating.store(false, Ordering::SeqCst);
+        result
     }
 
     /// Migrate old single-file models to new unified directory structure.
@@ -98,48 +130,91 @@ impl ModelManager {
     /// - Old: models_dir/ggml-small.bin
     /// - New: models_dir/whisper-small/ggml-small.bin
     ///
+    /// Each file is copied and verified before the original is removed (see the
+    /// `migration` module). Progress is reported via `ModelMigrationStateChanged`
+    /// and `get_migration_status`. A model that fails keeps its old location.
+    ///
     /// Multi-file models (Parakeet) don't need migration as they already use directory structure.
-    pub async fn migrate_old_models(&self) -> Result<(), String> {
+    pub async fn migrate_old_models(&self, app: &AppHandle) -> Result<(), String> {
         info!("Checking for models to migrate to new structure...");
 
-        for entry in get_model_catalog() {
-            // Only single-file models need migration
-            if entry.files.len() == 1 {
-                let old_file_path = self.models_dir.join(&entry.filename);
-                let new_dir = self.models_dir.join(&entry.name);
-                let new_file_path = new_dir.join(&entry.files[0].filename);
-
-                // Migrate if old exists and new doesn't
-                if old_file_path.exists() && !new_file_path.exists() {
-                    tokio::fs::create_dir_all(&new_dir).await.map_err(|e| {
-                        format!("Failed to create directory during migration: {}", e)
-                    })?;
-
-                    tokio::fs::rename(&old_file_path, &new_file_path)
-                        .await
-                        .map_err(|e| format!("Failed to migrate {}: {}", entry.name, e))?;
-
-                    info!(
-                        "Migrated {} to new structure: {:?}",
-                        entry.name, new_file_path
-                    );
+        let store = self.store();
+        migration::recover_interrupted(store.models_dir()).await?;
+
+        let pending = migration::pending_migrations(store.models_dir());
+        let total_models = pending.len() as u32;
+        self.update_migration_status(|status| {
+            *status = MigrationStatus {
+                state: MigrationState::Running,
+                total_models,
+                ..Default::default()
+            };
+        });
+
+        if !pending.is_empty() {
+            info!("Migrating {} model(s)", total_models);
+            let _ = ModelMigrationStateChanged::Started { total_models }.emit(app);
+        }
 
-                    // Also migrate .partial file if exists
-                    let old_partial = self.models_dir.join(format!("{}.partial", entry.filename));
-                    if old_partial.exists() {
-                        let new_partial =
-                            new_dir.join(format!("{}.partial", entry.files[0].filename));
-                        let _ = tokio::fs::rename(&old_partial, &new_partial).await;
-                        debug!("Migrated partial file for {}", entry.name);
+        for item in &pending {
+            self.update_migration_status(|status| {
+                status.current_model = Some(item.model_name.clone());
+            });
+
+            match migration::migrate(store.models_dir(), item, &self.pool).await {
+                Ok(()) => {
+                    let migrated_models = self.update_migration_status(|status| {
+                        status.migrated_models += 1;
+                        status.migrated_models
+                    });
+                    let _ = ModelMigrationStateChanged::Progress {
+                        model_name: item.model_name.clone(),
+                        migrated_models,
+                        total_models,
+                    }
+                    .emit(app);
+                }
+                Err(e) => {
+                    error!("Failed to migrate {}: {}", item.model_name, e);
+                    self.update_migration_status(|status| {
+                        status.failed_models.push(item.model_name.clone());
+                    });
+                    let _ = ModelMigrationStateChanged::Error {
+                        model_name: item.model_name.clone(),
+                        error: e,
                     }
+                    .emit(app);
                 }
             }
         }
 
+        let status = self.update_migration_status(|status| {
+            status.state = MigrationState::Complete;
+            status.current_model = None;
+            status.clone()
+        });
+
+        if !pending.is_empty() {
+            let _ = ModelMigrationStateChanged::Complete {
+                migrated_models: status.migrated_models,
+                failed_models: status.failed_models,
+            }
+            .emit(app);
+        }
+
         info!("Model migration check complete");
         Ok(())
     }
 
+    /// Current progress of the model migration
+    pub fn migration_status(&self) -> MigrationStatus {
+        self.migration_status.lock().unwrap().clone()
+    }
+
+    fn update_migration_status<T>(&self, update: impl FnOnce(&mut MigrationStatus) -> T) -> T {
+        update(&mut self.migration_status.lock().unwrap())
+    }
+
     /// Get all models with their current status.
     pub fn get_all_models(&self, loader: &ModelLoader) -> Vec<ModelInfo> {
         get_model_catalog()
@@ -162,46 +237,13 @@ impl ModelManager {
             .copied()
             .unwrap_or(false);
 
-        // Check if downloaded in new structure: models_dir/{name}/{files}
-        let new_dir = self.models_dir.join(&entry.name);
-        let is_downloaded_new = new_dir.is_dir()
-            && entry
-                .files
-                .iter()
-                .all(|f| new_dir.join(&f.filename).exists());
+        let store = self.store();
+        let is_downloaded = store.is_downloaded(entry);
+        // Partial download progress (aggregate across all files)
+        let downloaded_bytes = store.partial_bytes(entry);
 
-        // Check if downloaded in old structure
-        let is_downloaded_old = if entry.files.len() == 1 {
-            // Old single-file: models_dir/{filename}
-            self.models_dir.join(&entry.filename).exists()
-        } else {
-            // Old multi-file: models_dir/{filename}/ (same as new for current Parakeet models)
-            let old_dir = self.models_dir.join(&entry.filename);
-            old_dir.is_dir()
-                && entry
-                    .files
-                    .iter()
-                    .all(|f| old_dir.join(&f.filename).exists())
-        };
-
-        let is_downloaded = is_downloaded_new || is_downloaded_old;
-
-        // Track partial download progress (aggregate across all files)
-        let downloaded_bytes = {
-            let mut total = 0u64;
-            for file in &entry.files {
-                // Check new location first
-                let new_partial = new_dir.join(format!("{}.partial", file.filename));
-                if new_partial.exists() {
-                    total += new_partial.metadata().map(|m| m.len()).unwrap_or(0);
-                } else if entry.files.len() == 1 {
-                    // Check old location for single-file models
-                    let old_partial = self.models_dir.join(format!("{}.partial", entry.filename));
-                    total += old_partial.metadata().map(|m| m.len()).unwrap_or(0);
-                }
-            }
-            total
-        };
+        let is_coreml_downloaded = entry.coreml_encoder.is_some()
+            && coreml_encoder_path(&store.model_file_path(entry)).exists();
 
         ModelStatus {
             is_downloaded,
@@ -209,6 +251,7 @@ impl ModelManager {
             is_loaded: loader.is_model_loaded(&entry.name),
             is_loading: loader.is_model_loading(&entry.name),
             downloaded_bytes,
+            is_coreml_downloaded,
         }
     }
 
@@ -223,33 +266,38 @@ impl ModelManager {
             .find(|e| e.name == model_name)
             .ok_or_else(|| format!("Model '{}' not found in catalog", model_name))?;
 
-        // Check if already downloaded (check both new and old structures)
-        let new_dir = self.models_dir.join(&entry.name);
-        let is_downloaded_new = new_dir.is_dir()
-            && entry
-                .files
-                .iter()
-                .all(|f| new_dir.join(&f.filename).exists());
-
-        let is_downloaded_old = if entry.files.len() == 1 {
-            self.models_dir.join(&entry.filename).exists()
-        } else {
-            let old_dir = self.models_dir.join(&entry.filename);
-            old_dir.is_dir()
-                && entry
-                    .files
-                    .iter()
-                    .all(|f| old_dir.join(&f.filename).exists())
-        };
-
-        if is_downloaded_new || is_downloaded_old {
+        if self.store().is_downloaded(&entry) {
             info!("Model '{}' already downloaded", model_name);
             return Ok(());
         }
 
-        // Check if already downloading
+        let cancel_token = self.begin_download(model_name)?;
+
+        info!("Starting download of model '{}'", model_name);
+
+        // Use unified download for all models (always use new structure)
+        let result = self
+            .download_model_unified(&entry, &app, &cancel_token)
+            .await;
+
+        self.end_download(model_name);
+        Self::emit_download_result(model_name, &result, &app);
+
+        result
+    }
+
+    /// Mark a model as downloading and register its cancellation token.
+    /// Fails if the model is already being downloaded, or the models directory is
+    /// unavailable or being moved.
+    fn begin_download(&self, model_name: &str) -> Result<CancellationToken, String> {
+        self.location.ensure_available()?;
+
+        // Check if already downloading, then mark as downloading
         {
-            let downloading = self.downloading.lock().unwrap();
+            let mut downloading = self.downloading.lock().unwrap();
+            if self.relocating.load(Ordering::SeqCst) {
+                return Err("Models are being moved to another directory".to_string());
+            }
             if downloading.get(model_name).copied().unwrap_or(false) {
                 warn!("Model '{}' is already being downloaded", model_name);
                 return Err(format!(
@@ -257,11 +305,6 @@ impl ModelManager {
                     model_name
                 ));
             }
-        }
-
-        // Mark as downloading
-        {
-            let mut downloading = self.downloading.lock().unwrap();
             downloading.insert(model_name.to_string(), true);
         }
 
@@ -272,14 +315,11 @@ impl ModelManager {
             tokens.insert(model_name.to_string(), cancel_token.clone());
         }
 
-        info!("Starting download of model '{}'", model_name);
-
-        // Use unified download for all models (always use new structure)
-        let result = self
-            .download_model_unified(&entry, &app, &cancel_token)
-            .await;
+        Ok(cancel_token)
+    }
 
-        // Clear downloading state
+    /// Clear the downloading state set by `begin_download`
+    fn end_download(&self, model_name: &str) {
         {
             let mut downloading = self.downloading.lock().unwrap();
             downloading.remove(model_name);
@@ -288,14 +328,17 @@ impl ModelManager {
             let mut tokens = self.cancel_tokens.lock().unwrap();
             tokens.remove(model_name);
         }
+    }
 
-        match &result {
+    /// Emit the final download state (complete or error) to the frontend
+    fn emit_download_result(model_name: &str, result: &Result<(), String>, app: &AppHandle) {
+        match result {
             Ok(()) => {
                 info!("Download complete: {}", model_name);
                 let _ = ModelDownloadStateChanged::Complete {
                     model_name: model_name.to_string(),
                 }
-                .emit(&app);
+                .emit(app);
             }
             Err(e) => {
                 error!("Download failed: {}", e);
@@ -303,16 +346,15 @@ impl ModelManager {
                     model_name: model_name.to_string(),
                     error: e.to_string(),
                 }
-                .emit(&app);
+                .emit(app);
             }
         }
-
-        result
     }
 
     /// Unified download implementation for all models (single-file and multi-file).
     ///
-    /// Downloads all files in parallel, aggregates progress, and verifies checksums in parallel.
+    /// Downloads all files in parallel, aggregates progress, and verifies checksums (hashed
+    /// during the download, read again only when that wasn't possible).
     /// Uses new structure: models_dir/{model_name}/{files}
     async fn download_model_unified(
         &self,
@@ -320,7 +362,7 @@ impl ModelManager {
         app: &AppHandle,
         cancel_token: &CancellationToken,
     ) -> Result<(), String> {
-        let model_dir = self.models_dir.join(&entry.name);
+        let model_dir = self.store().models_dir().join(&entry.name);
 
         // Create model directory
         tokio::fs::create_dir_all(&model_dir)
@@ -382,58 +424,45 @@ impl ModelManager {
         }
 
         // Wait for all downloads to complete
+        let mut streamed_hashes = vec![];
         for (idx, handle) in download_handles.into_iter().enumerate() {
-            handle
-                .await
-                .map_err(|e| format!("Download task {} failed: {}", idx, e))??;
+            streamed_hashes.push(
+                handle
+                    .await
+                    .map_err(|e| format!("Download task {} failed: {}", idx, e))??,
+            );
         }
 
         // Check for cancellation before verification
         if cancel_token.is_cancelled() {
-            // Clean up partial downloads
-            let _ = tokio::fs::remove_dir_all(&model_dir).await;
+            // Clean up partial downloads, unless they are resumed on the next launch
+            if !self.stopping.load(Ordering::SeqCst) {
+                let _ = tokio::fs::remove_dir_all(&model_dir).await;
+            }
             return Err("Download cancelled".to_string());
         }
 
         info!("All files downloaded, verifying checksums...");
 
-        // Emit verifying state
-        let _ = ModelDownloadStateChanged::Verifying {
-            model_name: entry.name.clone(),
-        }
-        .emit(app);
-
-        // Phase 2: Verify checksums in parallel
-        let mut verify_handles = vec![];
-
-        for file in &entry.files {
-            let file = file.clone();
-            let model_dir = model_dir.clone();
-
-            let handle = tokio::spawn(async move {
-                let partial_path = model_dir.join(format!("{}.partial", file.filename));
-
-                // Skip verification for "TBD" checksums (temporary during development)
-                if file.sha256 != "TBD" {
-                    Self::verify_checksum(&partial_path, &file.sha256).await
-                } else {
-                    Ok(())
-                }
-            });
-
-            verify_handles.push(handle);
-        }
-
-        // Wait for all verifications
-        for (idx, handle) in verify_handles.into_iter().enumerate() {
-            if let Err(e) = handle
-                .await
-                .map_err(|e| format!("Verification task {} failed: {}", idx, e))?
-            {
-                // Clean up on verification failure
-                let _ = tokio::fs::remove_dir_all(&model_dir).await;
-                return Err(format!("Checksum verification failed: {}", e));
-            }
+        // Phase 2: Verify checksums
+        let checks = entry
+            .files
+            .iter()
+            .zip(streamed_hashes)
+            .map(|(file, streamed_sha256)| FileCheck {
+                path: model_dir.join(format!("{}.partial", file.filename)),
+                expected_sha256: file.sha256.clone(),
+                streamed_sha256,
+            })
+            .collect();
+        if let Err(e) = verify_files(app, &entry.name, checks)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, String>>()
+        {
+            // Clean up on verification failure
+            let _ = tokio::fs::remove_dir_all(&model_dir).await;
+            return Err(format!("Checksum verification failed: {}", e));
         }
 
         info!("All checksums verified, finalizing...");
@@ -459,6 +488,8 @@ impl ModelManager {
     /// Download a single file with progress tracking and resume support.
     ///
     /// Updates shared progress state and emits progress events aggregated across all files.
+    /// Returns the SHA-256 of the whole file, hashed while downloading (`None` if the
+    /// resumed part couldn't be read).
     #[allow(clippy::too_many_arguments)]
     async fn download_file_with_progress(
         url: &str,
@@ -470,11 +501,26 @@ impl ModelManager {
         total_size: u64,
         model_name: &str,
         app: &AppHandle,
-    ) -> Result<(), String> {
-        let client = reqwest::Client::new();
+    ) -> Result<Option<String>, String> {
+        let config_store = app.state::<config::Config>();
+        let mirror = config_store
+            .get(&ConfigKey::LOCAL_MODEL)
+            .unwrap_or_default()
+            .download_mirror;
+        let url = mirror::resolve_url(url, &mirror);
+
+        let http_settings = network::http_settings(config_store.inner());
+        let client = http_settings
+            .configure(reqwest::Client::builder(), &url)
+            .map_err(|e| e.to_string())?
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
         // Build request with Range header for resume
-        let mut request = client.get(url);
+        let mut request = client.get(&url);
+        if let Some(token) = mirror::token_for(&url, &mirror) {
+            request = request.bearer_auth(token.expose_secret());
+        }
         if resume_from > 0 {
             request = request.header("Range", format!("bytes={}-", resume_from));
         }
@@ -482,7 +528,7 @@ impl ModelManager {
         let response = request
             .send()
             .await
-            .map_err(|e| format!("Failed to start download: {}", e))?;
+            .map_err(|e| format!("Failed to start download: {}", http::describe_error(&e)))?;
 
         // Check response status
         if !response.status().is_success()
@@ -494,6 +540,10 @@ impl ModelManager {
             ));
         }
 
+        // Hash the part downloaded before, the rest is hashed as it arrives
+        let pool = app.state::<Arc<WorkerPool>>();
+        let mut hash = StreamingHash::resume(&pool, dest_path, resume_from).await;
+
         // Open file for appending (if resuming) or create new
         let file = if resume_from > 0 {
             tokio::fs::OpenOptions::new()
@@ -518,14 +568,18 @@ impl ModelManager {
 
         while let Some(chunk_result) = stream.next().await {
             if cancel_token.is_cancelled() {
+                // Write out what was received, the .partial file may be resumed
+                let _ = file.flush().await;
                 return Err("Download cancelled".to_string());
             }
 
-            let chunk = chunk_result.map_err(|e| format!("Download error: {}", e))?;
+            let chunk = chunk_result
+                .map_err(|e| format!("Download error: {}", http::describe_error(&e)))?;
 
             file.write_all(&chunk)
                 .await
                 .map_err(|e| format!("Failed to write chunk: {}", e))?;
+            hash.update(&chunk);
 
             downloaded_this_session += chunk.len() as u64;
 
@@ -566,43 +620,499 @@ impl ModelManager {
             .await
             .map_err(|e| format!("Failed to flush file: {}", e))?;
 
-        Ok(())
+        Ok(hash.finalize())
     }
 
-    /// Verify SHA-256 checksum of a downloaded file.
-    async fn verify_checksum(file_path: &Path, expected_hash: &str) -> Result<(), String> {
-        let mut file = tokio::fs::File::open(file_path)
-            .await
-            .map_err(|e| format!("Failed to open file for verification: {}", e))?;
+    /// Re-hash the installed files of a model against the catalog checksums.
+    ///
+    /// Missing files count as corrupted, files without a pinned checksum as
+    /// unverifiable (not intact). With `repair`, only the corrupted files are
+    /// downloaded again (with the usual download events) and intact files are kept.
+    pub async fn verify_model(
+        &self,
+        model_name: &str,
+        repair: bool,
+        loader: &ModelLoader,
+        app: AppHandle,
+    ) -> Result<ModelVerification, String> {
+        let entry = get_model_catalog()
+            .into_iter()
+            .find(|e| e.name == model_name)
+            .ok_or_else(|| format!("Model '{}' not found in catalog", model_name))?;
+
+        if self
+            .downloading
+            .lock()
+            .unwrap()
+            .get(model_name)
+            .copied()
+            .unwrap_or(false)
+        {
+            return Err(format!(
+                "Model '{}' is currently being downloaded",
+                model_name
+            ));
+        }
+
+        let model_dir = self.store().model_dir(&entry);
+        if !entry
+            .files
+            .iter()
+            .any(|f| model_dir.join(&f.filename).exists())
+        {
+            return Err(format!("Model '{}' is not downloaded", model_name));
+        }
+
+        info!("Verifying model '{}' in {:?}", model_name, model_dir);
+
+        // Hash all files in parallel
+        let checks = entry
+            .files
+            .iter()
+            .map(|file| FileCheck {
+                path: model_dir.join(&file.filename),
+                expected_sha256: file.sha256.clone(),
+                streamed_sha256: None,
+            })
+            .collect();
+        let results = verify_files(&app, model_name, checks).await;
+
+        let mut verified_files = 0;
+        let mut unverifiable_files = vec![];
+        let mut corrupted_files = vec![];
+        for (file, result) in entry.files.iter().zip(results) {
+            let result = if model_dir.join(&file.filename).exists() {
+                result
+            } else {
+                Err("File is missing".to_string())
+            };
+            match result {
+                Ok(FileStatus::Verified) => verified_files += 1,
+                Ok(FileStatus::Unverifiable) => unverifiable_files.push(file.filename.clone()),
+                Err(e) => {
+                    warn!(
+                        "Model '{}': {} is corrupted: {}",
+                        model_name, file.filename, e
+                    );
+                    corrupted_files.push(file.filename.clone());
+                }
+            }
+        }
+
+        info!(
+            "Model '{}' verified: {} intact, {} unverifiable, {} corrupted",
+            model_name,
+            verified_files,
+            unverifiable_files.len(),
+            corrupted_files.len()
+        );
+
+        let repaired = repair && !corrupted_files.is_empty();
+        if repaired {
+            // The loaded model may come from the corrupted files
+            if loader.is_model_loaded(model_name) {
+                loader.unload_model();
+            }
+
+            let cancel_token = self.begin_download(model_name)?;
+            let result = self
+                .repair_model_files(&entry, &model_dir, &corrupted_files, &app, &cancel_token)
+                .await;
+            self.end_download(model_name);
+            Self::emit_download_result(model_name, &result, &app);
+            result?;
+        }
+
+        Ok(ModelVerification {
+            model_name: model_name.to_string(),
+            verified_files,
+            unverifiable_files,
+            corrupted_files,
+            repaired,
+        })
+    }
+
+    /// Download `corrupted_files` of a model again, replacing them once verified.
+    ///
+    /// Intact files are counted as downloaded so progress covers the whole model.
+    async fn repair_model_files(
+        &self,
+        entry: &ModelCatalogEntry,
+        model_dir: &Path,
+        corrupted_files: &[String],
+        app: &AppHandle,
+        cancel_token: &CancellationToken,
+    ) -> Result<(), String> {
+        let progress = Arc::new(Mutex::new(vec![0u64; entry.files.len()]));
+        let to_repair: Vec<_> = entry
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| corrupted_files.contains(&file.filename))
+            .collect();
 
-        let mut hasher = Sha256::new();
-        let mut buffer = vec![0u8; 1024 * 1024]; // 1MB buffer
+        for (idx, file) in entry.files.iter().enumerate() {
+            if !corrupted_files.contains(&file.filename) {
+                progress.lock().unwrap()[idx] = model_dir
+                    .join(&file.filename)
+                    .metadata()
+                    .map(|m| m.len())
+                    .unwrap_or(0);
+            }
+        }
+
+        info!(
+            "Re-downloading {} file(s) of model '{}'",
+            to_repair.len(),
+            entry.name
+        );
 
-        loop {
-            let bytes_read = file
-                .read(&mut buffer)
+        // Phase 1: Download corrupted files in parallel, from scratch
+        let mut download_handles = vec![];
+
+        for (idx, file) in &to_repair {
+            let idx = *idx;
+            let file = (*file).clone();
+            let model_dir = model_dir.to_path_buf();
+            let cancel_token = cancel_token.clone();
+            let progress = progress.clone();
+            let app = app.clone();
+            let model_name = entry.name.clone();
+            let total_size = entry.size_bytes;
+
+            let handle = tokio::spawn(async move {
+                let partial_path = model_dir.join(format!("{}.partial", file.filename));
+                Self::download_file_with_progress(
+                    &file.url,
+                    &partial_path,
+                    0,
+                    &cancel_token,
+                    idx,
+                    &progress,
+                    total_size,
+                    &model_name,
+                    &app,
+                )
                 .await
-                .map_err(|e| format!("Failed to read file for verification: {}", e))?;
+            });
 
-            if bytes_read == 0 {
-                break;
+            download_handles.push(handle);
+        }
+
+        let mut download_result = Ok(());
+        let mut streamed_hashes = vec![];
+        for (idx, handle) in download_handles.into_iter().enumerate() {
+            let result = handle
+                .await
+                .map_err(|e| format!("Download task {} failed: {}", idx, e))
+                .and_then(|result| result);
+            match result {
+                Ok(streamed_sha256) => streamed_hashes.push(streamed_sha256),
+                Err(e) => {
+                    if download_result.is_ok() {
+                        download_result = Err(e);
+                    }
+                }
             }
+        }
 
-            hasher.update(&buffer[..bytes_read]);
+        if download_result.is_ok() && cancel_token.is_cancelled() {
+            download_result = Err("Download cancelled".to_string());
         }
 
-        let computed_hash = format!("{:x}", hasher.finalize());
+        // Phase 2: Verify the new files
+        if download_result.is_ok() {
+            let checks = to_repair
+                .iter()
+                .zip(streamed_hashes)
+                .map(|((_, file), streamed_sha256)| FileCheck {
+                    path: model_dir.join(format!("{}.partial", file.filename)),
+                    expected_sha256: file.sha256.clone(),
+                    streamed_sha256,
+                })
+                .collect();
+            if let Err(e) = verify_files(app, &entry.name, checks)
+                .await
+                .into_iter()
+                .collect::<Result<Vec<_>, String>>()
+            {
+                download_result = Err(format!("Checksum verification failed: {}", e));
+            }
+        }
 
-        if computed_hash != expected_hash {
+        // Keep the intact files, only drop the new partial downloads
+        if let Err(e) = download_result {
+            if self.stopping.load(Ordering::SeqCst) {
+                return Err(e);
+            }
+            for (_, file) in &to_repair {
+                let partial_path = model_dir.join(format!("{}.partial", file.filename));
+                let _ = tokio::fs::remove_file(&partial_path).await;
+            }
+            return Err(e);
+        }
+
+        // Phase 3: Replace the corrupted files
+        for (_, file) in &to_repair {
+            let file_path = model_dir.join(&file.filename);
+            let partial_path = model_dir.join(format!("{}.partial", file.filename));
+
+            tokio::fs::rename(&partial_path, &file_path)
+                .await
+                .map_err(|e| format!("Failed to rename {} to final: {}", file.filename, e))?;
+        }
+
+        info!(
+            "Model '{}' repaired: {} file(s) replaced",
+            entry.name,
+            to_repair.len()
+        );
+
+        Ok(())
+    }
+
+    /// Download and extract the Core ML encoder of a downloaded Whisper model.
+    ///
+    /// whisper.cpp uses it on the next model load. Apple Silicon only.
+    pub async fn download_coreml_encoder(
+        &self,
+        model_name: &str,
+        app: AppHandle,
+    ) -> Result<(), String> {
+        let entry = get_model_catalog()
+            .into_iter()
+            .find(|e| e.name == model_name)
+            .ok_or_else(|| format!("Model '{}' not found in catalog", model_name))?;
+
+        if !coreml_supported() {
+            return Err("Core ML acceleration requires Apple Silicon".to_string());
+        }
+
+        let encoder = entry
+            .coreml_encoder
+            .clone()
+            .ok_or_else(|| format!("Model '{}' has no Core ML encoder", model_name))?;
+        if !encoder.file.is_pinned() {
             return Err(format!(
-                "Hash mismatch: expected {}, got {}",
-                expected_hash, computed_hash
+                "The Core ML encoder of '{}' can't be verified (its checksum isn't pinned), so it isn't downloaded",
+                model_name
             ));
         }
 
+        let model_file = self.store().model_file_path(&entry);
+        if !model_file.exists() {
+            return Err(format!("Model '{}' is not downloaded", model_name));
+        }
+
+        let encoder_dir = coreml_encoder_path(&model_file);
+        if encoder_dir.exists() {
+            info!("Core ML encoder for '{}' already downloaded", model_name);
+            return Ok(());
+        }
+
+        let cancel_token = self.begin_download(model_name)?;
+
+        info!("Downloading Core ML encoder for '{}'", model_name);
+        let result =
+            Self::install_coreml_encoder(&encoder, &encoder_dir, model_name, &app, &cancel_token)
+                .await;
+
+        self.end_download(model_name);
+        Self::emit_download_result(model_name, &result, &app);
+
+        result
+    }
+
+    /// Download, verify and extract a zipped Core ML encoder to `encoder_dir`
+    async fn install_coreml_encoder(
+        encoder: &CoreMlEncoder,
+        encoder_dir: &Path,
+        model_name: &str,
+        app: &AppHandle,
+        cancel_token: &CancellationToken,
+    ) -> Result<(), String> {
+        let parent_dir = encoder_dir
+            .parent()
+            .ok_or("Invalid Core ML encoder path")?
+            .to_path_buf();
+        let zip_path = parent_dir.join(format!("{}.partial", encoder.file.filename));
+
+        // Resume an interrupted download
+        let resume_from = zip_path.metadata().map(|m| m.len()).unwrap_or(0);
+        let progress = Arc::new(Mutex::new(vec![0u64]));
+
+        let streamed_sha256 = Self::download_file_with_progress(
+            &encoder.file.url,
+            &zip_path,
+            resume_from,
+            cancel_token,
+            0,
+            &progress,
+            encoder.size_bytes,
+            model_name,
+            app,
+        )
+        .await?;
+
+        let check = FileCheck {
+            path: zip_path.clone(),
+            expected_sha256: encoder.file.sha256.clone(),
+            streamed_sha256,
+        };
+        if let Err(e) = verify_files(app, model_name, vec![check])
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, String>>()
+        {
+            let _ = tokio::fs::remove_file(&zip_path).await;
+            return Err(format!("Checksum verification failed: {}", e));
+        }
+
+        // Extracting thousands of small files is blocking I/O
+        let archive_path = zip_path.clone();
+        let pool = app.state::<Arc<WorkerPool>>();
+        let result = pool
+            .spawn(WorkPriority::Background, move || -> Result<(), String> {
+                let file = std::fs::File::open(&archive_path)
+                    .map_err(|e| format!("Failed to open Core ML archive: {}", e))?;
+                let mut archive = zip::ZipArchive::new(file)
+                    .map_err(|e| format!("Invalid Core ML archive: {}", e))?;
+                archive
+                    .extract(&parent_dir)
+                    .map_err(|e| format!("Failed to extract Core ML encoder: {}", e))
+            })
+            .await
+            .map_err(|e| format!("Extraction task failed: {}", e))
+            .and_then(|result| result)
+            .and_then(|()| {
+                if encoder_dir.exists() {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Core ML archive doesn't contain {:?}",
+                        encoder_dir.file_name().unwrap_or_default()
+                    ))
+                }
+            });
+
+        let _ = tokio::fs::remove_file(&zip_path).await;
+
+        // A partially extracted encoder would be picked up by whisper.cpp
+        if result.is_err() {
+            let _ = tokio::fs::remove_dir_all(encoder_dir).await;
+        }
+
+        result
+    }
+
+    /// Remove the Core ML encoder of a model, Whisper falls back to Metal
+    pub fn delete_coreml_encoder(
+        &self,
+        model_name: &str,
+        loader: &ModelLoader,
+    ) -> Result<(), String> {
+        let entry = get_model_catalog()
+            .into_iter()
+            .find(|e| e.name == model_name)
+            .ok_or_else(|| format!("Model '{}' not found in catalog", model_name))?;
+
+        let encoder_dir = coreml_encoder_path(&self.store().model_file_path(&entry));
+        if !encoder_dir.exists() {
+            return Ok(());
+        }
+
+        // The loaded model keeps using the encoder until reloaded
+        if loader.is_model_loaded(model_name) {
+            loader.unload_model();
+        }
+
+        std::fs::remove_dir_all(&encoder_dir)
+            .map_err(|e| format!("Failed to delete Core ML encoder: {}", e))?;
+        info!("Deleted Core ML encoder: {:?}", encoder_dir);
+
         Ok(())
     }
 
+    /// Path of the speaker embedding model (meeting diarization)
+    pub fn speaker_model_path(&self) -> PathBuf {
+        self.store().speaker_model_path()
+    }
+
+    /// Download the speaker embedding model, progress is reported like a model download
+    pub async fn download_speaker_model(&self, app: AppHandle) -> Result<(), String> {
+        if !speaker_model_file().is_pinned() {
+            return Err(
+                "The speaker model can't be verified (its checksum isn't pinned), so it isn't downloaded"
+                    .to_string(),
+            );
+        }
+
+        let model_path = self.speaker_model_path();
+        if model_path.exists() {
+            info!("Speaker model already downloaded");
+            return Ok(());
+        }
+
+        let cancel_token = self.begin_download(SPEAKER_MODEL_NAME)?;
+
+        info!("Downloading speaker model");
+        let result = Self::install_speaker_model(&model_path, &app, &cancel_token).await;
+
+        self.end_download(SPEAKER_MODEL_NAME);
+        Self::emit_download_result(SPEAKER_MODEL_NAME, &result, &app);
+
+        result
+    }
+
+    /// Download and verify the speaker embedding model to `model_path`
+    async fn install_speaker_model(
+        model_path: &Path,
+        app: &AppHandle,
+        cancel_token: &CancellationToken,
+    ) -> Result<(), String> {
+        let file = speaker_model_file();
+        let model_dir = model_path.parent().ok_or("Invalid speaker model path")?;
+        tokio::fs::create_dir_all(model_dir)
+            .await
+            .map_err(|e| format!("Failed to create speaker model directory: {}", e))?;
+        let partial_path = model_dir.join(format!("{}.partial", file.filename));
+
+        // Resume an interrupted download
+        let resume_from = partial_path.metadata().map(|m| m.len()).unwrap_or(0);
+        let progress = Arc::new(Mutex::new(vec![0u64]));
+
+        let streamed_sha256 = Self::download_file_with_progress(
+            &file.url,
+            &partial_path,
+            resume_from,
+            cancel_token,
+            0,
+            &progress,
+            SPEAKER_MODEL_SIZE_BYTES,
+            SPEAKER_MODEL_NAME,
+            app,
+        )
+        .await?;
+
+        let check = FileCheck {
+            path: partial_path.clone(),
+            expected_sha256: file.sha256.clone(),
+            streamed_sha256,
+        };
+        if let Err(e) = verify_files(app, SPEAKER_MODEL_NAME, vec![check])
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, String>>()
+        {
+            let _ = tokio::fs::remove_file(&partial_path).await;
+            return Err(format!("Checksum verification failed: {}", e));
+        }
+
+        tokio::fs::rename(&partial_path, model_path)
+            .await
+            .map_err(|e| format!("Failed to rename {} to final: {}", file.filename, e))
+    }
+
     /// Cancel an ongoing download.
     pub fn cancel_download(&self, model_name: &str) -> Result<(), String> {
         let tokens = self.cancel_tokens.lock().unwrap();
@@ -615,9 +1125,52 @@ impl ModelManager {
         }
     }
 
-    /// Delete a downloaded model.
+    /// Cancel all active downloads because the app is quitting.
+    /// Their .partial files are kept so the downloads resume next time.
+    pub fn stop_downloads(&self) {
+        self.stopping.store(true, Ordering::SeqCst);
+        // Called on the way out: a panicked download must not stop the others
+        let tokens = self.cancel_tokens.lock().unwrap_or_else(|e| e.into_inner());
+        for (model_name, token) in tokens.iter() {
+            info!("Stopping download of model '{}'", model_name);
+            token.cancel();
+        }
+    }
+
+    /// Disk space used by the downloaded models, including interrupted downloads
+    pub fn disk_usage(&self) -> ModelsDiskUsage {
+        let store = self.store();
+        let mut models: Vec<ModelDiskUsage> = get_model_catalog()
+            .iter()
+            .map(|entry| ModelDiskUsage {
+                model_name: entry.name.clone(),
+                total_bytes: store.disk_bytes(entry),
+                partial_bytes: store.partial_bytes(entry),
+            })
+            .collect();
+        models.push(ModelDiskUsage {
+            model_name: SPEAKER_MODEL_NAME.to_string(),
+            total_bytes: store.speaker_model_disk_bytes(),
+            partial_bytes: 0,
+        });
+        models.retain(|usage| usage.total_bytes > 0);
+
+        ModelsDiskUsage {
+            models_dir: store.models_dir().to_string_lossy().to_string(),
+            total_bytes: models.iter().map(|usage| usage.total_bytes).sum(),
+            models,
+        }
+    }
+
+    /// Delete a downloaded model, moving its files to the Trash with `to_trash`.
     /// Removes model from both new and old locations if they exist.
-    pub fn delete_model(&self, model_name: &str, loader: &ModelLoader) -> Result<(), String> {
+    /// Interrupted downloads are always deleted.
+    pub fn delete_model(
+        &self,
+        model_name: &str,
+        loader: &ModelLoader,
+        to_trash: bool,
+    ) -> Result<(), String> {
         let entry = get_model_catalog()
             .into_iter()
             .find(|e| e.name == model_name)
@@ -629,11 +1182,12 @@ impl ModelManager {
         }
 
         let mut deleted_something = false;
+        let store = self.store();
 
         // Delete from new structure: models_dir/{name}/
-        let new_dir = self.models_dir.join(&entry.name);
+        let new_dir = store.models_dir().join(&entry.name);
         if new_dir.exists() && new_dir.is_dir() {
-            std::fs::remove_dir_all(&new_dir)
+            remove_model_path(&new_dir, to_trash)
                 .map_err(|e| format!("Failed to delete model directory: {}", e))?;
             info!("Deleted model from new location: {:?}", new_dir);
             deleted_something = true;
@@ -642,24 +1196,32 @@ impl ModelManager {
         // Delete from old structure if it exists
         if entry.files.len() == 1 {
             // Old single-file: models_dir/{filename}
-            let old_file = self.models_dir.join(&entry.filename);
+            let old_file = store.models_dir().join(&entry.filename);
             if old_file.exists() {
-                std::fs::remove_file(&old_file)
+                remove_model_path(&old_file, to_trash)
                     .map_err(|e| format!("Failed to delete old model file: {}", e))?;
                 info!("Deleted model from old location: {:?}", old_file);
                 deleted_something = true;
             }
 
+            // Core ML encoder extracted next to the old file
+            let old_encoder = coreml_encoder_path(&old_file);
+            if old_encoder.exists() {
+                let _ = remove_model_path(&old_encoder, to_trash);
+            }
+
             // Also delete old .partial if exists
-            let old_partial = self.models_dir.join(format!("{}.partial", entry.filename));
+            let old_partial = store
+                .models_dir()
+                .join(format!("{}.partial", entry.filename));
             if old_partial.exists() {
                 let _ = std::fs::remove_file(&old_partial);
             }
         } else {
             // Old multi-file: models_dir/{filename}/ (might be same as new for Parakeet)
-            let old_dir = self.models_dir.join(&entry.filename);
+            let old_dir = store.models_dir().join(&entry.filename);
             if old_dir.exists() && old_dir.is_dir() && old_dir != new_dir {
-                std::fs::remove_dir_all(&old_dir)
+                remove_model_path(&old_dir, to_trash)
                     .map_err(|e| format!("Failed to delete old model directory: {}", e))?;
                 info!("Deleted model from old location: {:?}", old_dir);
                 deleted_something = true;
@@ -684,7 +1246,7 @@ impl ModelManager {
             .find(|e| e.name == model_name)
             .ok_or_else(|| format!("Model '{}' not found in catalog", model_name))?;
 
-        Ok(self.get_model_path_with_fallback(&entry))
+        Ok(self.store().model_dir(&entry))
     }
 
     /// Check if a model is downloaded.
@@ -696,3 +1258,15 @@ impl ModelManager {
         }
     }
 }
+
+/// Delete a model file or directory, or move it to the Trash with `to_trash`
+fn remove_model_path(path: &Path, to_trash: bool) -> Result<(), String> {
+    if to_trash {
+        // A failure is reported rather than falling back to deleting for good
+        trash::delete(path).map_err(|e| format!("Moving to the Trash failed: {}", e))
+    } else if path.is_dir() {
+        std::fs::remove_dir_all(path).map_err(|e| e.to_string())
+    } else {
+        std::fs::remove_file(path).map_err(|e| e.to_string())
+    }
+}
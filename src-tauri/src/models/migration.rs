@@ -0,0 +1,304 @@
+//! Migration of single-file models to the per-model directory layout.
+//!
+//! - Old: models_dir/ggml-small.bin
+//! - New: models_dir/whisper-small/ggml-small.bin
+//!
+//! Each model is copied, the copy is verified and only then the original is removed.
+//! A journal file records the model being migrated, so a migration interrupted by
+//! a crash or quit is cleaned up on the next start instead of leaving a half-copied
+//! multi-GB file behind. Until a model is migrated it keeps loading from the old
+//! location.
+
+use std::path::{Path, PathBuf};
+
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+
+use dictara_core::models::{get_model_catalog, UNPINNED_SHA256};
+
+use super::verification::file_sha256;
+use crate::worker_pool::WorkerPool;
+
+/// Journal of the migration in progress, in the models directory
+const JOURNAL_FILENAME: &str = ".migration-journal.json";
+
+/// Overall state of the model migration
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum MigrationState {
+    /// Migration hasn't run yet this session
+    #[default]
+    NotStarted,
+    Running,
+    /// Finished, possibly with failed models
+    Complete,
+}
+
+/// Progress of the model migration, for the settings UI
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationStatus {
+    pub state: MigrationState,
+    /// Models that need migrating this session
+    pub total_models: u32,
+    pub migrated_models: u32,
+    /// Model being migrated right now
+    pub current_model: Option<String>,
+    /// Models that couldn't be migrated (they keep working from the old location)
+    pub failed_models: Vec<String>,
+}
+
+/// One model file to move to the new layout, also the journal content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct PendingMigration {
+    pub model_name: String,
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    /// Catalog checksum of the file
+    pub sha256: String,
+}
+
+/// Single-file models still stored in the old layout
+pub(super) fn pending_migrations(models_dir: &Path) -> Vec<PendingMigration> {
+    get_model_catalog()
+        .into_iter()
+        .filter(|entry| entry.files.len() == 1)
+        .filter_map(|entry| {
+            let file = &entry.files[0];
+            let source = models_dir.join(&entry.filename);
+            let destination = models_dir.join(&entry.name).join(&file.filename);
+
+            // Migrate if old exists and new doesn't
+            (source.is_file() && !destination.exists()).then(|| PendingMigration {
+                model_name: entry.name.clone(),
+                source,
+                destination,
+                sha256: file.sha256.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Finish or roll back a migration interrupted in a previous session
+pub(super) async fn recover_interrupted(models_dir: &Path) -> Result<(), String> {
+    let journal_path = models_dir.join(JOURNAL_FILENAME);
+    let Ok(json) = tokio::fs::read_to_string(&journal_path).await else {
+        return Ok(());
+    };
+
+    let migration: PendingMigration = match serde_json::from_str(&json) {
+        Ok(migration) => migration,
+        Err(e) => {
+            warn!("Discarding unreadable migration journal: {}", e);
+            return clear_journal(models_dir).await;
+        }
+    };
+
+    warn!(
+        "Recovering interrupted migration of '{}'",
+        migration.model_name
+    );
+
+    // A copy that was still being written or verified is discarded
+    let _ = tokio::fs::remove_file(copy_path(&migration)).await;
+
+    // The verified copy was moved into place, only the original is left to remove
+    if migration.destination.exists() && migration.source.exists() {
+        tokio::fs::remove_file(&migration.source)
+            .await
+            .map_err(|e| format!("Failed to remove migrated {:?}: {}", migration.source, e))?;
+    }
+
+    clear_journal(models_dir).await
+}
+
+/// Copy a model to the new layout, verify the copy and remove the original
+pub(super) async fn migrate(
+    models_dir: &Path,
+    migration: &PendingMigration,
+    pool: &WorkerPool,
+) -> Result<(), String> {
+    write_journal(models_dir, migration).await?;
+
+    let copy = copy_path(migration);
+    if let Err(e) = copy_and_verify(migration, &copy, pool).await {
+        let _ = tokio::fs::remove_file(&copy).await;
+        clear_journal(models_dir).await?;
+        return Err(e);
+    }
+
+    // The model directory only appears once its file is complete, as model
+    // lookup prefers the new structure as soon as the directory exists
+    if let Some(dir) = migration.destination.parent() {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| format!("Failed to create directory during migration: {}", e))?;
+    }
+
+    tokio::fs::rename(&copy, &migration.destination)
+        .await
+        .map_err(|e| format!("Failed to move {:?} into place: {}", copy, e))?;
+
+    tokio::fs::remove_file(&migration.source)
+        .await
+        .map_err(|e| format!("Failed to remove {:?}: {}", migration.source, e))?;
+
+    clear_journal(models_dir).await?;
+
+    // Also migrate .partial file if exists
+    let old_partial = with_suffix(&migration.source, ".partial");
+    if old_partial.exists() {
+        let new_partial = with_suffix(&migration.destination, ".partial");
+        let _ = tokio::fs::rename(&old_partial, &new_partial).await;
+        debug!("Migrated partial file for {}", migration.model_name);
+    }
+
+    info!(
+        "Migrated {} to new structure: {:?}",
+        migration.model_name, migration.destination
+    );
+    Ok(())
+}
+
+async fn copy_and_verify(
+    migration: &PendingMigration,
+    copy: &Path,
+    pool: &WorkerPool,
+) -> Result<(), String> {
+    tokio::fs::copy(&migration.source, copy)
+        .await
+        .map_err(|e| format!("Failed to copy {}: {}", migration.model_name, e))?;
+
+    let copied_hash = file_sha256(pool, copy).await?;
+    // Without a pinned checksum the copy is only compared to the original
+    if copied_hash == migration.sha256 {
+        return Ok(());
+    }
+
+    // The original may already be corrupted, migrate it as is (verify_model can repair it)
+    let source_hash = file_sha256(pool, &migration.source).await?;
+    if copied_hash == source_hash {
+        if migration.sha256 == UNPINNED_SHA256 {
+            return Ok(());
+        }
+        warn!(
+            "{} doesn't match its catalog checksum, migrating it unchanged",
+            migration.model_name
+        );
+        Ok(())
+    } else {
+        Err(format!(
+            "Copy of {} doesn't match the original",
+            migration.model_name
+        ))
+    }
+}
+
+/// Temporary copy until it is verified, next to the original
+fn copy_path(migration: &PendingMigration) -> PathBuf {
+    with_suffix(&migration.source, ".migrating")
+}
+
+/// `path` with `suffix` appended to the file name
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+async fn write_journal(models_dir: &Path, migration: &PendingMigration) -> Result<(), String> {
+    let json = serde_json::to_string(migration).map_err(|e| e.to_string())?;
+
+    // Write then rename, so the journal is never half-written
+    let journal_path = models_dir.join(JOURNAL_FILENAME);
+    let tmp_path = models_dir.join(format!("{}.tmp", JOURNAL_FILENAME));
+    tokio::fs::write(&tmp_path, json)
+        .await
+        .map_err(|e| format!("Failed to write migration journal: {}", e))?;
+    tokio::fs::rename(&tmp_path, &journal_path)
+        .await
+        .map_err(|e| format!("Failed to write migration journal: {}", e))
+}
+
+async fn clear_journal(models_dir: &Path) -> Result<(), String> {
+    match tokio::fs::remove_file(models_dir.join(JOURNAL_FILENAME)).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to clear migration journal: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Empty models directory unique to `name`
+    fn models_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "dictara-migration-test-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn pending(models_dir: &Path) -> PendingMigration {
+        PendingMigration {
+            model_name: "whisper-small".to_string(),
+            source: models_dir.join("ggml-small.bin"),
+            destination: models_dir.join("whisper-small").join("ggml-small.bin"),
+            sha256: UNPINNED_SHA256.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recover_discards_an_unfinished_copy() {
+        let dir = models_dir("copying");
+        let migration = pending(&dir);
+        std::fs::write(&migration.source, b"model").unwrap();
+        std::fs::write(copy_path(&migration), b"mod").unwrap();
+        write_journal(&dir, &migration).await.unwrap();
+
+        recover_interrupted(&dir).await.unwrap();
+
+        // The original keeps working from the old location
+        assert!(migration.source.exists());
+        assert!(!copy_path(&migration).exists());
+        assert!(!migration.destination.exists());
+        assert!(!dir.join(JOURNAL_FILENAME).exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_recover_removes_the_migrated_original() {
+        let dir = models_dir("moved");
+        let migration = pending(&dir);
+        std::fs::write(&migration.source, b"model").unwrap();
+        std::fs::create_dir_all(migration.destination.parent().unwrap()).unwrap();
+        std::fs::write(&migration.destination, b"model").unwrap();
+        write_journal(&dir, &migration).await.unwrap();
+
+        recover_interrupted(&dir).await.unwrap();
+
+        assert!(!migration.source.exists());
+        assert_eq!(std::fs::read(&migration.destination).unwrap(), b"model");
+        assert!(!dir.join(JOURNAL_FILENAME).exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_recover_discards_an_unreadable_journal() {
+        let dir = models_dir("unreadable");
+        std::fs::write(dir.join(JOURNAL_FILENAME), "{").unwrap();
+
+        recover_interrupted(&dir).await.unwrap();
+        assert!(!dir.join(JOURNAL_FILENAME).exists());
+
+        // Nothing to recover without a journal
+        recover_interrupted(&dir).await.unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,278 @@
+//! Download mirrors and authentication of the model catalog.
+//!
+//! Catalog URLs point at `https://huggingface.co/<repository>/resolve/main/<file>`.
+//! [`resolve_url`] rewrites them per [`ModelMirrorConfig`]. The optional token from
+//! the keychain (gated repos) is only sent as `Authorization` header over HTTPS to
+//! Hugging Face and to the hosts of the configured mirror, which proxy gated repos with
+//! it. Redirects to other hosts don't get it: reqwest drops `Authorization` when a
+//! redirect leaves the host.
+
+use std::time::{Duration, Instant};
+
+use dictara_core::http::{self, HttpSettings};
+use dictara_core::models::get_model_catalog;
+use log::warn;
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{HuggingFaceCredentials, ModelMirrorConfig};
+use crate::keychain::{self, ProviderAccount};
+
+const HUGGING_FACE_URL: &str = "https://huggingface.co/";
+const HUGGING_FACE_HOST: &str = "huggingface.co";
+const RESOLVE_MAIN: &str = "/resolve/main/";
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Reachability of a catalog source through the configured mirror
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorSourceHealth {
+    /// Hugging Face repository, e.g. "ggerganov/whisper.cpp"
+    pub source: String,
+    /// URL that was checked (a file of the source, after mirror rewriting)
+    pub url: String,
+    pub reachable: bool,
+    /// HTTP status, `None` when no response was received
+    pub status: Option<u16>,
+    pub error: Option<String>,
+    pub latency_ms: u64,
+}
+
+/// Download URL of a catalog file, per the mirror settings
+pub fn resolve_url(url: &str, mirror: &ModelMirrorConfig) -> String {
+    let Some((source, file)) = split_catalog_url(url) else {
+        return url.to_string();
+    };
+
+    if let Some(source_url) = mirror.source_urls.get(source) {
+        return format!("{}/{}", source_url.trim_end_matches('/'), file);
+    }
+    match &mirror.base_url {
+        Some(base_url) => format!(
+            "{}/{}",
+            base_url.trim_end_matches('/'),
+            &url[HUGGING_FACE_URL.len()..]
+        ),
+        None => url.to_string(),
+    }
+}
+
+/// Repository and file of a Hugging Face catalog URL
+fn split_catalog_url(url: &str) -> Option<(&str, &str)> {
+    url.strip_prefix(HUGGING_FACE_URL)?.split_once(RESOLVE_MAIN)
+}
+
+/// One file per catalog source, to check that the source is reachable
+fn catalog_sources() -> Vec<(String, String)> {
+    let mut sources: Vec<(String, String)> = Vec::new();
+    let urls = get_model_catalog().into_iter().flat_map(|entry| {
+        entry
+            .files
+            .into_iter()
+            .chain(entry.coreml_encoder.map(|encoder| encoder.file))
+            .map(|file| file.url)
+    });
+    for url in urls {
+        if let Some((source, _)) = split_catalog_url(&url) {
+            if !sources.iter().any(|(known, _)| known == source) {
+                sources.push((source.to_string(), url));
+            }
+        }
+    }
+    sources
+}
+
+/// Check that every catalog source answers through the mirror (HEAD request)
+pub async fn check_sources(
+    mirror: &ModelMirrorConfig,
+    http_settings: &HttpSettings,
+) -> Vec<MirrorSourceHealth> {
+    let token = load_token();
+    let mut results = Vec::new();
+
+    for (source, catalog_url) in catalog_sources() {
+        let url = resolve_url(&catalog_url, mirror);
+        let token = token.as_ref().filter(|_| accepts_token(&url, mirror));
+        let started_at = Instant::now();
+        let result = head(&url, http_settings, token).await;
+        let latency_ms = started_at.elapsed().as_millis() as u64;
+
+        let (status, error) = match result {
+            Ok(status) if status.is_success() => (Some(status.as_u16()), None),
+            Ok(status) if matches!(status.as_u16(), 401 | 403) && accepts_token(&url, mirror) => (
+                Some(status.as_u16()),
+                Some("Access denied, check the Hugging Face token".to_string()),
+            ),
+            Ok(status) => (Some(status.as_u16()), Some(format!("HTTP {}", status))),
+            Err(e) => (None, Some(e)),
+        };
+        results.push(MirrorSourceHealth {
+            source,
+            url,
+            reachable: error.is_none(),
+            status,
+            error,
+            latency_ms,
+        });
+    }
+    results
+}
+
+async fn head(
+    url: &str,
+    http_settings: &HttpSettings,
+    token: Option<&SecretString>,
+) -> Result<reqwest::StatusCode, String> {
+    let client = http_settings
+        .configure(
+            reqwest::Client::builder().timeout(HEALTH_CHECK_TIMEOUT),
+            url,
+        )
+        .map_err(|e| e.to_string())?
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut request = client.head(url);
+    if let Some(token) = token {
+        request = request.bearer_auth(token.expose_secret());
+    }
+    let response = request.send().await.map_err(|e| http::describe_error(&e))?;
+    Ok(response.status())
+}
+
+/// Whether the download token may be sent to `url`: only over HTTPS, to Hugging Face
+/// or a host of the configured mirror
+fn accepts_token(url: &str, mirror: &ModelMirrorConfig) -> bool {
+    let Some(host) = https_host(url) else {
+        return false;
+    };
+    host == HUGGING_FACE_HOST
+        || mirror
+            .base_url
+            .iter()
+            .chain(mirror.source_urls.values())
+            .any(|mirror_url| https_host(mirror_url).as_deref() == Some(host.as_str()))
+}
+
+/// Host of an HTTPS URL, `None` for other schemes and invalid URLs
+fn https_host(url: &str) -> Option<String> {
+    let url = tauri::Url::parse(url).ok()?;
+    if url.scheme() != "https" {
+        return None;
+    }
+    url.host_str().map(str::to_string)
+}
+
+/// Download token for `url`, `None` for other hosts and when none is stored
+pub fn token_for(url: &str, mirror: &ModelMirrorConfig) -> Option<SecretString> {
+    if accepts_token(url, mirror) {
+        load_token()
+    } else {
+        None
+    }
+}
+
+/// Save (or with `None`, delete) the download token in the keychain
+pub fn save_token(token: Option<String>) -> Result<(), String> {
+    match token {
+        Some(token) => keychain::save_provider_config(
+            ProviderAccount::HuggingFace,
+            &HuggingFaceCredentials { token },
+        ),
+        None => keychain::delete_provider_config(ProviderAccount::HuggingFace),
+    }
+    .map_err(|e| format!("Failed to save the Hugging Face token: {}", e))
+}
+
+/// Download token, `None` when none is stored or the keychain can't be read
+fn load_token() -> Option<SecretString> {
+    match keychain::load_provider_config::<HuggingFaceCredentials>(ProviderAccount::HuggingFace) {
+        Ok(credentials) => credentials.map(|credentials| SecretString::from(credentials.token)),
+        Err(e) => {
+            warn!("Failed to load the Hugging Face token: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHISPER_URL: &str =
+        "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin";
+
+    #[test]
+    fn test_resolve_url() {
+        let mut mirror = ModelMirrorConfig::default();
+        assert_eq!(resolve_url(WHISPER_URL, &mirror), WHISPER_URL);
+
+        mirror.base_url = Some("https://hf-mirror.com/".to_string());
+        assert_eq!(
+            resolve_url(WHISPER_URL, &mirror),
+            "https://hf-mirror.com/ggerganov/whisper.cpp/resolve/main/ggml-small.bin"
+        );
+
+        mirror.source_urls.insert(
+            "ggerganov/whisper.cpp".to_string(),
+            "https://artifacts.corp.example/whisper".to_string(),
+        );
+        assert_eq!(
+            resolve_url(WHISPER_URL, &mirror),
+            "https://artifacts.corp.example/whisper/ggml-small.bin"
+        );
+
+        // Not a catalog URL
+        assert_eq!(
+            resolve_url("https://example.com/model.bin", &mirror),
+            "https://example.com/model.bin"
+        );
+    }
+
+    #[test]
+    fn test_token_only_goes_to_hugging_face() {
+        let mirror = ModelMirrorConfig::default();
+        assert!(accepts_token(WHISPER_URL, &mirror));
+        assert!(!accepts_token(
+            "https://hf-mirror.com/ggerganov/whisper.cpp/resolve/main/ggml-small.bin",
+            &mirror
+        ));
+        assert!(!accepts_token(
+            "https://huggingface.co.evil.example/model.bin",
+            &mirror
+        ));
+        assert!(!accepts_token("http://huggingface.co/model.bin", &mirror));
+        assert!(!accepts_token("not a url", &mirror));
+    }
+
+    #[test]
+    fn test_token_goes_to_the_mirror_hosts() {
+        let mut mirror = ModelMirrorConfig {
+            base_url: Some("https://hf-mirror.com/".to_string()),
+            ..ModelMirrorConfig::default()
+        };
+        mirror.source_urls.insert(
+            "ggerganov/whisper.cpp".to_string(),
+            "https://artifacts.corp.example/whisper".to_string(),
+        );
+        mirror.source_urls.insert(
+            "openai/whisper-large-v3".to_string(),
+            "http://plain.corp.example/whisper".to_string(),
+        );
+
+        let mirrored = resolve_url(WHISPER_URL, &mirror);
+        assert!(accepts_token(&mirrored, &mirror));
+        assert!(accepts_token(
+            "https://hf-mirror.com/openai/whisper-large-v3/resolve/main/model.bin",
+            &mirror
+        ));
+        assert!(accepts_token(WHISPER_URL, &mirror));
+        // Only over HTTPS, and not to hosts the mirror redirects to
+        assert!(!accepts_token("http://hf-mirror.com/model.bin", &mirror));
+        assert!(!accepts_token(
+            "https://plain.corp.example/whisper/model.bin",
+            &mirror
+        ));
+        assert!(!accepts_token("https://cdn.example/model.bin", &mirror));
+    }
+}
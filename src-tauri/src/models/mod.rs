@@ -1,13 +1,24 @@
-mod catalog;
 pub mod events;
 mod loader;
-mod local_client;
+mod location;
 mod manager;
+mod migration;
+mod mirror;
+mod resources;
 mod status;
+mod verification;
 
-pub use catalog::{is_model_in_catalog, ModelCatalogEntry};
+pub use dictara_core::models::{
+    is_model_in_catalog, CoreMlEncoder, ModelCatalogEntry, Quantization, SpeedClass,
+};
 pub use loader::ModelLoader;
 pub use manager::ModelManager;
+pub use migration::{MigrationState, MigrationStatus};
+pub use mirror::{
+    check_sources as check_mirror_sources, save_token as save_hugging_face_token,
+    MirrorSourceHealth,
+};
+pub use resources::{InferencePhase, PhaseSummary, ResourceReport};
 pub use status::ModelStatus;
 
 use serde::{Deserialize, Serialize};
@@ -22,6 +33,11 @@ pub struct ModelInfo {
     pub description: String,
     pub size_bytes: u64,
     pub estimated_ram_mb: u64,
+    pub speed_class: SpeedClass,
+    pub languages: Vec<String>,
+    pub quantization: Option<Quantization>,
+    /// A Core ML encoder can be downloaded (Apple Silicon only)
+    pub coreml_available: bool,
 
     // From status
     pub is_downloaded: bool,
@@ -29,6 +45,7 @@ pub struct ModelInfo {
     pub is_loaded: bool,
     pub is_loading: bool,
     pub downloaded_bytes: u64,
+    pub is_coreml_downloaded: bool,
 }
 
 impl ModelInfo {
@@ -39,11 +56,58 @@ impl ModelInfo {
             description: catalog.description.clone(),
             size_bytes: catalog.size_bytes,
             estimated_ram_mb: catalog.estimated_ram_mb,
+            speed_class: catalog.speed_class,
+            languages: catalog.languages.clone(),
+            quantization: catalog.quantization,
+            // Unverifiable encoders aren't offered
+            coreml_available: catalog
+                .coreml_encoder
+                .as_ref()
+                .is_some_and(|encoder| encoder.file.is_pinned())
+                && dictara_core::transcription::coreml_supported(),
             is_downloaded: status.is_downloaded,
             is_downloading: status.is_downloading,
             is_loaded: status.is_loaded,
             is_loading: status.is_loading,
             downloaded_bytes: status.downloaded_bytes,
+            is_coreml_downloaded: status.is_coreml_downloaded,
         }
     }
 }
+
+/// Result of re-hashing an installed model against the catalog checksums.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelVerification {
+    pub model_name: String,
+    /// Number of files matching their checksum
+    pub verified_files: u32,
+    /// Files without a pinned checksum, which can't be checked
+    pub unverifiable_files: Vec<String>,
+    /// Files that are missing or don't match their checksum
+    pub corrupted_files: Vec<String>,
+    /// Whether the corrupted files were downloaded again
+    pub repaired: bool,
+}
+
+/// Disk space used by the models, for the storage settings
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelsDiskUsage {
+    /// Directory the models are stored in
+    pub models_dir: String,
+    /// Models with files on disk
+    pub models: Vec<ModelDiskUsage>,
+    pub total_bytes: u64,
+}
+
+/// Disk space used by one model (or the speaker model of meetings)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelDiskUsage {
+    pub model_name: String,
+    /// Everything on disk: model files, Core ML encoder and interrupted downloads
+    pub total_bytes: u64,
+    /// Interrupted downloads, freed by deleting the model
+    pub partial_bytes: u64,
+}
@@ -0,0 +1,282 @@
+//! Resource usage of local inference.
+//!
+//! While a model loads or transcribes, a background thread samples the process
+//! resident memory, the system swap usage and (on macOS) the GPU utilization.
+//! Samples are emitted as [`LocalInferenceResourceUsage`] events and summarized
+//! per phase in a [`ResourceReport`], so users can confirm that Metal is actually
+//! used and whether the model makes the system swap.
+//!
+//! GPU utilization is system wide (IOAccelerator statistics), the Neural Engine
+//! has no public utilization counters.
+
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use log::error;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use super::events::LocalInferenceResourceUsage;
+
+/// Interval between samples (the macOS GPU sample spawns `ioreg`)
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What the model is doing while sampled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum InferencePhase {
+    ModelLoad,
+    Transcription,
+}
+
+/// Resource usage at one point in time, `None` where unsupported on this platform
+#[derive(Debug, Clone, Copy, Default)]
+struct ResourceSample {
+    rss_bytes: Option<u64>,
+    swap_used_bytes: Option<u64>,
+    gpu_utilization: Option<u8>,
+}
+
+/// Resource usage over one model load or transcription
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseSummary {
+    pub model_name: String,
+    pub duration_ms: u64,
+    /// Highest process resident memory
+    pub peak_rss_bytes: Option<u64>,
+    /// Highest GPU utilization in percent (macOS only)
+    pub max_gpu_utilization: Option<u8>,
+    /// Swap used by the system at the end minus at the start
+    pub swap_growth_bytes: Option<u64>,
+}
+
+/// Last model load and transcription summaries, for diagnostics
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceReport {
+    pub last_model_load: Option<PhaseSummary>,
+    pub last_transcription: Option<PhaseSummary>,
+}
+
+impl ResourceReport {
+    pub fn record(&mut self, phase: InferencePhase, summary: PhaseSummary) {
+        match phase {
+            InferencePhase::ModelLoad => self.last_model_load = Some(summary),
+            InferencePhase::Transcription => self.last_transcription = Some(summary),
+        }
+    }
+}
+
+/// Samples resource usage on a background thread until stopped
+pub struct ResourceSampler {
+    /// Dropped to stop the thread
+    stop_tx: mpsc::Sender<()>,
+    handle: JoinHandle<PhaseSummary>,
+}
+
+impl ResourceSampler {
+    /// Start sampling, `None` if the thread couldn't be spawned
+    pub fn start(app: AppHandle, phase: InferencePhase, model_name: &str) -> Option<Self> {
+        let model_name = model_name.to_string();
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+
+        let handle = std::thread::Builder::new()
+            .name("resource-sampler".to_string())
+            .spawn(move || {
+                let started = Instant::now();
+                let first = sample();
+                let mut last = first;
+                let mut peak_rss_bytes = first.rss_bytes;
+                let mut max_gpu_utilization = first.gpu_utilization;
+
+                loop {
+                    let _ = LocalInferenceResourceUsage {
+                        phase,
+                        model_name: model_name.clone(),
+                        rss_bytes: last.rss_bytes,
+                        swap_used_bytes: last.swap_used_bytes,
+                        gpu_utilization: last.gpu_utilization,
+                    }
+                    .emit(&app);
+
+                    if let Err(RecvTimeoutError::Disconnected) =
+                        stop_rx.recv_timeout(SAMPLE_INTERVAL)
+                    {
+                        break;
+                    }
+
+                    last = sample();
+                    peak_rss_bytes = peak_rss_bytes.max(last.rss_bytes);
+                    max_gpu_utilization = max_gpu_utilization.max(last.gpu_utilization);
+                }
+
+                PhaseSummary {
+                    model_name,
+                    duration_ms: started.elapsed().as_millis() as u64,
+                    peak_rss_bytes,
+                    max_gpu_utilization,
+                    swap_growth_bytes: last
+                        .swap_used_bytes
+                        .zip(first.swap_used_bytes)
+                        .map(|(end, start)| end.saturating_sub(start)),
+                }
+            });
+
+        match handle {
+            Ok(handle) => Some(Self { stop_tx, handle }),
+            Err(e) => {
+                error!("Failed to start resource sampler: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Stop sampling and summarize the phase
+    pub fn stop(self) -> Option<PhaseSummary> {
+        drop(self.stop_tx);
+        match self.handle.join() {
+            Ok(summary) => Some(summary),
+            Err(_) => {
+                error!("Resource sampler thread panicked");
+                None
+            }
+        }
+    }
+}
+
+fn sample() -> ResourceSample {
+    ResourceSample {
+        rss_bytes: platform::rss_bytes(),
+        swap_used_bytes: platform::swap_used_bytes(),
+        gpu_utilization: platform::gpu_utilization(),
+    }
+}
+
+/// Highest "Device Utilization %" in `ioreg -c IOAccelerator` output
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_gpu_utilization(ioreg_output: &str) -> Option<u8> {
+    const KEY: &str = "\"Device Utilization %\"=";
+
+    ioreg_output
+        .match_indices(KEY)
+        .filter_map(|(pos, _)| {
+            let value = &ioreg_output[pos + KEY.len()..];
+            let digits = value
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(value.len());
+            value[..digits].parse::<u8>().ok()
+        })
+        .max()
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::process::Command;
+
+    pub fn rss_bytes() -> Option<u64> {
+        // SAFETY: proc_taskinfo is plain old data
+        let mut info: libc::proc_taskinfo = unsafe { std::mem::zeroed() };
+        let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+        // SAFETY: `info` is writable and `size` bytes long
+        let written = unsafe {
+            libc::proc_pidinfo(
+                libc::getpid(),
+                libc::PROC_PIDTASKINFO,
+                0,
+                &mut info as *mut libc::proc_taskinfo as *mut libc::c_void,
+                size,
+            )
+        };
+        (written == size).then_some(info.pti_resident_size)
+    }
+
+    pub fn swap_used_bytes() -> Option<u64> {
+        // SAFETY: xsw_usage is plain old data
+        let mut usage: libc::xsw_usage = unsafe { std::mem::zeroed() };
+        let mut size = std::mem::size_of::<libc::xsw_usage>();
+        // SAFETY: `usage` is writable and `size` bytes long
+        let result = unsafe {
+            libc::sysctlbyname(
+                c"vm.swapusage".as_ptr(),
+                &mut usage as *mut libc::xsw_usage as *mut libc::c_void,
+                &mut size,
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+        (result == 0).then_some(usage.xsu_used)
+    }
+
+    pub fn gpu_utilization() -> Option<u8> {
+        let output = Command::new("ioreg")
+            .args(["-r", "-d", "1", "-w", "0", "-c", "IOAccelerator"])
+            .output()
+            .ok()?;
+        super::parse_gpu_utilization(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    /// Value in kB of a `Key:   123 kB` line in a /proc file
+    fn proc_kb(path: &str, key: &str) -> Option<u64> {
+        let content = std::fs::read_to_string(path).ok()?;
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+            .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+    }
+
+    pub fn rss_bytes() -> Option<u64> {
+        proc_kb("/proc/self/status", "VmRSS").map(|kb| kb * 1024)
+    }
+
+    pub fn swap_used_bytes() -> Option<u64> {
+        let total = proc_kb("/proc/meminfo", "SwapTotal")?;
+        let free = proc_kb("/proc/meminfo", "SwapFree")?;
+        Some(total.saturating_sub(free) * 1024)
+    }
+
+    pub fn gpu_utilization() -> Option<u8> {
+        None
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+mod platform {
+    pub fn rss_bytes() -> Option<u64> {
+        None
+    }
+
+    pub fn swap_used_bytes() -> Option<u64> {
+        None
+    }
+
+    pub fn gpu_utilization() -> Option<u8> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gpu_utilization() {
+        let output = r#"
++-o AGXAcceleratorG13X  <class AGXAcceleratorG13X>
+    {
+      "PerformanceStatistics" = {"In use system memory"=123,"Device Utilization %"=37,"Renderer Utilization %"=35}
+    }
++-o Other  <class IOAccelerator>
+    {
+      "PerformanceStatistics" = {"Device Utilization %"=5}
+    }
+"#;
+        assert_eq!(parse_gpu_utilization(output), Some(37));
+        assert_eq!(parse_gpu_utilization("no accelerator"), None);
+    }
+}
@@ -14,4 +14,6 @@ pub struct ModelStatus {
     pub is_loading: bool,
     /// Size of partial download file (for resume support)
     pub downloaded_bytes: u64,
+    /// Is the Core ML encoder installed next to the model?
+    pub is_coreml_downloaded: bool,
 }
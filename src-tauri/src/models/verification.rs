@@ -0,0 +1,370 @@
+//! Checksum verification of model files.
+//!
+//! Downloads hash their bytes as they are written ([`StreamingHash`]), so a finished
+//! download is checked without reading gigabytes back from disk. Files that couldn't
+//! be hashed on the fly, and installed models checked by `verify_model`, are hashed
+//! again in parallel, reporting the bytes hashed through
+//! `ModelDownloadStateChanged::Verifying`.
+//!
+//! Files whose checksum isn't pinned in the catalog yet can't be checked: they come
+//! out as [`FileStatus::Unverifiable`], never as verified.
+//!
+//! Files are read on the worker pool with background priority, so hashing
+//! gigabytes doesn't hold up a transcription.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use dictara_core::models::UNPINNED_SHA256;
+use log::warn;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use super::events::ModelDownloadStateChanged;
+use crate::worker_pool::{WorkPriority, WorkerPool};
+
+/// Minimum time between two `Verifying` progress events
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Read buffer size for hashing
+const BUFFER_SIZE: usize = 1024 * 1024;
+
+/// SHA-256 of a download, computed as its chunks are written
+pub struct StreamingHash(Option<Sha256>);
+
+impl StreamingHash {
+    /// Hasher of a download resuming after `resume_from` bytes of `path`, which are
+    /// hashed first. Gives no hash if they can't be read, the file is then hashed
+    /// again once downloaded.
+    pub async fn resume(pool: &WorkerPool, path: &Path, resume_from: u64) -> Self {
+        let hasher = Sha256::new();
+        if resume_from == 0 {
+            return Self(Some(hasher));
+        }
+
+        match hash_on_pool(pool, path, hasher, |_| {}).await {
+            Ok((hasher, hashed)) if hashed == resume_from => Self(Some(hasher)),
+            Ok((_, hashed)) => {
+                warn!(
+                    "{:?} changed while resuming ({} of {} bytes hashed)",
+                    path, hashed, resume_from
+                );
+                Self(None)
+            }
+            Err(e) => {
+                warn!("Failed to hash the downloaded part of {:?}: {}", path, e);
+                Self(None)
+            }
+        }
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) {
+        if let Some(hasher) = &mut self.0 {
+            hasher.update(chunk);
+        }
+    }
+
+    /// Hex SHA-256 of the whole file, if every byte went through the hasher
+    pub fn finalize(self) -> Option<String> {
+        self.0.map(|hasher| format!("{:x}", hasher.finalize()))
+    }
+}
+
+/// Outcome of checking a file that is intact as far as can be told
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    /// Matches its catalog checksum
+    Verified,
+    /// No checksum is pinned for the file, it wasn't checked
+    Unverifiable,
+}
+
+/// A downloaded or installed file to check against its catalog checksum
+pub struct FileCheck {
+    pub path: PathBuf,
+    pub expected_sha256: String,
+    /// Hash computed during the download, saves reading the file again
+    pub streamed_sha256: Option<String>,
+}
+
+impl FileCheck {
+    /// Whether the file has to be read to check it
+    fn needs_hashing(&self) -> bool {
+        self.streamed_sha256.is_none() && self.expected_sha256 != UNPINNED_SHA256
+    }
+
+    async fn verify(
+        self,
+        pool: &WorkerPool,
+        on_progress: impl FnMut(u64) + Send + 'static,
+    ) -> Result<FileStatus, String> {
+        if self.expected_sha256 == UNPINNED_SHA256 {
+            warn!(
+                "{:?} has no pinned checksum, it can't be verified",
+                self.path
+            );
+            return Ok(FileStatus::Unverifiable);
+        }
+
+        let computed_hash = match self.streamed_sha256 {
+            Some(hash) => hash,
+            None => file_sha256_with_progress(pool, &self.path, on_progress).await?,
+        };
+
+        if computed_hash != self.expected_sha256 {
+            return Err(format!(
+                "Hash mismatch: expected {}, got {}",
+                self.expected_sha256, computed_hash
+            ));
+        }
+
+        Ok(FileStatus::Verified)
+    }
+}
+
+/// Check `files` of `model_name` in parallel, one result per file in the same order
+/// (an error when a file doesn't match its checksum).
+///
+/// `Verifying` events are only emitted when a file has to be hashed again.
+pub async fn verify_files(
+    app: &AppHandle,
+    model_name: &str,
+    files: Vec<FileCheck>,
+) -> Vec<Result<FileStatus, String>> {
+    // Only the files read again count towards the progress
+    let sizes: Vec<u64> = files
+        .iter()
+        .map(|file| {
+            if file.needs_hashing() {
+                file.path.metadata().map(|m| m.len()).unwrap_or(0)
+            } else {
+                0
+            }
+        })
+        .collect();
+    let progress = Arc::new(VerificationProgress::new(
+        app.clone(),
+        model_name,
+        files.len(),
+        sizes.iter().sum(),
+    ));
+    if files.iter().any(FileCheck::needs_hashing) {
+        progress.emit(0);
+    }
+
+    let pool = app.state::<Arc<WorkerPool>>().inner().clone();
+    let handles: Vec<_> = files
+        .into_iter()
+        .enumerate()
+        .map(|(idx, file)| {
+            let progress = progress.clone();
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                file.verify(&pool, move |hashed| progress.update(idx, hashed))
+                    .await
+            })
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(handles.len());
+    for (idx, handle) in handles.into_iter().enumerate() {
+        results.push(
+            handle
+                .await
+                .map_err(|e| format!("Verification task {} failed: {}", idx, e))
+                .and_then(|result| result),
+        );
+    }
+    results
+}
+
+/// Bytes hashed across the files of a model, emitted as `Verifying` events
+struct VerificationProgress {
+    app: AppHandle,
+    model_name: String,
+    total_bytes: u64,
+    /// Bytes hashed per file, and when progress was last emitted
+    hashed: Mutex<(Vec<u64>, Instant)>,
+}
+
+impl VerificationProgress {
+    fn new(app: AppHandle, model_name: &str, file_count: usize, total_bytes: u64) -> Self {
+        Self {
+            app,
+            model_name: model_name.to_string(),
+            total_bytes,
+            hashed: Mutex::new((vec![0; file_count], Instant::now())),
+        }
+    }
+
+    fn update(&self, file_index: usize, hashed_bytes: u64) {
+        let verified_bytes = {
+            let mut hashed = self.hashed.lock().unwrap();
+            hashed.0[file_index] = hashed_bytes;
+            if hashed.1.elapsed() < PROGRESS_INTERVAL {
+                return;
+            }
+            hashed.1 = Instant::now();
+            hashed.0.iter().sum()
+        };
+        self.emit(verified_bytes);
+    }
+
+    fn emit(&self, verified_bytes: u64) {
+        let percentage = if self.total_bytes == 0 {
+            100.0
+        } else {
+            (verified_bytes as f64 / self.total_bytes as f64 * 100.0).min(100.0)
+        };
+
+        let _ = ModelDownloadStateChanged::Verifying {
+            model_name: self.model_name.clone(),
+            verified_bytes,
+            total_bytes: self.total_bytes,
+            percentage,
+        }
+        .emit(&self.app);
+    }
+}
+
+/// SHA-256 of a file as a hex string
+pub async fn file_sha256(pool: &WorkerPool, file_path: &Path) -> Result<String, String> {
+    file_sha256_with_progress(pool, file_path, |_| {}).await
+}
+
+/// SHA-256 of a file as a hex string, calling `on_progress` with the bytes hashed so far
+async fn file_sha256_with_progress(
+    pool: &WorkerPool,
+    file_path: &Path,
+    on_progress: impl FnMut(u64) + Send + 'static,
+) -> Result<String, String> {
+    let (hasher, _) = hash_on_pool(pool, file_path, Sha256::new(), on_progress).await?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Feed a file into `hasher` on the worker pool, returns the hasher and the number
+/// of bytes hashed
+async fn hash_on_pool(
+    pool: &WorkerPool,
+    file_path: &Path,
+    mut hasher: Sha256,
+    on_progress: impl FnMut(u64) + Send + 'static,
+) -> Result<(Sha256, u64), String> {
+    let file_path = file_path.to_path_buf();
+    pool.spawn(WorkPriority::Background, move || {
+        hash_file(&file_path, &mut hasher, on_progress).map(|hashed| (hasher, hashed))
+    })
+    .await
+    .and_then(|result| result)
+}
+
+/// Feed a file into `hasher`, returns the number of bytes hashed. Blocking.
+fn hash_file(
+    file_path: &Path,
+    hasher: &mut Sha256,
+    mut on_progress: impl FnMut(u64),
+) -> Result<u64, String> {
+    let mut file = File::open(file_path)
+        .map_err(|e| format!("Failed to open file for verification: {}", e))?;
+
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    let mut hashed = 0u64;
+
+    loop {
+        let bytes_read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read file for verification: {}", e))?;
+
+        if bytes_read == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..bytes_read]);
+        hashed += bytes_read as u64;
+        on_progress(hashed);
+    }
+
+    Ok(hashed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "dictara-verification-test-{}-{}",
+            std::process::id(),
+            name
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_resumed_hash_matches_file_hash() {
+        let pool = WorkerPool::new(2);
+        let path = temp_file("resumed");
+        let content: Vec<u8> = (0..3 * BUFFER_SIZE as u32).map(|i| i as u8).collect();
+        let (downloaded, remaining) = content.split_at(BUFFER_SIZE + 17);
+        std::fs::write(&path, downloaded).unwrap();
+
+        let mut hash = StreamingHash::resume(&pool, &path, downloaded.len() as u64).await;
+        hash.update(remaining);
+        std::fs::write(&path, &content).unwrap();
+
+        assert_eq!(
+            hash.finalize(),
+            Some(file_sha256(&pool, &path).await.unwrap())
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_verify() {
+        let pool = WorkerPool::new(2);
+        let path = temp_file("verify");
+        std::fs::write(&path, b"model").unwrap();
+        let check = |expected_sha256: &str| FileCheck {
+            path: path.clone(),
+            expected_sha256: expected_sha256.to_string(),
+            streamed_sha256: None,
+        };
+        let sha256 = file_sha256(&pool, &path).await.unwrap();
+
+        assert_eq!(
+            check(&sha256).verify(&pool, |_| {}).await,
+            Ok(FileStatus::Verified)
+        );
+        assert!(check(&"0".repeat(64)).verify(&pool, |_| {}).await.is_err());
+        // Not reported as verified without a checksum to compare with
+        let unpinned = check(UNPINNED_SHA256);
+        assert!(!unpinned.needs_hashing());
+        assert_eq!(
+            unpinned.verify(&pool, |_| {}).await,
+            Ok(FileStatus::Unverifiable)
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_resume_of_changed_file_gives_no_hash() {
+        let pool = WorkerPool::new(2);
+        let path = temp_file("changed");
+        std::fs::write(&path, b"downloaded").unwrap();
+
+        assert_eq!(
+            StreamingHash::resume(&pool, &path, 4).await.finalize(),
+            None
+        );
+        assert_eq!(
+            StreamingHash::resume(&pool, &temp_file("missing"), 4)
+                .await
+                .finalize(),
+            None
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+}
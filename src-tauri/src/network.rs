@@ -0,0 +1,68 @@
+//! Proxy and TLS settings of the outbound HTTP clients.
+//!
+//! Transcription APIs, post-processing, model downloads and the updater build their
+//! clients from [`http_settings`], so the network preferences apply everywhere. The
+//! proxy password is read from the keychain when a client is built.
+
+use std::path::PathBuf;
+
+use dictara_core::http::{HttpProxy, HttpSettings, TlsSettings};
+use log::warn;
+use secrecy::SecretString;
+
+use crate::config::{ConfigKey, ConfigStore, ProxyConfig, ProxyCredentials, ProxyMode, TlsConfig};
+use crate::keychain::{self, ProviderAccount};
+
+/// Proxy and TLS settings of the HTTP clients, per the app config
+pub fn http_settings(config_store: &impl ConfigStore) -> HttpSettings {
+    let config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    HttpSettings {
+        proxy: http_proxy(config.proxy),
+        tls: tls_settings(config.tls),
+    }
+}
+
+fn http_proxy(proxy: ProxyConfig) -> HttpProxy {
+    match proxy.mode {
+        ProxyMode::System => HttpProxy::System,
+        ProxyMode::Direct => HttpProxy::Direct,
+        ProxyMode::Manual => HttpProxy::Manual {
+            url: proxy.url(),
+            credentials: proxy.username.map(|username| (username, load_password())),
+        },
+    }
+}
+
+fn tls_settings(tls: TlsConfig) -> TlsSettings {
+    TlsSettings {
+        custom_ca_path: tls.custom_ca_path.map(PathBuf::from),
+        pinned_roots: tls
+            .pinned_certificates
+            .into_iter()
+            .map(|(host, path)| (host, PathBuf::from(path)))
+            .collect(),
+    }
+}
+
+/// Save (or with `None`, delete) the proxy password in the keychain
+pub fn save_password(password: Option<String>) -> Result<(), String> {
+    match password {
+        Some(password) => {
+            keychain::save_provider_config(ProviderAccount::Proxy, &ProxyCredentials { password })
+        }
+        None => keychain::delete_provider_config(ProviderAccount::Proxy),
+    }
+    .map_err(|e| format!("Failed to save the proxy password: {}", e))
+}
+
+/// Proxy password, empty when none is stored or the keychain can't be read
+fn load_password() -> SecretString {
+    match keychain::load_provider_config::<ProxyCredentials>(ProviderAccount::Proxy) {
+        Ok(Some(credentials)) => SecretString::from(credentials.password),
+        Ok(None) => SecretString::from(String::new()),
+        Err(e) => {
+            warn!("Failed to load the proxy password: {}", e);
+            SecretString::from(String::new())
+        }
+    }
+}
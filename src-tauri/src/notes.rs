@@ -0,0 +1,219 @@
+//! Dictation into a new note, in Obsidian or Apple Notes.
+//!
+//! Instead of being pasted into the focused field, the dictation becomes a note of
+//! its own, titled after its first sentence:
+//! - Obsidian: opened through its URL scheme (`obsidian://new`), the note goes to the
+//!   vault open last.
+//! - Apple Notes: created with AppleScript in the default account. The first time,
+//!   macOS asks the user to let Dictara control Notes.
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+/// Longest inferred title, cut at a word boundary
+const MAX_TITLE_CHARS: usize = 60;
+
+/// Not allowed in Obsidian file names (or meaningful in links)
+const FORBIDDEN_TITLE_CHARS: &[char] = &[
+    '\\', '/', ':', '*', '?', '"', '<', '>', '|', '#', '^', '[', ']',
+];
+
+/// App the note is created in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum NoteTarget {
+    Obsidian,
+    AppleNotes,
+}
+
+/// Create a note in `target` with `text`
+pub fn create_note(app_handle: &AppHandle, target: NoteTarget, text: &str) -> Result<(), String> {
+    let title = infer_title(text)
+        .unwrap_or_else(|| format!("Dictation {}", chrono::Local::now().format("%Y-%m-%d %H%M")));
+    log::info!("Creating a {:?} note \"{}\"", target, title);
+
+    match target {
+        NoteTarget::Obsidian => app_handle
+            .opener()
+            .open_url(obsidian_url(&title, text), None::<&str>)
+            .map_err(|e| format!("Failed to open Obsidian: {}", e)),
+        NoteTarget::AppleNotes => create_apple_note(&title, text),
+    }
+}
+
+/// Title from the first sentence of `text`, `None` when nothing is left of it
+fn infer_title(text: &str) -> Option<String> {
+    let first_line = text.trim().lines().next()?;
+
+    let mut end = first_line.len();
+    let mut chars = first_line.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let ends_sentence = match c {
+            '。' | '！' | '？' => true,
+            '.' | '!' | '?' => chars.peek().is_none_or(|(_, next)| next.is_whitespace()),
+            _ => false,
+        };
+        if ends_sentence {
+            end = index;
+            break;
+        }
+    }
+
+    let sentence: String = first_line[..end]
+        .chars()
+        .map(|c| {
+            if FORBIDDEN_TITLE_CHARS.contains(&c) {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    let mut title = String::new();
+    for word in sentence.split_whitespace() {
+        let length = title.chars().count() + word.chars().count() + 1;
+        if !title.is_empty() && length > MAX_TITLE_CHARS {
+            break;
+        }
+        if !title.is_empty() {
+            title.push(' ');
+        }
+        title.push_str(word);
+    }
+    // A single word longer than the limit
+    if title.chars().count() > MAX_TITLE_CHARS {
+        title = title.chars().take(MAX_TITLE_CHARS).collect();
+    }
+
+    let title = title.trim_end_matches(|c: char| c.is_ascii_punctuation() || c == '、');
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Percent-encode `value` for a URL query, keeping only the unreserved characters
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// URL creating the note `title` with `text` in the vault Obsidian opened last
+fn obsidian_url(title: &str, text: &str) -> String {
+    format!(
+        "obsidian://new?name={}&content={}",
+        percent_encode(title),
+        percent_encode(text)
+    )
+}
+
+/// Escape `text` for HTML
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Body of an Apple Notes note: the title as heading, then one line per paragraph
+fn apple_note_body(title: &str, text: &str) -> String {
+    let mut body = format!("<h1>{}</h1>", escape_html(title));
+    for line in text.trim().lines() {
+        if line.trim().is_empty() {
+            body.push_str("<div><br></div>");
+        } else {
+            body.push_str(&format!("<div>{}</div>", escape_html(line)));
+        }
+    }
+    body
+}
+
+#[cfg(target_os = "macos")]
+fn create_apple_note(title: &str, text: &str) -> Result<(), String> {
+    // The body is passed as an argument, so nothing in it is read as AppleScript
+    let output = std::process::Command::new("osascript")
+        .args([
+            "-e",
+            "on run argv",
+            "-e",
+            "tell application \"Notes\" to make new note with properties {body:item 1 of argv}",
+            "-e",
+            "end run",
+        ])
+        .arg(apple_note_body(title, text))
+        .output()
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Notes refused the note: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn create_apple_note(_title: &str, _text: &str) -> Result<(), String> {
+    Err("Apple Notes is only available on macOS".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_title() {
+        assert_eq!(
+            infer_title("Buy milk. Then call mom.").as_deref(),
+            Some("Buy milk")
+        );
+        assert_eq!(
+            infer_title("  Version 2.5 is out!\nDetails follow").as_deref(),
+            Some("Version 2.5 is out")
+        );
+        assert_eq!(
+            infer_title("Meeting notes: Q3/Q4 plan").as_deref(),
+            Some("Meeting notes Q3 Q4 plan")
+        );
+        assert_eq!(
+            infer_title("会議のメモ。次は金曜日").as_deref(),
+            Some("会議のメモ")
+        );
+
+        let long = infer_title(
+            "This is a rather long first sentence that keeps going well past the limit of a title",
+        )
+        .unwrap();
+        assert_eq!(
+            long,
+            "This is a rather long first sentence that keeps going well"
+        );
+
+        assert_eq!(infer_title(" ... "), None);
+        assert_eq!(infer_title(""), None);
+    }
+
+    #[test]
+    fn test_obsidian_url() {
+        assert_eq!(
+            obsidian_url("Café & co", "Line 1\nLine 2"),
+            "obsidian://new?name=Caf%C3%A9%20%26%20co&content=Line%201%0ALine%202"
+        );
+    }
+
+    #[test]
+    fn test_apple_note_body() {
+        assert_eq!(
+            apple_note_body("A <b>", "First\n\nSecond & last"),
+            "<h1>A &lt;b&gt;</h1><div>First</div><div><br></div><div>Second &amp; last</div>"
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Configuration profiles.
+//!
+//! A profile bundles the provider, local model, shortcuts, vocabulary (learned
+//! corrections and snippets) and post-processing settings under a name, e.g. "Work"
+//! with Azure and a formal prompt and "Personal" with a local model. Switching saves
+//! the current settings into the active profile, then writes the other profile's
+//! values to their config entries: every subsystem watching the config (keyboard
+//! listener, model preload, engine menu, Globe key) picks them up at once.
+//!
+//! The device-level model settings (models directory, download mirror, Trash) are not
+//! part of a profile. Settings locked by IT stay as managed, whatever the profile holds.
+
+use log::info;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::config::{
+    AppConfig, ConfigKey, ConfigProfile, ConfigStore, LocalModelConfig, ProfileLocalModel,
+    ProfilesConfig,
+};
+
+/// Longest profile name
+const MAX_NAME_LEN: usize = 40;
+
+/// Saved profiles, none until the first is created
+pub fn load_profiles(store: &impl ConfigStore) -> ProfilesConfig {
+    store.get(&ConfigKey::PROFILES).unwrap_or_default()
+}
+
+/// The current settings as a profile named `name`
+fn capture(store: &impl ConfigStore, name: &str) -> ConfigProfile {
+    let app: AppConfig = store.get(&ConfigKey::APP).unwrap_or_default();
+    let local_model: LocalModelConfig = store.get(&ConfigKey::LOCAL_MODEL).unwrap_or_default();
+    ConfigProfile {
+        name: name.to_string(),
+        active_provider: app.active_provider,
+        fallback_providers: app.fallback_providers,
+        transcription_options: app.transcription_options,
+        post_process_enabled: app.post_process_enabled,
+        post_process_model: app.post_process_model,
+        post_process_prompt: app.post_process_prompt,
+        post_process_prompts_by_language: app.post_process_prompts_by_language,
+        post_process_params: app.post_process_params,
+        format_normalization: app.format_normalization,
+        redaction: app.redaction,
+        markdown_mode: app.markdown_mode,
+        local_model: ProfileLocalModel {
+            selected_model: local_model.selected_model,
+            model_options: local_model.model_options,
+        },
+        shortcuts: store.get(&ConfigKey::SHORTCUTS).unwrap_or_default(),
+        corrections: store
+            .get(&ConfigKey::LEARNED_CORRECTIONS)
+            .unwrap_or_default(),
+        snippets: store.get(&ConfigKey::SNIPPETS).unwrap_or_default(),
+    }
+}
+
+/// Trimmed profile name, rejecting empty and overlong ones
+fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(format!(
+            "Profile name must be at most {} characters",
+            MAX_NAME_LEN
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Save the current settings as `name`, replacing a profile of that name, and make
+/// it the active profile
+pub fn save_current(store: &impl ConfigStore, name: &str) -> Result<(), String> {
+    let name = validate_name(name)?;
+    let mut profiles = load_profiles(store);
+    let profile = capture(store, &name);
+    match profiles.profiles.iter_mut().find(|p| p.name == name) {
+        Some(existing) => *existing = profile,
+        None => profiles.profiles.push(profile),
+    }
+    profiles.active = Some(name);
+    store.set(&ConfigKey::PROFILES, profiles)
+}
+
+/// Make `name` the active profile: the current settings are saved into the active
+/// profile first, then replaced by those of `name`
+pub fn switch(store: &impl ConfigStore, name: &str) -> Result<(), String> {
+    let mut profiles = load_profiles(store);
+    let target = profiles
+        .profiles
+        .iter()
+        .find(|p| p.name == name)
+        .cloned()
+        .ok_or_else(|| format!("Profile not found: {}", name))?;
+    if profiles.active.as_deref() == Some(name) {
+        return Ok(());
+    }
+
+    // Changes made since switching to the active profile stay with it
+    if let Some(active) = profiles.active.clone() {
+        let current = capture(store, &active);
+        if let Some(existing) = profiles.profiles.iter_mut().find(|p| p.name == active) {
+            *existing = current;
+        }
+    }
+
+    apply(store, target)?;
+    profiles.active = Some(name.to_string());
+    store.set(&ConfigKey::PROFILES, profiles)?;
+    info!("Switched to profile {}", name);
+    Ok(())
+}
+
+/// Delete the profile `name`, the current settings stay as they are
+pub fn delete(store: &impl ConfigStore, name: &str) -> Result<(), String> {
+    let mut profiles = load_profiles(store);
+    profiles.profiles.retain(|p| p.name != name);
+    if profiles.active.as_deref() == Some(name) {
+        profiles.active = None;
+    }
+    store.set(&ConfigKey::PROFILES, profiles)
+}
+
+/// Write the settings of `profile` to their config entries
+fn apply(store: &impl ConfigStore, profile: ConfigProfile) -> Result<(), String> {
+    let app = AppConfig {
+        active_provider: profile.active_provider,
+        fallback_providers: profile.fallback_providers,
+        transcription_options: profile.transcription_options,
+        post_process_enabled: profile.post_process_enabled,
+        post_process_model: profile.post_process_model,
+        post_process_prompt: profile.post_process_prompt,
+        post_process_prompts_by_language: profile.post_process_prompts_by_language,
+        post_process_params: profile.post_process_params,
+        format_normalization: profile.format_normalization,
+        redaction: profile.redaction,
+        markdown_mode: profile.markdown_mode,
+        ..store.get(&ConfigKey::APP).unwrap_or_default()
+    };
+    let local_model = LocalModelConfig {
+        selected_model: profile.local_model.selected_model,
+        model_options: profile.local_model.model_options,
+        ..store.get(&ConfigKey::LOCAL_MODEL).unwrap_or_default()
+    };
+
+    set_unlocked(store, &ConfigKey::APP, app)?;
+    set_unlocked(store, &ConfigKey::LOCAL_MODEL, local_model)?;
+    set_unlocked(store, &ConfigKey::SHORTCUTS, profile.shortcuts)?;
+    set_unlocked(store, &ConfigKey::LEARNED_CORRECTIONS, profile.corrections)?;
+    set_unlocked(store, &ConfigKey::SNIPPETS, profile.snippets)
+}
+
+/// Store `value` with the settings locked by IT kept at their managed values,
+/// instead of failing on them
+fn set_unlocked<T: Serialize + DeserializeOwned>(
+    store: &impl ConfigStore,
+    key: &ConfigKey<T>,
+    value: T,
+) -> Result<(), String> {
+    store.set(key, store.with_managed(key, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::mock::MockConfigStore;
+    use crate::config::{Provider, ShortcutsConfig};
+
+    fn select(store: &MockConfigStore, provider: Provider, model: &str) {
+        let app = AppConfig {
+            active_provider: Some(provider),
+            ..store.get(&ConfigKey::APP).unwrap_or_default()
+        };
+        store.set(&ConfigKey::APP, app).unwrap();
+        let local_model = LocalModelConfig {
+            selected_model: Some(model.to_string()),
+            ..store.get(&ConfigKey::LOCAL_MODEL).unwrap_or_default()
+        };
+        store.set(&ConfigKey::LOCAL_MODEL, local_model).unwrap();
+    }
+
+    fn selected(store: &MockConfigStore) -> (Option<Provider>, Option<String>) {
+        let app: AppConfig = store.get(&ConfigKey::APP).unwrap();
+        let local_model: LocalModelConfig = store.get(&ConfigKey::LOCAL_MODEL).unwrap();
+        (app.active_provider, local_model.selected_model)
+    }
+
+    #[test]
+    fn test_switch_round_trip() {
+        let store = MockConfigStore::new();
+        select(&store, Provider::AzureOpenAI, "whisper-small");
+        save_current(&store, "Work").unwrap();
+        select(&store, Provider::Local, "whisper-large-v3-turbo");
+        save_current(&store, "Personal").unwrap();
+
+        switch(&store, "Work").unwrap();
+        assert_eq!(
+            selected(&store),
+            (
+                Some(Provider::AzureOpenAI),
+                Some("whisper-small".to_string())
+            )
+        );
+
+        // Changes made in a profile are kept when switching away
+        select(&store, Provider::OpenAI, "whisper-small");
+        switch(&store, "Personal").unwrap();
+        assert_eq!(
+            selected(&store),
+            (
+                Some(Provider::Local),
+                Some("whisper-large-v3-turbo".to_string())
+            )
+        );
+        switch(&store, "Work").unwrap();
+        assert_eq!(
+            selected(&store),
+            (Some(Provider::OpenAI), Some("whisper-small".to_string()))
+        );
+
+        assert!(switch(&store, "Missing").is_err());
+    }
+
+    #[test]
+    fn test_switch_keeps_device_settings() {
+        let store = MockConfigStore::new();
+        select(&store, Provider::Local, "whisper-small");
+        save_current(&store, "Work").unwrap();
+        save_current(&store, "Personal").unwrap();
+
+        let local_model = LocalModelConfig {
+            models_dir: Some("/Volumes/External/models".to_string()),
+            delete_to_trash: true,
+            ..store.get(&ConfigKey::LOCAL_MODEL).unwrap()
+        };
+        store.set(&ConfigKey::LOCAL_MODEL, local_model).unwrap();
+        switch(&store, "Work").unwrap();
+
+        let local_model: LocalModelConfig = store.get(&ConfigKey::LOCAL_MODEL).unwrap();
+        assert_eq!(
+            local_model.models_dir.as_deref(),
+            Some("/Volumes/External/models")
+        );
+        assert!(local_model.delete_to_trash);
+    }
+
+    #[test]
+    fn test_delete() {
+        let store = MockConfigStore::new();
+        select(&store, Provider::Local, "whisper-small");
+        save_current(&store, "Work").unwrap();
+        save_current(&store, "Personal").unwrap();
+
+        delete(&store, "Personal").unwrap();
+        let profiles = load_profiles(&store);
+        assert_eq!(profiles.active, None);
+        assert_eq!(profiles.profiles.len(), 1);
+        assert_eq!(profiles.profiles[0].name, "Work");
+        // The current settings stay
+        assert_eq!(
+            selected(&store),
+            (Some(Provider::Local), Some("whisper-small".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_profile_saved_by_an_older_version() {
+        let profile: ConfigProfile = serde_json::from_value(serde_json::json!({
+            "name": "Work",
+            "postProcessEnabled": true,
+            "localModel": { "selectedModel": "whisper-small", "modelsDir": "/tmp" }
+        }))
+        .unwrap();
+        assert_eq!(profile.name, "Work");
+        assert!(profile.post_process_enabled);
+        assert_eq!(
+            profile.local_model.selected_model.as_deref(),
+            Some("whisper-small")
+        );
+        assert_eq!(profile.shortcuts, ShortcutsConfig::default());
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert_eq!(validate_name("  Work "), Ok("Work".to_string()));
+        assert!(validate_name("   ").is_err());
+        assert!(validate_name(&"x".repeat(MAX_NAME_LEN + 1)).is_err());
+    }
+}
@@ -0,0 +1,557 @@
+//! Dependencies of the recording [`Controller`](super::Controller), behind traits.
+//!
+//! The app wires in the real microphone, the configured transcription provider and
+//! the Tauri UI; tests substitute mocks so the state machine and the controller
+//! actions can run end-to-end without audio devices, network or windows.
+//!
+//! Backends are `Send` because the controller runs on its own thread; the recording
+//! session is not (cpal streams aren't) and stays on that thread. The transcription
+//! backend is also `Sync`: post-processing runs on a second thread while the host
+//! prepares the paste.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use dictara_core::recorder::RecordingResult;
+use dictara_core::transcription::Transcription;
+use tauri::ipc::Channel;
+use tauri::Manager;
+use tauri_plugin_notification::NotificationExt;
+use tauri_specta::Event;
+
+use crate::audit::AuditLog;
+use crate::clients::{post_process_with_openai, Transcriber, TranscriptionError};
+use crate::config::{
+    self, AppConfig, ClipboardMarker, ConfigKey, ConfigStore, DictationTemplate, LearnedCorrection,
+    PostProcessParams,
+};
+use crate::corrections;
+use crate::insertion::{self, InsertionMethod};
+use crate::keyboard_listener::app_name_for_pid;
+use crate::language::{self, SessionLanguage};
+use crate::network;
+use crate::notes::{self, NoteTarget};
+use crate::sinks::{Dictation, OutputSinks};
+use crate::snippets;
+use crate::templates;
+use crate::text_paster::{self, PreparedClipboard};
+use crate::transcription_eta;
+use crate::ui::held_notification;
+use crate::ui::menu::Menu;
+use crate::ui::window::{close_recording_popup, open_recording_popup};
+use crate::updater;
+use crate::usage;
+
+use super::audio_recorder::{AudioRecorder, RecorderError, Recording};
+use super::events::{
+    InputDeviceChanged, RecordingStateChanged, RecordingTick, TranscriptionHeld,
+    TranscriptionResult,
+};
+use super::raw_mode::RawMode;
+use super::state_manager::RecordingState;
+use super::status_export::StatusExporter;
+
+/// Starts recording sessions
+pub trait AudioRecorderBackend: Send {
+    /// Start a session, streaming audio levels to `level_channel` if set
+    fn start(
+        &self,
+        level_channel: Option<Channel<f32>>,
+    ) -> Result<Box<dyn RecordingSession>, RecorderError>;
+
+    /// Name of the current system default input device
+    fn default_input_name(&self) -> Option<String>;
+}
+
+/// A running recording session
+pub trait RecordingSession {
+    /// Stop recording and finalize the audio file
+    fn stop(self: Box<Self>) -> Result<RecordingResult, RecorderError>;
+
+    /// Continue recording on the system default input device, returns its name
+    fn switch_to_default_input(&mut self) -> Result<String, RecorderError>;
+}
+
+/// Speech-to-text and LLM post-processing
+pub trait TranscriptionBackend: Send + Sync {
+    fn transcribe(
+        &self,
+        audio_path: &Path,
+        duration_ms: u64,
+        language: Option<&str>,
+    ) -> Result<Transcription, TranscriptionError>;
+
+    /// Best-effort post-processing, returns `text` unchanged on failure
+    fn post_process(
+        &self,
+        text: &str,
+        model: &str,
+        prompt: &str,
+        params: PostProcessParams,
+    ) -> String;
+}
+
+/// Source of the current time
+pub trait Clock: Send {
+    fn now(&self) -> SystemTime;
+}
+
+/// App-side effects of the controller: UI, settings, paste
+pub trait ControllerHost: Send {
+    /// Current app configuration
+    fn app_config(&self) -> AppConfig;
+
+    /// Emit a recording state event to the frontend
+    fn emit(&self, event: RecordingStateChanged) -> Result<(), String>;
+
+    /// Tell the frontend the default input device changed while recording
+    fn emit_input_device_changed(&self, event: InputDeviceChanged);
+
+    /// Tell the popup how long the recording has been running
+    fn emit_recording_tick(&self, event: RecordingTick);
+
+    /// Expected time to transcribe `duration_ms` of audio with the configured engine,
+    /// `None` without enough past transcriptions
+    fn estimate_transcription_ms(&self, duration_ms: u64) -> Option<u64>;
+
+    /// Add a successful transcription to the estimates of the configured engine
+    fn record_transcription_time(&self, duration_ms: u64, elapsed: Duration);
+
+    /// Report the recognized and post-processed text of a successful dictation
+    fn emit_transcription_result(&self, event: TranscriptionResult);
+
+    fn show_popup(&self);
+
+    fn hide_popup(&self);
+
+    /// Save the clipboard for the next [`paste`](Self::paste), ahead of time
+    fn prepare_paste(&self);
+
+    /// Insert text into the focused app (short text is typed, longer text pasted),
+    /// falling back to other strategies when the insertion can't be verified
+    fn paste(&self, text: &str, marker: ClipboardMarker) -> Result<(), String>;
+
+    /// Process id of the frontmost app, `None` if unknown
+    fn frontmost_app(&self) -> Option<i32>;
+
+    /// Bundle identifier of the frontmost app (per-app settings), `None` if unknown
+    fn frontmost_app_id(&self) -> Option<String>;
+
+    /// Tell the user a result was held back instead of pasted (notification and event)
+    fn notify_held_result(&self, text: &str);
+
+    /// Enable or disable the "Paste Last Recording" menu item
+    fn set_paste_last_enabled(&self, enabled: bool);
+
+    /// Language pinned for this session, `None` = auto-detect
+    fn pinned_language(&self) -> Option<String>;
+
+    /// Count a transcription in the language statistics
+    fn record_detected_language(&self, language: &str);
+
+    /// Language last used in the app with bundle identifier `app_id`
+    fn app_language(&self, app_id: &str) -> Option<String>;
+
+    /// Remember the language used in the app with bundle identifier `app_id`
+    fn remember_app_language(&self, app_id: &str, language: &str);
+
+    /// Add a pasted dictation to the usage statistics (daily word goal)
+    fn record_dictation(&self, text: &str);
+
+    /// Hand a pasted dictation to the enabled output sinks (journal file, webhook).
+    /// `app` is the process id and `app_id` the bundle identifier of the app it went to.
+    fn deliver_dictation(
+        &self,
+        text: &str,
+        language: Option<&str>,
+        app: Option<i32>,
+        app_id: Option<String>,
+        duration_ms: u64,
+    );
+
+    /// Create a note with `text` in `target` (dictation into a new note)
+    fn create_note(&self, target: NoteTarget, text: &str) -> Result<(), String>;
+
+    /// Corrections the user made repeatedly to dictated text, for the post-processing prompt
+    fn learned_corrections(&self) -> Vec<LearnedCorrection>;
+
+    /// Replace the spoken trigger phrases of voice snippets with their text
+    fn expand_snippets(&self, text: &str) -> String;
+
+    /// Template for this transcription and the dictated content, if any applies
+    fn select_template(&self, text: &str) -> Option<(DictationTemplate, String)>;
+
+    /// Whether raw (verbatim) mode is enabled
+    fn is_raw_mode(&self) -> bool;
+
+    /// Publish the state to external status bars
+    fn publish_status(&mut self, state: RecordingState);
+
+    /// A recording or transcription finished (lets the updater install pending updates)
+    fn on_recording_finished(&self);
+}
+
+// ===== App implementations =====
+
+impl AudioRecorderBackend for AudioRecorder {
+    fn start(
+        &self,
+        level_channel: Option<Channel<f32>>,
+    ) -> Result<Box<dyn RecordingSession>, RecorderError> {
+        let recording = AudioRecorder::start(self, level_channel)?;
+        Ok(Box::new(recording))
+    }
+
+    fn default_input_name(&self) -> Option<String> {
+        dictara_core::recorder::default_input_name()
+    }
+}
+
+impl RecordingSession for Recording {
+    fn stop(self: Box<Self>) -> Result<RecordingResult, RecorderError> {
+        Recording::stop(*self)
+    }
+
+    fn switch_to_default_input(&mut self) -> Result<String, RecorderError> {
+        Recording::switch_to_default_input(self)
+    }
+}
+
+/// Transcribes with the provider configured in the app
+pub struct AppTranscriptionBackend {
+    app_handle: tauri::AppHandle,
+}
+
+impl AppTranscriptionBackend {
+    pub fn new(app_handle: tauri::AppHandle) -> Self {
+        Self { app_handle }
+    }
+}
+
+impl TranscriptionBackend for AppTranscriptionBackend {
+    fn transcribe(
+        &self,
+        audio_path: &Path,
+        duration_ms: u64,
+        language: Option<&str>,
+    ) -> Result<Transcription, TranscriptionError> {
+        // Created on demand so provider/model changes apply to the next recording
+        let transcriber = Transcriber::from_app(&self.app_handle)?;
+        transcriber.transcribe_detailed(audio_path.to_path_buf(), duration_ms, language)
+    }
+
+    fn post_process(
+        &self,
+        text: &str,
+        model: &str,
+        prompt: &str,
+        params: PostProcessParams,
+    ) -> String {
+        let audit = self.app_handle.try_state::<Arc<AuditLog>>();
+        let http_settings =
+            network::http_settings(self.app_handle.state::<config::Config>().inner());
+        post_process_with_openai(
+            text,
+            model,
+            prompt,
+            params,
+            &http_settings,
+            audit.as_ref().map(|audit| audit.inner().as_ref()),
+        )
+    }
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Tauri app: popup window, tray menu, managed state and events
+pub struct TauriHost {
+    app_handle: tauri::AppHandle,
+    menu: Menu,
+    /// Opt-in state export for external status bars
+    status_exporter: StatusExporter,
+    /// Clipboard saved by `prepare_paste`, used by the next paste
+    prepared_clipboard: Mutex<Option<PreparedClipboard>>,
+}
+
+impl TauriHost {
+    pub fn new(app_handle: tauri::AppHandle, menu: Menu) -> Self {
+        let status_exporter = StatusExporter::new(app_handle.clone());
+        Self {
+            app_handle,
+            menu,
+            status_exporter,
+            prepared_clipboard: Mutex::new(None),
+        }
+    }
+
+    /// Key of the configured transcription engine, for the time estimates
+    fn transcription_engine(&self) -> Option<String> {
+        let config_store = self.app_handle.state::<config::Config>();
+        let local_config = config_store
+            .get(&ConfigKey::LOCAL_MODEL)
+            .unwrap_or_default();
+        transcription_eta::engine_key(&self.app_config(), &local_config)
+    }
+}
+
+impl ControllerHost for TauriHost {
+    fn app_config(&self) -> AppConfig {
+        self.app_handle
+            .state::<config::Config>()
+            .get(&ConfigKey::APP)
+            .unwrap_or_default()
+    }
+
+    fn emit(&self, event: RecordingStateChanged) -> Result<(), String> {
+        event.emit(&self.app_handle).map_err(|e| e.to_string())
+    }
+
+    fn emit_input_device_changed(&self, event: InputDeviceChanged) {
+        if let Err(e) = event.emit(&self.app_handle) {
+            log::error!("Failed to emit input device changed event: {}", e);
+        }
+    }
+
+    fn emit_recording_tick(&self, event: RecordingTick) {
+        if let Err(e) = event.emit(&self.app_handle) {
+            log::error!("Failed to emit recording tick event: {}", e);
+        }
+    }
+
+    fn estimate_transcription_ms(&self, duration_ms: u64) -> Option<u64> {
+        let config_store = self.app_handle.state::<config::Config>();
+        let engine = self.transcription_engine()?;
+        transcription_eta::estimate_ms(config_store.inner(), &engine, duration_ms)
+    }
+
+    fn record_transcription_time(&self, duration_ms: u64, elapsed: Duration) {
+        let config_store = self.app_handle.state::<config::Config>();
+        if let Some(engine) = self.transcription_engine() {
+            transcription_eta::record(
+                config_store.inner(),
+                &engine,
+                duration_ms,
+                elapsed.as_millis() as u64,
+            );
+        }
+    }
+
+    fn emit_transcription_result(&self, event: TranscriptionResult) {
+        if let Err(e) = event.emit(&self.app_handle) {
+            log::error!("Failed to emit transcription result event: {}", e);
+        }
+    }
+
+    fn show_popup(&self) {
+        if let Err(e) = open_recording_popup(&self.app_handle) {
+            log::error!("Failed to open recording popup: {}", e);
+        }
+    }
+
+    fn hide_popup(&self) {
+        if let Err(e) = close_recording_popup(&self.app_handle) {
+            log::error!("Failed to close recording popup: {}", e);
+        }
+    }
+
+    fn prepare_paste(&self) {
+        let prepared = text_paster::prepare_clipboard();
+        if let Ok(mut guard) = self.prepared_clipboard.lock() {
+            *guard = Some(prepared);
+        }
+    }
+
+    fn paste(&self, text: &str, marker: ClipboardMarker) -> Result<(), String> {
+        let timing = self.app_config().paste_timing;
+        let prepared = self
+            .prepared_clipboard
+            .lock()
+            .ok()
+            .and_then(|mut guard| guard.take());
+        let inserted = insertion::insert_text_verified(text, marker, timing, prepared.as_ref())?;
+        if inserted.method == InsertionMethod::Clipboard {
+            let result = self
+                .app_handle
+                .notification()
+                .builder()
+                .title("Transcription copied")
+                .body("It couldn't be inserted into the focused app, paste it from the clipboard.")
+                .show();
+            if let Err(e) = result {
+                log::error!("Failed to show clipboard fallback notification: {}", e);
+            }
+        }
+        if let Err(e) = inserted.emit(&self.app_handle) {
+            log::error!("Failed to emit text inserted event: {}", e);
+        }
+        Ok(())
+    }
+
+    fn frontmost_app(&self) -> Option<i32> {
+        frontmost_app_pid()
+    }
+
+    fn frontmost_app_id(&self) -> Option<String> {
+        frontmost_app_bundle_id()
+    }
+
+    fn notify_held_result(&self, text: &str) {
+        let event = TranscriptionHeld {
+            text: text.to_string(),
+        };
+        if let Err(e) = event.emit(&self.app_handle) {
+            log::error!("Failed to emit transcription held event: {}", e);
+        }
+
+        held_notification::show(&self.app_handle, &notification_preview(text));
+    }
+
+    fn set_paste_last_enabled(&self, enabled: bool) {
+        let result = if enabled {
+            self.menu.set_paste_last_active()
+        } else {
+            self.menu.set_paste_last_inactive()
+        };
+        if let Err(e) = result {
+            log::error!("Failed to update paste menu item: {}", e);
+        }
+    }
+
+    fn pinned_language(&self) -> Option<String> {
+        self.app_handle.state::<Arc<SessionLanguage>>().pinned()
+    }
+
+    fn record_detected_language(&self, detected_language: &str) {
+        let config_store = self.app_handle.state::<config::Config>();
+        language::record_detected_language(config_store.inner(), detected_language);
+    }
+
+    fn app_language(&self, app_id: &str) -> Option<String> {
+        let config_store = self.app_handle.state::<config::Config>();
+        language::app_language(config_store.inner(), app_id)
+    }
+
+    fn remember_app_language(&self, app_id: &str, used_language: &str) {
+        let config_store = self.app_handle.state::<config::Config>();
+        language::remember_app_language(config_store.inner(), app_id, used_language);
+    }
+
+    fn record_dictation(&self, text: &str) {
+        let config_store = self.app_handle.state::<config::Config>();
+        let goal = self.app_config().daily_word_goal;
+        let today = chrono::Local::now().date_naive();
+        if let Some(reached) = usage::record_dictated_words(config_store.inner(), text, goal, today)
+        {
+            log::info!(
+                "Daily word goal reached: {} words (goal {})",
+                reached.words_today,
+                reached.goal
+            );
+            if let Err(e) = reached.emit(&self.app_handle) {
+                log::error!("Failed to emit word goal event: {}", e);
+            }
+        }
+    }
+
+    fn deliver_dictation(
+        &self,
+        text: &str,
+        language: Option<&str>,
+        app: Option<i32>,
+        app_id: Option<String>,
+        duration_ms: u64,
+    ) {
+        let Some(sinks) = self.app_handle.try_state::<OutputSinks>() else {
+            return;
+        };
+        sinks.deliver(Dictation {
+            text: text.to_string(),
+            language: language.map(str::to_string),
+            app_id,
+            app_name: app.and_then(app_name_for_pid),
+            timestamp: chrono::Local::now(),
+            duration_ms,
+        });
+    }
+
+    fn create_note(&self, target: NoteTarget, text: &str) -> Result<(), String> {
+        notes::create_note(&self.app_handle, target, text)
+    }
+
+    fn learned_corrections(&self) -> Vec<LearnedCorrection> {
+        let config_store = self.app_handle.state::<config::Config>();
+        corrections::learned_corrections(config_store.inner())
+    }
+
+    fn expand_snippets(&self, text: &str) -> String {
+        let config_store = self.app_handle.state::<config::Config>();
+        snippets::expand_snippets(
+            &snippets::load_snippets(config_store.inner()).snippets,
+            text,
+        )
+    }
+
+    fn select_template(&self, text: &str) -> Option<(DictationTemplate, String)> {
+        let config_store = self.app_handle.state::<config::Config>();
+        templates::select_template(config_store.inner(), text)
+    }
+
+    fn is_raw_mode(&self) -> bool {
+        self.app_handle.state::<Arc<RawMode>>().is_enabled()
+    }
+
+    fn publish_status(&mut self, state: RecordingState) {
+        self.status_exporter.publish(state);
+    }
+
+    fn on_recording_finished(&self) {
+        updater::on_recording_finished(&self.app_handle);
+    }
+}
+
+/// Characters of the transcription shown in the notification
+const NOTIFICATION_PREVIEW_CHARS: usize = 120;
+
+fn notification_preview(text: &str) -> String {
+    if text.chars().count() <= NOTIFICATION_PREVIEW_CHARS {
+        return text.to_string();
+    }
+    let preview: String = text.chars().take(NOTIFICATION_PREVIEW_CHARS).collect();
+    format!("{}…", preview.trim_end())
+}
+
+#[cfg(target_os = "macos")]
+fn frontmost_app_pid() -> Option<i32> {
+    use objc2_app_kit::NSWorkspace;
+
+    NSWorkspace::sharedWorkspace()
+        .frontmostApplication()
+        .map(|app| app.processIdentifier())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn frontmost_app_pid() -> Option<i32> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn frontmost_app_bundle_id() -> Option<String> {
+    use objc2_app_kit::NSWorkspace;
+
+    NSWorkspace::sharedWorkspace()
+        .frontmostApplication()
+        .and_then(|app| app.bundleIdentifier())
+        .map(|id| id.to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn frontmost_app_bundle_id() -> Option<String> {
+    None
+}
@@ -1,29 +1,115 @@
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::oneshot;
+
+use crate::notes::NoteTarget;
+
+use super::events::RecordingStateChanged;
 use super::RecordingEvent;
 
+/// Receives the outcome of a stopped recording (`Stopped`, `Cancelled` or `Error`).
+/// Dropped without an outcome when there was no recording to stop.
+#[derive(Debug, Clone)]
+pub struct ResultWaiter(Arc<Mutex<Option<oneshot::Sender<RecordingStateChanged>>>>);
+
+impl ResultWaiter {
+    pub fn new() -> (Self, oneshot::Receiver<RecordingStateChanged>) {
+        let (tx, rx) = oneshot::channel();
+        (Self(Arc::new(Mutex::new(Some(tx)))), rx)
+    }
+
+    /// Resolve the waiting command (only the first outcome is delivered)
+    pub fn send(&self, outcome: RecordingStateChanged) {
+        let sender = self.0.lock().ok().and_then(|mut guard| guard.take());
+        if let Some(sender) = sender {
+            // The command may have been abandoned by the frontend
+            let _ = sender.send(outcome);
+        }
+    }
+}
+
+/// Where the text of a recording goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictationDestination {
+    /// Pasted into the app that was frontmost when the recording started
+    Paste,
+    /// A new note
+    Note(NoteTarget),
+}
+
 /// Commands for controlling audio recording
 /// These are sent through channels (NOT Tauri events) for zero-overhead internal communication
 #[derive(Debug, Clone)]
 pub enum RecordingCommand {
     /// Start a new recording session
     StartRecording,
+    /// Start a new hands-free (locked) recording session whose text goes to a new note
+    /// instead of being pasted
+    StartNoteDictation(NoteTarget),
     /// Stop the current recording and begin transcription
     StopRecording,
+    /// Stop the current recording and report its outcome once the pipeline completes
+    StopRecordingAndWait(ResultWaiter),
     /// Lock the recording (Fn release will be ignored, press Fn again to stop)
     LockRecording,
     /// Cancel the current recording without transcribing
     Cancel,
     /// Retry transcription of the last failed recording
     RetryTranscription,
+    /// Discard the last failed recording instead of retrying it (deletes its audio)
+    DiscardError,
+    /// The input device was disconnected while recording
+    DeviceLost,
+    /// The system default input device changed
+    DefaultInputChanged,
+    /// Periodic tick while recording (see `RecordingTicker`)
+    Tick,
+    /// The app is quitting: end any recording, keeping its audio for recovery on the
+    /// next launch, then stop the controller. Acknowledged through the sender.
+    Shutdown(std::sync::mpsc::Sender<()>),
 }
 
-impl From<RecordingCommand> for RecordingEvent {
-    fn from(command: RecordingCommand) -> Self {
-        match command {
-            RecordingCommand::StartRecording => RecordingEvent::Start,
-            RecordingCommand::StopRecording => RecordingEvent::Stop,
-            RecordingCommand::LockRecording => RecordingEvent::Lock,
-            RecordingCommand::Cancel => RecordingEvent::Cancel,
-            RecordingCommand::RetryTranscription => RecordingEvent::Retry,
+impl RecordingCommand {
+    /// Event of the command for the state machine. `None` for the commands the
+    /// controller handles itself: `Shutdown` ends it, `Tick` only updates the popup
+    /// (or becomes `StopRecording` at the length limit) and `DiscardError` only
+    /// applies in Ready.
+    pub fn event(&self) -> Option<RecordingEvent> {
+        match self {
+            RecordingCommand::StartRecording => Some(RecordingEvent::Start),
+            RecordingCommand::StartNoteDictation(_) => Some(RecordingEvent::StartLocked),
+            RecordingCommand::StopRecording | RecordingCommand::StopRecordingAndWait(_) => {
+                Some(RecordingEvent::Stop)
+            }
+            RecordingCommand::LockRecording => Some(RecordingEvent::Lock),
+            RecordingCommand::Cancel => Some(RecordingEvent::Cancel),
+            RecordingCommand::RetryTranscription => Some(RecordingEvent::Retry),
+            RecordingCommand::DeviceLost => Some(RecordingEvent::DeviceLost),
+            RecordingCommand::DefaultInputChanged => Some(RecordingEvent::DefaultInputChanged),
+            RecordingCommand::Shutdown(_)
+            | RecordingCommand::Tick
+            | RecordingCommand::DiscardError => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_controller_commands_have_no_event() {
+        let (done, _) = std::sync::mpsc::channel();
+        assert_eq!(RecordingCommand::Shutdown(done).event(), None);
+        assert_eq!(RecordingCommand::Tick.event(), None);
+        assert_eq!(RecordingCommand::DiscardError.event(), None);
+        assert_eq!(
+            RecordingCommand::StartNoteDictation(NoteTarget::Obsidian).event(),
+            Some(RecordingEvent::StartLocked)
+        );
+        assert_eq!(
+            RecordingCommand::Cancel.event(),
+            Some(RecordingEvent::Cancel)
+        );
+    }
+}
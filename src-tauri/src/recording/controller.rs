@@ -1,23 +1,32 @@
-use std::path::PathBuf;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
-use tauri::{ipc::Channel, Manager};
-use tauri_specta::Event;
-use tokio::sync::mpsc::Receiver;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use tauri::ipc::Channel;
+use tokio::sync::mpsc::{Receiver, WeakSender};
 
-use crate::clients::{post_process_with_openai, Transcriber, TranscriptionError};
+use crate::clients::TranscriptionError;
 use crate::config::{
-    self, AppConfig, ConfigKey, ConfigStore, DEFAULT_MIN_SPEECH_DURATION_MS,
+    InputDeviceChangePolicy, PostProcessParams, DEFAULT_MIN_SPEECH_DURATION_MS,
     MAX_ALLOWED_SPEECH_DURATION_MS, MIN_ALLOWED_SPEECH_DURATION_MS,
 };
+use crate::corrections;
+use crate::document_context::DocumentContext;
 use crate::recording::{
     audio_recorder::{cleanup_recording_file, AudioRecorder},
-    commands::RecordingCommand,
-    events::RecordingStateChanged,
-    LastRecordingState, Recording, RecordingAction, RecordingStateManager, TransitionResult,
+    backend::{
+        AppTranscriptionBackend, AudioRecorderBackend, Clock, ControllerHost, RecordingSession,
+        SystemClock, TauriHost, TranscriptionBackend,
+    },
+    commands::{DictationDestination, RecordingCommand, ResultWaiter},
+    events::{InputDeviceChanged, RecordingStateChanged, RecordingTick, TranscriptionResult},
+    text_diff::diff_words,
+    ticker::{recording_limit, RecordingTicker},
+    LastRecordingState, RecordingAction, RecordingStateManager, TransitionResult,
 };
+use crate::redaction;
+use crate::templates;
 use crate::ui::menu::Menu;
-use crate::ui::window::{close_recording_popup, open_recording_popup};
-use crate::updater;
 
 /// Bytes per second for 16kHz mono 16-bit audio (~32KB/s)
 const AUDIO_BYTES_PER_SECOND: u64 = 32000;
@@ -85,6 +94,23 @@ impl ActionError {
         }
     }
 
+    /// Create a device-lost error (microphone unplugged mid-recording)
+    ///
+    /// Keeps the audio recorded before the disconnect for retry, if it has speech.
+    fn device_lost(audio_file_path: Option<String>) -> Self {
+        let user_message = if audio_file_path.is_some() {
+            "Microphone disconnected. Retry to transcribe what was recorded."
+        } else {
+            "Microphone disconnected. Please reconnect it and try again."
+        };
+        Self {
+            error_type: "recording".to_string(),
+            error_message: "Input device disconnected during recording".to_string(),
+            user_message: user_message.to_string(),
+            audio_file_path,
+        }
+    }
+
     /// Create a no-speech error (user didn't say anything)
     fn no_speech() -> Self {
         Self {
@@ -96,55 +122,169 @@ impl ActionError {
     }
 }
 
+/// External dependencies of the controller (see [`super::backend`])
+pub struct ControllerBackends {
+    pub host: Box<dyn ControllerHost>,
+    pub recorder: Box<dyn AudioRecorderBackend>,
+    pub transcription: Box<dyn TranscriptionBackend>,
+    pub clock: Box<dyn Clock>,
+}
+
 pub struct Controller {
     command_rx: Receiver<RecordingCommand>,
-    audio_recorder: AudioRecorder,
-    app_handle: tauri::AppHandle,
+    host: Box<dyn ControllerHost>,
+    audio_recorder: Box<dyn AudioRecorderBackend>,
+    transcription: Box<dyn TranscriptionBackend>,
+    clock: Box<dyn Clock>,
     state_manager: Arc<RecordingStateManager>,
     audio_level_channel: Arc<Mutex<Option<Channel<f32>>>>,
     last_recording_state: LastRecordingState,
-    menu: Menu,
+    /// Frontmost document context captured when the current recording started
+    document_context: Mutex<Option<DocumentContext>>,
+    /// Frontmost app when the current recording started (the paste target)
+    paste_target: Mutex<Option<i32>>,
+    /// Bundle identifier of the frontmost app when the current recording started
+    target_app_id: Mutex<Option<String>>,
+    /// `stop_recording_and_wait` call resolved by the outcome of the running action
+    result_waiter: Mutex<Option<ResultWaiter>>,
+    /// Ticks while a recording runs
+    ticker: RecordingTicker,
+    /// When the current recording started
+    recording_started_at: Mutex<Option<SystemTime>>,
+    /// Where the text of the current (or last failed) recording goes
+    destination: Mutex<DictationDestination>,
 }
 
 impl Controller {
     pub fn new(
         command_rx: Receiver<RecordingCommand>,
+        tick_tx: WeakSender<RecordingCommand>,
         app_handle: tauri::AppHandle,
         state_manager: Arc<RecordingStateManager>,
         audio_level_channel: Arc<Mutex<Option<Channel<f32>>>>,
         last_recording_state: LastRecordingState,
         menu: Menu,
     ) -> Self {
-        let audio_recorder = AudioRecorder::new(app_handle.clone());
+        let backends = ControllerBackends {
+            host: Box::new(TauriHost::new(app_handle.clone(), menu)),
+            recorder: Box::new(AudioRecorder::new(app_handle.clone())),
+            transcription: Box::new(AppTranscriptionBackend::new(app_handle)),
+            clock: Box::new(SystemClock),
+        };
 
+        Self::with_backends(
+            command_rx,
+            tick_tx,
+            state_manager,
+            audio_level_channel,
+            last_recording_state,
+            backends,
+        )
+    }
+
+    /// Create a controller with explicit backends (mocks in tests)
+    ///
+    /// `tick_tx` sends [`RecordingCommand::Tick`] back to the controller while recording.
+    pub fn with_backends(
+        command_rx: Receiver<RecordingCommand>,
+        tick_tx: WeakSender<RecordingCommand>,
+        state_manager: Arc<RecordingStateManager>,
+        audio_level_channel: Arc<Mutex<Option<Channel<f32>>>>,
+        last_recording_state: LastRecordingState,
+        backends: ControllerBackends,
+    ) -> Self {
         // Ensure we start in Ready state
         state_manager.reset();
 
         Controller {
             command_rx,
-            audio_recorder,
-            app_handle,
+            host: backends.host,
+            audio_recorder: backends.recorder,
+            transcription: backends.transcription,
+            clock: backends.clock,
             state_manager,
             audio_level_channel,
             last_recording_state,
-            menu,
+            document_context: Mutex::new(None),
+            paste_target: Mutex::new(None),
+            target_app_id: Mutex::new(None),
+            result_waiter: Mutex::new(None),
+            ticker: RecordingTicker::new(tick_tx),
+            recording_started_at: Mutex::new(None),
+            destination: Mutex::new(DictationDestination::Paste),
         }
     }
 
     /// Main control loop - consumes self, runs in blocking thread
     pub fn run(mut self) {
         // Recording session lives here (not Send, so stays in this thread)
-        let mut current_recording: Option<Recording> = None;
+        let mut current_recording: Option<Box<dyn RecordingSession>> = None;
+
+        self.host.publish_status(self.state_manager.current());
+
+        while let Some(mut command) = self.command_rx.blocking_recv() {
+            if let RecordingCommand::Shutdown(done) = &command {
+                self.shut_down(current_recording.take());
+                let _ = done.send(());
+                return;
+            }
+
+            // Ticks bypass the state machine (they would flood its history), unless the
+            // recording reached its length limit and is stopped
+            if let RecordingCommand::Tick = command {
+                if current_recording.is_none() || !self.handle_tick() {
+                    continue;
+                }
+                command = RecordingCommand::StopRecording;
+            }
+
+            if let RecordingCommand::DiscardError = command {
+                self.discard_error();
+                continue;
+            }
+
+            let waiter = match &command {
+                RecordingCommand::StopRecordingAndWait(waiter) => Some(waiter.clone()),
+                _ => None,
+            };
+            let destination = match &command {
+                RecordingCommand::StartRecording => Some(DictationDestination::Paste),
+                RecordingCommand::StartNoteDictation(target) => {
+                    Some(DictationDestination::Note(*target))
+                }
+                _ => None,
+            };
+
+            let Some(event) = command.event() else {
+                // Shutdown, ticks and discards were handled above
+                continue;
+            };
 
-        while let Some(command) = self.command_rx.blocking_recv() {
             // Attempt state transition
-            match self.state_manager.transition(command.into()) {
-                Ok(TransitionResult::Changed { action, .. }) => {
+            match self.state_manager.transition(event) {
+                Ok(TransitionResult::Changed { to, action, .. }) => {
+                    self.host.publish_status(to);
+                    self.set_result_waiter(waiter);
+                    if let Some(destination) = destination {
+                        self.set_destination(destination);
+                    }
+
                     if let Some(action) = action {
                         if let Err(error) = self.execute_action(action, &mut current_recording) {
                             self.handle_action_error(error);
                         }
                     }
+                    // Actions run to completion, a waiter left unresolved had nothing to wait for
+                    self.set_result_waiter(None);
+
+                    // Actions reset the state machine when they finish (Transcribing -> Ready)
+                    self.host.publish_status(self.state_manager.current());
+
+                    match (&current_recording, self.ticker.is_running()) {
+                        (Some(_), false) => self.ticker.start(),
+                        (None, true) => self.ticker.stop(),
+                        _ => {}
+                    }
                 }
                 Ok(TransitionResult::Unchanged) => {
                     // Valid event but no state change (edge case)
@@ -156,6 +296,122 @@ impl Controller {
         }
     }
 
+    /// End the running recording before the app quits
+    ///
+    /// The audio file is kept when it holds enough speech: it is offered for retry on
+    /// the next launch (see `recover_interrupted_recording`). Runs on the way out, so
+    /// nothing is shown to the user.
+    fn shut_down(&mut self, recording: Option<Box<dyn RecordingSession>>) {
+        if let Some(recording) = recording {
+            match recording.stop() {
+                Ok(result) if result.speech_duration_ms >= self.get_min_speech_duration_ms() => {
+                    log::info!(
+                        "Quitting mid-recording, kept {} for recovery",
+                        result.file_path
+                    );
+                }
+                Ok(result) => {
+                    if CLEANUP_AUDIO_AFTER_TRANSCRIPTION {
+                        cleanup_recording_file(&result.file_path);
+                    }
+                }
+                Err(e) => log::error!("Failed to stop recording on shutdown: {:?}", e),
+            }
+        }
+
+        self.state_manager.reset();
+        self.host.publish_status(self.state_manager.current());
+        log::info!("Recording controller stopped");
+    }
+
+    /// Delete the audio of the last failed recording, which won't be retried
+    ///
+    /// Ignored unless Ready: a new recording may have started since the error.
+    fn discard_error(&self) {
+        let state = self.state_manager.current();
+        if state.is_busy() {
+            log::warn!("Ignoring DiscardError in {} state", state);
+            return;
+        }
+
+        match self.last_recording_state.lock() {
+            Ok(mut last_recording) => last_recording.discard_audio(),
+            Err(e) => log::error!("Failed to lock last_recording_state: {}", e),
+        }
+        self.host.publish_status(state);
+    }
+
+    /// Report the elapsed and remaining time of the recording.
+    /// Returns whether the recording reached its length limit.
+    fn handle_tick(&self) -> bool {
+        let started_at = match self.recording_started_at.lock() {
+            Ok(guard) => *guard,
+            Err(e) => {
+                log::error!("Failed to lock recording_started_at: {}", e);
+                None
+            }
+        };
+        let Some(started_at) = started_at else {
+            return false;
+        };
+
+        let elapsed = self
+            .clock
+            .now()
+            .duration_since(started_at)
+            .unwrap_or_default();
+        let limit = recording_limit(&self.host.app_config());
+        let remaining = limit.saturating_sub(elapsed);
+        self.host.emit_recording_tick(RecordingTick {
+            elapsed_ms: elapsed.as_millis() as u64,
+            remaining_ms: remaining.as_millis() as u64,
+        });
+
+        if remaining.is_zero() {
+            log::info!("Recording reached its length limit ({:?}), stopping", limit);
+            return true;
+        }
+        false
+    }
+
+    fn set_result_waiter(&self, waiter: Option<ResultWaiter>) {
+        match self.result_waiter.lock() {
+            Ok(mut guard) => *guard = waiter,
+            Err(e) => log::error!("Failed to lock result_waiter: {}", e),
+        }
+    }
+
+    fn set_destination(&self, destination: DictationDestination) {
+        match self.destination.lock() {
+            Ok(mut guard) => *guard = destination,
+            Err(e) => log::error!("Failed to lock destination: {}", e),
+        }
+    }
+
+    fn destination(&self) -> DictationDestination {
+        match self.destination.lock() {
+            Ok(guard) => *guard,
+            Err(e) => {
+                log::error!("Failed to lock destination: {}", e);
+                DictationDestination::Paste
+            }
+        }
+    }
+
+    /// Emit the final state of a recording (`Stopped`, `Cancelled` or `Error`), also
+    /// resolving a `stop_recording_and_wait` call
+    fn emit_outcome(&self, event: RecordingStateChanged) -> Result<(), String> {
+        let waiter = self
+            .result_waiter
+            .lock()
+            .ok()
+            .and_then(|mut guard| guard.take());
+        if let Some(waiter) = waiter {
+            waiter.send(event.clone());
+        }
+        self.host.emit(event)
+    }
+
     /// Centralized error handler for all action failures
     ///
     /// This ensures consistent error handling across all actions:
@@ -179,6 +435,7 @@ impl Controller {
             Ok(mut last_recording) => {
                 last_recording.text = None;
                 last_recording.timestamp = None;
+                last_recording.duration_ms = None;
                 // Keep audio file for retry if available
                 last_recording.audio_file_path = error.audio_file_path.clone();
             }
@@ -188,9 +445,7 @@ impl Controller {
         }
 
         // Disable paste menu item since there's no valid text
-        if let Err(e) = self.menu.set_paste_last_inactive() {
-            log::error!("Failed to disable paste menu item: {}", e);
-        }
+        self.host.set_paste_last_enabled(false);
 
         // Emit error event to frontend
         let error_event = RecordingStateChanged::Error {
@@ -200,7 +455,7 @@ impl Controller {
             audio_file_path: error.audio_file_path,
         };
 
-        if let Err(e) = error_event.emit(&self.app_handle) {
+        if let Err(e) = self.emit_outcome(error_event) {
             log::error!("Failed to emit error event: {}", e);
         }
     }
@@ -209,7 +464,7 @@ impl Controller {
     fn execute_action(
         &self,
         action: RecordingAction,
-        recording: &mut Option<Recording>,
+        recording: &mut Option<Box<dyn RecordingSession>>,
     ) -> Result<(), ActionError> {
         match action {
             RecordingAction::StartRecording => {
@@ -221,31 +476,68 @@ impl Controller {
                     self.handle_stop(rec)?;
                 }
                 // Notify updater that recording/transcription finished
-                updater::on_recording_finished(&self.app_handle);
+                self.host.on_recording_finished();
             }
             RecordingAction::CancelRecording => {
                 if let Some(rec) = recording.take() {
                     self.handle_cancel(rec)?;
                 }
                 // Notify updater that recording was cancelled
-                updater::on_recording_finished(&self.app_handle);
+                self.host.on_recording_finished();
             }
             RecordingAction::RetryTranscription => {
                 self.handle_retry_transcription()?;
                 // Notify updater that transcription finished
-                updater::on_recording_finished(&self.app_handle);
+                self.host.on_recording_finished();
+            }
+            RecordingAction::RecoverLostDevice => {
+                if let Some(rec) = recording.as_mut() {
+                    if self.follow_default_input(rec.as_mut()) {
+                        return Ok(());
+                    }
+                }
+                let result = match recording.take() {
+                    Some(rec) => self.handle_device_lost(rec),
+                    None => Ok(()),
+                };
+                // Notify updater that recording ended
+                self.host.on_recording_finished();
+                result?;
+            }
+            RecordingAction::SwitchInputDevice => {
+                if let Some(rec) = recording.as_mut() {
+                    self.handle_default_input_changed(rec.as_mut());
+                }
             }
         }
         Ok(())
     }
 
-    fn handle_start(&self) -> Result<Recording, ActionError> {
-        // Show recording popup window
-        if let Err(e) = open_recording_popup(&self.app_handle) {
-            log::error!("Failed to open recording popup: {}", e);
+    fn handle_start(&self) -> Result<Box<dyn RecordingSession>, ActionError> {
+        // Read the frontmost document before our popup shows up (opt-in)
+        let document_context = DocumentContext::capture(&self.host.app_config());
+        match self.document_context.lock() {
+            Ok(mut guard) => *guard = document_context,
+            Err(e) => log::error!("Failed to lock document_context: {}", e),
+        }
+        match self.paste_target.lock() {
+            Ok(mut guard) => *guard = self.host.frontmost_app(),
+            Err(e) => log::error!("Failed to lock paste_target: {}", e),
+        }
+        match self.target_app_id.lock() {
+            Ok(mut guard) => *guard = self.host.frontmost_app_id(),
+            Err(e) => log::error!("Failed to lock target_app_id: {}", e),
         }
 
-        if let Err(e) = RecordingStateChanged::Started.emit(&self.app_handle) {
+        match self.recording_started_at.lock() {
+            Ok(mut guard) => *guard = Some(self.clock.now()),
+            Err(e) => log::error!("Failed to lock recording_started_at: {}", e),
+        }
+
+        // Show recording popup window
+        self.host.show_popup();
+
+        if let Err(e) = self.host.emit(RecordingStateChanged::Started) {
             return Err(ActionError::recording(
                 format!("Failed to emit started event: {}", e),
                 "Failed to start recording".to_string(),
@@ -263,16 +555,14 @@ impl Controller {
 
         let recording = self.audio_recorder.start(level_channel).map_err(|e| {
             // Close popup since recording failed to start
-            if let Err(close_err) = close_recording_popup(&self.app_handle) {
-                log::error!("Failed to close recording popup: {}", close_err);
-            }
+            self.host.hide_popup();
             ActionError::recording(format!("{:?}", e), e.user_message())
         })?;
 
         Ok(recording)
     }
 
-    fn handle_stop(&self, recording: Recording) -> Result<(), ActionError> {
+    fn handle_stop(&self, recording: Box<dyn RecordingSession>) -> Result<(), ActionError> {
         let recording_result = recording
             .stop()
             .map_err(|e| ActionError::stop(format!("{:?}", e), None))?;
@@ -297,9 +587,7 @@ impl Controller {
             return Err(ActionError::no_speech());
         }
 
-        if let Err(e) = RecordingStateChanged::Transcribing.emit(&self.app_handle) {
-            log::error!("Failed to emit recording-transcribing event: {:?}", e);
-        }
+        self.emit_transcribing(recording_result.speech_duration_ms);
 
         // Use speech_duration_ms for validation (actual content duration, not wall-clock time)
         self.perform_transcription(
@@ -309,9 +597,7 @@ impl Controller {
     }
 
     fn get_min_speech_duration_ms(&self) -> u64 {
-        let config_store = self.app_handle.state::<config::Config>();
-        let app_config: AppConfig = config_store.get(&ConfigKey::APP).unwrap_or_default();
-        let configured = app_config.min_speech_duration_ms;
+        let configured = self.host.app_config().min_speech_duration_ms;
 
         if (MIN_ALLOWED_SPEECH_DURATION_MS..=MAX_ALLOWED_SPEECH_DURATION_MS).contains(&configured) {
             configured
@@ -325,7 +611,7 @@ impl Controller {
         }
     }
 
-    fn handle_cancel(&self, recording: Recording) -> Result<(), ActionError> {
+    fn handle_cancel(&self, recording: Box<dyn RecordingSession>) -> Result<(), ActionError> {
         // Stop recording (creates file but we don't use it)
         let recording_result = recording
             .stop()
@@ -337,18 +623,83 @@ impl Controller {
         }
 
         // Hide recording popup window
-        if let Err(e) = close_recording_popup(&self.app_handle) {
-            log::error!("Failed to close recording popup: {}", e);
-        }
+        self.host.hide_popup();
 
         // Emit cancellation event for frontend awareness
-        RecordingStateChanged::Cancelled
-            .emit(&self.app_handle)
+        self.emit_outcome(RecordingStateChanged::Cancelled)
             .map_err(|e| ActionError::cancel(format!("Failed to emit cancelled event: {}", e)))?;
 
         Ok(())
     }
 
+    /// Apply the input device change policy to the running recording
+    fn handle_default_input_changed(&self, recording: &mut dyn RecordingSession) {
+        match self.host.app_config().input_device_change_policy {
+            InputDeviceChangePolicy::Follow => {
+                self.follow_default_input(recording);
+            }
+            InputDeviceChangePolicy::Notify => {
+                let device_name = self.audio_recorder.default_input_name();
+                log::info!(
+                    "Default input changed to {:?}, recording continues on the previous device",
+                    device_name
+                );
+                self.host.emit_input_device_changed(InputDeviceChanged {
+                    device_name,
+                    switched: false,
+                });
+            }
+            InputDeviceChangePolicy::Ignore => {
+                log::info!("Default input changed, ignored by policy");
+            }
+        }
+    }
+
+    /// Move the recording to the default input device if the policy allows it
+    ///
+    /// Returns whether the recording now runs on the default device.
+    fn follow_default_input(&self, recording: &mut dyn RecordingSession) -> bool {
+        if self.host.app_config().input_device_change_policy != InputDeviceChangePolicy::Follow {
+            return false;
+        }
+
+        match recording.switch_to_default_input() {
+            Ok(device_name) => {
+                self.host.emit_input_device_changed(InputDeviceChanged {
+                    device_name: Some(device_name),
+                    switched: true,
+                });
+                true
+            }
+            Err(e) => {
+                log::warn!("Failed to switch to the default input device: {:?}", e);
+                false
+            }
+        }
+    }
+
+    /// Stop a recording whose microphone was unplugged
+    ///
+    /// Always fails with a device error so the popup tells the user what happened
+    /// (the error handler resets the state machine to Ready).
+    fn handle_device_lost(&self, recording: Box<dyn RecordingSession>) -> Result<(), ActionError> {
+        log::warn!("Input device disconnected, stopping recording");
+
+        let recording_result = recording
+            .stop()
+            .map_err(|e| ActionError::stop(format!("{:?}", e), None))?;
+
+        // Keep what was said before the disconnect so the user can retry
+        if recording_result.speech_duration_ms >= self.get_min_speech_duration_ms() {
+            return Err(ActionError::device_lost(Some(recording_result.file_path)));
+        }
+
+        if CLEANUP_AUDIO_AFTER_TRANSCRIPTION {
+            cleanup_recording_file(&recording_result.file_path);
+        }
+        Err(ActionError::device_lost(None))
+    }
+
     fn handle_retry_transcription(&self) -> Result<(), ActionError> {
         // Get audio file path from last recording state
         let (audio_file_path, duration_ms) = {
@@ -379,54 +730,254 @@ impl Controller {
             (path, duration_ms)
         };
 
-        // Emit transcribing event
-        if let Err(e) = RecordingStateChanged::Transcribing.emit(&self.app_handle) {
-            log::error!("Failed to emit recording-transcribing event: {:?}", e);
-        }
+        self.emit_transcribing(duration_ms);
 
         self.perform_transcription(&audio_file_path, duration_ms)
     }
 
+    /// Emit the transcribing event with the expected transcription time
+    fn emit_transcribing(&self, duration_ms: u64) {
+        let estimated_ms = self.host.estimate_transcription_ms(duration_ms);
+        if let Err(e) = self
+            .host
+            .emit(RecordingStateChanged::Transcribing { estimated_ms })
+        {
+            log::error!("Failed to emit recording-transcribing event: {:?}", e);
+        }
+    }
+
     /// Shared transcription logic used by both handle_stop and handle_retry_transcription.
     ///
-    /// Uses the unified Transcriber abstraction which handles both API-based
-    /// (OpenAI, Azure) and local (Whisper) transcription transparently.
+    /// The transcription backend handles both API-based (OpenAI, Azure) and
+    /// local (Whisper) transcription transparently.
     fn perform_transcription(
         &self,
         audio_file_path: &str,
         duration_ms: u64,
     ) -> Result<(), ActionError> {
-        // Create transcriber from app handle - handles all providers uniformly
-        let transcriber = Transcriber::from_app(&self.app_handle)
-            .map_err(|e| ActionError::transcription(&e, audio_file_path.to_string()))?;
+        let target_app_id = self
+            .target_app_id
+            .lock()
+            .ok()
+            .and_then(|guard| guard.clone());
+        // Use the language pinned for this session, or the one last used in the target app
+        let pinned_language = self.host.pinned_language().or_else(|| {
+            target_app_id
+                .as_deref()
+                .and_then(|app_id| self.host.app_language(app_id))
+        });
 
-        // Transcribe - the transcriber handles API vs local internally
-        let text = transcriber
-            .transcribe(PathBuf::from(audio_file_path), duration_ms)
-            .map_err(|e| ActionError::transcription(&e, audio_file_path.to_string()))?;
+        let started_at = Instant::now();
+        let transcription = match self.transcription.transcribe(
+            Path::new(audio_file_path),
+            duration_ms,
+            pinned_language.as_deref(),
+        ) {
+            Ok(transcription) => {
+                self.host
+                    .record_transcription_time(duration_ms, started_at.elapsed());
+                transcription
+            }
+            Err(TranscriptionError::Cancelled) => {
+                return self.handle_transcription_cancelled(audio_file_path)
+            }
+            Err(e) => return Err(ActionError::transcription(&e, audio_file_path.to_string())),
+        };
+        let text = transcription.text;
+
+        let app_config = self.host.app_config();
+
+        // Only a language the model detected: a pinned one would count (and remember) itself
+        if let Some(detected_language) = transcription
+            .language
+            .as_deref()
+            .filter(|_| transcription.detected)
+        {
+            self.host.record_detected_language(detected_language);
+            if let Some(app_id) = &target_app_id {
+                self.host.remember_app_language(app_id, detected_language);
+            }
+        }
+
+        // Raw mode: paste exactly what the recognizer returned
+        if self.host.is_raw_mode() {
+            log::info!("Raw mode enabled, skipping post-processing, templates and filters");
+            return self.handle_transcription_success(
+                &text,
+                transcription.language,
+                audio_file_path,
+                duration_ms,
+            );
+        }
+
+        // Select the template on the raw text, before post-processing can reword the keyword
+        let (template, text) = match self.host.select_template(&text) {
+            Some((template, content)) => (Some(template), content),
+            None => (None, text),
+        };
 
-        let config_store = self.app_handle.state::<config::Config>();
-        let app_config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+        // The detected language, or the pinned one if the provider doesn't report it
+        let language = transcription.language.clone().or(pinned_language);
+
+        // Deterministic formatting of spoken numbers and dates, before the model sees them
+        let text = match &language {
+            Some(language) if app_config.format_normalization => {
+                dictara_core::normalize::normalize(&text, language)
+            }
+            _ => text,
+        };
+        // Spoken formatting cues as Markdown, for the apps the user enabled it in
+        let text = match &language {
+            Some(language)
+                if app_config
+                    .markdown_mode
+                    .enabled_for(target_app_id.as_deref()) =>
+            {
+                dictara_core::normalize::apply_markdown_cues(&text, language)
+            }
+            _ => text,
+        };
 
         let post_processed_text = if app_config.post_process_enabled {
-            post_process_with_openai(
+            // Kept for retries, replaced when the next recording starts
+            let document_context = self
+                .document_context
+                .lock()
+                .ok()
+                .and_then(|guard| guard.clone());
+            let base_prompt = app_config.post_process_prompt_for(language.as_deref());
+            let prompt = match &document_context {
+                Some(context) => context.augment_prompt(base_prompt),
+                None => base_prompt.to_string(),
+            };
+            let prompt = corrections::augment_prompt(&prompt, &self.host.learned_corrections());
+
+            Some(self.post_process_and_prepare_paste(
                 &text,
                 &app_config.post_process_model,
-                &app_config.post_process_prompt,
-            )
+                &prompt,
+                app_config.post_process_params,
+            ))
         } else {
             log::info!("Skipping post-processing because it is disabled in settings");
-            text
+            None
         };
 
-        self.handle_transcription_success(&post_processed_text, audio_file_path)
+        // Profanity and sensitive data are filtered from what the model returned, before
+        // the user's own snippets are added
+        let filtered_text = redaction::redact(
+            post_processed_text.as_ref().unwrap_or(&text),
+            &app_config.redaction,
+        );
+        // Snippets are expanded after post-processing, so the model doesn't reword them
+        let final_text = self.host.expand_snippets(&filtered_text);
+        let output_text = match &template {
+            Some(template) => templates::render_template(template, &final_text),
+            None => final_text,
+        };
+
+        self.handle_transcription_success(
+            &output_text,
+            transcription.language,
+            audio_file_path,
+            duration_ms,
+        )?;
+
+        if let Some((post_processed_text, saved)) = post_processed_text {
+            let diff = diff_words(&text, &post_processed_text);
+            self.host.emit_transcription_result(TranscriptionResult {
+                raw_text: text,
+                post_processed_text,
+                diff,
+                saved_ms: saved.as_millis() as u64,
+            });
+        }
+        Ok(())
+    }
+
+    /// Post-process `text` while the popup is closed and the clipboard is saved for
+    /// the paste, instead of after the LLM call.
+    ///
+    /// Returns the post-processed text and the time saved by the overlap.
+    fn post_process_and_prepare_paste(
+        &self,
+        text: &str,
+        model: &str,
+        prompt: &str,
+        params: PostProcessParams,
+    ) -> (String, Duration) {
+        thread::scope(|scope| {
+            let post_processing = scope.spawn(|| {
+                let started_at = Instant::now();
+                let text = self.transcription.post_process(text, model, prompt, params);
+                (text, started_at.elapsed())
+            });
+
+            let started_at = Instant::now();
+            self.host.hide_popup();
+            // Nothing is pasted into a note, the clipboard stays as it is
+            if self.destination() == DictationDestination::Paste {
+                self.host.prepare_paste();
+            }
+            let preparation = started_at.elapsed();
+
+            let (text, post_processing) = post_processing
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            let saved = preparation.min(post_processing);
+            log::info!(
+                "Post-processing took {:?}, {:?} of paste preparation overlapped",
+                post_processing,
+                saved
+            );
+            (text, saved)
+        })
     }
 
-    /// Handle successful transcription: cleanup, paste, update state, emit event
+    /// Handle a transcription the user cancelled: discard the audio like a cancelled recording
+    fn handle_transcription_cancelled(&self, audio_file_path: &str) -> Result<(), ActionError> {
+        log::info!("Transcription cancelled by user");
+
+        // Reset state: Transcribing -> Ready
+        self.state_manager.reset();
+
+        if CLEANUP_AUDIO_AFTER_TRANSCRIPTION {
+            cleanup_recording_file(audio_file_path);
+        }
+
+        self.host.hide_popup();
+
+        self.emit_outcome(RecordingStateChanged::Cancelled)
+            .map_err(|e| ActionError::cancel(format!("Failed to emit cancelled event: {}", e)))
+    }
+
+    /// Whether the paste should be held back because the user switched apps since the
+    /// recording started (opt-in). Retries of failed transcriptions always paste.
+    fn focus_moved_away(&self) -> bool {
+        let paste_target = match self.paste_target.lock() {
+            Ok(mut guard) => guard.take(),
+            Err(e) => {
+                log::error!("Failed to lock paste_target: {}", e);
+                None
+            }
+        };
+        if !self.host.app_config().hold_paste_on_focus_change {
+            return false;
+        }
+        match (paste_target, self.host.frontmost_app()) {
+            (Some(target), Some(frontmost)) => target != frontmost,
+            _ => false,
+        }
+    }
+
+    /// Handle successful transcription: cleanup, paste (or create the note), update state,
+    /// emit event
     fn handle_transcription_success(
         &self,
         text: &str,
+        language: Option<String>,
         audio_file_path: &str,
+        duration_ms: u64,
     ) -> Result<(), ActionError> {
         // Reset state: Transcribing -> Ready
         self.state_manager.reset();
@@ -436,20 +987,63 @@ impl Controller {
             cleanup_recording_file(audio_file_path);
         }
 
-        if !text.is_empty() {
-            crate::text_paster::paste_text(text).map_err(|e| {
-                ActionError::transcription(
-                    &TranscriptionError::ApiError(format!("Failed to paste text: {}", e)),
-                    audio_file_path.to_string(),
-                )
-            })?;
+        match self.destination() {
+            _ if text.is_empty() => {}
+            DictationDestination::Note(target) => {
+                self.host.create_note(target, text).map_err(|e| {
+                    // Closed early if the text was post-processed, the error shows in it
+                    self.host.show_popup();
+                    ActionError::transcription(
+                        &TranscriptionError::ApiError(format!("Failed to create the note: {}", e)),
+                        audio_file_path.to_string(),
+                    )
+                })?;
+                self.host.record_dictation(text);
+                // The text went to a note, not into an app
+                self.host
+                    .deliver_dictation(text, language.as_deref(), None, None, duration_ms);
+            }
+            DictationDestination::Paste => {
+                // Read before `focus_moved_away` clears it
+                let target_app = self.paste_target.lock().ok().and_then(|guard| *guard);
+                let target_app_id = self
+                    .target_app_id
+                    .lock()
+                    .ok()
+                    .and_then(|guard| guard.clone());
+                if self.focus_moved_away() {
+                    log::info!(
+                        "Frontmost app changed since the recording started, holding back paste"
+                    );
+                    self.host.notify_held_result(text);
+                } else {
+                    let marker = self.host.app_config().clipboard_marker;
+                    self.host.paste(text, marker).map_err(|e| {
+                        // Closed early if the text was post-processed, the error shows in it
+                        self.host.show_popup();
+                        ActionError::transcription(
+                            &TranscriptionError::ApiError(format!("Failed to paste text: {}", e)),
+                            audio_file_path.to_string(),
+                        )
+                    })?;
+                }
+                self.host.record_dictation(text);
+                self.host.deliver_dictation(
+                    text,
+                    language.as_deref(),
+                    target_app,
+                    target_app_id,
+                    duration_ms,
+                );
+            }
         }
 
         // Update last recording state with successful transcription
         match self.last_recording_state.lock() {
             Ok(mut last_recording) => {
                 last_recording.text = Some(text.to_string());
-                last_recording.timestamp = Some(std::time::SystemTime::now());
+                last_recording.timestamp = Some(self.clock.now());
+                last_recording.duration_ms = Some(duration_ms);
                 last_recording.audio_file_path = None;
             }
             Err(e) => {
@@ -457,23 +1051,1032 @@ impl Controller {
             }
         }
 
-        if let Err(e) = self.menu.set_paste_last_active() {
-            log::error!("Failed to enable paste menu item: {}", e);
-        }
+        self.host.set_paste_last_enabled(true);
 
         // Hide recording popup window
-        if let Err(e) = close_recording_popup(&self.app_handle) {
-            log::error!("Failed to close recording popup: {}", e);
-        }
+        self.host.hide_popup();
 
-        if let Err(e) = (RecordingStateChanged::Stopped {
+        if let Err(e) = self.emit_outcome(RecordingStateChanged::Stopped {
             text: text.to_string(),
-        })
-        .emit(&self.app_handle)
-        {
+            language,
+        }) {
             log::error!("Failed to emit stopped event: {}", e);
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    use dictara_core::recorder::RecordingResult;
+    use dictara_core::transcription::Transcription;
+    use tokio::sync::mpsc;
+
+    use crate::config::{
+        AppConfig, ClipboardMarker, DictationTemplate, LearnedCorrection, PostProcessParams,
+        RedactionConfig,
+    };
+    use crate::notes::NoteTarget;
+    use crate::recording::state_manager::RecordingState;
+    use crate::recording::{LastRecording, RecorderError};
+
+    /// Everything the controller did through the host
+    #[derive(Default)]
+    struct HostLog {
+        events: Vec<RecordingStateChanged>,
+        pasted: Vec<String>,
+        held: Vec<String>,
+        /// Texts passed to `create_note`
+        notes: Vec<String>,
+        /// Texts passed to `deliver_dictation`
+        delivered: Vec<String>,
+        /// States passed to `publish_status`
+        statuses: Vec<RecordingState>,
+        /// Calls to `frontmost_app`
+        frontmost_calls: i32,
+        popup_visible: bool,
+        /// Calls to `prepare_paste`
+        prepared_pastes: i32,
+        paste_last_enabled: bool,
+        input_device_changes: Vec<InputDeviceChanged>,
+        ticks: Vec<RecordingTick>,
+        /// Audio durations passed to `record_transcription_time`
+        timed_transcriptions: Vec<u64>,
+        transcription_results: Vec<TranscriptionResult>,
+        /// Languages passed to `transcribe`
+        language_hints: Vec<Option<String>>,
+        /// Languages passed to `remember_app_language`
+        remembered_languages: Vec<String>,
+        /// Languages passed to `record_detected_language`
+        detected_languages: Vec<String>,
+    }
+
+    /// Frontmost app of the dictations, with the language remembered for it
+    struct MockApp {
+        id: &'static str,
+        language: Option<&'static str>,
+    }
+
+    struct MockHost {
+        app_config: AppConfig,
+        raw_mode: bool,
+        app: Option<MockApp>,
+        log: Arc<Mutex<HostLog>>,
+    }
+
+    impl ControllerHost for MockHost {
+        fn app_config(&self) -> AppConfig {
+            self.app_config.clone()
+        }
+
+        fn emit(&self, event: RecordingStateChanged) -> Result<(), String> {
+            self.log.lock().unwrap().events.push(event);
+            Ok(())
+        }
+
+        fn emit_input_device_changed(&self, event: InputDeviceChanged) {
+            self.log.lock().unwrap().input_device_changes.push(event);
+        }
+
+        fn emit_recording_tick(&self, event: RecordingTick) {
+            self.log.lock().unwrap().ticks.push(event);
+        }
+
+        fn estimate_transcription_ms(&self, duration_ms: u64) -> Option<u64> {
+            Some(duration_ms / 10)
+        }
+
+        fn record_transcription_time(&self, duration_ms: u64, _elapsed: Duration) {
+            self.log
+                .lock()
+                .unwrap()
+                .timed_transcriptions
+                .push(duration_ms);
+        }
+
+        fn emit_transcription_result(&self, event: TranscriptionResult) {
+            self.log.lock().unwrap().transcription_results.push(event);
+        }
+
+        fn show_popup(&self) {
+            self.log.lock().unwrap().popup_visible = true;
+        }
+
+        fn hide_popup(&self) {
+            self.log.lock().unwrap().popup_visible = false;
+        }
+
+        fn prepare_paste(&self) {
+            self.log.lock().unwrap().prepared_pastes += 1;
+        }
+
+        fn paste(&self, text: &str, _marker: ClipboardMarker) -> Result<(), String> {
+            self.log.lock().unwrap().pasted.push(text.to_string());
+            Ok(())
+        }
+
+        /// A different app on every call, as if the user kept switching apps
+        fn frontmost_app(&self) -> Option<i32> {
+            let mut log = self.log.lock().unwrap();
+            log.frontmost_calls += 1;
+            Some(log.frontmost_calls)
+        }
+
+        fn frontmost_app_id(&self) -> Option<String> {
+            self.app.as_ref().map(|app| app.id.to_string())
+        }
+
+        fn notify_held_result(&self, text: &str) {
+            self.log.lock().unwrap().held.push(text.to_string());
+        }
+
+        fn set_paste_last_enabled(&self, enabled: bool) {
+            self.log.lock().unwrap().paste_last_enabled = enabled;
+        }
+
+        fn pinned_language(&self) -> Option<String> {
+            None
+        }
+
+        fn record_detected_language(&self, language: &str) {
+            self.log
+                .lock()
+                .unwrap()
+                .detected_languages
+                .push(language.to_string());
+        }
+
+        fn app_language(&self, app_id: &str) -> Option<String> {
+            let app = self.app.as_ref().filter(|app| app.id == app_id)?;
+            app.language.map(str::to_string)
+        }
+
+        fn remember_app_language(&self, _app_id: &str, language: &str) {
+            self.log
+                .lock()
+                .unwrap()
+                .remembered_languages
+                .push(language.to_string());
+        }
+
+        fn record_dictation(&self, _text: &str) {}
+
+        fn deliver_dictation(
+            &self,
+            text: &str,
+            _language: Option<&str>,
+            _app: Option<i32>,
+            _app_id: Option<String>,
+            _duration_ms: u64,
+        ) {
+            self.log.lock().unwrap().delivered.push(text.to_string());
+        }
+
+        fn create_note(&self, _target: NoteTarget, text: &str) -> Result<(), String> {
+            self.log.lock().unwrap().notes.push(text.to_string());
+            Ok(())
+        }
+
+        fn learned_corrections(&self) -> Vec<LearnedCorrection> {
+            Vec::new()
+        }
+
+        fn expand_snippets(&self, text: &str) -> String {
+            text.to_string()
+        }
+
+        fn select_template(&self, _text: &str) -> Option<(DictationTemplate, String)> {
+            None
+        }
+
+        fn is_raw_mode(&self) -> bool {
+            self.raw_mode
+        }
+
+        fn publish_status(&mut self, state: RecordingState) {
+            self.log.lock().unwrap().statuses.push(state);
+        }
+
+        fn on_recording_finished(&self) {}
+    }
+
+    /// Name of the default input device seen by the mock recorder
+    const DEFAULT_INPUT: &str = "AirPods";
+
+    /// Recorder producing `result` for every session, or failing to start if `None`
+    struct MockRecorder {
+        result: Option<RecordingResult>,
+    }
+
+    struct MockSession {
+        result: RecordingResult,
+    }
+
+    impl AudioRecorderBackend for MockRecorder {
+        fn start(
+            &self,
+            _level_channel: Option<Channel<f32>>,
+        ) -> Result<Box<dyn RecordingSession>, RecorderError> {
+            let result = self.result.clone().ok_or(RecorderError::NoInputDevice)?;
+            Ok(Box::new(MockSession { result }))
+        }
+
+        fn default_input_name(&self) -> Option<String> {
+            Some(DEFAULT_INPUT.to_string())
+        }
+    }
+
+    impl RecordingSession for MockSession {
+        fn stop(self: Box<Self>) -> Result<RecordingResult, RecorderError> {
+            Ok(self.result)
+        }
+
+        fn switch_to_default_input(&mut self) -> Result<String, RecorderError> {
+            Ok(DEFAULT_INPUT.to_string())
+        }
+    }
+
+    /// Returns queued transcription results in order; post-processing upper-cases
+    struct MockTranscription {
+        results: Mutex<VecDeque<Result<Transcription, TranscriptionError>>>,
+        log: Arc<Mutex<HostLog>>,
+    }
+
+    impl TranscriptionBackend for MockTranscription {
+        fn transcribe(
+            &self,
+            _audio_path: &Path,
+            _duration_ms: u64,
+            language: Option<&str>,
+        ) -> Result<Transcription, TranscriptionError> {
+            self.log
+                .lock()
+                .unwrap()
+                .language_hints
+                .push(language.map(str::to_string));
+            self.results
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("unexpected transcription")
+        }
+
+        fn post_process(
+            &self,
+            text: &str,
+            _model: &str,
+            _prompt: &str,
+            _params: PostProcessParams,
+        ) -> String {
+            text.to_uppercase()
+        }
+    }
+
+    /// Clock advancing by `step` on every reading
+    struct MockClock {
+        now: Mutex<SystemTime>,
+        step: Duration,
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> SystemTime {
+            let mut now = self.now.lock().unwrap();
+            let reading = *now;
+            *now += self.step;
+            reading
+        }
+    }
+
+    fn fixed_time() -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+    }
+
+    fn transcription(text: &str) -> Result<Transcription, TranscriptionError> {
+        Ok(Transcription {
+            text: text.to_string(),
+            language: Some("en".to_string()),
+            detected: true,
+        })
+    }
+
+    /// A real (empty) audio file so cleanup and retry behave like in the app
+    fn audio_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "dictara-controller-test-{}-{}.wav",
+            std::process::id(),
+            name
+        ));
+        std::fs::write(&path, vec![0u8; AUDIO_BYTES_PER_SECOND as usize]).unwrap();
+        path
+    }
+
+    fn recording_result(path: &Path, speech_duration_ms: u64) -> RecordingResult {
+        RecordingResult {
+            file_path: path.to_string_lossy().to_string(),
+            duration_ms: speech_duration_ms,
+            speech_duration_ms,
+        }
+    }
+
+    struct Harness {
+        log: Arc<Mutex<HostLog>>,
+        state_manager: Arc<RecordingStateManager>,
+        last_recording: LastRecordingState,
+    }
+
+    /// Run `commands` through a controller built on mocks until they are all processed
+    fn run_controller(
+        commands: Vec<RecordingCommand>,
+        app_config: AppConfig,
+        raw_mode: bool,
+        recording: Option<RecordingResult>,
+        transcriptions: Vec<Result<Transcription, TranscriptionError>>,
+        last_recording: LastRecording,
+    ) -> Harness {
+        run_controller_with_clock(
+            commands,
+            app_config,
+            raw_mode,
+            recording,
+            transcriptions,
+            last_recording,
+            Duration::ZERO,
+            None,
+        )
+    }
+
+    /// [`run_controller`] with a clock advancing by `clock_step` on every reading, and
+    /// dictating into `app`
+    #[allow(clippy::too_many_arguments)]
+    fn run_controller_with_clock(
+        commands: Vec<RecordingCommand>,
+        app_config: AppConfig,
+        raw_mode: bool,
+        recording: Option<RecordingResult>,
+        transcriptions: Vec<Result<Transcription, TranscriptionError>>,
+        last_recording: LastRecording,
+        clock_step: Duration,
+        app: Option<MockApp>,
+    ) -> Harness {
+        let log = Arc::new(Mutex::new(HostLog::default()));
+        let state_manager = Arc::new(RecordingStateManager::new());
+        let last_recording = Arc::new(Mutex::new(last_recording));
+
+        let (command_tx, command_rx) = mpsc::channel(commands.len().max(1));
+        for command in commands {
+            command_tx.try_send(command).unwrap();
+        }
+        let tick_tx = command_tx.downgrade();
+        drop(command_tx);
+
+        let backends = ControllerBackends {
+            host: Box::new(MockHost {
+                app_config,
+                raw_mode,
+                app,
+                log: log.clone(),
+            }),
+            recorder: Box::new(MockRecorder { result: recording }),
+            transcription: Box::new(MockTranscription {
+                results: Mutex::new(transcriptions.into()),
+                log: log.clone(),
+            }),
+            clock: Box::new(MockClock {
+                now: Mutex::new(fixed_time()),
+                step: clock_step,
+            }),
+        };
+
+        Controller::with_backends(
+            command_rx,
+            tick_tx,
+            state_manager.clone(),
+            Arc::new(Mutex::new(None)),
+            last_recording.clone(),
+            backends,
+        )
+        .run();
+
+        Harness {
+            log,
+            state_manager,
+            last_recording,
+        }
+    }
+
+    fn post_processing_config(enabled: bool) -> AppConfig {
+        AppConfig {
+            post_process_enabled: enabled,
+            ..AppConfig::default()
+        }
+    }
+
+    fn event_names(log: &HostLog) -> Vec<&'static str> {
+        log.events
+            .iter()
+            .map(|event| match event {
+                RecordingStateChanged::Started => "started",
+                RecordingStateChanged::Transcribing { .. } => "transcribing",
+                RecordingStateChanged::Stopped { .. } => "stopped",
+                RecordingStateChanged::Cancelled => "cancelled",
+                RecordingStateChanged::Error { .. } => "error",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_record_transcribe_and_paste() {
+        let audio = audio_file("success");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartRecording,
+                RecordingCommand::StopRecording,
+            ],
+            post_processing_config(true),
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![transcription("hello world")],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert_eq!(
+            event_names(&log),
+            vec!["started", "transcribing", "stopped"]
+        );
+        assert_eq!(log.pasted, vec!["HELLO WORLD"]);
+        assert!(!log.popup_visible);
+        // The clipboard was saved while post-processing ran
+        assert_eq!(log.prepared_pastes, 1);
+        assert!(log.paste_last_enabled);
+        // The estimate and the measured time are for the speech duration
+        assert!(matches!(
+            log.events[1],
+            RecordingStateChanged::Transcribing {
+                estimated_ms: Some(200)
+            }
+        ));
+        assert_eq!(log.timed_transcriptions, vec![2000]);
+
+        let [result] = log.transcription_results.as_slice() else {
+            panic!("expected one transcription result");
+        };
+        assert_eq!(result.raw_text, "hello world");
+        assert_eq!(result.post_processed_text, "HELLO WORLD");
+        assert_eq!((result.diff.words_removed, result.diff.words_added), (2, 2));
+
+        let last_recording = harness.last_recording.lock().unwrap();
+        assert_eq!(last_recording.text.as_deref(), Some("HELLO WORLD"));
+        assert_eq!(last_recording.timestamp, Some(fixed_time()));
+        assert_eq!(last_recording.duration_ms, Some(2000));
+        assert_eq!(last_recording.audio_file_path, None);
+        assert_eq!(harness.state_manager.current(), RecordingState::Ready);
+        assert!(!audio.exists(), "audio should be cleaned up");
+    }
+
+    #[test]
+    fn test_stop_recording_and_wait_resolves_with_outcome() {
+        let audio = audio_file("wait");
+        let (waiter, mut outcome) = ResultWaiter::new();
+        let (idle_waiter, mut idle_outcome) = ResultWaiter::new();
+        run_controller(
+            vec![
+                RecordingCommand::StopRecordingAndWait(idle_waiter),
+                RecordingCommand::StartRecording,
+                RecordingCommand::StopRecordingAndWait(waiter),
+            ],
+            post_processing_config(false),
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![transcription("hello world")],
+            LastRecording::new(),
+        );
+
+        match outcome.try_recv() {
+            Ok(RecordingStateChanged::Stopped { text, .. }) => assert_eq!(text, "hello world"),
+            other => panic!("expected a stopped outcome, got {:?}", other),
+        }
+        // Nothing was recording: the waiter is dropped without an outcome
+        assert!(idle_outcome.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_focus_change_holds_back_paste() {
+        let audio = audio_file("focus");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartRecording,
+                RecordingCommand::StopRecording,
+            ],
+            AppConfig {
+                hold_paste_on_focus_change: true,
+                ..post_processing_config(false)
+            },
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![transcription("hello world")],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert!(log.pasted.is_empty());
+        assert_eq!(log.held, vec!["hello world"]);
+        assert!(log.paste_last_enabled);
+        assert_eq!(
+            harness.last_recording.lock().unwrap().text.as_deref(),
+            Some("hello world")
+        );
+    }
+
+    #[test]
+    fn test_note_dictation_creates_a_note() {
+        let audio = audio_file("note");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartNoteDictation(NoteTarget::Obsidian),
+                RecordingCommand::StopRecording,
+            ],
+            post_processing_config(true),
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![transcription("hello world")],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert_eq!(
+            event_names(&log),
+            vec!["started", "transcribing", "stopped"]
+        );
+        assert_eq!(log.notes, vec!["HELLO WORLD"]);
+        // Hands-free from the start, without a separate lock command
+        assert!(log.statuses.contains(&RecordingState::RecordingLocked));
+        assert!(!log.statuses.contains(&RecordingState::Recording));
+        // The output sinks get it like a pasted dictation
+        assert_eq!(log.delivered, vec!["HELLO WORLD"]);
+        assert!(log.pasted.is_empty());
+        // Nothing is pasted, the clipboard isn't touched
+        assert_eq!(log.prepared_pastes, 0);
+        assert_eq!(harness.state_manager.current(), RecordingState::Ready);
+    }
+
+    #[test]
+    fn test_raw_mode_skips_post_processing() {
+        let audio = audio_file("raw");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartRecording,
+                RecordingCommand::StopRecording,
+            ],
+            post_processing_config(true),
+            true,
+            Some(recording_result(&audio, 2000)),
+            vec![transcription("um, hello world")],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert_eq!(log.pasted, vec!["um, hello world"]);
+        assert!(log.transcription_results.is_empty());
+        assert_eq!(log.prepared_pastes, 0);
+    }
+
+    #[test]
+    fn test_raw_mode_skips_redaction() {
+        let audio = audio_file("raw-redaction");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartRecording,
+                RecordingCommand::StopRecording,
+            ],
+            AppConfig {
+                redaction: RedactionConfig {
+                    enabled: true,
+                    redact_emails: true,
+                    ..Default::default()
+                },
+                ..post_processing_config(false)
+            },
+            true,
+            Some(recording_result(&audio, 2000)),
+            vec![transcription("damn, mail jane@example.com")],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert_eq!(log.pasted, vec!["damn, mail jane@example.com"]);
+    }
+
+    #[test]
+    fn test_app_language_is_remembered_when_detected() {
+        let dictate = |app: MockApp, language: &str, detected: bool| {
+            let audio = audio_file("app-language");
+            let harness = run_controller_with_clock(
+                vec![
+                    RecordingCommand::StartRecording,
+                    RecordingCommand::StopRecording,
+                ],
+                post_processing_config(false),
+                false,
+                Some(recording_result(&audio, 2000)),
+                vec![Ok(Transcription {
+                    text: "hello".to_string(),
+                    language: Some(language.to_string()),
+                    detected,
+                })],
+                LastRecording::new(),
+                Duration::ZERO,
+                Some(app),
+            );
+            let log = harness.log.lock().unwrap();
+            (
+                log.language_hints.clone(),
+                log.remembered_languages.clone(),
+                log.detected_languages.clone(),
+            )
+        };
+
+        // A new app: the model detects the language, which is remembered and counted
+        let (hints, remembered, counted) = dictate(
+            MockApp {
+                id: "com.tinyspeck.slackmacgap",
+                language: None,
+            },
+            "en",
+            true,
+        );
+        assert_eq!(hints, vec![None]);
+        assert_eq!(remembered, vec!["en"]);
+        assert_eq!(counted, vec!["en"]);
+
+        // The remembered language is passed on, and neither remembered again from itself
+        // nor counted as detected
+        let (hints, remembered, counted) = dictate(
+            MockApp {
+                id: "jp.naver.line.mac",
+                language: Some("ja"),
+            },
+            "ja",
+            false,
+        );
+        assert_eq!(hints, vec![Some("ja".to_string())]);
+        assert!(remembered.is_empty());
+        assert!(counted.is_empty());
+    }
+
+    #[test]
+    fn test_no_speech_skips_transcription() {
+        let audio = audio_file("silence");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartRecording,
+                RecordingCommand::StopRecording,
+            ],
+            post_processing_config(false),
+            false,
+            Some(recording_result(&audio, 50)),
+            vec![],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert_eq!(event_names(&log), vec!["started", "error"]);
+        assert!(matches!(
+            &log.events[1],
+            RecordingStateChanged::Error { error_type, audio_file_path: None, .. }
+                if error_type == "no_speech"
+        ));
+        assert!(log.pasted.is_empty());
+        assert_eq!(harness.state_manager.current(), RecordingState::Ready);
+        assert!(!audio.exists(), "silent audio should be cleaned up");
+    }
+
+    #[test]
+    fn test_failed_transcription_keeps_audio_for_retry() {
+        let audio = audio_file("retry");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartRecording,
+                RecordingCommand::StopRecording,
+                RecordingCommand::RetryTranscription,
+            ],
+            post_processing_config(false),
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![
+                Err(TranscriptionError::ApiError(
+                    "service unavailable".to_string(),
+                )),
+                transcription("second try"),
+            ],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert_eq!(
+            event_names(&log),
+            vec![
+                "started",
+                "transcribing",
+                "error",
+                "transcribing",
+                "stopped"
+            ]
+        );
+        assert!(matches!(
+            &log.events[2],
+            RecordingStateChanged::Error { error_type, audio_file_path: Some(path), .. }
+                if error_type == "transcription" && Path::new(path) == audio
+        ));
+        assert_eq!(log.pasted, vec!["second try"]);
+        // Only the successful retry is timed
+        assert_eq!(log.timed_transcriptions, vec![1000]);
+
+        let last_recording = harness.last_recording.lock().unwrap();
+        assert_eq!(last_recording.text.as_deref(), Some("second try"));
+        // Duration of the retry is estimated from the file size (1 second of audio)
+        assert_eq!(last_recording.duration_ms, Some(1000));
+        assert!(
+            !audio.exists(),
+            "audio should be cleaned up after the retry"
+        );
+    }
+
+    #[test]
+    fn test_discard_error_deletes_the_audio() {
+        let audio = audio_file("discard");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartRecording,
+                RecordingCommand::StopRecording,
+                RecordingCommand::DiscardError,
+                // Nothing left to retry
+                RecordingCommand::RetryTranscription,
+            ],
+            post_processing_config(false),
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![Err(TranscriptionError::ApiError(
+                "service unavailable".to_string(),
+            ))],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert!(log.pasted.is_empty());
+        assert_eq!(harness.state_manager.current(), RecordingState::Ready);
+        assert_eq!(harness.last_recording.lock().unwrap().audio_file_path, None);
+        assert!(!audio.exists());
+    }
+
+    #[test]
+    fn test_cancel_recording() {
+        let audio = audio_file("cancel");
+        let harness = run_controller(
+            vec![RecordingCommand::StartRecording, RecordingCommand::Cancel],
+            post_processing_config(false),
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert_eq!(event_names(&log), vec!["started", "cancelled"]);
+        assert!(log.pasted.is_empty());
+        assert!(!log.popup_visible);
+        assert_eq!(harness.state_manager.current(), RecordingState::Ready);
+        assert!(!audio.exists());
+    }
+
+    #[test]
+    fn test_cancelled_transcription() {
+        let audio = audio_file("cancel_transcription");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartRecording,
+                RecordingCommand::StopRecording,
+            ],
+            post_processing_config(false),
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![Err(TranscriptionError::Cancelled)],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert_eq!(
+            event_names(&log),
+            vec!["started", "transcribing", "cancelled"]
+        );
+        assert!(log.pasted.is_empty());
+        assert!(!log.popup_visible);
+        assert_eq!(harness.state_manager.current(), RecordingState::Ready);
+        assert!(!audio.exists());
+    }
+
+    #[test]
+    fn test_device_lost_keeps_audio_for_retry() {
+        let audio = audio_file("unplugged");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartRecording,
+                RecordingCommand::LockRecording,
+                RecordingCommand::DeviceLost,
+            ],
+            AppConfig {
+                input_device_change_policy: InputDeviceChangePolicy::Notify,
+                ..post_processing_config(false)
+            },
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert_eq!(event_names(&log), vec!["started", "error"]);
+        assert!(matches!(
+            &log.events[1],
+            RecordingStateChanged::Error { error_type, audio_file_path: Some(_), .. }
+                if error_type == "recording"
+        ));
+        assert_eq!(harness.state_manager.current(), RecordingState::Ready);
+        assert_eq!(
+            harness.last_recording.lock().unwrap().audio_file_path,
+            Some(audio.to_string_lossy().to_string())
+        );
+        std::fs::remove_file(audio).unwrap();
+    }
+
+    #[test]
+    fn test_shutdown_keeps_recording_for_recovery() {
+        let audio = audio_file("shutdown");
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartRecording,
+                RecordingCommand::Shutdown(done_tx),
+                // Never handled, the controller has stopped
+                RecordingCommand::StartRecording,
+            ],
+            post_processing_config(false),
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![],
+            LastRecording::new(),
+        );
+
+        assert!(done_rx.try_recv().is_ok());
+        let log = harness.log.lock().unwrap();
+        assert_eq!(event_names(&log), vec!["started"]);
+        assert!(log.pasted.is_empty());
+        assert_eq!(harness.state_manager.current(), RecordingState::Ready);
+        assert!(audio.exists(), "audio should be kept for recovery");
+        std::fs::remove_file(audio).unwrap();
+    }
+
+    #[test]
+    fn test_device_lost_follows_default_input() {
+        let audio = audio_file("follow");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartRecording,
+                RecordingCommand::DeviceLost,
+                RecordingCommand::StopRecording,
+            ],
+            post_processing_config(false),
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![transcription("still recording")],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert_eq!(
+            event_names(&log),
+            vec!["started", "transcribing", "stopped"]
+        );
+        assert_eq!(log.pasted, vec!["still recording"]);
+        assert_eq!(log.input_device_changes.len(), 1);
+        assert_eq!(
+            log.input_device_changes[0].device_name.as_deref(),
+            Some(DEFAULT_INPUT)
+        );
+        assert!(log.input_device_changes[0].switched);
+    }
+
+    #[test]
+    fn test_default_input_change_notifies() {
+        let audio = audio_file("notify");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::DefaultInputChanged,
+                RecordingCommand::StartRecording,
+                RecordingCommand::DefaultInputChanged,
+                RecordingCommand::Cancel,
+            ],
+            AppConfig {
+                input_device_change_policy: InputDeviceChangePolicy::Notify,
+                ..post_processing_config(false)
+            },
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert_eq!(event_names(&log), vec!["started", "cancelled"]);
+        // Only the change during the recording is reported
+        assert_eq!(log.input_device_changes.len(), 1);
+        assert!(!log.input_device_changes[0].switched);
+    }
+
+    #[test]
+    fn test_ticks_report_remaining_time() {
+        let audio = audio_file("ticks");
+        let harness = run_controller(
+            vec![
+                RecordingCommand::Tick,
+                RecordingCommand::StartRecording,
+                RecordingCommand::Tick,
+                RecordingCommand::StopRecording,
+                RecordingCommand::Tick,
+            ],
+            AppConfig {
+                max_recording_secs: 60,
+                ..post_processing_config(false)
+            },
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![transcription("hello")],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        // Only the tick during the recording is reported
+        assert_eq!(log.ticks.len(), 1);
+        assert_eq!(log.ticks[0].elapsed_ms, 0);
+        assert_eq!(log.ticks[0].remaining_ms, 60_000);
+        assert_eq!(
+            event_names(&log),
+            vec!["started", "transcribing", "stopped"]
+        );
+    }
+
+    #[test]
+    fn test_recording_stops_at_length_limit() {
+        let audio = audio_file("limit");
+        let harness = run_controller_with_clock(
+            vec![RecordingCommand::StartRecording, RecordingCommand::Tick],
+            AppConfig {
+                max_recording_secs: 1,
+                ..post_processing_config(false)
+            },
+            false,
+            Some(recording_result(&audio, 2000)),
+            vec![transcription("cut off")],
+            LastRecording::new(),
+            Duration::from_secs(1),
+            None,
+        );
+
+        let log = harness.log.lock().unwrap();
+        assert_eq!(log.ticks.len(), 1);
+        assert_eq!(log.ticks[0].remaining_ms, 0);
+        assert_eq!(
+            event_names(&log),
+            vec!["started", "transcribing", "stopped"]
+        );
+        assert_eq!(log.pasted, vec!["cut off"]);
+    }
+
+    #[test]
+    fn test_recorder_start_failure() {
+        let harness = run_controller(
+            vec![
+                RecordingCommand::StartRecording,
+                RecordingCommand::StopRecording,
+            ],
+            post_processing_config(false),
+            false,
+            None,
+            vec![],
+            LastRecording::new(),
+        );
+
+        let log = harness.log.lock().unwrap();
+        // Stop is rejected: the failed start already reset the state machine to Ready
+        assert_eq!(event_names(&log), vec!["started", "error"]);
+        assert!(!log.popup_visible);
+        assert!(!log.paste_last_enabled);
+        assert_eq!(harness.state_manager.current(), RecordingState::Ready);
+    }
+}
@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::text_diff::TextDiff;
+
 /// Recording state change event - single event stream for all state transitions
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
 #[serde(tag = "state", rename_all = "camelCase")]
@@ -14,12 +16,19 @@ pub enum RecordingStateChanged {
     Started,
     /// Recording is being transcribed
     #[serde(rename = "transcribing")]
-    Transcribing,
+    Transcribing {
+        /// Expected transcription time from past dictations with the same engine,
+        /// `None` until there are enough of them
+        #[serde(rename = "estimatedMs")]
+        estimated_ms: Option<u64>,
+    },
     /// Recording completed successfully
     #[serde(rename = "stopped")]
     Stopped {
         /// The transcribed text
         text: String,
+        /// Language code detected by the provider (e.g. "en"), if reported
+        language: Option<String>,
     },
     /// Recording was cancelled by user
     #[serde(rename = "cancelled")]
@@ -41,3 +50,54 @@ pub enum RecordingStateChanged {
         audio_file_path: Option<String>,
     },
 }
+
+/// Raw (verbatim) dictation mode was toggled
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct RawModeChanged {
+    pub enabled: bool,
+}
+
+/// The default input device changed while recording
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct InputDeviceChanged {
+    /// Name of the new default input device, if known
+    pub device_name: Option<String>,
+    /// Whether the recording continues on the new device
+    pub switched: bool,
+}
+
+/// Sent every half second while recording, for the elapsed time in the popup and a
+/// warning before the recording is stopped at its length limit (configured maximum
+/// or the 25MB transcription file limit)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingTick {
+    pub elapsed_ms: u64,
+    /// Time left before the recording is stopped and transcribed
+    pub remaining_ms: u64,
+}
+
+/// The user switched apps while transcribing, so the result was not pasted
+/// (it can be pasted or copied from the tray menu)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionHeld {
+    pub text: String,
+}
+
+/// The transcription was post-processed: the recognized and the rewritten text
+/// with a word diff, so the UI can show what the LLM changed
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionResult {
+    /// Recognized text (without the template keyword, if a template was selected)
+    pub raw_text: String,
+    /// Text returned by post-processing, before template rendering
+    pub post_processed_text: String,
+    pub diff: TextDiff,
+    /// Time the popup closing and clipboard saving ran during post-processing instead
+    /// of after it (ms)
+    pub saved_ms: u64,
+}
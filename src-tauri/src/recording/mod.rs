@@ -1,22 +1,35 @@
 mod audio_recorder;
+pub mod backend;
 mod commands;
 mod controller;
 pub mod events;
+mod raw_mode;
 mod state_manager;
-pub mod vad;
+mod state_push;
+mod status_export;
+mod system_sleep;
+mod text_diff;
+mod ticker;
 
 use std::sync::{Arc, Mutex};
 use std::time::SystemTime;
 
 // Re-export state manager types
-pub use state_manager::{RecordingAction, RecordingEvent, RecordingStateManager, TransitionResult};
+pub use state_manager::{
+    RecordingAction, RecordingEvent, RecordingState, RecordingStateManager, StateHistoryEntry,
+    TransitionResult,
+};
 
 // Public exports
 pub use audio_recorder::{
-    cleanup_old_recordings, cleanup_recording_file, RecorderError, Recording,
+    cleanup_recording_file, core_recorder, recover_interrupted_recording, RecorderError, Recording,
 };
-pub use commands::RecordingCommand;
-pub use controller::Controller;
+pub use commands::{RecordingCommand, ResultWaiter};
+pub use controller::{Controller, ControllerBackends};
+pub use raw_mode::RawMode;
+pub use state_push::save_mqtt_password;
+pub use system_sleep::watch_system_sleep;
+pub use text_diff::{diff_words, DiffKind};
 
 /// Stores the last recording attempt for paste retry functionality
 #[derive(Debug, Clone)]
@@ -25,6 +38,8 @@ pub struct LastRecording {
     pub text: Option<String>,
     /// Timestamp of when the recording was made
     pub timestamp: Option<SystemTime>,
+    /// Speech duration of the transcribed recording in milliseconds (for subtitle export)
+    pub duration_ms: Option<u64>,
     /// Audio file path. Some = transcription failed (keep for retry), None = succeeded (cleaned up)
     pub audio_file_path: Option<String>,
 }
@@ -34,10 +49,18 @@ impl LastRecording {
         Self {
             text: None,
             timestamp: None,
+            duration_ms: None,
             audio_file_path: None,
         }
     }
 
+    /// Delete the audio kept for retry after a failed transcription
+    pub fn discard_audio(&mut self) {
+        if let Some(path) = self.audio_file_path.take() {
+            cleanup_recording_file(&path);
+        }
+    }
+
     #[allow(dead_code)]
     pub fn can_paste(&self) -> bool {
         self.text.is_some()
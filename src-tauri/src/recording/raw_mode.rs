@@ -0,0 +1,57 @@
+//! Raw ("verbatim") dictation mode.
+//!
+//! While enabled, transcriptions are pasted exactly as the recognizer returned
+//! them: no LLM post-processing, no document context, no templates. Meant for
+//! codes, IDs and other strings that post-processing would "fix".
+//!
+//! The mode is a quick toggle (tray, command or shortcut) and is not persisted.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use log::{error, info};
+use tauri::menu::CheckMenuItem;
+use tauri::{AppHandle, Wry};
+use tauri_specta::Event;
+
+use super::events::RawModeChanged;
+
+pub struct RawMode {
+    enabled: AtomicBool,
+    app_handle: AppHandle,
+    /// Tray menu item mirroring the mode
+    menu_item: CheckMenuItem<Wry>,
+}
+
+impl RawMode {
+    pub fn new(app_handle: AppHandle, menu_item: CheckMenuItem<Wry>) -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            app_handle,
+            menu_item,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Enable or disable raw mode, updating the tray and notifying the frontend
+    pub fn set(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+        info!("Raw mode {}", if enabled { "enabled" } else { "disabled" });
+
+        if let Err(e) = self.menu_item.set_checked(enabled) {
+            error!("Failed to update raw mode menu item: {}", e);
+        }
+        if let Err(e) = (RawModeChanged { enabled }).emit(&self.app_handle) {
+            error!("Failed to emit RawModeChanged event: {}", e);
+        }
+    }
+
+    /// Flip the mode, returns the new state
+    pub fn toggle(&self) -> bool {
+        let enabled = !self.is_enabled();
+        self.set(enabled);
+        enabled
+    }
+}
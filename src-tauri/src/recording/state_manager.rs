@@ -3,9 +3,9 @@
 //! State diagram:
 //! ```text
 //! Ready ──Start──> Recording ──Stop──> Transcribing ──reset()──> Ready
-//!   │                   │
-//! [Retry]            [Lock]
-//!   │                   ↓
+//!   │ │                 │
+//!   │ └─StartLocked─┐ [Lock]
+//! [Retry]           ↓   ↓
 //!   │          RecordingLocked
 //!   │                   │
 //!   │               [Start]──> Transcribing (Fn pressed again to stop)
@@ -13,11 +13,26 @@
 //!   └──────────────────────────> Transcribing
 //! ```
 //!
+//! Input device events keep Recording/RecordingLocked in place but carry an action:
+//! `DefaultInputChanged` applies the device change policy, `DeviceLost` (microphone
+//! unplugged) continues on the new default device or fails back to Ready.
+//!
 //! Note: Transcribing state exits via reset() - no dedicated events needed
-//! since both success and failure return to Ready state.
+//! since both success and failure return to Ready state. The controller is busy
+//! while transcribing, so cancelling a local transcription bypasses the state
+//! machine (`ModelLoader::cancel_transcription`) and the transcription fails
+//! as cancelled.
+//!
+//! The last [`HISTORY_CAPACITY`] transitions (including rejected ones and resets) are
+//! kept in memory, so "it got stuck in Transcribing" reports can include the exact
+//! event sequence (`dump_state_history`).
 
+use std::collections::VecDeque;
 use std::sync::Mutex;
 
+/// Transitions kept in the state history
+pub const HISTORY_CAPACITY: usize = 200;
+
 /// Events that can trigger state transitions
 #[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
 pub enum RecordingEvent {
@@ -27,10 +42,16 @@ pub enum RecordingEvent {
     Stop,
     /// Lock recording (Fn release will be ignored)
     Lock,
+    /// Start a recording already locked (hands-free from the start, e.g. a note dictation)
+    StartLocked,
     /// Escape or cancel action
     Cancel,
     /// Retry transcription with existing audio file
     Retry,
+    /// Input device disconnected mid-recording
+    DeviceLost,
+    /// System default input device changed
+    DefaultInputChanged,
 }
 
 /// Actions the Controller should perform after a state transition
@@ -44,10 +65,16 @@ pub enum RecordingAction {
     CancelRecording,
     /// Retry transcription with existing audio file
     RetryTranscription,
+    /// Move a recording whose input device disappeared to the new default device,
+    /// or stop it and report the error
+    RecoverLostDevice,
+    /// Apply the input device change policy to the running recording
+    SwitchInputDevice,
 }
 
 /// Recording states
-#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub enum RecordingState {
     /// Controller is ready to start recording
     Ready,
@@ -69,7 +96,7 @@ impl RecordingState {
 /// Result of a successful state transition
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TransitionResult {
-    /// State changed
+    /// State changed, or stayed the same with an action to perform
     Changed {
         from: RecordingState,
         to: RecordingState,
@@ -87,16 +114,34 @@ pub struct TransitionRejection {
     pub attempted_event: RecordingEvent,
 }
 
+/// An attempted transition, for debugging
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StateHistoryEntry {
+    /// Local time, RFC 3339
+    pub timestamp: String,
+    /// Event name ("Start", "Stop", ...), "Reset" for a reset to Ready
+    pub event: String,
+    pub from: RecordingState,
+    /// State after the transition, `from` if it was rejected
+    pub to: RecordingState,
+    /// Action the controller was asked to perform
+    pub action: Option<String>,
+    pub accepted: bool,
+}
+
 /// Thread-safe recording state manager
 #[derive(Debug)]
 pub struct RecordingStateManager {
     state: Mutex<RecordingState>,
+    history: Mutex<VecDeque<StateHistoryEntry>>,
 }
 
 impl RecordingStateManager {
     pub fn new() -> Self {
         Self {
             state: Mutex::new(RecordingState::Ready),
+            history: Mutex::new(VecDeque::with_capacity(HISTORY_CAPACITY)),
         }
     }
 
@@ -133,12 +178,10 @@ impl RecordingStateManager {
         let mut state = self.state.lock().unwrap();
         let current = *state;
 
-        match self.compute_transition(current, event) {
+        let result = match self.compute_transition(current, event) {
+            // Self-transitions only matter if they carry an action (input device changes)
+            Some((new_state, None)) if new_state == current => Ok(TransitionResult::Unchanged),
             Some((new_state, action)) => {
-                if new_state == current {
-                    return Ok(TransitionResult::Unchanged);
-                }
-
                 *state = new_state;
                 Ok(TransitionResult::Changed {
                     from: current,
@@ -150,7 +193,21 @@ impl RecordingStateManager {
                 current_state: current,
                 attempted_event: event,
             }),
-        }
+        };
+
+        let (to, action) = match &result {
+            Ok(TransitionResult::Changed { to, action, .. }) => (*to, *action),
+            _ => (current, None),
+        };
+        self.record(StateHistoryEntry {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            event: event.to_string(),
+            from: current,
+            to,
+            action: action.map(|action| format!("{:?}", action)),
+            accepted: result.is_ok(),
+        });
+        result
     }
 
     /// Pure function: compute what transition should happen (if any)
@@ -166,10 +223,16 @@ impl RecordingStateManager {
                     RecordingState::Recording,
                     Some(RecordingAction::StartRecording),
                 )),
+                RecordingEvent::StartLocked => Some((
+                    RecordingState::RecordingLocked,
+                    Some(RecordingAction::StartRecording),
+                )),
                 RecordingEvent::Retry => Some((
                     RecordingState::Transcribing,
                     Some(RecordingAction::RetryTranscription),
                 )),
+                // Nothing to switch, the next recording opens the new default device
+                RecordingEvent::DefaultInputChanged => Some((current, None)),
                 _ => None,
             },
 
@@ -183,6 +246,12 @@ impl RecordingStateManager {
                     RecordingState::Ready,
                     Some(RecordingAction::CancelRecording),
                 )),
+                RecordingEvent::DeviceLost => {
+                    Some((current, Some(RecordingAction::RecoverLostDevice)))
+                }
+                RecordingEvent::DefaultInputChanged => {
+                    Some((current, Some(RecordingAction::SwitchInputDevice)))
+                }
                 _ => None,
             },
 
@@ -196,11 +265,20 @@ impl RecordingStateManager {
                     RecordingState::Ready,
                     Some(RecordingAction::CancelRecording),
                 )),
+                RecordingEvent::DeviceLost => {
+                    Some((current, Some(RecordingAction::RecoverLostDevice)))
+                }
+                RecordingEvent::DefaultInputChanged => {
+                    Some((current, Some(RecordingAction::SwitchInputDevice)))
+                }
                 _ => None,
             },
 
             // Transcribing state exits via reset() - no events trigger transitions
-            RecordingState::Transcribing => None,
+            RecordingState::Transcribing => match event {
+                RecordingEvent::DefaultInputChanged => Some((current, None)),
+                _ => None,
+            },
         }
     }
 
@@ -209,7 +287,29 @@ impl RecordingStateManager {
     /// Used to exit Transcribing state (both success and failure)
     /// and for error recovery in other states.
     pub fn reset(&self) {
-        *self.state.lock().unwrap() = RecordingState::Ready;
+        let mut state = self.state.lock().unwrap();
+        self.record(StateHistoryEntry {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            event: "Reset".to_string(),
+            from: *state,
+            to: RecordingState::Ready,
+            action: None,
+            accepted: true,
+        });
+        *state = RecordingState::Ready;
+    }
+
+    /// The last [`HISTORY_CAPACITY`] transitions, oldest first
+    pub fn history(&self) -> Vec<StateHistoryEntry> {
+        self.history.lock().unwrap().iter().cloned().collect()
+    }
+
+    fn record(&self, entry: StateHistoryEntry) {
+        let mut history = self.history.lock().unwrap();
+        if history.len() == HISTORY_CAPACITY {
+            history.pop_front();
+        }
+        history.push_back(entry);
     }
 }
 
@@ -218,3 +318,76 @@ impl Default for RecordingStateManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_records_transitions() {
+        let manager = RecordingStateManager::new();
+        manager.transition(RecordingEvent::Start).unwrap();
+        manager.transition(RecordingEvent::Stop).unwrap();
+        assert!(manager.transition(RecordingEvent::Start).is_err());
+        manager.reset();
+
+        let history: Vec<_> = manager
+            .history()
+            .into_iter()
+            .map(|entry| (entry.event, entry.from, entry.to, entry.accepted))
+            .collect();
+        assert_eq!(
+            history,
+            vec![
+                (
+                    "Start".to_string(),
+                    RecordingState::Ready,
+                    RecordingState::Recording,
+                    true
+                ),
+                (
+                    "Stop".to_string(),
+                    RecordingState::Recording,
+                    RecordingState::Transcribing,
+                    true
+                ),
+                (
+                    "Start".to_string(),
+                    RecordingState::Transcribing,
+                    RecordingState::Transcribing,
+                    false
+                ),
+                (
+                    "Reset".to_string(),
+                    RecordingState::Transcribing,
+                    RecordingState::Ready,
+                    true
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_start_locked() {
+        let manager = RecordingStateManager::new();
+        assert_eq!(
+            manager.transition(RecordingEvent::StartLocked).unwrap(),
+            TransitionResult::Changed {
+                from: RecordingState::Ready,
+                to: RecordingState::RecordingLocked,
+                action: Some(RecordingAction::StartRecording),
+            }
+        );
+        // Only from Ready: a running recording isn't restarted
+        assert!(manager.transition(RecordingEvent::StartLocked).is_err());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let manager = RecordingStateManager::new();
+        for _ in 0..HISTORY_CAPACITY + 10 {
+            manager.reset();
+        }
+        assert_eq!(manager.history().len(), HISTORY_CAPACITY);
+    }
+}
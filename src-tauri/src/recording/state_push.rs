@@ -0,0 +1,414 @@
+//! Push of the recording state for home automation, e.g. a smart light that turns red
+//! while the mic is hot.
+//!
+//! Two opt-in channels, set in `AppConfig::state_push`:
+//! - MQTT: the state JSON is published (retained) to the configured topic. If Dictara
+//!   goes away without disconnecting, e.g. after a crash, the broker publishes
+//!   [`OFFLINE_PAYLOAD`] there instead (last will).
+//! - WebSocket: a server on 127.0.0.1 sends the current state to each client when it
+//!   connects, then every change. Browsers send an `Origin` header, so any website
+//!   could connect: only pages served from this machine are accepted, clients without
+//!   an `Origin` (scripts, home automation bridges) always are.
+//!
+//! The status exporter calls [`StatePush::publish`] on every state transition of the
+//! controller; channels are (re)connected there when their settings changed, so nothing
+//! listens or connects until a channel is enabled. The MQTT password is read from the
+//! keychain once and again only after [`save_mqtt_password`] changed it.
+
+use std::net::Ipv4Addr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use log::{info, warn};
+use rumqttc::{AsyncClient, LastWill, MqttOptions, QoS};
+use secrecy::{ExposeSecret, SecretString};
+use tauri::async_runtime::{self, JoinHandle};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::{header, StatusCode};
+use tokio_tungstenite::tungstenite::{self, Message};
+
+use crate::config::{MqttCredentials, MqttPushConfig, StatePushConfig, WebSocketPushConfig};
+use crate::keychain::{self, ProviderAccount};
+
+/// Published by the broker when Dictara lost the connection (MQTT last will)
+pub const OFFLINE_PAYLOAD: &str = r#"{"state":"offline"}"#;
+
+const MQTT_KEEP_ALIVE: Duration = Duration::from_secs(30);
+
+/// Wait before reconnecting to the broker
+const MQTT_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// States kept while the broker is unreachable
+const MQTT_QUEUE_CAPACITY: usize = 10;
+
+/// Hosts of the browser origins allowed to connect to the WebSocket
+const WEBSOCKET_ORIGIN_HOSTS: &[&str] = &["localhost", "127.0.0.1", "[::1]"];
+
+/// Bumped by [`save_mqtt_password`], so the cached password is read again
+static MQTT_PASSWORD_VERSION: AtomicU64 = AtomicU64::new(0);
+
+/// The running channels
+#[derive(Default)]
+pub struct StatePush {
+    mqtt: Option<MqttPublisher>,
+    /// MQTT password with the [`MQTT_PASSWORD_VERSION`] it was loaded at
+    mqtt_password: Option<(u64, Option<SecretString>)>,
+    websocket: Option<WebSocketServer>,
+}
+
+impl StatePush {
+    /// Send `payload` (the state JSON) on the channels enabled in `config`
+    pub fn publish(&mut self, config: &StatePushConfig, payload: &str) {
+        self.update_mqtt(&config.mqtt);
+        if let Some(mqtt) = &self.mqtt {
+            mqtt.publish(payload);
+        }
+
+        self.update_websocket(config.websocket);
+        if let Some(websocket) = &self.websocket {
+            websocket.publish(payload);
+        }
+    }
+
+    /// Connect to the broker, reconnect with changed settings or disconnect
+    fn update_mqtt(&mut self, config: &MqttPushConfig) {
+        if !config.enabled {
+            self.mqtt = None;
+            return;
+        }
+
+        let password = match config.username {
+            Some(_) => self.mqtt_password(),
+            None => None,
+        };
+        let unchanged = self.mqtt.as_ref().is_some_and(|mqtt| {
+            mqtt.config == *config
+                && mqtt.password.as_ref().map(|p| p.expose_secret())
+                    == password.as_ref().map(|p| p.expose_secret())
+        });
+        if !unchanged {
+            self.mqtt = Some(MqttPublisher::connect(config.clone(), password));
+        }
+    }
+
+    /// The stored MQTT password, from the keychain when it changed since the last read
+    fn mqtt_password(&mut self) -> Option<SecretString> {
+        let version = MQTT_PASSWORD_VERSION.load(Ordering::Acquire);
+        match &self.mqtt_password {
+            Some((loaded, password)) if *loaded == version => password.clone(),
+            _ => {
+                let password = load_mqtt_password();
+                self.mqtt_password = Some((version, password.clone()));
+                password
+            }
+        }
+    }
+
+    /// Start the server, restart it on another port or stop it
+    fn update_websocket(&mut self, config: WebSocketPushConfig) {
+        if !config.enabled {
+            self.websocket = None;
+            return;
+        }
+        if self
+            .websocket
+            .as_ref()
+            .is_none_or(|websocket| websocket.port != config.port)
+        {
+            self.websocket = Some(WebSocketServer::start(config.port));
+        }
+    }
+}
+
+/// Connection to the MQTT broker, closed when dropped
+struct MqttPublisher {
+    config: MqttPushConfig,
+    password: Option<SecretString>,
+    client: AsyncClient,
+    event_loop: JoinHandle<()>,
+}
+
+impl MqttPublisher {
+    fn connect(config: MqttPushConfig, password: Option<SecretString>) -> Self {
+        let client_id = format!("dictara-{}", uuid::Uuid::new_v4().simple());
+        let mut options = MqttOptions::new(client_id, config.host.trim(), config.port);
+        options.set_keep_alive(MQTT_KEEP_ALIVE);
+        options.set_last_will(LastWill::new(
+            config.topic.trim(),
+            OFFLINE_PAYLOAD,
+            QoS::AtLeastOnce,
+            true,
+        ));
+        if let Some(username) = &config.username {
+            let password = password
+                .as_ref()
+                .map(|p| p.expose_secret().to_string())
+                .unwrap_or_default();
+            options.set_credentials(username, password);
+        }
+
+        let (client, mut event_loop) = AsyncClient::new(options, MQTT_QUEUE_CAPACITY);
+        let broker = format!("{}:{}", config.host.trim(), config.port);
+        info!("Publishing the recording state to MQTT broker {}", broker);
+        // Polling drives the connection: it reconnects after an error
+        let event_loop = async_runtime::spawn(async move {
+            loop {
+                if let Err(e) = event_loop.poll().await {
+                    warn!("MQTT connection to {} failed: {}", broker, e);
+                    tokio::time::sleep(MQTT_RECONNECT_DELAY).await;
+                }
+            }
+        });
+
+        Self {
+            config,
+            password,
+            client,
+            event_loop,
+        }
+    }
+
+    fn publish(&self, payload: &str) {
+        let topic = self.config.topic.trim();
+        if let Err(e) = self
+            .client
+            .try_publish(topic, QoS::AtLeastOnce, true, payload)
+        {
+            warn!("Failed to publish the recording state over MQTT: {}", e);
+        }
+    }
+}
+
+impl Drop for MqttPublisher {
+    fn drop(&mut self) {
+        self.event_loop.abort();
+    }
+}
+
+/// WebSocket server on 127.0.0.1, stopped when dropped
+struct WebSocketServer {
+    port: u16,
+    /// Latest state, sent to the clients (they stop when it's dropped)
+    states: watch::Sender<String>,
+    server: JoinHandle<()>,
+}
+
+impl WebSocketServer {
+    fn start(port: u16) -> Self {
+        let (states, receiver) = watch::channel(String::new());
+        let server = async_runtime::spawn(async move {
+            let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    warn!(
+                        "Failed to start the state WebSocket on port {}: {}",
+                        port, e
+                    );
+                    return;
+                }
+            };
+            info!("Recording state WebSocket listening on 127.0.0.1:{}", port);
+            accept_clients(listener, receiver).await;
+        });
+
+        Self {
+            port,
+            states,
+            server,
+        }
+    }
+
+    fn publish(&self, payload: &str) {
+        self.states.send_if_modified(|state| {
+            let changed = *state != payload;
+            if changed {
+                *state = payload.to_string();
+            }
+            changed
+        });
+    }
+}
+
+impl Drop for WebSocketServer {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+async fn accept_clients(listener: TcpListener, states: watch::Receiver<String>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, address)) => {
+                let states = states.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = serve_client(stream, states).await {
+                        info!("State WebSocket client {} disconnected: {}", address, e);
+                    }
+                });
+            }
+            Err(e) => warn!("Failed to accept a state WebSocket client: {}", e),
+        }
+    }
+}
+
+/// Send the current state, then every change until the client or the server stops
+async fn serve_client(
+    stream: TcpStream,
+    mut states: watch::Receiver<String>,
+) -> Result<(), tungstenite::Error> {
+    let mut socket = tokio_tungstenite::accept_hdr_async(stream, check_origin).await?;
+
+    let state = states.borrow_and_update().clone();
+    if !state.is_empty() {
+        socket.send(Message::text(state)).await?;
+    }
+    loop {
+        tokio::select! {
+            changed = states.changed() => {
+                if changed.is_err() {
+                    return socket.close(None).await;
+                }
+                let state = states.borrow_and_update().clone();
+                socket.send(Message::text(state)).await?;
+            }
+            message = socket.next() => match message {
+                None | Some(Ok(Message::Close(_))) => return Ok(()),
+                Some(Err(e)) => return Err(e),
+                // Pings are answered by the next send, other messages are ignored
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// Refuse the handshake of a browser page that isn't served from this machine
+#[allow(clippy::result_large_err)]
+fn check_origin(request: &Request, response: Response) -> Result<Response, ErrorResponse> {
+    let origin = request
+        .headers()
+        .get(header::ORIGIN)
+        .map(|origin| origin.to_str().unwrap_or_default());
+    if is_allowed_origin(origin) {
+        return Ok(response);
+    }
+
+    info!("Refused a state WebSocket client from origin {:?}", origin);
+    let mut refusal = ErrorResponse::new(Some("Origin not allowed".to_string()));
+    *refusal.status_mut() = StatusCode::FORBIDDEN;
+    Err(refusal)
+}
+
+/// Whether a client with this `Origin` header may connect: none (not a browser) or a
+/// local page. `null` (a file or a sandboxed frame) is refused.
+fn is_allowed_origin(origin: Option<&str>) -> bool {
+    let Some(origin) = origin else {
+        return true;
+    };
+    let Some((scheme, authority)) = origin.split_once("://") else {
+        return false;
+    };
+    let host = match authority.rfind(':') {
+        // The port after the host, not a colon of an IPv6 address
+        Some(colon) if !authority[colon..].contains(']') => &authority[..colon],
+        _ => authority,
+    };
+    matches!(scheme, "http" | "https")
+        && WEBSOCKET_ORIGIN_HOSTS
+            .iter()
+            .any(|allowed| host.eq_ignore_ascii_case(allowed))
+}
+
+/// Save (or with `None`, delete) the MQTT broker password in the keychain
+pub fn save_mqtt_password(password: Option<String>) -> Result<(), String> {
+    match password {
+        Some(password) => {
+            keychain::save_provider_config(ProviderAccount::Mqtt, &MqttCredentials { password })
+        }
+        None => keychain::delete_provider_config(ProviderAccount::Mqtt),
+    }
+    .map_err(|e| format!("Failed to save the MQTT password: {}", e))?;
+    MQTT_PASSWORD_VERSION.fetch_add(1, Ordering::Release);
+    Ok(())
+}
+
+/// MQTT broker password, `None` when none is stored or the keychain can't be read
+fn load_mqtt_password() -> Option<SecretString> {
+    match keychain::load_provider_config::<MqttCredentials>(ProviderAccount::Mqtt) {
+        Ok(credentials) => credentials.map(|credentials| SecretString::from(credentials.password)),
+        Err(e) => {
+            warn!("Failed to load the MQTT password: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+    #[tokio::test]
+    async fn test_websocket_sends_the_state_changes() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (states, receiver) = watch::channel(r#"{"state":"ready"}"#.to_string());
+        tokio::spawn(accept_clients(listener, receiver));
+
+        let (mut client, _) = tokio_tungstenite::connect_async(format!("ws://{}", address))
+            .await
+            .unwrap();
+        let first = client.next().await.unwrap().unwrap();
+        assert_eq!(first.to_text().unwrap(), r#"{"state":"ready"}"#);
+
+        states.send_replace(r#"{"state":"recording"}"#.to_string());
+        let second = client.next().await.unwrap().unwrap();
+        assert_eq!(second.to_text().unwrap(), r#"{"state":"recording"}"#);
+
+        // The clients stop with the server
+        drop(states);
+        assert!(matches!(
+            client.next().await,
+            None | Some(Ok(Message::Close(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_websocket_refuses_other_websites() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (_states, receiver) = watch::channel(r#"{"state":"ready"}"#.to_string());
+        tokio::spawn(accept_clients(listener, receiver));
+
+        let connect = |origin: &'static str| {
+            let mut request = format!("ws://{}", address).into_client_request().unwrap();
+            request
+                .headers_mut()
+                .insert(header::ORIGIN, origin.parse().unwrap());
+            tokio_tungstenite::connect_async(request)
+        };
+        match connect("https://evil.example").await {
+            Err(tungstenite::Error::Http(response)) => {
+                assert_eq!(response.status(), StatusCode::FORBIDDEN)
+            }
+            other => panic!("expected a refusal, got {:?}", other.map(|_| ())),
+        }
+        assert!(connect("http://localhost:3000").await.is_ok());
+    }
+
+    #[test]
+    fn test_allowed_origins() {
+        assert!(is_allowed_origin(None));
+        assert!(is_allowed_origin(Some("http://localhost:8123")));
+        assert!(is_allowed_origin(Some("https://127.0.0.1")));
+        assert!(is_allowed_origin(Some("http://[::1]:8080")));
+        assert!(is_allowed_origin(Some("http://LOCALHOST")));
+
+        assert!(!is_allowed_origin(Some("https://evil.example")));
+        assert!(!is_allowed_origin(Some("http://localhost.evil.example")));
+        assert!(!is_allowed_origin(Some("null")));
+        assert!(!is_allowed_origin(Some("")));
+    }
+}
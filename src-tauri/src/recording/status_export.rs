@@ -0,0 +1,138 @@
+//! Opt-in export of the recording state for external status bars (SketchyBar, iStat, ...).
+//!
+//! Channels, each enabled separately in AppConfig:
+//! - a JSON status file at `<app cache>/status.json`, rewritten atomically on every change
+//! - a macOS distributed notification named [`STATUS_NOTIFICATION_NAME`] whose object is the state
+//! - MQTT and a local WebSocket, for home automation (see [`StatePush`])
+
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::config::{self, ConfigKey, ConfigStore};
+
+use super::state_manager::RecordingState;
+use super::state_push::StatePush;
+
+/// Distributed notification posted on every state change (macOS)
+pub const STATUS_NOTIFICATION_NAME: &str = "app.dictara.recordingStateChanged";
+
+/// Contents of the status file and of the pushed states
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusSnapshot {
+    /// "ready" | "recording" | "recordingLocked" | "transcribing"
+    state: &'static str,
+    /// Unix timestamp (seconds) of the state change
+    updated_at: u64,
+}
+
+/// Publishes state changes, skipping duplicates
+pub struct StatusExporter {
+    app_handle: AppHandle,
+    last_state: Option<RecordingState>,
+    state_push: StatePush,
+}
+
+impl StatusExporter {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            app_handle,
+            last_state: None,
+            state_push: StatePush::default(),
+        }
+    }
+
+    /// Publish `state` if it changed and at least one channel is enabled
+    pub fn publish(&mut self, state: RecordingState) {
+        if self.last_state == Some(state) {
+            return;
+        }
+        self.last_state = Some(state);
+
+        let config_store = self.app_handle.state::<config::Config>();
+        let app_config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+
+        let snapshot = StatusSnapshot {
+            state: state_name(state),
+            updated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        };
+        let json = match serde_json::to_string(&snapshot) {
+            Ok(json) => json,
+            Err(e) => {
+                log::warn!("Failed to serialize the recording state: {}", e);
+                return;
+            }
+        };
+
+        if app_config.status_file_enabled {
+            if let Err(e) = self.write_status_file(&json) {
+                log::warn!("Failed to write status file: {}", e);
+            }
+        }
+
+        if app_config.status_notification_enabled {
+            post_status_notification(state_name(state));
+        }
+
+        // Also stops the channels that were turned off
+        self.state_push.publish(&app_config.state_push, &json);
+    }
+
+    fn write_status_file(&self, json: &str) -> Result<(), String> {
+        let path = status_file_path(&self.app_handle)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+
+        // Write + rename so readers never see a half-written file
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, json).map_err(|e| e.to_string())?;
+        std::fs::rename(&tmp_path, &path).map_err(|e| e.to_string())
+    }
+}
+
+/// Location of the status file (`<app cache>/status.json`)
+pub fn status_file_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    app_handle
+        .path()
+        .app_cache_dir()
+        .map(|dir| dir.join("status.json"))
+        .map_err(|e| format!("Cache directory not found: {}", e))
+}
+
+fn state_name(state: RecordingState) -> &'static str {
+    match state {
+        RecordingState::Ready => "ready",
+        RecordingState::Recording => "recording",
+        RecordingState::RecordingLocked => "recordingLocked",
+        RecordingState::Transcribing => "transcribing",
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn post_status_notification(state: &str) {
+    use objc2_foundation::{NSDistributedNotificationCenter, NSString};
+
+    let center = NSDistributedNotificationCenter::defaultCenter();
+    let name = NSString::from_str(STATUS_NOTIFICATION_NAME);
+    let object = NSString::from_str(state);
+
+    // SAFETY: no userInfo dictionary is passed, name and object are valid NSStrings
+    unsafe {
+        center.postNotificationName_object_userInfo_deliverImmediately(
+            &name,
+            Some(&object),
+            None,
+            true,
+        );
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn post_status_notification(_state: &str) {}
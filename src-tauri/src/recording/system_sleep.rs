@@ -0,0 +1,87 @@
+//! Ends a running recording when the Mac goes to sleep or the screen locks.
+//!
+//! Audio captured with the lid closed is silence at best, and the input stream
+//! does not reliably survive the wake. The recording is stopped and transcribed,
+//! or cancelled, per [`SystemSleepPolicy`]. The same commands as the shortcuts
+//! are sent, so the state machine goes through its usual transitions.
+
+use log::{info, warn};
+use std::sync::Arc;
+use tauri::Manager;
+use tokio::sync::mpsc::Sender;
+
+use crate::config::{self, ConfigKey, ConfigStore, SystemSleepPolicy};
+use crate::recording::{RecordingCommand, RecordingState, RecordingStateManager};
+
+/// Distributed notification posted by loginwindow when the screen locks
+#[cfg(target_os = "macos")]
+const SCREEN_LOCKED_NOTIFICATION: &str = "com.apple.screenIsLocked";
+
+/// Stop or cancel recordings on system sleep and screen lock, for the app lifetime
+pub fn watch_system_sleep(
+    app_handle: tauri::AppHandle,
+    command_tx: Sender<RecordingCommand>,
+    state_manager: Arc<RecordingStateManager>,
+) {
+    observe(move |reason| {
+        if !matches!(
+            state_manager.current(),
+            RecordingState::Recording | RecordingState::RecordingLocked
+        ) {
+            return;
+        }
+
+        let policy = app_handle
+            .state::<config::Config>()
+            .get(&ConfigKey::APP)
+            .unwrap_or_default()
+            .system_sleep_policy;
+        let command = match policy {
+            SystemSleepPolicy::Transcribe => RecordingCommand::StopRecording,
+            SystemSleepPolicy::Cancel => RecordingCommand::Cancel,
+        };
+
+        info!("{} while recording, sending {:?}", reason, command);
+        if let Err(e) = command_tx.try_send(command) {
+            warn!("Failed to end the recording before sleep: {}", e);
+        }
+    });
+}
+
+#[cfg(target_os = "macos")]
+fn observe(on_sleep: impl Fn(&str) + 'static) {
+    use block2::RcBlock;
+    use objc2_app_kit::{NSWorkspace, NSWorkspaceWillSleepNotification};
+    use objc2_foundation::{
+        NSDistributedNotificationCenter, NSNotification, NSNotificationCenter, NSString,
+    };
+    use std::ptr::NonNull;
+    use std::rc::Rc;
+
+    let on_sleep = Rc::new(on_sleep);
+    let add_observer = |center: &NSNotificationCenter, name: &NSString, reason: &'static str| {
+        let on_sleep = Rc::clone(&on_sleep);
+        let block = RcBlock::new(move |_: NonNull<NSNotification>| on_sleep(reason));
+        // SAFETY: name is a valid NSString, no sender filter and no queue (the block
+        // runs on the posting thread, the main thread for both centers). The center
+        // keeps the observer registered for the app lifetime.
+        let _ = unsafe {
+            center.addObserverForName_object_queue_usingBlock(Some(name), None, None, &block)
+        };
+    };
+
+    let workspace_center = NSWorkspace::sharedWorkspace().notificationCenter();
+    // SAFETY: AppKit extern static, initialized before main
+    let will_sleep = unsafe { NSWorkspaceWillSleepNotification };
+    add_observer(&workspace_center, will_sleep, "System going to sleep");
+
+    let distributed_center = NSDistributedNotificationCenter::defaultCenter();
+    add_observer(
+        &distributed_center,
+        &NSString::from_str(SCREEN_LOCKED_NOTIFICATION),
+        "Screen locked",
+    );
+}
+
+#[cfg(not(target_os = "macos"))]
+fn observe(_on_sleep: impl Fn(&str) + 'static) {}
@@ -0,0 +1,189 @@
+//! Word-level diff between the recognized and the post-processed text, so the UI
+//! can show what the LLM changed.
+
+use serde::{Deserialize, Serialize};
+
+/// Largest LCS table (words x words) computed, longer texts show the middle as replaced
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffKind {
+    Unchanged,
+    Added,
+    Removed,
+}
+
+/// Consecutive words with the same kind, joined by single spaces
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffSegment {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TextDiff {
+    /// The original text, removed and added words in reading order
+    pub segments: Vec<DiffSegment>,
+    pub words_added: u32,
+    pub words_removed: u32,
+}
+
+/// Diff `original` and `rewritten` word by word (whitespace-separated, punctuation included)
+pub fn diff_words(original: &str, rewritten: &str) -> TextDiff {
+    let old: Vec<&str> = original.split_whitespace().collect();
+    let new: Vec<&str> = rewritten.split_whitespace().collect();
+
+    // The LLM usually touches a few words: trim the common ends before the LCS
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut diff = TextDiff::default();
+    for word in &old[..prefix] {
+        push(&mut diff, DiffKind::Unchanged, word);
+    }
+    for (kind, word) in diff_middle(old_middle, new_middle) {
+        push(&mut diff, kind, word);
+    }
+    for word in &old[old.len() - suffix..] {
+        push(&mut diff, DiffKind::Unchanged, word);
+    }
+    diff
+}
+
+fn diff_middle<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(DiffKind, &'a str)> {
+    if old.len().saturating_mul(new.len()) > MAX_DIFF_CELLS {
+        return old
+            .iter()
+            .map(|word| (DiffKind::Removed, *word))
+            .chain(new.iter().map(|word| (DiffKind::Added, *word)))
+            .collect();
+    }
+
+    // lcs[i][j] = length of the longest common subsequence of old[i..] and new[j..]
+    let width = new.len() + 1;
+    let mut lcs = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i * width + j] = if old[i] == new[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            ops.push((DiffKind::Unchanged, old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+            ops.push((DiffKind::Removed, old[i]));
+            i += 1;
+        } else {
+            ops.push((DiffKind::Added, new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|word| (DiffKind::Removed, *word)));
+    ops.extend(new[j..].iter().map(|word| (DiffKind::Added, *word)));
+    ops
+}
+
+fn push(diff: &mut TextDiff, kind: DiffKind, word: &str) {
+    match kind {
+        DiffKind::Added => diff.words_added += 1,
+        DiffKind::Removed => diff.words_removed += 1,
+        DiffKind::Unchanged => {}
+    }
+    match diff.segments.last_mut() {
+        Some(segment) if segment.kind == kind => {
+            segment.text.push(' ');
+            segment.text.push_str(word);
+        }
+        _ => diff.segments.push(DiffSegment {
+            kind,
+            text: word.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(diff: &TextDiff) -> Vec<(DiffKind, &str)> {
+        diff.segments
+            .iter()
+            .map(|segment| (segment.kind, segment.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_identical_text() {
+        let diff = diff_words("hello  world", "hello world");
+        assert_eq!(segments(&diff), [(DiffKind::Unchanged, "hello world")]);
+        assert_eq!((diff.words_added, diff.words_removed), (0, 0));
+    }
+
+    #[test]
+    fn test_replaced_words_in_the_middle() {
+        let diff = diff_words("so um I think we should go", "I think we should leave now");
+        assert_eq!(
+            segments(&diff),
+            [
+                (DiffKind::Removed, "so um"),
+                (DiffKind::Unchanged, "I think we should"),
+                (DiffKind::Removed, "go"),
+                (DiffKind::Added, "leave now"),
+            ]
+        );
+        assert_eq!((diff.words_added, diff.words_removed), (2, 3));
+    }
+
+    #[test]
+    fn test_punctuation_counts_as_a_change() {
+        let diff = diff_words("hello world", "Hello, world.");
+        assert_eq!(
+            segments(&diff),
+            [
+                (DiffKind::Removed, "hello world"),
+                (DiffKind::Added, "Hello, world."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_sides() {
+        assert_eq!(diff_words("", ""), TextDiff::default());
+        assert_eq!(
+            segments(&diff_words("", "new text")),
+            [(DiffKind::Added, "new text")]
+        );
+        assert_eq!(
+            segments(&diff_words("old text", "")),
+            [(DiffKind::Removed, "old text")]
+        );
+    }
+
+    #[test]
+    fn test_repeated_words_keep_common_ends() {
+        let diff = diff_words("a a a", "a a");
+        assert_eq!(
+            segments(&diff),
+            [(DiffKind::Unchanged, "a a"), (DiffKind::Removed, "a")]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Periodic ticks while recording, so the popup can show the elapsed time and warn
+//! before the recording hits its length limit.
+//!
+//! The ticker thread only sends [`RecordingCommand::Tick`] to the controller, which
+//! emits `RecordingTick` and stops the recording once the limit is reached.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use dictara_core::recorder::AUDIO_BYTES_PER_SECOND;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::mpsc::WeakSender;
+
+use super::commands::RecordingCommand;
+use crate::clients::MAX_FILE_SIZE_BYTES;
+use crate::config::AppConfig;
+
+/// Time between two ticks
+pub const RECORDING_TICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Size of the WAV header in front of the samples
+const WAV_HEADER_BYTES: u64 = 44;
+
+/// Longest recording whose audio file is accepted for transcription
+pub fn file_size_limit() -> Duration {
+    Duration::from_millis((MAX_FILE_SIZE_BYTES - WAV_HEADER_BYTES) * 1000 / AUDIO_BYTES_PER_SECOND)
+}
+
+/// Longest recording: the configured maximum, capped by the file size limit
+pub fn recording_limit(config: &AppConfig) -> Duration {
+    let limit = file_size_limit();
+    match config.max_recording_secs {
+        0 => limit,
+        secs => limit.min(Duration::from_secs(secs.into())),
+    }
+}
+
+/// Sends [`RecordingCommand::Tick`] every [`RECORDING_TICK_INTERVAL`] while running.
+///
+/// Holds a weak sender, so the command channel still closes when everyone else is gone.
+pub struct RecordingTicker {
+    command_tx: WeakSender<RecordingCommand>,
+    running: Option<Arc<AtomicBool>>,
+}
+
+impl RecordingTicker {
+    pub fn new(command_tx: WeakSender<RecordingCommand>) -> Self {
+        Self {
+            command_tx,
+            running: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    pub fn start(&mut self) {
+        self.stop();
+
+        let running = Arc::new(AtomicBool::new(true));
+        self.running = Some(running.clone());
+        let command_tx = self.command_tx.clone();
+
+        let spawned = thread::Builder::new()
+            .name("recording-ticker".to_string())
+            .spawn(move || loop {
+                thread::sleep(RECORDING_TICK_INTERVAL);
+                if !running.load(Ordering::SeqCst) {
+                    return;
+                }
+                let Some(command_tx) = command_tx.upgrade() else {
+                    return;
+                };
+                // A full channel means the controller is busy, the next tick catches up
+                if let Err(TrySendError::Closed(_)) = command_tx.try_send(RecordingCommand::Tick) {
+                    return;
+                }
+            });
+        if let Err(e) = spawned {
+            log::error!("Failed to start recording ticker: {}", e);
+            self.running = None;
+        }
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(running) = self.running.take() {
+            running.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+impl Drop for RecordingTicker {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_limit() {
+        // 25MB of 16kHz mono 16-bit audio
+        assert_eq!(file_size_limit().as_secs(), 819);
+
+        let mut config = AppConfig::default();
+        assert_eq!(recording_limit(&config), file_size_limit());
+
+        config.max_recording_secs = 60;
+        assert_eq!(recording_limit(&config), Duration::from_secs(60));
+
+        config.max_recording_secs = 3600;
+        assert_eq!(recording_limit(&config), file_size_limit());
+    }
+}
@@ -0,0 +1,269 @@
+//! Profanity and sensitive data filter.
+//!
+//! For users dictating where the pasted text is shared or recorded (screen sharing,
+//! streaming, support tickets), the filter masks or removes profanity, payment card
+//! numbers and email addresses from the final text before it is pasted. It runs after
+//! post-processing, so the model can't bring filtered words back, and before voice
+//! snippets are expanded, so the user's own address or signature is left alone.
+
+use std::ops::Range;
+
+use dictara_core::words::{normalized, word_ranges};
+
+use crate::config::{RedactionConfig, RedactionStrictness, RedactionStyle};
+
+/// Always filtered, as whole words
+const STRONG_WORDS: &[&str] = &[
+    "asshole", "assholes", "bastard", "bastards", "bitch", "bitches", "cock", "cocks", "dick",
+    "dicks", "prick", "pricks", "pussy", "slut", "sluts", "twat", "wanker", "wankers", "whore",
+    "whores",
+];
+
+/// Always filtered, with any ending ("fucking", "shitty")
+const STRONG_STEMS: &[&str] = &["bullshit", "cunt", "fuck", "motherfuck", "shit"];
+
+/// Filtered in [`RedactionStrictness::Strict`] only
+const MILD_WORDS: &[&str] = &[
+    "arse", "ass", "bloody", "bugger", "crap", "crappy", "damn", "damned", "dammit", "goddamn",
+    "hell", "piss", "pissed",
+];
+
+/// What a filtered range of the text is
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Redacted {
+    Word,
+    Email,
+    CardNumber,
+}
+
+/// Mask or remove profanity and sensitive data of `text`, as configured
+pub fn redact(text: &str, config: &RedactionConfig) -> String {
+    if !config.enabled {
+        return text.to_string();
+    }
+
+    let allowed: Vec<String> = config.allowed_words.iter().map(|w| normalized(w)).collect();
+    let blocked: Vec<String> = config.blocked_words.iter().map(|w| normalized(w)).collect();
+
+    let mut redactions: Vec<(Range<usize>, Redacted)> = Vec::new();
+    if config.redact_card_numbers {
+        redactions.extend(
+            card_numbers(text)
+                .into_iter()
+                .map(|range| (range, Redacted::CardNumber)),
+        );
+    }
+    for word in words(text) {
+        let overlaps = redactions
+            .iter()
+            .any(|(range, _)| range.start < word.end && word.start < range.end);
+        if overlaps || allowed.contains(&normalized(&text[word.start..word.end])) {
+            continue;
+        }
+        let redacted = if config.redact_emails && is_email(&text[word.start..word.end]) {
+            Redacted::Email
+        } else if is_profane(&word.normalized, config.strictness)
+            || blocked.contains(&word.normalized)
+        {
+            Redacted::Word
+        } else {
+            continue;
+        };
+        redactions.push((word.start..word.end, redacted));
+    }
+    redactions.sort_by_key(|(range, _)| range.start);
+
+    let mut output = String::with_capacity(text.len());
+    let mut copied_to = 0;
+    for (range, redacted) in redactions {
+        let before = &text[copied_to..range.start];
+        match config.style {
+            RedactionStyle::Mask => {
+                output.push_str(before);
+                output.push_str(&mask(&text[range.clone()], redacted));
+                copied_to = range.end;
+            }
+            RedactionStyle::Remove => {
+                // Drop the space before the word too, or after it when there is none
+                let trimmed = before.trim_end();
+                output.push_str(trimmed);
+                let rest = &text[range.end..];
+                copied_to = if trimmed.len() < before.len() {
+                    range.end
+                } else {
+                    range.end + (rest.len() - rest.trim_start().len())
+                };
+            }
+        }
+    }
+    output.push_str(&text[copied_to..]);
+    output
+}
+
+fn mask(text: &str, redacted: Redacted) -> String {
+    match redacted {
+        Redacted::Word => {
+            let mut chars = text.chars();
+            let first = chars.next().map(String::from).unwrap_or_default();
+            first + &"*".repeat(chars.count())
+        }
+        Redacted::Email => "[email]".to_string(),
+        Redacted::CardNumber => "[card number]".to_string(),
+    }
+}
+
+fn is_profane(word: &str, strictness: RedactionStrictness) -> bool {
+    STRONG_WORDS.contains(&word)
+        || STRONG_STEMS.iter().any(|stem| word.starts_with(stem))
+        || (strictness == RedactionStrictness::Strict && MILD_WORDS.contains(&word))
+}
+
+/// A word of the text (see [`dictara_core::words`]) and its lowercase letters and digits
+struct Word {
+    start: usize,
+    end: usize,
+    normalized: String,
+}
+
+fn words(text: &str) -> Vec<Word> {
+    word_ranges(text)
+        .into_iter()
+        .map(|range| Word {
+            start: range.start,
+            end: range.end,
+            normalized: normalized(&text[range]),
+        })
+        .collect()
+}
+
+/// Whether a word (without surrounding punctuation) is an email address
+fn is_email(word: &str) -> bool {
+    let Some((local, domain)) = word.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && domain.contains('.')
+        && domain.split('.').all(|label| !label.is_empty())
+        && word
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '@' | '.' | '-' | '_' | '+'))
+        && !domain.contains('@')
+}
+
+/// Byte ranges of 13-19 digit numbers, optionally grouped by single spaces or dashes,
+/// that pass the Luhn check of payment card numbers
+fn card_numbers(text: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() || (i > 0 && bytes[i - 1].is_ascii_alphanumeric()) {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let mut end = i;
+        let mut digits = Vec::new();
+        while i < bytes.len() {
+            if bytes[i].is_ascii_digit() {
+                digits.push(bytes[i] - b'0');
+                i += 1;
+                end = i;
+            } else if matches!(bytes[i], b' ' | b'-')
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+
+        let standalone = !bytes.get(end).is_some_and(u8::is_ascii_alphanumeric);
+        if standalone && (13..=19).contains(&digits.len()) && passes_luhn(&digits) {
+            ranges.push(start..end);
+        }
+    }
+    ranges
+}
+
+fn passes_luhn(digits: &[u8]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &digit)| {
+            let digit = u32::from(digit);
+            match (i % 2 == 1, digit * 2) {
+                (true, doubled) if doubled > 9 => doubled - 9,
+                (true, doubled) => doubled,
+                (false, _) => digit,
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(style: RedactionStyle) -> RedactionConfig {
+        RedactionConfig {
+            enabled: true,
+            style,
+            redact_card_numbers: true,
+            redact_emails: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_mask() {
+        let config = config(RedactionStyle::Mask);
+        assert_eq!(
+            redact("This fucking build is shit, damn it.", &config),
+            "This f****** build is s***, damn it."
+        );
+        assert_eq!(
+            redact(
+                "Mail jane.doe@example.com my card 4111 1111 1111 1111.",
+                &config
+            ),
+            "Mail [email] my card [card number]."
+        );
+        // Not Luhn-valid, or part of a word
+        assert_eq!(
+            redact(
+                "Order 1234 5678 9012 3456 and ID A4111111111111111",
+                &config
+            ),
+            "Order 1234 5678 9012 3456 and ID A4111111111111111"
+        );
+        assert_eq!(redact("Damn", &RedactionConfig::default()), "Damn");
+    }
+
+    #[test]
+    fn test_remove() {
+        let config = config(RedactionStyle::Remove);
+        assert_eq!(
+            redact("What the fuck is this?", &config),
+            "What the is this?"
+        );
+        assert_eq!(redact("Shit it broke", &config), "it broke");
+    }
+
+    #[test]
+    fn test_strictness_and_word_lists() {
+        let config = RedactionConfig {
+            strictness: RedactionStrictness::Strict,
+            blocked_words: vec!["Frak".to_string()],
+            allowed_words: vec!["Dick".to_string()],
+            ..config(RedactionStyle::Mask)
+        };
+        assert_eq!(
+            redact("Damn, frak it. Ask Dick.", &config),
+            "D***, f*** it. Ask Dick."
+        );
+    }
+}
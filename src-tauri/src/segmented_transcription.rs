@@ -0,0 +1,286 @@
+//! Continuous microphone transcription in segments, for live captions and meeting mode.
+//!
+//! A separate recording (own VAD, scratch WAV in a work dir) hands its speech samples
+//! to the calling thread, which cuts them into segments of at most
+//! [`Segmentation::max_segment_ms`] of speech, or shorter once speech pauses, and
+//! transcribes each with the active provider. System audio is mixed in when a loopback
+//! device is configured, as for dictation. Dictation keeps working meanwhile.
+//!
+//! Segments are timed by their position in the recorded speech: the pauses the VAD
+//! leaves out don't count, and each segment starts where the previous one ended.
+
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use dictara_core::audio_file;
+use dictara_core::recorder::{cleanup_recording_file, RecordingOptions, SAMPLE_RATE};
+use dictara_core::transcription::TranscriptSegment;
+use log::{error, warn};
+use tauri::{AppHandle, Manager};
+
+use crate::clients::Transcriber;
+use crate::config::{self, ConfigKey, ConfigStore};
+use crate::recording::core_recorder;
+
+/// Segments shorter than this are dropped (too short to transcribe reliably)
+const MIN_SEGMENT_MS: u64 = 500;
+
+/// How often the stop signal is checked while no speech arrives
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Where the speech is cut into segments
+#[derive(Debug, Clone, Copy)]
+pub struct Segmentation {
+    /// Longest segment, in milliseconds of speech
+    pub max_segment_ms: u64,
+    /// A shorter segment is cut once no speech arrived for this long
+    pub pause: Duration,
+}
+
+/// Speech samples cut off for transcription
+#[derive(Debug, PartialEq)]
+struct Cut {
+    samples: Vec<f32>,
+    /// Position in the speech of the session, in milliseconds
+    start_ms: u64,
+    end_ms: u64,
+}
+
+/// Cuts the incoming speech into segments of [`Segmentation`]
+struct SegmentCutter {
+    max_samples: usize,
+    pause: Duration,
+    segment: Vec<f32>,
+    /// Samples of speech before `segment`
+    offset: u64,
+}
+
+impl SegmentCutter {
+    fn new(segmentation: Segmentation) -> Self {
+        let max_samples = (segmentation.max_segment_ms * SAMPLE_RATE as u64 / 1000) as usize;
+        Self {
+            max_samples,
+            pause: segmentation.pause,
+            segment: Vec::with_capacity(max_samples),
+            offset: 0,
+        }
+    }
+
+    fn push(&mut self, samples: &[f32]) {
+        self.segment.extend_from_slice(samples);
+    }
+
+    /// The next segment once it is full, or once no speech arrived for `silence`
+    fn cut(&mut self, silence: Duration) -> Option<Cut> {
+        if self.segment.len() >= self.max_samples {
+            Some(self.take(self.max_samples))
+        } else if !self.segment.is_empty() && silence >= self.pause {
+            Some(self.take(self.segment.len()))
+        } else {
+            None
+        }
+    }
+
+    /// The speech not cut yet, on stop
+    fn finish(mut self) -> Option<Cut> {
+        (!self.segment.is_empty()).then(|| self.take(self.segment.len()))
+    }
+
+    fn take(&mut self, len: usize) -> Cut {
+        let samples: Vec<f32> = self.segment.drain(..len).collect();
+        let start = self.offset;
+        self.offset += len as u64;
+        Cut {
+            samples,
+            start_ms: start * 1000 / SAMPLE_RATE as u64,
+            end_ms: self.offset * 1000 / SAMPLE_RATE as u64,
+        }
+    }
+}
+
+/// Record and transcribe until the sender of `stop_rx` is dropped
+///
+/// Each transcribed, non-empty segment goes to `on_segment` with its speech samples,
+/// timed by its position in the speech. Speech recorded after the last cut is
+/// transcribed on stop.
+pub fn transcribe_microphone(
+    app: &AppHandle,
+    transcriber: &Transcriber,
+    segmentation: Segmentation,
+    work_dir: &Path,
+    stop_rx: &Receiver<()>,
+    mut on_segment: impl FnMut(TranscriptSegment, &[f32]),
+) -> Result<(), String> {
+    std::fs::create_dir_all(work_dir)
+        .map_err(|e| format!("Failed to create {}: {}", work_dir.display(), e))?;
+    let recorder = core_recorder(app, work_dir.to_path_buf());
+    let loopback_device = app
+        .state::<config::Config>()
+        .get(&ConfigKey::APP)
+        .unwrap_or_default()
+        .loopback_device;
+
+    let (samples_tx, samples_rx) = mpsc::channel::<Vec<f32>>();
+    let recording = recorder
+        .start(RecordingOptions {
+            speech_callback: Some(Box::new(move |samples: &[f32]| {
+                let _ = samples_tx.send(samples.to_vec());
+            })),
+            loopback_device,
+            ..Default::default()
+        })
+        .map_err(|e| e.user_message())?;
+
+    let mut cutter = SegmentCutter::new(segmentation);
+    let mut last_speech = Instant::now();
+
+    loop {
+        if let Err(mpsc::TryRecvError::Disconnected) = stop_rx.try_recv() {
+            break;
+        }
+
+        match samples_rx.recv_timeout(POLL_INTERVAL) {
+            Ok(samples) => {
+                cutter.push(&samples);
+                last_speech = Instant::now();
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        while let Some(cut) = cutter.cut(last_speech.elapsed()) {
+            if let Some(transcribed) = transcribe_segment(transcriber, &cut, work_dir) {
+                on_segment(transcribed, &cut.samples);
+            }
+        }
+    }
+
+    let result = recording.stop();
+    if let Ok(result) = &result {
+        cleanup_recording_file(&result.file_path);
+    }
+    if let Some(cut) = cutter.finish() {
+        if let Some(transcribed) = transcribe_segment(transcriber, &cut, work_dir) {
+            on_segment(transcribed, &cut.samples);
+        }
+    }
+
+    result.map(|_| ()).map_err(|e| e.user_message())
+}
+
+/// Transcribe one segment, `None` if it is too short, silent or failed
+fn transcribe_segment(
+    transcriber: &Transcriber,
+    cut: &Cut,
+    work_dir: &Path,
+) -> Option<TranscriptSegment> {
+    let duration_ms = cut.end_ms - cut.start_ms;
+    if duration_ms < MIN_SEGMENT_MS {
+        return None;
+    }
+
+    let path = work_dir.join("segment.wav");
+    if let Err(e) = audio_file::write_wav_16k_mono(&path, &cut.samples) {
+        error!("Failed to write speech segment: {}", e);
+        return None;
+    }
+    let text = transcriber.transcribe(path.clone(), duration_ms);
+    if let Err(e) = std::fs::remove_file(&path) {
+        warn!("Failed to clean up speech segment: {}", e);
+    }
+
+    match text {
+        Ok(text) if !text.trim().is_empty() => Some(TranscriptSegment {
+            start_ms: cut.start_ms,
+            end_ms: cut.end_ms,
+            text: text.trim().to_string(),
+        }),
+        Ok(_) => None,
+        Err(e) => {
+            warn!("Failed to transcribe speech segment: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEGMENTATION: Segmentation = Segmentation {
+        max_segment_ms: 1_000,
+        pause: Duration::from_millis(500),
+    };
+
+    /// `ms` of speech
+    fn speech(ms: u64) -> Vec<f32> {
+        vec![0.1; (ms * SAMPLE_RATE as u64 / 1000) as usize]
+    }
+
+    fn times(cut: &Cut) -> (u64, u64) {
+        (cut.start_ms, cut.end_ms)
+    }
+
+    #[test]
+    fn test_cut_at_max_length() {
+        let mut cutter = SegmentCutter::new(SEGMENTATION);
+        cutter.push(&speech(900));
+        assert_eq!(cutter.cut(Duration::ZERO), None);
+
+        // A long chunk is cut into full segments, the rest waits for more speech
+        cutter.push(&speech(1_600));
+        let first = cutter.cut(Duration::ZERO).unwrap();
+        assert_eq!(first.samples.len(), speech(1_000).len());
+        assert_eq!(times(&first), (0, 1_000));
+        assert_eq!(times(&cutter.cut(Duration::ZERO).unwrap()), (1_000, 2_000));
+        assert_eq!(cutter.cut(Duration::ZERO), None);
+
+        assert_eq!(times(&cutter.finish().unwrap()), (2_000, 2_500));
+    }
+
+    #[test]
+    fn test_cut_on_pause() {
+        let mut cutter = SegmentCutter::new(SEGMENTATION);
+        assert_eq!(cutter.cut(Duration::from_secs(10)), None);
+
+        cutter.push(&speech(300));
+        assert_eq!(cutter.cut(Duration::from_millis(499)), None);
+        assert_eq!(
+            times(&cutter.cut(Duration::from_millis(500)).unwrap()),
+            (0, 300)
+        );
+        // Silence alone makes no segment
+        assert_eq!(cutter.cut(Duration::from_secs(10)), None);
+        assert_eq!(cutter.finish(), None);
+    }
+
+    #[test]
+    fn test_timestamps_are_continuous() {
+        let mut cutter = SegmentCutter::new(SEGMENTATION);
+        let mut cuts = Vec::new();
+        // Chunks of odd lengths, with pauses in between
+        for ms in [130, 470, 990, 20, 1_333, 7] {
+            cutter.push(&speech(ms));
+            while let Some(cut) = cutter.cut(Duration::ZERO) {
+                cuts.push(cut);
+            }
+            cuts.extend(cutter.cut(SEGMENTATION.pause));
+        }
+        cuts.extend(cutter.finish());
+
+        assert_eq!(cuts[0].start_ms, 0);
+        for pair in cuts.windows(2) {
+            assert_eq!(pair[0].end_ms, pair[1].start_ms);
+        }
+        assert_eq!(
+            cuts.last().unwrap().end_ms,
+            130 + 470 + 990 + 20 + 1_333 + 7
+        );
+        let samples: usize = cuts.iter().map(|cut| cut.samples.len()).sum();
+        assert_eq!(
+            samples as u64,
+            (130 + 470 + 990 + 20 + 1_333 + 7) * SAMPLE_RATE as u64 / 1000
+        );
+    }
+}
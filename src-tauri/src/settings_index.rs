@@ -0,0 +1,520 @@
+//! Searchable index of the preferences.
+//!
+//! Each entry points at a config field (`<config key>.<camelCase field path>`) and
+//! carries the words a user might type to find it. The test at the bottom checks
+//! every id against the serialized config, so a renamed or removed field fails
+//! the build instead of leaving a dead search result.
+
+use serde::{Deserialize, Serialize};
+
+use SettingsCategory::*;
+
+/// Preferences page a setting lives on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum SettingsCategory {
+    General,
+    Transcription,
+    PostProcessing,
+    Recording,
+    Paste,
+    Shortcuts,
+    Appearance,
+    Privacy,
+}
+
+/// One searchable preference
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingMetadata {
+    /// `<config key>.<field path>`, e.g. `appConfig.postProcessModel`
+    pub id: String,
+    pub title: String,
+    pub keywords: Vec<String>,
+    pub category: SettingsCategory,
+}
+
+struct Setting {
+    id: &'static str,
+    title: &'static str,
+    keywords: &'static [&'static str],
+    category: SettingsCategory,
+}
+
+const fn setting(
+    id: &'static str,
+    title: &'static str,
+    keywords: &'static [&'static str],
+    category: SettingsCategory,
+) -> Setting {
+    Setting {
+        id,
+        title,
+        keywords,
+        category,
+    }
+}
+
+const SETTINGS: &[Setting] = &[
+    // General
+    setting(
+        "appConfig.autostartEnabled",
+        "Launch at login",
+        &["autostart", "startup", "login", "boot"],
+        General,
+    ),
+    setting(
+        "appConfig.dailyWordGoal",
+        "Daily word goal",
+        &["goal", "words", "target", "statistics"],
+        General,
+    ),
+    setting(
+        "automationConfig.rules",
+        "Automation rules",
+        &["schedule", "time of day", "weekdays", "night"],
+        General,
+    ),
+    setting(
+        "profilesConfig.profiles",
+        "Profiles",
+        &["work", "personal", "workspace", "switch"],
+        General,
+    ),
+    setting(
+        "outputSinksConfig.journal.enabled",
+        "Daily journal file",
+        &["obsidian", "markdown", "notes", "export", "log"],
+        General,
+    ),
+    setting(
+        "outputSinksConfig.webhook.enabled",
+        "Webhook",
+        &["zapier", "n8n", "integration", "http", "post"],
+        General,
+    ),
+    setting(
+        "appConfig.statusFileEnabled",
+        "Export status to a file",
+        &["status bar", "sketchybar", "integration", "status.json"],
+        General,
+    ),
+    setting(
+        "appConfig.statusNotificationEnabled",
+        "Post status notifications",
+        &["status bar", "distributed notification", "integration"],
+        General,
+    ),
+    setting(
+        "appConfig.statePush.mqtt.enabled",
+        "Publish recording state over MQTT",
+        &["home automation", "home assistant", "smart light", "broker"],
+        General,
+    ),
+    setting(
+        "appConfig.statePush.websocket.enabled",
+        "Recording state WebSocket",
+        &[
+            "home automation",
+            "smart light",
+            "integration",
+            "local server",
+        ],
+        General,
+    ),
+    // Transcription
+    setting(
+        "appConfig.activeProvider",
+        "Transcription provider",
+        &["engine", "openai", "azure", "local", "whisper", "api"],
+        Transcription,
+    ),
+    setting(
+        "appConfig.fallbackProviders",
+        "Fallback providers",
+        &["failover", "retry", "backup", "offline"],
+        Transcription,
+    ),
+    setting(
+        "appConfig.transcriptionOptions",
+        "Transcription model and prompt",
+        &[
+            "temperature",
+            "vocabulary",
+            "language",
+            "gpt-4o-transcribe",
+            "whisper-1",
+        ],
+        Transcription,
+    ),
+    setting(
+        "appConfig.minSpeechDurationMs",
+        "Minimum speech duration",
+        &["silence", "short", "vad", "threshold"],
+        Transcription,
+    ),
+    setting(
+        "localModelConfig.selectedModel",
+        "Local model",
+        &["whisper", "parakeet", "offline", "download", "model"],
+        Transcription,
+    ),
+    setting(
+        "localModelConfig.downloadMirror",
+        "Model download mirror",
+        &["mirror", "hugging face", "token", "download", "model"],
+        Transcription,
+    ),
+    setting(
+        "localModelConfig.modelsDir",
+        "Models folder",
+        &[
+            "storage",
+            "disk",
+            "space",
+            "external drive",
+            "location",
+            "model",
+        ],
+        Transcription,
+    ),
+    setting(
+        "localModelConfig.deleteToTrash",
+        "Move deleted models to the Trash",
+        &["trash", "recycle bin", "delete", "remove", "model"],
+        Transcription,
+    ),
+    setting(
+        "appConfig.formatNormalization",
+        "Number and date formatting",
+        &["numbers", "dates", "units", "digits", "normalize", "format"],
+        Transcription,
+    ),
+    setting(
+        "appConfig.markdownMode",
+        "Markdown dictation mode",
+        &[
+            "markdown",
+            "bullet",
+            "heading",
+            "code block",
+            "formatting",
+            "editor",
+        ],
+        Transcription,
+    ),
+    // Post-processing
+    setting(
+        "appConfig.postProcessEnabled",
+        "Post-processing",
+        &["llm", "rewrite", "cleanup", "punctuation", "ai"],
+        PostProcessing,
+    ),
+    setting(
+        "appConfig.postProcessModel",
+        "Post-processing model",
+        &["llm", "gpt", "model"],
+        PostProcessing,
+    ),
+    setting(
+        "appConfig.postProcessPrompt",
+        "Post-processing prompt",
+        &["instructions", "prompt", "style"],
+        PostProcessing,
+    ),
+    setting(
+        "appConfig.postProcessPromptsByLanguage",
+        "Prompts per language",
+        &["language", "japanese", "prompt", "locale"],
+        PostProcessing,
+    ),
+    setting(
+        "appConfig.postProcessParams.timeoutSecs",
+        "Post-processing timeout",
+        &["timeout", "slow", "latency"],
+        PostProcessing,
+    ),
+    setting(
+        "appConfig.postProcessParams.temperature",
+        "Post-processing temperature",
+        &["temperature", "sampling", "creativity"],
+        PostProcessing,
+    ),
+    setting(
+        "appConfig.postProcessParams.maxOutputTokens",
+        "Post-processing output limit",
+        &["tokens", "length", "limit"],
+        PostProcessing,
+    ),
+    setting(
+        "appConfig.postProcessParams.guardOutput",
+        "Reject derailed rewrites",
+        &["prompt injection", "guard", "safety", "translate"],
+        PostProcessing,
+    ),
+    setting(
+        "appConfig.documentContextEnabled",
+        "Use the window title as context",
+        &["context", "accessibility", "document", "names"],
+        PostProcessing,
+    ),
+    setting(
+        "appConfig.documentContextIncludeSelection",
+        "Include the selected text as context",
+        &["context", "selection"],
+        PostProcessing,
+    ),
+    // Recording
+    setting(
+        "appConfig.loopbackDevice",
+        "System audio device",
+        &["loopback", "blackhole", "meeting", "call", "system audio"],
+        Recording,
+    ),
+    setting(
+        "appConfig.inputDeviceChangePolicy",
+        "When the microphone changes",
+        &["microphone", "input", "device", "airpods", "unplugged"],
+        Recording,
+    ),
+    setting(
+        "appConfig.recordingFlushPolicy",
+        "Recording crash safety",
+        &["flush", "fsync", "crash", "disk"],
+        Recording,
+    ),
+    setting(
+        "appConfig.meetingDiarization",
+        "Speaker labels in meeting notes",
+        &["diarization", "speaker", "meeting", "who said"],
+        Recording,
+    ),
+    setting(
+        "appConfig.systemSleepPolicy",
+        "When the Mac sleeps or locks",
+        &["sleep", "lock", "lid", "screen saver", "cancel"],
+        Recording,
+    ),
+    setting(
+        "appConfig.audioLevelMaxHz",
+        "Level meter update rate",
+        &["level", "meter", "battery", "cpu", "waveform"],
+        Recording,
+    ),
+    setting(
+        "appConfig.maxRecordingSecs",
+        "Maximum recording length",
+        &["limit", "duration", "countdown", "timer", "25mb"],
+        Recording,
+    ),
+    // Paste
+    setting(
+        "appConfig.clipboardMarker",
+        "Hide dictations from clipboard managers",
+        &[
+            "clipboard",
+            "history",
+            "maccy",
+            "raycast",
+            "transient",
+            "concealed",
+        ],
+        Paste,
+    ),
+    setting(
+        "appConfig.holdPasteOnFocusChange",
+        "Hold back the paste after switching apps",
+        &["focus", "wrong app", "notification", "paste"],
+        Paste,
+    ),
+    setting(
+        "appConfig.pasteTiming",
+        "Clipboard restore timing",
+        &["delay", "restore", "electron", "slow", "paste"],
+        Paste,
+    ),
+    // Shortcuts
+    setting(
+        "appConfig.recordingTrigger",
+        "Recording key",
+        &["fn", "globe", "trigger", "hotkey"],
+        Shortcuts,
+    ),
+    setting(
+        "shortcutsConfig.pushToRecord",
+        "Push-to-record shortcut",
+        &["hold", "hotkey", "keyboard"],
+        Shortcuts,
+    ),
+    setting(
+        "shortcutsConfig.handsFree",
+        "Hands-free shortcut",
+        &["toggle", "hotkey", "keyboard"],
+        Shortcuts,
+    ),
+    setting(
+        "shortcutsConfig.rawMode",
+        "Raw mode shortcut",
+        &["verbatim", "raw", "hotkey"],
+        Shortcuts,
+    ),
+    setting(
+        "shortcutsConfig.deviceShortcuts",
+        "Per-keyboard shortcuts",
+        &["keyboard", "external", "device"],
+        Shortcuts,
+    ),
+    setting(
+        "shortcutsConfig.holdThresholdMs",
+        "Tap vs. hold threshold",
+        &["tap", "hold", "fn", "emoji", "delay"],
+        Shortcuts,
+    ),
+    setting(
+        "shortcutsConfig.minStartIntervalMs",
+        "Minimum time between recordings",
+        &["debounce", "rate limit", "rapid", "double tap"],
+        Shortcuts,
+    ),
+    setting(
+        "shortcutsConfig.bounceMs",
+        "Key bounce filter",
+        &["debounce", "bounce", "flicker", "fn"],
+        Shortcuts,
+    ),
+    setting(
+        "appConfig.workerThreads",
+        "Worker threads",
+        &["performance", "cpu", "threads", "download", "priority"],
+        General,
+    ),
+    setting(
+        "appConfig.keyboardTap",
+        "Keyboard event tap",
+        &["tap", "lag", "listen only", "karabiner", "secure input"],
+        Shortcuts,
+    ),
+    // Appearance
+    setting(
+        "uiPrefs.popupStyle",
+        "Popup style",
+        &["popup", "compact", "waveform", "appearance"],
+        Appearance,
+    ),
+    setting(
+        "uiPrefs.popupContent",
+        "Popup content",
+        &["popup", "timer", "live text"],
+        Appearance,
+    ),
+    setting(
+        "uiPrefs.popupOpacity",
+        "Popup opacity",
+        &["transparency", "opacity"],
+        Appearance,
+    ),
+    setting(
+        "uiPrefs.followSystemTheme",
+        "Follow the system theme",
+        &["dark mode", "light mode", "theme"],
+        Appearance,
+    ),
+    // Privacy
+    setting(
+        "appConfig.proxy.mode",
+        "Proxy",
+        &["proxy", "network", "corporate", "firewall", "http"],
+        Privacy,
+    ),
+    setting(
+        "appConfig.tls",
+        "Custom root certificate and certificate pinning",
+        &["certificate", "tls", "ssl", "ca", "pinning", "corporate"],
+        Privacy,
+    ),
+    setting(
+        "appConfig.auditLog",
+        "Audit log of API requests",
+        &["audit", "log", "requests", "compliance", "privacy"],
+        Privacy,
+    ),
+    setting(
+        "appConfig.redaction",
+        "Profanity and sensitive data filter",
+        &[
+            "profanity",
+            "swearing",
+            "redact",
+            "censor",
+            "credit card",
+            "email",
+            "privacy",
+        ],
+        Privacy,
+    ),
+];
+
+/// All searchable preferences
+pub fn settings_metadata() -> Vec<SettingMetadata> {
+    SETTINGS
+        .iter()
+        .map(|setting| SettingMetadata {
+            id: setting.id.to_string(),
+            title: setting.title.to_string(),
+            keywords: setting.keywords.iter().map(|k| k.to_string()).collect(),
+            category: setting.category,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{
+        AppConfig, AutomationConfig, LocalModelConfig, OutputSinksConfig, ProfilesConfig,
+        ShortcutsConfig, UiPrefs,
+    };
+
+    #[test]
+    fn test_settings_point_at_config_fields() {
+        let configs = [
+            ("appConfig", serde_json::to_value(AppConfig::default())),
+            (
+                "localModelConfig",
+                serde_json::to_value(LocalModelConfig::default()),
+            ),
+            (
+                "shortcutsConfig",
+                serde_json::to_value(ShortcutsConfig::default()),
+            ),
+            ("uiPrefs", serde_json::to_value(UiPrefs::default())),
+            (
+                "automationConfig",
+                serde_json::to_value(AutomationConfig::default()),
+            ),
+            (
+                "profilesConfig",
+                serde_json::to_value(ProfilesConfig::default()),
+            ),
+            (
+                "outputSinksConfig",
+                serde_json::to_value(OutputSinksConfig::default()),
+            ),
+        ];
+
+        for setting in SETTINGS {
+            let mut path = setting.id.split('.');
+            let key = path.next().unwrap();
+            let (_, config) = configs
+                .iter()
+                .find(|(name, _)| *name == key)
+                .unwrap_or_else(|| panic!("{}: unknown config key", setting.id));
+            let mut value = config.as_ref().unwrap();
+            for field in path {
+                value = value
+                    .get(field)
+                    .unwrap_or_else(|| panic!("{}: no field {}", setting.id, field));
+            }
+        }
+    }
+}
@@ -1,26 +1,46 @@
 use crate::updater::{self, Updater};
 use crate::{
-    autolaunch,
+    audit::AuditLog,
+    autolaunch, automation,
+    clients::ProviderHealth,
     config::{
-        self, AzureOpenAIConfig, ConfigKey, ConfigStore, OnboardingStep, OpenAIConfig, Provider,
-        ShortcutsConfig,
+        self, AppConfig, AzureOpenAIConfig, ConfigKey, ConfigStore, LocalModelConfig,
+        OnboardingStep, OpenAIConfig, Provider, ShortcutsConfig, UiPrefs,
     },
-    globe_key,
+    crash_guard::{self, SystemGlobeKey, SystemGuard},
+    file_transcription::FileTranscriber,
     keyboard_listener::KeyListener,
     keychain::{self, ProviderAccount},
+    language::SessionLanguage,
+    live_captions::LiveCaptions,
+    login_session::watch_login_session,
+    managed_config::ManagedConfig,
+    meeting::MeetingRecorder,
     models::{ModelLoader, ModelManager},
     recording::{
-        cleanup_old_recordings, Controller, LastRecording, LastRecordingState, RecordingCommand,
-        RecordingStateManager,
+        recover_interrupted_recording, watch_system_sleep, Controller, LastRecording,
+        LastRecordingState, RawMode, RecordingCommand, RecordingStateManager,
     },
-    specta, telemetry,
-    ui::{menu::Menu, tray::Tray, window},
+    sinks::OutputSinks,
+    specta,
+    startup::StartupProfiler,
+    telemetry, templates,
+    ui::{
+        engine_menu, held_notification,
+        menu::Menu,
+        prefs::{self, UiPrefsChanged},
+        tray::Tray,
+        window,
+    },
+    worker_pool::{self, WorkerPool},
 };
+use dictara_core::input_monitor::DefaultInputMonitor;
 use log::{error, info, warn};
 use std::sync::{Arc, Mutex};
 use tauri::ipc::Channel;
 use tauri::Manager;
 use tauri_plugin_store::StoreExt;
+use tauri_specta::Event;
 use tokio::sync::mpsc;
 
 pub struct RecordingCommandSender {
@@ -34,11 +54,15 @@ pub struct AudioLevelChannel {
 pub fn setup_app(app: &mut tauri::App<tauri::Wry>) -> Result<(), Box<dyn std::error::Error>> {
     info!("Dictara v{}", env!("CARGO_PKG_VERSION"));
 
+    // Time the startup phases, run what the tray doesn't need once setup returned
+    let profiler = Arc::new(StartupProfiler::new());
+    app.manage(profiler.clone());
+
     // Setup Specta for type-safe TypeScript bindings and event emission
     specta::setup(app.handle());
 
-    // Clean up old recordings from previous sessions
-    cleanup_old_recordings(app.app_handle());
+    // Clean up old recordings from previous sessions, keeping one that was interrupted
+    let interrupted_recording = recover_interrupted_recording(app.app_handle());
 
     // Check accessibility permission on macOS
     #[cfg(target_os = "macos")]
@@ -58,8 +82,9 @@ pub fn setup_app(app: &mut tauri::App<tauri::Wry>) -> Result<(), Box<dyn std::er
     // Load app config and check if properly configured
     let store = app.store("config.json")?;
 
-    // Create and register Config as managed state
-    let config_store = config::Config::new(store.clone());
+    // Create and register Config as managed state, with the values locked by IT on top
+    let managed_config = ManagedConfig::load(&app.config().identifier);
+    let config_store = config::Config::new(store.clone(), managed_config);
     app.manage(config_store.clone());
 
     // Initialize telemetry and Sentry
@@ -68,16 +93,28 @@ pub fn setup_app(app: &mut tauri::App<tauri::Wry>) -> Result<(), Box<dyn std::er
     let device_id = telemetry::get_or_create_device_id(&config_store);
     let _sentry_guard = telemetry::init_sentry(&device_id, &config_store);
 
+    // Bring the stored config up to the current schema version
+    let config_backup_dir = app.path().app_data_dir()?;
+    if let Err(e) = config::run_migrations(&config_store, |version| {
+        backup_config_file(&config_backup_dir, version)
+    }) {
+        warn!("Failed to migrate config: {}", e);
+    }
+
+    // Put back the system settings a crashed session left changed, and restore this
+    // session's changes on exit or a termination signal
+    app.manage(SystemGuard::start(config_store.clone(), SystemGlobeKey));
+    crash_guard::watch_exit_signals(app.app_handle());
+
+    // Journal file, webhook: the destinations of dictations besides the paste
+    app.manage(OutputSinks::start(app.app_handle()));
+
     let mut app_config = config_store.get(&ConfigKey::APP).unwrap_or_default();
     let mut onboarding_config = config_store.get(&ConfigKey::ONBOARDING).unwrap_or_default();
 
     // Enable autostart on first launch (before onboarding is completed)
     autolaunch::setup_autolaunch_if_needed(app.handle(), &config_store, &mut app_config);
 
-    // Load or migrate shortcuts config
-    if let Err(e) = config::migrate_trigger_to_shortcuts(&config_store) {
-        warn!("Failed to migrate shortcuts config: {}", e);
-    }
     let shortcuts_config = config_store
         .get(&ConfigKey::<ShortcutsConfig>::SHORTCUTS)
         .unwrap_or_default();
@@ -99,81 +136,41 @@ pub fn setup_app(app: &mut tauri::App<tauri::Wry>) -> Result<(), Box<dyn std::er
         // Save the updated config
         config_store.set(&ConfigKey::ONBOARDING, onboarding_config.clone())?;
     }
+    profiler.phase("config");
+
+    // Threads for blocking work, used by the model manager and loader (interactive
+    // transcription ahead of background downloads)
+    app.manage(Arc::new(WorkerPool::new(worker_pool::pool_size(
+        app_config.worker_threads,
+    ))));
 
     // Initialize ModelManager and ModelLoader for local transcription
     let model_manager = Arc::new(
         ModelManager::new(app.app_handle())
             .map_err(|e| format!("Failed to create ModelManager: {}", e))?,
     );
-    let model_loader = Arc::new(ModelLoader::new(model_manager.models_dir().clone()));
+    let model_loader = Arc::new(ModelLoader::new(
+        model_manager.location().clone(),
+        app.app_handle().clone(),
+    ));
 
     // Migrate old models to new unified directory structure in background
     // This runs asynchronously to not block app startup
     {
         let manager = model_manager.clone();
+        let app_handle = app.app_handle().clone();
         tauri::async_runtime::spawn(async move {
-            if let Err(e) = manager.migrate_old_models().await {
+            if let Err(e) = manager.migrate_old_models(&app_handle).await {
                 error!("Failed to migrate models: {}", e);
             }
         });
     }
 
-    // Check if any provider is properly configured
-    let needs_configuration = match &app_config.active_provider {
-        Some(Provider::OpenAI) => {
-            keychain::load_provider_config::<OpenAIConfig>(ProviderAccount::OpenAI)
-                .ok()
-                .flatten()
-                .is_none()
-        }
-        Some(Provider::AzureOpenAI) => {
-            keychain::load_provider_config::<AzureOpenAIConfig>(ProviderAccount::AzureOpenAI)
-                .ok()
-                .flatten()
-                .is_none()
-        }
-        Some(Provider::Local) => {
-            // Local provider is configured if a model is selected AND downloaded
-            let local_config = config_store.get(&ConfigKey::LOCAL_MODEL);
-            match local_config {
-                Some(cfg) => {
-                    cfg.selected_model.is_none()
-                        || !model_manager
-                            .is_model_downloaded(cfg.selected_model.as_deref().unwrap_or(""))
-                }
-                None => true,
-            }
-        }
-        None => true,
-    };
-
-    if needs_configuration {
-        warn!("AI provider not configured");
-    }
-
     // Store model manager and loader in app state
     app.manage(model_manager.clone());
     app.manage(model_loader.clone());
 
-    // Eager load local model if Local provider is active and model is selected/downloaded
-    if app_config.active_provider == Some(Provider::Local) {
-        if let Some(local_config) = config_store.get(&ConfigKey::LOCAL_MODEL) {
-            if let Some(model_name) = local_config.selected_model {
-                if model_manager.is_model_downloaded(&model_name) {
-                    info!("Eagerly loading local model: {}", model_name);
-                    let loader = model_loader.clone();
-                    let app_handle = app.app_handle().clone();
-                    // Load in background to not block app startup
-                    // Use tauri::async_runtime::spawn which works in setup context
-                    tauri::async_runtime::spawn(async move {
-                        if let Err(e) = loader.load_model(&model_name, &app_handle).await {
-                            error!("Failed to load local model on startup: {}", e);
-                        }
-                    });
-                }
-            }
-        }
-    }
+    profiler.phase("models");
 
     // Determine if we need to show onboarding
     let show_onboarding = !onboarding_config.finished;
@@ -198,14 +195,36 @@ pub fn setup_app(app: &mut tauri::App<tauri::Wry>) -> Result<(), Box<dyn std::er
     };
 
     // Create last recording state for paste retry functionality
-    let last_recording_state: LastRecordingState = Arc::new(Mutex::new(LastRecording::new()));
+    // An interrupted take from the previous session can be transcribed with "retry"
+    let mut last_recording = LastRecording::new();
+    if let Some(path) = interrupted_recording {
+        info!(
+            "Recovered interrupted recording {}, available for retry",
+            path
+        );
+        last_recording.audio_file_path = Some(path);
+    }
+    let last_recording_state: LastRecordingState = Arc::new(Mutex::new(last_recording));
 
-    let menu = Menu::new(app)?;
+    let menu = Menu::new(app, &templates::load_templates(&config_store))?;
     let _tray = Tray::new(app, &menu)?;
+    // "Paste" and "Copy" actions of the notification of a held-back transcription
+    held_notification::setup(app.app_handle());
+    profiler.phase("menu and tray");
+
+    // Template submenu handle, refreshed when templates are edited
+    app.manage(menu.templates_menu());
+    // Engine submenu handle, refreshed on provider, model and download changes
+    app.manage(menu.engine_menu());
+
+    // Raw (verbatim) dictation mode, off at startup
+    let raw_mode = Arc::new(RawMode::new(app.app_handle().clone(), menu.raw_mode_item()));
+    app.manage(raw_mode.clone());
 
     // Initialize controller (transcriber created on-demand from config)
     let controller = Controller::new(
         command_rx,
+        command_tx.downgrade(),
         app.app_handle().clone(),
         state_manager.clone(),
         audio_level_channel.channel.clone(),
@@ -217,12 +236,61 @@ pub fn setup_app(app: &mut tauri::App<tauri::Wry>) -> Result<(), Box<dyn std::er
     std::thread::spawn(move || {
         controller.run();
     });
+    profiler.phase("controller");
+
+    // Tell the controller when the default input device changes (e.g. AirPods connected).
+    // Managed so the listener stays registered for the app lifetime.
+    let device_change_tx = command_tx.clone();
+    if let Some(monitor) = DefaultInputMonitor::start(Box::new(move || {
+        // Called on a CoreAudio thread, never block it
+        if let Err(e) = device_change_tx.try_send(RecordingCommand::DefaultInputChanged) {
+            warn!("Failed to report default input change: {}", e);
+        }
+    })) {
+        app.manage(monitor);
+    }
+
+    // End a running recording when the Mac goes to sleep or the screen locks
+    watch_system_sleep(
+        app.app_handle().clone(),
+        command_tx.clone(),
+        state_manager.clone(),
+    );
+
+    // Stay out of another user's session after fast user switching
+    watch_login_session(
+        app.app_handle().clone(),
+        command_tx.clone(),
+        state_manager.clone(),
+    );
 
-    // Store sender and audio level channel in app state for Tauri commands
+    // Store sender, state manager and audio level channel in app state for Tauri commands
     app.manage(command_sender_state);
+    app.manage(state_manager.clone());
     app.manage(audio_level_channel);
     app.manage(last_recording_state.clone());
 
+    // Language pinned for this session (auto-detect by default)
+    app.manage(Arc::new(SessionLanguage::new()));
+
+    // Batch transcription of existing audio files
+    app.manage(Arc::new(FileTranscriber::new()));
+
+    // Floating captions window transcribing the microphone
+    app.manage(LiveCaptions::new());
+
+    // Long-form meeting recording with summary
+    app.manage(MeetingRecorder::new());
+
+    // Recent success/failure of each provider (failover)
+    app.manage(Arc::new(ProviderHealth::default()));
+
+    // Audit log of outbound API requests (off unless enabled in preferences)
+    app.manage(Arc::new(AuditLog::new(
+        app.path().app_data_dir()?.join("audit"),
+        app_config.audit_log,
+    )));
+
     // Only start keyboard listener if accessibility permission is granted
     // This prevents the permission dialog from appearing during onboarding
     #[cfg(target_os = "macos")]
@@ -231,36 +299,280 @@ pub fn setup_app(app: &mut tauri::App<tauri::Wry>) -> Result<(), Box<dyn std::er
     let has_accessibility = true;
 
     if has_accessibility {
-        let listener =
-            KeyListener::start(command_tx, state_manager.clone(), shortcuts_config.clone());
+        let listener = KeyListener::start(
+            command_tx,
+            state_manager.clone(),
+            raw_mode,
+            shortcuts_config.clone(),
+            app_config.keyboard_tap.to_grab_config(),
+            app.handle().clone(),
+        );
 
         // Manage KeyListener in Tauri state for hot-swapping
         app.manage(listener);
     }
+    profiler.phase("keyboard listener");
 
     // Initialize and start the updater
     // In debug mode: checks and downloads updates but skips installation
     // In release mode: checks, downloads, and installs updates when user is idle
     let updater = Arc::new(Updater::new(state_manager));
     app.manage(updater.clone());
-    updater::start_periodic_update_check(app.app_handle().clone(), updater);
+
+    // Hot-reload subsystems when preferences change
+    subscribe_to_config_changes(app.app_handle(), &config_store);
+
+    // Apply the time-of-day automation rules as they start. After the subscription:
+    // a rule active at launch changes settings the subsystems must reload.
+    automation::start_scheduler(app.app_handle().clone());
 
     // Only fix the Globe key setting when using Fn in any shortcut
     // This prevents the emoji picker from appearing when using Fn for recording
     if KeyListener::uses_fn_key(&shortcuts_config) {
-        globe_key::fix_globe_key_if_needed();
+        app.state::<SystemGuard>().fix_globe_key();
     }
 
-    // Decide which window to open
+    // Onboarding opens right away, the preferences only once the deferred
+    // configuration check found no usable provider
     if show_onboarding {
         if let Err(e) = window::open_onboarding_window(app.app_handle()) {
             error!("Failed to open onboarding window: {}", e);
         }
-    } else if needs_configuration {
-        if let Err(e) = window::open_preferences_window(app.app_handle()) {
-            error!("Failed to open preferences window: {}", e);
+    }
+    profiler.phase("windows");
+
+    // Deferred: each of these reads the models directory, the keychain or the network
+    let handle = app.app_handle().clone();
+    profiler.defer("engine menu", move || {
+        engine_menu::refresh_engine_menu(&handle)
+    });
+    let handle = app.app_handle().clone();
+    profiler.defer("configuration check", move || {
+        if !show_onboarding && needs_configuration(&handle) {
+            if let Err(e) = window::open_preferences_window(&handle) {
+                error!("Failed to open preferences window: {}", e);
+            }
+        }
+    });
+    // Eager load local model if Local provider is active and model is selected/downloaded
+    let handle = app.app_handle().clone();
+    profiler.defer("model preload", move || preload_selected_model(&handle));
+    let handle = app.app_handle().clone();
+    profiler.defer("updater", move || {
+        updater::start_periodic_update_check(handle, updater)
+    });
+    profiler.finish_setup();
+
+    Ok(())
+}
+
+/// Another copy of the app was launched and exited: bring this instance forward
+/// instead (the onboarding window while it's open, the preferences otherwise)
+pub fn focus_existing_instance(app_handle: &tauri::AppHandle, _args: Vec<String>, _cwd: String) {
+    info!("Another instance was launched, showing this one");
+    let result = if app_handle.get_webview_window("onboarding").is_some() {
+        window::open_onboarding_window(app_handle)
+    } else {
+        window::open_preferences_window(app_handle)
+    };
+    if let Err(e) = result {
+        error!("Failed to show the running instance: {}", e);
+    }
+}
+
+/// Whether the active provider can't transcribe: no API key, or no downloaded model.
+/// A locked or denied keychain doesn't mean the key is missing: it is reported instead.
+fn needs_configuration(app_handle: &tauri::AppHandle) -> bool {
+    let config_store = app_handle.state::<config::Config>();
+    let api_key_missing = |result: Result<bool, keychain::KeychainError>, account| match result {
+        Ok(configured) => !configured,
+        Err(e) => {
+            keychain::report_access_error(app_handle, account, &e);
+            false
         }
+    };
+    let needs_configuration = match config_store
+        .get(&ConfigKey::APP)
+        .unwrap_or_default()
+        .active_provider
+    {
+        Some(Provider::OpenAI) => api_key_missing(
+            keychain::load_provider_config::<OpenAIConfig>(ProviderAccount::OpenAI)
+                .map(|config| config.is_some()),
+            ProviderAccount::OpenAI,
+        ),
+        Some(Provider::AzureOpenAI) => api_key_missing(
+            keychain::load_provider_config::<AzureOpenAIConfig>(ProviderAccount::AzureOpenAI)
+                .map(|config| config.is_some()),
+            ProviderAccount::AzureOpenAI,
+        ),
+        // Local provider is configured if a model is selected AND downloaded
+        Some(Provider::Local) => match config_store
+            .get(&ConfigKey::LOCAL_MODEL)
+            .and_then(|cfg| cfg.selected_model)
+        {
+            Some(model_name) => !app_handle
+                .state::<Arc<ModelManager>>()
+                .is_model_downloaded(&model_name),
+            None => true,
+        },
+        None => true,
+    };
+
+    if needs_configuration {
+        warn!("AI provider not configured");
+    }
+    needs_configuration
+}
+
+/// Load the selected local model in the background when the Local provider is active,
+/// so the first transcription doesn't wait for it
+fn preload_selected_model(app_handle: &tauri::AppHandle) {
+    let config_store = app_handle.state::<config::Config>();
+    if config_store
+        .get(&ConfigKey::APP)
+        .unwrap_or_default()
+        .active_provider
+        != Some(Provider::Local)
+    {
+        return;
+    }
+    let Some(model_name) = config_store
+        .get(&ConfigKey::LOCAL_MODEL)
+        .and_then(|local_config| local_config.selected_model)
+    else {
+        return;
+    };
+
+    let model_manager = app_handle.state::<Arc<ModelManager>>();
+    let loader = app_handle.state::<Arc<ModelLoader>>().inner().clone();
+    if !model_manager.is_model_downloaded(&model_name)
+        || loader.is_model_loaded(&model_name)
+        || loader.is_model_loading(&model_name)
+    {
+        return;
     }
 
+    info!("Eagerly loading local model: {}", model_name);
+    let app_handle = app_handle.clone();
+    // Load in background to not block app startup
+    // Use tauri::async_runtime::spawn which works in setup context
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = loader.load_model(&model_name, &app_handle).await {
+            error!("Failed to load local model: {}", e);
+        }
+    });
+}
+
+/// Forward config changes to the frontend and hot-reload the subsystems that cache
+/// settings. The recording controller reads `AppConfig` for every recording, so it
+/// picks up changes without a subscription.
+fn subscribe_to_config_changes(app_handle: &tauri::AppHandle, config_store: &config::Config) {
+    let handle = app_handle.clone();
+    config_store.changes().subscribe(move |change| {
+        if let Err(e) = change.clone().emit(&handle) {
+            error!("Failed to emit ConfigChanged: {}", e);
+        }
+    });
+
+    // Keyboard listener: new shortcuts take effect immediately
+    let handle = app_handle.clone();
+    config_store.changes().subscribe(move |change| {
+        if !ConfigKey::<ShortcutsConfig>::SHORTCUTS.matches(change) {
+            return;
+        }
+        let Some(listener) = handle.try_state::<KeyListener>() else {
+            return;
+        };
+        let shortcuts = handle
+            .state::<config::Config>()
+            .get(&ConfigKey::SHORTCUTS)
+            .unwrap_or_default();
+        match listener.update_shortcuts(shortcuts) {
+            Ok(()) => info!("Shortcuts config hot-swapped to KeyListener"),
+            Err(e) => error!("Failed to update KeyListener shortcuts: {}", e),
+        }
+    });
+
+    // Globe key: changed while a shortcut uses Fn, the user's setting back otherwise
+    let handle = app_handle.clone();
+    config_store.changes().subscribe(move |change| {
+        if !ConfigKey::<ShortcutsConfig>::SHORTCUTS.matches(change) {
+            return;
+        }
+        let Some(guard) = handle.try_state::<SystemGuard>() else {
+            return;
+        };
+        let shortcuts = handle
+            .state::<config::Config>()
+            .get(&ConfigKey::SHORTCUTS)
+            .unwrap_or_default();
+        if KeyListener::uses_fn_key(&shortcuts) {
+            guard.fix_globe_key();
+        } else if let Err(e) = guard.restore() {
+            error!("{}", e);
+        }
+    });
+
+    // Audit log: enabling or disabling takes effect for the next request
+    let handle = app_handle.clone();
+    config_store.changes().subscribe(move |change| {
+        if !ConfigKey::<AppConfig>::APP.matches(change) {
+            return;
+        }
+        if let Some(audit) = handle.try_state::<Arc<AuditLog>>() {
+            let config = handle
+                .state::<config::Config>()
+                .get(&ConfigKey::APP)
+                .unwrap_or_default();
+            audit.set_config(config.audit_log);
+        }
+    });
+
+    // Model loader: preload the selected model when it or the provider changes
+    let handle = app_handle.clone();
+    config_store.changes().subscribe(move |change| {
+        if ConfigKey::<AppConfig>::APP.matches(change)
+            || ConfigKey::<LocalModelConfig>::LOCAL_MODEL.matches(change)
+        {
+            preload_selected_model(&handle);
+        }
+    });
+
+    // Windows: restyle live when the appearance preferences change
+    let handle = app_handle.clone();
+    config_store.changes().subscribe(move |change| {
+        if !ConfigKey::<UiPrefs>::UI_PREFS.matches(change) {
+            return;
+        }
+        let event = UiPrefsChanged {
+            prefs: prefs::load_ui_prefs(&handle),
+        };
+        if let Err(e) = event.emit(&handle) {
+            error!("Failed to emit UiPrefsChanged: {}", e);
+        }
+    });
+
+    // Tray menu: keep the engine checkmarks in sync with Preferences
+    let handle = app_handle.clone();
+    config_store.changes().subscribe(move |change| {
+        if ConfigKey::<AppConfig>::APP.matches(change)
+            || ConfigKey::<LocalModelConfig>::LOCAL_MODEL.matches(change)
+        {
+            engine_menu::refresh_engine_menu(&handle);
+        }
+    });
+}
+
+/// Copy the config file aside before migrating it (`config.v<version>.backup.json`)
+fn backup_config_file(dir: &std::path::Path, version: u32) -> Result<(), String> {
+    let config_file = dir.join("config.json");
+    if !config_file.exists() {
+        return Ok(());
+    }
+    let backup_file = dir.join(format!("config.v{}.backup.json", version));
+    std::fs::copy(&config_file, &backup_file)
+        .map_err(|e| format!("Failed to back up config to {:?}: {}", backup_file, e))?;
+    info!("Backed up config version {} to {:?}", version, backup_file);
     Ok(())
 }
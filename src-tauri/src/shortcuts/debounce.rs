@@ -0,0 +1,196 @@
+//! Debouncing of rapid shortcut presses.
+//!
+//! Tapping Fn quickly used to send a Start/Stop pair per tap, flooding the recording
+//! controller. [`Debouncer`] sits between the keyboard listener and the command channel:
+//!
+//! - a start less than [`ShortcutsConfig::min_start_interval_ms`] after the previous one
+//!   is dropped, together with the stop of the same press
+//! - the stop on release of push-to-talk waits [`ShortcutsConfig::bounce_ms`]: a press of
+//!   the same key within that time is contact bounce (a spurious FlagsChanged pair), the
+//!   stop is cancelled and the recording goes on
+//!
+//! [`ShortcutsConfig::min_start_interval_ms`]: crate::config::ShortcutsConfig::min_start_interval_ms
+//! [`ShortcutsConfig::bounce_ms`]: crate::config::ShortcutsConfig::bounce_ms
+
+use log::debug;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use crate::recording::RecordingCommand;
+
+/// A stop waiting out the bounce time
+struct PendingStop {
+    id: u64,
+    keycode: u32,
+}
+
+/// Rate limit of recording starts and bounce filter of push-to-talk releases
+#[derive(Default)]
+pub struct Debouncer {
+    last_start: Option<Instant>,
+    /// The start of the current press was dropped, so is its stop
+    suppressed: bool,
+    pending_stop: Arc<Mutex<Option<PendingStop>>>,
+    next_id: u64,
+}
+
+impl Debouncer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send `commands` (a start, possibly followed by a lock) unless the previous start
+    /// was less than `min_interval` ago. Returns whether they were sent.
+    pub fn start(
+        &mut self,
+        commands: &[RecordingCommand],
+        min_interval: Duration,
+        command_tx: &mpsc::Sender<RecordingCommand>,
+    ) -> bool {
+        let now = Instant::now();
+        if self
+            .last_start
+            .is_some_and(|last| now.duration_since(last) < min_interval)
+        {
+            debug!("Shortcut pressed again within {:?}, ignored", min_interval);
+            self.suppressed = true;
+            return false;
+        }
+
+        self.last_start = Some(now);
+        self.suppressed = false;
+        for command in commands {
+            let _ = command_tx.blocking_send(command.clone());
+        }
+        true
+    }
+
+    /// Send `commands` for a press already started with [`Self::start`] (push-to-talk
+    /// turning into hands-free), unless its start was dropped
+    pub fn continue_press(
+        &self,
+        commands: &[RecordingCommand],
+        command_tx: &mpsc::Sender<RecordingCommand>,
+    ) {
+        if self.suppressed {
+            return;
+        }
+        for command in commands {
+            let _ = command_tx.blocking_send(command.clone());
+        }
+    }
+
+    /// Stop the recording once `keycode` has stayed released for `bounce`, unless the
+    /// start of this press was dropped
+    pub fn stop_on_release(
+        &mut self,
+        keycode: u32,
+        bounce: Duration,
+        command_tx: &mpsc::Sender<RecordingCommand>,
+    ) {
+        if std::mem::take(&mut self.suppressed) {
+            return;
+        }
+        if bounce.is_zero() {
+            let _ = command_tx.blocking_send(RecordingCommand::StopRecording);
+            return;
+        }
+
+        self.next_id += 1;
+        let id = self.next_id;
+        if let Ok(mut pending) = self.pending_stop.lock() {
+            *pending = Some(PendingStop { id, keycode });
+        }
+
+        let pending = self.pending_stop.clone();
+        let command_tx = command_tx.clone();
+        thread::spawn(move || {
+            thread::sleep(bounce);
+            let released = match pending.lock() {
+                Ok(mut pending) if pending.as_ref().is_some_and(|p| p.id == id) => {
+                    *pending = None;
+                    true
+                }
+                _ => false,
+            };
+            if released {
+                let _ = command_tx.blocking_send(RecordingCommand::StopRecording);
+            }
+        });
+    }
+
+    /// Whether a press of `keycode` is bounce of its release: the pending stop is
+    /// cancelled and the press continues the recording
+    pub fn is_bounce(&self, keycode: u32) -> bool {
+        let Ok(mut pending) = self.pending_stop.lock() else {
+            return false;
+        };
+        if pending.as_ref().is_some_and(|p| p.keycode == keycode) {
+            debug!("Key {} bounced, recording continues", keycode);
+            *pending = None;
+            return true;
+        }
+        false
+    }
+
+    /// Forget the current press (keys were reset). A pending stop is still sent.
+    pub fn reset(&mut self) {
+        self.suppressed = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FN: u32 = 63;
+
+    fn received(command_rx: &mut mpsc::Receiver<RecordingCommand>) -> Vec<String> {
+        std::iter::from_fn(|| command_rx.try_recv().ok())
+            .map(|command| format!("{:?}", command))
+            .collect()
+    }
+
+    #[test]
+    fn test_rapid_starts_are_dropped_with_their_stop() {
+        let (command_tx, mut command_rx) = mpsc::channel(8);
+        let mut debouncer = Debouncer::new();
+        let interval = Duration::from_secs(60);
+
+        assert!(debouncer.start(&[RecordingCommand::StartRecording], interval, &command_tx));
+        debouncer.stop_on_release(FN, Duration::ZERO, &command_tx);
+        assert!(!debouncer.start(&[RecordingCommand::StartRecording], interval, &command_tx));
+        debouncer.stop_on_release(FN, Duration::ZERO, &command_tx);
+
+        assert_eq!(
+            received(&mut command_rx),
+            ["StartRecording", "StopRecording"]
+        );
+    }
+
+    #[test]
+    fn test_bounce_cancels_the_stop() {
+        let (command_tx, mut command_rx) = mpsc::channel(8);
+        let mut debouncer = Debouncer::new();
+        let bounce = Duration::from_millis(20);
+
+        debouncer.start(
+            &[RecordingCommand::StartRecording],
+            Duration::ZERO,
+            &command_tx,
+        );
+        debouncer.stop_on_release(FN, bounce, &command_tx);
+        assert!(!debouncer.is_bounce(FN + 1));
+        assert!(debouncer.is_bounce(FN));
+        thread::sleep(bounce * 3);
+        assert_eq!(received(&mut command_rx), ["StartRecording"]);
+
+        // A real release stops after the bounce time
+        debouncer.stop_on_release(FN, bounce, &command_tx);
+        thread::sleep(bounce * 3);
+        assert!(!debouncer.is_bounce(FN));
+        assert_eq!(received(&mut command_rx), ["StopRecording"]);
+    }
+}
@@ -6,8 +6,109 @@ use serde::{Deserialize, Serialize};
 pub enum KeyCaptureEvent {
     /// Key was pressed
     #[serde(rename = "keyDown")]
-    KeyDown { keycode: u32, label: String },
+    KeyDown {
+        keycode: u32,
+        label: String,
+        /// Keyboard the key was pressed on (for per-keyboard shortcuts)
+        device: Option<CapturedKeyboard>,
+    },
     /// Key was released
     #[serde(rename = "keyUp")]
-    KeyUp { keycode: u32, label: String },
+    KeyUp {
+        keycode: u32,
+        label: String,
+        device: Option<CapturedKeyboard>,
+    },
+}
+
+/// macOS Secure Input was enabled or disabled by an app (password field, terminal with
+/// "Secure Keyboard Entry"). While enabled, shortcuts receive no key events.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct SecureInputChanged {
+    pub enabled: bool,
+    /// Process that enabled Secure Input, if known
+    pub pid: Option<i32>,
+    /// Name of the app that enabled Secure Input, if known
+    pub app_name: Option<String>,
+}
+
+/// The keyboard listener's event tap failed (shortcuts unavailable until it's
+/// restarted) or recovered
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyListenerHealthChanged {
+    pub healthy: bool,
+    /// Why the event tap stopped
+    pub error: Option<String>,
+    /// Delay before the next restart attempt
+    pub retry_in_ms: Option<u64>,
+}
+
+/// Physical keyboard a captured key came from
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedKeyboard {
+    pub vendor_id: u32,
+    pub product_id: u32,
+    pub name: Option<String>,
+    pub built_in: bool,
+}
+
+impl From<dictara_keyboard::KeyboardDevice> for CapturedKeyboard {
+    fn from(device: dictara_keyboard::KeyboardDevice) -> Self {
+        Self {
+            vendor_id: device.vendor_id,
+            product_id: device.product_id,
+            name: device.name,
+            built_in: device.built_in,
+        }
+    }
+}
+
+/// One bucket of the tap callback latency histogram
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyBucket {
+    /// Inclusive upper bound in microseconds, `None` for the overflow bucket
+    pub upper_bound_us: Option<u64>,
+    pub count: u64,
+}
+
+/// Event suppression statistics of the keyboard tap, for "keys feel laggy" reports
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyboardGrabStats {
+    pub events_seen: u64,
+    pub swallowed: u64,
+    pub passed: u64,
+    /// Times macOS disabled the tap because the callback was too slow
+    pub tap_timeouts: u64,
+    pub latency_buckets: Vec<LatencyBucket>,
+    pub mean_latency_us: Option<u64>,
+    pub max_latency_us: u64,
+}
+
+impl From<dictara_keyboard::GrabStats> for KeyboardGrabStats {
+    fn from(stats: dictara_keyboard::GrabStats) -> Self {
+        let bounds = dictara_keyboard::LATENCY_BUCKET_BOUNDS_US
+            .into_iter()
+            .map(Some)
+            .chain(std::iter::once(None));
+        Self {
+            events_seen: stats.events_seen,
+            swallowed: stats.swallowed,
+            passed: stats.passed,
+            tap_timeouts: stats.tap_timeouts,
+            latency_buckets: bounds
+                .zip(stats.latency_buckets)
+                .map(|(upper_bound_us, count)| LatencyBucket {
+                    upper_bound_us,
+                    count,
+                })
+                .collect(),
+            mean_latency_us: stats.mean_latency_us(),
+            max_latency_us: stats.max_latency_us,
+        }
+    }
 }
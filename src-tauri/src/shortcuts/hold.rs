@@ -0,0 +1,133 @@
+//! Tap or hold of the push-to-talk shortcut.
+//!
+//! With a hold threshold (see [`ShortcutsConfig::hold_threshold_ms`]), the key press
+//! completing the shortcut is withheld instead of starting a recording. Held past the
+//! threshold, the recording starts. Released sooner, or pressed together with another
+//! key (Fn+arrow), it wasn't meant for us: the withheld press is posted again, so the
+//! key keeps its normal behavior.
+//!
+//! [`ShortcutsConfig::hold_threshold_ms`]: crate::config::ShortcutsConfig::hold_threshold_ms
+
+use dictara_keyboard::EventType;
+use log::{info, warn};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::recording::RecordingCommand;
+
+/// A withheld shortcut press, waiting for the threshold
+struct Pending {
+    id: u64,
+    press: EventType,
+}
+
+/// Withheld press of the push-to-talk shortcut, shared with the threshold timer
+pub struct HoldGate {
+    pending: Arc<Mutex<Option<Pending>>>,
+    next_id: u64,
+    /// Withheld events were swallowed and must be posted again (not with a
+    /// listen-only tap, which delivers every event anyway)
+    repost: bool,
+}
+
+impl HoldGate {
+    pub fn new(repost: bool) -> Self {
+        Self {
+            pending: Arc::new(Mutex::new(None)),
+            next_id: 0,
+            repost,
+        }
+    }
+
+    /// Withhold `press` and start recording once it has been held for `threshold`,
+    /// unless [`Self::take`] is called first
+    pub fn withhold(
+        &mut self,
+        press: EventType,
+        threshold: Duration,
+        command_tx: &mpsc::Sender<RecordingCommand>,
+    ) {
+        self.next_id += 1;
+        let id = self.next_id;
+        if let Ok(mut pending) = self.pending.lock() {
+            *pending = Some(Pending { id, press });
+        }
+
+        let pending = self.pending.clone();
+        let command_tx = command_tx.clone();
+        thread::spawn(move || {
+            thread::sleep(threshold);
+            let held = match pending.lock() {
+                Ok(mut pending) if pending.as_ref().is_some_and(|p| p.id == id) => {
+                    pending.take().is_some()
+                }
+                _ => false,
+            };
+            if held {
+                let _ = command_tx.blocking_send(RecordingCommand::StartRecording);
+            }
+        });
+    }
+
+    /// Whether a press is withheld, waiting for the threshold
+    pub fn is_pending(&self) -> bool {
+        self.pending
+            .lock()
+            .map(|pending| pending.is_some())
+            .unwrap_or(false)
+    }
+
+    /// End the wait: the withheld press, `None` if there was none or the recording
+    /// already started
+    pub fn take(&self) -> Option<EventType> {
+        self.pending
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.take())
+            .map(|pending| pending.press)
+    }
+
+    /// Deliver a withheld press after all, followed by `event` (swallowed by the caller
+    /// so it can't overtake the press)
+    pub fn release(&self, press: EventType, event: EventType) {
+        if !self.repost {
+            return;
+        }
+        info!("Shortcut tapped, passing {:?} through", press);
+        if let Err(e) = dictara_keyboard::post_events(&[press, event]) {
+            warn!("Failed to pass the tapped shortcut through: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dictara_keyboard::Key;
+
+    #[test]
+    fn test_tap_and_hold() {
+        let (command_tx, mut command_rx) = mpsc::channel(4);
+        let mut gate = HoldGate::new(false);
+        let press = EventType::KeyPress(Key::Function);
+        let threshold = Duration::from_millis(20);
+
+        // Tap: released before the threshold
+        gate.withhold(press.clone(), threshold, &command_tx);
+        assert!(gate.is_pending());
+        assert_eq!(gate.take(), Some(press.clone()));
+        thread::sleep(threshold * 3);
+        assert!(command_rx.try_recv().is_err());
+
+        // Hold: the recording starts, nothing is left to pass through
+        gate.withhold(press, threshold, &command_tx);
+        thread::sleep(threshold * 3);
+        assert!(matches!(
+            command_rx.try_recv(),
+            Ok(RecordingCommand::StartRecording)
+        ));
+        assert_eq!(gate.take(), None);
+    }
+}
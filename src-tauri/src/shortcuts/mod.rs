@@ -1 +1,3 @@
+pub mod debounce;
 pub mod events;
+pub mod hold;
@@ -0,0 +1,117 @@
+//! Orderly shutdown when the app quits.
+//!
+//! Quitting used to just exit: a recording in progress was cut off mid-write and the
+//! event tap could be torn down while holding a key. [`shutdown`] winds the subsystems
+//! down in order before the process exits:
+//!
+//! 1. the keyboard listener lets all key events through, so no new recording starts
+//! 2. the controller ends a running recording, keeping its audio for recovery on the
+//!    next launch (a transcription in progress is abandoned the same way)
+//! 3. meeting mode and live captions stop recording the microphone
+//! 4. model downloads stop, keeping their .partial files to resume next time
+//! 5. the config store is written to disk, with the history kept in it (usage
+//!    statistics, transcription times, learned corrections, per-app languages). The
+//!    store plugin saves any other store on exit.
+//! 6. system settings changed by the app (the Globe key) are put back
+
+use log::{error, info, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::config::Config;
+use crate::crash_guard::SystemGuard;
+use crate::keyboard_listener::KeyListener;
+use crate::live_captions::LiveCaptions;
+use crate::meeting::MeetingRecorder;
+use crate::models::ModelManager;
+use crate::recording::RecordingCommand;
+use crate::setup::RecordingCommandSender;
+
+/// Longest wait for the controller to end the recording (it may be busy transcribing)
+const CONTROLLER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Longest wait for meeting mode and live captions to stop their recording
+const SESSIONS_TIMEOUT: Duration = Duration::from_secs(2);
+
+static SHUT_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Wind down all subsystems before the app exits. Only the first call does anything,
+/// so both the Quit menu item and the exit hook can call it.
+pub fn shutdown(app: &AppHandle) {
+    if SHUT_DOWN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    info!("Shutting down");
+
+    if let Some(listener) = app.try_state::<KeyListener>() {
+        listener.suspend();
+    }
+
+    stop_controller(app);
+    stop_sessions(app);
+
+    if let Some(manager) = app.try_state::<Arc<ModelManager>>() {
+        manager.stop_downloads();
+    }
+
+    if let Some(config) = app.try_state::<Config>() {
+        if let Err(e) = config.flush() {
+            error!("Failed to save config and history on shutdown: {}", e);
+        }
+    }
+
+    if let Some(guard) = app.try_state::<SystemGuard>() {
+        guard.restore_on_exit();
+    }
+
+    info!("Shutdown complete");
+}
+
+fn stop_controller(app: &AppHandle) {
+    let Some(sender) = app.try_state::<RecordingCommandSender>() else {
+        return;
+    };
+
+    let (done_tx, done_rx) = mpsc::channel();
+    if let Err(e) = sender.sender.try_send(RecordingCommand::Shutdown(done_tx)) {
+        warn!("Failed to stop the recording controller: {}", e);
+        return;
+    }
+    if done_rx.recv_timeout(CONTROLLER_TIMEOUT).is_err() {
+        warn!(
+            "Recording controller didn't stop within {:?}, exiting anyway",
+            CONTROLLER_TIMEOUT
+        );
+    }
+}
+
+/// Stop meeting mode and live captions, waiting a little for their microphone
+/// recordings to end
+fn stop_sessions(app: &AppHandle) {
+    let meeting = app.try_state::<MeetingRecorder>();
+    let captions = app.try_state::<LiveCaptions>();
+    if let Some(meeting) = &meeting {
+        meeting.stop();
+    }
+    if let Some(captions) = &captions {
+        captions.stop(app);
+    }
+
+    let deadline = Instant::now() + SESSIONS_TIMEOUT;
+    let running = || {
+        meeting.as_ref().is_some_and(|m| m.is_running())
+            || captions.as_ref().is_some_and(|c| c.is_running())
+    };
+    while running() {
+        if Instant::now() >= deadline {
+            warn!(
+                "Meeting mode or live captions didn't stop within {:?}, exiting anyway",
+                SESSIONS_TIMEOUT
+            );
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
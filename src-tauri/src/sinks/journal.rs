@@ -0,0 +1,130 @@
+//! Daily Markdown journal, e.g. in an Obsidian vault.
+//!
+//! Each dictation is appended as one entry to `<folder>/YYYY-MM-DD.md` (the day it
+//! was pasted), formatted with the user's entry template. The folder and the file
+//! are created when missing.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::{JournalSinkConfig, OutputSinksConfig};
+use crate::templates;
+
+use super::{Dictation, OutputSink};
+
+/// Appends dictations to the daily journal file
+pub struct JournalSink;
+
+impl OutputSink for JournalSink {
+    fn name(&self) -> &'static str {
+        "journal"
+    }
+
+    fn enabled(&self, config: &OutputSinksConfig) -> bool {
+        config.journal.enabled
+    }
+
+    fn write(&mut self, dictation: &Dictation, config: &OutputSinksConfig) -> Result<(), String> {
+        let folder = config
+            .journal
+            .folder
+            .as_deref()
+            .filter(|folder| !folder.trim().is_empty())
+            .ok_or("No journal folder chosen")?;
+        append_entry(Path::new(folder), &config.journal, dictation)
+    }
+}
+
+/// Journal file of the day `dictation` was pasted
+fn journal_path(folder: &Path, dictation: &Dictation) -> PathBuf {
+    folder.join(format!("{}.md", dictation.timestamp.format("%Y-%m-%d")))
+}
+
+/// The entry for `dictation`, ending with a newline
+fn render_entry(template: &str, dictation: &Dictation) -> String {
+    let mut entry = templates::fill_placeholders(template, |name| match name {
+        "text" => Some(dictation.text.trim().to_string()),
+        "date" => Some(dictation.timestamp.format("%Y-%m-%d").to_string()),
+        "time" => Some(dictation.timestamp.format("%H:%M").to_string()),
+        "app" => Some(
+            dictation
+                .app_name
+                .clone()
+                .or_else(|| dictation.app_id.clone())
+                .unwrap_or_else(|| "Unknown app".to_string()),
+        ),
+        "language" => Some(dictation.language.clone().unwrap_or_default()),
+        _ => None,
+    });
+    if !entry.ends_with('\n') {
+        entry.push('\n');
+    }
+    entry
+}
+
+fn append_entry(
+    folder: &Path,
+    config: &JournalSinkConfig,
+    dictation: &Dictation,
+) -> Result<(), String> {
+    fs::create_dir_all(folder)
+        .map_err(|e| format!("Failed to create {}: {}", folder.display(), e))?;
+
+    let path = journal_path(folder, dictation);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    file.write_all(render_entry(&config.entry_template, dictation).as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DEFAULT_JOURNAL_ENTRY_TEMPLATE;
+    use chrono::{Local, TimeZone};
+
+    fn dictation(text: &str, hour: u32) -> Dictation {
+        Dictation {
+            text: text.to_string(),
+            language: Some("en".to_string()),
+            app_id: Some("com.apple.Notes".to_string()),
+            app_name: Some("Notes".to_string()),
+            timestamp: Local.with_ymd_and_hms(2025, 3, 7, hour, 5, 0).unwrap(),
+            duration_ms: 4200,
+        }
+    }
+
+    #[test]
+    fn test_render_entry() {
+        assert_eq!(
+            render_entry(DEFAULT_JOURNAL_ENTRY_TEMPLATE, &dictation(" Buy milk ", 9)),
+            "- **09:05** (Notes): Buy milk\n"
+        );
+        assert_eq!(
+            render_entry("{{{language}}} {date} {unknown}\n", &dictation("", 9)),
+            "{en} 2025-03-07 {unknown}\n"
+        );
+    }
+
+    #[test]
+    fn test_append_entry() {
+        let folder = std::env::temp_dir()
+            .join(format!("dictara-journal-test-{}", std::process::id()))
+            .join("vault");
+        let config = JournalSinkConfig::default();
+
+        append_entry(&folder, &config, &dictation("First", 9)).unwrap();
+        append_entry(&folder, &config, &dictation("Second", 10)).unwrap();
+
+        let contents = fs::read_to_string(folder.join("2025-03-07.md")).unwrap();
+        assert_eq!(
+            contents,
+            "- **09:05** (Notes): First\n- **10:05** (Notes): Second\n"
+        );
+        fs::remove_dir_all(folder.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,115 @@
+//! Output sinks: where a dictation goes besides being pasted.
+//!
+//! Pasting into the frontmost app stays the controller's job. Every sink enabled in
+//! `outputSinksConfig` also gets each pasted [`Dictation`]. Every sink has its own
+//! thread ("output-sink-<name>") and queue, taking the dictations in order, so a slow
+//! disk or a webhook retrying an unreachable server holds up neither the next
+//! recording nor the other sinks. A failing sink is logged and doesn't stop the others.
+
+mod journal;
+mod webhook;
+
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+
+use chrono::{DateTime, Local};
+use log::{error, warn};
+use tauri::{AppHandle, Manager};
+
+use crate::config::{self, ConfigKey, ConfigStore, OutputSinksConfig};
+
+pub use journal::JournalSink;
+pub use webhook::{save_webhook_secret, WebhookSink};
+
+/// A pasted dictation, as handed to the sinks
+#[derive(Debug, Clone)]
+pub struct Dictation {
+    pub text: String,
+    /// Detected or pinned language, ISO-639-1
+    pub language: Option<String>,
+    /// Bundle identifier of the app the text went to
+    pub app_id: Option<String>,
+    /// Name of that app, e.g. "Notes"
+    pub app_name: Option<String>,
+    /// When the dictation was pasted
+    pub timestamp: DateTime<Local>,
+    /// Length of the recording
+    pub duration_ms: u64,
+}
+
+/// A destination for dictations
+pub trait OutputSink: Send {
+    /// Name in the logs
+    fn name(&self) -> &'static str;
+
+    /// Whether the sink is turned on in `config`
+    fn enabled(&self, config: &OutputSinksConfig) -> bool;
+
+    /// Deliver `dictation`
+    fn write(&mut self, dictation: &Dictation, config: &OutputSinksConfig) -> Result<(), String>;
+}
+
+/// Hands dictations to the enabled sinks in the background
+pub struct OutputSinks {
+    /// Queue of each running sink
+    senders: Vec<Sender<Dictation>>,
+}
+
+impl OutputSinks {
+    /// Start a thread for every built-in sink
+    pub fn start(app_handle: &AppHandle) -> Self {
+        let config = app_handle.state::<config::Config>().inner().clone();
+        Self::with_sinks(
+            config,
+            vec![
+                Box::new(JournalSink),
+                Box::new(WebhookSink::new(app_handle.clone())),
+            ],
+        )
+    }
+
+    fn with_sinks(config: config::Config, sinks: Vec<Box<dyn OutputSink>>) -> Self {
+        let senders = sinks
+            .into_iter()
+            .filter_map(|sink| spawn_sink(config.clone(), sink))
+            .collect();
+        Self { senders }
+    }
+
+    /// Queue `dictation` for the enabled sinks
+    pub fn deliver(&self, dictation: Dictation) {
+        for sender in &self.senders {
+            if sender.send(dictation.clone()).is_err() {
+                error!("Output sink thread stopped, dictation not delivered");
+            }
+        }
+    }
+}
+
+/// Run `sink` on its own thread, `None` when the thread can't be started
+fn spawn_sink(config: config::Config, mut sink: Box<dyn OutputSink>) -> Option<Sender<Dictation>> {
+    let (sender, receiver) = mpsc::channel::<Dictation>();
+    let name = sink.name();
+    let spawned = thread::Builder::new()
+        .name(format!("output-sink-{}", name))
+        .spawn(move || {
+            for dictation in receiver {
+                // Read for every dictation: changes apply from the next one
+                let sinks_config = config.get(&ConfigKey::OUTPUT_SINKS).unwrap_or_default();
+                if !sink.enabled(&sinks_config) {
+                    continue;
+                }
+                if let Err(e) = sink.write(&dictation, &sinks_config) {
+                    warn!("Output sink {} failed: {}", sink.name(), e);
+                }
+            }
+        });
+
+    match spawned {
+        Ok(_) => Some(sender),
+        Err(e) => {
+            error!("Failed to start the output sink {}: {}", name, e);
+            None
+        }
+    }
+}
@@ -0,0 +1,290 @@
+//! Webhook: each dictation POSTed as JSON to a user-configured URL, for note apps,
+//! task managers, n8n or Zapier.
+//!
+//! With a signing secret stored in the keychain, the request carries
+//! `X-Dictara-Signature: sha256=<hex HMAC-SHA256 of the body>` so the receiver can
+//! check it comes from this Mac. Connection failures, timeouts, 429 and 5xx responses
+//! are retried after each of [`RETRY_DELAYS`]; when the last attempt fails too, the
+//! user is notified (unless turned off).
+
+use std::thread;
+use std::time::Duration;
+
+use dictara_core::http::{HttpBody, HttpRequest, HttpTransport, ReqwestTransport};
+use hmac::{Hmac, Mac};
+use log::{error, warn};
+use secrecy::{ExposeSecret, SecretString};
+use sha2::Sha256;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::config::{self, OutputSinksConfig, WebhookCredentials};
+use crate::keychain::{self, ProviderAccount};
+use crate::network;
+
+use super::{Dictation, OutputSink};
+
+/// Header with the signature of the body
+pub const SIGNATURE_HEADER: &str = "X-Dictara-Signature";
+
+/// Timeout of one attempt
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Wait before each retry
+const RETRY_DELAYS: [Duration; 3] = [
+    Duration::from_secs(1),
+    Duration::from_secs(5),
+    Duration::from_secs(15),
+];
+
+/// POSTs dictations to the webhook URL
+pub struct WebhookSink {
+    app_handle: AppHandle,
+}
+
+impl WebhookSink {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self { app_handle }
+    }
+
+    fn notify_failure(&self, message: &str) {
+        let result = self
+            .app_handle
+            .notification()
+            .builder()
+            .title("Webhook failed")
+            .body(format!("A dictation couldn't be delivered: {}", message))
+            .show();
+        if let Err(e) = result {
+            error!("Failed to show webhook notification: {}", e);
+        }
+    }
+}
+
+impl OutputSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn enabled(&self, config: &OutputSinksConfig) -> bool {
+        config.webhook.enabled
+    }
+
+    fn write(&mut self, dictation: &Dictation, config: &OutputSinksConfig) -> Result<(), String> {
+        let url = config.webhook.url.as_deref().ok_or("No webhook URL set")?;
+        let http_settings =
+            network::http_settings(self.app_handle.state::<config::Config>().inner());
+        let transport = ReqwestTransport::new(http_settings);
+
+        let result = send(
+            &transport,
+            url,
+            dictation,
+            load_secret().as_ref(),
+            &RETRY_DELAYS,
+        );
+        if let Err(e) = &result {
+            if config.webhook.notify_on_failure {
+                self.notify_failure(e);
+            }
+        }
+        result
+    }
+}
+
+/// Save (or with `None`, delete) the signing secret in the keychain
+pub fn save_webhook_secret(secret: Option<String>) -> Result<(), String> {
+    match secret {
+        Some(secret) => {
+            keychain::save_provider_config(ProviderAccount::Webhook, &WebhookCredentials { secret })
+        }
+        None => keychain::delete_provider_config(ProviderAccount::Webhook),
+    }
+    .map_err(|e| format!("Failed to save the webhook secret: {}", e))
+}
+
+/// Signing secret, `None` when none is stored or the keychain can't be read
+fn load_secret() -> Option<SecretString> {
+    match keychain::load_provider_config::<WebhookCredentials>(ProviderAccount::Webhook) {
+        Ok(credentials) => credentials.map(|credentials| SecretString::from(credentials.secret)),
+        Err(e) => {
+            warn!("Failed to load the webhook secret: {}", e);
+            None
+        }
+    }
+}
+
+/// JSON body of the request
+fn payload(dictation: &Dictation) -> serde_json::Value {
+    serde_json::json!({
+        "text": dictation.text,
+        "language": dictation.language,
+        "durationMs": dictation.duration_ms,
+        "app": dictation.app_name,
+        "appId": dictation.app_id,
+        "timestamp": dictation.timestamp.to_rfc3339(),
+    })
+}
+
+/// Hex HMAC-SHA256 of `message`
+fn sign(secret: &[u8], message: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC takes keys of any length");
+    mac.update(message);
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+/// Whether a response with `status` is worth retrying
+fn is_retryable(status: u16) -> bool {
+    status == 429 || status >= 500
+}
+
+/// POST `dictation` to `url`, retrying after each of `retry_delays`
+fn send(
+    transport: &dyn HttpTransport,
+    url: &str,
+    dictation: &Dictation,
+    secret: Option<&SecretString>,
+    retry_delays: &[Duration],
+) -> Result<(), String> {
+    let body = payload(dictation);
+    // Signed as sent: the transport serializes the JSON the same way
+    let signature = secret.map(|secret| {
+        sign(
+            secret.expose_secret().as_bytes(),
+            body.to_string().as_bytes(),
+        )
+    });
+    let mut request = HttpRequest::post(url, HttpBody::Json(body), REQUEST_TIMEOUT);
+    if let Some(signature) = signature {
+        request = request.header(
+            SIGNATURE_HEADER,
+            SecretString::from(format!("sha256={}", signature)),
+        );
+    }
+
+    let mut delays = retry_delays.iter();
+    loop {
+        let error = match transport.send(request.clone()) {
+            Ok(response) if response.is_success() => return Ok(()),
+            Ok(response) if !is_retryable(response.status) => {
+                return Err(format!("Webhook returned HTTP {}", response.status))
+            }
+            Ok(response) => format!("HTTP {}", response.status),
+            Err(e) => e.to_string(),
+        };
+
+        match delays.next() {
+            Some(delay) => {
+                warn!("Webhook failed ({}), retrying in {:?}", error, delay);
+                thread::sleep(*delay);
+            }
+            None => {
+                return Err(format!(
+                    "Webhook failed after {} attempts: {}",
+                    retry_delays.len() + 1,
+                    error
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, TimeZone};
+    use dictara_core::http::{MockTransport, TransportError};
+
+    const NO_DELAYS: [Duration; 2] = [Duration::ZERO, Duration::ZERO];
+
+    fn dictation() -> Dictation {
+        Dictation {
+            text: "Buy milk".to_string(),
+            language: Some("en".to_string()),
+            app_id: Some("com.apple.Notes".to_string()),
+            app_name: Some("Notes".to_string()),
+            timestamp: Local.with_ymd_and_hms(2025, 3, 7, 9, 5, 0).unwrap(),
+            duration_ms: 4200,
+        }
+    }
+
+    #[test]
+    fn test_sign() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_send_signs_the_body() {
+        let transport = MockTransport::new().respond(200, "");
+        let secret = SecretString::from("secret".to_string());
+        send(
+            &transport,
+            "https://example.com/hook",
+            &dictation(),
+            Some(&secret),
+            &NO_DELAYS,
+        )
+        .unwrap();
+
+        let request = &transport.requests()[0];
+        let HttpBody::Json(body) = &request.body else {
+            panic!("Expected a JSON body");
+        };
+        assert_eq!(body["text"], "Buy milk");
+        assert_eq!(body["app"], "Notes");
+        assert_eq!(body["durationMs"], 4200);
+        let expected = format!("sha256={}", sign(b"secret", body.to_string().as_bytes()));
+        assert_eq!(
+            request.header_value(SIGNATURE_HEADER),
+            Some(expected.as_str())
+        );
+    }
+
+    #[test]
+    fn test_send_retries() {
+        let transport = MockTransport::new()
+            .respond(503, "")
+            .respond_with(Err(TransportError::Timeout(REQUEST_TIMEOUT)))
+            .respond(200, "");
+        send(
+            &transport,
+            "https://example.com",
+            &dictation(),
+            None,
+            &NO_DELAYS,
+        )
+        .unwrap();
+        assert_eq!(transport.requests().len(), 3);
+        assert_eq!(transport.requests()[0].header_value(SIGNATURE_HEADER), None);
+
+        // Gives up after the last retry
+        let transport = MockTransport::new()
+            .respond(500, "")
+            .respond(500, "")
+            .respond(500, "");
+        assert!(send(
+            &transport,
+            "https://example.com",
+            &dictation(),
+            None,
+            &NO_DELAYS
+        )
+        .is_err());
+
+        // Client errors aren't retried
+        let transport = MockTransport::new().respond(404, "");
+        assert!(send(
+            &transport,
+            "https://example.com",
+            &dictation(),
+            None,
+            &NO_DELAYS
+        )
+        .is_err());
+        assert_eq!(transport.requests().len(), 1);
+    }
+}
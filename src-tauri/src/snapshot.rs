@@ -0,0 +1,75 @@
+//! Snapshot of the app state for windows that open mid-session.
+//!
+//! Windows only learn about state through change events, so a window created
+//! while recording or downloading a model would start out of date. The snapshot
+//! is emitted when a window finishes loading and can be queried at any time.
+
+use crate::config::{self, ConfigKey, ConfigStore, Provider};
+use crate::keyboard_listener::KeyListener;
+use crate::models::{MigrationStatus, ModelInfo, ModelLoader, ModelManager};
+use crate::recording::{RawMode, RecordingState, RecordingStateManager};
+use log::error;
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+/// Current app state, assembled from the managers
+#[derive(Debug, Clone, Serialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct StateSnapshot {
+    /// `None` until the recording controller is set up
+    pub recording_state: Option<RecordingState>,
+    pub raw_mode: bool,
+    pub active_provider: Option<Provider>,
+    /// Local models with download/load status
+    pub models: Vec<ModelInfo>,
+    pub migration: MigrationStatus,
+    /// False while the keyboard listener is down (or not started yet)
+    pub listener_healthy: bool,
+}
+
+impl StateSnapshot {
+    /// Read the current state. Managers that aren't set up yet report defaults.
+    pub fn collect(app: &AppHandle) -> Self {
+        let recording_state = app
+            .try_state::<Arc<RecordingStateManager>>()
+            .map(|state_manager| state_manager.current());
+        let raw_mode = app
+            .try_state::<Arc<RawMode>>()
+            .is_some_and(|raw_mode| raw_mode.is_enabled());
+        let active_provider = app
+            .try_state::<config::Config>()
+            .and_then(|store| store.get(&ConfigKey::APP))
+            .and_then(|config| config.active_provider);
+
+        let model_manager = app.try_state::<Arc<ModelManager>>();
+        let models = match (&model_manager, app.try_state::<Arc<ModelLoader>>()) {
+            (Some(manager), Some(loader)) => manager.get_all_models(&loader),
+            _ => Vec::new(),
+        };
+        let migration = model_manager
+            .map(|manager| manager.migration_status())
+            .unwrap_or_default();
+
+        let listener_healthy = app
+            .try_state::<KeyListener>()
+            .is_some_and(|listener| listener.is_healthy());
+
+        Self {
+            recording_state,
+            raw_mode,
+            active_provider,
+            models,
+            migration,
+            listener_healthy,
+        }
+    }
+}
+
+/// Send the current snapshot to all windows
+pub fn emit_snapshot(app: &AppHandle) {
+    if let Err(e) = StateSnapshot::collect(app).emit(app) {
+        error!("Failed to emit StateSnapshot: {}", e);
+    }
+}
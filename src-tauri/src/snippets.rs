@@ -0,0 +1,138 @@
+//! Voice snippets.
+//!
+//! A snippet is a stored text block (an address, a signature, ...) that replaces its
+//! spoken trigger phrase anywhere in a dictation: "ship it to insert my address please"
+//! pastes the address in the middle of the sentence. Unlike templates, which wrap the
+//! whole dictation and are selected by a keyword at its start, snippets are expanded
+//! inline and a dictation can contain any number of them.
+
+use dictara_core::words::{normalized, word_ranges};
+use log::info;
+
+use crate::config::{ConfigKey, ConfigStore, SnippetsConfig, VoiceSnippet};
+
+/// Load the snippets, empty if none have been created yet
+pub fn load_snippets(store: &impl ConfigStore) -> SnippetsConfig {
+    store.get(&ConfigKey::SNIPPETS).unwrap_or_default()
+}
+
+/// A word of the dictation (see [`dictara_core::words`]) and its lowercase letters
+/// and digits
+struct Word {
+    start: usize,
+    end: usize,
+    normalized: String,
+}
+
+/// Words of `text`, skipping punctuation-only tokens
+fn words(text: &str) -> Vec<Word> {
+    word_ranges(text)
+        .into_iter()
+        .map(|range| Word {
+            start: range.start,
+            end: range.end,
+            normalized: normalized(&text[range]),
+        })
+        .collect()
+}
+
+/// Replace every spoken trigger phrase in `text` with its snippet
+///
+/// Matching is on whole words, ignoring case and the punctuation the transcriber adds
+/// ("Insert, my address." matches "insert my address"); punctuation around the phrase
+/// is kept. Longer triggers win over triggers they start with.
+pub fn expand_snippets(snippets: &[VoiceSnippet], text: &str) -> String {
+    let mut triggers: Vec<(Vec<String>, &VoiceSnippet)> = snippets
+        .iter()
+        .map(|snippet| {
+            let trigger: Vec<String> = words(&snippet.trigger)
+                .into_iter()
+                .map(|word| word.normalized)
+                .collect();
+            (trigger, snippet)
+        })
+        .filter(|(trigger, _)| !trigger.is_empty())
+        .collect();
+    if triggers.is_empty() {
+        return text.to_string();
+    }
+    triggers.sort_by_key(|(trigger, _)| std::cmp::Reverse(trigger.len()));
+
+    let words = words(text);
+    let mut output = String::with_capacity(text.len());
+    let mut copied_to = 0;
+    let mut i = 0;
+    while i < words.len() {
+        let matched = triggers.iter().find(|(trigger, _)| {
+            words.len() - i >= trigger.len()
+                && trigger
+                    .iter()
+                    .zip(&words[i..])
+                    .all(|(expected, word)| *expected == word.normalized)
+        });
+        match matched {
+            Some((trigger, snippet)) => {
+                info!("Expanding voice snippet '{}'", snippet.trigger);
+                output.push_str(&text[copied_to..words[i].start]);
+                output.push_str(&snippet.text);
+                copied_to = words[i + trigger.len() - 1].end;
+                i += trigger.len();
+            }
+            None => i += 1,
+        }
+    }
+    output.push_str(&text[copied_to..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet(trigger: &str, text: &str) -> VoiceSnippet {
+        VoiceSnippet {
+            id: trigger.to_string(),
+            trigger: trigger.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_expand_snippets_inline() {
+        let snippets = [snippet("insert my address", "1 Main St\nSpringfield")];
+        assert_eq!(
+            expand_snippets(&snippets, "Please ship it to insert my address. Thanks!"),
+            "Please ship it to 1 Main St\nSpringfield. Thanks!"
+        );
+        // Case and punctuation added by the transcriber are ignored
+        assert_eq!(
+            expand_snippets(&snippets, "Insert, my address"),
+            "1 Main St\nSpringfield"
+        );
+        // Whole words only
+        assert_eq!(
+            expand_snippets(&snippets, "I inserted my address"),
+            "I inserted my address"
+        );
+        assert_eq!(
+            expand_snippets(&[], "insert my address"),
+            "insert my address"
+        );
+    }
+
+    #[test]
+    fn test_longer_trigger_wins() {
+        let snippets = [
+            snippet("sign off", "Best,\nAlex"),
+            snippet("sign off formally", "Kind regards,\nAlex Smith"),
+        ];
+        assert_eq!(
+            expand_snippets(&snippets, "See you then. Sign off formally."),
+            "See you then. Kind regards,\nAlex Smith."
+        );
+        assert_eq!(
+            expand_snippets(&snippets, "sign off and sign off"),
+            "Best,\nAlex and Best,\nAlex"
+        );
+    }
+}
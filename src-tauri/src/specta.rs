@@ -1,4 +1,4 @@
-use crate::{models, recording, shortcuts, with_commands};
+use crate::{with_commands, with_events};
 
 /// Setup Specta for type-safe TypeScript bindings and event emission
 ///
@@ -11,14 +11,7 @@ pub fn setup(app: &tauri::AppHandle) {
         // Commands with specta support (type-safe bindings will be generated)
         .commands(with_commands!(tauri_specta::collect_commands))
         // Events with specta support (type-safe bindings will be generated)
-        .events(tauri_specta::collect_events![
-            recording::events::RecordingStateChanged,
-            // Model events (discriminated unions for state machine patterns)
-            models::events::ModelDownloadStateChanged,
-            models::events::ModelLoadingStateChanged,
-            // Shortcuts events
-            shortcuts::events::KeyCaptureEvent,
-        ]);
+        .events(with_events!(tauri_specta::collect_events));
 
     // Export TypeScript bindings in debug mode
     #[cfg(debug_assertions)]
@@ -0,0 +1,175 @@
+//! Startup phase profiling and deferred initialization.
+//!
+//! `setup_app` marks the end of each phase, so the log and `get_startup_report`
+//! show where launch time goes. Work the tray doesn't need (scanning the models for
+//! the engine menu and the configuration check, preloading the local model, the
+//! first update check) is deferred: it runs on a background thread once setup
+//! returned, and is timed too.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{error, info};
+use serde::Serialize;
+
+/// A timed part of the startup
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupPhase {
+    pub name: String,
+    pub duration_ms: u64,
+    /// Ran after setup, in the background
+    pub deferred: bool,
+}
+
+/// Timings of the last launch
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupReport {
+    pub phases: Vec<StartupPhase>,
+    /// Time until setup returned and the tray was usable
+    pub setup_ms: Option<u64>,
+    /// Time until the deferred tasks finished, `None` while they run
+    pub deferred_ms: Option<u64>,
+}
+
+type DeferredTask = (&'static str, Box<dyn FnOnce() + Send>);
+
+/// Records the startup phases, holds the deferred tasks until setup returns
+pub struct StartupProfiler {
+    started_at: Instant,
+    state: Mutex<ProfilerState>,
+}
+
+struct ProfilerState {
+    /// End of the last phase
+    last_mark: Instant,
+    phases: Vec<StartupPhase>,
+    deferred: Vec<DeferredTask>,
+    setup: Option<Duration>,
+    deferred_done: Option<Duration>,
+}
+
+impl StartupProfiler {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            started_at: now,
+            state: Mutex::new(ProfilerState {
+                last_mark: now,
+                phases: Vec::new(),
+                deferred: Vec::new(),
+                setup: None,
+                deferred_done: None,
+            }),
+        }
+    }
+
+    /// End of the setup phase `name`, timed since the previous one
+    pub fn phase(&self, name: &str) {
+        let mut state = self.lock();
+        let now = Instant::now();
+        let duration = now - state.last_mark;
+        state.last_mark = now;
+        state.phases.push(StartupPhase {
+            name: name.to_string(),
+            duration_ms: duration.as_millis() as u64,
+            deferred: false,
+        });
+    }
+
+    /// Run `task` after setup, in the order deferred
+    pub fn defer(&self, name: &'static str, task: impl FnOnce() + Send + 'static) {
+        self.lock().deferred.push((name, Box::new(task)));
+    }
+
+    /// Setup returned: log its phases and run the deferred tasks on a background thread
+    pub fn finish_setup(self: &Arc<Self>) {
+        let tasks = {
+            let mut state = self.lock();
+            state.setup = Some(self.started_at.elapsed());
+            std::mem::take(&mut state.deferred)
+        };
+        info!("Startup setup finished in {:?}", self.started_at.elapsed());
+        self.log_phases(false);
+
+        let profiler = self.clone();
+        let spawned = thread::Builder::new()
+            .name("startup-deferred".to_string())
+            .spawn(move || {
+                for (name, task) in tasks {
+                    let started_at = Instant::now();
+                    task();
+                    profiler.record_deferred(name, started_at.elapsed());
+                }
+                profiler.lock().deferred_done = Some(profiler.started_at.elapsed());
+                info!(
+                    "Deferred startup tasks finished after {:?}",
+                    profiler.started_at.elapsed()
+                );
+                profiler.log_phases(true);
+            });
+        if let Err(e) = spawned {
+            error!("Failed to run the deferred startup tasks: {}", e);
+        }
+    }
+
+    pub fn report(&self) -> StartupReport {
+        let state = self.lock();
+        StartupReport {
+            phases: state.phases.clone(),
+            setup_ms: state.setup.map(|d| d.as_millis() as u64),
+            deferred_ms: state.deferred_done.map(|d| d.as_millis() as u64),
+        }
+    }
+
+    fn record_deferred(&self, name: &str, duration: Duration) {
+        self.lock().phases.push(StartupPhase {
+            name: name.to_string(),
+            duration_ms: duration.as_millis() as u64,
+            deferred: true,
+        });
+    }
+
+    fn log_phases(&self, deferred: bool) {
+        let state = self.lock();
+        for phase in state.phases.iter().filter(|p| p.deferred == deferred) {
+            info!("  startup phase {}: {}ms", phase.name, phase.duration_ms);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ProfilerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for StartupProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_deferred_tasks_run_after_setup() {
+        let profiler = Arc::new(StartupProfiler::new());
+        let (tx, rx) = mpsc::channel();
+        profiler.phase("config");
+        profiler.defer("engine menu", move || tx.send(()).unwrap());
+        profiler.phase("menu and tray");
+
+        let report = profiler.report();
+        assert_eq!(report.phases.len(), 2);
+        assert_eq!(report.setup_ms, None);
+        assert!(rx.try_recv().is_err());
+
+        profiler.finish_setup();
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(profiler.report().setup_ms.is_some());
+    }
+}
@@ -31,12 +31,15 @@ pub fn get_or_create_device_id(config: &Config) -> String {
     let device_id = Uuid::new_v4().to_string();
     info!("Generated new device ID for telemetry: {}", device_id);
 
-    // Store it
-    let telemetry_config = TelemetryConfig {
-        device_id: device_id.clone(),
-        telemetry_enabled: true, // Enable by default
-        last_session_start: None,
-    };
+    // Store it, with the settings locked by IT (a missing entry has nothing to merge them into)
+    let telemetry_config = config.with_managed(
+        &ConfigKey::TELEMETRY,
+        TelemetryConfig {
+            device_id: device_id.clone(),
+            telemetry_enabled: true, // Enable by default
+            last_session_start: None,
+        },
+    );
 
     if let Err(e) = config.set(&ConfigKey::TELEMETRY, telemetry_config) {
         warn!("Failed to save telemetry config: {}", e);
@@ -48,7 +51,7 @@ pub fn get_or_create_device_id(config: &Config) -> String {
 /// Initialize Sentry for error tracking and telemetry
 ///
 /// Reads SENTRY_DSN from environment variables.
-/// If not set, or in debug builds (unless ENABLE_SENTRY_IN_DEBUG=true), Sentry will be disabled.
+/// If not set, telemetry is turned off (by the user or managed config), or in debug builds (unless ENABLE_SENTRY_IN_DEBUG=true), Sentry will be disabled.
 pub fn init_sentry(device_id: &str, config: &Config) -> Option<sentry::ClientInitGuard> {
     // Disable Sentry in debug builds unless explicitly enabled for testing
     #[cfg(debug_assertions)]
@@ -61,6 +64,15 @@ pub fn init_sentry(device_id: &str, config: &Config) -> Option<sentry::ClientIni
         info!("⚠️  Sentry ENABLED in debug build for testing (your dev sessions will count toward DAU!)");
     }
 
+    // The effective setting, which IT may have turned off
+    let telemetry_enabled = config
+        .get(&ConfigKey::TELEMETRY)
+        .is_some_and(|telemetry| telemetry.telemetry_enabled);
+    if !telemetry_enabled {
+        info!("Telemetry disabled in settings");
+        return None;
+    }
+
     // Get DSN from compile-time environment variable (baked into binary at build time)
     // For local dev: .env is loaded before compilation via build script
     // For production: .env is loaded before tauri build via package.json script
@@ -0,0 +1,223 @@
+//! Dictation templates.
+//!
+//! A template is a user-defined snippet (e.g. a standup update) whose `{summary}`
+//! slot is filled with the dictated text. A template is selected either by
+//! starting the dictation with its spoken keyword ("standup update, yesterday I...")
+//! or by making it the active template in the tray menu. The rendered text is
+//! what gets pasted.
+
+use chrono::{DateTime, Local};
+use log::{error, info};
+use tauri::{AppHandle, Manager};
+
+use crate::config::{self, ConfigKey, ConfigStore, DictationTemplate, TemplatesConfig};
+use crate::ui::menu::TemplatesMenu;
+
+/// Placeholder replaced with the dictated text
+const SUMMARY_PLACEHOLDER: &str = "summary";
+
+/// Load the templates, empty if none have been created yet
+pub fn load_templates(store: &impl ConfigStore) -> TemplatesConfig {
+    store.get(&ConfigKey::TEMPLATES).unwrap_or_default()
+}
+
+/// Save the templates and refresh the tray menu
+pub fn save_templates(app: &AppHandle, templates: TemplatesConfig) -> Result<(), String> {
+    app.state::<config::Config>()
+        .set(&ConfigKey::TEMPLATES, templates.clone())?;
+
+    if let Some(templates_menu) = app.try_state::<TemplatesMenu>() {
+        if let Err(e) = templates_menu.refresh(app, &templates) {
+            error!("Failed to refresh template menu: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Make `template_id` the template applied to every dictation, `None` to turn it off
+pub fn set_active_template(app: &AppHandle, template_id: Option<String>) -> Result<(), String> {
+    let mut templates = load_templates(app.state::<config::Config>().inner());
+
+    if let Some(id) = &template_id {
+        if !templates.templates.iter().any(|t| &t.id == id) {
+            return Err(format!("Template not found: {}", id));
+        }
+    }
+
+    info!("Active template set to {:?}", template_id);
+    templates.active_template_id = template_id;
+    save_templates(app, templates)
+}
+
+/// Select the template for a transcription
+///
+/// A spoken keyword at the start of the text wins over the active template.
+/// Returns the template and the dictated content (without the keyword), or
+/// `None` if no template applies.
+pub fn select_template(
+    store: &impl ConfigStore,
+    text: &str,
+) -> Option<(DictationTemplate, String)> {
+    let config = load_templates(store);
+
+    if let Some((template, content)) = match_spoken_keyword(&config.templates, text) {
+        info!("Using template '{}' (spoken keyword)", template.name);
+        return Some((template.clone(), content.to_string()));
+    }
+
+    let id = config.active_template_id.as_ref()?;
+    let template = config.templates.iter().find(|t| &t.id == id)?;
+    info!("Using template '{}' (active template)", template.name);
+    Some((template.clone(), text.trim().to_string()))
+}
+
+/// Render a template with the dictated content in its `{summary}` slot
+pub fn render_template(template: &DictationTemplate, content: &str) -> String {
+    render(&template.body, content, Local::now())
+}
+
+/// Find the template whose keyword starts `text`
+///
+/// Matching ignores case and the punctuation the transcriber adds after the
+/// keyword ("Standup update: ..."). Returns the template and the rest of the text.
+fn match_spoken_keyword<'a>(
+    templates: &'a [DictationTemplate],
+    text: &'a str,
+) -> Option<(&'a DictationTemplate, &'a str)> {
+    let text = text.trim_start();
+
+    templates.iter().find_map(|template| {
+        let keyword = template.keyword.as_deref()?.trim();
+        if keyword.is_empty() {
+            return None;
+        }
+
+        let rest = strip_prefix_ignore_case(text, keyword)?;
+
+        // The keyword must be a whole word ("standup updates" doesn't match "standup update")
+        if rest.chars().next().is_some_and(char::is_alphanumeric) {
+            return None;
+        }
+
+        let rest = rest.trim_start_matches(|c: char| c.is_whitespace() || ",.:;-!?".contains(c));
+        Some((template, rest))
+    })
+}
+
+/// `text` without `prefix`, comparing case-insensitively character by character
+///
+/// Upper and lower case can differ in UTF-8 length ("STRAẞE" / "straße"), so the
+/// prefix is matched by characters rather than by its byte length.
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let mut chars = text.chars();
+    for expected in prefix.chars() {
+        let actual = chars.next()?;
+        if !actual.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.as_str())
+}
+
+/// Fill the placeholders of a template body
+///
+/// Supported placeholders: `{summary}`, `{date}` (YYYY-MM-DD) and `{time}` (HH:MM).
+fn render(body: &str, summary: &str, now: DateTime<Local>) -> String {
+    fill_placeholders(body, |name| match name {
+        SUMMARY_PLACEHOLDER => Some(summary.to_string()),
+        "date" => Some(now.format("%Y-%m-%d").to_string()),
+        "time" => Some(now.format("%H:%M").to_string()),
+        _ => None,
+    })
+}
+
+/// Replace every `{name}` in `body` with `value(name)`
+///
+/// Placeholders `value` returns `None` for are kept as-is; `{{` and `}}` produce
+/// literal braces.
+pub fn fill_placeholders(body: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut output = String::with_capacity(body.len());
+    let mut rest = body;
+
+    while let Some(start) = rest.find(['{', '}']) {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            output.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+
+        let placeholder = rest
+            .strip_prefix('{')
+            .and_then(|inner| inner.find('}').map(|end| &inner[..end]));
+        match placeholder {
+            Some(name) => {
+                match value(name.trim()) {
+                    Some(value) => output.push_str(&value),
+                    None => output.push_str(&rest[..name.len() + 2]),
+                }
+                rest = &rest[name.len() + 2..];
+            }
+            None => {
+                output.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn template(keyword: Option<&str>, body: &str) -> DictationTemplate {
+        DictationTemplate {
+            id: "id".to_string(),
+            name: "Standup".to_string(),
+            keyword: keyword.map(str::to_string),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let now = Local.with_ymd_and_hms(2025, 3, 7, 9, 5, 0).unwrap();
+        let cases = vec![
+            ("Update: {summary}", "Done", "Update: Done"),
+            ("{date} {time}", "", "2025-03-07 09:05"),
+            ("{unknown} {summary}", "x", "{unknown} x"),
+            ("{{summary}} {summary}", "x", "{summary} x"),
+            ("open { brace", "x", "open { brace"),
+        ];
+
+        for (body, summary, expected) in cases {
+            assert_eq!(render(body, summary, now), expected, "body: {}", body);
+        }
+    }
+
+    #[test]
+    fn test_match_spoken_keyword() {
+        let templates = vec![template(Some("standup update"), "{summary}")];
+
+        let (_, rest) =
+            match_spoken_keyword(&templates, "Standup update, fixed the login bug.").unwrap();
+        assert_eq!(rest, "fixed the login bug.");
+
+        assert!(match_spoken_keyword(&templates, "Standup updates are boring").is_none());
+        assert!(match_spoken_keyword(&templates, "Send the standup update").is_none());
+        assert!(match_spoken_keyword(&[template(None, "{summary}")], "anything").is_none());
+
+        // Case variants with a different UTF-8 length, and text shorter than the keyword
+        let templates = vec![template(Some("straße notiz"), "{summary}")];
+        let (_, rest) = match_spoken_keyword(&templates, "STRAẞE NOTIZ: Baustelle").unwrap();
+        assert_eq!(rest, "Baustelle");
+        assert!(match_spoken_keyword(&templates, "Straß").is_none());
+        assert!(match_spoken_keyword(&templates, "Ähm, straße notiz").is_none());
+    }
+}
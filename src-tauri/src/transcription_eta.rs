@@ -0,0 +1,160 @@
+//! Estimated transcription time.
+//!
+//! Every successful transcription updates a rolling model of transcription time vs.
+//! audio duration for the engine that ran it (provider, and model for local
+//! transcription), stored locally with the usage statistics. When the next
+//! transcription starts, the model predicts how long it will take, so the popup can
+//! show "about 4s remaining" instead of an indeterminate spinner.
+
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AppConfig, ConfigKey, ConfigStore, LocalModelConfig, Provider};
+
+/// Weight of the newest transcription in the rolling averages (about the last 10 count)
+const SMOOTHING: f64 = 0.1;
+
+/// Transcriptions needed before an estimate is given
+const MIN_SAMPLES: u32 = 3;
+
+/// Below this spread of audio durations (ms², a standard deviation of 0.5s) the
+/// slope can't be fitted and the estimate scales the average speed instead
+const MIN_DURATION_VARIANCE: f64 = 250_000.0;
+
+/// Rolling linear model `transcription_ms = overhead + slope * audio_ms`, from
+/// exponentially weighted means, variance and covariance of past transcriptions
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionSpeed {
+    pub samples: u32,
+    pub mean_audio_ms: f64,
+    pub mean_transcription_ms: f64,
+    pub audio_variance: f64,
+    pub covariance: f64,
+}
+
+impl TranscriptionSpeed {
+    /// Add a transcription of `audio_ms` of audio that took `transcription_ms`
+    pub fn record(&mut self, audio_ms: u64, transcription_ms: u64) {
+        self.samples = self.samples.saturating_add(1);
+        // Plain averages until there are enough samples to smooth
+        let weight = (1.0 / self.samples as f64).max(SMOOTHING);
+
+        let audio_delta = audio_ms as f64 - self.mean_audio_ms;
+        let transcription_delta = transcription_ms as f64 - self.mean_transcription_ms;
+        self.mean_audio_ms += weight * audio_delta;
+        self.mean_transcription_ms += weight * transcription_delta;
+        self.audio_variance = (1.0 - weight) * (self.audio_variance + weight * audio_delta.powi(2));
+        self.covariance =
+            (1.0 - weight) * (self.covariance + weight * audio_delta * transcription_delta);
+    }
+
+    /// Expected transcription time of `audio_ms` of audio, `None` until there are
+    /// enough samples
+    pub fn estimate_ms(&self, audio_ms: u64) -> Option<u64> {
+        if self.samples < MIN_SAMPLES || self.mean_audio_ms <= 0.0 {
+            return None;
+        }
+        let audio_ms = audio_ms as f64;
+        let scaled = self.mean_transcription_ms * audio_ms / self.mean_audio_ms;
+
+        let estimate = if self.audio_variance >= MIN_DURATION_VARIANCE {
+            let slope = (self.covariance / self.audio_variance).max(0.0);
+            let overhead = self.mean_transcription_ms - slope * self.mean_audio_ms;
+            let fitted = overhead + slope * audio_ms;
+            if fitted > 0.0 {
+                fitted
+            } else {
+                scaled
+            }
+        } else {
+            scaled
+        };
+        Some(estimate.round() as u64)
+    }
+}
+
+/// Key of the configured transcription engine ("local/whisper-small", "open_ai"),
+/// `None` without a provider or local model
+pub fn engine_key(app_config: &AppConfig, local_config: &LocalModelConfig) -> Option<String> {
+    match app_config.active_provider.as_ref()? {
+        Provider::OpenAI => Some("open_ai".to_string()),
+        Provider::AzureOpenAI => Some("azure_open_ai".to_string()),
+        Provider::Local => local_config
+            .selected_model
+            .as_ref()
+            .map(|model| format!("local/{}", model)),
+    }
+}
+
+/// Expected transcription time of `audio_ms` of audio with `engine`
+pub fn estimate_ms(store: &impl ConfigStore, engine: &str, audio_ms: u64) -> Option<u64> {
+    store
+        .get(&ConfigKey::USAGE_STATS)
+        .unwrap_or_default()
+        .transcription_speed
+        .get(engine)
+        .and_then(|speed| speed.estimate_ms(audio_ms))
+}
+
+/// Add a transcription of `audio_ms` of audio by `engine` that took `transcription_ms`
+pub fn record(store: &impl ConfigStore, engine: &str, audio_ms: u64, transcription_ms: u64) {
+    let mut stats = store.get(&ConfigKey::USAGE_STATS).unwrap_or_default();
+    stats
+        .transcription_speed
+        .entry(engine.to_string())
+        .or_default()
+        .record(audio_ms, transcription_ms);
+
+    if let Err(e) = store.set(&ConfigKey::USAGE_STATS, stats) {
+        error!("Failed to save transcription times: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_fits_overhead_and_speed() {
+        let mut speed = TranscriptionSpeed::default();
+        // 400ms overhead plus a tenth of the audio duration
+        for audio_ms in [2_000, 10_000, 5_000] {
+            assert_eq!(speed.estimate_ms(audio_ms), None);
+            speed.record(audio_ms, 400 + audio_ms / 10);
+        }
+
+        assert_eq!(speed.estimate_ms(20_000), Some(2_400));
+        assert_eq!(speed.estimate_ms(1_000), Some(500));
+    }
+
+    #[test]
+    fn test_estimate_scales_speed_of_similar_durations() {
+        let mut speed = TranscriptionSpeed::default();
+        for _ in 0..5 {
+            speed.record(3_000, 1_500);
+        }
+
+        assert_eq!(speed.estimate_ms(3_000), Some(1_500));
+        assert_eq!(speed.estimate_ms(6_000), Some(3_000));
+    }
+
+    #[test]
+    fn test_engine_key() {
+        let local = AppConfig {
+            active_provider: Some(Provider::Local),
+            ..AppConfig::default()
+        };
+        let model = LocalModelConfig {
+            selected_model: Some("whisper-small".to_string()),
+            ..LocalModelConfig::default()
+        };
+
+        assert_eq!(
+            engine_key(&local, &model).as_deref(),
+            Some("local/whisper-small")
+        );
+        assert_eq!(engine_key(&local, &LocalModelConfig::default()), None);
+        assert_eq!(engine_key(&AppConfig::default(), &model), None);
+    }
+}
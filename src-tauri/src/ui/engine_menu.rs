@@ -0,0 +1,155 @@
+//! "Engine" tray submenu: switch the transcription provider or local model
+//! without opening Preferences.
+//!
+//! Selecting an item saves the config like the Preferences window does, so the
+//! config change bus preloads the model and refreshes the checkmarks.
+
+use std::sync::Arc;
+
+use log::{error, info};
+use tauri::{menu, AppHandle, Manager, Wry};
+
+use crate::config::{self, ConfigKey, ConfigStore, Provider};
+use crate::models::{ModelLoader, ModelManager};
+
+/// Prefix of the cloud provider menu item ids (`provider:<provider>`)
+pub const PROVIDER_MENU_ID_PREFIX: &str = "provider:";
+/// Prefix of the local model menu item ids (`local_model:<model name>`)
+pub const LOCAL_MODEL_MENU_ID_PREFIX: &str = "local_model:";
+
+/// Cloud providers listed in the submenu, with their menu id and label
+const CLOUD_PROVIDERS: [(Provider, &str, &str); 2] = [
+    (Provider::OpenAI, "open_ai", "OpenAI"),
+    (Provider::AzureOpenAI, "azure_open_ai", "Azure OpenAI"),
+];
+
+#[derive(Clone)]
+pub struct EngineMenu {
+    submenu: menu::Submenu<Wry>,
+}
+
+impl EngineMenu {
+    pub fn new(app: &tauri::App<Wry>) -> Result<Self, tauri::Error> {
+        let submenu = menu::SubmenuBuilder::new(app, "Engine").build()?;
+        Ok(Self { submenu })
+    }
+
+    pub fn submenu(&self) -> &menu::Submenu<Wry> {
+        &self.submenu
+    }
+
+    /// Rebuild the items from the config and the downloaded models, checking the active one
+    pub fn refresh(&self, app: &AppHandle<Wry>) -> Result<(), tauri::Error> {
+        for item in self.submenu.items()? {
+            self.submenu.remove(&item)?;
+        }
+
+        let config_store = app.state::<config::Config>();
+        let active_provider = config_store
+            .get(&ConfigKey::APP)
+            .unwrap_or_default()
+            .active_provider;
+        let selected_model = config_store
+            .get(&ConfigKey::LOCAL_MODEL)
+            .and_then(|local_config| local_config.selected_model);
+
+        for (provider, id, label) in CLOUD_PROVIDERS {
+            let item = menu::CheckMenuItemBuilder::with_id(
+                format!("{}{}", PROVIDER_MENU_ID_PREFIX, id),
+                label,
+            )
+            .checked(active_provider.as_ref() == Some(&provider))
+            .build(app)?;
+            self.submenu.append(&item)?;
+        }
+
+        let downloaded_models: Vec<_> = match (
+            app.try_state::<Arc<ModelManager>>(),
+            app.try_state::<Arc<ModelLoader>>(),
+        ) {
+            (Some(manager), Some(loader)) => manager
+                .get_all_models(&loader)
+                .into_iter()
+                .filter(|model| model.is_downloaded)
+                .collect(),
+            _ => Vec::new(),
+        };
+        if !downloaded_models.is_empty() {
+            self.submenu
+                .append(&menu::PredefinedMenuItem::separator(app)?)?;
+        }
+
+        for model in downloaded_models {
+            let checked = active_provider == Some(Provider::Local)
+                && selected_model.as_ref() == Some(&model.name);
+            let item = menu::CheckMenuItemBuilder::with_id(
+                format!("{}{}", LOCAL_MODEL_MENU_ID_PREFIX, model.name),
+                format!("Local: {}", model.display_name),
+            )
+            .checked(checked)
+            .build(app)?;
+            self.submenu.append(&item)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Refresh the submenu after the provider, the selected model or the downloaded models changed
+pub fn refresh_engine_menu(app: &AppHandle) {
+    if let Some(engine_menu) = app.try_state::<EngineMenu>() {
+        if let Err(e) = engine_menu.refresh(app) {
+            error!("Failed to refresh engine menu: {}", e);
+        }
+    }
+}
+
+/// Handle a click on an engine menu item, returns `false` for other menu items
+pub fn handle_menu_event(app: &AppHandle, menu_id: &str) -> bool {
+    let result = if let Some(id) = menu_id.strip_prefix(PROVIDER_MENU_ID_PREFIX) {
+        select_provider(app, id)
+    } else if let Some(model_name) = menu_id.strip_prefix(LOCAL_MODEL_MENU_ID_PREFIX) {
+        select_local_model(app, model_name)
+    } else {
+        return false;
+    };
+
+    if let Err(e) = result {
+        error!("Failed to switch engine: {}", e);
+        // Undo the checkmark toggled by the click
+        refresh_engine_menu(app);
+    }
+    true
+}
+
+fn select_provider(app: &AppHandle, id: &str) -> Result<(), String> {
+    let (provider, _, _) = CLOUD_PROVIDERS
+        .into_iter()
+        .find(|(_, provider_id, _)| *provider_id == id)
+        .ok_or_else(|| format!("Unknown provider: {}", id))?;
+
+    info!("Switching provider to {:?} from the tray menu", provider);
+    let config_store = app.state::<config::Config>();
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    config.active_provider = Some(provider);
+    config_store.set(&ConfigKey::APP, config)
+}
+
+fn select_local_model(app: &AppHandle, model_name: &str) -> Result<(), String> {
+    info!("Switching to local model {} from the tray menu", model_name);
+    let config_store = app.state::<config::Config>();
+
+    // Model first: switching the provider afterwards preloads the new model only once
+    let mut local_config = config_store
+        .get(&ConfigKey::LOCAL_MODEL)
+        .unwrap_or_default();
+    local_config.selected_model = Some(model_name.to_string());
+    config_store.set(&ConfigKey::LOCAL_MODEL, local_config)?;
+
+    let mut config = config_store.get(&ConfigKey::APP).unwrap_or_default();
+    if config.active_provider != Some(Provider::Local) {
+        config.active_provider = Some(Provider::Local);
+        config_store.set(&ConfigKey::APP, config)?;
+    }
+    Ok(())
+}
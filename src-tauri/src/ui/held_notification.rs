@@ -0,0 +1,190 @@
+//! Notification of a transcription held back after an app switch, with "Paste" and
+//! "Copy" actions.
+//!
+//! The notification plugin only has actions on mobile, so on macOS the notification is
+//! posted through `UNUserNotificationCenter`. Its actions paste or copy the last
+//! recording, like the menu bar items. Without an app bundle (`tauri dev`) or on other
+//! platforms, a plain notification points to the menu bar instead.
+
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+const TITLE: &str = "Transcription ready";
+
+/// Register the actions and ask for the notification permission, once at startup
+pub fn setup(app: &AppHandle) {
+    #[cfg(target_os = "macos")]
+    if let Err(e) = macos::setup(app) {
+        log::warn!("Notification actions unavailable: {}", e);
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = app;
+}
+
+/// Show `preview` of the held transcription
+pub fn show(app: &AppHandle, preview: &str) {
+    #[cfg(target_os = "macos")]
+    if macos::show(preview) {
+        return;
+    }
+
+    let result = app
+        .notification()
+        .builder()
+        .title(TITLE)
+        .body(format!("{}\nPaste or copy it from the menu bar.", preview))
+        .show();
+    if let Err(e) = result {
+        log::error!("Failed to show transcription notification: {}", e);
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::sync::OnceLock;
+
+    use block2::{DynBlock, RcBlock};
+    use objc2::rc::Retained;
+    use objc2::runtime::{Bool, NSObject, NSObjectProtocol, ProtocolObject};
+    use objc2::{define_class, msg_send, AllocAnyThread, DefinedClass};
+    use objc2_foundation::{NSArray, NSBundle, NSError, NSSet, NSString};
+    use objc2_user_notifications::{
+        UNAuthorizationOptions, UNMutableNotificationContent, UNNotification, UNNotificationAction,
+        UNNotificationActionOptions, UNNotificationCategory, UNNotificationCategoryOptions,
+        UNNotificationPresentationOptions, UNNotificationRequest, UNNotificationResponse,
+        UNUserNotificationCenter, UNUserNotificationCenterDelegate,
+    };
+    use tauri::AppHandle;
+
+    use crate::ui::tray::Tray;
+
+    const CATEGORY: &str = "held-transcription";
+    const PASTE_ACTION: &str = "paste";
+    const COPY_ACTION: &str = "copy";
+    /// A new held transcription replaces the notification of the previous one
+    const REQUEST_ID: &str = "held-transcription";
+
+    /// Set once the category and the delegate are registered
+    static READY: OnceLock<()> = OnceLock::new();
+
+    define_class!(
+        #[unsafe(super(NSObject))]
+        #[name = "DictaraHeldNotificationDelegate"]
+        #[ivars = AppHandle]
+        struct Delegate;
+
+        unsafe impl NSObjectProtocol for Delegate {}
+
+        unsafe impl UNUserNotificationCenterDelegate for Delegate {
+            #[unsafe(method(userNotificationCenter:didReceiveNotificationResponse:withCompletionHandler:))]
+            fn did_receive_response(
+                &self,
+                _center: &UNUserNotificationCenter,
+                response: &UNNotificationResponse,
+                completion_handler: &DynBlock<dyn Fn()>,
+            ) {
+                let action = unsafe { response.actionIdentifier() }.to_string();
+                let app = self.ivars().clone();
+                // Off the main thread: pasting simulates key presses
+                tauri::async_runtime::spawn_blocking(move || match action.as_str() {
+                    PASTE_ACTION => Tray::handle_paste_last_recording(&app),
+                    COPY_ACTION => Tray::handle_copy_last_recording(&app),
+                    _ => {}
+                });
+                completion_handler.call(());
+            }
+
+            /// Shown even while Dictara is the active app (e.g. its settings are open)
+            #[unsafe(method(userNotificationCenter:willPresentNotification:withCompletionHandler:))]
+            fn will_present(
+                &self,
+                _center: &UNUserNotificationCenter,
+                _notification: &UNNotification,
+                completion_handler: &DynBlock<dyn Fn(UNNotificationPresentationOptions)>,
+            ) {
+                completion_handler.call((UNNotificationPresentationOptions::Banner
+                    | UNNotificationPresentationOptions::List,));
+            }
+        }
+    );
+
+    impl Delegate {
+        fn new(app: AppHandle) -> Retained<Self> {
+            let this = Self::alloc().set_ivars(app);
+            unsafe { msg_send![super(this), init] }
+        }
+    }
+
+    pub fn setup(app: &AppHandle) -> Result<(), String> {
+        // The notification center needs an app bundle, it raises an exception without
+        if NSBundle::mainBundle().bundleIdentifier().is_none() {
+            return Err("not running from an app bundle".to_string());
+        }
+
+        let paste = action(PASTE_ACTION, "Paste");
+        let copy = action(COPY_ACTION, "Copy");
+        unsafe {
+            let center = UNUserNotificationCenter::currentNotificationCenter();
+            let category =
+                UNNotificationCategory::categoryWithIdentifier_actions_intentIdentifiers_options(
+                    &NSString::from_str(CATEGORY),
+                    &NSArray::from_retained_slice(&[paste, copy]),
+                    &NSArray::new(),
+                    UNNotificationCategoryOptions::empty(),
+                );
+            center.setNotificationCategories(&NSSet::from_retained_slice(&[category]));
+
+            // The center only keeps a weak reference, the delegate lives as long as the app
+            let delegate = Delegate::new(app.clone());
+            center.setDelegate(Some(ProtocolObject::from_ref(&*delegate)));
+            std::mem::forget(delegate);
+
+            let on_authorization = RcBlock::new(|granted: Bool, _error: *mut NSError| {
+                if !granted.as_bool() {
+                    log::info!("Notifications not allowed, held transcriptions stay silent");
+                }
+            });
+            center.requestAuthorizationWithOptions_completionHandler(
+                UNAuthorizationOptions::Alert | UNAuthorizationOptions::Sound,
+                &on_authorization,
+            );
+        }
+
+        let _ = READY.set(());
+        Ok(())
+    }
+
+    /// Action running in the background: the app isn't activated, so the paste goes
+    /// to the frontmost app
+    fn action(identifier: &str, title: &str) -> Retained<UNNotificationAction> {
+        unsafe {
+            UNNotificationAction::actionWithIdentifier_title_options(
+                &NSString::from_str(identifier),
+                &NSString::from_str(title),
+                UNNotificationActionOptions::empty(),
+            )
+        }
+    }
+
+    /// Post the notification with the actions, `false` when they aren't set up
+    pub fn show(preview: &str) -> bool {
+        if READY.get().is_none() {
+            return false;
+        }
+
+        unsafe {
+            let content = UNMutableNotificationContent::new();
+            content.setTitle(&NSString::from_str(super::TITLE));
+            content.setBody(&NSString::from_str(preview));
+            content.setCategoryIdentifier(&NSString::from_str(CATEGORY));
+            let request = UNNotificationRequest::requestWithIdentifier_content_trigger(
+                &NSString::from_str(REQUEST_ID),
+                &content,
+                None,
+            );
+            UNUserNotificationCenter::currentNotificationCenter()
+                .addNotificationRequest_withCompletionHandler(&request, None);
+        }
+        true
+    }
+}
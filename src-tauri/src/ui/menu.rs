@@ -1,38 +1,98 @@
-use tauri::{self, menu, Wry};
+use tauri::{self, menu, AppHandle, Wry};
+
+use crate::config::TemplatesConfig;
+
+use super::engine_menu::EngineMenu;
 
 #[derive(strum::AsRefStr, strum::EnumString)]
 #[strum(serialize_all = "snake_case")]
 pub enum MenuId {
     Preferences,
     PasteLastRecording,
+    CopyLastRecording,
+    RestorePreviousClipboard,
+    RawMode,
+    NoTemplate,
     Quit,
 }
 
+/// Prefix of the template menu item ids (`template:<template id>`)
+pub const TEMPLATE_MENU_ID_PREFIX: &str = "template:";
+
 pub struct Menu {
     pub menu: menu::Menu<Wry>,
     paste_last_item: menu::MenuItem<Wry>,
+    copy_last_item: menu::MenuItem<Wry>,
+    raw_mode_item: menu::CheckMenuItem<Wry>,
+    engine_menu: EngineMenu,
+    templates_menu: TemplatesMenu,
 }
 
 impl Menu {
-    pub fn new(app: &tauri::App<tauri::Wry>) -> Result<Menu, tauri::Error> {
+    pub fn new(
+        app: &tauri::App<tauri::Wry>,
+        templates: &TemplatesConfig,
+    ) -> Result<Menu, tauri::Error> {
         let preferences_item = Self::create_preferences_item(app)?;
         let paste_last_item = Self::create_paste_last_item(app)?;
+        let copy_last_item = Self::create_copy_last_item(app)?;
+        let restore_clipboard_item = Self::create_restore_clipboard_item(app)?;
+        let raw_mode_item = Self::create_raw_mode_item(app)?;
+        let engine_menu = EngineMenu::new(app)?;
+        let templates_submenu = menu::SubmenuBuilder::new(app, "Template").build()?;
         let quit_item = Self::create_quit_item(app)?;
 
-        let menu = Self::create_menu(app, &[&preferences_item, &paste_last_item, &quit_item])?;
+        let menu = menu::MenuBuilder::new(app)
+            .item(&preferences_item)
+            .item(&paste_last_item)
+            .item(&copy_last_item)
+            .item(&restore_clipboard_item)
+            .item(&raw_mode_item)
+            .item(engine_menu.submenu())
+            .item(&templates_submenu)
+            .separator()
+            .item(&quit_item)
+            .build()?;
+
+        let templates_menu = TemplatesMenu {
+            submenu: templates_submenu,
+        };
+        templates_menu.refresh(app.handle(), templates)?;
+        // The engine submenu scans the models on disk, it is filled after startup
 
         Ok(Menu {
             menu,
             paste_last_item,
+            copy_last_item,
+            raw_mode_item,
+            engine_menu,
+            templates_menu,
         })
     }
 
     pub fn set_paste_last_active(&self) -> Result<(), tauri::Error> {
-        self.paste_last_item.set_enabled(true)
+        self.paste_last_item.set_enabled(true)?;
+        self.copy_last_item.set_enabled(true)
     }
 
     pub fn set_paste_last_inactive(&self) -> Result<(), tauri::Error> {
-        self.paste_last_item.set_enabled(false)
+        self.paste_last_item.set_enabled(false)?;
+        self.copy_last_item.set_enabled(false)
+    }
+
+    /// Raw mode checkbox, kept in sync by `RawMode`
+    pub fn raw_mode_item(&self) -> menu::CheckMenuItem<Wry> {
+        self.raw_mode_item.clone()
+    }
+
+    /// Handle to the engine submenu, kept in app state to refresh it after config changes
+    pub fn engine_menu(&self) -> EngineMenu {
+        self.engine_menu.clone()
+    }
+
+    /// Handle to the template submenu, kept in app state to refresh it after edits
+    pub fn templates_menu(&self) -> TemplatesMenu {
+        self.templates_menu.clone()
     }
 
     fn create_preferences_item(
@@ -49,25 +109,75 @@ impl Menu {
             .build(app)
     }
 
+    fn create_copy_last_item(
+        app: &tauri::App<tauri::Wry>,
+    ) -> Result<menu::MenuItem<Wry>, tauri::Error> {
+        menu::MenuItemBuilder::with_id(MenuId::CopyLastRecording.as_ref(), "Copy Last Recording")
+            .enabled(false) // Initially disabled until first recording
+            .build(app)
+    }
+
+    fn create_restore_clipboard_item(
+        app: &tauri::App<tauri::Wry>,
+    ) -> Result<menu::MenuItem<Wry>, tauri::Error> {
+        menu::MenuItemBuilder::with_id(
+            MenuId::RestorePreviousClipboard.as_ref(),
+            "Restore Previous Clipboard",
+        )
+        .build(app)
+    }
+
+    fn create_raw_mode_item(
+        app: &tauri::App<tauri::Wry>,
+    ) -> Result<menu::CheckMenuItem<Wry>, tauri::Error> {
+        menu::CheckMenuItemBuilder::with_id(MenuId::RawMode.as_ref(), "Raw Mode (Verbatim)")
+            .checked(false)
+            .build(app)
+    }
+
     fn create_quit_item(app: &tauri::App<tauri::Wry>) -> Result<menu::MenuItem<Wry>, tauri::Error> {
         menu::MenuItemBuilder::with_id(MenuId::Quit.as_ref(), "Quit").build(app)
     }
+}
 
-    /// Creates the menu with a separator before the last item
-    fn create_menu(
-        app: &tauri::App<tauri::Wry>,
-        items: &[&menu::MenuItem<Wry>],
-    ) -> Result<menu::Menu<Wry>, tauri::Error> {
-        let mut builder = menu::MenuBuilder::new(app);
-
-        let len = items.len();
-        for (i, item) in items.iter().enumerate() {
-            if i == len - 1 && len > 1 {
-                builder = builder.separator();
-            }
-            builder = builder.item(*item);
+/// "Template" submenu: pick the template applied to every dictation
+#[derive(Clone)]
+pub struct TemplatesMenu {
+    submenu: menu::Submenu<Wry>,
+}
+
+impl TemplatesMenu {
+    /// Rebuild the items from the templates, checking the active one
+    pub fn refresh(
+        &self,
+        app: &AppHandle<Wry>,
+        templates: &TemplatesConfig,
+    ) -> Result<(), tauri::Error> {
+        for item in self.submenu.items()? {
+            self.submenu.remove(&item)?;
+        }
+
+        let no_template_item =
+            menu::CheckMenuItemBuilder::with_id(MenuId::NoTemplate.as_ref(), "None")
+                .checked(templates.active_template_id.is_none())
+                .build(app)?;
+        self.submenu.append(&no_template_item)?;
+
+        if !templates.templates.is_empty() {
+            self.submenu
+                .append(&menu::PredefinedMenuItem::separator(app)?)?;
+        }
+
+        for template in &templates.templates {
+            let item = menu::CheckMenuItemBuilder::with_id(
+                format!("{}{}", TEMPLATE_MENU_ID_PREFIX, template.id),
+                &template.name,
+            )
+            .checked(templates.active_template_id.as_ref() == Some(&template.id))
+            .build(app)?;
+            self.submenu.append(&item)?;
         }
 
-        builder.build()
+        Ok(())
     }
 }
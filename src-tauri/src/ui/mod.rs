@@ -1,3 +1,6 @@
+pub mod engine_menu;
+pub mod held_notification;
 pub mod menu;
+pub mod prefs;
 pub mod tray;
 pub mod window;
@@ -0,0 +1,34 @@
+//! Appearance preferences shared by all windows.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::config::{self, ConfigKey, ConfigStore, UiPrefs, MIN_POPUP_OPACITY};
+
+/// UI preferences were saved, every window restyles live
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct UiPrefsChanged {
+    pub prefs: UiPrefs,
+}
+
+/// Current UI preferences, defaults if never saved
+pub fn load_ui_prefs(app: &AppHandle) -> UiPrefs {
+    app.state::<config::Config>()
+        .get(&ConfigKey::UI_PREFS)
+        .unwrap_or_default()
+}
+
+/// Validate and save UI preferences (windows are notified through the config change bus)
+pub fn save_ui_prefs(app: &AppHandle, prefs: UiPrefs) -> Result<(), String> {
+    if !prefs.popup_opacity.is_finite() || !(MIN_POPUP_OPACITY..=1.0).contains(&prefs.popup_opacity)
+    {
+        return Err(format!(
+            "Popup opacity must be between {} and 1.0",
+            MIN_POPUP_OPACITY
+        ));
+    }
+
+    app.state::<config::Config>()
+        .set(&ConfigKey::UI_PREFS, prefs)
+}
@@ -1,10 +1,15 @@
-use crate::recording::LastRecordingState;
+use crate::config::{self, ConfigKey, ConfigStore};
+use crate::recording::{LastRecordingState, RawMode};
+use crate::shutdown;
+use crate::templates;
 use crate::ui::{
-    menu::{Menu, MenuId},
+    engine_menu,
+    menu::{Menu, MenuId, TEMPLATE_MENU_ID_PREFIX},
     window,
 };
 use log::{error, warn};
 use std::str::FromStr;
+use std::sync::Arc;
 use tauri::{self, menu::MenuEvent, tray, Manager, Wry};
 
 const TRAY_ICON_BYTES: &[u8] = include_bytes!("../../icons/tray-icon.png");
@@ -45,6 +50,16 @@ impl Tray {
     }
 
     fn handle_menu_event(app: &tauri::AppHandle<Wry>, event: MenuEvent) {
+        if let Some(template_id) = event.id().as_ref().strip_prefix(TEMPLATE_MENU_ID_PREFIX) {
+            if let Err(e) = templates::set_active_template(app, Some(template_id.to_string())) {
+                error!("Failed to select template: {}", e);
+            }
+            return;
+        }
+        if engine_menu::handle_menu_event(app, event.id().as_ref()) {
+            return;
+        }
+
         let Ok(menu_id) = MenuId::from_str(event.id().as_ref()) else {
             warn!("Unknown menu event id: {}", event.id().as_ref());
             return;
@@ -59,7 +74,28 @@ impl Tray {
             MenuId::PasteLastRecording => {
                 Self::handle_paste_last_recording(app);
             }
+            MenuId::CopyLastRecording => {
+                Self::handle_copy_last_recording(app);
+            }
+            MenuId::RestorePreviousClipboard => {
+                match crate::text_paster::restore_previous_clipboard() {
+                    Ok(true) => {}
+                    Ok(false) => warn!("No previous clipboard content to restore"),
+                    Err(e) => error!("Failed to restore previous clipboard: {:?}", e),
+                }
+            }
+            MenuId::RawMode => {
+                if let Some(raw_mode) = app.try_state::<Arc<RawMode>>() {
+                    raw_mode.toggle();
+                }
+            }
+            MenuId::NoTemplate => {
+                if let Err(e) = templates::set_active_template(app, None) {
+                    error!("Failed to clear active template: {}", e);
+                }
+            }
             MenuId::Quit => {
+                shutdown::shutdown(app);
                 app.exit(0);
             }
         }
@@ -67,11 +103,16 @@ impl Tray {
 
     // TODO: Refactor: I do not like this nesting. Will leave it like this for now and will refactor later.
     // Also might need to pull the pasting into a separate module
-    fn handle_paste_last_recording(app: &tauri::AppHandle<Wry>) {
+    pub fn handle_paste_last_recording(app: &tauri::AppHandle<Wry>) {
         if let Some(state) = app.try_state::<LastRecordingState>() {
             if let Ok(last_recording) = state.lock() {
                 if let Some(text) = &last_recording.text {
-                    if let Err(e) = crate::text_paster::paste_text(text) {
+                    let marker = app
+                        .state::<config::Config>()
+                        .get(&ConfigKey::APP)
+                        .unwrap_or_default()
+                        .clipboard_marker;
+                    if let Err(e) = crate::text_paster::insert_text_with_marker(text, marker) {
                         error!("Failed to paste last recording: {:?}", e);
                     }
                 }
@@ -80,4 +121,19 @@ impl Tray {
             }
         }
     }
+
+    pub fn handle_copy_last_recording(app: &tauri::AppHandle<Wry>) {
+        let Some(state) = app.try_state::<LastRecordingState>() else {
+            return;
+        };
+        let Ok(last_recording) = state.lock() else {
+            error!("Failed to lock last recording state");
+            return;
+        };
+        if let Some(text) = &last_recording.text {
+            if let Err(e) = crate::text_paster::copy_text(text) {
+                error!("Failed to copy last recording: {:?}", e);
+            }
+        }
+    }
 }
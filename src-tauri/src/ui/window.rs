@@ -9,6 +9,7 @@ const POPUP_WIDTH_NORMAL: u32 = 80;
 const POPUP_WIDTH_ERROR: u32 = 400; // 5x wider for error display
 const POPUP_HEIGHT: u32 = 74;
 const BOTTOM_MARGIN: i32 = 100;
+const CAPTIONS_WINDOW: &str = "captions";
 
 /// Show a window without stealing focus (macOS only).
 /// Uses `orderFront:` instead of `makeKeyAndOrderFront:` to avoid activating the app.
@@ -244,6 +245,43 @@ pub fn open_preferences_window(app_handle: &tauri::AppHandle) -> Result<(), AnyE
     Ok(())
 }
 
+/// Open the floating live captions window (always on top, without taking focus)
+pub fn open_captions_window(
+    app_handle: &tauri::AppHandle,
+) -> Result<tauri::WebviewWindow, AnyError> {
+    let (width, height) = (720.0, 160.0);
+
+    let window = match app_handle.get_webview_window(CAPTIONS_WINDOW) {
+        Some(w) => w,
+        None => tauri::WebviewWindowBuilder::new(
+            app_handle,
+            CAPTIONS_WINDOW,
+            tauri::WebviewUrl::App("captions".into()),
+        )
+        .title("Live Captions")
+        .inner_size(width, height)
+        .min_inner_size(320.0, 80.0)
+        .always_on_top(true)
+        .visible_on_all_workspaces(true)
+        .skip_taskbar(true)
+        .focused(false)
+        .background_color(Color(10, 10, 10, 255)) // Dark background to prevent white flash
+        .visible(false)
+        .build()?,
+    };
+
+    show_window_without_focus(&window)?;
+    Ok(window)
+}
+
+/// Close the live captions window, if open
+pub fn close_captions_window(app_handle: &tauri::AppHandle) -> Result<(), AnyError> {
+    if let Some(window) = app_handle.get_webview_window(CAPTIONS_WINDOW) {
+        window.close()?;
+    }
+    Ok(())
+}
+
 pub fn open_onboarding_window(app_handle: &tauri::AppHandle) -> Result<(), AnyError> {
     let (width, height) = (800.0, 800.0);
 
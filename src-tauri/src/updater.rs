@@ -10,6 +10,8 @@ use tauri::Manager;
 use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
 use tauri_plugin_updater::UpdaterExt;
 
+use crate::config;
+use crate::network;
 use crate::recording::RecordingStateManager;
 
 /// Check interval: 30 minutes in release, 1 minute in debug for testing
@@ -193,7 +195,7 @@ fn perform_install(app_handle: &tauri::AppHandle, pending: PendingInstall) {
     let handle = app_handle.clone();
     tauri::async_runtime::spawn(async move {
         let result: Result<(), Box<dyn std::error::Error + Send + Sync>> = async {
-            let updater = handle.updater()?;
+            let updater = plugin_updater(&handle)?;
             let update = updater.check().await?;
 
             let Some(update) = update else {
@@ -314,12 +316,49 @@ async fn check_and_download_update(app_handle: tauri::AppHandle, updater_state:
     updater_state.set_checking(false);
 }
 
+/// Updater of the plugin, with the proxy and TLS settings of the network preferences
+/// like the other HTTP clients (including a direct connection past the system proxy).
+/// Roots pinned for the update endpoint's host apply to the check and the download.
+fn plugin_updater(
+    app_handle: &tauri::AppHandle,
+) -> Result<tauri_plugin_updater::Updater, Box<dyn std::error::Error + Send + Sync>> {
+    let settings = network::http_settings(app_handle.state::<config::Config>().inner());
+    let endpoint = update_endpoint(app_handle).unwrap_or_default();
+    // An unreadable certificate file fails the check here, the client closure can't
+    settings.configure(reqwest::Client::builder(), &endpoint)?;
+
+    let updater = app_handle
+        .updater_builder()
+        .configure_client(move |client| {
+            settings.configure(client, &endpoint).unwrap_or_else(|e| {
+                error!("Failed to configure the updater client: {}", e);
+                // Trusts nothing: the settings became unusable since the check above
+                reqwest::Client::builder().tls_built_in_root_certs(false)
+            })
+        })
+        .build()?;
+    Ok(updater)
+}
+
+/// First URL of `plugins.updater.endpoints` in the Tauri config
+fn update_endpoint(app_handle: &tauri::AppHandle) -> Option<String> {
+    app_handle
+        .config()
+        .plugins
+        .0
+        .get("updater")?
+        .get("endpoints")?
+        .get(0)?
+        .as_str()
+        .map(str::to_string)
+}
+
 /// Check for updates and download (without installing)
 async fn download_update_only(
     app_handle: &tauri::AppHandle,
     updater_state: &Updater,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let updater = app_handle.updater()?;
+    let updater = plugin_updater(app_handle)?;
     let update = updater.check().await?;
 
     let Some(update) = update else {
@@ -392,9 +431,8 @@ async fn manual_check_and_prompt(
     app_handle: &tauri::AppHandle,
     show_no_update_message: bool,
 ) -> Result<bool, String> {
-    let updater = app_handle
-        .updater()
-        .map_err(|e| format!("Failed to get updater: {}", e))?;
+    let updater =
+        plugin_updater(app_handle).map_err(|e| format!("Failed to get updater: {}", e))?;
 
     let update = updater
         .check()
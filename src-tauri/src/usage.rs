@@ -0,0 +1,177 @@
+//! Local usage statistics and the daily word goal.
+//!
+//! Every successful dictation adds its word count to today's total (stored
+//! locally, never sent anywhere). When the total first reaches the goal set in
+//! `AppConfig::daily_word_goal`, a [`WordGoalReached`] event is emitted once
+//! for that day.
+
+use chrono::{Days, NaiveDate};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ConfigKey, ConfigStore, UsageStats};
+
+/// Days of history kept in the usage statistics
+const HISTORY_DAYS: u64 = 90;
+
+/// Fired once per day when the dictated words reach the daily goal
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct WordGoalReached {
+    pub goal: u32,
+    pub words_today: u32,
+}
+
+/// Progress towards today's word goal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProgress {
+    /// Daily word goal, `None` = no goal set
+    pub goal: Option<u32>,
+    pub words_today: u32,
+    pub reached: bool,
+}
+
+/// Number of words in dictated text
+pub fn count_words(text: &str) -> u32 {
+    text.split_whitespace().count() as u32
+}
+
+fn day_key(day: NaiveDate) -> String {
+    day.format("%Y-%m-%d").to_string()
+}
+
+/// Add the words of a dictation to `today`'s total. Returns the event to emit
+/// when this dictation is the one that reaches the goal.
+pub fn record_dictated_words(
+    store: &impl ConfigStore,
+    text: &str,
+    goal: Option<u32>,
+    today: NaiveDate,
+) -> Option<WordGoalReached> {
+    let words = count_words(text);
+    if words == 0 {
+        return None;
+    }
+
+    let mut stats = store.get(&ConfigKey::USAGE_STATS).unwrap_or_default();
+    let today_key = day_key(today);
+    let words_today = {
+        let total = stats.words_by_day.entry(today_key.clone()).or_insert(0);
+        *total = total.saturating_add(words);
+        *total
+    };
+
+    if let Some(oldest) = today.checked_sub_days(Days::new(HISTORY_DAYS)) {
+        let oldest = day_key(oldest);
+        stats.words_by_day.retain(|day, _| *day >= oldest);
+    }
+
+    let reached = goal
+        .filter(|goal| *goal > 0 && words_today >= *goal)
+        .and_then(|goal| {
+            (stats.goal_reached_on.as_deref() != Some(today_key.as_str()))
+                .then_some(WordGoalReached { goal, words_today })
+        });
+    if reached.is_some() {
+        stats.goal_reached_on = Some(today_key);
+    }
+
+    if let Err(e) = store.set(&ConfigKey::USAGE_STATS, stats) {
+        error!("Failed to save usage stats: {}", e);
+    }
+
+    reached
+}
+
+/// Words dictated on `today` compared to the goal
+pub fn goal_progress(
+    store: &impl ConfigStore,
+    goal: Option<u32>,
+    today: NaiveDate,
+) -> GoalProgress {
+    let stats: UsageStats = store.get(&ConfigKey::USAGE_STATS).unwrap_or_default();
+    let words_today = stats
+        .words_by_day
+        .get(&day_key(today))
+        .copied()
+        .unwrap_or(0);
+
+    GoalProgress {
+        goal,
+        words_today,
+        reached: goal.is_some_and(|goal| goal > 0 && words_today >= goal),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::mock::MockConfigStore;
+
+    fn day(d: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(count_words(""), 0);
+        assert_eq!(count_words("  hello   world\nagain "), 3);
+    }
+
+    #[test]
+    fn test_goal_reached_once_per_day() {
+        let store = MockConfigStore::new();
+        let today = day("2025-03-07");
+
+        assert!(record_dictated_words(&store, "one two three", Some(5), today).is_none());
+        let reached = record_dictated_words(&store, "four five", Some(5), today).unwrap();
+        assert_eq!((reached.goal, reached.words_today), (5, 5));
+        assert!(record_dictated_words(&store, "six", Some(5), today).is_none());
+
+        // A new day starts from zero and can notify again
+        let tomorrow = day("2025-03-08");
+        assert!(record_dictated_words(&store, "a b c d", Some(5), tomorrow).is_none());
+        assert!(record_dictated_words(&store, "e", Some(5), tomorrow).is_some());
+
+        assert_eq!(
+            goal_progress(&store, Some(5), today),
+            GoalProgress {
+                goal: Some(5),
+                words_today: 6,
+                reached: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_no_goal_or_zero_goal_never_fires() {
+        let store = MockConfigStore::new();
+        let today = day("2025-03-07");
+
+        assert!(record_dictated_words(&store, "some words", None, today).is_none());
+        assert!(record_dictated_words(&store, "more words", Some(0), today).is_none());
+        assert_eq!(
+            goal_progress(&store, None, today),
+            GoalProgress {
+                goal: None,
+                words_today: 4,
+                reached: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_history_is_pruned() {
+        let store = MockConfigStore::new();
+        record_dictated_words(&store, "old", None, day("2025-01-01"));
+        record_dictated_words(&store, "recent", None, day("2025-03-01"));
+        record_dictated_words(&store, "today", None, day("2025-04-10"));
+
+        let stats = store.get(&ConfigKey::USAGE_STATS).unwrap();
+        assert_eq!(
+            stats.words_by_day.keys().collect::<Vec<_>>(),
+            ["2025-03-01", "2025-04-10"]
+        );
+    }
+}
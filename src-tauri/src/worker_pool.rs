@@ -0,0 +1,261 @@
+//! Dedicated threads for blocking work.
+//!
+//! Model loading, local transcription, checksum hashing and archive extraction run
+//! here instead of on the recording controller thread or Tokio's shared blocking
+//! pool. Every job has a [`WorkPriority`]: queued interactive jobs start before
+//! background ones, and background jobs never take the last free worker, so a model
+//! download being verified can't delay a dictation.
+//!
+//! The size is read from `AppConfig::worker_threads` at startup (0 = automatic).
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+
+use log::{error, info};
+
+/// Fewest workers: one is always kept for interactive jobs
+pub const MIN_WORKER_THREADS: usize = 2;
+
+/// Most workers picked automatically, more rarely helps: inference uses its own threads
+const MAX_AUTO_WORKER_THREADS: usize = 4;
+
+/// Most workers that can be configured
+pub const MAX_WORKER_THREADS: u32 = 16;
+
+/// Which jobs run first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkPriority {
+    /// The user is waiting: transcription, loading the model to transcribe with
+    Interactive,
+    /// Downloads, verification, migration and moving models
+    Background,
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct Queues {
+    interactive: VecDeque<Job>,
+    background: VecDeque<Job>,
+    /// Background jobs currently running
+    background_running: usize,
+    shutdown: bool,
+}
+
+struct Shared {
+    queues: Mutex<Queues>,
+    /// Signalled when a job is queued or a background job finishes
+    changed: Condvar,
+    size: usize,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Queues> {
+        self.queues.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Fixed set of worker threads running jobs by priority
+pub struct WorkerPool {
+    shared: Arc<Shared>,
+}
+
+/// Number of workers for the configured value, 0 = automatic
+pub fn pool_size(configured: u32) -> usize {
+    match configured {
+        0 => thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(MIN_WORKER_THREADS)
+            .clamp(MIN_WORKER_THREADS, MAX_AUTO_WORKER_THREADS),
+        n => (n.min(MAX_WORKER_THREADS) as usize).max(MIN_WORKER_THREADS),
+    }
+}
+
+impl WorkerPool {
+    /// Start `size` workers (at least [`MIN_WORKER_THREADS`])
+    pub fn new(size: usize) -> Self {
+        let size = size.max(MIN_WORKER_THREADS);
+        let shared = Arc::new(Shared {
+            queues: Mutex::new(Queues::default()),
+            changed: Condvar::new(),
+            size,
+        });
+
+        for index in 0..size {
+            let shared = shared.clone();
+            let spawned = thread::Builder::new()
+                .name(format!("worker-{}", index))
+                .spawn(move || work(&shared));
+            if let Err(e) = spawned {
+                error!("Failed to start worker thread {}: {}", index, e);
+            }
+        }
+        info!("Worker pool started with {} threads", size);
+
+        Self { shared }
+    }
+
+    /// Run `job` on a worker, the result is awaited
+    pub fn spawn<T: Send + 'static>(
+        &self,
+        priority: WorkPriority,
+        job: impl FnOnce() -> T + Send + 'static,
+    ) -> impl Future<Output = Result<T, String>> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.submit(
+            priority,
+            Box::new(move || {
+                let _ = tx.send(job());
+            }),
+        );
+        async move { rx.await.map_err(|_| "Worker job failed".to_string()) }
+    }
+
+    /// Run `job` on a worker and block until it finished.
+    ///
+    /// Not to be called from a job: with every worker waiting, nothing would run it.
+    pub fn run<T: Send + 'static>(
+        &self,
+        priority: WorkPriority,
+        job: impl FnOnce() -> T + Send + 'static,
+    ) -> Result<T, String> {
+        let (tx, rx) = mpsc::channel();
+        self.submit(
+            priority,
+            Box::new(move || {
+                let _ = tx.send(job());
+            }),
+        );
+        rx.recv().map_err(|_| "Worker job failed".to_string())
+    }
+
+    fn submit(&self, priority: WorkPriority, job: Job) {
+        let mut queues = self.shared.lock();
+        match priority {
+            WorkPriority::Interactive => queues.interactive.push_back(job),
+            WorkPriority::Background => queues.background.push_back(job),
+        }
+        self.shared.changed.notify_all();
+    }
+}
+
+impl Drop for WorkerPool {
+    /// Workers finish the queued jobs, then exit
+    fn drop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.changed.notify_all();
+    }
+}
+
+/// Worker loop: interactive jobs first, background jobs while a worker stays free
+fn work(shared: &Shared) {
+    loop {
+        let (job, background) = {
+            let mut queues = shared.lock();
+            loop {
+                if let Some(job) = queues.interactive.pop_front() {
+                    break (job, false);
+                }
+                if queues.background_running + 1 < shared.size {
+                    if let Some(job) = queues.background.pop_front() {
+                        queues.background_running += 1;
+                        break (job, true);
+                    }
+                }
+                if queues.shutdown && queues.interactive.is_empty() && queues.background.is_empty()
+                {
+                    return;
+                }
+                queues = shared
+                    .changed
+                    .wait(queues)
+                    .unwrap_or_else(|e| e.into_inner());
+            }
+        };
+
+        // A panicking job drops its result sender, the caller gets an error
+        if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+            error!("Worker job panicked");
+        }
+
+        if background {
+            shared.lock().background_running -= 1;
+            shared.changed.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_interactive_job_runs_while_background_jobs_wait() {
+        let pool = WorkerPool::new(2);
+        let release = Arc::new((Mutex::new(false), Condvar::new()));
+
+        // Background jobs block until released: only one may run at a time
+        let started = Arc::new(Mutex::new(0));
+        let receivers: Vec<_> = (0..3)
+            .map(|_| {
+                let release = release.clone();
+                let started = started.clone();
+                let (tx, rx) = mpsc::channel();
+                pool.submit(
+                    WorkPriority::Background,
+                    Box::new(move || {
+                        *started.lock().unwrap() += 1;
+                        let (released, changed) = &*release;
+                        let mut released = released.lock().unwrap();
+                        while !*released {
+                            released = changed.wait(released).unwrap();
+                        }
+                        let _ = tx.send(());
+                    }),
+                );
+                rx
+            })
+            .collect();
+
+        // The other worker is kept free for it
+        assert_eq!(pool.run(WorkPriority::Interactive, || 42), Ok(42));
+        assert!(*started.lock().unwrap() <= 1);
+
+        *release.0.lock().unwrap() = true;
+        release.1.notify_all();
+        for rx in receivers {
+            rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_panicking_job_reports_an_error() {
+        let pool = WorkerPool::new(2);
+        let ran = Arc::new(AtomicBool::new(false));
+
+        assert!(pool
+            .run(WorkPriority::Interactive, || panic!("job failed"))
+            .is_err());
+
+        // The worker survived
+        let flag = ran.clone();
+        pool.run(WorkPriority::Background, move || {
+            flag.store(true, Ordering::SeqCst)
+        })
+        .unwrap();
+        assert!(ran.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_pool_size() {
+        assert!((MIN_WORKER_THREADS..=MAX_AUTO_WORKER_THREADS).contains(&pool_size(0)));
+        assert_eq!(pool_size(1), MIN_WORKER_THREADS);
+        assert_eq!(pool_size(6), 6);
+        assert_eq!(pool_size(100), MAX_WORKER_THREADS as usize);
+    }
+}
@@ -13,6 +13,54 @@ export const commands = {
 async getAppVersion() : Promise<string> {
     return await TAURI_INVOKE("get_app_version");
 },
+/**
+ * Current app state for windows opened mid-session (also emitted when a window loads)
+ */
+async getAppSnapshot() : Promise<StateSnapshot> {
+    return await TAURI_INVOKE("get_app_snapshot");
+},
+/**
+ * Get the appearance preferences (popup style, opacity, theme)
+ */
+async getUiPrefs() : Promise<UiPrefs> {
+    return await TAURI_INVOKE("get_ui_prefs");
+},
+/**
+ * Save the appearance preferences, all windows receive `UiPrefsChanged`
+ */
+async setUiPrefs(prefs: UiPrefs) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_ui_prefs", { prefs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Searchable index of the preferences (id, title, keywords, category) for the settings search
+ */
+async listSettingsMetadata() : Promise<SettingMetadata[]> {
+    return await TAURI_INVOKE("list_settings_metadata");
+},
+/**
+ * Ids of the settings locked by a managed (MDM) configuration, shown read-only
+ */
+async listManagedSettings() : Promise<string[]> {
+    return await TAURI_INVOKE("list_managed_settings");
+},
+/**
+ * Timings of the startup phases, including the tasks deferred after setup
+ */
+async getStartupReport() : Promise<StartupReport> {
+    return await TAURI_INVOKE("get_startup_report");
+},
+/**
+ * Version, platform, startup timings, local inference resource usage, keyboard tap
+ * statistics and recent state transitions, for bug reports
+ */
+async getDiagnosticsReport() : Promise<DiagnosticsReport> {
+    return await TAURI_INVOKE("get_diagnostics_report");
+},
 async checkAccessibilityPermission() : Promise<boolean> {
     return await TAURI_INVOKE("check_accessibility_permission");
 },
@@ -52,416 +100,1436 @@ async loadAppConfig() : Promise<Result<AppConfig, string>> {
 /**
  * Save app configuration (general-purpose command that can update multiple fields)
  */
-async saveAppConfig(activeProvider: string | null, recordingTrigger: RecordingTrigger | null, postProcessEnabled: boolean | null, postProcessModel: string | null, postProcessPrompt: string | null, minSpeechDurationMs: number | null) : Promise<Result<null, string>> {
+async saveAppConfig(activeProvider: string | null, recordingTrigger: RecordingTrigger | null, postProcessEnabled: boolean | null, postProcessModel: string | null, postProcessPrompt: string | null, postProcessParams: PostProcessParams | null, minSpeechDurationMs: number | null) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_app_config", { activeProvider, recordingTrigger, postProcessEnabled, postProcessModel, postProcessPrompt, minSpeechDurationMs }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_app_config", { activeProvider, recordingTrigger, postProcessEnabled, postProcessModel, postProcessPrompt, postProcessParams, minSpeechDurationMs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Get the currently active provider
+ * Set consent for reading the frontmost window (title and optionally selected text)
+ * into the post-processing prompt
  */
-async getCurrentProvider() : Promise<Result<Provider | null, string>> {
+async setDocumentContextConsent(enabled: boolean, includeSelection: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_current_provider") };
+    return { status: "ok", data: await TAURI_INVOKE("set_document_context_consent", { enabled, includeSelection }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Set the currently active provider
+ * Enable or disable writing spoken numbers, dates and units in written form
  */
-async setCurrentProvider(provider: string) : Promise<Result<null, string>> {
+async setFormatNormalization(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_current_provider", { provider }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_format_normalization", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Clear the currently active provider (set to None)
+ * Set which apps get spoken formatting cues written as Markdown
  */
-async clearCurrentProvider() : Promise<Result<null, string>> {
+async setMarkdownMode(markdownMode: MarkdownModeConfig) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("clear_current_provider") };
+    return { status: "ok", data: await TAURI_INVOKE("set_markdown_mode", { markdownMode }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async loadOpenaiConfig() : Promise<Result<OpenAIConfigStatus | null, string>> {
+/**
+ * Set the profanity and sensitive data filter of pasted dictations
+ */
+async setRedactionConfig(redaction: RedactionConfig) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("load_openai_config") };
+    return { status: "ok", data: await TAURI_INVOKE("set_redaction_config", { redaction }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveOpenaiConfig(apiKey: string) : Promise<Result<null, string>> {
+/**
+ * Set what happens when the default input device changes while recording
+ */
+async setInputDeviceChangePolicy(policy: InputDeviceChangePolicy) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_openai_config", { apiKey }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_input_device_change_policy", { policy }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteOpenaiConfig() : Promise<Result<null, string>> {
+/**
+ * Set what happens to a running recording when the Mac sleeps or the screen locks
+ */
+async setSystemSleepPolicy(policy: SystemSleepPolicy) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_openai_config") };
+    return { status: "ok", data: await TAURI_INVOKE("set_system_sleep_policy", { policy }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async testOpenaiConfig(apiKey: string) : Promise<Result<boolean, string>> {
+/**
+ * Set the event tap used by the keyboard listener (applied on next launch)
+ */
+async setKeyboardTapConfig(tap: KeyboardTapConfig) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("test_openai_config", { apiKey }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_keyboard_tap_config", { tap }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async loadAzureOpenaiConfig() : Promise<Result<AzureOpenAIConfigStatus | null, string>> {
+/**
+ * Set the number of worker threads, 0 = automatic (applied on next launch)
+ */
+async setWorkerThreads(threads: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("load_azure_openai_config") };
+    return { status: "ok", data: await TAURI_INVOKE("set_worker_threads", { threads }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveAzureOpenaiConfig(apiKey: string, endpoint: string) : Promise<Result<null, string>> {
+/**
+ * List the input devices that can be used as loopback (system audio) source
+ */
+async listInputDevices() : Promise<string[]> {
+    return await TAURI_INVOKE("list_input_devices");
+},
+/**
+ * Mix system audio from `device_name` into recordings, `None` to record the microphone only
+ */
+async setLoopbackDevice(deviceName: string | null) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_azure_openai_config", { apiKey, endpoint }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_loopback_device", { deviceName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteAzureOpenaiConfig() : Promise<Result<null, string>> {
+/**
+ * Set how often recordings are flushed to disk while recording
+ */
+async setRecordingFlushPolicy(policy: FlushPolicy) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_azure_openai_config") };
+    return { status: "ok", data: await TAURI_INVOKE("set_recording_flush_policy", { policy }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async testAzureOpenaiConfig(apiKey: string, endpoint: string) : Promise<Result<boolean, string>> {
+/**
+ * Set the most audio level updates per second sent to the recording popup
+ * (0 = every audio buffer), applied to the next recording
+ */
+async setAudioLevelMaxHz(maxHz: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("test_azure_openai_config", { apiKey, endpoint }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_audio_level_max_hz", { maxHz }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Get list of all available models with their current status
- */
-async getAvailableModels() : Promise<ModelInfo[]> {
-    return await TAURI_INVOKE("get_available_models");
-},
-/**
- * Start downloading a model
+ * Set after how many seconds a recording is stopped and transcribed (0 = only at the
+ * transcription file limit), applied to the next tick of the running recording
  */
-async downloadModel(modelName: string) : Promise<Result<null, string>> {
+async setMaxRecordingSecs(maxSecs: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("download_model", { modelName }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_max_recording_secs", { maxSecs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Cancel an ongoing model download
+ * Set the proxy of outbound HTTP requests. `password` replaces the stored proxy
+ * password (`None` keeps it), which is deleted when there is no username.
  */
-async cancelModelDownload(modelName: string) : Promise<Result<null, string>> {
+async setProxyConfig(proxy: ProxyConfig, password: string | null) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cancel_model_download", { modelName }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_proxy_config", { proxy, password }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Delete a downloaded model
+ * Set the custom root CA and pinned certificates of outbound HTTPS requests.
+ * Every certificate file must exist and contain PEM certificates.
  */
-async deleteModel(modelName: string) : Promise<Result<null, string>> {
+async setTlsConfig(tls: TlsConfig) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_model", { modelName }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_tls_config", { tls }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Load a model into memory for transcription
+ * Set the push of the recording state over MQTT or a local WebSocket.
+ * `mqtt_password` replaces the stored broker password (`None` keeps it), which is
+ * deleted when there is no username.
  */
-async loadModel(modelName: string) : Promise<Result<null, string>> {
+async setStatePushConfig(statePush: StatePushConfig, mqttPassword: string | null) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("load_model", { modelName }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_state_push_config", { statePush, mqttPassword }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Unload the currently loaded model (frees memory)
- */
-async unloadModel() : Promise<void> {
-    await TAURI_INVOKE("unload_model");
-},
-/**
- * Get the name of the currently loaded model
- */
-async getLoadedModel() : Promise<string | null> {
-    return await TAURI_INVOKE("get_loaded_model");
-},
-/**
- * Load local model configuration
+ * Enable or disable the audit log of outbound API requests
  */
-async loadLocalModelConfig() : Promise<Result<LocalModelConfig | null, string>> {
+async setAuditLogConfig(auditLog: AuditLogConfig) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("load_local_model_config") };
+    return { status: "ok", data: await TAURI_INVOKE("set_audit_log_config", { auditLog }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Save local model configuration (selected model)
+ * Start a new audit log file, returning the path of the previous one (if any)
  */
-async saveLocalModelConfig(modelName: string) : Promise<Result<null, string>> {
+async rotateAuditLog() : Promise<Result<string | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_local_model_config", { modelName }) };
+    return { status: "ok", data: await TAURI_INVOKE("rotate_audit_log") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Delete local model configuration
+ * Export all audit log files, oldest first, into a single file at `destination`
  */
-async deleteLocalModelConfig() : Promise<Result<null, string>> {
+async exportAuditLog(destination: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_local_model_config") };
+    return { status: "ok", data: await TAURI_INVOKE("export_audit_log", { destination }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async stopRecording() : Promise<Result<null, string>> {
+/**
+ * Get the currently active provider
+ */
+async getCurrentProvider() : Promise<Result<Provider | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("stop_recording") };
+    return { status: "ok", data: await TAURI_INVOKE("get_current_provider") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async cancelRecording() : Promise<Result<null, string>> {
+/**
+ * Set the currently active provider
+ */
+async setCurrentProvider(provider: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cancel_recording") };
+    return { status: "ok", data: await TAURI_INVOKE("set_current_provider", { provider }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async retryTranscription() : Promise<Result<null, string>> {
+/**
+ * Clear the currently active provider (set to None)
+ */
+async clearCurrentProvider() : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("retry_transcription") };
+    return { status: "ok", data: await TAURI_INVOKE("clear_current_provider") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dismissError() : Promise<Result<null, string>> {
+/**
+ * Set the providers tried, in order, when the active provider fails
+ */
+async setFallbackProviders(providers: Provider[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("dismiss_error") };
+    return { status: "ok", data: await TAURI_INVOKE("set_fallback_providers", { providers }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async resizePopupForError() : Promise<Result<null, string>> {
+/**
+ * Set the model, language, prompt and temperature of a provider's transcription requests
+ */
+async setTranscriptionOptions(provider: Provider, options: TranscriptionOptions) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("resize_popup_for_error") };
+    return { status: "ok", data: await TAURI_INVOKE("set_transcription_options", { provider, options }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async registerAudioLevelChannel(channel: TAURI_CHANNEL<number>) : Promise<Result<null, string>> {
+/**
+ * Recent successes and failures of each provider, and which one served the last request
+ */
+async getProviderHealth() : Promise<ProviderHealthReport[]> {
+    return await TAURI_INVOKE("get_provider_health");
+},
+/**
+ * Try to read every provider's keychain item, so the UI can tell a locked or
+ * denied keychain apart from a missing API key
+ */
+async testKeychainAccess() : Promise<KeychainAccessStatus[]> {
+    return await TAURI_INVOKE("test_keychain_access");
+},
+async loadOpenaiConfig() : Promise<Result<OpenAIConfigStatus | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("register_audio_level_channel", { channel }) };
+    return { status: "ok", data: await TAURI_INVOKE("load_openai_config") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async restartApp() : Promise<void> {
-    await TAURI_INVOKE("restart_app");
-},
-async loadOnboardingConfig() : Promise<Result<OnboardingConfig, string>> {
+async saveOpenaiConfig(apiKey: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("load_onboarding_config") };
+    return { status: "ok", data: await TAURI_INVOKE("save_openai_config", { apiKey }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveOnboardingStep(step: OnboardingStep) : Promise<Result<null, string>> {
+async deleteOpenaiConfig() : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_onboarding_step", { step }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_openai_config") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async finishOnboarding() : Promise<Result<null, string>> {
+async testOpenaiConfig(apiKey: string) : Promise<Result<boolean, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("finish_onboarding") };
+    return { status: "ok", data: await TAURI_INVOKE("test_openai_config", { apiKey }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async skipOnboarding() : Promise<Result<null, string>> {
+async loadAzureOpenaiConfig() : Promise<Result<AzureOpenAIConfigStatus | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("skip_onboarding") };
+    return { status: "ok", data: await TAURI_INVOKE("load_azure_openai_config") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setPendingRestart(pending: boolean) : Promise<Result<null, string>> {
+async saveAzureOpenaiConfig(apiKey: string, endpoint: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_pending_restart", { pending }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_azure_openai_config", { apiKey, endpoint }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async restartOnboarding() : Promise<Result<null, string>> {
+async deleteAzureOpenaiConfig() : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("restart_onboarding") };
+    return { status: "ok", data: await TAURI_INVOKE("delete_azure_openai_config") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async loadShortcutsConfig() : Promise<Result<ShortcutsConfig, string>> {
+async testAzureOpenaiConfig(apiKey: string, endpoint: string) : Promise<Result<boolean, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("load_shortcuts_config") };
+    return { status: "ok", data: await TAURI_INVOKE("test_azure_openai_config", { apiKey, endpoint }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveShortcutsConfig(config: ShortcutsConfig) : Promise<Result<null, string>> {
+/**
+ * Get list of all available models with their current status
+ */
+async getAvailableModels() : Promise<ModelInfo[]> {
+    return await TAURI_INVOKE("get_available_models");
+},
+/**
+ * Start downloading a model
+ */
+async downloadModel(modelName: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_shortcuts_config", { config }) };
+    return { status: "ok", data: await TAURI_INVOKE("download_model", { modelName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async resetShortcutsConfig() : Promise<Result<ShortcutsConfig, string>> {
+/**
+ * Cancel an ongoing model download
+ */
+async cancelModelDownload(modelName: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("reset_shortcuts_config") };
+    return { status: "ok", data: await TAURI_INVOKE("cancel_model_download", { modelName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async startKeyCapture() : Promise<Result<null, string>> {
+/**
+ * Delete a downloaded model, to the Trash if `deleteToTrash` is set
+ */
+async deleteModel(modelName: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("start_key_capture") };
+    return { status: "ok", data: await TAURI_INVOKE("delete_model", { modelName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async stopKeyCapture() : Promise<Result<null, string>> {
+/**
+ * Check a downloaded model's files against their checksums,
+ * re-downloading only the corrupted ones if `repair` is set
+ */
+async verifyModel(modelName: string, repair: boolean) : Promise<Result<ModelVerification, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("stop_key_capture") };
+    return { status: "ok", data: await TAURI_INVOKE("verify_model", { modelName, repair }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Enable autostart on system boot
+ * Download the Core ML encoder of a Whisper model (Apple Silicon only)
  */
-async enableAutostart() : Promise<Result<null, string>> {
+async downloadCoremlEncoder(modelName: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("enable_autostart") };
+    return { status: "ok", data: await TAURI_INVOKE("download_coreml_encoder", { modelName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Disable autostart on system boot
+ * Delete the Core ML encoder of a model (falls back to Metal)
  */
-async disableAutostart() : Promise<Result<null, string>> {
+async deleteCoremlEncoder(modelName: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("disable_autostart") };
+    return { status: "ok", data: await TAURI_INVOKE("delete_coreml_encoder", { modelName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Check if autostart is enabled
+ * Get the progress of the startup migration to the per-model directory structure
  */
-async isAutostartEnabled() : Promise<Result<boolean, string>> {
+async getMigrationStatus() : Promise<MigrationStatus> {
+    return await TAURI_INVOKE("get_migration_status");
+},
+/**
+ * Get the directory the models are stored in
+ */
+async getModelsDirectory() : Promise<string> {
+    return await TAURI_INVOKE("get_models_directory");
+},
+/**
+ * Get the disk space used by each downloaded model, including interrupted downloads
+ */
+async getModelsDiskUsage() : Promise<ModelsDiskUsage> {
+    return await TAURI_INVOKE("get_models_disk_usage");
+},
+/**
+ * Move the downloaded models to `path` (e.g. an external drive), new models are
+ * downloaded there too. `path` must be empty or not exist yet.
+ * Returns the new models directory.
+ */
+async setModelsDirectory(path: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("is_autostart_enabled") };
+    return { status: "ok", data: await TAURI_INVOKE("set_models_directory", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Mark that initial autostart setup has been completed
- * This is called after enabling autostart on first launch
+ * Load a model into memory for transcription
  */
-async markAutostartSetupDone() : Promise<Result<null, string>> {
+async loadModel(modelName: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("mark_autostart_setup_done") };
+    return { status: "ok", data: await TAURI_INVOKE("load_model", { modelName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Check if initial autostart setup has been completed
+ * Unload the currently loaded model (frees memory)
  */
-async isAutostartSetupDone() : Promise<Result<boolean, string>> {
+async unloadModel() : Promise<void> {
+    await TAURI_INVOKE("unload_model");
+},
+/**
+ * Get the name of the currently loaded model
+ */
+async getLoadedModel() : Promise<string | null> {
+    return await TAURI_INVOKE("get_loaded_model");
+},
+/**
+ * Get the memory, swap and GPU usage of the last model load and local transcription
+ */
+async getResourceReport() : Promise<ResourceReport> {
+    return await TAURI_INVOKE("get_resource_report");
+},
+/**
+ * Load local model configuration
+ */
+async loadLocalModelConfig() : Promise<Result<LocalModelConfig | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("is_autostart_setup_done") };
+    return { status: "ok", data: await TAURI_INVOKE("load_local_model_config") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
-}
-}
+},
+/**
+ * Save local model configuration (selected model)
+ */
+async saveLocalModelConfig(modelName: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_local_model_config", { modelName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Save the transcription options of a local model (language hint, timestamps, beam size)
+ */
+async setLocalModelOptions(modelName: string, options: LocalModelOptions) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_local_model_options", { modelName, options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Set the mirrors model files are downloaded from
+ */
+async setModelDownloadMirror(mirror: ModelMirrorConfig) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_model_download_mirror", { mirror }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Move deleted models to the Trash instead of removing them for good
+ */
+async setDeleteModelsToTrash(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_delete_models_to_trash", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Save the Hugging Face token sent with model downloads, `None` to delete it
+ */
+async setHuggingFaceToken(token: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_hugging_face_token", { token }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check that every model source is reachable through the configured mirror
+ */
+async checkModelMirror() : Promise<Result<MirrorSourceHealth[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_model_mirror") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete local model configuration
+ * 
+ * A relocated models directory is kept, the downloaded models are still there.
+ */
+async deleteLocalModelConfig() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_local_model_config") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async stopRecording() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_recording") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop the current recording and wait until it is transcribed and pasted. Resolves
+ * with the final state: `stopped` with the text, `cancelled`, or `error` with the
+ * error details.
+ */
+async stopRecordingAndWait() : Promise<Result<RecordingStateChanged, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_recording_and_wait") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Cancel the current recording, or abort the local transcription in progress
+ * 
+ * The controller is busy while transcribing, so a local transcription is aborted
+ * directly through the model loader. API transcriptions can't be aborted.
+ */
+async cancelRecording() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_recording") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lock the current recording into hands-free mode (same as pressing the hands-free shortcut)
+ */
+async lockRecording() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lock_recording") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Start a hands-free dictation into a new note in `target`, instead of pasting
+ * 
+ * Stops like any hands-free recording (Fn or the stop button). The note is titled
+ * after the first sentence of the text. Ignored while a recording is in progress.
+ */
+async dictateToNewNote(target: NoteTarget) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("dictate_to_new_note", { target }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Current recording state, for windows that open after the last `RecordingStateChanged`
+ */
+async getRecordingState() : Promise<RecordingState> {
+    return await TAURI_INVOKE("get_recording_state");
+},
+/**
+ * The last recording state transitions with timestamps, oldest first, for bug reports
+ */
+async dumpStateHistory() : Promise<StateHistoryEntry[]> {
+    return await TAURI_INVOKE("dump_state_history");
+},
+async retryTranscription() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("retry_transcription") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async dismissError() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("dismiss_error") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Discard a failed recording from the error popup
+ * 
+ * Unlike `dismiss_error`, the audio kept for retry is deleted by the recording
+ * controller, in order with the other recording commands. It refuses to run while a
+ * recording or transcription is still in progress.
+ */
+async discardError() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("discard_error") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resizePopupForError() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resize_popup_for_error") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async registerAudioLevelChannel(channel: TAURI_CHANNEL<number>) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("register_audio_level_channel", { channel }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Whether raw (verbatim) dictation mode is enabled
+ */
+async getRawMode() : Promise<boolean> {
+    return await TAURI_INVOKE("get_raw_mode");
+},
+/**
+ * Enable or disable raw (verbatim) dictation mode: no post-processing, no templates
+ */
+async setRawMode(enabled: boolean) : Promise<void> {
+    await TAURI_INVOKE("set_raw_mode", { enabled });
+},
+/**
+ * Put back clipboard content replaced by a paste (one paste further back per call).
+ * Returns whether anything was restored.
+ */
+async restorePreviousClipboard() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_previous_clipboard") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Transcribe existing audio files (wav/mp3/m4a) with the active provider
+ * 
+ * Runs in the background and writes `.txt`/`.srt` files next to each source file.
+ * Progress is reported through `FileTranscriptionStateChanged` events.
+ */
+async transcribeFiles(paths: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("transcribe_files", { paths }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Transcribe the audio on the clipboard (a voice memo file, a copied path, or audio
+ * copied from Messages or Voice Memos) with the active provider
+ * 
+ * Returns the text, and also inserts it into the focused app when `paste` is set.
+ */
+async transcribeClipboardAudio(paste: boolean) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("transcribe_clipboard_audio", { paste }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Open the floating captions window and start transcribing the microphone
+ * 
+ * Caption lines arrive as `CaptionLine` events, the end of the session as
+ * `LiveCaptionsStateChanged`. Nothing is pasted.
+ */
+async startLiveCaptions() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_live_captions") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop the live captions and close their window
+ */
+async stopLiveCaptions() : Promise<void> {
+    await TAURI_INVOKE("stop_live_captions");
+},
+/**
+ * Whether live captions are running
+ */
+async isLiveCaptionsRunning() : Promise<boolean> {
+    return await TAURI_INVOKE("is_live_captions_running");
+},
+/**
+ * Start recording a meeting, transcribed in segments in the background
+ * 
+ * Progress arrives as `MeetingStateChanged` events.
+ */
+async startMeeting() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_meeting") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop the meeting; its summary is generated in the background
+ */
+async stopMeeting() : Promise<void> {
+    await TAURI_INVOKE("stop_meeting");
+},
+/**
+ * Whether a meeting is being recorded
+ */
+async isMeetingRunning() : Promise<boolean> {
+    return await TAURI_INVOKE("is_meeting_running");
+},
+/**
+ * Save the summary and transcript of the last meeting as a Markdown file at `path`
+ */
+async saveMeetingNotes(path: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_meeting_notes", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Label meeting transcripts with Speaker 1/Speaker 2, applied to the next meeting.
+ * Requires the speaker model (`download_speaker_model`) and a build with diarization.
+ */
+async setMeetingDiarization(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_meeting_diarization", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Download the speaker model of meeting speaker labels (~26MB)
+ * 
+ * Progress arrives as `ModelDownloadStateChanged` events for "speaker-embedding".
+ */
+async downloadSpeakerModel() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_speaker_model") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Export a file transcribed with `transcribe_files` as subtitles next to the source file
+ * 
+ * Returns the path of the written subtitle file.
+ */
+async exportFileTranscription(filePath: string, format: SubtitleFormat) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_file_transcription", { filePath, format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Export the last dictation as subtitles (a single cue spanning the speech duration)
+ */
+async exportLastTranscription(format: SubtitleFormat, outputPath: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_last_transcription", { format, outputPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the number of transcriptions per detected language
+ */
+async getLanguageStats() : Promise<LanguageStats> {
+    return await TAURI_INVOKE("get_language_stats");
+},
+/**
+ * Clear the per-language statistics
+ */
+async resetLanguageStats() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reset_language_stats") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the language pinned for this session (`null` = auto-detect)
+ */
+async getSessionLanguage() : Promise<string | null> {
+    return await TAURI_INVOKE("get_session_language");
+},
+/**
+ * Pin a language for this session (e.g. "en" or "english"), `null` to auto-detect again
+ */
+async setSessionLanguage(language: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_session_language", { language }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the language remembered for each app, keyed by bundle identifier
+ */
+async getAppLanguages() : Promise<AppLanguages> {
+    return await TAURI_INVOKE("get_app_languages");
+},
+/**
+ * Forget the language remembered for an app, so its next dictation is auto-detected
+ */
+async forgetAppLanguage(appId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("forget_app_language", { appId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Forget the languages remembered for all apps
+ */
+async clearAppLanguages() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_app_languages") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Set the post-processing prompt for transcriptions in a language (e.g. "ja" or
+ * "japanese"), `null` to use the default prompt for it again
+ */
+async setLanguagePostProcessPrompt(language: string, prompt: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_language_post_process_prompt", { language, prompt }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the words dictated today and the progress towards the daily goal
+ */
+async getGoalProgress() : Promise<GoalProgress> {
+    return await TAURI_INVOKE("get_goal_progress");
+},
+/**
+ * Set the daily dictated-words goal, `null` (or 0) to turn it off
+ */
+async setDailyWordGoal(goal: number | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_daily_word_goal", { goal }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get all dictation templates and the active one
+ */
+async listTemplates() : Promise<TemplatesConfig> {
+    return await TAURI_INVOKE("list_templates");
+},
+/**
+ * Create a template, returns it with its generated id
+ */
+async createTemplate(name: string, keyword: string | null, body: string) : Promise<Result<DictationTemplate, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_template", { name, keyword, body }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replace an existing template (matched by id)
+ */
+async updateTemplate(template: DictationTemplate) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_template", { template }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete a template, deactivating it if it was active
+ */
+async deleteTemplate(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_template", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Set the template applied to every dictation (`null` = none)
+ */
+async setActiveTemplate(id: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_active_template", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the saved profiles and the active one
+ */
+async listProfiles() : Promise<ProfilesConfig> {
+    return await TAURI_INVOKE("list_profiles");
+},
+/**
+ * Save the current settings as a profile (replacing one of the same name) and make
+ * it the active profile
+ */
+async saveProfile(name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_profile", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Switch to another profile, the current settings are kept in the active one
+ */
+async switchProfile(name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("switch_profile", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete a profile, the current settings stay as they are
+ */
+async deleteProfile(name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_profile", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the time-of-day automation rules
+ */
+async listAutomationRules() : Promise<AutomationConfig> {
+    return await TAURI_INVOKE("list_automation_rules");
+},
+/**
+ * Create an automation rule, returns it with its generated id
+ */
+async createAutomationRule(rule: AutomationRule) : Promise<Result<AutomationRule, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_automation_rule", { rule }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replace an existing automation rule (matched by id)
+ */
+async updateAutomationRule(rule: AutomationRule) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_automation_rule", { rule }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete an automation rule
+ */
+async deleteAutomationRule(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_automation_rule", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the settings of the output sinks (journal file, webhook)
+ */
+async getOutputSinksConfig() : Promise<OutputSinksConfig> {
+    return await TAURI_INVOKE("get_output_sinks_config");
+},
+/**
+ * Save the settings of the output sinks, applied from the next dictation.
+ * `webhook_secret` replaces the stored signing secret (`None` keeps it, empty
+ * deletes it); it is deleted when the webhook has no URL.
+ */
+async saveOutputSinksConfig(config: OutputSinksConfig, webhookSecret: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_output_sinks_config", { config, webhookSecret }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get all voice snippets
+ */
+async listSnippets() : Promise<SnippetsConfig> {
+    return await TAURI_INVOKE("list_snippets");
+},
+/**
+ * Create a voice snippet, returns it with its generated id
+ */
+async createSnippet(trigger: string, text: string) : Promise<Result<VoiceSnippet, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_snippet", { trigger, text }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replace an existing voice snippet (matched by id)
+ */
+async updateSnippet(snippet: VoiceSnippet) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_snippet", { snippet }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete a voice snippet
+ */
+async deleteSnippet(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_snippet", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Learn from the user's edited version of the last dictation (review flow)
+ * 
+ * Only accepted within two minutes of the dictation. Returns the corrections that
+ * this edit made frequent enough to be added to the post-processing prompt.
+ */
+async submitDictationCorrection(correctedText: string) : Promise<Result<LearnedCorrection[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("submit_dictation_correction", { correctedText }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * List the recorded corrections of dictated text, most recent first
+ */
+async getLearnedCorrections() : Promise<LearnedCorrection[]> {
+    return await TAURI_INVOKE("get_learned_corrections");
+},
+/**
+ * Forget the corrections of a misrecognized word or phrase
+ */
+async forgetLearnedCorrection(original: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("forget_learned_correction", { original }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restartApp() : Promise<void> {
+    await TAURI_INVOKE("restart_app");
+},
+async loadOnboardingConfig() : Promise<Result<OnboardingConfig, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("load_onboarding_config") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveOnboardingStep(step: OnboardingStep) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_onboarding_step", { step }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async finishOnboarding() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("finish_onboarding") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async skipOnboarding() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("skip_onboarding") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setPendingRestart(pending: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_pending_restart", { pending }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restartOnboarding() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restart_onboarding") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async loadShortcutsConfig() : Promise<Result<ShortcutsConfig, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("load_shortcuts_config") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveShortcutsConfig(config: ShortcutsConfig) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_shortcuts_config", { config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resetShortcutsConfig() : Promise<Result<ShortcutsConfig, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reset_shortcuts_config") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Put back the Globe key behavior the user had before Dictara changed it.
+ * Returns `false` when it wasn't changed. With a shortcut still using Fn, the
+ * next launch or shortcut change sets it to "Do Nothing" again.
+ */
+async restoreGlobeKeyBehavior() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_globe_key_behavior") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Whether an app currently has macOS Secure Input enabled (shortcuts don't work then)
+ */
+async getSecureInputState() : Promise<SecureInputChanged> {
+    return await TAURI_INVOKE("get_secure_input_state");
+},
+/**
+ * Whether the keyboard listener is running. False while it waits to be restarted
+ * after a failure, or when it was never started (no accessibility permission).
+ */
+async isListenerHealthy() : Promise<boolean> {
+    return await TAURI_INVOKE("is_listener_healthy");
+},
+/**
+ * Event counters and callback latency of the keyboard tap since launch (or the last reset)
+ */
+async getKeyboardGrabStats() : Promise<KeyboardGrabStats> {
+    return await TAURI_INVOKE("get_keyboard_grab_stats");
+},
+/**
+ * Reset the keyboard tap statistics, e.g. before reproducing input lag
+ */
+async resetKeyboardGrabStats() : Promise<void> {
+    await TAURI_INVOKE("reset_keyboard_grab_stats");
+},
+async startKeyCapture() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_key_capture") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async stopKeyCapture() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_key_capture") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Enable autostart on system boot
+ */
+async enableAutostart() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("enable_autostart") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Disable autostart on system boot
+ */
+async disableAutostart() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("disable_autostart") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check if autostart is enabled
+ */
+async isAutostartEnabled() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("is_autostart_enabled") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Whether the app launches at login, as currently registered with the system
+ * (reflects changes made in System Settings)
+ */
+async getAutostartEnabled() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_autostart_enabled") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Turn launch at login on or off, returning the resulting system state
+ */
+async setAutostartEnabled(enabled: boolean) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_autostart_enabled", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Mark that initial autostart setup has been completed
+ * This is called after enabling autostart on first launch
+ */
+async markAutostartSetupDone() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mark_autostart_setup_done") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check if initial autostart setup has been completed
+ */
+async isAutostartSetupDone() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("is_autostart_setup_done") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
 
 /** user-defined events **/
 
 
 export const events = __makeEvents__<{
+automationRuleActivated: AutomationRuleActivated,
+captionLine: CaptionLine,
+configChanged: ConfigChanged,
+fileTranscriptionStateChanged: FileTranscriptionStateChanged,
+inputDeviceChanged: InputDeviceChanged,
 keyCaptureEvent: KeyCaptureEvent,
+keyListenerHealthChanged: KeyListenerHealthChanged,
+keychainAccessFailed: KeychainAccessFailed,
+liveCaptionsStateChanged: LiveCaptionsStateChanged,
+localInferenceResourceUsage: LocalInferenceResourceUsage,
+meetingStateChanged: MeetingStateChanged,
 modelDownloadStateChanged: ModelDownloadStateChanged,
 modelLoadingStateChanged: ModelLoadingStateChanged,
-recordingStateChanged: RecordingStateChanged
+modelMigrationStateChanged: ModelMigrationStateChanged,
+rawModeChanged: RawModeChanged,
+recordingStateChanged: RecordingStateChanged,
+recordingTick: RecordingTick,
+secureInputChanged: SecureInputChanged,
+stateSnapshot: StateSnapshot,
+textInserted: TextInserted,
+transcriptionHeld: TranscriptionHeld,
+transcriptionProgress: TranscriptionProgress,
+transcriptionResult: TranscriptionResult,
+uiPrefsChanged: UiPrefsChanged,
+wordGoalReached: WordGoalReached
 }>({
+automationRuleActivated: "automation-rule-activated",
+captionLine: "caption-line",
+configChanged: "config-changed",
+fileTranscriptionStateChanged: "file-transcription-state-changed",
+inputDeviceChanged: "input-device-changed",
 keyCaptureEvent: "key-capture-event",
+keyListenerHealthChanged: "key-listener-health-changed",
+keychainAccessFailed: "keychain-access-failed",
+liveCaptionsStateChanged: "live-captions-state-changed",
+localInferenceResourceUsage: "local-inference-resource-usage",
+meetingStateChanged: "meeting-state-changed",
 modelDownloadStateChanged: "model-download-state-changed",
 modelLoadingStateChanged: "model-loading-state-changed",
-recordingStateChanged: "recording-state-changed"
+modelMigrationStateChanged: "model-migration-state-changed",
+rawModeChanged: "raw-mode-changed",
+recordingStateChanged: "recording-state-changed",
+recordingTick: "recording-tick",
+secureInputChanged: "secure-input-changed",
+stateSnapshot: "state-snapshot",
+textInserted: "text-inserted",
+transcriptionHeld: "transcription-held",
+transcriptionProgress: "transcription-progress",
+transcriptionResult: "transcription-result",
+uiPrefsChanged: "ui-prefs-changed",
+wordGoalReached: "word-goal-reached"
 })
 
 /** user-defined constants **/
@@ -471,130 +1539,1074 @@ recordingStateChanged: "recording-state-changed"
 /** user-defined types **/
 
 /**
- * App configuration (stored locally)
+ * App configuration (stored locally)
+ */
+export type AppConfig = { 
+/**
+ * Currently active provider (only one can be active)
+ */
+activeProvider: Provider | null; 
+/**
+ * Key used to trigger recording (default: Fn)
+ */
+recordingTrigger?: RecordingTrigger; 
+/**
+ * Whether autostart has been set up on first launch
+ * This prevents re-enabling autostart after user manually disables it
+ */
+autostartInitialSetupDone?: boolean; 
+/**
+ * Last known launch-at-login state, synced with the system whenever it is read
+ */
+autostartEnabled?: boolean; 
+/**
+ * Whether to run LLM post-processing after transcription
+ */
+postProcessEnabled?: boolean; 
+/**
+ * OpenAI model used for transcription post-processing
+ */
+postProcessModel?: string; 
+/**
+ * Prompt used for transcription post-processing
+ */
+postProcessPrompt?: string; 
+/**
+ * Prompts by ISO-639-1 language code (e.g. "ja"), used instead of `post_process_prompt`
+ * when the transcription is in that language
+ */
+postProcessPromptsByLanguage?: Partial<{ [key in string]: string }>; 
+/**
+ * Timeout and sampling parameters of the post-processing request
+ */
+postProcessParams?: PostProcessParams; 
+/**
+ * Minimum speech duration required before running transcription (milliseconds)
+ */
+minSpeechDurationMs?: number; 
+/**
+ * Marker added to the temporary clipboard content so clipboard managers ignore it
+ */
+clipboardMarker?: ClipboardMarker; 
+/**
+ * Write the recording state to `<app cache>/status.json` for external status bars
+ */
+statusFileEnabled?: boolean; 
+/**
+ * Post the recording state as a macOS distributed notification
+ */
+statusNotificationEnabled?: boolean; 
+/**
+ * Publish the recording state over MQTT or a local WebSocket
+ */
+statePush?: StatePushConfig; 
+/**
+ * Add the frontmost window title (read via Accessibility) to the post-processing prompt
+ */
+documentContextEnabled?: boolean; 
+/**
+ * Also add the selected text of the frontmost window (requires `document_context_enabled`)
+ */
+documentContextIncludeSelection?: boolean; 
+/**
+ * Reaction to a default input device change (or unplugged microphone) while recording
+ */
+inputDeviceChangePolicy?: InputDeviceChangePolicy; 
+/**
+ * Input device capturing system audio (e.g. BlackHole or an aggregate device),
+ * mixed with the microphone for call transcription. `None` = microphone only.
+ */
+loopbackDevice?: string | null; 
+/**
+ * How often the recording file is flushed to disk, so a crash loses at most a few seconds
+ */
+recordingFlushPolicy?: FlushPolicy; 
+/**
+ * Most audio level updates per second sent to the popup (peak of each window),
+ * 0 = one per audio buffer
+ */
+audioLevelMaxHz?: number; 
+/**
+ * Recordings are stopped and transcribed after this many seconds, 0 = only at the
+ * 25MB transcription file limit (about 13 minutes)
+ */
+maxRecordingSecs?: number; 
+/**
+ * Reaction to system sleep or a screen lock while recording
+ */
+systemSleepPolicy?: SystemSleepPolicy; 
+/**
+ * Event tap used by the keyboard listener (requires a restart)
+ */
+keyboardTap?: KeyboardTapConfig; 
+/**
+ * Providers tried in order when the active provider fails with a retryable error
+ */
+fallbackProviders?: Provider[]; 
+/**
+ * Local log of outbound API requests (metadata only unless text is included)
+ */
+auditLog?: AuditLogConfig; 
+/**
+ * Daily dictated-words target, `None` = no goal
+ */
+dailyWordGoal?: number | null; 
+/**
+ * Don't paste if the frontmost app changed since the recording started,
+ * post a notification instead
+ */
+holdPasteOnFocusChange?: boolean; 
+/**
+ * Bounds of the wait before the clipboard is restored after a paste
+ */
+pasteTiming?: PasteTiming; 
+/**
+ * Proxy of outbound HTTP requests
+ */
+proxy?: ProxyConfig; 
+/**
+ * Custom root CA and pinned certificates of outbound HTTPS requests
+ */
+tls?: TlsConfig; 
+/**
+ * Label meeting transcripts with Speaker 1/Speaker 2 (requires the speaker model and
+ * a build with the `diarization` feature)
+ */
+meetingDiarization?: boolean; 
+/**
+ * Write spoken numbers, dates and units in written form ("twenty third of march" →
+ * "March 23") before post-processing, for languages with formatting rules
+ */
+formatNormalization?: boolean; 
+/**
+ * Profanity and sensitive data filter of pasted dictations
+ */
+redaction?: RedactionConfig; 
+/**
+ * Spoken formatting cues written as Markdown, per app
+ */
+markdownMode?: MarkdownModeConfig; 
+/**
+ * Model, language, prompt and temperature of the transcription requests, per provider
+ */
+transcriptionOptions?: Partial<{ [key in Provider]: TranscriptionOptions }>; 
+/**
+ * Threads running model loading, local transcription and checksum hashing,
+ * 0 = automatic (requires a restart)
+ */
+workerThreads?: number }
+/**
+ * Transcription language last used in each app (stored locally)
+ */
+export type AppLanguages = { 
+/**
+ * ISO-639-1 language code keyed by app bundle identifier (e.g. "com.tinyspeck.slackmacgap")
+ */
+languages: Partial<{ [key in string]: string }> }
+/**
+ * Audit log settings
+ */
+export type AuditLogConfig = { enabled: boolean; 
+/**
+ * Also log the transcribed / post-processed text
+ */
+includeText: boolean }
+/**
+ * Time-of-day automation rules (stored locally), see `automation`
+ */
+export type AutomationConfig = { rules: AutomationRule[] }
+/**
+ * Settings applied at a time of day, e.g. Azure on weekdays from 09:00 to 17:00
+ */
+export type AutomationRule = { 
+/**
+ * Stable identifier (UUID)
+ */
+id: string; name: string; enabled: boolean; 
+/**
+ * Days the rule starts on, empty = every day
+ */
+days: Weekday[]; 
+/**
+ * Local time the rule starts, "HH:MM"
+ */
+start: string; 
+/**
+ * Local time the rule ends, "HH:MM". Before `start`, the rule runs past midnight.
+ */
+end: string; overlay: ConfigOverlay }
+/**
+ * Fired when an automation rule started and its settings were applied
+ */
+export type AutomationRuleActivated = { ruleId: string; name: string }
+/**
+ * Frontend-facing status for Azure OpenAI provider (never exposes API key)
+ */
+export type AzureOpenAIConfigStatus = { configured: boolean; endpoint: string; 
+/**
+ * The endpoint is set by IT and can't be changed
+ */
+endpointManaged: boolean }
+/**
+ * A transcribed segment of live captions
+ */
+export type CaptionLine = { text: string; 
+/**
+ * Offset from the start of the session, in milliseconds
+ */
+startMs: number; endMs: number }
+/**
+ * Physical keyboard a captured key came from
+ */
+export type CapturedKeyboard = { vendorId: number; productId: number; name: string | null; builtIn: boolean }
+/**
+ * Pasteboard metadata that tells clipboard managers (Maccy, Paste, Raycast, ...)
+ * to keep our temporary clipboard writes out of their history.
+ * 
+ * See <http://nspasteboard.org> for the conventions.
+ */
+export type ClipboardMarker = 
+/**
+ * No marker, transcriptions show up in clipboard history
+ */
+"none" | 
+/**
+ * `org.nspasteboard.TransientType`: content is replaced right away, don't record it
+ */
+"transient" | 
+/**
+ * `org.nspasteboard.ConcealedType`: content is sensitive, don't record or display it
+ */
+"concealed"
+/**
+ * A config entry was saved or deleted. Sent to subscribers of [`ConfigChangeBus`]
+ * and to the frontend.
+ */
+export type ConfigChanged = { 
+/**
+ * Store key of the entry (e.g. "appConfig", "shortcutsConfig")
+ */
+key: string; deleted: boolean }
+/**
+ * Settings an automation rule applies when it starts, `None` fields are left alone
+ */
+export type ConfigOverlay = { 
+/**
+ * Configuration profile to switch to, applied before the other fields
+ */
+profile: string | null; activeProvider: Provider | null; 
+/**
+ * Local model to select
+ */
+localModel: string | null; postProcessEnabled: boolean | null }
+/**
+ * Named set of settings switched as a whole (e.g. "Work" and "Personal"), see `profiles`.
+ * Fields missing from a profile saved by an older version take their default.
+ */
+export type ConfigProfile = { name: string; activeProvider: Provider | null; fallbackProviders: Provider[]; transcriptionOptions: Partial<{ [key in Provider]: TranscriptionOptions }>; postProcessEnabled: boolean; postProcessModel: string; postProcessPrompt: string; postProcessPromptsByLanguage: Partial<{ [key in string]: string }>; postProcessParams: PostProcessParams; formatNormalization: boolean; redaction: RedactionConfig; markdownMode: MarkdownModeConfig; localModel: ProfileLocalModel; shortcuts: ShortcutsConfig; 
+/**
+ * Vocabulary: learned corrections and voice snippets
+ */
+corrections: LearnedCorrections; snippets: SnippetsConfig }
+/**
+ * Push-to-talk and hands-free shortcuts used instead of the defaults on one keyboard
+ */
+export type DeviceShortcuts = { 
+/**
+ * USB/Bluetooth vendor id of the keyboard
+ */
+vendorId: number; 
+/**
+ * USB/Bluetooth product id of the keyboard
+ */
+productId: number; 
+/**
+ * Keyboard name shown in settings
+ */
+name: string; pushToRecord: Shortcut; handsFree: Shortcut }
+export type DiagnosticsReport = { appVersion: string; 
+/**
+ * `std::env::consts::OS` ("macos", "windows", "linux")
+ */
+os: string; arch: string; 
+/**
+ * `None` until the startup profiler is set up
+ */
+startup: StartupReport | null; 
+/**
+ * Resource usage of local inference, empty until a local model was used
+ */
+localInference: ResourceReport; keyboardGrab: KeyboardGrabStats; 
+/**
+ * Oldest first
+ */
+stateHistory: StateHistoryEntry[] }
+/**
+ * User-defined snippet the dictated text is inserted into
+ */
+export type DictationTemplate = { 
+/**
+ * Stable identifier (UUID)
+ */
+id: string; 
+/**
+ * Display name (tray menu, settings)
+ */
+name: string; 
+/**
+ * Spoken keyword that selects the template when it starts the dictation
+ * (e.g. "standup update"), `None` = tray menu selection only
+ */
+keyword: string | null; 
+/**
+ * Template text with placeholders: `{summary}` (the dictated text), `{date}`, `{time}`
+ */
+body: string }
+export type DiffKind = "unchanged" | "added" | "removed"
+/**
+ * Consecutive words with the same kind, joined by single spaces
+ */
+export type DiffSegment = { kind: DiffKind; text: string }
+/**
+ * File transcription state change event - single event stream for a whole batch
+ */
+export type FileTranscriptionStateChanged = 
+/**
+ * Transcription of a file has started
+ */
+{ state: "started"; filePath: string; fileIndex: number; totalFiles: number } | 
+/**
+ * A chunk of the file has been transcribed
+ */
+{ state: "progress"; filePath: string; completedChunks: number; totalChunks: number; percentage: number } | 
+/**
+ * File transcribed, outputs written next to the source file
+ */
+{ state: "complete"; filePath: string; textPath: string; srtPath: string } | 
+/**
+ * Transcription of a file failed, the batch continues with the next file
+ */
+{ state: "error"; filePath: string; error: string } | 
+/**
+ * All files of the batch have been processed
+ */
+{ state: "finished"; succeeded: number; failed: number }
+/**
+ * How the WAV file is persisted while recording
+ * 
+ * The header holds the data size, so a file that was never finalized looks
+ * empty. Periodic flushes keep it readable: a crash loses at most
+ * [`FLUSH_INTERVAL`] of audio.
+ */
+export type FlushPolicy = 
+/**
+ * Write the header when the recording stops only, a crash loses the whole take
+ */
+"never" | 
+/**
+ * Update the header and hand the data to the OS periodically (survives an app crash)
+ */
+"periodic" | 
+/**
+ * Like `Periodic`, plus fsync (survives a power loss or kernel panic)
+ */
+"periodicSync"
+/**
+ * Progress towards today's word goal
+ */
+export type GoalProgress = { 
+/**
+ * Daily word goal, `None` = no goal set
+ */
+goal: number | null; wordsToday: number; reached: boolean }
+/**
+ * What the model is doing while sampled
+ */
+export type InferencePhase = "modelLoad" | "transcription"
+/**
+ * What to do when the default input device changes (e.g. AirPods connected) mid-recording
+ */
+export type InputDeviceChangePolicy = 
+/**
+ * Continue the recording on the new default device
+ */
+"follow" | 
+/**
+ * Keep recording on the current device and tell the user
  */
-export type AppConfig = { 
+"notify" | 
+/**
+ * Keep recording on the current device
+ */
+"ignore"
+/**
+ * The default input device changed while recording
+ */
+export type InputDeviceChanged = { 
+/**
+ * Name of the new default input device, if known
+ */
+deviceName: string | null; 
+/**
+ * Whether the recording continues on the new device
+ */
+switched: boolean }
+/**
+ * How the text ended up being inserted
+ */
+export type InsertionMethod = 
+/**
+ * Pasted or typed by the default strategy
+ */
+"paste" | 
+/**
+ * Inserted through Accessibility
+ */
+"accessibilityInsert" | 
+/**
+ * Typed as keystrokes after the paste didn't land
+ */
+"typing" | 
+/**
+ * Nothing worked, the text was left on the clipboard
+ */
+"clipboard"
+/**
+ * Daily Markdown journal: every dictation appended to `<folder>/YYYY-MM-DD.md`
+ */
+export type JournalSinkConfig = { enabled: boolean; 
+/**
+ * Folder of the daily files, e.g. an Obsidian vault, `None` until chosen
+ */
+folder: string | null; 
+/**
+ * Format of an entry with placeholders: `{text}`, `{date}` (YYYY-MM-DD),
+ * `{time}` (HH:MM), `{app}`, `{language}`
+ */
+entryTemplate: string }
+/**
+ * Key capture event - streamed to frontend during shortcut configuration
+ */
+export type KeyCaptureEvent = 
+/**
+ * Key was pressed
+ */
+{ type: "keyDown"; keycode: number; label: string; device: CapturedKeyboard | null } | 
+/**
+ * Key was released
+ */
+{ type: "keyUp"; keycode: number; label: string; device: CapturedKeyboard | null }
+/**
+ * The keyboard listener's event tap failed (shortcuts unavailable until it's
+ * restarted) or recovered
+ */
+export type KeyListenerHealthChanged = { healthy: boolean; 
+/**
+ * Why the event tap stopped
+ */
+error: string | null; 
+/**
+ * Delay before the next restart attempt
+ */
+retryInMs: number | null }
+/**
+ * Event suppression statistics of the keyboard tap, for "keys feel laggy" reports
+ */
+export type KeyboardGrabStats = { eventsSeen: number; swallowed: number; passed: number; 
+/**
+ * Times macOS disabled the tap because the callback was too slow
+ */
+tapTimeouts: number; latencyBuckets: LatencyBucket[]; meanLatencyUs: number | null; maxLatencyUs: number }
+/**
+ * Event tap used by the keyboard listener. Applied when the listener starts.
+ */
+export type KeyboardTapConfig = { location: KeyboardTapLocation; 
+/**
+ * Only observe key events. Shortcut keys are no longer swallowed
+ * (e.g. Fn still opens the emoji picker), but input is never delayed by the listener.
+ */
+listenOnly: boolean }
+/**
+ * Where the keyboard listener taps key events (macOS `CGEventTapLocation`)
+ */
+export type KeyboardTapLocation = 
+/**
+ * Hardware level: sees every key first, but may interfere with secure input
+ * (password fields)
+ */
+"hid" | 
+/**
+ * Login session level: leaves secure input alone, but runs after other
+ * session taps such as key remappers
+ */
+"session"
+/**
+ * The keychain couldn't be read: tells the UI to guide the user (e.g. unlock the keychain)
+ */
+export type KeychainAccessFailed = { 
+/**
+ * Keychain account (e.g. "openAI")
+ */
+account: string; kind: KeychainErrorKind; userMessage: string }
+/**
+ * Result of reading one keychain account
+ */
+export type KeychainAccessStatus = { 
+/**
+ * Keychain account (e.g. "openAI")
+ */
+account: string; 
+/**
+ * `None` when the item could be read
+ */
+error: KeychainErrorKind | null; 
+/**
+ * What the user should do about the error
+ */
+userMessage: string | null }
+/**
+ * Why the keychain couldn't be used, for the UI
+ */
+export type KeychainErrorKind = 
+/**
+ * The keychain is locked, the user must unlock it (re-entering keys won't help)
+ */
+"locked" | 
+/**
+ * The user denied access, or Dictara isn't allowed to use the item
+ */
+"accessDenied" | 
+/**
+ * No item stored for the account
+ */
+"notFound" | "other"
+/**
+ * Number of transcriptions per detected language (stored locally)
+ */
+export type LanguageStats = { 
+/**
+ * Transcription count keyed by ISO-639-1 language code (e.g. "en")
+ */
+counts: Partial<{ [key in string]: number }> }
+/**
+ * One bucket of the tap callback latency histogram
+ */
+export type LatencyBucket = { 
+/**
+ * Inclusive upper bound in microseconds, `None` for the overflow bucket
+ */
+upperBoundUs: number | null; count: number }
+/**
+ * A word or short phrase the user corrected in dictated text
+ */
+export type LearnedCorrection = { 
+/**
+ * As recognized
+ */
+original: string; 
+/**
+ * As corrected by the user
+ */
+corrected: string; 
+/**
+ * How often the user made this correction
+ */
+count: number }
+/**
+ * Corrections of dictated text (stored locally), most recent first
+ */
+export type LearnedCorrections = { corrections: LearnedCorrection[] }
+/**
+ * Live captions started or stopped (by command, closing the window or an error)
+ */
+export type LiveCaptionsStateChanged = { running: boolean; 
+/**
+ * Why the captions stopped on their own
+ */
+error: string | null }
+/**
+ * Resource usage sampled about every second while a local model loads or transcribes
+ */
+export type LocalInferenceResourceUsage = { phase: InferencePhase; modelName: string; 
+/**
+ * Process resident memory
+ */
+rssBytes: number | null; 
+/**
+ * Swap used by the system
+ */
+swapUsedBytes: number | null; 
+/**
+ * System wide GPU utilization in percent (macOS only)
+ */
+gpuUtilization: number | null }
+/**
+ * Local model provider configuration (stored in local store, not keychain)
+ */
+export type LocalModelConfig = { 
+/**
+ * Name of the selected model (e.g., "whisper-small")
+ */
+selectedModel: string | null; 
+/**
+ * Transcription options by model name, defaults for models not listed
+ */
+modelOptions?: Partial<{ [key in string]: LocalModelOptions }>; 
+/**
+ * Where model files are downloaded from
+ */
+downloadMirror?: ModelMirrorConfig; 
+/**
+ * Directory of the downloaded models, `None` for the app data directory.
+ * Changed with `set_models_directory`, which moves the models.
+ */
+modelsDir?: string | null; 
+/**
+ * Deleted models go to the Trash instead of being removed for good
+ */
+deleteToTrash?: boolean }
+/**
+ * Per-model transcription options
+ */
+export type LocalModelOptions = { 
+/**
+ * Language to transcribe when none is pinned for the session, `None` to auto-detect
+ */
+language: string | null; 
+/**
+ * Compute timestamps (Whisper segments, Parakeet word timings)
+ */
+timestamps: boolean; 
+/**
+ * Whisper beam search width, `None` or 1 for greedy decoding (Parakeet is always greedy)
+ */
+beamSize: number | null }
+/**
+ * Markdown dictation mode: spoken formatting cues ("bullet point", "heading two") are
+ * written as Markdown. Great in editors, harmful in plain chat boxes, hence per app.
+ */
+export type MarkdownModeConfig = { 
+/**
+ * Whether apps without an override get Markdown
+ */
+enabled: boolean; 
+/**
+ * Bundle identifier of an app (e.g. "md.obsidian") to whether it gets Markdown
+ */
+appOverrides: Partial<{ [key in string]: boolean }> }
+/**
+ * Meeting mode state change event - single event stream for a meeting
+ */
+export type MeetingStateChanged = 
+/**
+ * Recording has started
+ */
+{ state: "started" } | 
+/**
+ * A segment of the meeting has been transcribed
+ */
+{ state: "segment"; text: string; startMs: number; endMs: number } | 
+/**
+ * Recording has stopped, the transcript is being summarized
+ */
+{ state: "summarizing" } | 
+/**
+ * The notes are ready to be saved
+ */
+{ state: "finished"; segments: number; hasSummary: boolean; speakers: number } | 
+/**
+ * Recording stopped on an error, the segments transcribed so far are kept
+ */
+{ state: "error"; error: string }
+/**
+ * Overall state of the model migration
+ */
+export type MigrationState = 
+/**
+ * Migration hasn't run yet this session
+ */
+"notStarted" | "running" | 
+/**
+ * Finished, possibly with failed models
+ */
+"complete"
+/**
+ * Progress of the model migration, for the settings UI
+ */
+export type MigrationStatus = { state: MigrationState; 
+/**
+ * Models that need migrating this session
+ */
+totalModels: number; migratedModels: number; 
+/**
+ * Model being migrated right now
+ */
+currentModel: string | null; 
+/**
+ * Models that couldn't be migrated (they keep working from the old location)
+ */
+failedModels: string[] }
+/**
+ * Reachability of a catalog source through the configured mirror
+ */
+export type MirrorSourceHealth = { 
+/**
+ * Hugging Face repository, e.g. "ggerganov/whisper.cpp"
+ */
+source: string; 
+/**
+ * URL that was checked (a file of the source, after mirror rewriting)
+ */
+url: string; reachable: boolean; 
+/**
+ * HTTP status, `None` when no response was received
+ */
+status: number | null; error: string | null; latencyMs: number }
+/**
+ * Disk space used by one model (or the speaker model of meetings)
+ */
+export type ModelDiskUsage = { modelName: string; 
+/**
+ * Everything on disk: model files, Core ML encoder and interrupted downloads
+ */
+totalBytes: number; 
+/**
+ * Interrupted downloads, freed by deleting the model
+ */
+partialBytes: number }
+/**
+ * Model download state change event - single event stream for all download state transitions
+ */
+export type ModelDownloadStateChanged = 
+/**
+ * Download is in progress
+ */
+{ state: "progress"; modelName: string; downloadedBytes: number; totalBytes: number; percentage: number } | 
+/**
+ * Checksums are being verified, files not hashed during their download are read again
+ */
+{ state: "verifying"; modelName: string; verifiedBytes: number; totalBytes: number; percentage: number } | 
+/**
+ * Download completed successfully
+ */
+{ state: "complete"; modelName: string } | 
+/**
+ * Download failed with an error
+ */
+{ state: "error"; modelName: string; error: string }
+/**
+ * Combined view sent to frontend (catalog + status merged).
+ */
+export type ModelInfo = { name: string; displayName: string; description: string; sizeBytes: number; estimatedRamMb: number; speedClass: SpeedClass; languages: string[]; quantization: Quantization | null; 
+/**
+ * A Core ML encoder can be downloaded (Apple Silicon only)
+ */
+coremlAvailable: boolean; isDownloaded: boolean; isDownloading: boolean; isLoaded: boolean; isLoading: boolean; downloadedBytes: number; isCoremlDownloaded: boolean }
+/**
+ * Model loading state change event - single event stream for all loading state transitions
+ */
+export type ModelLoadingStateChanged = 
+/**
+ * Model loading has started
+ */
+{ state: "started"; modelName: string } | 
+/**
+ * Model loaded successfully
+ */
+{ state: "complete"; modelName: string } | 
+/**
+ * Model loading failed with an error
+ */
+{ state: "error"; modelName: string; error: string }
+/**
+ * Model migration state change event - progress of moving single-file models
+ * into the per-model directory structure at startup
+ */
+export type ModelMigrationStateChanged = 
+/**
+ * Migration has started
+ */
+{ state: "started"; totalModels: number } | 
+/**
+ * A model was migrated and verified
+ */
+{ state: "progress"; modelName: string; migratedModels: number; totalModels: number } | 
+/**
+ * A model couldn't be migrated, it stays in the old location
+ */
+{ state: "error"; modelName: string; error: string } | 
+/**
+ * Migration finished
+ */
+{ state: "complete"; migratedModels: number; failedModels: string[] }
+/**
+ * Mirrors of the model downloads, for networks that block huggingface.co.
+ * Downloaded files are still verified against the catalog checksums.
+ */
+export type ModelMirrorConfig = { 
+/**
+ * Replaces `https://huggingface.co` in every model URL (e.g. "https://hf-mirror.com")
+ */
+baseUrl: string | null; 
+/**
+ * Catalog source (Hugging Face repository, e.g. "ggerganov/whisper.cpp") to the URL
+ * its files are downloaded from, instead of `<repository>/resolve/main`
+ */
+sourceUrls: Partial<{ [key in string]: string }> }
+/**
+ * Result of re-hashing an installed model against the catalog checksums.
+ */
+export type ModelVerification = { modelName: string; 
+/**
+ * Number of files matching their checksum
+ */
+verifiedFiles: number; 
+/**
+ * Files without a pinned checksum, which can't be checked
+ */
+unverifiableFiles: string[]; 
+/**
+ * Files that are missing or don't match their checksum
+ */
+corruptedFiles: string[]; 
+/**
+ * Whether the corrupted files were downloaded again
+ */
+repaired: boolean }
+/**
+ * Disk space used by the models, for the storage settings
+ */
+export type ModelsDiskUsage = { 
+/**
+ * Directory the models are stored in
+ */
+modelsDir: string; 
+/**
+ * Models with files on disk
+ */
+models: ModelDiskUsage[]; totalBytes: number }
+/**
+ * Recording state published to an MQTT broker. The password of `username` is stored
+ * in the keychain.
+ */
+export type MqttPushConfig = { enabled: boolean; 
+/**
+ * Broker host, e.g. "homeassistant.local"
+ */
+host: string; port: number; 
+/**
+ * Topic the state is published to (retained)
+ */
+topic: string; 
+/**
+ * `None` = no authentication
+ */
+username: string | null }
 /**
- * Currently active provider (only one can be active)
+ * App the note is created in
  */
-activeProvider: Provider | null; 
+export type NoteTarget = "obsidian" | "appleNotes"
 /**
- * Key used to trigger recording (default: Fn)
+ * Onboarding configuration (stored locally)
  */
-recordingTrigger?: RecordingTrigger; 
+export type OnboardingConfig = { 
 /**
- * Whether autostart has been set up on first launch
- * This prevents re-enabling autostart after user manually disables it
+ * Whether the user has completed or skipped onboarding
  */
-autostartInitialSetupDone?: boolean; 
+finished: boolean; 
 /**
- * Whether to run LLM post-processing after transcription
+ * Current step in the onboarding flow
  */
-postProcessEnabled: boolean; 
+currentStep: OnboardingStep; 
 /**
- * OpenAI model used for transcription post-processing
+ * Flag to track if we're resuming after an accessibility restart
  */
-postProcessModel: string; 
+pendingRestart: boolean }
 /**
- * Prompt used for transcription post-processing
+ * Onboarding step enum - tracks current position in the wizard
  */
-postProcessPrompt: string; 
+export type OnboardingStep = "welcome" | "accessibility" | "microphone" | "api_keys" | "shortcuts" | "fn_hold" | "fn_space" | "complete"
 /**
- * Minimum speech duration required before running transcription (milliseconds)
+ * Frontend-facing status for OpenAI provider (never exposes API key)
  */
-minSpeechDurationMs: number }
+export type OpenAIConfigStatus = { configured: boolean }
 /**
- * Frontend-facing status for Azure OpenAI provider (never exposes API key)
+ * Where dictations go besides being pasted (stored locally), see `sinks`
  */
-export type AzureOpenAIConfigStatus = { configured: boolean; endpoint: string }
+export type OutputSinksConfig = { journal: JournalSinkConfig; webhook: WebhookSinkConfig }
 /**
- * Key capture event - streamed to frontend during shortcut configuration
+ * Bounds of the wait between the simulated paste and the clipboard restore
+ * 
+ * Restoring too early makes slow apps (Electron, remote desktops) paste the
+ * previous clipboard content or a truncated text.
  */
-export type KeyCaptureEvent = 
+export type PasteTiming = { 
 /**
- * Key was pressed
+ * Never restore earlier than this after the paste
  */
-{ type: "keyDown"; keycode: number; label: string } | 
+minRestoreDelayMs: number; 
 /**
- * Key was released
+ * Restore at the latest after this, even if the paste wasn't confirmed
  */
-{ type: "keyUp"; keycode: number; label: string }
+maxRestoreDelayMs: number }
 /**
- * Local model provider configuration (stored in local store, not keychain)
+ * Resource usage over one model load or transcription
  */
-export type LocalModelConfig = { 
+export type PhaseSummary = { modelName: string; durationMs: number; 
 /**
- * Name of the selected model (e.g., "whisper-small")
+ * Highest process resident memory
  */
-selectedModel: string | null }
+peakRssBytes: number | null; 
 /**
- * Model download state change event - single event stream for all download state transitions
+ * Highest GPU utilization in percent (macOS only)
  */
-export type ModelDownloadStateChanged = 
+maxGpuUtilization: number | null; 
 /**
- * Download is in progress
+ * Swap used by the system at the end minus at the start
  */
-{ state: "progress"; modelName: string; downloadedBytes: number; totalBytes: number; percentage: number } | 
+swapGrowthBytes: number | null }
 /**
- * Download complete, verifying checksum
+ * What the recording popup shows while recording
  */
-{ state: "verifying"; modelName: string } | 
+export type PopupContent = 
 /**
- * Download completed successfully
+ * Live audio level waveform
  */
-{ state: "complete"; modelName: string } | 
+"waveform" | 
 /**
- * Download failed with an error
+ * Status text only
  */
-{ state: "error"; modelName: string; error: string }
+"textOnly"
 /**
- * Combined view sent to frontend (catalog + status merged).
+ * Size of the recording popup
  */
-export type ModelInfo = { name: string; displayName: string; description: string; sizeBytes: number; estimatedRamMb: number; isDownloaded: boolean; isDownloading: boolean; isLoaded: boolean; isLoading: boolean; downloadedBytes: number }
+export type PopupStyle = 
 /**
- * Model loading state change event - single event stream for all loading state transitions
+ * Only the recording indicator
  */
-export type ModelLoadingStateChanged = 
+"compact" | 
 /**
- * Model loading has started
+ * Indicator and controls
  */
-{ state: "started"; modelName: string } | 
+"full"
 /**
- * Model loaded successfully
+ * Request parameters of the post-processing call
  */
-{ state: "complete"; modelName: string } | 
+export type PostProcessParams = { 
 /**
- * Model loading failed with an error
+ * Request timeout, the transcription is pasted unprocessed when it expires
  */
-{ state: "error"; modelName: string; error: string }
+timeoutSecs: number; 
 /**
- * Onboarding configuration (stored locally)
+ * Sampling temperature, `None` = model default (reasoning models reject it)
  */
-export type OnboardingConfig = { 
+temperature: number | null; 
 /**
- * Whether the user has completed or skipped onboarding
+ * Output token limit, `None` = model default
  */
-finished: boolean; 
+maxOutputTokens: number | null; 
 /**
- * Current step in the onboarding flow
+ * Reject outputs whose length or script diverges from the transcription (the
+ * dictation derailed the model). Disable for prompts that translate or summarize.
  */
-currentStep: OnboardingStep; 
+guardOutput: boolean }
 /**
- * Flag to track if we're resuming after an accessibility restart
+ * Local model settings of a profile. The device-level ones (models directory,
+ * download mirror, Trash) stay with the machine.
  */
-pendingRestart: boolean }
+export type ProfileLocalModel = { selectedModel: string | null; modelOptions: Partial<{ [key in string]: LocalModelOptions }> }
 /**
- * Onboarding step enum - tracks current position in the wizard
+ * Saved configuration profiles (stored locally)
  */
-export type OnboardingStep = "welcome" | "accessibility" | "microphone" | "api_keys" | "shortcuts" | "fn_hold" | "fn_space" | "complete"
+export type ProfilesConfig = { 
 /**
- * Frontend-facing status for OpenAI provider (never exposes API key)
+ * Profile the current settings belong to, `None` until a profile is switched to
  */
-export type OpenAIConfigStatus = { configured: boolean }
+active: string | null; profiles: ConfigProfile[] }
 /**
  * Provider types supported by the application
  */
 export type Provider = "open_ai" | "azure_open_ai" | "local"
+/**
+ * Health of one provider, for the preferences page
+ */
+export type ProviderHealthReport = { provider: Provider; 
+/**
+ * Outcome counts of the last attempts (at most 20)
+ */
+recentSuccesses: number; recentFailures: number; 
+/**
+ * Unix time in milliseconds
+ */
+lastSuccessAt: number | null; lastFailureAt: number | null; lastError: string | null; 
+/**
+ * This provider served the last successful transcription
+ */
+servedLastRequest: boolean }
+/**
+ * Proxy settings. The password of `username` is stored in the keychain.
+ */
+export type ProxyConfig = { mode: ProxyMode; 
+/**
+ * Manual proxy host, e.g. "proxy.corp.example"
+ */
+host: string; port: number; 
+/**
+ * Basic authentication user, `None` = no authentication
+ */
+username: string | null }
+/**
+ * How outbound HTTP requests (APIs, model downloads, updates) reach the internet
+ */
+export type ProxyMode = 
+/**
+ * System proxy settings and `HTTP(S)_PROXY` variables
+ */
+"system" | 
+/**
+ * Direct connections, ignoring the system proxy
+ */
+"direct" | 
+/**
+ * The proxy in [`ProxyConfig`]
+ */
+"manual"
+/**
+ * Quantization of a ggml Whisper model (smaller and faster, slightly less accurate)
+ */
+export type Quantization = 
+/**
+ * 5-bit (ggml q5_0 / q5_1)
+ */
+"q5" | 
+/**
+ * 8-bit (ggml q8_0)
+ */
+"q8"
+/**
+ * Raw (verbatim) dictation mode was toggled
+ */
+export type RawModeChanged = { enabled: boolean }
+/**
+ * Recording states
+ */
+export type RecordingState = 
+/**
+ * Controller is ready to start recording
+ */
+"ready" | 
+/**
+ * Controller is currently recording
+ */
+"recording" | 
+/**
+ * Recording is locked - Fn release will be ignored
+ */
+"recordingLocked" | 
+/**
+ * Audio is being transcribed
+ */
+"transcribing"
 /**
  * Recording state change event - single event stream for all state transitions
  */
@@ -606,11 +2618,11 @@ export type RecordingStateChanged =
 /**
  * Recording is being transcribed
  */
-{ state: "transcribing" } | 
+{ state: "transcribing"; estimatedMs: number | null } | 
 /**
  * Recording completed successfully
  */
-{ state: "stopped"; text: string } | 
+{ state: "stopped"; text: string; language: string | null } | 
 /**
  * Recording was cancelled by user
  */
@@ -619,10 +2631,89 @@ export type RecordingStateChanged =
  * An error occurred during recording or transcription
  */
 { state: "error"; errorType: string; errorMessage: string; userMessage: string; audioFilePath: string | null }
+/**
+ * Sent every half second while recording, for the elapsed time in the popup and a
+ * warning before the recording is stopped at its length limit (configured maximum
+ * or the 25MB transcription file limit)
+ */
+export type RecordingTick = { elapsedMs: number; 
+/**
+ * Time left before the recording is stopped and transcribed
+ */
+remainingMs: number }
 /**
  * Recording trigger key options
  */
 export type RecordingTrigger = "fn" | "control" | "option" | "command"
+/**
+ * Filter of profanity and sensitive data applied to dictations before pasting
+ */
+export type RedactionConfig = { enabled: boolean; strictness: RedactionStrictness; style: RedactionStyle; 
+/**
+ * Numbers that look like payment card numbers (13-19 digits passing the Luhn check)
+ */
+redactCardNumbers: boolean; redactEmails: boolean; 
+/**
+ * Additional words to filter
+ */
+blockedWords: string[]; 
+/**
+ * Words never filtered, e.g. names or email addresses caught by mistake
+ */
+allowedWords: string[] }
+/**
+ * Which profanity the redaction filter catches
+ */
+export type RedactionStrictness = 
+/**
+ * Strong profanity and slurs
+ */
+"standard" | 
+/**
+ * Also mild swearing ("damn", "hell", "crap")
+ */
+"strict"
+/**
+ * How the redaction filter hides what it catches
+ */
+export type RedactionStyle = 
+/**
+ * Mask words ("f***") and replace sensitive data with a placeholder ("[email]")
+ */
+"mask" | 
+/**
+ * Leave them out of the text
+ */
+"remove"
+/**
+ * Last model load and transcription summaries, for diagnostics
+ */
+export type ResourceReport = { lastModelLoad: PhaseSummary | null; lastTranscription: PhaseSummary | null }
+/**
+ * macOS Secure Input was enabled or disabled by an app (password field, terminal with
+ * "Secure Keyboard Entry"). While enabled, shortcuts receive no key events.
+ */
+export type SecureInputChanged = { enabled: boolean; 
+/**
+ * Process that enabled Secure Input, if known
+ */
+pid: number | null; 
+/**
+ * Name of the app that enabled Secure Input, if known
+ */
+appName: string | null }
+/**
+ * One searchable preference
+ */
+export type SettingMetadata = { 
+/**
+ * `<config key>.<field path>`, e.g. `appConfig.postProcessModel`
+ */
+id: string; title: string; keywords: string[]; category: SettingsCategory }
+/**
+ * Preferences page a setting lives on
+ */
+export type SettingsCategory = "general" | "transcription" | "postProcessing" | "recording" | "paste" | "shortcuts" | "appearance" | "privacy"
 /**
  * A keyboard shortcut (1-3 keys)
  */
@@ -642,7 +2733,255 @@ pushToRecord: Shortcut;
 /**
  * Hands-free: Press to toggle (start/stop)
  */
-handsFree: Shortcut }
+handsFree: Shortcut; 
+/**
+ * Raw mode: Press to toggle verbatim dictation (no post-processing), `None` = no shortcut
+ */
+rawMode?: Shortcut | null; 
+/**
+ * Shortcuts for specific keyboards (e.g. external keyboards without a usable Fn key)
+ */
+deviceShortcuts?: DeviceShortcuts[]; 
+/**
+ * Push-to-talk presses shorter than this (ms) are passed to the system instead of
+ * recording, so a tap of Fn keeps its normal behavior. 0 = record on press.
+ */
+holdThresholdMs?: number; 
+/**
+ * Recording starts less than this (ms) after the previous one are ignored, so rapid
+ * tapping can't interleave starts and stops. 0 = no limit.
+ */
+minStartIntervalMs?: number; 
+/**
+ * A push-to-talk key pressed again less than this (ms) after its release is key
+ * bounce, and the recording goes on. 0 = stop on release.
+ */
+bounceMs?: number }
+/**
+ * Voice snippets (stored locally)
+ */
+export type SnippetsConfig = { snippets: VoiceSnippet[] }
+/**
+ * Expected transcription speed on Apple Silicon, relative to the other models
+ */
+export type SpeedClass = "fastest" | "fast" | "balanced" | "slow"
+/**
+ * A timed part of the startup
+ */
+export type StartupPhase = { name: string; durationMs: number; 
+/**
+ * Ran after setup, in the background
+ */
+deferred: boolean }
+/**
+ * Timings of the last launch
+ */
+export type StartupReport = { phases: StartupPhase[]; 
+/**
+ * Time until setup returned and the tray was usable
+ */
+setupMs: number | null; 
+/**
+ * Time until the deferred tasks finished, `None` while they run
+ */
+deferredMs: number | null }
+/**
+ * An attempted transition, for debugging
+ */
+export type StateHistoryEntry = { 
+/**
+ * Local time, RFC 3339
+ */
+timestamp: string; 
+/**
+ * Event name ("Start", "Stop", ...), "Reset" for a reset to Ready
+ */
+event: string; from: RecordingState; 
+/**
+ * State after the transition, `from` if it was rejected
+ */
+to: RecordingState; 
+/**
+ * Action the controller was asked to perform
+ */
+action: string | null; accepted: boolean }
+/**
+ * Push of the recording state for home automation (e.g. a light turning red while
+ * recording)
+ */
+export type StatePushConfig = { mqtt: MqttPushConfig; websocket: WebSocketPushConfig }
+/**
+ * Current app state, assembled from the managers
+ */
+export type StateSnapshot = { 
+/**
+ * `None` until the recording controller is set up
+ */
+recordingState: RecordingState | null; rawMode: boolean; activeProvider: Provider | null; 
+/**
+ * Local models with download/load status
+ */
+models: ModelInfo[]; migration: MigrationStatus; 
+/**
+ * False while the keyboard listener is down (or not started yet)
+ */
+listenerHealthy: boolean }
+/**
+ * Supported subtitle formats
+ */
+export type SubtitleFormat = "srt" | "vtt"
+/**
+ * What to do with a running recording when the Mac goes to sleep or the screen locks
+ */
+export type SystemSleepPolicy = 
+/**
+ * Stop the recording and transcribe what was captured
+ */
+"transcribe" | 
+/**
+ * Discard the recording
+ */
+"cancel"
+/**
+ * Dictation templates (stored locally)
+ */
+export type TemplatesConfig = { templates: DictationTemplate[]; 
+/**
+ * Template applied to every dictation (selected in the tray menu), `None` = off
+ */
+activeTemplateId: string | null }
+export type TextDiff = { 
+/**
+ * The original text, removed and added words in reading order
+ */
+segments: DiffSegment[]; wordsAdded: number; wordsRemoved: number }
+/**
+ * Result of an insertion, reported to the frontend
+ */
+export type TextInserted = { method: InsertionMethod; 
+/**
+ * Whether the insertion was confirmed via Accessibility
+ */
+verified: boolean }
+/**
+ * Certificates trusted for outbound HTTPS, on top of the system roots
+ */
+export type TlsConfig = { 
+/**
+ * PEM file with a corporate root CA (e.g. of a proxy inspecting TLS)
+ */
+customCaPath: string | null; 
+/**
+ * Host (e.g. "api.openai.com") to the PEM file of the only roots trusted for it
+ */
+pinnedCertificates: Partial<{ [key in string]: string }> }
+/**
+ * The user switched apps while transcribing, so the result was not pasted
+ * (it can be pasted or copied from the tray menu)
+ */
+export type TranscriptionHeld = { text: string }
+/**
+ * Options of a transcription request, the same for every provider.
+ * 
+ * API clients send them as form fields; local models only use the language.
+ */
+export type TranscriptionOptions = { 
+/**
+ * Model to transcribe with, `None` for the provider default (OpenAI: whisper-1).
+ * Azure ignores it, the deployment in the endpoint picks the model.
+ */
+model: string | null; 
+/**
+ * Language code to pin (e.g. "en"), `None` to auto-detect
+ */
+language: string | null; 
+/**
+ * Text the transcript continues, guides spelling of names and jargon
+ */
+prompt: string | null; 
+/**
+ * Sampling temperature from 0.0 (most deterministic) to 1.0
+ */
+temperature: number }
+/**
+ * Progress of a local transcription, lets the popup show a progress bar for long audio
+ */
+export type TranscriptionProgress = { modelName: string; percentage: number }
+/**
+ * The transcription was post-processed: the recognized and the rewritten text
+ * with a word diff, so the UI can show what the LLM changed
+ */
+export type TranscriptionResult = { 
+/**
+ * Recognized text (without the template keyword, if a template was selected)
+ */
+rawText: string; 
+/**
+ * Text returned by post-processing, before template rendering
+ */
+postProcessedText: string; diff: TextDiff; 
+/**
+ * Time the popup closing and clipboard saving ran during post-processing instead
+ * of after it (ms)
+ */
+savedMs: number }
+/**
+ * Appearance of the recording popup and the other windows (stored locally)
+ */
+export type UiPrefs = { popupStyle: PopupStyle; popupContent: PopupContent; 
+/**
+ * Popup opacity, from 0.2 to 1.0
+ */
+popupOpacity: number; 
+/**
+ * Follow the system light/dark appearance, light theme otherwise
+ */
+followSystemTheme: boolean }
+/**
+ * UI preferences were saved, every window restyles live
+ */
+export type UiPrefsChanged = { prefs: UiPrefs }
+/**
+ * Stored text inserted wherever its trigger phrase is spoken within a dictation
+ */
+export type VoiceSnippet = { 
+/**
+ * Stable identifier (UUID)
+ */
+id: string; 
+/**
+ * Spoken phrase replaced by the snippet (e.g. "insert my address")
+ */
+trigger: string; 
+/**
+ * Text inserted in place of the trigger phrase
+ */
+text: string }
+/**
+ * Recording state sent to the clients of a WebSocket server on 127.0.0.1
+ */
+export type WebSocketPushConfig = { enabled: boolean; port: number }
+/**
+ * Every dictation POSTed as JSON to a URL, signed with the secret stored in the
+ * keychain (`WebhookCredentials`) if there is one
+ */
+export type WebhookSinkConfig = { enabled: boolean; 
+/**
+ * http(s) URL the dictations are sent to, `None` until set
+ */
+url: string | null; 
+/**
+ * Show a notification when a dictation couldn't be delivered after the retries
+ */
+notifyOnFailure: boolean }
+/**
+ * Day of the week of an automation rule
+ */
+export type Weekday = "monday" | "tuesday" | "wednesday" | "thursday" | "friday" | "saturday" | "sunday"
+/**
+ * Fired once per day when the dictated words reach the daily goal
+ */
+export type WordGoalReached = { goal: number; wordsToday: number }
 
 /** tauri-specta globals **/
 
@@ -0,0 +1,42 @@
+import { X } from 'lucide-react'
+import { useCaptionLines, useStopLiveCaptions } from '@/hooks/useLiveCaptions'
+
+function LiveCaptions() {
+  const { lines, error } = useCaptionLines()
+  const stopLiveCaptions = useStopLiveCaptions()
+
+  return (
+    <div className="flex items-end w-screen h-screen px-4 py-3 gap-3 bg-[#0a0a0a] text-white">
+      <div className="flex-1 min-w-0 flex flex-col justify-end gap-1">
+        {error ? (
+          <div className="text-red-400 text-sm">{error}</div>
+        ) : lines.length === 0 ? (
+          <div className="text-gray-500 text-sm">Listening…</div>
+        ) : (
+          lines.map((line, index) => (
+            <div
+              key={line.startMs}
+              className={`leading-snug ${
+                index === lines.length - 1 ? 'text-lg' : 'text-base text-gray-400'
+              }`}
+            >
+              {line.text}
+            </div>
+          ))
+        )}
+      </div>
+
+      <button
+        type="button"
+        onClick={() => stopLiveCaptions.mutate()}
+        disabled={stopLiveCaptions.isPending}
+        className="self-start p-1 rounded text-gray-400 hover:text-white hover:bg-white/10"
+        title="Stop live captions"
+      >
+        <X className="w-4 h-4" />
+      </button>
+    </div>
+  )
+}
+
+export default LiveCaptions
@@ -0,0 +1,48 @@
+import { useMutation } from '@tanstack/react-query'
+import { useEffect, useState } from 'react'
+import { commands, events, type CaptionLine } from '@/bindings'
+
+/** Lines kept on screen, older ones scroll out */
+const MAX_LINES = 3
+
+/**
+ * Hook to follow the live captions: the latest caption lines, and the error that
+ * stopped the captions, if any.
+ */
+export function useCaptionLines() {
+  const [lines, setLines] = useState<CaptionLine[]>([])
+  const [error, setError] = useState<string | null>(null)
+
+  useEffect(() => {
+    const unlistenLine = events.captionLine.listen((event) => {
+      setLines((prev) => [...prev, event.payload].slice(-MAX_LINES))
+    })
+
+    const unlistenState = events.liveCaptionsStateChanged.listen((event) => {
+      if (event.payload.running) {
+        setLines([])
+        setError(null)
+      } else {
+        setError(event.payload.error)
+      }
+    })
+
+    return () => {
+      unlistenLine.then((fn) => fn())
+      unlistenState.then((fn) => fn())
+    }
+  }, [])
+
+  return { lines, error }
+}
+
+/**
+ * Hook to stop the live captions, which closes their window.
+ */
+export function useStopLiveCaptions() {
+  return useMutation({
+    mutationFn: async (): Promise<void> => {
+      await commands.stopLiveCaptions()
+    },
+  })
+}
@@ -10,6 +10,7 @@
 
 import { Route as rootRouteImport } from './routes/__root'
 import { Route as RecordingPopupRouteRouteImport } from './routes/recording-popup/route'
+import { Route as CaptionsRouteImport } from './routes/captions'
 import { Route as PreferencesRouteRouteImport } from './routes/preferences/route'
 import { Route as OnboardingRouteRouteImport } from './routes/onboarding/route'
 import { Route as RecordingPopupIndexRouteImport } from './routes/recording-popup/index'
@@ -37,6 +38,11 @@ const PreferencesRouteRoute = PreferencesRouteRouteImport.update({
   path: '/preferences',
   getParentRoute: () => rootRouteImport,
 } as any)
+const CaptionsRoute = CaptionsRouteImport.update({
+  id: '/captions',
+  path: '/captions',
+  getParentRoute: () => rootRouteImport,
+} as any)
 const OnboardingRouteRoute = OnboardingRouteRouteImport.update({
   id: '/onboarding',
   path: '/onboarding',
@@ -114,6 +120,7 @@ const OnboardingAccessibilityRoute = OnboardingAccessibilityRouteImport.update({
 } as any)
 
 export interface FileRoutesByFullPath {
+  '/captions': typeof CaptionsRoute
   '/onboarding': typeof OnboardingRouteRouteWithChildren
   '/preferences': typeof PreferencesRouteRouteWithChildren
   '/recording-popup': typeof RecordingPopupRouteRouteWithChildren
@@ -133,6 +140,7 @@ export interface FileRoutesByFullPath {
   '/recording-popup/': typeof RecordingPopupIndexRoute
 }
 export interface FileRoutesByTo {
+  '/captions': typeof CaptionsRoute
   '/onboarding/accessibility': typeof OnboardingAccessibilityRoute
   '/onboarding/api-keys': typeof OnboardingApiKeysRoute
   '/onboarding/complete': typeof OnboardingCompleteRoute
@@ -150,6 +158,7 @@ export interface FileRoutesByTo {
 }
 export interface FileRoutesById {
   __root__: typeof rootRouteImport
+  '/captions': typeof CaptionsRoute
   '/onboarding': typeof OnboardingRouteRouteWithChildren
   '/preferences': typeof PreferencesRouteRouteWithChildren
   '/recording-popup': typeof RecordingPopupRouteRouteWithChildren
@@ -171,6 +180,7 @@ export interface FileRoutesById {
 export interface FileRouteTypes {
   fileRoutesByFullPath: FileRoutesByFullPath
   fullPaths:
+    | '/captions'
     | '/onboarding'
     | '/preferences'
     | '/recording-popup'
@@ -190,6 +200,7 @@ export interface FileRouteTypes {
     | '/recording-popup/'
   fileRoutesByTo: FileRoutesByTo
   to:
+    | '/captions'
     | '/onboarding/accessibility'
     | '/onboarding/api-keys'
     | '/onboarding/complete'
@@ -206,6 +217,7 @@ export interface FileRouteTypes {
     | '/recording-popup'
   id:
     | '__root__'
+    | '/captions'
     | '/onboarding'
     | '/preferences'
     | '/recording-popup'
@@ -226,6 +238,7 @@ export interface FileRouteTypes {
   fileRoutesById: FileRoutesById
 }
 export interface RootRouteChildren {
+  CaptionsRoute: typeof CaptionsRoute
   OnboardingRouteRoute: typeof OnboardingRouteRouteWithChildren
   PreferencesRouteRoute: typeof PreferencesRouteRouteWithChildren
   RecordingPopupRouteRoute: typeof RecordingPopupRouteRouteWithChildren
@@ -254,6 +267,13 @@ declare module '@tanstack/react-router' {
       preLoaderRoute: typeof OnboardingRouteRouteImport
       parentRoute: typeof rootRouteImport
     }
+    '/captions': {
+      id: '/captions'
+      path: '/captions'
+      fullPath: '/captions'
+      preLoaderRoute: typeof CaptionsRouteImport
+      parentRoute: typeof rootRouteImport
+    }
     '/recording-popup/': {
       id: '/recording-popup/'
       path: '/'
@@ -412,6 +432,7 @@ const RecordingPopupRouteRouteWithChildren =
   RecordingPopupRouteRoute._addFileChildren(RecordingPopupRouteRouteChildren)
 
 const rootRouteChildren: RootRouteChildren = {
+  CaptionsRoute: CaptionsRoute,
   OnboardingRouteRoute: OnboardingRouteRouteWithChildren,
   PreferencesRouteRoute: PreferencesRouteRouteWithChildren,
   RecordingPopupRouteRoute: RecordingPopupRouteRouteWithChildren,
@@ -0,0 +1,10 @@
+import { createFileRoute } from '@tanstack/react-router'
+import LiveCaptions from '@/components/captions/LiveCaptions'
+
+export const Route = createFileRoute('/captions')({
+  component: CaptionsRoute,
+})
+
+function CaptionsRoute() {
+  return <LiveCaptions />
+}